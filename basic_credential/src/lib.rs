@@ -14,7 +14,7 @@ use openmls_traits::{
 
 use p256::ecdsa::{signature::Signer as P256Signer, Signature, SigningKey};
 
-use rand_core::OsRng;
+use rand_core::{OsRng, RngCore};
 use serde::{Deserialize, Serialize};
 use tls_codec::{SecretVLBytes, TlsDeserialize, TlsDeserializeBytes, TlsSerialize, TlsSize};
 use zeroize::Zeroize;
@@ -79,12 +79,24 @@ impl Signer for SignatureKeyPair {
                 let signature: p384::ecdsa::Signature = k.sign(payload);
                 Ok(signature.to_der().to_bytes().into())
             }
+            SignatureScheme::ECDSA_SECP521R1_SHA512 => {
+                let k = p521::ecdsa::SigningKey::from_slice(self.private.as_slice())
+                    .map_err(|_| SignerError::SigningError)?;
+                let signature: p521::ecdsa::Signature = k.sign(payload);
+                Ok(signature.to_der().to_bytes().into())
+            }
             SignatureScheme::ED25519 => {
                 let k = ed25519_dalek::SigningKey::try_from(self.private.as_slice())
                     .map_err(|_| SignerError::SigningError)?;
                 let signature = k.sign(payload);
                 Ok(signature.to_bytes().into())
             }
+            SignatureScheme::ED448 => {
+                let k = ed448_goldilocks_plus::SigningKey::try_from(self.private.as_slice())
+                    .map_err(|_| SignerError::SigningError)?;
+                let signature = k.sign_raw(payload);
+                Ok(signature.to_bytes().into())
+            }
             #[cfg(feature = "draft-ietf-mls-pq-ciphersuites")]
             SignatureScheme::MLDSA65 => {
                 use ml_dsa::Signer;
@@ -109,7 +121,6 @@ impl Signer for SignatureKeyPair {
                 let signature = k.sign(payload);
                 Ok(signature.encode().to_vec())
             }
-            _ => Err(SignerError::SigningError),
         }
     }
 
@@ -146,6 +157,14 @@ impl SignatureKeyPair {
                 let pk = k.verifying_key().to_encoded_point(false).as_bytes().into();
                 (k.to_bytes().as_slice().into(), pk)
             }
+            SignatureScheme::ECDSA_SECP521R1_SHA512 => {
+                let k = p521::ecdsa::SigningKey::random(&mut OsRng);
+                let pk = p521::ecdsa::VerifyingKey::from(&k)
+                    .to_encoded_point(false)
+                    .as_bytes()
+                    .into();
+                (k.to_bytes().as_slice().into(), pk)
+            }
             SignatureScheme::ED25519 => {
                 let sk = ed25519_dalek::SigningKey::generate(&mut OsRng);
                 let pk = sk.verifying_key().to_bytes().into();
@@ -153,6 +172,16 @@ impl SignatureKeyPair {
                 // sk itself implements ZeroizeOnDrop.
                 (sk.as_bytes().as_slice().into(), pk)
             }
+            SignatureScheme::ED448 => {
+                // An Ed448 private key is a random 57 byte string.
+                let mut key_bytes = [0u8; ed448_goldilocks_plus::SECRET_KEY_LENGTH];
+                OsRng.fill_bytes(&mut key_bytes);
+                let sk = ed448_goldilocks_plus::SigningKey::try_from(key_bytes.as_slice())
+                    .map_err(|_| CryptoError::CryptoLibraryError)?;
+                key_bytes.zeroize();
+                let pk = sk.verifying_key().to_bytes().to_vec();
+                (sk.as_bytes().as_slice().into(), pk)
+            }
             #[cfg(feature = "draft-ietf-mls-pq-ciphersuites")]
             SignatureScheme::MLDSA65 => {
                 use ml_dsa::{Generate, Keypair};
@@ -169,7 +198,6 @@ impl SignatureKeyPair {
                 let sk = sk.to_seed().to_vec();
                 (sk.into(), pk)
             }
-            _ => return Err(CryptoError::UnsupportedSignatureScheme),
         };
 
         Ok(Self {
@@ -1,5 +1,7 @@
+use std::time::Duration;
+
 use base64::Engine;
-use tls_codec::{Deserialize, TlsVecU16, TlsVecU32};
+use tls_codec::{Deserialize, Serialize, TlsVecU16, TlsVecU32};
 use url::Url;
 
 use crate::networking::get_with_body;
@@ -11,12 +13,13 @@ use super::{
 
 use ds_lib::{
     messages::{
-        AuthToken, PublishKeyPackagesRequest, RecvMessageRequest, RegisterClientRequest,
-        RegisterClientSuccessResponse,
+        AckMessagesRequest, AuthChallenge, ClientAuth, GroupInfoResponse, PollMessagesRequest,
+        PublishKeyPackagesRequest, RegisterClientRequest, RegisterGroupRequest, SendMessageRequest,
+        SendWelcomeRequest,
     },
     *,
 };
-use openmls::prelude::*;
+use openmls::{prelude::*, treesync::RatchetTree};
 
 pub struct Backend {
     ds_url: Url,
@@ -24,10 +27,7 @@ pub struct Backend {
 
 impl Backend {
     /// Register a new client with the server.
-    pub fn register_client(
-        &self,
-        key_packages: Vec<(Vec<u8>, KeyPackage)>,
-    ) -> Result<AuthToken, String> {
+    pub fn register_client(&self, key_packages: Vec<(Vec<u8>, KeyPackage)>) -> Result<(), String> {
         let mut url = self.ds_url.clone();
         url.set_path("/clients/register");
 
@@ -39,21 +39,43 @@ impl Backend {
                 .into(),
         );
         let request = RegisterClientRequest { key_packages };
-        let response_bytes = post(&url, &request)?;
-        let response =
-            RegisterClientSuccessResponse::tls_deserialize(&mut response_bytes.as_slice())
-                .map_err(|e| format!("Error decoding server response: {e:?}"))?;
 
-        Ok(response.auth_token)
+        // The response should be empty.
+        let _response = post(&url, &request)?;
+        Ok(())
+    }
+
+    /// Authenticate a request of the user to `endpoint` with the given `body`.
+    /// This fetches a challenge from the server and signs it together with the
+    /// request with the user's signature key.
+    fn auth(&self, user: &User, endpoint: &Url, body: &[u8]) -> Result<ClientAuth, String> {
+        let identity = user.identity.borrow();
+        let mut url = self.ds_url.clone();
+        let path = "/clients/challenge/".to_string()
+            + &base64::engine::general_purpose::URL_SAFE.encode(identity.identity());
+        url.set_path(&path);
+
+        let response = get(&url)?;
+        let challenge = AuthChallenge::tls_deserialize(&mut response.as_slice())
+            .map_err(|e| format!("Error decoding server response: {e:?}"))?;
+        ClientAuth::new(
+            identity.identity().to_vec(),
+            challenge,
+            endpoint.path(),
+            body,
+            user.provider.crypto(),
+            &identity.signer,
+        )
+        .map_err(|e| format!("Error signing the challenge: {e:?}"))
     }
 
     /// Get a list of all clients with name, ID, and key packages from the
     /// server.
-    pub fn list_clients(&self) -> Result<Vec<Vec<u8>>, String> {
+    pub fn list_clients(&self, user: &User) -> Result<Vec<Vec<u8>>, String> {
         let mut url = self.ds_url.clone();
         url.set_path("/clients/list");
 
-        let response = get(&url)?;
+        let response = get_with_body(&url, &self.auth(user, &url, &[])?)?;
         match TlsVecU32::<Vec<u8>>::tls_deserialize(&mut response.as_slice()) {
             Ok(clients) => Ok(clients.into()),
             Err(e) => Err(format!("Error decoding server response: {e:?}")),
@@ -61,13 +83,17 @@ impl Backend {
     }
 
     /// Get and reserve a key package for a client.
-    pub fn consume_key_package(&self, client_id: &[u8]) -> Result<KeyPackageIn, String> {
+    pub fn consume_key_package(
+        &self,
+        user: &User,
+        client_id: &[u8],
+    ) -> Result<KeyPackageIn, String> {
         let mut url = self.ds_url.clone();
         let path = "/clients/key_package/".to_string()
             + &base64::engine::general_purpose::URL_SAFE.encode(client_id);
         url.set_path(&path);
 
-        let response = get(&url)?;
+        let response = get_with_body(&url, &self.auth(user, &url, &[])?)?;
         match KeyPackageIn::tls_deserialize(&mut response.as_slice()) {
             Ok(kp) => Ok(kp),
             Err(e) => Err(format!("Error decoding server response: {e:?}")),
@@ -76,17 +102,15 @@ impl Backend {
 
     /// Publish client additional key packages
     pub fn publish_key_packages(&self, user: &User, ckp: ClientKeyPackages) -> Result<(), String> {
-        let Some(auth_token) = user.auth_token() else {
-            return Err("Please register user before publishing key packages".to_string());
-        };
         let mut url = self.ds_url.clone();
         let path = "/clients/key_packages/".to_string()
             + &base64::engine::general_purpose::URL_SAFE.encode(user.identity.borrow().identity());
         url.set_path(&path);
 
+        let body = serialize(&ckp)?;
         let request = PublishKeyPackagesRequest {
             key_packages: ckp,
-            auth_token: auth_token.clone(),
+            auth: self.auth(user, &url, &body)?,
         };
 
         // The response should be empty.
@@ -94,47 +118,121 @@ impl Backend {
         Ok(())
     }
 
+    /// Register a group with the server.
+    /// The `group_info` has to be signed by the user.
+    pub fn register_group(
+        &self,
+        user: &User,
+        group_info: MlsMessageOut,
+        ratchet_tree: RatchetTree,
+    ) -> Result<(), String> {
+        let mut url = self.ds_url.clone();
+        url.set_path("/groups/register");
+
+        let group_info: MlsMessageIn = group_info.into();
+        let ratchet_tree: RatchetTreeIn = ratchet_tree.into();
+        let mut body = serialize(&group_info)?;
+        body.extend(serialize(&ratchet_tree)?);
+        let request = RegisterGroupRequest {
+            group_info,
+            ratchet_tree,
+            auth: self.auth(user, &url, &body)?,
+        };
+
+        // The response should be empty.
+        let _response = post(&url, &request)?;
+        Ok(())
+    }
+
+    /// Get the latest group info and the ratchet tree of a group.
+    pub fn group_info(&self, group_id: &GroupId) -> Result<(MlsMessageIn, RatchetTreeIn), String> {
+        let mut url = self.ds_url.clone();
+        let path = "/groups/group_info/".to_string()
+            + &base64::engine::general_purpose::URL_SAFE.encode(group_id.as_slice());
+        url.set_path(&path);
+
+        let response = get(&url)?;
+        match GroupInfoResponse::tls_deserialize(&mut response.as_slice()) {
+            Ok(r) => Ok((r.group_info, r.ratchet_tree)),
+            Err(e) => Err(format!("Error decoding server response: {e:?}")),
+        }
+    }
+
     /// Send a welcome message.
-    pub fn send_welcome(&self, welcome_msg: &MlsMessageOut) -> Result<(), String> {
+    pub fn send_welcome(&self, user: &User, welcome_msg: &MlsMessageOut) -> Result<(), String> {
         let mut url = self.ds_url.clone();
         url.set_path("/send/welcome");
 
+        let welcome: MlsMessageIn = welcome_msg.clone().into();
+        let body = serialize(&welcome)?;
+        let request = SendWelcomeRequest {
+            welcome,
+            auth: self.auth(user, &url, &body)?,
+        };
+
         // The response should be empty.
-        let _response = post(&url, welcome_msg)?;
+        let _response = post(&url, &request)?;
         Ok(())
     }
 
     /// Send a group message.
-    pub fn send_msg(&self, group_msg: &GroupMessage) -> Result<(), String> {
+    pub fn send_msg(&self, user: &User, group_msg: &GroupMessage) -> Result<(), String> {
         let mut url = self.ds_url.clone();
         url.set_path("/send/message");
 
+        let body = serialize(group_msg)?;
+        let request = SendMessageRequest {
+            group_msg: group_msg.clone(),
+            auth: self.auth(user, &url, &body)?,
+        };
+
         // The response should be empty.
-        let _response = post(&url, group_msg)?;
+        let _response = post(&url, &request)?;
         Ok(())
     }
 
-    /// Get a list of all new messages for the user.
-    pub fn recv_msgs(&self, user: &User) -> Result<Vec<MlsMessageIn>, String> {
-        let Some(auth_token) = user.auth_token() else {
-            return Err("Please register user before publishing key packages".to_string());
-        };
+    /// Wait for new messages for the user.
+    /// The server responds as soon as there are messages that haven't been
+    /// acknowledged yet, or with an empty list after `timeout`.
+    pub fn poll_msgs(&self, user: &User, timeout: Duration) -> Result<Vec<QueuedMessage>, String> {
         let mut url = self.ds_url.clone();
-        let path = "/recv/".to_string()
+        let path = "/poll/".to_string()
             + &base64::engine::general_purpose::URL_SAFE.encode(user.identity.borrow().identity());
         url.set_path(&path);
 
-        let request = RecvMessageRequest {
-            auth_token: auth_token.clone(),
+        let timeout_ms = timeout.as_millis() as u64;
+        let body = serialize(&timeout_ms)?;
+        let request = PollMessagesRequest {
+            timeout_ms,
+            auth: self.auth(user, &url, &body)?,
         };
 
         let response = get_with_body(&url, &request)?;
-        match TlsVecU16::<MlsMessageIn>::tls_deserialize(&mut response.as_slice()) {
+        match TlsVecU16::<QueuedMessage>::tls_deserialize(&mut response.as_slice()) {
             Ok(r) => Ok(r.into()),
             Err(e) => Err(format!("Invalid message list: {e:?}")),
         }
     }
 
+    /// Acknowledge processed messages, such that the server doesn't deliver
+    /// them again.
+    pub fn ack_msgs(&self, user: &User, sequence_numbers: Vec<u64>) -> Result<(), String> {
+        let mut url = self.ds_url.clone();
+        let path = "/ack/".to_string()
+            + &base64::engine::general_purpose::URL_SAFE.encode(user.identity.borrow().identity());
+        url.set_path(&path);
+
+        let body = serialize(&sequence_numbers)?;
+        let request = AckMessagesRequest {
+            sequence_numbers,
+            auth: self.auth(user, &url, &body)?,
+        };
+
+        // The response should be empty.
+        let _response = post(&url, &request)?;
+        Ok(())
+    }
+
     /// Reset the DS.
     pub fn reset_server(&self) {
         let mut url = self.ds_url.clone();
@@ -151,3 +249,9 @@ impl Default for Backend {
         }
     }
 }
+
+/// Serialize the body of a request.
+fn serialize(body: &impl Serialize) -> Result<Vec<u8>, String> {
+    body.tls_serialize_detached()
+        .map_err(|e| format!("Error encoding the request: {e:?}"))
+}
@@ -23,6 +23,7 @@ const HELP: &str = "
 >>>     - autosave                              enable automatic save of the current client state upon each update
 >>>     - create kp                             create a new key package
 >>>     - create group {group name}             create a new group
+>>>     - join {group name}                     join a group through an external commit
 >>>     - group {group name}                    group operations
 >>>         - send {message}                    send message to group
 >>>         - invite {client name}              invite a user to the group
@@ -155,6 +156,23 @@ fn main() {
             continue;
         }
 
+        // Join a group through an external commit.
+        if let Some(group_name) = op.strip_prefix("join ") {
+            if let Some(client) = &mut client {
+                match client.join_by_external_commit(group_name.to_string()) {
+                    Ok(()) => stdout
+                        .write_all(format!(" >>> Joined group {group_name} :)\n\n").as_bytes())
+                        .unwrap(),
+                    Err(e) => println!("Error joining group: {e:?}"),
+                }
+            } else {
+                stdout
+                    .write_all(b" >>> No client to join a group :(\n\n")
+                    .unwrap();
+            }
+            continue;
+        }
+
         // Group operations.
         if let Some(group_name) = op.strip_prefix("group ") {
             if let Some(client) = &mut client {
@@ -1,4 +1,3 @@
-use ds_lib::messages::AuthToken;
 use reqwest::{self, blocking::Client, StatusCode};
 use url::Url;
 
@@ -26,8 +25,8 @@ pub fn post(url: &Url, msg: &impl Serialize) -> Result<Vec<u8>, String> {
 }
 
 pub fn get(url: &Url) -> Result<Vec<u8>, String> {
-    let auth_token_option: Option<&AuthToken> = None;
-    get_internal(url, auth_token_option)
+    let no_body: Option<&Vec<u8>> = None;
+    get_internal(url, no_body)
 }
 
 pub fn get_with_body(url: &Url, body: &impl Serialize) -> Result<Vec<u8>, String> {
@@ -3,9 +3,9 @@ use std::collections::HashSet;
 use std::fs::File;
 use std::io::{BufReader, BufWriter};
 use std::path::PathBuf;
+use std::time::Duration;
 use std::{cell::RefCell, collections::HashMap, str};
 
-use ds_lib::messages::AuthToken;
 use ds_lib::{ClientKeyPackages, GroupMessage};
 use openmls::prelude::{tls_codec::*, *};
 use openmls_traits::OpenMlsProvider;
@@ -18,6 +18,9 @@ use super::{
 
 const CIPHERSUITE: Ciphersuite = Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519;
 
+/// How long to wait for new messages from the DS on every update.
+const POLL_TIMEOUT: Duration = Duration::from_millis(500);
+
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct Contact {
     id: Vec<u8>,
@@ -49,9 +52,8 @@ pub struct User {
     #[serde(skip)]
     backend: Backend,
     #[serde(skip)]
-    provider: OpenMlsRustPersistentCrypto,
+    pub(crate) provider: OpenMlsRustPersistentCrypto,
     autosave_enabled: bool,
-    auth_token: Option<AuthToken>,
 }
 
 #[derive(PartialEq)]
@@ -72,7 +74,6 @@ impl User {
             backend: Backend::default(),
             provider: crypto,
             autosave_enabled: false,
-            auth_token: None,
         };
         out
     }
@@ -209,49 +210,11 @@ impl User {
 
     pub fn register(&mut self) {
         match self.backend.register_client(self.key_packages()) {
-            Ok(token) => {
-                log::debug!("Created new user: {:?}", self.username());
-                self.set_auth_token(token)
-            }
+            Ok(()) => log::debug!("Created new user: {:?}", self.username()),
             Err(e) => log::error!("Error creating user: {e:?}"),
         }
     }
 
-    /// Get a list of clients in the group to send messages to.
-    fn recipients(&self, group: &Group) -> Vec<Vec<u8>> {
-        let mut recipients = Vec::new();
-
-        let mls_group = group.mls_group.borrow();
-        for Member {
-            index: _,
-            encryption_key: _,
-            signature_key,
-            credential,
-        } in mls_group.members()
-        {
-            if self
-                .identity
-                .borrow()
-                .credential_with_key
-                .signature_key
-                .as_slice()
-                != signature_key.as_slice()
-            {
-                let credential = BasicCredential::try_from(credential).unwrap();
-                log::debug!(
-                    "Searching for contact {:?}",
-                    str::from_utf8(credential.identity()).unwrap()
-                );
-                let contact = match self.contacts.get(credential.identity()) {
-                    Some(c) => c.id.clone(),
-                    None => panic!("There's a member in the group we don't know."),
-                };
-                recipients.push(contact);
-            }
-        }
-        recipients
-    }
-
     /// Return the last 100 messages sent to the group.
     pub fn read_msgs(
         &self,
@@ -303,9 +266,9 @@ impl User {
             )
             .map_err(|e| format!("{e}"))?;
 
-        let msg = GroupMessage::new(message_out.into(), &self.recipients(group));
+        let msg = GroupMessage::new(message_out.into());
         log::debug!(" >>> send: {msg:?}");
-        match self.backend.send_msg(&msg) {
+        match self.backend.send_msg(self, &msg) {
             Ok(()) => (),
             Err(e) => println!("Error sending group message: {e:?}"),
         }
@@ -319,7 +282,7 @@ impl User {
     /// Update the user clients list.
     /// It updates the contacts with all the clients known by the server
     fn update_clients(&mut self) {
-        match self.backend.list_clients() {
+        match self.backend.list_clients(self) {
             Ok(mut v) => {
                 for client_id in v.drain(..) {
                     log::debug!(
@@ -492,14 +455,22 @@ impl User {
         let mut messages_out: Vec<ConversationMessage> = Vec::new();
 
         log::debug!("update::Processing messages for {} ", self.username());
+        let queued_messages = self.backend.poll_msgs(self, POLL_TIMEOUT)?;
         // Go through the list of messages and process or store them.
-        for message in self.backend.recv_msgs(self)?.drain(..) {
+        // Each message is acknowledged once it is processed and the state is
+        // saved, such that it is delivered again if anything goes wrong
+        // before. Messages that can't be processed are dropped, such that
+        // they don't block the following ones.
+        for queued_message in queued_messages {
+            let message = queued_message.msg;
             log::debug!("Reading message format {:#?} ...", message.wire_format());
             match message.extract() {
                 MlsMessageBodyIn::Welcome(welcome) => {
                     // Join the group. (Later we should ask the user to
                     // approve first ...)
-                    self.join_group(welcome)?;
+                    if let Err(e) = self.join_group(welcome) {
+                        log::error!("update::Dropping welcome message: {e}");
+                    }
                 }
                 MlsMessageBodyIn::PrivateMessage(message) => {
                     match self.process_protocol_message(group_name.clone(), message.into()) {
@@ -521,21 +492,22 @@ impl User {
                                 }
                             }
                         }
-                        Err(_e) => {
-                            continue;
-                        }
+                        Err(e) => log::error!("update::Dropping private message: {e}"),
                     };
                 }
                 MlsMessageBodyIn::PublicMessage(message) => {
-                    if self
-                        .process_protocol_message(group_name.clone(), message.into())
-                        .is_err()
+                    if let Err(e) =
+                        self.process_protocol_message(group_name.clone(), message.into())
                     {
-                        continue;
+                        log::error!("update::Dropping public message: {e}");
                     }
                 }
-                _ => panic!("Unsupported message type"),
+                _ => log::error!("update::Dropping message of unsupported type"),
             }
+
+            self.autosave();
+            self.backend
+                .ack_msgs(self, vec![queued_message.sequence_number])?;
         }
         log::debug!("update::Processing messages done");
 
@@ -553,8 +525,10 @@ impl User {
 
         // NOTE: Since the DS currently doesn't distribute copies of the group's ratchet
         // tree, we need to include the ratchet_tree_extension.
+        // The DS only accepts handshake messages it can validate, i.e. public messages.
         let group_config = MlsGroupCreateConfig::builder()
             .use_ratchet_tree_extension(true)
+            .wire_format_policy(MIXED_PLAINTEXT_WIRE_FORMAT_POLICY)
             .build();
 
         let mls_group = MlsGroup::new_with_group_id(
@@ -566,6 +540,21 @@ impl User {
         )
         .expect("Failed to create MlsGroup");
 
+        // Register the group with the DS so that it accepts messages for it.
+        let group_info = mls_group
+            .export_group_info(
+                self.provider.crypto(),
+                &self.identity.borrow().signer,
+                false,
+            )
+            .expect("Failed to export group info");
+        if let Err(e) =
+            self.backend
+                .register_group(self, group_info, mls_group.export_ratchet_tree())
+        {
+            println!("Error registering group: {e:?}");
+        }
+
         let group = Group {
             group_name: name.clone(),
             conversation: Conversation::default(),
@@ -590,7 +579,7 @@ impl User {
         };
 
         // Reclaim a key package from the server
-        let joiner_key_package = self.backend.consume_key_package(&contact.id).unwrap();
+        let joiner_key_package = self.backend.consume_key_package(self, &contact.id).unwrap();
 
         // Build a proposal with this key package and do the MLS bits.
         let mut groups = self.groups.borrow_mut();
@@ -599,7 +588,7 @@ impl User {
             None => return Err(format!("No group with name {group_name} known.")),
         };
 
-        let (out_messages, welcome, _group_info) = group
+        let (out_messages, welcome, group_info) = group
             .mls_group
             .borrow_mut()
             .add_members(
@@ -614,10 +603,12 @@ impl User {
         It avoids the invited member to receive the commit message (which is in the previous group epoch).*/
         log::trace!("Sending commit");
         let group = groups.get_mut(&group_name).unwrap(); // XXX: not cool.
-        let group_recipients = self.recipients(group);
 
-        let msg = GroupMessage::new(out_messages.into(), &group_recipients);
-        self.backend.send_msg(&msg)?;
+        let mut msg = GroupMessage::new(out_messages.into());
+        if let Some(group_info) = group_info {
+            msg = msg.with_group_info(MlsMessageOut::from(group_info).into());
+        }
+        self.backend.send_msg(self, &msg)?;
 
         // Second, process the invitation on our end.
         group
@@ -629,7 +620,7 @@ impl User {
         // Finally, send Welcome to the joiner.
         log::trace!("Sending welcome");
         self.backend
-            .send_welcome(&welcome)
+            .send_welcome(self, &welcome)
             .expect("Error sending Welcome message");
 
         drop(groups);
@@ -654,7 +645,7 @@ impl User {
         let leaf_index = self.find_member_index(name, group)?;
 
         // Remove operation on the mls group
-        let (remove_message, _welcome, _group_info) = group
+        let (remove_message, _welcome, group_info) = group
             .mls_group
             .borrow_mut()
             .remove_members(
@@ -667,10 +658,12 @@ impl User {
         // First, send the MlsMessage remove commit to the group.
         log::trace!("Sending commit");
         let group = groups.get_mut(&group_name).unwrap(); // XXX: not cool.
-        let group_recipients = self.recipients(group);
 
-        let msg = GroupMessage::new(remove_message.into(), &group_recipients);
-        self.backend.send_msg(&msg)?;
+        let mut msg = GroupMessage::new(remove_message.into());
+        if let Some(group_info) = group_info {
+            msg = msg.with_group_info(MlsMessageOut::from(group_info).into());
+        }
+        self.backend.send_msg(self, &msg)?;
 
         // Second, process the removal on our end.
         group
@@ -686,6 +679,80 @@ impl User {
         Ok(())
     }
 
+    /// Join the group with the given name through an external commit, using
+    /// the group info published on the DS.
+    pub fn join_by_external_commit(&mut self, group_name: String) -> Result<(), String> {
+        log::debug!("{} joins group {} externally", self.username(), group_name);
+
+        if self.groups.borrow().contains_key(&group_name) {
+            return Err(format!("Group '{group_name}' existed already"));
+        }
+
+        let (group_info, ratchet_tree) = self
+            .backend
+            .group_info(&GroupId::from_slice(group_name.as_bytes()))?;
+        let verifiable_group_info = group_info
+            .into_verifiable_group_info()
+            .ok_or("The DS didn't return a group info")?;
+
+        // Like the other members, we include the ratchet tree in our welcome
+        // messages.
+        let group_config = MlsGroupJoinConfig::builder()
+            .use_ratchet_tree_extension(true)
+            .wire_format_policy(MIXED_PLAINTEXT_WIRE_FORMAT_POLICY)
+            .build();
+        let (mut mls_group, commit_bundle) = MlsGroup::external_commit_builder()
+            .with_config(group_config)
+            .with_ratchet_tree(ratchet_tree)
+            .build_group(
+                &self.provider,
+                verifiable_group_info,
+                self.identity.borrow().credential_with_key.clone(),
+            )
+            .map_err(|e| format!("Failed to build external commit - {e}"))?
+            .load_psks(self.provider.storage())
+            .map_err(|e| format!("Failed to build external commit - {e}"))?
+            .create_group_info(true)
+            .build(
+                self.provider.rand(),
+                self.provider.crypto(),
+                &self.identity.borrow().signer,
+                |_| true,
+            )
+            .map_err(|e| format!("Failed to build external commit - {e}"))?
+            .finalize(&self.provider)
+            .map_err(|e| format!("Failed to build external commit - {e}"))?;
+
+        // The commit goes to all members of the group. The DS only accepts it
+        // if nobody else committed to the epoch of the group info in the
+        // meantime.
+        let (commit, _welcome, group_info) = commit_bundle.into_messages();
+        let mut msg = GroupMessage::new(commit.into());
+        if let Some(group_info) = group_info {
+            msg = msg.with_group_info(group_info.into());
+        }
+        if let Err(e) = self.backend.send_msg(self, &msg) {
+            // The join failed, so the group we stored with the external commit
+            // is of no use.
+            if let Err(e) = mls_group.delete(self.provider.storage()) {
+                log::error!("Failed to delete group {group_name} - {e:?}");
+            }
+            return Err(e);
+        }
+
+        let group = Group {
+            group_name: group_name.clone(),
+            conversation: Conversation::default(),
+            mls_group: RefCell::new(mls_group),
+        };
+        self.groups.borrow_mut().insert(group_name.clone(), group);
+        self.group_list.insert(group_name);
+
+        self.autosave();
+
+        Ok(())
+    }
+
     /// Join a group with the provided welcome message.
     fn join_group(&self, welcome: Welcome) -> Result<(), String> {
         log::debug!("{} joining group ...", self.username());
@@ -701,6 +768,7 @@ impl User {
         // tree, we need to include the ratchet_tree_extension.
         let group_config = MlsGroupJoinConfig::builder()
             .use_ratchet_tree_extension(true)
+            .wire_format_policy(MIXED_PLAINTEXT_WIRE_FORMAT_POLICY)
             .build();
         let mls_group =
             StagedWelcome::new_from_welcome(&self.provider, &group_config, welcome, None)
@@ -729,12 +797,4 @@ impl User {
     pub(crate) fn username(&self) -> String {
         self.identity.borrow().identity_as_string()
     }
-
-    pub(super) fn set_auth_token(&mut self, token: AuthToken) {
-        self.auth_token = Some(token);
-    }
-
-    pub(super) fn auth_token(&self) -> Option<&AuthToken> {
-        self.auth_token.as_ref()
-    }
 }
@@ -9,23 +9,32 @@ pub mod messages;
 
 use std::collections::HashSet;
 
-use messages::AuthToken;
 use openmls::prelude::tls_codec::*;
 use openmls::prelude::*;
 
 /// Information about a client.
 /// To register a new client create a new `ClientInfo` and send it to
 /// `/clients/register`.
-#[derive(Debug, Default, Clone, TlsSize, TlsSerialize, TlsDeserialize)]
+#[derive(Debug, Clone, TlsSize, TlsSerialize, TlsDeserialize)]
 pub struct ClientInfo {
     pub id: Vec<u8>,
     pub key_packages: ClientKeyPackages,
     /// map of reserved key_packages [group_id, key_package_hash]
     #[tls_codec(with = "hashset_codec")]
     pub reserved_key_pkg_hash: HashSet<Vec<u8>>,
-    pub msgs: Vec<MlsMessageIn>,
-    pub welcome_queue: Vec<MlsMessageIn>,
-    pub auth_token: AuthToken,
+    /// The signature key of the client's credential, which the client
+    /// authenticates with.
+    pub signature_key: Vec<u8>,
+    pub signature_scheme: SignatureScheme,
+}
+
+/// A message queued for a client.
+/// Messages stay in the queue until the client acknowledges their
+/// `sequence_number`.
+#[derive(Debug, Clone, TlsSize, TlsSerialize, TlsDeserialize)]
+pub struct QueuedMessage {
+    pub sequence_number: u64,
+    pub msg: MlsMessageIn,
 }
 
 mod hashset_codec {
@@ -34,17 +43,11 @@ mod hashset_codec {
         io::{Read, Write},
     };
 
-    use crate::tls_codec::{self, Deserialize, Serialize};
+    use crate::tls_codec::{self, Deserialize, Serialize, Size};
 
     pub fn tls_serialized_len(hashset: &HashSet<Vec<u8>>) -> usize {
-        let hashset_len = hashset.len();
-        let length_encoding_bytes = match hashset_len {
-            0..=0x3f => 1,
-            0x40..=0x3fff => 2,
-            0x4000..=0x3fff_ffff => 4,
-            _ => 8,
-        };
-        hashset_len + length_encoding_bytes
+        let vec = hashset.iter().map(|v| v.as_slice()).collect::<Vec<_>>();
+        vec.tls_serialized_len()
     }
 
     pub fn tls_serialize<W>(
@@ -88,6 +91,8 @@ impl ClientInfo {
     pub fn new(mut key_packages: Vec<(Vec<u8>, KeyPackageIn)>) -> Self {
         let key_package: KeyPackage = KeyPackage::from(key_packages[0].1.clone());
         let id = key_package.leaf_node().credential().serialized_content();
+        let signature_key = key_package.leaf_node().signature_key().as_slice().to_vec();
+        let signature_scheme = key_package.ciphersuite().signature_algorithm();
         Self {
             id: id.into(),
             key_packages: ClientKeyPackages(
@@ -98,9 +103,8 @@ impl ClientInfo {
                     .into(),
             ),
             reserved_key_pkg_hash: HashSet::new(),
-            msgs: Vec::new(),
-            welcome_queue: Vec::new(),
-            auth_token: AuthToken::random(),
+            signature_key,
+            signature_scheme,
         }
     }
 
@@ -110,6 +114,14 @@ impl ClientInfo {
         self.id.as_slice()
     }
 
+    /// The public key to verify the client's `ClientAuth`s with.
+    pub fn signature_public_key(&self) -> OpenMlsSignaturePublicKey {
+        OpenMlsSignaturePublicKey::from_signature_key(
+            self.signature_key.as_slice().into(),
+            self.signature_scheme,
+        )
+    }
+
     /// Acquire a key package from the client's key packages
     /// Mark the key package hash ref as "reserved key package"
     /// The reserved hash ref will be used in DS::send_welcome and removed once welcome is distributed
@@ -129,46 +141,49 @@ impl ClientInfo {
 }
 
 /// An core group message.
-/// This is an `MLSMessage` plus the list of recipients as a vector of client
-/// names.
-#[derive(Debug)]
+/// This is an `MLSMessage`. The DS delivers it to all members of the group
+/// except for the sender.
+/// Commits can carry the `GroupInfo` of the new epoch, which the DS then
+/// publishes for external joiners.
+#[derive(Debug, Clone)]
 pub struct GroupMessage {
     pub msg: MlsMessageIn,
-    pub recipients: TlsVecU32<TlsByteVecU32>,
+    pub group_info: Option<MlsMessageIn>,
 }
 
 impl GroupMessage {
-    /// Create a new `GroupMessage` taking an `MlsMessageIn` and slice of
-    /// recipient names.
-    pub fn new(msg: MlsMessageIn, recipients: &[Vec<u8>]) -> Self {
+    /// Create a new `GroupMessage` taking an `MlsMessageIn`.
+    pub fn new(msg: MlsMessageIn) -> Self {
         Self {
             msg,
-            recipients: recipients
-                .iter()
-                .map(|r| r.clone().into())
-                .collect::<Vec<TlsByteVecU32>>()
-                .into(),
+            group_info: None,
         }
     }
+
+    /// Attach the `GroupInfo` for the epoch following the commit in `msg`.
+    pub fn with_group_info(mut self, group_info: MlsMessageIn) -> Self {
+        self.group_info = Some(group_info);
+        self
+    }
 }
 
 impl tls_codec::Size for GroupMessage {
     fn tls_serialized_len(&self) -> usize {
-        self.msg.tls_serialized_len() + self.recipients.tls_serialized_len()
+        self.msg.tls_serialized_len() + self.group_info.tls_serialized_len()
     }
 }
 
 impl tls_codec::Serialize for GroupMessage {
     fn tls_serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<usize, tls_codec::Error> {
         let written = self.msg.tls_serialize(writer)?;
-        self.recipients.tls_serialize(writer).map(|l| l + written)
+        self.group_info.tls_serialize(writer).map(|l| l + written)
     }
 }
 
 impl tls_codec::Deserialize for GroupMessage {
     fn tls_deserialize<R: std::io::Read>(bytes: &mut R) -> Result<Self, tls_codec::Error> {
         let msg = MlsMessageIn::tls_deserialize(bytes)?;
-        let recipients = TlsVecU32::<TlsByteVecU32>::tls_deserialize(bytes)?;
-        Ok(Self { msg, recipients })
+        let group_info = Option::<MlsMessageIn>::tls_deserialize(bytes)?;
+        Ok(Self { msg, group_info })
     }
 }
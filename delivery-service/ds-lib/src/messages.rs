@@ -1,29 +1,171 @@
-use crate::tls_codec::{self, TlsDeserialize, TlsSerialize, TlsSize};
+use crate::tls_codec::{self, Serialize, Size, TlsDeserialize, TlsSerialize, TlsSize};
+use openmls_traits::{signatures::Signer, types::HashType};
 use rand::{rng, RngExt};
-use serde::{Deserialize, Serialize};
 
-use crate::ClientKeyPackages;
+use openmls::prelude::{
+    MlsMessageIn, OpenMlsCrypto, OpenMlsSignaturePublicKey, RatchetTreeIn, Signable, Signature,
+    SignatureError, Verifiable, VerifiedStruct,
+};
 
-#[derive(
-    Debug, Clone, TlsSize, TlsSerialize, TlsDeserialize, PartialEq, Serialize, Deserialize,
-)]
-pub struct AuthToken {
-    token: Vec<u8>,
+use crate::{ClientKeyPackages, GroupMessage};
+
+/// The label used when signing `AuthContent`.
+const AUTH_LABEL: &str = "OpenMLS DS Auth";
+
+/// A challenge issued by the DS through `/clients/challenge/{id}`.
+/// The client answers it with a `ClientAuth` in its next request.
+#[derive(Debug, Clone, PartialEq, TlsSize, TlsSerialize, TlsDeserialize)]
+pub struct AuthChallenge {
+    pub nonce: Vec<u8>,
 }
 
-impl Default for AuthToken {
-    fn default() -> Self {
-        Self::random()
+impl AuthChallenge {
+    /// Create a challenge with a fresh random nonce.
+    pub fn random() -> Self {
+        let nonce = rng().random::<[u8; 32]>().to_vec();
+        Self { nonce }
     }
 }
 
-impl AuthToken {
-    pub(super) fn random() -> Self {
-        let token = rng().random::<[u8; 32]>().to_vec();
-        Self { token }
+/// The content signed by a client to answer a challenge.
+/// Besides the nonce, it binds the answer to the request it authenticates,
+/// such that it can't be used for another endpoint or request body.
+#[derive(Debug, Clone, TlsSize, TlsSerialize, TlsDeserialize)]
+struct AuthContent {
+    client_id: Vec<u8>,
+    nonce: Vec<u8>,
+    endpoint: Vec<u8>,
+    body_hash: Vec<u8>,
+}
+
+impl AuthContent {
+    fn new(
+        client_id: Vec<u8>,
+        nonce: Vec<u8>,
+        endpoint: &str,
+        body: &[u8],
+        crypto: &impl OpenMlsCrypto,
+    ) -> Option<Self> {
+        let body_hash = crypto.hash(HashType::Sha2_256, body).ok()?;
+        Some(Self {
+            client_id,
+            nonce,
+            endpoint: endpoint.as_bytes().to_vec(),
+            body_hash,
+        })
     }
 }
 
+impl Signable for AuthContent {
+    type SignedOutput = Signature;
+
+    fn unsigned_payload(&self) -> Result<Vec<u8>, tls_codec::Error> {
+        self.tls_serialize_detached()
+    }
+
+    fn label(&self) -> &str {
+        AUTH_LABEL
+    }
+}
+
+impl VerifiedStruct for AuthContent {}
+
+struct VerifiableAuthContent {
+    content: AuthContent,
+    signature: Signature,
+}
+
+impl Verifiable for VerifiableAuthContent {
+    type VerifiedStruct = AuthContent;
+
+    fn unsigned_payload(&self) -> Result<Vec<u8>, tls_codec::Error> {
+        self.content.tls_serialize_detached()
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn label(&self) -> &str {
+        AUTH_LABEL
+    }
+
+    fn verify(
+        self,
+        crypto: &impl OpenMlsCrypto,
+        pk: &OpenMlsSignaturePublicKey,
+    ) -> Result<Self::VerifiedStruct, SignatureError> {
+        self.verify_no_out(crypto, pk)?;
+        Ok(self.content)
+    }
+}
+
+/// Authenticates a request as coming from the client `client_id`.
+/// The client signs the nonce of an `AuthChallenge` with the signature key of
+/// its credential, i.e. the key in the leaf node of its key packages.
+/// The signature also covers the path of the endpoint and a SHA-256 hash of the
+/// request body. The `ClientAuth` always comes last in a request and the body
+/// is everything before it, see [`request_body`].
+#[derive(Debug, Clone, TlsSize, TlsSerialize, TlsDeserialize)]
+pub struct ClientAuth {
+    pub client_id: Vec<u8>,
+    pub nonce: Vec<u8>,
+    signature: Signature,
+}
+
+impl ClientAuth {
+    /// Answer the `challenge` as the client `client_id` for a request to
+    /// `endpoint` with the given `body`.
+    pub fn new(
+        client_id: Vec<u8>,
+        challenge: AuthChallenge,
+        endpoint: &str,
+        body: &[u8],
+        crypto: &impl OpenMlsCrypto,
+        signer: &impl Signer,
+    ) -> Result<Self, SignatureError> {
+        let content = AuthContent::new(client_id, challenge.nonce, endpoint, body, crypto)
+            .ok_or(SignatureError::SigningError)?;
+        let signature = content.clone().sign(signer)?;
+        Ok(Self {
+            client_id: content.client_id,
+            nonce: content.nonce,
+            signature,
+        })
+    }
+
+    /// Verify the signature of a request to `endpoint` with the given `body`
+    /// with the client's `signature_key`.
+    pub fn verify(
+        &self,
+        endpoint: &str,
+        body: &[u8],
+        crypto: &impl OpenMlsCrypto,
+        signature_key: &OpenMlsSignaturePublicKey,
+    ) -> Result<(), SignatureError> {
+        let content = AuthContent::new(
+            self.client_id.clone(),
+            self.nonce.clone(),
+            endpoint,
+            body,
+            crypto,
+        )
+        .ok_or(SignatureError::VerificationError)?;
+        let verifiable = VerifiableAuthContent {
+            content,
+            signature: self.signature.clone(),
+        };
+        verifiable.verify(crypto, signature_key).map(|_| ())
+    }
+}
+
+/// Returns the body of the serialized `request` that its `auth` signs, i.e.
+/// everything before the `ClientAuth` at the end of the request.
+pub fn request_body<'a>(request: &'a [u8], auth: &ClientAuth) -> &'a [u8] {
+    let body_len = request.len().saturating_sub(auth.tls_serialized_len());
+    &request[..body_len]
+}
+
 #[derive(Debug, Clone, TlsSize, TlsSerialize, TlsDeserialize)]
 pub struct RegisterClientRequest {
     pub key_packages: ClientKeyPackages,
@@ -33,18 +175,72 @@ pub struct RegisterClientErrorResponse {
     pub message: String,
 }
 
-#[derive(Debug, Clone, TlsSize, TlsSerialize, TlsDeserialize)]
-pub struct RegisterClientSuccessResponse {
-    pub auth_token: AuthToken,
-}
-
 #[derive(Debug, Clone, TlsSize, TlsSerialize, TlsDeserialize)]
 pub struct PublishKeyPackagesRequest {
     pub key_packages: ClientKeyPackages,
-    pub auth_token: AuthToken,
+    pub auth: ClientAuth,
 }
 
 #[derive(Debug, Clone, TlsSize, TlsSerialize, TlsDeserialize)]
 pub struct RecvMessageRequest {
-    pub auth_token: AuthToken,
+    pub auth: ClientAuth,
+}
+
+/// Wait for messages for a client.
+/// The DS responds as soon as there are unacknowledged messages for the
+/// client, or with an empty list after `timeout_ms` milliseconds.
+#[derive(Debug, Clone, TlsSize, TlsSerialize, TlsDeserialize)]
+pub struct PollMessagesRequest {
+    pub timeout_ms: u64,
+    pub auth: ClientAuth,
+}
+
+/// Acknowledge messages received through `PollMessagesRequest`s.
+/// Messages that are not acknowledged are delivered again on the next poll.
+#[derive(Debug, Clone, TlsSize, TlsSerialize, TlsDeserialize)]
+pub struct AckMessagesRequest {
+    pub sequence_numbers: Vec<u64>,
+    pub auth: ClientAuth,
+}
+
+/// Register a group with the DS so that it can track the group's public state.
+/// The `group_info` is an `MlsMessageIn` holding a `GroupInfo` of the group.
+/// It has to be signed by the registering client's own leaf in the group.
+#[derive(Debug, Clone, TlsSize, TlsSerialize, TlsDeserialize)]
+pub struct RegisterGroupRequest {
+    pub group_info: MlsMessageIn,
+    pub ratchet_tree: RatchetTreeIn,
+    pub auth: ClientAuth,
+}
+
+/// Publish the latest `GroupInfo` of a group, such that new members can join
+/// through an external commit.
+/// The `group_info` is an `MlsMessageIn` holding a `GroupInfo` for the current
+/// epoch of the group, signed by the member that publishes it.
+#[derive(Debug, Clone, TlsSize, TlsSerialize, TlsDeserialize)]
+pub struct PublishGroupInfoRequest {
+    pub group_info: MlsMessageIn,
+    pub auth: ClientAuth,
+}
+
+/// The latest `GroupInfo` of a group together with the group's ratchet tree.
+#[derive(Debug, Clone, TlsSize, TlsSerialize, TlsDeserialize)]
+pub struct GroupInfoResponse {
+    pub group_info: MlsMessageIn,
+    pub ratchet_tree: RatchetTreeIn,
+}
+
+/// Send a `GroupMessage` to a group.
+#[derive(Debug, TlsSize, TlsSerialize, TlsDeserialize)]
+pub struct SendMessageRequest {
+    pub group_msg: GroupMessage,
+    pub auth: ClientAuth,
+}
+
+/// Send a `Welcome` message to the new members of a group.
+/// The `welcome` is an `MlsMessageIn` holding the `Welcome`.
+#[derive(Debug, Clone, TlsSize, TlsSerialize, TlsDeserialize)]
+pub struct SendWelcomeRequest {
+    pub welcome: MlsMessageIn,
+    pub auth: ClientAuth,
 }
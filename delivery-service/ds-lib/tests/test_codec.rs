@@ -41,3 +41,89 @@ fn test_client_info() {
         .unwrap();
     assert_eq!(client_data.tls_serialize_detached().unwrap(), client_data2);
 }
+
+#[test]
+fn test_client_auth() {
+    let crypto = &OpenMlsRustCrypto::default();
+    let ciphersuite = Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519;
+
+    let credential = BasicCredential::new(b"Client1".to_vec());
+    let signature_keys = SignatureKeyPair::new(ciphersuite.signature_algorithm()).unwrap();
+    let credential_with_key = CredentialWithKey {
+        credential: credential.into(),
+        signature_key: signature_keys.to_public_vec().into(),
+    };
+    signature_keys.store(crypto.storage()).unwrap();
+
+    let client_key_package = KeyPackage::builder()
+        .build(ciphersuite, crypto, &signature_keys, credential_with_key)
+        .unwrap();
+    let client_data = ClientInfo::new(vec![(
+        vec![],
+        KeyPackageIn::from(client_key_package.key_package().clone()),
+    )]);
+
+    // The client answers a challenge with its signature key.
+    let challenge = messages::AuthChallenge::random();
+    let endpoint = "/send/message";
+    let body = b"request body";
+    let auth = messages::ClientAuth::new(
+        client_data.id.clone(),
+        challenge,
+        endpoint,
+        body,
+        crypto.crypto(),
+        &signature_keys,
+    )
+    .unwrap();
+    let auth = messages::ClientAuth::tls_deserialize_exact(auth.tls_serialize_detached().unwrap())
+        .unwrap();
+    auth.verify(
+        endpoint,
+        body,
+        crypto.crypto(),
+        &client_data.signature_public_key(),
+    )
+    .expect("Invalid client auth");
+
+    // The signature doesn't verify for another endpoint or body.
+    assert!(auth
+        .verify(
+            "/send/welcome",
+            body,
+            crypto.crypto(),
+            &client_data.signature_public_key()
+        )
+        .is_err());
+    assert!(auth
+        .verify(
+            endpoint,
+            b"other body",
+            crypto.crypto(),
+            &client_data.signature_public_key()
+        )
+        .is_err());
+
+    // The signature doesn't verify for another nonce.
+    let mut wrong_nonce = auth.clone();
+    wrong_nonce.nonce = messages::AuthChallenge::random().nonce;
+    assert!(wrong_nonce
+        .verify(
+            endpoint,
+            body,
+            crypto.crypto(),
+            &client_data.signature_public_key()
+        )
+        .is_err());
+
+    // Nor with another signature key.
+    let other_keys = SignatureKeyPair::new(ciphersuite.signature_algorithm()).unwrap();
+    let other_key = OpenMlsSignaturePublicKey::new(
+        other_keys.to_public_vec().into(),
+        ciphersuite.signature_algorithm(),
+    )
+    .unwrap();
+    assert!(auth
+        .verify(endpoint, body, crypto.crypto(), &other_key)
+        .is_err());
+}
@@ -8,19 +8,34 @@
 //!
 //! Because the infrastructure description doesn't give a lot of guidelines on
 //! the design of the DS we take a couple of deliberate design decisions here:
-//! * The DS does not know about groups.
-//! * Clients have to send a list of clients (group members) along with each
-//!   message for the DS to know where to send the message.
+//! * Groups have to be registered with the DS through a `GroupInfo` and the
+//!   group's ratchet tree by the member that signed the `GroupInfo`. The DS
+//!   then tracks the public state of the group with a `PublicGroup`.
+//! * Handshake messages have to be sent as `PublicMessage`s. The DS validates
+//!   them against the group state, accepts exactly one commit per epoch and
+//!   rejects everything else.
+//! * The DS derives the recipients of a message from the group's membership.
 //! * The DS stores and delivers key packages.
+//! * The DS publishes the latest `GroupInfo` of each group, together with the
+//!   group's ratchet tree, for new members joining through an external commit.
+//!   Commits carry the `GroupInfo` of the new epoch to keep it up to date.
+//!   Members can also publish a `GroupInfo` they signed for the current epoch.
 //!
 //! This is a very basic delivery service that allows to register clients and
 //! send messages to MLS groups.
+//! The state of the DS is kept in a [`DsStorage`]. By default, everything is
+//! kept in memory and gets lost when the process shuts down. With the
+//! `--database` option, the state is persisted in an SQLite database instead.
+//!
 //! Note that there are a lot of limitations to this service:
-//! * No persistence layer such that all information gets lost when the process
-//!   shuts down.
-//! * No authentication for clients.
+//! * Clients authenticate through challenge–response with the signature key
+//!   of their credential. The signature covers the endpoint and the body of
+//!   the request. There is no further access control, i.e. every
+//!   registered client can list clients and fetch their key packages.
 //! * Key packages can't be updated, changed or deleted at the moment.
-//! * Messages lost in transit are gone.
+//! * Messages fetched through `/recv/{id}` are deleted right away. Use
+//!   `/poll/{id}` and `/ack/{id}` to only delete messages once the client
+//!   processed them.
 //!
 //! **⚠️ DON'T EXPECT ANY SECURITY OR PRIVACY FROM THIS!**
 //!
@@ -28,45 +43,195 @@
 //! if accessible on the public internet.
 //!
 //! The DS returns a list of messages queued for the client in all groups they
-//! are part of.
+//! are part of. Clients can long-poll for new messages, in which case the DS
+//! responds as soon as a message arrives.
 
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+    sync::Arc,
+    time::Duration,
+};
 
 use axum::{
     body::Bytes,
     extract::{Path, State},
-    http::{HeaderMap, StatusCode},
+    http::{HeaderMap, StatusCode, Uri},
     response::{IntoResponse, Response},
     routing::{get, post},
     Router,
 };
 use base64::Engine;
 use clap::Command;
-use parking_lot::Mutex;
+use parking_lot::{Mutex, MutexGuard};
 use tls_codec::{Deserialize, Serialize, TlsSliceU16, TlsVecU32};
+use tokio::sync::Notify;
 
 use ds_lib::{
     messages::{
-        PublishKeyPackagesRequest, RecvMessageRequest, RegisterClientRequest,
-        RegisterClientSuccessResponse,
+        request_body, AckMessagesRequest, AuthChallenge, ClientAuth, GroupInfoResponse,
+        PollMessagesRequest, PublishGroupInfoRequest, PublishKeyPackagesRequest,
+        RecvMessageRequest, RegisterClientRequest, RegisterGroupRequest, SendMessageRequest,
+        SendWelcomeRequest,
     },
     *,
 };
 use openmls::prelude::*;
+use openmls_memory_storage::MemoryStorage;
+use openmls_rust_crypto::RustCrypto;
 
+mod storage;
 #[cfg(test)]
 mod test;
 
+use storage::{DsStorage, MemoryDsStorage, SqliteDsStorage};
+
 /// The DS state.
-/// It holds a list of clients and their information.
-#[derive(Default, Debug)]
-pub struct DsData {
-    // (ClientIdentity, ClientInfo)
-    clients: Mutex<HashMap<Vec<u8>, ClientInfo>>,
-
-    // (group_id, epoch)
-    groups: Mutex<HashMap<Vec<u8>, u64>>,
+/// It holds the storage with the clients and groups.
+pub struct DsData<S: DsStorage> {
+    // The lock serializes all requests, in particular the processing of
+    // handshake messages. Requests access the storage through a
+    // `StorageTransaction`.
+    storage: Mutex<S>,
+    crypto: RustCrypto,
+
+    // Wakes up clients that are waiting for new messages.
+    new_msgs: Notify,
+
+    // The open authentication challenges of each client. They are only kept
+    // in memory and can be used once.
+    challenges: Mutex<HashMap<Vec<u8>, Vec<Vec<u8>>>>,
+}
+
+/// The maximum time a client can wait for new messages in a single poll.
+const MAX_POLL_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The maximum number of open challenges per client. When a client requests
+/// more, the oldest challenge is dropped.
+const MAX_OPEN_CHALLENGES: usize = 8;
+
+impl<S: DsStorage> DsData<S> {
+    /// Create the DS state on top of the given storage.
+    pub fn new(storage: S) -> Self {
+        Self {
+            storage: Mutex::new(storage),
+            crypto: RustCrypto::default(),
+            new_msgs: Notify::new(),
+            challenges: Mutex::default(),
+        }
+    }
+
+    /// Locks the storage and begins a transaction on it.
+    fn transaction(&self) -> Result<StorageTransaction<'_, S>, S::Error> {
+        let mut storage = self.storage.lock();
+        storage.begin_transaction()?;
+        Ok(StorageTransaction {
+            storage,
+            open: true,
+        })
+    }
+
+    /// Checks that `auth` answers an open challenge with a valid signature of
+    /// the client's signature key over the request to `uri` with the given
+    /// `body`. The challenge is used up in any case.
+    /// Returns the authenticated client, or the status to respond with
+    /// otherwise.
+    fn authenticate(
+        &self,
+        storage: &S,
+        auth: &ClientAuth,
+        uri: &Uri,
+        body: &[u8],
+    ) -> Result<ClientInfo, StatusCode> {
+        let open_challenge = self
+            .challenges
+            .lock()
+            .get_mut(&auth.client_id)
+            .and_then(|nonces| {
+                let position = nonces.iter().position(|nonce| nonce == &auth.nonce)?;
+                Some(nonces.remove(position))
+            });
+        if open_challenge.is_none() {
+            log::debug!("No open challenge for client {:?}", auth.client_id);
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+
+        let client = match storage.read_client(&auth.client_id) {
+            Ok(Some(client)) => client,
+            Ok(None) => return Err(StatusCode::UNAUTHORIZED),
+            Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+        };
+        if let Err(e) = auth.verify(
+            uri.path(),
+            body,
+            &self.crypto,
+            &client.signature_public_key(),
+        ) {
+            log::debug!(
+                "Invalid authentication for client {:?}: {e}",
+                auth.client_id
+            );
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+        Ok(client)
+    }
+}
+
+/// A transaction on the DS storage, which holds the lock on the storage.
+/// All writes are discarded if it is dropped without being committed, e.g.
+/// when a request fails halfway.
+struct StorageTransaction<'a, S: DsStorage> {
+    storage: MutexGuard<'a, S>,
+    open: bool,
+}
+
+impl<S: DsStorage> StorageTransaction<'_, S> {
+    /// Commits all writes of the transaction.
+    fn commit(mut self) -> Result<(), S::Error> {
+        self.open = false;
+        self.storage.commit_transaction()
+    }
+}
+
+impl<S: DsStorage> Deref for StorageTransaction<'_, S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        &self.storage
+    }
+}
+
+impl<S: DsStorage> DerefMut for StorageTransaction<'_, S> {
+    fn deref_mut(&mut self) -> &mut S {
+        &mut self.storage
+    }
+}
+
+impl<S: DsStorage> Drop for StorageTransaction<'_, S> {
+    fn drop(&mut self) {
+        if self.open {
+            if let Err(e) = self.storage.rollback_transaction() {
+                log::error!("Error rolling back the storage transaction: {e}");
+            }
+        }
+    }
+}
+
+/// Checks that all key packages are valid and bound to the `signature_key`.
+fn valid_key_packages(
+    crypto: &RustCrypto,
+    key_packages: &ClientKeyPackages,
+    signature_key: &[u8],
+) -> bool {
+    key_packages.0.iter().all(|(_, key_package)| {
+        match key_package.clone().validate(crypto, ProtocolVersion::Mls10) {
+            Ok(key_package) => key_package.leaf_node().signature_key().as_slice() == signature_key,
+            Err(e) => {
+                log::debug!("Invalid key package: {e}");
+                false
+            }
+        }
+    })
 }
 
 macro_rules! unwrap_data {
@@ -78,13 +243,26 @@ macro_rules! unwrap_data {
     };
 }
 
+macro_rules! authenticate {
+    ( $data:expr, $storage:expr, $auth:expr, $uri:expr, $request:expr ) => {
+        match $data.authenticate($storage, $auth, $uri, request_body($request, $auth)) {
+            Ok(client) => client,
+            Err(status) => return status.into_response(),
+        }
+    };
+}
+
 // === API ===
 
-/// Registering a new client takes a serialised `ClientInfo` object and returns
-/// a simple "Welcome {client name}" on success.
+/// Registering a new client takes a serialised `RegisterClientRequest`.
+/// The client authenticates with the signature key in the leaf node of its key
+/// packages from now on.
 /// An HTTP conflict (409) is returned if a client with this name exists
-/// already.
-async fn register_client(State(data): State<Arc<DsData>>, body: Bytes) -> Response {
+/// already and a bad request (400) if the key packages are invalid.
+async fn register_client<S: DsStorage>(
+    State(data): State<Arc<DsData<S>>>,
+    body: Bytes,
+) -> Response {
     let req = match RegisterClientRequest::tls_deserialize(&mut &body[..]) {
         Ok(i) => i,
         Err(_) => {
@@ -106,34 +284,71 @@ async fn register_client(State(data): State<Arc<DsData>>, body: Bytes) -> Respon
         .map(|(b, kp)| (b.into_vec(), kp))
         .collect();
     let new_client_info = ClientInfo::new(key_packages);
+    if !valid_key_packages(
+        &data.crypto,
+        &new_client_info.key_packages,
+        &new_client_info.signature_key,
+    ) {
+        return StatusCode::BAD_REQUEST.into_response();
+    }
 
     log::debug!("Registering client: {:?}", new_client_info.id);
 
-    let response = RegisterClientSuccessResponse {
-        auth_token: new_client_info.auth_token.clone(),
+    let mut storage = unwrap_data!(data.transaction());
+    if unwrap_data!(storage.read_client(&new_client_info.id)).is_some() {
+        return StatusCode::CONFLICT.into_response();
+    }
+    unwrap_data!(storage.write_client(&new_client_info));
+    unwrap_data!(storage.commit());
+
+    StatusCode::OK.into_response()
+}
+
+/// Get a challenge for the client `{id}`.
+/// This returns a serialised `AuthChallenge`. The client authenticates a
+/// request by signing it into a `ClientAuth` (see the `ds-lib` for details).
+async fn auth_challenge<S: DsStorage>(
+    Path(path): Path<String>,
+    State(data): State<Arc<DsData<S>>>,
+) -> Response {
+    let id = match base64::engine::general_purpose::URL_SAFE.decode(path) {
+        Ok(v) => v,
+        Err(_) => return StatusCode::BAD_REQUEST.into_response(),
     };
 
-    let mut clients = data.clients.lock();
-    if clients.contains_key(&new_client_info.id) {
-        return StatusCode::CONFLICT.into_response();
+    let storage = unwrap_data!(data.transaction());
+    if unwrap_data!(storage.read_client(&id)).is_none() {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+
+    let challenge = AuthChallenge::random();
+    let mut challenges = data.challenges.lock();
+    let nonces = challenges.entry(id).or_default();
+    if nonces.len() >= MAX_OPEN_CHALLENGES {
+        nonces.remove(0);
     }
-    let old = clients.insert(new_client_info.id.clone(), new_client_info);
-    assert!(old.is_none());
+    nonces.push(challenge.nonce.clone());
 
-    response.tls_serialize_detached().unwrap().into_response()
+    unwrap_data!(challenge.tls_serialize_detached()).into_response()
 }
 
 /// Returns a list of clients with their names and IDs.
-async fn list_clients(State(data): State<Arc<DsData>>) -> Response {
+/// This takes a serialised `ClientAuth` of the requesting client.
+async fn list_clients<S: DsStorage>(
+    uri: Uri,
+    State(data): State<Arc<DsData<S>>>,
+    body: Bytes,
+) -> Response {
+    let auth = match ClientAuth::tls_deserialize_exact(&body) {
+        Ok(i) => i,
+        Err(_) => return StatusCode::BAD_REQUEST.into_response(),
+    };
+
     log::debug!("Listing clients");
-    let clients = data.clients.lock();
-
-    // XXX: we could encode while iterating to be less wasteful.
-    let clients: TlsVecU32<Vec<u8>> = clients
-        .values()
-        .map(|c| c.id().to_vec())
-        .collect::<Vec<Vec<u8>>>()
-        .into();
+    let storage = unwrap_data!(data.transaction());
+    authenticate!(data, &storage, &auth, &uri, &body);
+
+    let clients: TlsVecU32<Vec<u8>> = unwrap_data!(storage.client_ids()).into();
     let mut out_bytes = Vec::new();
     if clients.tls_serialize(&mut out_bytes).is_err() {
         return StatusCode::INTERNAL_SERVER_ERROR.into_response();
@@ -142,34 +357,44 @@ async fn list_clients(State(data): State<Arc<DsData>>) -> Response {
 }
 
 /// Resets the server state.
-async fn reset(headers: HeaderMap, State(data): State<Arc<DsData>>) -> Response {
+async fn reset<S: DsStorage>(headers: HeaderMap, State(data): State<Arc<DsData<S>>>) -> Response {
     if let Some(reset_key) = headers.get("reset-key") {
         if reset_key != "poc-reset-password" {
             return StatusCode::NETWORK_AUTHENTICATION_REQUIRED.into_response();
         }
     }
     log::debug!("Resetting server");
-    let mut clients = data.clients.lock();
-    let mut groups = data.groups.lock();
-    clients.clear();
-    groups.clear();
+    let mut storage = unwrap_data!(data.transaction());
+    unwrap_data!(storage.clear());
+    unwrap_data!(storage.commit());
+    data.challenges.lock().clear();
     StatusCode::OK.into_response()
 }
 
 /// Get the list of key packages for a given client `{id}`.
-/// This returns a serialised vector of `ClientKeyPackages` (see the `ds-lib`
-/// for details).
-async fn get_key_packages(Path(path): Path<String>, State(data): State<Arc<DsData>>) -> Response {
-    let clients = data.clients.lock();
+/// This takes a serialised `ClientAuth` of the requesting client and returns a
+/// serialised vector of `ClientKeyPackages` (see the `ds-lib` for details).
+async fn get_key_packages<S: DsStorage>(
+    uri: Uri,
+    Path(path): Path<String>,
+    State(data): State<Arc<DsData<S>>>,
+    body: Bytes,
+) -> Response {
+    let storage = unwrap_data!(data.transaction());
 
     let id = match base64::engine::general_purpose::URL_SAFE.decode(path) {
         Ok(v) => v,
         Err(_) => return StatusCode::BAD_REQUEST.into_response(),
     };
+    let auth = match ClientAuth::tls_deserialize_exact(&body) {
+        Ok(i) => i,
+        Err(_) => return StatusCode::BAD_REQUEST.into_response(),
+    };
+    authenticate!(data, &storage, &auth, &uri, &body);
 
     log::debug!("Getting key packages for {id:?}");
 
-    let client = match clients.get(&id) {
+    let client = match unwrap_data!(storage.read_client(&id)) {
         Some(c) => c,
         None => return StatusCode::NO_CONTENT.into_response(),
     };
@@ -177,25 +402,21 @@ async fn get_key_packages(Path(path): Path<String>, State(data): State<Arc<DsDat
 }
 
 /// Publish key packages for a given client `{id}`.
-async fn publish_key_packages(
+async fn publish_key_packages<S: DsStorage>(
+    uri: Uri,
     Path(path): Path<String>,
-    State(data): State<Arc<DsData>>,
+    State(data): State<Arc<DsData<S>>>,
     body: Bytes,
 ) -> Response {
-    let mut clients = data.clients.lock();
+    let mut storage = unwrap_data!(data.transaction());
 
     let id = match base64::engine::general_purpose::URL_SAFE.decode(path) {
         Ok(v) => v,
         Err(_) => return StatusCode::BAD_REQUEST.into_response(),
     };
 
-    let client = match clients.get(&id) {
-        Some(c) => c,
-        None => return StatusCode::NOT_FOUND.into_response(),
-    };
-
     // Deserialize request
-    let req = match PublishKeyPackagesRequest::tls_deserialize(&mut &body[..]) {
+    let req = match PublishKeyPackagesRequest::tls_deserialize_exact(&body) {
         Ok(i) => i,
         Err(_) => {
             log::error!("Invalid payload for /clients/key_packages/{id:?}\n{body:?}");
@@ -204,73 +425,103 @@ async fn publish_key_packages(
     };
 
     // Auth
-    if client.auth_token != req.auth_token {
-        return StatusCode::UNAUTHORIZED.into_response();
+    let mut client = authenticate!(data, &storage, &req.auth, &uri, &body);
+    if client.id != id {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    if !valid_key_packages(&data.crypto, &req.key_packages, &client.signature_key) {
+        return StatusCode::BAD_REQUEST.into_response();
     }
 
     log::debug!("Add key package for {id:?}");
 
-    let client = match clients.get_mut(&id) {
-        Some(client) => client,
-        None => return StatusCode::NOT_FOUND.into_response(),
-    };
-
     req.key_packages
         .0
         .into_vec()
         .into_iter()
         .for_each(|value| client.key_packages.0.push(value));
+    unwrap_data!(storage.write_client(&client));
+    unwrap_data!(storage.commit());
 
     StatusCode::OK.into_response()
 }
 
 /// Consume a key package for a given client `{id}`.
-/// This returns a serialised `KeyPackage` (see the `ds-lib`
-/// for details).
-async fn consume_key_package(
+/// This takes a serialised `ClientAuth` of the requesting client and returns a
+/// serialised `KeyPackage` (see the `ds-lib` for details).
+async fn consume_key_package<S: DsStorage>(
+    uri: Uri,
     Path(path): Path<String>,
-    State(data): State<Arc<DsData>>,
+    State(data): State<Arc<DsData<S>>>,
+    body: Bytes,
 ) -> Response {
-    let mut clients = data.clients.lock();
+    let mut storage = unwrap_data!(data.transaction());
 
     let id = match base64::engine::general_purpose::URL_SAFE.decode(path) {
         Ok(v) => v,
         Err(_) => return StatusCode::BAD_REQUEST.into_response(),
     };
+    let auth = match ClientAuth::tls_deserialize_exact(&body) {
+        Ok(i) => i,
+        Err(_) => return StatusCode::BAD_REQUEST.into_response(),
+    };
+    authenticate!(data, &storage, &auth, &uri, &body);
     log::debug!("Consuming key package for {id:?}");
 
-    let key_package = match clients.get_mut(&id) {
-        Some(c) => match c.consume_kp() {
-            Ok(kp) => kp,
-            Err(e) => {
-                log::debug!("Error consuming key package: {e}");
-                return StatusCode::NO_CONTENT.into_response();
-            }
-        },
+    let mut client = match unwrap_data!(storage.read_client(&id)) {
+        Some(c) => c,
         None => return StatusCode::NO_CONTENT.into_response(),
     };
+    let key_package = match client.consume_kp() {
+        Ok(kp) => kp,
+        Err(e) => {
+            log::debug!("Error consuming key package: {e}");
+            return StatusCode::NO_CONTENT.into_response();
+        }
+    };
+    unwrap_data!(storage.write_client(&client));
+    unwrap_data!(storage.commit());
 
     unwrap_data!(key_package.tls_serialize_detached()).into_response()
 }
 
 /// Send a welcome message to a client.
-/// This takes a serialised `Welcome` message and stores the message for all
+/// This takes a serialised `SendWelcomeRequest` and stores the message for all
 /// clients in the welcome message.
-async fn send_welcome(State(data): State<Arc<DsData>>, body: Bytes) -> Response {
-    let welcome_msg = unwrap_data!(MlsMessageIn::tls_deserialize(&mut &body[..]));
-    let welcome = welcome_msg.clone().into_welcome().unwrap();
+async fn send_welcome<S: DsStorage>(
+    uri: Uri,
+    State(data): State<Arc<DsData<S>>>,
+    body: Bytes,
+) -> Response {
+    let req = match SendWelcomeRequest::tls_deserialize_exact(&body) {
+        Ok(i) => i,
+        Err(_) => return StatusCode::BAD_REQUEST.into_response(),
+    };
+    let welcome_msg = req.welcome;
+    let welcome = match welcome_msg.clone().into_welcome() {
+        Some(welcome) => welcome,
+        None => return StatusCode::BAD_REQUEST.into_response(),
+    };
     log::debug!("Storing welcome message: {welcome_msg:?}");
 
-    let mut clients = data.clients.lock();
+    let mut storage = unwrap_data!(data.transaction());
+    authenticate!(data, &storage, &req.auth, &uri, &body);
+    let client_ids = unwrap_data!(storage.client_ids());
     for secret in welcome.secrets().iter() {
         let key_package_hash = &secret.new_member();
-        for (_client_name, client) in clients.iter_mut() {
+        for client_id in client_ids.iter() {
+            let Some(mut client) = unwrap_data!(storage.read_client(client_id)) else {
+                continue;
+            };
             match client
                 .reserved_key_pkg_hash
                 .take(key_package_hash.as_slice())
             {
                 Some(_kp_hash) => {
-                    client.welcome_queue.push(welcome_msg);
+                    unwrap_data!(storage.write_client(&client));
+                    unwrap_data!(storage.enqueue_welcome(client_id, &welcome_msg));
+                    unwrap_data!(storage.commit());
+                    data.new_msgs.notify_waiters();
                     return StatusCode::OK.into_response();
                 }
                 None => continue,
@@ -280,54 +531,341 @@ async fn send_welcome(State(data): State<Arc<DsData>>, body: Bytes) -> Response
     StatusCode::NO_CONTENT.into_response()
 }
 
-/// Send an MLS message to a set of clients (group).
-/// This takes a serialised `GroupMessage` and stores the message for each
-/// client in the recipient list.
-/// If a handshake message is sent with an epoch smaller or equal to another
-/// handshake message this DS has seen, a 409 is returned and the message is not
-/// processed.
-async fn msg_send(State(data): State<Arc<DsData>>, body: Bytes) -> Response {
-    let group_msg = unwrap_data!(GroupMessage::tls_deserialize(&mut &body[..]));
+/// Register a group with the DS.
+/// This takes a serialised `RegisterGroupRequest` and starts tracking the
+/// public state of the group. The `GroupInfo` has to be signed by the leaf of
+/// the registering client, i.e. only members can register a group.
+/// An HTTP conflict (409) is returned if the group is known already, a bad
+/// request (400) if the group info or ratchet tree are invalid and a forbidden
+/// (403) if the group info is signed by another member.
+async fn register_group<S: DsStorage>(
+    uri: Uri,
+    State(data): State<Arc<DsData<S>>>,
+    body: Bytes,
+) -> Response {
+    let req = match RegisterGroupRequest::tls_deserialize_exact(&body) {
+        Ok(i) => i,
+        Err(_) => {
+            log::error!("Invalid payload for /groups/register\n{body:?}");
+            return StatusCode::BAD_REQUEST.into_response();
+        }
+    };
+    let group_info = match req.group_info.clone().extract() {
+        MlsMessageBodyIn::GroupInfo(group_info) => group_info,
+        _ => return StatusCode::BAD_REQUEST.into_response(),
+    };
+    let signer = group_info.signer();
+
+    let mut storage = unwrap_data!(data.transaction());
+    let client = authenticate!(data, &storage, &req.auth, &uri, &body);
+    match PublicGroup::load(storage.group_storage(), group_info.group_id()) {
+        Ok(None) => (),
+        Ok(Some(_)) => return StatusCode::CONFLICT.into_response(),
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+
+    let group = match PublicGroup::from_external(
+        &data.crypto,
+        storage.group_storage(),
+        req.ratchet_tree,
+        group_info,
+        ProposalStore::new(),
+    ) {
+        Ok((group, _)) => group,
+        Err(e) => {
+            log::debug!("Error registering group: {e}");
+            return StatusCode::BAD_REQUEST.into_response();
+        }
+    };
+
+    // The signature of the group info was verified with the signer's leaf, so
+    // the client registers the group if that leaf is its own.
+    if !is_client_leaf(&group, signer, &client) {
+        log::debug!(
+            "Client {:?} is not the signer of the group info for {:?}",
+            client.id,
+            group.group_id()
+        );
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    log::debug!("Registered group {:?}", group.group_id());
+    unwrap_data!(storage.write_group_info(group.group_id(), &req.group_info));
+    unwrap_data!(storage.commit());
+    StatusCode::OK.into_response()
+}
+
+/// Checks that the leaf at `leaf_index` in `group` is the leaf of `client`.
+fn is_client_leaf(group: &PublicGroup, leaf_index: LeafNodeIndex, client: &ClientInfo) -> bool {
+    group.leaf(leaf_index).is_some_and(|leaf| {
+        leaf.credential().serialized_content() == client.id
+            && leaf.signature_key().as_slice() == client.signature_key
+    })
+}
+
+/// Checks that `group_info` is a `GroupInfo` for the current epoch of `group`,
+/// signed by one of its members.
+fn verify_group_info(crypto: &RustCrypto, group: &PublicGroup, group_info: MlsMessageIn) -> bool {
+    let Some(group_info) = group_info.into_verifiable_group_info() else {
+        return false;
+    };
+
+    // Building a throwaway public group from the group info and our copy of the
+    // ratchet tree checks the signature and the tree hash.
+    match PublicGroup::from_external(
+        crypto,
+        &MemoryStorage::default(),
+        group.export_ratchet_tree().into(),
+        group_info,
+        ProposalStore::new(),
+    ) {
+        Ok((published_group, _)) => {
+            published_group.group_context() == group.group_context()
+                && published_group.confirmation_tag() == group.confirmation_tag()
+        }
+        Err(e) => {
+            log::debug!("Invalid group info: {e}");
+            false
+        }
+    }
+}
+
+/// Publish the latest `GroupInfo` of a group.
+/// This takes a serialised `PublishGroupInfoRequest`. The `GroupInfo` has to be
+/// for the current epoch of the group, which is checked against the group's
+/// public state, and signed by the leaf of the publishing client.
+/// A not found (404) is returned if the group is unknown, an HTTP conflict
+/// (409) if the `GroupInfo` is for another epoch, a bad request (400) if it
+/// is invalid and a forbidden (403) if it is signed by another member.
+async fn publish_group_info<S: DsStorage>(
+    uri: Uri,
+    State(data): State<Arc<DsData<S>>>,
+    body: Bytes,
+) -> Response {
+    let req = match PublishGroupInfoRequest::tls_deserialize_exact(&body) {
+        Ok(i) => i,
+        Err(_) => {
+            log::error!("Invalid payload for /groups/group_info\n{body:?}");
+            return StatusCode::BAD_REQUEST.into_response();
+        }
+    };
+    let (group_id, epoch, signer) = match req.group_info.clone().into_verifiable_group_info() {
+        Some(group_info) => (
+            group_info.group_id().clone(),
+            group_info.epoch(),
+            group_info.signer(),
+        ),
+        None => return StatusCode::BAD_REQUEST.into_response(),
+    };
+
+    let mut storage = unwrap_data!(data.transaction());
+    let client = authenticate!(data, &storage, &req.auth, &uri, &body);
+    let group = match PublicGroup::load(storage.group_storage(), &group_id) {
+        Ok(Some(group)) => group,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+
+    if epoch != group.group_context().epoch() {
+        return StatusCode::CONFLICT.into_response();
+    }
+    if !verify_group_info(&data.crypto, &group, req.group_info.clone()) {
+        return StatusCode::BAD_REQUEST.into_response();
+    }
+    // The group info was verified against the current tree, so the client
+    // publishes it if the signer's leaf is its own.
+    if !is_client_leaf(&group, signer, &client) {
+        log::debug!(
+            "Client {:?} is not the signer of the group info for {:?}",
+            client.id,
+            group_id
+        );
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    unwrap_data!(storage.write_group_info(&group_id, &req.group_info));
+    unwrap_data!(storage.commit());
+    StatusCode::OK.into_response()
+}
+
+/// Get the latest `GroupInfo` of the group `{id}`.
+/// This returns a serialised `GroupInfoResponse` with the `GroupInfo` and the
+/// ratchet tree of the group, which is all a new member needs to join the
+/// group through an external commit.
+/// A not found (404) is returned if the group is unknown or there is no
+/// `GroupInfo` for its current epoch.
+async fn get_group_info<S: DsStorage>(
+    Path(path): Path<String>,
+    State(data): State<Arc<DsData<S>>>,
+) -> Response {
+    let group_id = match base64::engine::general_purpose::URL_SAFE.decode(path) {
+        Ok(v) => GroupId::from_slice(&v),
+        Err(_) => return StatusCode::BAD_REQUEST.into_response(),
+    };
+
+    let storage = unwrap_data!(data.transaction());
+    let group = match PublicGroup::load(storage.group_storage(), &group_id) {
+        Ok(Some(group)) => group,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+    let group_info = match unwrap_data!(storage.read_group_info(&group_id)) {
+        Some(group_info) => group_info,
+        None => return StatusCode::NOT_FOUND.into_response(),
+    };
+
+    let response = GroupInfoResponse {
+        group_info,
+        ratchet_tree: group.export_ratchet_tree().into(),
+    };
+    unwrap_data!(response.tls_serialize_detached()).into_response()
+}
+
+/// Send an MLS message to a group.
+/// This takes a serialised `SendMessageRequest` and stores the message for each
+/// member of the group. Members can only send messages in their own name.
+///
+/// Handshake messages must be `PublicMessage`s of the group's current epoch.
+/// They are processed with the group's `PublicGroup` and a bad request (400) is
+/// returned if they are invalid. A commit moves the group to the next epoch, so
+/// any other commit or proposal for the same epoch is stale afterwards and a
+/// 409 is returned. A 409 is also returned for messages from a future epoch.
+///
+/// The message is delivered to the current members of the group, except for
+/// the sender. For a commit, these are the members before the commit, such
+/// that removed members receive it as well. Because the DS can't see the
+/// sender of a `PrivateMessage`, those are delivered to all members except for
+/// the sending client.
+async fn msg_send<S: DsStorage>(
+    uri: Uri,
+    State(data): State<Arc<DsData<S>>>,
+    body: Bytes,
+) -> Response {
+    let req = match SendMessageRequest::tls_deserialize_exact(&body) {
+        Ok(i) => i,
+        Err(_) => return StatusCode::BAD_REQUEST.into_response(),
+    };
+    let group_msg = req.group_msg;
     log::debug!("Storing group message: {group_msg:?}");
 
-    let mut clients = data.clients.lock();
-    let mut groups = data.groups.lock();
-
-    let protocol_msg: ProtocolMessage = group_msg.msg.clone().try_into().unwrap();
-
-    // Reject any handshake message that has an earlier epoch than the one we know
-    // about.
-    // XXX: There's no test for this block in here right now because it's pretty
-    //      painful to test in the current setting. This should get tested through
-    //      the client and maybe later with the MlsGroup API.
-    if protocol_msg.is_handshake_message() {
-        let epoch = protocol_msg.epoch().as_u64();
-        let group_id = protocol_msg.group_id().as_slice();
-        if let Some(&group_epoch) = groups.get(group_id) {
-            if group_epoch > epoch {
-                return StatusCode::CONFLICT.into_response();
-            }
-            // Update server state to the latest epoch.
-            let old_value = groups.insert(group_id.to_vec(), epoch);
-            if old_value.is_none() {
-                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    let protocol_msg: ProtocolMessage = match group_msg.msg.clone().try_into() {
+        Ok(msg) => msg,
+        Err(_) => return StatusCode::BAD_REQUEST.into_response(),
+    };
+
+    let mut storage = unwrap_data!(data.transaction());
+    let sender_client = authenticate!(data, &storage, &req.auth, &uri, &body);
+
+    let mut group = match PublicGroup::load(storage.group_storage(), protocol_msg.group_id()) {
+        Ok(Some(group)) => group,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+
+    // Reject messages from future epochs and handshake messages that are not
+    // for the current epoch.
+    let epoch = group.group_context().epoch();
+    if protocol_msg.epoch() > epoch
+        || (protocol_msg.is_handshake_message() && protocol_msg.epoch() != epoch)
+    {
+        return StatusCode::CONFLICT.into_response();
+    }
+
+    // The members need to be collected before a commit is merged, such that
+    // removed members still receive the commit.
+    let members: Vec<(LeafNodeIndex, Vec<u8>)> = group
+        .members()
+        .map(|member| {
+            (
+                member.index,
+                member.credential.serialized_content().to_vec(),
+            )
+        })
+        .collect();
+
+    let recipients: Vec<Vec<u8>> = match protocol_msg {
+        ProtocolMessage::PublicMessage(_) => {
+            let processed_message = match group.process_message(&data.crypto, protocol_msg) {
+                Ok(processed_message) => processed_message,
+                Err(e) => {
+                    log::debug!("Rejecting invalid message: {e}");
+                    return StatusCode::BAD_REQUEST.into_response();
+                }
+            };
+            let sender = match processed_message.sender() {
+                Sender::Member(index) => Some(*index),
+                _ => None,
+            };
+            // Members can only send messages in their own name.
+            if let Some(sender) = sender {
+                if !members
+                    .iter()
+                    .any(|(index, id)| *index == sender && *id == sender_client.id)
+                {
+                    return StatusCode::FORBIDDEN.into_response();
+                }
             }
-        } else {
-            // We haven't seen this group_id yet. Store it.
-            let old_value = groups.insert(group_id.to_vec(), epoch);
-            if old_value.is_some() {
-                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            match processed_message.into_content() {
+                ProcessedMessageContent::StagedCommitMessage(staged_commit) => {
+                    unwrap_data!(group.merge_commit(storage.group_storage(), *staged_commit));
+
+                    // The previous group info is stale now. Replace it with the
+                    // one for the new epoch if the committer sent one along.
+                    match group_msg.group_info.clone() {
+                        Some(group_info)
+                            if verify_group_info(&data.crypto, &group, group_info.clone()) =>
+                        {
+                            unwrap_data!(storage.write_group_info(group.group_id(), &group_info))
+                        }
+                        Some(_) => {
+                            log::debug!("Dropping invalid group info for {:?}", group.group_id());
+                            unwrap_data!(storage.delete_group_info(group.group_id()))
+                        }
+                        None => unwrap_data!(storage.delete_group_info(group.group_id())),
+                    }
+                }
+                ProcessedMessageContent::ProposalMessage(proposal)
+                | ProcessedMessageContent::ExternalJoinProposalMessage(proposal) => {
+                    unwrap_data!(group.add_proposal(storage.group_storage(), *proposal))
+                }
+                ProcessedMessageContent::ApplicationMessage(_)
+                | ProcessedMessageContent::OwnPendingCommit => (),
             }
+
+            // Public messages go to all members but the sender.
+            members
+                .into_iter()
+                .filter(|(index, _)| sender != Some(*index))
+                .map(|(_, id)| id)
+                .collect::<Vec<_>>()
         }
-    }
+        ProtocolMessage::PrivateMessage(_) if protocol_msg.is_handshake_message() => {
+            log::debug!("Rejecting encrypted handshake message");
+            return StatusCode::BAD_REQUEST.into_response();
+        }
+        // The DS can't see the sender of private messages. It only checks that
+        // the sending client is a member of the group.
+        ProtocolMessage::PrivateMessage(_)
+            if !members.iter().any(|(_, id)| *id == sender_client.id) =>
+        {
+            return StatusCode::FORBIDDEN.into_response();
+        }
+        // Private messages go to all members but the sending client.
+        ProtocolMessage::PrivateMessage(_) => members
+            .into_iter()
+            .map(|(_, id)| id)
+            .filter(|id| *id != sender_client.id)
+            .collect(),
+    };
 
-    for recipient in group_msg.recipients.iter() {
-        let client = match clients.get_mut(recipient.as_slice()) {
-            Some(client) => client,
-            None => return StatusCode::NOT_FOUND.into_response(),
-        };
-        client.msgs.push(group_msg.msg.clone());
+    for id in recipients {
+        match unwrap_data!(storage.read_client(&id)) {
+            Some(_) => unwrap_data!(storage.enqueue_msg(&id, &group_msg.msg)),
+            None => log::debug!("Member {id:?} is not registered with the DS"),
+        }
     }
+    unwrap_data!(storage.commit());
+    data.new_msgs.notify_waiters();
     StatusCode::OK.into_response()
 }
 
@@ -335,44 +873,42 @@ async fn msg_send(State(data): State<Arc<DsData>>, body: Bytes) -> Response {
 /// This returns a serialised vector of `Message`s (see the `ds-lib` for
 /// details) the DS has stored for the given client.
 /// The messages are deleted on the DS when sent out.
-async fn msg_recv(
+async fn msg_recv<S: DsStorage>(
+    uri: Uri,
     Path(path): Path<String>,
-    State(data): State<Arc<DsData>>,
+    State(data): State<Arc<DsData<S>>>,
     body: Bytes,
 ) -> Response {
-    let mut clients = data.clients.lock();
+    let mut storage = unwrap_data!(data.transaction());
 
     let id = match base64::engine::general_purpose::URL_SAFE.decode(path) {
         Ok(v) => v,
         Err(_) => return StatusCode::BAD_REQUEST.into_response(),
     };
 
-    let client = match clients.get_mut(&id) {
-        Some(client) => client,
-        None => return StatusCode::NOT_FOUND.into_response(),
-    };
-
-    // Auth
     // Deserialize request
-    let req = match RecvMessageRequest::tls_deserialize(&mut &body[..]) {
+    let req = match RecvMessageRequest::tls_deserialize_exact(&body) {
         Ok(i) => i,
         Err(_) => {
-            log::error!("Invalid payload for /clients/key_packages/{id:?}\n{body:?}");
+            log::error!("Invalid payload for /recv/{id:?}\n{body:?}");
             return StatusCode::BAD_REQUEST.into_response();
         }
     };
 
-    if req.auth_token != client.auth_token {
-        return StatusCode::UNAUTHORIZED.into_response();
+    // Auth
+    let client = authenticate!(data, &storage, &req.auth, &uri, &body);
+    if client.id != id {
+        return StatusCode::FORBIDDEN.into_response();
     }
 
     log::debug!("Getting messages for client {id:?}");
 
-    let mut out: Vec<MlsMessageIn> = Vec::new();
-    let mut welcomes: Vec<MlsMessageIn> = client.welcome_queue.drain(..).collect();
-    out.append(&mut welcomes);
-    let mut msgs: Vec<MlsMessageIn> = client.msgs.drain(..).collect();
-    out.append(&mut msgs);
+    let pending = unwrap_data!(storage.pending_msgs(&id));
+    let sequence_numbers: Vec<u64> = pending.iter().map(|m| m.sequence_number).collect();
+    unwrap_data!(storage.acknowledge(&id, &sequence_numbers));
+    unwrap_data!(storage.commit());
+
+    let out: Vec<MlsMessageIn> = pending.into_iter().map(|m| m.msg).collect();
 
     match TlsSliceU16(&out).tls_serialize_detached() {
         Ok(out) => out.into_response(),
@@ -380,20 +916,129 @@ async fn msg_recv(
     }
 }
 
+/// Wait for messages for the client `{id}`.
+/// This returns a serialised vector of `QueuedMessage`s as soon as there are
+/// messages for the client that haven't been acknowledged yet. If no message
+/// arrives within the requested timeout, the vector is empty.
+/// The messages stay on the DS until they are acknowledged through
+/// `/ack/{id}` and are delivered again on every poll until then.
+async fn msg_poll<S: DsStorage>(
+    uri: Uri,
+    Path(path): Path<String>,
+    State(data): State<Arc<DsData<S>>>,
+    body: Bytes,
+) -> Response {
+    let id = match base64::engine::general_purpose::URL_SAFE.decode(path) {
+        Ok(v) => v,
+        Err(_) => return StatusCode::BAD_REQUEST.into_response(),
+    };
+
+    // Deserialize request
+    let req = match PollMessagesRequest::tls_deserialize_exact(&body) {
+        Ok(i) => i,
+        Err(_) => {
+            log::error!("Invalid payload for /poll/{id:?}\n{body:?}");
+            return StatusCode::BAD_REQUEST.into_response();
+        }
+    };
+
+    // Auth
+    {
+        let storage = unwrap_data!(data.transaction());
+        let client = authenticate!(data, &storage, &req.auth, &uri, &body);
+        if client.id != id {
+            return StatusCode::FORBIDDEN.into_response();
+        }
+    }
+
+    let timeout = Duration::from_millis(req.timeout_ms).min(MAX_POLL_TIMEOUT);
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        // Register for new messages before looking at the queue, such that
+        // messages arriving in between wake us up.
+        let new_msgs = data.new_msgs.notified();
+        tokio::pin!(new_msgs);
+        new_msgs.as_mut().enable();
+
+        let pending = {
+            let storage = unwrap_data!(data.transaction());
+            if unwrap_data!(storage.read_client(&id)).is_none() {
+                return StatusCode::NOT_FOUND.into_response();
+            }
+            unwrap_data!(storage.pending_msgs(&id))
+        };
+
+        if !pending.is_empty() || tokio::time::timeout_at(deadline, new_msgs).await.is_err() {
+            log::debug!("Delivering {} messages to client {id:?}", pending.len());
+            return match TlsSliceU16(&pending).tls_serialize_detached() {
+                Ok(out) => out.into_response(),
+                Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+            };
+        }
+    }
+}
+
+/// Acknowledge messages for the client `{id}`.
+/// This takes a serialised `AckMessagesRequest` and deletes the acknowledged
+/// messages from the DS.
+async fn msg_ack<S: DsStorage>(
+    uri: Uri,
+    Path(path): Path<String>,
+    State(data): State<Arc<DsData<S>>>,
+    body: Bytes,
+) -> Response {
+    let mut storage = unwrap_data!(data.transaction());
+
+    let id = match base64::engine::general_purpose::URL_SAFE.decode(path) {
+        Ok(v) => v,
+        Err(_) => return StatusCode::BAD_REQUEST.into_response(),
+    };
+
+    // Deserialize request
+    let req = match AckMessagesRequest::tls_deserialize_exact(&body) {
+        Ok(i) => i,
+        Err(_) => {
+            log::error!("Invalid payload for /ack/{id:?}\n{body:?}");
+            return StatusCode::BAD_REQUEST.into_response();
+        }
+    };
+
+    // Auth
+    let client = authenticate!(data, &storage, &req.auth, &uri, &body);
+    if client.id != id {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    log::debug!(
+        "Acknowledging messages {:?} for client {id:?}",
+        req.sequence_numbers
+    );
+    unwrap_data!(storage.acknowledge(&id, &req.sequence_numbers));
+    unwrap_data!(storage.commit());
+
+    StatusCode::OK.into_response()
+}
+
 /// Build the axum router for the DS.
-fn app(data: Arc<DsData>) -> Router {
+fn app<S: DsStorage>(data: Arc<DsData<S>>) -> Router {
     Router::new()
-        .route("/clients/register", post(register_client))
-        .route("/clients/list", get(list_clients))
+        .route("/clients/register", post(register_client::<S>))
+        .route("/clients/challenge/{id}", get(auth_challenge::<S>))
+        .route("/clients/list", get(list_clients::<S>))
         .route(
             "/clients/key_packages/{id}",
-            get(get_key_packages).post(publish_key_packages),
+            get(get_key_packages::<S>).post(publish_key_packages::<S>),
         )
-        .route("/clients/key_package/{id}", get(consume_key_package))
-        .route("/send/welcome", post(send_welcome))
-        .route("/send/message", post(msg_send))
-        .route("/recv/{id}", get(msg_recv))
-        .route("/reset", get(reset))
+        .route("/clients/key_package/{id}", get(consume_key_package::<S>))
+        .route("/groups/register", post(register_group::<S>))
+        .route("/groups/group_info", post(publish_group_info::<S>))
+        .route("/groups/group_info/{id}", get(get_group_info::<S>))
+        .route("/send/welcome", post(send_welcome::<S>))
+        .route("/send/message", post(msg_send::<S>))
+        .route("/recv/{id}", get(msg_recv::<S>))
+        .route("/poll/{id}", get(msg_poll::<S>))
+        .route("/ack/{id}", post(msg_ack::<S>))
+        .route("/reset", get(reset::<S>))
         .with_state(data)
 }
 
@@ -416,10 +1061,25 @@ async fn main() -> std::io::Result<()> {
                 .value_parser(clap::value_parser!(u16))
                 .help("Sets a custom port number"),
         )
+        .arg(
+            clap::Arg::new("database")
+                .short('d')
+                .long("database")
+                .value_name("path")
+                .value_parser(clap::value_parser!(std::path::PathBuf))
+                .help("Persists the DS state in the SQLite database at the given path"),
+        )
         .get_matches();
 
-    // The data this app operates on.
-    let data = Arc::new(DsData::default());
+    // The router with the data this app operates on.
+    let router = match matches.get_one::<std::path::PathBuf>("database") {
+        Some(path) => {
+            log::info!("Using database: {}", path.display());
+            let storage = SqliteDsStorage::open(path).map_err(std::io::Error::other)?;
+            app(Arc::new(DsData::new(storage)))
+        }
+        None => app(Arc::new(DsData::new(MemoryDsStorage::default()))),
+    };
 
     // Set default port or use port provided on the command line.
     let port = *matches.get_one("port").unwrap_or(&8080u16);
@@ -430,5 +1090,5 @@ async fn main() -> std::io::Result<()> {
 
     // Start the server.
     let listener = tokio::net::TcpListener::bind(&addr).await?;
-    axum::serve(listener, app(data)).await
+    axum::serve(listener, router).await
 }
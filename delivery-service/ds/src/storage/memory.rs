@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+use ds_lib::{ClientInfo, QueuedMessage};
+use openmls::prelude::{GroupId, MlsMessageIn};
+use openmls_memory_storage::{MemoryStorage, MemoryStorageError};
+use openmls_traits::storage::{StorageProvider, CURRENT_VERSION};
+
+use super::DsStorage;
+
+/// The state of the DS besides the groups.
+#[derive(Default, Debug, Clone)]
+struct MemoryDsState {
+    // (ClientIdentity, ClientInfo)
+    clients: HashMap<Vec<u8>, ClientInfo>,
+    // (ClientIdentity, [QueuedMessage])
+    welcome_queues: HashMap<Vec<u8>, Vec<QueuedMessage>>,
+    msg_queues: HashMap<Vec<u8>, Vec<QueuedMessage>>,
+    // (GroupId, GroupInfo)
+    group_infos: HashMap<Vec<u8>, MlsMessageIn>,
+    next_sequence_number: u64,
+}
+
+impl MemoryDsState {
+    fn queued_message(&mut self, msg: &MlsMessageIn) -> QueuedMessage {
+        let sequence_number = self.next_sequence_number;
+        self.next_sequence_number += 1;
+        QueuedMessage {
+            sequence_number,
+            msg: msg.clone(),
+        }
+    }
+}
+
+/// A [`DsStorage`] that keeps all state in memory.
+///
+/// A transaction keeps a copy of the state to restore on rollback.
+#[derive(Default, Debug)]
+pub struct MemoryDsStorage {
+    state: MemoryDsState,
+    snapshot: Option<MemoryDsState>,
+    groups: MemoryStorage,
+}
+
+impl DsStorage for MemoryDsStorage {
+    type Error = MemoryStorageError;
+    type GroupStorage = MemoryStorage;
+
+    fn begin_transaction(&mut self) -> Result<(), Self::Error> {
+        self.snapshot = Some(self.state.clone());
+        StorageProvider::<CURRENT_VERSION>::begin_transaction(&self.groups)
+    }
+
+    fn commit_transaction(&mut self) -> Result<(), Self::Error> {
+        self.snapshot = None;
+        StorageProvider::<CURRENT_VERSION>::commit_transaction(&self.groups)
+    }
+
+    fn rollback_transaction(&mut self) -> Result<(), Self::Error> {
+        if let Some(snapshot) = self.snapshot.take() {
+            self.state = snapshot;
+        }
+        StorageProvider::<CURRENT_VERSION>::rollback_transaction(&self.groups)
+    }
+
+    fn client_ids(&self) -> Result<Vec<Vec<u8>>, Self::Error> {
+        Ok(self.state.clients.keys().cloned().collect())
+    }
+
+    fn read_client(&self, id: &[u8]) -> Result<Option<ClientInfo>, Self::Error> {
+        Ok(self.state.clients.get(id).cloned())
+    }
+
+    fn write_client(&mut self, client: &ClientInfo) -> Result<(), Self::Error> {
+        self.state.clients.insert(client.id.clone(), client.clone());
+        Ok(())
+    }
+
+    fn enqueue_welcome(&mut self, client_id: &[u8], msg: &MlsMessageIn) -> Result<(), Self::Error> {
+        let queued = self.state.queued_message(msg);
+        self.state
+            .welcome_queues
+            .entry(client_id.to_vec())
+            .or_default()
+            .push(queued);
+        Ok(())
+    }
+
+    fn enqueue_msg(&mut self, client_id: &[u8], msg: &MlsMessageIn) -> Result<(), Self::Error> {
+        let queued = self.state.queued_message(msg);
+        self.state
+            .msg_queues
+            .entry(client_id.to_vec())
+            .or_default()
+            .push(queued);
+        Ok(())
+    }
+
+    fn pending_msgs(&self, client_id: &[u8]) -> Result<Vec<QueuedMessage>, Self::Error> {
+        let welcomes = self.state.welcome_queues.get(client_id).into_iter();
+        let msgs = self.state.msg_queues.get(client_id).into_iter();
+        Ok(welcomes.chain(msgs).flatten().cloned().collect())
+    }
+
+    fn acknowledge(
+        &mut self,
+        client_id: &[u8],
+        sequence_numbers: &[u64],
+    ) -> Result<(), Self::Error> {
+        for queues in [&mut self.state.welcome_queues, &mut self.state.msg_queues] {
+            if let Some(queue) = queues.get_mut(client_id) {
+                queue.retain(|m| !sequence_numbers.contains(&m.sequence_number));
+            }
+        }
+        Ok(())
+    }
+
+    fn read_group_info(&self, group_id: &GroupId) -> Result<Option<MlsMessageIn>, Self::Error> {
+        Ok(self.state.group_infos.get(group_id.as_slice()).cloned())
+    }
+
+    fn write_group_info(
+        &mut self,
+        group_id: &GroupId,
+        group_info: &MlsMessageIn,
+    ) -> Result<(), Self::Error> {
+        self.state
+            .group_infos
+            .insert(group_id.to_vec(), group_info.clone());
+        Ok(())
+    }
+
+    fn delete_group_info(&mut self, group_id: &GroupId) -> Result<(), Self::Error> {
+        self.state.group_infos.remove(group_id.as_slice());
+        Ok(())
+    }
+
+    fn group_storage(&self) -> &Self::GroupStorage {
+        &self.groups
+    }
+
+    fn clear(&mut self) -> Result<(), Self::Error> {
+        self.state = MemoryDsState::default();
+        self.groups.values.write().unwrap().clear();
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+//! # Persistence for the DS
+//!
+//! The DS keeps its state in a [`DsStorage`]. This covers the registered
+//! clients with their key packages, the messages queued for them, the public
+//! state of the registered groups and their latest `GroupInfo`s.
+//!
+//! The DS handles each request in a storage transaction, such that a failing
+//! request doesn't leave a partial update behind.
+//!
+//! There are two implementations:
+//! * [`MemoryDsStorage`] keeps everything in memory and is used for testing.
+//! * [`SqliteDsStorage`] persists everything in an SQLite database, such that
+//!   the DS can be restarted without losing state.
+
+use ds_lib::{ClientInfo, QueuedMessage};
+use openmls::{
+    prelude::{GroupId, MlsMessageIn},
+    storage::PublicStorageProvider,
+};
+
+mod memory;
+mod sqlite;
+
+pub use memory::MemoryDsStorage;
+pub use sqlite::SqliteDsStorage;
+
+/// The persistence layer of the DS.
+///
+/// Clients are read and written as a whole, while their messages are queued
+/// and acknowledged one by one. The public state of the groups is stored
+/// through an OpenMLS [`PublicStorageProvider`], which takes part in the
+/// transactions of the DS storage.
+pub trait DsStorage: Send + 'static {
+    /// The error returned when reading or writing clients.
+    type Error: std::error::Error;
+    /// The storage for the public state of the groups.
+    type GroupStorage: PublicStorageProvider;
+
+    /// Begins a transaction. All writes until the matching
+    /// [`commit_transaction`](Self::commit_transaction) are persisted together
+    /// or discarded with [`rollback_transaction`](Self::rollback_transaction),
+    /// including those of the group storage.
+    fn begin_transaction(&mut self) -> Result<(), Self::Error>;
+
+    /// Commits the open transaction.
+    fn commit_transaction(&mut self) -> Result<(), Self::Error>;
+
+    /// Discards all writes of the open transaction.
+    fn rollback_transaction(&mut self) -> Result<(), Self::Error>;
+
+    /// Returns the ids of all registered clients.
+    fn client_ids(&self) -> Result<Vec<Vec<u8>>, Self::Error>;
+
+    /// Reads the client with the given id.
+    fn read_client(&self, id: &[u8]) -> Result<Option<ClientInfo>, Self::Error>;
+
+    /// Writes the client, replacing a client with the same id.
+    fn write_client(&mut self, client: &ClientInfo) -> Result<(), Self::Error>;
+
+    /// Queues a welcome message for the client.
+    fn enqueue_welcome(&mut self, client_id: &[u8], msg: &MlsMessageIn) -> Result<(), Self::Error>;
+
+    /// Queues a group message for the client.
+    fn enqueue_msg(&mut self, client_id: &[u8], msg: &MlsMessageIn) -> Result<(), Self::Error>;
+
+    /// Returns all messages queued for the client that haven't been
+    /// acknowledged yet, welcome messages first. Each message has a sequence
+    /// number that is unique for the client.
+    fn pending_msgs(&self, client_id: &[u8]) -> Result<Vec<QueuedMessage>, Self::Error>;
+
+    /// Removes the messages with the given sequence numbers from the client's
+    /// queues.
+    fn acknowledge(
+        &mut self,
+        client_id: &[u8],
+        sequence_numbers: &[u64],
+    ) -> Result<(), Self::Error>;
+
+    /// Reads the latest `GroupInfo` published for the group.
+    fn read_group_info(&self, group_id: &GroupId) -> Result<Option<MlsMessageIn>, Self::Error>;
+
+    /// Writes the latest `GroupInfo` for the group, replacing the previous
+    /// one.
+    fn write_group_info(
+        &mut self,
+        group_id: &GroupId,
+        group_info: &MlsMessageIn,
+    ) -> Result<(), Self::Error>;
+
+    /// Deletes the `GroupInfo` of the group.
+    fn delete_group_info(&mut self, group_id: &GroupId) -> Result<(), Self::Error>;
+
+    /// Returns the storage for the public state of the groups.
+    fn group_storage(&self) -> &Self::GroupStorage;
+
+    /// Deletes all clients, messages and groups.
+    fn clear(&mut self) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,243 @@
+use std::path::Path;
+
+use ds_lib::{ClientInfo, QueuedMessage};
+use openmls::{
+    group::PublicGroup,
+    prelude::{GroupId, MlsMessageIn},
+};
+use openmls_sqlite_storage::{Codec, Connection, SqliteStorageProvider};
+use rusqlite::{params, OptionalExtension};
+use tls_codec::{Deserialize, Serialize};
+
+use super::DsStorage;
+
+/// The codec used to store the public state of the groups.
+#[derive(Default)]
+pub struct JsonCodec;
+
+impl Codec for JsonCodec {
+    type Error = serde_json::Error;
+
+    fn to_vec<T: serde::Serialize>(value: &T) -> Result<Vec<u8>, Self::Error> {
+        serde_json::to_vec(value)
+    }
+
+    fn from_slice<T: serde::de::DeserializeOwned>(slice: &[u8]) -> Result<T, Self::Error> {
+        serde_json::from_slice(slice)
+    }
+}
+
+/// Errors of the [`SqliteDsStorage`].
+#[derive(thiserror::Error, Debug)]
+pub enum SqliteDsStorageError {
+    /// An error occurred in the database.
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
+    /// A stored client could not be encoded or decoded.
+    #[error(transparent)]
+    Codec(#[from] tls_codec::Error),
+    /// The database could not be migrated.
+    #[error("Error running migrations: {0}")]
+    Migration(String),
+}
+
+/// A [`DsStorage`] that persists all state in an SQLite database.
+///
+/// Clients are stored in the `ds_clients` table, the messages queued for them
+/// in the `ds_messages` table and the latest `GroupInfo` of each group in the
+/// `ds_group_infos` table. The public state of the groups is stored with the
+/// [`SqliteStorageProvider`], which shares the connection, such that a
+/// transaction covers all tables.
+pub struct SqliteDsStorage {
+    groups: SqliteStorageProvider<JsonCodec, Connection>,
+}
+
+impl SqliteDsStorage {
+    /// Opens the database at `path` and creates the necessary tables if they
+    /// don't exist yet.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SqliteDsStorageError> {
+        let mut groups = SqliteStorageProvider::new(Connection::open(path)?);
+        groups
+            .run_migrations()
+            .map_err(|e| SqliteDsStorageError::Migration(e.to_string()))?;
+
+        groups.connection().execute_batch(
+            "CREATE TABLE IF NOT EXISTS ds_clients (
+                id BLOB PRIMARY KEY,
+                client_info BLOB NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS ds_messages (
+                sequence_number INTEGER PRIMARY KEY AUTOINCREMENT,
+                client_id BLOB NOT NULL,
+                welcome INTEGER NOT NULL,
+                message BLOB NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS ds_group_infos (
+                group_id BLOB PRIMARY KEY,
+                group_info BLOB NOT NULL
+            );",
+        )?;
+
+        Ok(Self { groups })
+    }
+
+    fn connection(&self) -> &Connection {
+        self.groups.connection()
+    }
+
+    fn enqueue(
+        &self,
+        client_id: &[u8],
+        welcome: bool,
+        msg: &MlsMessageIn,
+    ) -> Result<(), SqliteDsStorageError> {
+        self.connection().execute(
+            "INSERT INTO ds_messages (client_id, welcome, message) VALUES (?1, ?2, ?3)",
+            params![client_id, welcome, msg.tls_serialize_detached()?],
+        )?;
+        Ok(())
+    }
+}
+
+impl DsStorage for SqliteDsStorage {
+    type Error = SqliteDsStorageError;
+    type GroupStorage = SqliteStorageProvider<JsonCodec, Connection>;
+
+    fn begin_transaction(&mut self) -> Result<(), Self::Error> {
+        self.connection().execute_batch("BEGIN IMMEDIATE")?;
+        Ok(())
+    }
+
+    fn commit_transaction(&mut self) -> Result<(), Self::Error> {
+        self.connection().execute_batch("COMMIT")?;
+        Ok(())
+    }
+
+    fn rollback_transaction(&mut self) -> Result<(), Self::Error> {
+        self.connection().execute_batch("ROLLBACK")?;
+        Ok(())
+    }
+
+    fn client_ids(&self) -> Result<Vec<Vec<u8>>, Self::Error> {
+        let mut statement = self.connection().prepare("SELECT id FROM ds_clients")?;
+        let ids = statement
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<_, _>>()?;
+        Ok(ids)
+    }
+
+    fn read_client(&self, id: &[u8]) -> Result<Option<ClientInfo>, Self::Error> {
+        let client_info: Option<Vec<u8>> = self
+            .connection()
+            .query_row(
+                "SELECT client_info FROM ds_clients WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        client_info
+            .map(ClientInfo::tls_deserialize_exact)
+            .transpose()
+            .map_err(SqliteDsStorageError::from)
+    }
+
+    fn write_client(&mut self, client: &ClientInfo) -> Result<(), Self::Error> {
+        self.connection().execute(
+            "INSERT OR REPLACE INTO ds_clients (id, client_info) VALUES (?1, ?2)",
+            params![client.id, client.tls_serialize_detached()?],
+        )?;
+        Ok(())
+    }
+
+    fn enqueue_welcome(&mut self, client_id: &[u8], msg: &MlsMessageIn) -> Result<(), Self::Error> {
+        self.enqueue(client_id, true, msg)
+    }
+
+    fn enqueue_msg(&mut self, client_id: &[u8], msg: &MlsMessageIn) -> Result<(), Self::Error> {
+        self.enqueue(client_id, false, msg)
+    }
+
+    fn pending_msgs(&self, client_id: &[u8]) -> Result<Vec<QueuedMessage>, Self::Error> {
+        let mut statement = self.connection().prepare(
+            "SELECT sequence_number, message FROM ds_messages WHERE client_id = ?1
+            ORDER BY welcome DESC, sequence_number",
+        )?;
+        let rows = statement
+            .query_map(params![client_id], |row| {
+                Ok((row.get::<_, u64>(0)?, row.get::<_, Vec<u8>>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.into_iter()
+            .map(|(sequence_number, msg)| {
+                Ok(QueuedMessage {
+                    sequence_number,
+                    msg: MlsMessageIn::tls_deserialize_exact(msg)?,
+                })
+            })
+            .collect()
+    }
+
+    fn acknowledge(
+        &mut self,
+        client_id: &[u8],
+        sequence_numbers: &[u64],
+    ) -> Result<(), Self::Error> {
+        let mut statement = self
+            .connection()
+            .prepare("DELETE FROM ds_messages WHERE client_id = ?1 AND sequence_number = ?2")?;
+        for sequence_number in sequence_numbers {
+            statement.execute(params![client_id, sequence_number])?;
+        }
+        Ok(())
+    }
+
+    fn read_group_info(&self, group_id: &GroupId) -> Result<Option<MlsMessageIn>, Self::Error> {
+        let group_info: Option<Vec<u8>> = self
+            .connection()
+            .query_row(
+                "SELECT group_info FROM ds_group_infos WHERE group_id = ?1",
+                params![group_id.as_slice()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        group_info
+            .map(MlsMessageIn::tls_deserialize_exact)
+            .transpose()
+            .map_err(SqliteDsStorageError::from)
+    }
+
+    fn write_group_info(
+        &mut self,
+        group_id: &GroupId,
+        group_info: &MlsMessageIn,
+    ) -> Result<(), Self::Error> {
+        self.connection().execute(
+            "INSERT OR REPLACE INTO ds_group_infos (group_id, group_info) VALUES (?1, ?2)",
+            params![group_id.as_slice(), group_info.tls_serialize_detached()?],
+        )?;
+        Ok(())
+    }
+
+    fn delete_group_info(&mut self, group_id: &GroupId) -> Result<(), Self::Error> {
+        self.connection().execute(
+            "DELETE FROM ds_group_infos WHERE group_id = ?1",
+            params![group_id.as_slice()],
+        )?;
+        Ok(())
+    }
+
+    fn group_storage(&self) -> &Self::GroupStorage {
+        &self.groups
+    }
+
+    fn clear(&mut self) -> Result<(), Self::Error> {
+        self.connection().execute("DELETE FROM ds_clients", [])?;
+        self.connection().execute("DELETE FROM ds_messages", [])?;
+        self.connection()
+            .execute("DELETE FROM ds_group_infos", [])?;
+        for group_id in self.groups.group_ids::<GroupId>()? {
+            PublicGroup::delete(&self.groups, &group_id)?;
+        }
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@ use axum::{
     http::{Request, StatusCode},
 };
 use http_body_util::BodyExt;
+use openmls::treesync::RatchetTree;
 use openmls_basic_credential::SignatureKeyPair;
 use openmls_rust_crypto::OpenMlsRustCrypto;
 use openmls_traits::types::SignatureScheme;
@@ -42,26 +43,128 @@ async fn body_bytes(response: axum::response::Response) -> Bytes {
     response.into_body().collect().await.unwrap().to_bytes()
 }
 
-#[tokio::test]
-async fn test_list_clients() {
-    let app = app(Arc::new(DsData::default()));
-
-    // There is no client. So the response body is empty.
-    let req = Request::get("/clients/list").body(Body::empty()).unwrap();
+/// Register a client with a single key package.
+async fn register_client(
+    app: &Router,
+    ciphersuite: Ciphersuite,
+    credential_with_key: CredentialWithKey,
+    crypto: &OpenMlsRustCrypto,
+    signer: &SignatureKeyPair,
+) {
+    let key_package = generate_key_package(
+        ciphersuite,
+        credential_with_key,
+        Extensions::empty(),
+        crypto,
+        signer,
+    );
+    let body = RegisterClientRequest {
+        key_packages: ClientKeyPackages(
+            vec![(
+                key_package
+                    .key_package()
+                    .hash_ref(crypto.crypto())
+                    .unwrap()
+                    .as_slice()
+                    .to_vec()
+                    .into(),
+                KeyPackageIn::from(key_package),
+            )]
+            .into(),
+        ),
+    };
+    let req = Request::post("/clients/register")
+        .body(Body::from(body.tls_serialize_detached().unwrap()))
+        .unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
 
+/// Answer a fresh challenge of the DS for the client `client_id` for a request
+/// to `endpoint` with the given `body`.
+async fn client_auth(
+    app: &Router,
+    client_id: &[u8],
+    signer: &SignatureKeyPair,
+    endpoint: &str,
+    body: &[u8],
+) -> ClientAuth {
+    let path = "/clients/challenge/".to_owned()
+        + &base64::engine::general_purpose::URL_SAFE.encode(client_id);
+    let req = Request::get(&path).body(Body::empty()).unwrap();
     let response = app.clone().oneshot(req).await.unwrap();
     assert_eq!(response.status(), StatusCode::OK);
+    let challenge = AuthChallenge::tls_deserialize_exact(body_bytes(response).await).unwrap();
+    ClientAuth::new(
+        client_id.to_vec(),
+        challenge,
+        endpoint,
+        body,
+        &RustCrypto::default(),
+        signer,
+    )
+    .unwrap()
+}
 
-    let bytes = body_bytes(response).await;
-    let client_info =
-        TlsVecU32::<ClientInfo>::tls_deserialize(&mut bytes.as_ref()).expect("Invalid client list");
+/// Register a group with the DS as the client `client_id`, which authenticates
+/// with `signer`.
+async fn register_group(
+    app: &Router,
+    group_info: MlsMessageOut,
+    ratchet_tree: RatchetTree,
+    client_id: &[u8],
+    signer: &SignatureKeyPair,
+) -> axum::response::Response {
+    let group_info: MlsMessageIn = group_info.into();
+    let ratchet_tree: RatchetTreeIn = ratchet_tree.into();
+    let mut body = group_info.tls_serialize_detached().unwrap();
+    body.extend(ratchet_tree.tls_serialize_detached().unwrap());
+    let body = RegisterGroupRequest {
+        auth: client_auth(app, client_id, signer, "/groups/register", &body).await,
+        group_info,
+        ratchet_tree,
+    };
+    let req = Request::post("/groups/register")
+        .body(Body::from(body.tls_serialize_detached().unwrap()))
+        .unwrap();
+    app.clone().oneshot(req).await.unwrap()
+}
+
+/// Publish a group info with the DS as the client `client_id`, which
+/// authenticates with `signer`.
+async fn publish_group_info(
+    app: &Router,
+    group_info: MlsMessageIn,
+    client_id: &[u8],
+    signer: &SignatureKeyPair,
+) -> axum::response::Response {
+    let body = PublishGroupInfoRequest {
+        auth: client_auth(
+            app,
+            client_id,
+            signer,
+            "/groups/group_info",
+            &group_info.tls_serialize_detached().unwrap(),
+        )
+        .await,
+        group_info,
+    };
+    let req = Request::post("/groups/group_info")
+        .body(Body::from(body.tls_serialize_detached().unwrap()))
+        .unwrap();
+    app.clone().oneshot(req).await.unwrap()
+}
 
-    let expected = TlsVecU32::<ClientInfo>::new(vec![]);
+#[tokio::test]
+async fn test_list_clients() {
+    let app = app(Arc::new(DsData::new(MemoryDsStorage::default())));
 
-    assert_eq!(
-        client_info.tls_serialize_detached().unwrap(),
-        expected.tls_serialize_detached().unwrap()
-    );
+    // There is no client. So there are no challenges for it.
+    let path = "/clients/challenge/".to_owned()
+        + &base64::engine::general_purpose::URL_SAFE.encode("Client1");
+    let req = Request::get(&path).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
 
     // Add a client.
     let client_name = "Client1";
@@ -87,7 +190,7 @@ async fn test_list_clients() {
             .to_vec(),
         KeyPackageIn::from(client_key_package.clone()),
     )];
-    let mut client_data = ClientInfo::new(client_key_package.clone());
+    let client_data = ClientInfo::new(client_key_package.clone());
     let body = RegisterClientRequest {
         key_packages: ClientKeyPackages(
             client_key_package
@@ -105,12 +208,27 @@ async fn test_list_clients() {
 
     let response = app.clone().oneshot(req).await.unwrap();
     assert_eq!(response.status(), StatusCode::OK);
-    let response_body =
-        RegisterClientSuccessResponse::tls_deserialize_exact(body_bytes(response).await).unwrap();
-    client_data.auth_token = response_body.auth_token;
 
-    // There should be Client1 now.
+    // Listing clients requires authentication.
     let req = Request::get("/clients/list").body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // Client1 can't authenticate with another signature key.
+    let (_, other_signer) =
+        generate_credential(client_name.into(), SignatureScheme::from(ciphersuite));
+    let auth = client_auth(&app, &client_id, &other_signer, "/clients/list", &[]).await;
+    let req = Request::get("/clients/list")
+        .body(Body::from(auth.tls_serialize_detached().unwrap()))
+        .unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // There should be Client1 now.
+    let auth = client_auth(&app, &client_id, &signer, "/clients/list", &[]).await;
+    let req = Request::get("/clients/list")
+        .body(Body::from(auth.tls_serialize_detached().unwrap()))
+        .unwrap();
 
     let response = app.clone().oneshot(req).await.unwrap();
     assert_eq!(response.status(), StatusCode::OK);
@@ -126,10 +244,29 @@ async fn test_list_clients() {
         expected.tls_serialize_detached().unwrap()
     );
 
+    // Challenges can only be used once.
+    let req = Request::get("/clients/list")
+        .body(Body::from(auth.tls_serialize_detached().unwrap()))
+        .unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
     // Get Client1 key packages.
     let path = "/clients/key_packages/".to_owned()
-        + &base64::engine::general_purpose::URL_SAFE.encode(client_id);
-    let req = Request::get(&path).body(Body::empty()).unwrap();
+        + &base64::engine::general_purpose::URL_SAFE.encode(&client_id);
+
+    // An answer for another endpoint is rejected.
+    let auth = client_auth(&app, &client_id, &signer, "/clients/list", &[]).await;
+    let req = Request::get(&path)
+        .body(Body::from(auth.tls_serialize_detached().unwrap()))
+        .unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let auth = client_auth(&app, &client_id, &signer, &path, &[]).await;
+    let req = Request::get(&path)
+        .body(Body::from(auth.tls_serialize_detached().unwrap()))
+        .unwrap();
 
     let response = app.clone().oneshot(req).await.unwrap();
     assert_eq!(response.status(), StatusCode::OK);
@@ -151,8 +288,11 @@ async fn test_list_clients() {
 #[tokio::test]
 async fn test_group() {
     let crypto = &OpenMlsRustCrypto::default();
-    let mls_group_create_config = MlsGroupCreateConfig::default();
-    let app = app(Arc::new(DsData::default()));
+    // The DS can only validate handshake messages sent as `PublicMessage`s.
+    let mls_group_create_config = MlsGroupCreateConfig::builder()
+        .wire_format_policy(MIXED_PLAINTEXT_WIRE_FORMAT_POLICY)
+        .build();
+    let app = app(Arc::new(DsData::new(MemoryDsStorage::default())));
 
     // Add two clients.
     let clients = ["Client1", "Client2"];
@@ -160,7 +300,6 @@ async fn test_group() {
     let mut credentials_with_key = Vec::new();
     let mut signers = Vec::new();
     let mut client_ids = Vec::new();
-    let mut client_data_vec = Vec::new();
     for client_name in clients.iter() {
         let ciphersuite = Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519;
         let (credential_with_key, signer) = generate_credential(
@@ -184,7 +323,6 @@ async fn test_group() {
             client_key_package.clone().into(),
         );
 
-        let mut client_data = ClientInfo::new(vec![(client_key_packages.clone())]);
         key_packages.push(client_key_package);
 
         let id = credential_with_key.credential.serialized_content();
@@ -207,11 +345,6 @@ async fn test_group() {
             .unwrap();
         let response = app.clone().oneshot(req).await.unwrap();
         assert_eq!(response.status(), StatusCode::OK);
-        let response_body =
-            RegisterClientSuccessResponse::tls_deserialize_exact(body_bytes(response).await)
-                .unwrap();
-        client_data.auth_token = response_body.auth_token;
-        client_data_vec.push(client_data);
     }
 
     // Add an additional key package for Client2
@@ -246,8 +379,15 @@ async fn test_group() {
     let path = "/clients/key_packages/".to_string()
         + &base64::engine::general_purpose::URL_SAFE.encode(&client_ids[1]);
     let body = PublishKeyPackagesRequest {
+        auth: client_auth(
+            &app,
+            &client_ids[1],
+            &signers[1],
+            &path,
+            &ckp.tls_serialize_detached().unwrap(),
+        )
+        .await,
         key_packages: ckp,
-        auth_token: client_data_vec[1].auth_token.clone(),
     };
     let req = Request::post(&path)
         .body(Body::from(body.tls_serialize_detached().unwrap()))
@@ -257,11 +397,30 @@ async fn test_group() {
     let response = app.clone().oneshot(req).await.unwrap();
     assert_eq!(response.status(), StatusCode::OK);
 
+    // Client1 can't publish key packages for Client2.
+    let body = PublishKeyPackagesRequest {
+        auth: client_auth(
+            &app,
+            &client_ids[0],
+            &signers[0],
+            &path,
+            &body.key_packages.tls_serialize_detached().unwrap(),
+        )
+        .await,
+        key_packages: body.key_packages,
+    };
+    let req = Request::post(&path)
+        .body(Body::from(body.tls_serialize_detached().unwrap()))
+        .unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
     // Client1 creates MyFirstGroup
     let group_id = GroupId::from_slice(b"MyFirstGroup");
 
     let credential_with_key_1 = credentials_with_key.remove(0);
     let signer_1 = signers.remove(0);
+    let signer_2 = signers.remove(0);
     let mut group = MlsGroup::new_with_group_id(
         crypto,
         &signer_1,
@@ -271,12 +430,65 @@ async fn test_group() {
     )
     .expect("An unexpected error occurred.");
 
+    // Register the group with the DS
+    let group_info = group
+        .export_group_info(crypto.crypto(), &signer_1, false)
+        .unwrap();
+
+    // Client2 isn't in the group and can't register it.
+    let response = register_group(
+        &app,
+        group_info.clone(),
+        group.export_ratchet_tree(),
+        &client_ids[1],
+        &signer_2,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // Registering without a valid authentication fails.
+    let auth = client_auth(&app, &client_ids[0], &signer_1, "/groups/register", &[]).await;
+    let body = RegisterGroupRequest {
+        group_info: group_info.clone().into(),
+        ratchet_tree: group.export_ratchet_tree().into(),
+        auth,
+    };
+    let req = Request::post("/groups/register")
+        .body(Body::from(body.tls_serialize_detached().unwrap()))
+        .unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = register_group(
+        &app,
+        group_info.clone(),
+        group.export_ratchet_tree(),
+        &client_ids[0],
+        &signer_1,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Registering the group again fails.
+    let response = register_group(
+        &app,
+        group_info,
+        group.export_ratchet_tree(),
+        &client_ids[0],
+        &signer_1,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
     // === Client1 invites Client2 ===
     // First we need to reserve the key package for Client2 from the DS.
     let path = "/clients/key_package/".to_owned()
         + &base64::engine::general_purpose::URL_SAFE.encode(&client_ids[1]);
 
-    let req = Request::get(&path).body(Body::empty()).unwrap();
+    let auth = client_auth(&app, &client_ids[0], &signer_1, &path, &[]).await;
+    let req = Request::get(&path)
+        .body(Body::from(auth.tls_serialize_detached().unwrap()))
+        .unwrap();
 
     let response = app.clone().oneshot(req).await.unwrap();
     assert_eq!(response.status(), StatusCode::OK);
@@ -287,25 +499,85 @@ async fn test_group() {
 
     // With the key package we can invite Client2 (create proposal and merge it
     // locally.)
-    let (_out_messages, welcome_msg, _group_info) = group
+    let (out_messages, welcome_msg, _group_info) = group
         .add_members(crypto, &signer_1, &[client2_key_package.into()])
         .expect("Could not add member to group.");
+
+    // Send the commit to the group. The DS accepts it and moves to the next
+    // epoch.
+    let commit = GroupMessage::new(out_messages.into());
+    let body = SendMessageRequest {
+        auth: client_auth(
+            &app,
+            &client_ids[0],
+            &signer_1,
+            "/send/message",
+            &commit.tls_serialize_detached().unwrap(),
+        )
+        .await,
+        group_msg: commit,
+    };
+    let req = Request::post("/send/message")
+        .body(Body::from(body.tls_serialize_detached().unwrap()))
+        .unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The commit is stale now.
+    let body = SendMessageRequest {
+        auth: client_auth(
+            &app,
+            &client_ids[0],
+            &signer_1,
+            "/send/message",
+            &body.group_msg.tls_serialize_detached().unwrap(),
+        )
+        .await,
+        group_msg: body.group_msg,
+    };
+    let req = Request::post("/send/message")
+        .body(Body::from(body.tls_serialize_detached().unwrap()))
+        .unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
     group
         .merge_pending_commit(crypto)
         .expect("error merging pending commit");
 
     // Send welcome message for Client2
+    let body = SendWelcomeRequest {
+        welcome: welcome_msg.clone().into(),
+        auth: client_auth(
+            &app,
+            &client_ids[0],
+            &signer_1,
+            "/send/welcome",
+            &welcome_msg.tls_serialize_detached().unwrap(),
+        )
+        .await,
+    };
     let req = Request::post("/send/welcome")
-        .body(Body::from(welcome_msg.tls_serialize_detached().unwrap()))
+        .body(Body::from(body.tls_serialize_detached().unwrap()))
         .unwrap();
     let response = app.clone().oneshot(req).await.unwrap();
     assert_eq!(response.status(), StatusCode::OK);
 
+    // Client1 can't receive the messages of Client2.
+    let path = "/recv/".to_owned() + &base64::engine::general_purpose::URL_SAFE.encode(clients[1]);
+    let body = RecvMessageRequest {
+        auth: client_auth(&app, &client_ids[0], &signer_1, &path, &[]).await,
+    };
+    let req = Request::get(&path)
+        .body(Body::from(body.tls_serialize_detached().unwrap()))
+        .unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
     // There should be a welcome message now for Client2.
     let body = RecvMessageRequest {
-        auth_token: client_data_vec[1].auth_token.clone(),
+        auth: client_auth(&app, &client_ids[1], &signer_2, &path, &[]).await,
     };
-    let path = "/recv/".to_owned() + &base64::engine::general_purpose::URL_SAFE.encode(clients[1]);
     let req = Request::get(&path)
         .body(Body::from(body.tls_serialize_detached().unwrap()))
         .unwrap();
@@ -351,33 +623,106 @@ async fn test_group() {
 
     // === Client2 sends a message to the group ===
     let client2_message = b"Thanks for adding me Client1.";
-    let signer_2 = signers.remove(0);
     let out_messages = group_on_client2
         .create_message(crypto, &signer_2, client2_message)
         .unwrap();
 
+    // Client1 waits for messages from the DS
+    let poll_path =
+        "/poll/".to_owned() + &base64::engine::general_purpose::URL_SAFE.encode(clients[0]);
+    let poll_auth = |timeout_ms: u64| {
+        let (app, client_id, signer, poll_path) = (&app, &client_ids[0], &signer_1, &poll_path);
+        async move {
+            let body = timeout_ms.tls_serialize_detached().unwrap();
+            client_auth(app, client_id, signer, poll_path, &body).await
+        }
+    };
+    let poll = |auth, timeout_ms| {
+        let body = PollMessagesRequest { timeout_ms, auth };
+        let req = Request::get(&poll_path)
+            .body(Body::from(body.tls_serialize_detached().unwrap()))
+            .unwrap();
+        let app = app.clone();
+        async move {
+            let response = app.oneshot(req).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            TlsVecU16::<QueuedMessage>::tls_deserialize_exact(body_bytes(response).await)
+                .expect("Invalid message list")
+                .into_vec()
+        }
+    };
+    let pending_poll = tokio::spawn(poll(poll_auth(10_000).await, 10_000));
+
     // Send private_message to the group
-    let msg = GroupMessage::new(out_messages.into(), &client_ids);
+    let group_msg = GroupMessage::new(out_messages.into());
+    let body = SendMessageRequest {
+        auth: client_auth(
+            &app,
+            &client_ids[1],
+            &signer_2,
+            "/send/message",
+            &group_msg.tls_serialize_detached().unwrap(),
+        )
+        .await,
+        group_msg,
+    };
     let req = Request::post("/send/message")
-        .body(Body::from(msg.tls_serialize_detached().unwrap()))
+        .body(Body::from(body.tls_serialize_detached().unwrap()))
         .unwrap();
     let response = app.clone().oneshot(req).await.unwrap();
     assert_eq!(response.status(), StatusCode::OK);
 
-    // Client1 retrieves messages from the DS
+    // The sender doesn't get its own message back.
+    let path = "/recv/".to_owned() + &base64::engine::general_purpose::URL_SAFE.encode(clients[1]);
     let body = RecvMessageRequest {
-        auth_token: client_data_vec[0].auth_token.clone(),
+        auth: client_auth(&app, &client_ids[1], &signer_2, &path, &[]).await,
     };
-    let path = "/recv/".to_owned() + &base64::engine::general_purpose::URL_SAFE.encode(clients[0]);
     let req = Request::get(&path)
         .body(Body::from(body.tls_serialize_detached().unwrap()))
         .unwrap();
     let response = app.clone().oneshot(req).await.unwrap();
     assert_eq!(response.status(), StatusCode::OK);
-
     let bytes = body_bytes(response).await;
-    let mut messages = TlsVecU16::<MlsMessageIn>::tls_deserialize(&mut bytes.as_ref())
-        .expect("Invalid message list");
+    assert!(
+        TlsVecU16::<MlsMessageIn>::tls_deserialize(&mut bytes.as_ref())
+            .expect("Invalid message list")
+            .is_empty()
+    );
+
+    // The waiting poll returns the message.
+    let queued_messages = pending_poll.await.unwrap();
+    assert_eq!(queued_messages.len(), 1);
+
+    // Until it is acknowledged, the message is delivered again.
+    let redelivered = poll(poll_auth(0).await, 0).await;
+    assert_eq!(
+        redelivered.tls_serialize_detached().unwrap(),
+        queued_messages.tls_serialize_detached().unwrap()
+    );
+
+    let sequence_numbers: Vec<u64> = queued_messages.iter().map(|m| m.sequence_number).collect();
+    let path = "/ack/".to_owned() + &base64::engine::general_purpose::URL_SAFE.encode(clients[0]);
+    let body = AckMessagesRequest {
+        auth: client_auth(
+            &app,
+            &client_ids[0],
+            &signer_1,
+            &path,
+            &sequence_numbers.tls_serialize_detached().unwrap(),
+        )
+        .await,
+        sequence_numbers,
+    };
+    let req = Request::post(&path)
+        .body(Body::from(body.tls_serialize_detached().unwrap()))
+        .unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // There are no more messages now.
+    assert!(poll(poll_auth(10).await, 10).await.is_empty());
+
+    let mut messages: Vec<MlsMessageIn> = queued_messages.into_iter().map(|m| m.msg).collect();
 
     let mls_message = messages
         .iter()
@@ -407,3 +752,396 @@ async fn test_group() {
         panic!("Expected application message");
     }
 }
+
+#[tokio::test]
+async fn test_reject_invalid_commit() {
+    let crypto = &OpenMlsRustCrypto::default();
+    let ciphersuite = Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519;
+    let app = app(Arc::new(DsData::new(MemoryDsStorage::default())));
+
+    let (credential_with_key, signer) =
+        generate_credential(b"Client1".to_vec(), SignatureScheme::from(ciphersuite));
+    register_client(
+        &app,
+        ciphersuite,
+        credential_with_key.clone(),
+        crypto,
+        &signer,
+    )
+    .await;
+    let send = |group_msg: GroupMessage| {
+        let app = app.clone();
+        let signer = &signer;
+        async move {
+            let body = SendMessageRequest {
+                auth: client_auth(
+                    &app,
+                    b"Client1",
+                    signer,
+                    "/send/message",
+                    &group_msg.tls_serialize_detached().unwrap(),
+                )
+                .await,
+                group_msg,
+            };
+            let req = Request::post("/send/message")
+                .body(Body::from(body.tls_serialize_detached().unwrap()))
+                .unwrap();
+            app.oneshot(req).await.unwrap()
+        }
+    };
+    let mut group = MlsGroup::new(
+        crypto,
+        &signer,
+        &MlsGroupCreateConfig::builder()
+            .wire_format_policy(MIXED_PLAINTEXT_WIRE_FORMAT_POLICY)
+            .ciphersuite(ciphersuite)
+            .build(),
+        credential_with_key,
+    )
+    .unwrap();
+
+    let (commit, _welcome, _group_info) = group
+        .self_update(crypto, &signer, LeafNodeParameters::default())
+        .unwrap()
+        .into_contents();
+    let commit = GroupMessage::new(commit.into());
+
+    // The group isn't registered yet.
+    let response = send(commit.clone()).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // Register the group in the new epoch, such that the commit is stale.
+    group.merge_pending_commit(crypto).unwrap();
+    let response = register_group(
+        &app,
+        group
+            .export_group_info(crypto.crypto(), &signer, false)
+            .unwrap(),
+        group.export_ratchet_tree(),
+        b"Client1",
+        &signer,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = send(commit).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    // A commit signed by someone else is rejected.
+    let (_credential_with_key, other_signer) =
+        generate_credential(b"Client2".to_vec(), SignatureScheme::from(ciphersuite));
+    let (commit, _welcome, _group_info) = group
+        .self_update(crypto, &other_signer, LeafNodeParameters::default())
+        .unwrap()
+        .into_contents();
+    let response = send(GroupMessage::new(commit.into())).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_sqlite_persistence() {
+    let path = std::env::temp_dir().join(format!("openmls-ds-test-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let ciphersuite = Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519;
+    let crypto = &OpenMlsRustCrypto::default();
+    let (credential_with_key, signer) =
+        generate_credential("Client1".into(), SignatureScheme::from(ciphersuite));
+    let client_id = credential_with_key.credential.serialized_content().to_vec();
+    let client_key_package = generate_key_package(
+        ciphersuite,
+        credential_with_key.clone(),
+        Extensions::empty(),
+        crypto,
+        &signer,
+    );
+    let body = RegisterClientRequest {
+        key_packages: ClientKeyPackages(
+            vec![(
+                client_key_package
+                    .key_package()
+                    .hash_ref(crypto.crypto())
+                    .unwrap()
+                    .as_slice()
+                    .to_vec()
+                    .into(),
+                KeyPackageIn::from(client_key_package.clone()),
+            )]
+            .into(),
+        ),
+    };
+
+    // Register the client with a fresh database.
+    {
+        let storage = SqliteDsStorage::open(&path).unwrap();
+        let app = app(Arc::new(DsData::new(storage)));
+        let req = Request::post("/clients/register")
+            .body(Body::from(body.tls_serialize_detached().unwrap()))
+            .unwrap();
+        let response = app.oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    // After re-opening the database, the client and its key package are still
+    // there.
+    let storage = SqliteDsStorage::open(&path).unwrap();
+    let app = app(Arc::new(DsData::new(storage)));
+
+    let auth = client_auth(&app, &client_id, &signer, "/clients/list", &[]).await;
+    let req = Request::get("/clients/list")
+        .body(Body::from(auth.tls_serialize_detached().unwrap()))
+        .unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let client_ids = TlsVecU32::<Vec<u8>>::tls_deserialize_exact(body_bytes(response).await)
+        .expect("Invalid client list");
+    assert_eq!(client_ids.into_vec(), vec![client_id.clone()]);
+
+    let path_kps = "/clients/key_packages/".to_owned()
+        + &base64::engine::general_purpose::URL_SAFE.encode(&client_id);
+    let auth = client_auth(&app, &client_id, &signer, &path_kps, &[]).await;
+    let req = Request::get(&path_kps)
+        .body(Body::from(auth.tls_serialize_detached().unwrap()))
+        .unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let key_packages = ClientKeyPackages::tls_deserialize_exact(body_bytes(response).await)
+        .expect("Invalid key packages");
+    assert_eq!(key_packages, body.key_packages);
+
+    // Resetting the DS clears the database.
+    let req = Request::get("/reset")
+        .header("reset-key", "poc-reset-password")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The client is gone and can't authenticate anymore.
+    let path_challenge = "/clients/challenge/".to_owned()
+        + &base64::engine::general_purpose::URL_SAFE.encode(&client_id);
+    let req = Request::get(&path_challenge).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // After registering again, the client is the only one.
+    let req = Request::post("/clients/register")
+        .body(Body::from(body.tls_serialize_detached().unwrap()))
+        .unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let auth = client_auth(&app, &client_id, &signer, "/clients/list", &[]).await;
+    let req = Request::get("/clients/list")
+        .body(Body::from(auth.tls_serialize_detached().unwrap()))
+        .unwrap();
+    let response = app.oneshot(req).await.unwrap();
+    let client_ids = TlsVecU32::<Vec<u8>>::tls_deserialize_exact(body_bytes(response).await)
+        .expect("Invalid client list");
+    assert_eq!(client_ids.into_vec(), vec![client_id]);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_sqlite_message_queue() {
+    let path =
+        std::env::temp_dir().join(format!("openmls-ds-queue-test-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let ciphersuite = Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519;
+    let crypto = &OpenMlsRustCrypto::default();
+    let (credential_with_key, signer) =
+        generate_credential("Client1".into(), SignatureScheme::from(ciphersuite));
+    let client_id = credential_with_key.credential.serialized_content().to_vec();
+    let key_package = generate_key_package(
+        ciphersuite,
+        credential_with_key,
+        Extensions::empty(),
+        crypto,
+        &signer,
+    );
+    let msg = MlsMessageIn::from(MlsMessageOut::from(key_package.key_package().clone()));
+
+    // Queue a message and a welcome.
+    {
+        let mut storage = SqliteDsStorage::open(&path).unwrap();
+        storage.begin_transaction().unwrap();
+        storage.enqueue_msg(&client_id, &msg).unwrap();
+        storage.enqueue_welcome(&client_id, &msg).unwrap();
+        storage.commit_transaction().unwrap();
+
+        // A rolled back message is never queued.
+        storage.begin_transaction().unwrap();
+        storage.enqueue_msg(&client_id, &msg).unwrap();
+        storage.rollback_transaction().unwrap();
+    }
+
+    // After re-opening the database, both messages are still queued, the
+    // welcome first.
+    let mut storage = SqliteDsStorage::open(&path).unwrap();
+    let pending = storage.pending_msgs(&client_id).unwrap();
+    assert_eq!(pending.len(), 2);
+    assert!(pending[0].sequence_number > pending[1].sequence_number);
+    assert!(storage.pending_msgs(b"Client2").unwrap().is_empty());
+
+    // Acknowledged messages are gone.
+    storage.begin_transaction().unwrap();
+    storage
+        .acknowledge(&client_id, &[pending[1].sequence_number])
+        .unwrap();
+    storage.commit_transaction().unwrap();
+    let remaining = storage.pending_msgs(&client_id).unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].sequence_number, pending[0].sequence_number);
+
+    drop(storage);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_external_join() {
+    let crypto = &OpenMlsRustCrypto::default();
+    let mls_group_create_config = MlsGroupCreateConfig::builder()
+        .wire_format_policy(MIXED_PLAINTEXT_WIRE_FORMAT_POLICY)
+        .build();
+    let app = app(Arc::new(DsData::new(MemoryDsStorage::default())));
+    let ciphersuite = Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519;
+
+    let group_id = GroupId::from_slice(b"MyFirstGroup");
+    let path = "/groups/group_info/".to_owned()
+        + &base64::engine::general_purpose::URL_SAFE.encode(group_id.as_slice());
+    let get_group_info = || {
+        let req = Request::get(&path).body(Body::empty()).unwrap();
+        let app = app.clone();
+        async move { app.oneshot(req).await.unwrap() }
+    };
+
+    // There is no group info for an unknown group.
+    assert_eq!(get_group_info().await.status(), StatusCode::NOT_FOUND);
+
+    // Client1 creates and registers the group.
+    let (credential_with_key_1, signer_1) =
+        generate_credential(b"Client1".to_vec(), SignatureScheme::from(ciphersuite));
+    register_client(
+        &app,
+        ciphersuite,
+        credential_with_key_1.clone(),
+        crypto,
+        &signer_1,
+    )
+    .await;
+    let group = MlsGroup::new_with_group_id(
+        crypto,
+        &signer_1,
+        &mls_group_create_config,
+        group_id,
+        credential_with_key_1,
+    )
+    .unwrap();
+    let group_info = group
+        .export_group_info(crypto.crypto(), &signer_1, false)
+        .unwrap();
+    let response = register_group(
+        &app,
+        group_info,
+        group.export_ratchet_tree(),
+        b"Client1",
+        &signer_1,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The group info from the registration is published.
+    let response = get_group_info().await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let published = GroupInfoResponse::tls_deserialize_exact(body_bytes(response).await).unwrap();
+    let stale_group_info = published.group_info.clone();
+
+    // Client2 joins the group through an external commit.
+    let crypto_2 = &OpenMlsRustCrypto::default();
+    let (credential_with_key_2, signer_2) =
+        generate_credential(b"Client2".to_vec(), SignatureScheme::from(ciphersuite));
+    register_client(
+        &app,
+        ciphersuite,
+        credential_with_key_2.clone(),
+        crypto_2,
+        &signer_2,
+    )
+    .await;
+    let (group_2, commit_bundle) = MlsGroup::external_commit_builder()
+        .with_config(mls_group_create_config.join_config().clone())
+        .with_ratchet_tree(published.ratchet_tree)
+        .build_group(
+            crypto_2,
+            published.group_info.into_verifiable_group_info().unwrap(),
+            credential_with_key_2,
+        )
+        .unwrap()
+        .load_psks(crypto_2.storage())
+        .unwrap()
+        .create_group_info(true)
+        .build(crypto_2.rand(), crypto_2.crypto(), &signer_2, |_| true)
+        .unwrap()
+        .finalize(crypto_2)
+        .unwrap();
+    let (commit, _welcome, group_info) = commit_bundle.into_messages();
+    let group_msg = GroupMessage::new(commit.into()).with_group_info(group_info.unwrap().into());
+    let body = SendMessageRequest {
+        auth: client_auth(
+            &app,
+            b"Client2",
+            &signer_2,
+            "/send/message",
+            &group_msg.tls_serialize_detached().unwrap(),
+        )
+        .await,
+        group_msg,
+    };
+    let req = Request::post("/send/message")
+        .body(Body::from(body.tls_serialize_detached().unwrap()))
+        .unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The DS publishes the group info of the new epoch.
+    let response = get_group_info().await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let published = GroupInfoResponse::tls_deserialize_exact(body_bytes(response).await).unwrap();
+    assert_eq!(
+        published
+            .group_info
+            .into_verifiable_group_info()
+            .unwrap()
+            .epoch(),
+        group_2.epoch()
+    );
+    assert_eq!(
+        published.ratchet_tree.tls_serialize_detached().unwrap(),
+        RatchetTreeIn::from(group_2.export_ratchet_tree())
+            .tls_serialize_detached()
+            .unwrap()
+    );
+
+    // Group infos for old epochs are rejected.
+    let response = publish_group_info(&app, stale_group_info, b"Client1", &signer_1).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let group_info: MlsMessageIn = group_2
+        .export_group_info(crypto_2.crypto(), &signer_2, false)
+        .unwrap()
+        .into();
+
+    // Client2 can't authenticate with the signature key of Client1.
+    let response = publish_group_info(&app, group_info.clone(), b"Client2", &signer_1).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Members can't publish group infos signed by another member.
+    let response = publish_group_info(&app, group_info.clone(), b"Client1", &signer_1).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // Members can publish their own group infos for the current epoch.
+    let response = publish_group_info(&app, group_info, b"Client2", &signer_2).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
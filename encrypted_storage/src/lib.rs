@@ -0,0 +1,328 @@
+//! # Encrypted Storage
+//!
+//! This crate provides the [`EncryptedStorageProvider`], which wraps any
+//! [`StorageProvider`] and encrypts all values before they are handed to it.
+//!
+//! ## Usage
+//!
+//! The [`EncryptedStorageProvider`] is created from the wrapped storage
+//! provider, a crypto provider implementing [`OpenMlsCrypto`] and
+//! [`OpenMlsRand`], and a [`KeyEncryptionKey`] supplied by the application,
+//! e.g. from a key management service or the platform keystore. It can then be
+//! used like any other storage provider.
+//!
+//! ## Encryption
+//!
+//! Every value is serialized and encrypted with the AEAD of the current key
+//! under a fresh random nonce. The lookup keys, i.e. group ids, public keys,
+//! hash references, proposal references and PSK ids, are passed to the wrapped
+//! storage provider unchanged, so that it can still address the values.
+//!
+//! The ciphertext is bound to the kind of value and to its lookup key through
+//! the associated data, so that a value cannot be moved to a different slot in
+//! the storage without being detected.
+//!
+//! ## Key rotation
+//!
+//! Each ciphertext starts with the id of the key it was encrypted under. Keys
+//! that are no longer used for encryption can be kept for decryption with
+//! [`EncryptedStorageProvider::with_previous_key`], or moved there by
+//! [`EncryptedStorageProvider::rotate_key`].
+//!
+//! Values migrate to the current key as they are used: every write uses the
+//! current key, and a value that is read and was encrypted under a previous
+//! key is re-encrypted under the current key and written back. Loading a
+//! group, e.g. with `MlsGroup::load`, migrates all of its state. Once all
+//! values were migrated, a previous key can be dropped with
+//! [`EncryptedStorageProvider::retire_key`]. Values that are still encrypted
+//! under a retired key can't be read anymore.
+
+#[cfg(doc)]
+use openmls_traits::storage::StorageProvider;
+
+use openmls_traits::{
+    crypto::OpenMlsCrypto,
+    random::OpenMlsRand,
+    types::{AeadType, CryptoError},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use zeroize::Zeroizing;
+
+mod sealed;
+mod storage_provider;
+
+use sealed::Sealed;
+
+/// The length of the key id prefix of every ciphertext.
+const KEY_ID_LEN: usize = 4;
+
+/// A decrypted value, and the value encrypted under the current key if it was
+/// encrypted under a previous one.
+type Unsealed = (Zeroizing<Vec<u8>>, Option<Sealed>);
+
+/// A key that encrypts the values in the storage.
+pub struct KeyEncryptionKey {
+    id: u32,
+    algorithm: AeadType,
+    secret: Zeroizing<Vec<u8>>,
+}
+
+impl KeyEncryptionKey {
+    /// Create a new key with the given `id` for the AEAD `algorithm`.
+    ///
+    /// The `id` is stored with every ciphertext and must be unique among the
+    /// keys used with a storage. Returns [`CryptoError::InvalidLength`] if the
+    /// `secret` does not have the key size of the `algorithm`.
+    pub fn new(id: u32, algorithm: AeadType, secret: Vec<u8>) -> Result<Self, CryptoError> {
+        if secret.len() != algorithm.key_size() {
+            return Err(CryptoError::InvalidLength);
+        }
+
+        Ok(Self {
+            id,
+            algorithm,
+            secret: Zeroizing::new(secret),
+        })
+    }
+
+    /// Get the id of this key.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Get the AEAD algorithm of this key.
+    pub fn algorithm(&self) -> AeadType {
+        self.algorithm
+    }
+}
+
+impl std::fmt::Debug for KeyEncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyEncryptionKey")
+            .field("id", &self.id)
+            .field("algorithm", &self.algorithm)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Errors returned by the [`EncryptedStorageProvider`].
+#[derive(thiserror::Error, Debug)]
+pub enum EncryptedStorageError<E> {
+    /// The wrapped storage provider returned an error.
+    #[error("Storage error: {0}")]
+    Storage(E),
+    /// Encrypting or decrypting a value failed.
+    #[error("Error encrypting or decrypting a value: {0}")]
+    Crypto(CryptoError),
+    /// Serializing or deserializing a value failed.
+    #[error("Error serializing or deserializing a value: {0}")]
+    Serialization(#[from] serde_json::Error),
+    /// The value was encrypted under a key that is not known to the provider.
+    #[error("The value was encrypted under the unknown key {0}.")]
+    UnknownKey(u32),
+    /// The stored value is too short to be a ciphertext.
+    #[error("The stored value is malformed.")]
+    Malformed,
+}
+
+/// A storage provider that encrypts all values stored in the wrapped storage
+/// provider.
+///
+/// See the [crate documentation](crate) for details.
+pub struct EncryptedStorageProvider<Storage, Crypto> {
+    storage: Storage,
+    crypto: Crypto,
+    current_key: KeyEncryptionKey,
+    previous_keys: Vec<KeyEncryptionKey>,
+}
+
+impl<Storage, Crypto: OpenMlsCrypto + OpenMlsRand> EncryptedStorageProvider<Storage, Crypto> {
+    /// Create a new provider that encrypts the values stored in `storage`
+    /// under `key`.
+    pub fn new(storage: Storage, crypto: Crypto, key: KeyEncryptionKey) -> Self {
+        Self {
+            storage,
+            crypto,
+            current_key: key,
+            previous_keys: Vec::new(),
+        }
+    }
+
+    /// Add a key that is only used to decrypt values written before it was
+    /// replaced.
+    pub fn with_previous_key(mut self, key: KeyEncryptionKey) -> Self {
+        self.previous_keys.push(key);
+        self
+    }
+
+    /// Encrypt all future writes under `key`, and keep the current key for
+    /// decrypting the values written so far.
+    pub fn rotate_key(&mut self, key: KeyEncryptionKey) {
+        let previous_key = std::mem::replace(&mut self.current_key, key);
+        self.previous_keys.push(previous_key);
+    }
+
+    /// Drop the previous key with the given `id`, and return it if there was
+    /// one.
+    ///
+    /// Values that are still encrypted under the key can't be read anymore,
+    /// so it must only be retired once all values were migrated to the
+    /// current key. See the [crate documentation](crate) for details.
+    pub fn retire_key(&mut self, id: u32) -> Option<KeyEncryptionKey> {
+        let position = self.previous_keys.iter().position(|key| key.id == id)?;
+        Some(self.previous_keys.remove(position))
+    }
+
+    /// Get a reference to the wrapped storage provider.
+    pub fn storage(&self) -> &Storage {
+        &self.storage
+    }
+
+    /// Consume the provider and return the wrapped storage provider.
+    pub fn into_inner(self) -> Storage {
+        self.storage
+    }
+
+    /// Serialize and encrypt `value`, binding it to the `label` of its kind and
+    /// to its lookup `key`.
+    fn seal<E>(
+        &self,
+        label: &str,
+        key: &impl Serialize,
+        value: &impl Serialize,
+    ) -> Result<Sealed, EncryptedStorageError<E>> {
+        let plaintext = Zeroizing::new(serde_json::to_vec(value)?);
+        self.seal_plaintext(label, key, &plaintext)
+    }
+
+    /// Encrypt the serialized `plaintext` under the current key.
+    fn seal_plaintext<E>(
+        &self,
+        label: &str,
+        key: &impl Serialize,
+        plaintext: &[u8],
+    ) -> Result<Sealed, EncryptedStorageError<E>> {
+        let KeyEncryptionKey {
+            id,
+            algorithm,
+            secret,
+        } = &self.current_key;
+        let aad = serde_json::to_vec(&(label, key))?;
+        let nonce = self
+            .crypto
+            .random_vec(algorithm.nonce_size())
+            .map_err(|_| EncryptedStorageError::Crypto(CryptoError::InsufficientRandomness))?;
+        let ciphertext = self
+            .crypto
+            .aead_encrypt(*algorithm, secret, plaintext, &nonce, &aad)
+            .map_err(EncryptedStorageError::Crypto)?;
+
+        let mut sealed = Vec::with_capacity(KEY_ID_LEN + nonce.len() + ciphertext.len());
+        sealed.extend_from_slice(&id.to_be_bytes());
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(Sealed(sealed))
+    }
+
+    /// Decrypt a value written by [`Self::seal`] with the same `label` and
+    /// `key`.
+    ///
+    /// Returns the serialized plaintext, and the value encrypted under the
+    /// current key if it was encrypted under a previous one.
+    fn unseal<E>(
+        &self,
+        label: &str,
+        key: &impl Serialize,
+        sealed: &Sealed,
+    ) -> Result<Unsealed, EncryptedStorageError<E>> {
+        let (id, rest) = sealed
+            .0
+            .split_first_chunk::<KEY_ID_LEN>()
+            .ok_or(EncryptedStorageError::Malformed)?;
+        let id = u32::from_be_bytes(*id);
+        let KeyEncryptionKey {
+            algorithm, secret, ..
+        } = std::iter::once(&self.current_key)
+            .chain(&self.previous_keys)
+            .find(|key| key.id == id)
+            .ok_or(EncryptedStorageError::UnknownKey(id))?;
+        if rest.len() < algorithm.nonce_size() {
+            return Err(EncryptedStorageError::Malformed);
+        }
+        let (nonce, ciphertext) = rest.split_at(algorithm.nonce_size());
+        let aad = serde_json::to_vec(&(label, key))?;
+        let plaintext = Zeroizing::new(
+            self.crypto
+                .aead_decrypt(*algorithm, secret, ciphertext, nonce, &aad)
+                .map_err(EncryptedStorageError::Crypto)?,
+        );
+
+        let resealed = if id == self.current_key.id {
+            None
+        } else {
+            Some(self.seal_plaintext(label, key, &plaintext)?)
+        };
+        Ok((plaintext, resealed))
+    }
+
+    /// Decrypt and deserialize a value written by [`Self::seal`] with the same
+    /// `label` and `key`.
+    ///
+    /// If the value was encrypted under a previous key, it is re-encrypted
+    /// under the current key and handed to `write_back`, which writes it to
+    /// the wrapped storage provider in place of the old one.
+    fn open<E, V: DeserializeOwned>(
+        &self,
+        label: &str,
+        key: &impl Serialize,
+        sealed: Sealed,
+        write_back: impl FnOnce(&Sealed) -> Result<(), E>,
+    ) -> Result<V, EncryptedStorageError<E>> {
+        let (plaintext, resealed) = self.unseal(label, key, &sealed)?;
+        let value = serde_json::from_slice(&plaintext)?;
+        if let Some(resealed) = resealed {
+            write_back(&resealed).map_err(EncryptedStorageError::Storage)?;
+        }
+        Ok(value)
+    }
+
+    fn open_option<E, V: DeserializeOwned>(
+        &self,
+        label: &str,
+        key: &impl Serialize,
+        sealed: Option<Sealed>,
+        write_back: impl FnOnce(&Sealed) -> Result<(), E>,
+    ) -> Result<Option<V>, EncryptedStorageError<E>> {
+        sealed
+            .map(|sealed| self.open(label, key, sealed, write_back))
+            .transpose()
+    }
+
+    /// Decrypt and deserialize a list of values written by [`Self::seal`] with
+    /// the same `label` and `key`.
+    ///
+    /// If one of the values was encrypted under a previous key, the whole
+    /// list is handed to `write_back` with all values encrypted under the
+    /// current key, so that the order of the list is kept.
+    fn open_list<E, V: DeserializeOwned>(
+        &self,
+        label: &str,
+        key: &impl Serialize,
+        sealed: Vec<Sealed>,
+        write_back: impl FnOnce(&[Sealed]) -> Result<(), E>,
+    ) -> Result<Vec<V>, EncryptedStorageError<E>> {
+        let mut values = Vec::with_capacity(sealed.len());
+        let mut migrated = Vec::with_capacity(sealed.len());
+        let mut stale = false;
+        for sealed in sealed {
+            let (plaintext, resealed) = self.unseal(label, key, &sealed)?;
+            values.push(serde_json::from_slice(&plaintext)?);
+            stale |= resealed.is_some();
+            migrated.push(resealed.unwrap_or(sealed));
+        }
+        if stale {
+            write_back(&migrated).map_err(EncryptedStorageError::Storage)?;
+        }
+        Ok(values)
+    }
+}
@@ -0,0 +1,54 @@
+use openmls_traits::storage::{traits, Entity, CURRENT_VERSION};
+use serde::{Deserialize, Serialize};
+
+/// An encrypted value, as it is handed to the wrapped storage provider.
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+pub(crate) struct Sealed(pub(crate) Vec<u8>);
+
+impl Entity<CURRENT_VERSION> for Sealed {}
+impl traits::QueuedProposal<CURRENT_VERSION> for Sealed {}
+impl traits::TreeSync<CURRENT_VERSION> for Sealed {}
+impl traits::GroupContext<CURRENT_VERSION> for Sealed {}
+impl traits::InterimTranscriptHash<CURRENT_VERSION> for Sealed {}
+impl traits::ConfirmationTag<CURRENT_VERSION> for Sealed {}
+impl traits::SignatureKeyPair<CURRENT_VERSION> for Sealed {}
+impl traits::PskBundle<CURRENT_VERSION> for Sealed {}
+impl traits::HpkeKeyPair<CURRENT_VERSION> for Sealed {}
+impl traits::GroupState<CURRENT_VERSION> for Sealed {}
+impl traits::GroupEpochSecrets<CURRENT_VERSION> for Sealed {}
+impl traits::LeafNodeIndex<CURRENT_VERSION> for Sealed {}
+impl traits::MessageSecrets<CURRENT_VERSION> for Sealed {}
+impl traits::ResumptionPskStore<CURRENT_VERSION> for Sealed {}
+impl traits::KeyPackage<CURRENT_VERSION> for Sealed {}
+impl traits::MlsGroupJoinConfig<CURRENT_VERSION> for Sealed {}
+impl traits::LeafNode<CURRENT_VERSION> for Sealed {}
+impl traits::ApplicationExportTree<CURRENT_VERSION> for Sealed {}
+impl traits::FutureEpochMessages<CURRENT_VERSION> for Sealed {}
+#[cfg(feature = "virtual-clients-draft")]
+impl traits::VcEmulationEpochState<CURRENT_VERSION> for Sealed {}
+#[cfg(feature = "virtual-clients-draft")]
+impl traits::VcEmulationBindings<CURRENT_VERSION> for Sealed {}
+#[cfg(feature = "virtual-clients-draft")]
+impl traits::VcOperationTree<CURRENT_VERSION> for Sealed {}
+#[cfg(feature = "virtual-clients-draft")]
+impl traits::RetainedKeyPackageMaterial<CURRENT_VERSION> for Sealed {}
+
+/// A borrowed key that serializes like the key itself.
+///
+/// This is needed where the storage provider takes keys and values by slice.
+#[cfg(feature = "virtual-clients-draft")]
+#[derive(Serialize)]
+#[serde(transparent)]
+pub(crate) struct Borrowed<'a, K>(pub(crate) &'a K);
+
+#[cfg(feature = "virtual-clients-draft")]
+impl<K: openmls_traits::storage::Key<CURRENT_VERSION>> openmls_traits::storage::Key<CURRENT_VERSION>
+    for Borrowed<'_, K>
+{
+}
+#[cfg(feature = "virtual-clients-draft")]
+impl<K: traits::HashReference<CURRENT_VERSION>> traits::HashReference<CURRENT_VERSION>
+    for Borrowed<'_, K>
+{
+}
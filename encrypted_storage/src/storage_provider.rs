@@ -0,0 +1,1168 @@
+use openmls_traits::{
+    crypto::OpenMlsCrypto,
+    random::OpenMlsRand,
+    storage::{traits, MigrationLog, StorageProvider, CURRENT_VERSION},
+};
+
+use crate::{sealed::Sealed, EncryptedStorageError, EncryptedStorageProvider};
+
+#[cfg(feature = "virtual-clients-draft")]
+use crate::sealed::Borrowed;
+
+// The labels bind each ciphertext to the kind of value it holds.
+const JOIN_GROUP_CONFIG_LABEL: &str = "join_group_config";
+const OWN_LEAF_NODE_LABEL: &str = "own_leaf_node";
+const QUEUED_PROPOSAL_LABEL: &str = "queued_proposal";
+const TREE_LABEL: &str = "tree";
+const INTERIM_TRANSCRIPT_HASH_LABEL: &str = "interim_transcript_hash";
+const CONTEXT_LABEL: &str = "context";
+const CONFIRMATION_TAG_LABEL: &str = "confirmation_tag";
+const GROUP_STATE_LABEL: &str = "group_state";
+const MESSAGE_SECRETS_LABEL: &str = "message_secrets";
+const RESUMPTION_PSK_STORE_LABEL: &str = "resumption_psk_store";
+const OWN_LEAF_INDEX_LABEL: &str = "own_leaf_index";
+const GROUP_EPOCH_SECRETS_LABEL: &str = "group_epoch_secrets";
+const FUTURE_EPOCH_MESSAGES_LABEL: &str = "future_epoch_messages";
+#[cfg(feature = "extensions-draft")]
+const APPLICATION_EXPORT_TREE_LABEL: &str = "application_export_tree";
+#[cfg(feature = "virtual-clients-draft")]
+const VC_EMULATION_EPOCH_STATE_LABEL: &str = "vc_emulation_epoch_state";
+#[cfg(feature = "virtual-clients-draft")]
+const VC_EMULATION_BINDINGS_LABEL: &str = "vc_emulation_bindings";
+#[cfg(feature = "virtual-clients-draft")]
+const VC_OPERATION_TREE_LABEL: &str = "vc_operation_tree";
+#[cfg(feature = "virtual-clients-draft")]
+const RETAINED_KEY_PACKAGE_MATERIAL_LABEL: &str = "retained_key_package_material";
+const SIGNATURE_KEY_PAIR_LABEL: &str = "signature_key_pair";
+const ENCRYPTION_KEY_PAIR_LABEL: &str = "encryption_key_pair";
+const EPOCH_KEY_PAIR_LABEL: &str = "epoch_key_pair";
+const KEY_PACKAGE_LABEL: &str = "key_package";
+const PSK_LABEL: &str = "psk";
+
+impl<Storage, Crypto> EncryptedStorageProvider<Storage, Crypto>
+where
+    Storage: StorageProvider<CURRENT_VERSION>,
+{
+    /// Runs `write_back` in a transaction of the wrapped storage provider.
+    ///
+    /// Lists are migrated by deleting and rewriting them, so a write that
+    /// fails halfway would otherwise lose the values that were just read.
+    fn in_transaction(
+        &self,
+        write_back: impl FnOnce() -> Result<(), Storage::Error>,
+    ) -> Result<(), Storage::Error> {
+        self.storage.begin_transaction()?;
+        match write_back() {
+            Ok(()) => self.storage.commit_transaction(),
+            Err(e) => {
+                // The error of the write is returned even if the rollback
+                // fails, since it is the cause of the failure.
+                let _ = self.storage.rollback_transaction();
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<Storage, Crypto> StorageProvider<CURRENT_VERSION> for EncryptedStorageProvider<Storage, Crypto>
+where
+    Storage: StorageProvider<CURRENT_VERSION>,
+    Crypto: OpenMlsCrypto + OpenMlsRand,
+{
+    type Error = EncryptedStorageError<Storage::Error>;
+
+    fn begin_transaction(&self) -> Result<(), Self::Error> {
+        self.storage
+            .begin_transaction()
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn commit_transaction(&self) -> Result<(), Self::Error> {
+        self.storage
+            .commit_transaction()
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn rollback_transaction(&self) -> Result<(), Self::Error> {
+        self.storage
+            .rollback_transaction()
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_mls_join_config<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        config: &MlsGroupJoinConfig,
+    ) -> Result<(), Self::Error> {
+        let sealed = self.seal(JOIN_GROUP_CONFIG_LABEL, group_id, config)?;
+        self.storage
+            .write_mls_join_config(group_id, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn append_own_leaf_node<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNode: traits::LeafNode<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        leaf_node: &LeafNode,
+    ) -> Result<(), Self::Error> {
+        let sealed = self.seal(OWN_LEAF_NODE_LABEL, group_id, leaf_node)?;
+        self.storage
+            .append_own_leaf_node(group_id, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn queue_proposal<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+        QueuedProposal: traits::QueuedProposal<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+        proposal: &QueuedProposal,
+    ) -> Result<(), Self::Error> {
+        let sealed = self.seal(QUEUED_PROPOSAL_LABEL, &(group_id, proposal_ref), proposal)?;
+        self.storage
+            .queue_proposal(group_id, proposal_ref, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_tree<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        TreeSync: traits::TreeSync<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        tree: &TreeSync,
+    ) -> Result<(), Self::Error> {
+        let sealed = self.seal(TREE_LABEL, group_id, tree)?;
+        self.storage
+            .write_tree(group_id, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_interim_transcript_hash<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        InterimTranscriptHash: traits::InterimTranscriptHash<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        interim_transcript_hash: &InterimTranscriptHash,
+    ) -> Result<(), Self::Error> {
+        let sealed = self.seal(
+            INTERIM_TRANSCRIPT_HASH_LABEL,
+            group_id,
+            interim_transcript_hash,
+        )?;
+        self.storage
+            .write_interim_transcript_hash(group_id, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_context<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupContext: traits::GroupContext<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_context: &GroupContext,
+    ) -> Result<(), Self::Error> {
+        let sealed = self.seal(CONTEXT_LABEL, group_id, group_context)?;
+        self.storage
+            .write_context(group_id, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_confirmation_tag<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ConfirmationTag: traits::ConfirmationTag<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        confirmation_tag: &ConfirmationTag,
+    ) -> Result<(), Self::Error> {
+        let sealed = self.seal(CONFIRMATION_TAG_LABEL, group_id, confirmation_tag)?;
+        self.storage
+            .write_confirmation_tag(group_id, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_group_state<
+        GroupState: traits::GroupState<CURRENT_VERSION>,
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_state: &GroupState,
+    ) -> Result<(), Self::Error> {
+        let sealed = self.seal(GROUP_STATE_LABEL, group_id, group_state)?;
+        self.storage
+            .write_group_state(group_id, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_message_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MessageSecrets: traits::MessageSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        message_secrets: &MessageSecrets,
+    ) -> Result<(), Self::Error> {
+        let sealed = self.seal(MESSAGE_SECRETS_LABEL, group_id, message_secrets)?;
+        self.storage
+            .write_message_secrets(group_id, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_resumption_psk_store<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ResumptionPskStore: traits::ResumptionPskStore<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        resumption_psk_store: &ResumptionPskStore,
+    ) -> Result<(), Self::Error> {
+        let sealed = self.seal(RESUMPTION_PSK_STORE_LABEL, group_id, resumption_psk_store)?;
+        self.storage
+            .write_resumption_psk_store(group_id, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_own_leaf_index<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNodeIndex: traits::LeafNodeIndex<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        own_leaf_index: &LeafNodeIndex,
+    ) -> Result<(), Self::Error> {
+        let sealed = self.seal(OWN_LEAF_INDEX_LABEL, group_id, own_leaf_index)?;
+        self.storage
+            .write_own_leaf_index(group_id, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_group_epoch_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupEpochSecrets: traits::GroupEpochSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_epoch_secrets: &GroupEpochSecrets,
+    ) -> Result<(), Self::Error> {
+        let sealed = self.seal(GROUP_EPOCH_SECRETS_LABEL, group_id, group_epoch_secrets)?;
+        self.storage
+            .write_group_epoch_secrets(group_id, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn supports_future_epoch_messages(&self) -> bool {
+        self.storage.supports_future_epoch_messages()
+    }
+
+    fn write_future_epoch_messages<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        FutureEpochMessages: traits::FutureEpochMessages<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        future_epoch_messages: &FutureEpochMessages,
+    ) -> Result<(), Self::Error> {
+        let sealed = self.seal(FUTURE_EPOCH_MESSAGES_LABEL, group_id, future_epoch_messages)?;
+        self.storage
+            .write_future_epoch_messages(group_id, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    #[cfg(feature = "extensions-draft")]
+    fn write_application_export_tree<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ApplicationExportTree: traits::ApplicationExportTree<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        application_export_tree: &ApplicationExportTree,
+    ) -> Result<(), Self::Error> {
+        let sealed = self.seal(
+            APPLICATION_EXPORT_TREE_LABEL,
+            group_id,
+            application_export_tree,
+        )?;
+        self.storage
+            .write_application_export_tree(group_id, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn write_vc_emulation_epoch_state<
+        EpochId: traits::VcEpochId<CURRENT_VERSION>,
+        VcEmulationEpochState: traits::VcEmulationEpochState<CURRENT_VERSION>,
+    >(
+        &self,
+        epoch_id: &EpochId,
+        vc_emulation_epoch_state: &VcEmulationEpochState,
+    ) -> Result<(), Self::Error> {
+        let sealed = self.seal(
+            VC_EMULATION_EPOCH_STATE_LABEL,
+            epoch_id,
+            vc_emulation_epoch_state,
+        )?;
+        self.storage
+            .write_vc_emulation_epoch_state(epoch_id, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn write_vc_emulation_bindings<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        VcEmulationBindings: traits::VcEmulationBindings<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        bindings: &VcEmulationBindings,
+    ) -> Result<(), Self::Error> {
+        let sealed = self.seal(VC_EMULATION_BINDINGS_LABEL, group_id, bindings)?;
+        self.storage
+            .write_vc_emulation_bindings(group_id, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn write_vc_operation_tree<
+        EpochId: traits::VcEpochId<CURRENT_VERSION>,
+        VcOperationTree: traits::VcOperationTree<CURRENT_VERSION>,
+    >(
+        &self,
+        epoch_id: &EpochId,
+        vc_operation_tree: &VcOperationTree,
+    ) -> Result<(), Self::Error> {
+        let sealed = self.seal(VC_OPERATION_TREE_LABEL, epoch_id, vc_operation_tree)?;
+        self.storage
+            .write_vc_operation_tree(epoch_id, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn write_retained_key_package_material_batch<
+        EpochId: traits::VcEpochId<CURRENT_VERSION>,
+        VcOperationTree: traits::VcOperationTree<CURRENT_VERSION>,
+        KeyPackageRef: traits::HashReference<CURRENT_VERSION>,
+        RetainedKeyPackageMaterial: traits::RetainedKeyPackageMaterial<CURRENT_VERSION>,
+    >(
+        &self,
+        epoch_id: &EpochId,
+        operation_tree: &VcOperationTree,
+        materials: &[(KeyPackageRef, RetainedKeyPackageMaterial)],
+    ) -> Result<(), Self::Error> {
+        let sealed_operation_tree = self.seal(VC_OPERATION_TREE_LABEL, epoch_id, operation_tree)?;
+        let sealed_materials = materials
+            .iter()
+            .map(|(hash_ref, material)| {
+                let sealed = self.seal(RETAINED_KEY_PACKAGE_MATERIAL_LABEL, hash_ref, material)?;
+                Ok((Borrowed(hash_ref), sealed))
+            })
+            .collect::<Result<Vec<_>, Self::Error>>()?;
+        self.storage
+            .write_retained_key_package_material_batch(
+                epoch_id,
+                &sealed_operation_tree,
+                &sealed_materials,
+            )
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION>,
+        SignatureKeyPair: traits::SignatureKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+        signature_key_pair: &SignatureKeyPair,
+    ) -> Result<(), Self::Error> {
+        let sealed = self.seal(SIGNATURE_KEY_PAIR_LABEL, public_key, signature_key_pair)?;
+        self.storage
+            .write_signature_key_pair(public_key, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_encryption_key_pair<
+        EncryptionKey: traits::EncryptionKey<CURRENT_VERSION>,
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+        key_pair: &HpkeKeyPair,
+    ) -> Result<(), Self::Error> {
+        let sealed = self.seal(ENCRYPTION_KEY_PAIR_LABEL, public_key, key_pair)?;
+        self.storage
+            .write_encryption_key_pair(public_key, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        EpochKey: traits::EpochKey<CURRENT_VERSION>,
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+        key_pairs: &[HpkeKeyPair],
+    ) -> Result<(), Self::Error> {
+        let key = (group_id, epoch, leaf_index);
+        let sealed = key_pairs
+            .iter()
+            .map(|key_pair| self.seal(EPOCH_KEY_PAIR_LABEL, &key, key_pair))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.storage
+            .write_encryption_epoch_key_pairs(group_id, epoch, leaf_index, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_key_package<
+        HashReference: traits::HashReference<CURRENT_VERSION>,
+        KeyPackage: traits::KeyPackage<CURRENT_VERSION>,
+    >(
+        &self,
+        hash_ref: &HashReference,
+        key_package: &KeyPackage,
+    ) -> Result<(), Self::Error> {
+        let sealed = self.seal(KEY_PACKAGE_LABEL, hash_ref, key_package)?;
+        self.storage
+            .write_key_package(hash_ref, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_psk<
+        PskId: traits::PskId<CURRENT_VERSION>,
+        PskBundle: traits::PskBundle<CURRENT_VERSION>,
+    >(
+        &self,
+        psk_id: &PskId,
+        psk: &PskBundle,
+    ) -> Result<(), Self::Error> {
+        let sealed = self.seal(PSK_LABEL, psk_id, psk)?;
+        self.storage
+            .write_psk(psk_id, &sealed)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn mls_group_join_config<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<MlsGroupJoinConfig>, Self::Error> {
+        let sealed: Option<Sealed> = self
+            .storage
+            .mls_group_join_config(group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_option(JOIN_GROUP_CONFIG_LABEL, group_id, sealed, |sealed| {
+            self.storage.write_mls_join_config(group_id, sealed)
+        })
+    }
+
+    fn own_leaf_nodes<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNode: traits::LeafNode<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<LeafNode>, Self::Error> {
+        let sealed: Vec<Sealed> = self
+            .storage
+            .own_leaf_nodes(group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_list(OWN_LEAF_NODE_LABEL, group_id, sealed, |sealed| {
+            self.in_transaction(|| {
+                self.storage.delete_own_leaf_nodes(group_id)?;
+                sealed
+                    .iter()
+                    .try_for_each(|sealed| self.storage.append_own_leaf_node(group_id, sealed))
+            })
+        })
+    }
+
+    fn queued_proposal_refs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<ProposalRef>, Self::Error> {
+        self.storage
+            .queued_proposal_refs(group_id)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn queued_proposals<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+        QueuedProposal: traits::QueuedProposal<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<(ProposalRef, QueuedProposal)>, Self::Error> {
+        let sealed: Vec<(ProposalRef, Sealed)> = self
+            .storage
+            .queued_proposals(group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        let mut proposals = Vec::with_capacity(sealed.len());
+        let mut migrated = Vec::with_capacity(sealed.len());
+        let mut stale = false;
+        for (proposal_ref, sealed) in sealed {
+            let (plaintext, resealed) =
+                self.unseal(QUEUED_PROPOSAL_LABEL, &(group_id, &proposal_ref), &sealed)?;
+            proposals.push((proposal_ref, serde_json::from_slice(&plaintext)?));
+            stale |= resealed.is_some();
+            migrated.push(resealed.unwrap_or(sealed));
+        }
+
+        // Requeue all proposals if one of them was migrated, so that their
+        // order is kept.
+        if stale {
+            self.in_transaction(|| {
+                for (proposal_ref, _) in &proposals {
+                    self.storage.remove_proposal(group_id, proposal_ref)?;
+                }
+                for ((proposal_ref, _), sealed) in proposals.iter().zip(&migrated) {
+                    self.storage
+                        .queue_proposal(group_id, proposal_ref, sealed)?;
+                }
+                Ok(())
+            })
+            .map_err(EncryptedStorageError::Storage)?;
+        }
+
+        Ok(proposals)
+    }
+
+    fn tree<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        TreeSync: traits::TreeSync<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<TreeSync>, Self::Error> {
+        let sealed: Option<Sealed> = self
+            .storage
+            .tree(group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_option(TREE_LABEL, group_id, sealed, |sealed| {
+            self.storage.write_tree(group_id, sealed)
+        })
+    }
+
+    fn group_context<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupContext: traits::GroupContext<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupContext>, Self::Error> {
+        let sealed: Option<Sealed> = self
+            .storage
+            .group_context(group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_option(CONTEXT_LABEL, group_id, sealed, |sealed| {
+            self.storage.write_context(group_id, sealed)
+        })
+    }
+
+    fn interim_transcript_hash<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        InterimTranscriptHash: traits::InterimTranscriptHash<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<InterimTranscriptHash>, Self::Error> {
+        let sealed: Option<Sealed> = self
+            .storage
+            .interim_transcript_hash(group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_option(INTERIM_TRANSCRIPT_HASH_LABEL, group_id, sealed, |sealed| {
+            self.storage.write_interim_transcript_hash(group_id, sealed)
+        })
+    }
+
+    fn confirmation_tag<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ConfirmationTag: traits::ConfirmationTag<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<ConfirmationTag>, Self::Error> {
+        let sealed: Option<Sealed> = self
+            .storage
+            .confirmation_tag(group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_option(CONFIRMATION_TAG_LABEL, group_id, sealed, |sealed| {
+            self.storage.write_confirmation_tag(group_id, sealed)
+        })
+    }
+
+    fn group_state<
+        GroupState: traits::GroupState<CURRENT_VERSION>,
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupState>, Self::Error> {
+        let sealed: Option<Sealed> = self
+            .storage
+            .group_state(group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_option(GROUP_STATE_LABEL, group_id, sealed, |sealed| {
+            self.storage.write_group_state(group_id, sealed)
+        })
+    }
+
+    fn message_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MessageSecrets: traits::MessageSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<MessageSecrets>, Self::Error> {
+        let sealed: Option<Sealed> = self
+            .storage
+            .message_secrets(group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_option(MESSAGE_SECRETS_LABEL, group_id, sealed, |sealed| {
+            self.storage.write_message_secrets(group_id, sealed)
+        })
+    }
+
+    fn resumption_psk_store<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ResumptionPskStore: traits::ResumptionPskStore<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<ResumptionPskStore>, Self::Error> {
+        let sealed: Option<Sealed> = self
+            .storage
+            .resumption_psk_store(group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_option(RESUMPTION_PSK_STORE_LABEL, group_id, sealed, |sealed| {
+            self.storage.write_resumption_psk_store(group_id, sealed)
+        })
+    }
+
+    fn own_leaf_index<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNodeIndex: traits::LeafNodeIndex<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<LeafNodeIndex>, Self::Error> {
+        let sealed: Option<Sealed> = self
+            .storage
+            .own_leaf_index(group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_option(OWN_LEAF_INDEX_LABEL, group_id, sealed, |sealed| {
+            self.storage.write_own_leaf_index(group_id, sealed)
+        })
+    }
+
+    fn group_epoch_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupEpochSecrets: traits::GroupEpochSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupEpochSecrets>, Self::Error> {
+        let sealed: Option<Sealed> = self
+            .storage
+            .group_epoch_secrets(group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_option(GROUP_EPOCH_SECRETS_LABEL, group_id, sealed, |sealed| {
+            self.storage.write_group_epoch_secrets(group_id, sealed)
+        })
+    }
+
+    fn future_epoch_messages<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        FutureEpochMessages: traits::FutureEpochMessages<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<FutureEpochMessages>, Self::Error> {
+        let sealed: Option<Sealed> = self
+            .storage
+            .future_epoch_messages(group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_option(FUTURE_EPOCH_MESSAGES_LABEL, group_id, sealed, |sealed| {
+            self.storage.write_future_epoch_messages(group_id, sealed)
+        })
+    }
+
+    fn signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION>,
+        SignatureKeyPair: traits::SignatureKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> Result<Option<SignatureKeyPair>, Self::Error> {
+        let sealed: Option<Sealed> = self
+            .storage
+            .signature_key_pair(public_key)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_option(SIGNATURE_KEY_PAIR_LABEL, public_key, sealed, |sealed| {
+            self.storage.write_signature_key_pair(public_key, sealed)
+        })
+    }
+
+    fn encryption_key_pair<
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+        EncryptionKey: traits::EncryptionKey<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> Result<Option<HpkeKeyPair>, Self::Error> {
+        let sealed: Option<Sealed> = self
+            .storage
+            .encryption_key_pair(public_key)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_option(ENCRYPTION_KEY_PAIR_LABEL, public_key, sealed, |sealed| {
+            self.storage.write_encryption_key_pair(public_key, sealed)
+        })
+    }
+
+    fn encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        EpochKey: traits::EpochKey<CURRENT_VERSION>,
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> Result<Vec<HpkeKeyPair>, Self::Error> {
+        let key = (group_id, epoch, leaf_index);
+        let sealed: Vec<Sealed> = self
+            .storage
+            .encryption_epoch_key_pairs(group_id, epoch, leaf_index)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_list(EPOCH_KEY_PAIR_LABEL, &key, sealed, |sealed| {
+            self.storage
+                .write_encryption_epoch_key_pairs(group_id, epoch, leaf_index, sealed)
+        })
+    }
+
+    fn key_package<
+        KeyPackageRef: traits::HashReference<CURRENT_VERSION>,
+        KeyPackage: traits::KeyPackage<CURRENT_VERSION>,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<Option<KeyPackage>, Self::Error> {
+        let sealed: Option<Sealed> = self
+            .storage
+            .key_package(hash_ref)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_option(KEY_PACKAGE_LABEL, hash_ref, sealed, |sealed| {
+            self.storage.write_key_package(hash_ref, sealed)
+        })
+    }
+
+    fn psk<PskBundle: traits::PskBundle<CURRENT_VERSION>, PskId: traits::PskId<CURRENT_VERSION>>(
+        &self,
+        psk_id: &PskId,
+    ) -> Result<Option<PskBundle>, Self::Error> {
+        let sealed: Option<Sealed> = self
+            .storage
+            .psk(psk_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_option(PSK_LABEL, psk_id, sealed, |sealed| {
+            self.storage.write_psk(psk_id, sealed)
+        })
+    }
+
+    #[cfg(feature = "extensions-draft")]
+    fn application_export_tree<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ApplicationExportTree: traits::ApplicationExportTree<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<ApplicationExportTree>, Self::Error> {
+        let sealed: Option<Sealed> = self
+            .storage
+            .application_export_tree(group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_option(APPLICATION_EXPORT_TREE_LABEL, group_id, sealed, |sealed| {
+            self.storage.write_application_export_tree(group_id, sealed)
+        })
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn vc_emulation_epoch_state<
+        EpochId: traits::VcEpochId<CURRENT_VERSION>,
+        VcEmulationEpochState: traits::VcEmulationEpochState<CURRENT_VERSION>,
+    >(
+        &self,
+        epoch_id: &EpochId,
+    ) -> Result<Option<VcEmulationEpochState>, Self::Error> {
+        let sealed: Option<Sealed> = self
+            .storage
+            .vc_emulation_epoch_state(epoch_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_option(VC_EMULATION_EPOCH_STATE_LABEL, epoch_id, sealed, |sealed| {
+            self.storage
+                .write_vc_emulation_epoch_state(epoch_id, sealed)
+        })
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn vc_emulation_bindings<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        VcEmulationBindings: traits::VcEmulationBindings<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<VcEmulationBindings>, Self::Error> {
+        let sealed: Option<Sealed> = self
+            .storage
+            .vc_emulation_bindings(group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_option(VC_EMULATION_BINDINGS_LABEL, group_id, sealed, |sealed| {
+            self.storage.write_vc_emulation_bindings(group_id, sealed)
+        })
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn vc_operation_tree<
+        EpochId: traits::VcEpochId<CURRENT_VERSION>,
+        VcOperationTree: traits::VcOperationTree<CURRENT_VERSION>,
+    >(
+        &self,
+        epoch_id: &EpochId,
+    ) -> Result<Option<VcOperationTree>, Self::Error> {
+        let sealed: Option<Sealed> = self
+            .storage
+            .vc_operation_tree(epoch_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_option(VC_OPERATION_TREE_LABEL, epoch_id, sealed, |sealed| {
+            self.storage.write_vc_operation_tree(epoch_id, sealed)
+        })
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn retained_key_package_material<
+        KeyPackageRef: traits::HashReference<CURRENT_VERSION>,
+        RetainedKeyPackageMaterial: traits::RetainedKeyPackageMaterial<CURRENT_VERSION>,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<Option<RetainedKeyPackageMaterial>, Self::Error> {
+        let sealed: Option<Sealed> = self
+            .storage
+            .retained_key_package_material(hash_ref)
+            .map_err(EncryptedStorageError::Storage)?;
+        // The materials are only written together with the operation tree of
+        // their epoch, so they are migrated when that is rewritten.
+        self.open_option(
+            RETAINED_KEY_PACKAGE_MATERIAL_LABEL,
+            hash_ref,
+            sealed,
+            |_| Ok(()),
+        )
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn has_retained_key_package_material_for_epoch<EpochId: traits::VcEpochId<CURRENT_VERSION>>(
+        &self,
+        epoch_id: &EpochId,
+    ) -> Result<bool, Self::Error> {
+        self.storage
+            .has_retained_key_package_material_for_epoch(epoch_id)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn remove_proposal<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .remove_proposal(group_id, proposal_ref)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_own_leaf_nodes<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_own_leaf_nodes(group_id)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_group_config<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_group_config(group_id)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_tree<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_tree(group_id)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_confirmation_tag<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_confirmation_tag(group_id)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_group_state<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_group_state(group_id)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_context<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_context(group_id)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_interim_transcript_hash<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_interim_transcript_hash(group_id)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_message_secrets<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_message_secrets(group_id)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_all_resumption_psk_secrets<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_all_resumption_psk_secrets(group_id)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_own_leaf_index<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_own_leaf_index(group_id)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_group_epoch_secrets<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_group_epoch_secrets(group_id)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_future_epoch_messages<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_future_epoch_messages(group_id)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn clear_proposal_queue<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .clear_proposal_queue::<GroupId, ProposalRef>(group_id)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_signature_key_pair(public_key)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_encryption_key_pair<EncryptionKey: traits::EncryptionKey<CURRENT_VERSION>>(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_encryption_key_pair(public_key)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        EpochKey: traits::EpochKey<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_encryption_epoch_key_pairs(group_id, epoch, leaf_index)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_key_package<KeyPackageRef: traits::HashReference<CURRENT_VERSION>>(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_key_package(hash_ref)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_psk<PskKey: traits::PskId<CURRENT_VERSION>>(
+        &self,
+        psk_id: &PskKey,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_psk(psk_id)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    #[cfg(feature = "extensions-draft")]
+    fn delete_application_export_tree<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ApplicationExportTree: traits::ApplicationExportTree<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_application_export_tree::<GroupId, Sealed>(group_id)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn delete_vc_emulation_state_if_unreferenced<EpochId: traits::VcEpochId<CURRENT_VERSION>>(
+        &self,
+        epoch_id: &EpochId,
+    ) -> Result<bool, Self::Error> {
+        self.storage
+            .delete_vc_emulation_state_if_unreferenced(epoch_id)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn delete_vc_emulation_bindings<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_vc_emulation_bindings(group_id)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn delete_retained_key_package_material<
+        KeyPackageRef: traits::HashReference<CURRENT_VERSION>,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_retained_key_package_material(hash_ref)
+            .map_err(EncryptedStorageError::Storage)
+    }
+}
+
+// The migration records hold no secrets, so they are passed through unencrypted.
+impl<Storage, Crypto> MigrationLog<CURRENT_VERSION> for EncryptedStorageProvider<Storage, Crypto>
+where
+    Storage: MigrationLog<CURRENT_VERSION>,
+    Crypto: OpenMlsCrypto + OpenMlsRand,
+{
+    fn group_migrated<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+        name: &str,
+    ) -> Result<bool, Self::Error> {
+        self.storage
+            .group_migrated(group_id, name)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn record_group_migration<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+        name: &str,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .record_group_migration(group_id, name)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn key_package_migrated<KeyPackageRef: traits::HashReference<CURRENT_VERSION>>(
+        &self,
+        hash_ref: &KeyPackageRef,
+        name: &str,
+    ) -> Result<bool, Self::Error> {
+        self.storage
+            .key_package_migrated(hash_ref, name)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn record_key_package_migration<KeyPackageRef: traits::HashReference<CURRENT_VERSION>>(
+        &self,
+        hash_ref: &KeyPackageRef,
+        name: &str,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .record_key_package_migration(hash_ref, name)
+            .map_err(EncryptedStorageError::Storage)
+    }
+}
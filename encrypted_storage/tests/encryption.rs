@@ -0,0 +1,275 @@
+use openmls_encrypted_storage::{
+    EncryptedStorageError, EncryptedStorageProvider, KeyEncryptionKey,
+};
+use openmls_memory_storage::{MemoryStorage, MemoryStorageError};
+use openmls_rust_crypto::RustCrypto;
+use openmls_traits::{
+    storage::{traits, Entity, Key, StorageProvider, CURRENT_VERSION},
+    types::{AeadType, CryptoError},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct TestGroupId(Vec<u8>);
+impl Key<CURRENT_VERSION> for TestGroupId {}
+impl traits::GroupId<CURRENT_VERSION> for TestGroupId {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+struct TestProposalRef(usize);
+impl Key<CURRENT_VERSION> for TestProposalRef {}
+impl Entity<CURRENT_VERSION> for TestProposalRef {}
+impl traits::ProposalRef<CURRENT_VERSION> for TestProposalRef {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct TestEpochKey(Vec<u8>);
+impl Key<CURRENT_VERSION> for TestEpochKey {}
+impl traits::EpochKey<CURRENT_VERSION> for TestEpochKey {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct TestBlob(Vec<u8>);
+impl Entity<CURRENT_VERSION> for TestBlob {}
+impl traits::TreeSync<CURRENT_VERSION> for TestBlob {}
+impl traits::QueuedProposal<CURRENT_VERSION> for TestBlob {}
+impl traits::HpkeKeyPair<CURRENT_VERSION> for TestBlob {}
+impl traits::LeafNode<CURRENT_VERSION> for TestBlob {}
+
+fn key(id: u32, byte: u8) -> KeyEncryptionKey {
+    KeyEncryptionKey::new(id, AeadType::ChaCha20Poly1305, vec![byte; 32]).unwrap()
+}
+
+fn provider(key: KeyEncryptionKey) -> EncryptedStorageProvider<MemoryStorage, RustCrypto> {
+    EncryptedStorageProvider::new(MemoryStorage::default(), RustCrypto::default(), key)
+}
+
+#[test]
+fn roundtrip() {
+    let storage = provider(key(1, 0x11));
+    let group_id = TestGroupId(b"group".to_vec());
+    let tree = TestBlob(b"secret tree".to_vec());
+    let proposals = vec![
+        (TestProposalRef(2), TestBlob(b"secret proposal 2".to_vec())),
+        (TestProposalRef(1), TestBlob(b"secret proposal 1".to_vec())),
+    ];
+    let epoch = TestEpochKey(b"epoch".to_vec());
+    let key_pairs = vec![
+        TestBlob(b"secret key pair a".to_vec()),
+        TestBlob(b"secret key pair b".to_vec()),
+    ];
+
+    storage.write_tree(&group_id, &tree).unwrap();
+    for (proposal_ref, proposal) in &proposals {
+        storage
+            .queue_proposal(&group_id, proposal_ref, proposal)
+            .unwrap();
+    }
+    storage
+        .write_encryption_epoch_key_pairs(&group_id, &epoch, 3, &key_pairs)
+        .unwrap();
+
+    let tree_read: Option<TestBlob> = storage.tree(&group_id).unwrap();
+    assert_eq!(Some(tree), tree_read);
+    let proposals_read: Vec<(TestProposalRef, TestBlob)> =
+        storage.queued_proposals(&group_id).unwrap();
+    assert_eq!(proposals, proposals_read);
+    let proposal_refs_read: Vec<TestProposalRef> = storage.queued_proposal_refs(&group_id).unwrap();
+    assert_eq!(
+        vec![TestProposalRef(2), TestProposalRef(1)],
+        proposal_refs_read
+    );
+    let key_pairs_read: Vec<TestBlob> = storage
+        .encryption_epoch_key_pairs(&group_id, &epoch, 3)
+        .unwrap();
+    assert_eq!(key_pairs, key_pairs_read);
+
+    // None of the values is stored in plaintext.
+    let values = storage.storage().values.read().unwrap();
+    let plaintext = serde_json::to_vec(b"secret").unwrap();
+    let plaintext = &plaintext[1..plaintext.len() - 1];
+    for value in values.values() {
+        assert!(!value
+            .windows(plaintext.len())
+            .any(|window| window == plaintext));
+    }
+}
+
+#[test]
+fn key_rotation() {
+    let mut storage = provider(key(1, 0x11));
+    let old_group_id = TestGroupId(b"old group".to_vec());
+    let new_group_id = TestGroupId(b"new group".to_vec());
+    let old_tree = TestBlob(b"old tree".to_vec());
+    let new_tree = TestBlob(b"new tree".to_vec());
+
+    storage.write_tree(&old_group_id, &old_tree).unwrap();
+    storage.rotate_key(key(2, 0x22));
+    storage.write_tree(&new_group_id, &new_tree).unwrap();
+
+    // Without the previous key, only the value written after the rotation can
+    // be read.
+    let memory_storage = storage.into_inner();
+    let storage =
+        EncryptedStorageProvider::new(memory_storage, RustCrypto::default(), key(2, 0x22));
+    let new_tree_read: Option<TestBlob> = storage.tree(&new_group_id).unwrap();
+    assert_eq!(Some(new_tree), new_tree_read);
+    let error = storage.tree::<_, TestBlob>(&old_group_id).unwrap_err();
+    assert!(matches!(error, EncryptedStorageError::UnknownKey(1)));
+
+    // Registering the previous key makes the old value readable again.
+    let memory_storage = storage.into_inner();
+    let storage =
+        EncryptedStorageProvider::new(memory_storage, RustCrypto::default(), key(2, 0x22))
+            .with_previous_key(key(1, 0x11));
+    let old_tree_read: Option<TestBlob> = storage.tree(&old_group_id).unwrap();
+    assert_eq!(Some(old_tree), old_tree_read);
+}
+
+/// The id of the key the raw `value` was encrypted under.
+fn key_id(value: &[u8]) -> u32 {
+    u32::from_be_bytes(value[..4].try_into().unwrap())
+}
+
+#[test]
+fn values_migrate_on_read() {
+    let mut storage = provider(key(1, 0x11));
+    let group_id = TestGroupId(b"group".to_vec());
+    let tree = TestBlob(b"tree".to_vec());
+    let proposals = vec![
+        (TestProposalRef(2), TestBlob(b"proposal 2".to_vec())),
+        (TestProposalRef(1), TestBlob(b"proposal 1".to_vec())),
+    ];
+    let epoch = TestEpochKey(b"epoch".to_vec());
+    let key_pairs = vec![
+        TestBlob(b"key pair a".to_vec()),
+        TestBlob(b"key pair b".to_vec()),
+    ];
+
+    storage.write_tree(&group_id, &tree).unwrap();
+    for (proposal_ref, proposal) in &proposals {
+        storage
+            .queue_proposal(&group_id, proposal_ref, proposal)
+            .unwrap();
+    }
+    storage
+        .write_encryption_epoch_key_pairs(&group_id, &epoch, 3, &key_pairs)
+        .unwrap();
+    storage.rotate_key(key(2, 0x22));
+
+    // Reading the values re-encrypts them under the current key.
+    let tree_read: Option<TestBlob> = storage.tree(&group_id).unwrap();
+    assert_eq!(Some(tree.clone()), tree_read);
+    let proposals_read: Vec<(TestProposalRef, TestBlob)> =
+        storage.queued_proposals(&group_id).unwrap();
+    assert_eq!(proposals, proposals_read);
+    let key_pairs_read: Vec<TestBlob> = storage
+        .encryption_epoch_key_pairs(&group_id, &epoch, 3)
+        .unwrap();
+    assert_eq!(key_pairs, key_pairs_read);
+
+    let raw: Raw = storage.storage().tree(&group_id).unwrap().unwrap();
+    assert_eq!(2, key_id(&raw.0));
+
+    // Once migrated, the previous key can be retired and the values can still
+    // be read in the same order.
+    assert_eq!(1, storage.retire_key(1).unwrap().id());
+    assert!(storage.retire_key(1).is_none());
+    let tree_read: Option<TestBlob> = storage.tree(&group_id).unwrap();
+    assert_eq!(Some(tree), tree_read);
+    let proposals_read: Vec<(TestProposalRef, TestBlob)> =
+        storage.queued_proposals(&group_id).unwrap();
+    assert_eq!(proposals, proposals_read);
+    let key_pairs_read: Vec<TestBlob> = storage
+        .encryption_epoch_key_pairs(&group_id, &epoch, 3)
+        .unwrap();
+    assert_eq!(key_pairs, key_pairs_read);
+}
+
+#[test]
+fn failed_migration_keeps_values() {
+    let mut storage = provider(key(1, 0x11));
+    let group_id = TestGroupId(b"group".to_vec());
+    let leaf_nodes = vec![
+        TestBlob(b"leaf node a".to_vec()),
+        TestBlob(b"leaf node b".to_vec()),
+    ];
+    let proposals = vec![
+        (TestProposalRef(2), TestBlob(b"proposal 2".to_vec())),
+        (TestProposalRef(1), TestBlob(b"proposal 1".to_vec())),
+    ];
+
+    for leaf_node in &leaf_nodes {
+        storage.append_own_leaf_node(&group_id, leaf_node).unwrap();
+    }
+    for (proposal_ref, proposal) in &proposals {
+        storage
+            .queue_proposal(&group_id, proposal_ref, proposal)
+            .unwrap();
+    }
+    storage.rotate_key(key(2, 0x22));
+
+    // Lists are migrated by deleting and rewriting them. If a write fails
+    // after the values were deleted, the read fails but the values are kept.
+    storage.storage().fail_after_writes(1);
+    let error = storage
+        .own_leaf_nodes::<_, TestBlob>(&group_id)
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        EncryptedStorageError::Storage(MemoryStorageError::InjectedFailure)
+    ));
+    storage.storage().fail_after_writes(1);
+    let error = storage
+        .queued_proposals::<_, TestProposalRef, TestBlob>(&group_id)
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        EncryptedStorageError::Storage(MemoryStorageError::InjectedFailure)
+    ));
+
+    let leaf_nodes_read: Vec<TestBlob> = storage.own_leaf_nodes(&group_id).unwrap();
+    assert_eq!(leaf_nodes, leaf_nodes_read);
+    let proposals_read: Vec<(TestProposalRef, TestBlob)> =
+        storage.queued_proposals(&group_id).unwrap();
+    assert_eq!(proposals, proposals_read);
+
+    // The values were migrated by the second read.
+    assert_eq!(1, storage.retire_key(1).unwrap().id());
+    let leaf_nodes_read: Vec<TestBlob> = storage.own_leaf_nodes(&group_id).unwrap();
+    assert_eq!(leaf_nodes, leaf_nodes_read);
+    let proposals_read: Vec<(TestProposalRef, TestBlob)> =
+        storage.queued_proposals(&group_id).unwrap();
+    assert_eq!(proposals, proposals_read);
+}
+
+/// The ciphertext as stored in the wrapped storage provider.
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+struct Raw(Vec<u8>);
+impl Entity<CURRENT_VERSION> for Raw {}
+impl traits::TreeSync<CURRENT_VERSION> for Raw {}
+
+#[test]
+fn moved_values_are_rejected() {
+    let storage = provider(key(1, 0x11));
+    let group_a = TestGroupId(b"group a".to_vec());
+    let group_b = TestGroupId(b"group b".to_vec());
+
+    storage
+        .write_tree(&group_a, &TestBlob(b"tree a".to_vec()))
+        .unwrap();
+
+    // Copy the ciphertext of group a's tree to group b.
+    let raw: Raw = storage.storage().tree(&group_a).unwrap().unwrap();
+    storage.storage().write_tree(&group_b, &raw).unwrap();
+
+    let error = storage.tree::<_, TestBlob>(&group_b).unwrap_err();
+    assert!(matches!(
+        error,
+        EncryptedStorageError::Crypto(CryptoError::AeadDecryptionError)
+    ));
+}
+
+#[test]
+fn invalid_key_length() {
+    let error = KeyEncryptionKey::new(1, AeadType::Aes256Gcm, vec![0; 16]).unwrap_err();
+    assert_eq!(CryptoError::InvalidLength, error);
+}
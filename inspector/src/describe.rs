@@ -0,0 +1,283 @@
+//! JSON representations of the decoded structures.
+//!
+//! Byte strings, such as keys and hashes, are rendered as
+//! lowercase hex. Enums without a public payload are rendered with their
+//! `Debug` representation.
+
+use openmls::{
+    credentials::{BasicCredential, Credential, CredentialType},
+    extensions::{AnyObject, Extension, ExtensionType, Extensions},
+    framing::{PrivateMessageIn, PublicMessageIn},
+    key_packages::KeyPackageIn,
+    messages::{
+        group_info::VerifiableGroupInfo,
+        proposals_in::{ProposalIn, ProposalOrRefIn},
+        Welcome,
+    },
+    prelude::Capabilities,
+    treesync::{LeafNodeIn, LeafNodeSource, NodeIn, ParentNode, RatchetTreeIn},
+};
+use serde_json::{json, Value};
+
+pub(crate) fn public_message(message: &PublicMessageIn) -> Value {
+    let mut description = json!({
+        "group_id": hex::encode(message.group_id().as_slice()),
+        "epoch": message.epoch().as_u64(),
+        "sender": sender(message.sender()),
+        "content_type": format!("{:?}", message.content_type()),
+        "authenticated_data": hex::encode(message.authenticated_data()),
+    });
+    if let Some(proposal) = message.unverified_proposal() {
+        description["proposal"] = self::proposal(proposal);
+    }
+    if let Some(proposals) = message.unverified_committed_proposals() {
+        description["proposals"] = proposals.iter().map(proposal_or_ref).collect();
+    }
+    if let Some(leaf_node) = message.unverified_path_leaf_node() {
+        description["update_path_leaf_node"] = self::leaf_node(leaf_node);
+    }
+    description
+}
+
+pub(crate) fn private_message(message: &PrivateMessageIn) -> Value {
+    json!({
+        "group_id": hex::encode(message.group_id().as_slice()),
+        "epoch": message.epoch().as_u64(),
+        "content_type": format!("{:?}", message.content_type()),
+        "authenticated_data": hex::encode(message.aad()),
+    })
+}
+
+pub(crate) fn welcome(welcome: &Welcome) -> Value {
+    let new_members: Vec<_> = welcome
+        .secrets()
+        .iter()
+        .map(|secrets| hex::encode(secrets.new_member().as_slice()))
+        .collect();
+    json!({
+        "ciphersuite": format!("{:?}", welcome.ciphersuite()),
+        "new_member_key_package_refs": new_members,
+    })
+}
+
+pub(crate) fn group_info(group_info: &VerifiableGroupInfo) -> Value {
+    json!({
+        "group_id": hex::encode(group_info.group_id().as_slice()),
+        "epoch": group_info.epoch().as_u64(),
+        "ciphersuite": format!("{:?}", group_info.ciphersuite()),
+        "signer": group_info.signer().u32(),
+        "extensions": extensions(group_info.extensions().iter()),
+    })
+}
+
+pub(crate) fn key_package(key_package: &KeyPackageIn) -> Value {
+    let mut description = json!({
+        "ciphersuite": format!("{:?}", key_package.ciphersuite()),
+        "init_key": hex::encode(key_package.hpke_init_key().as_slice()),
+        "last_resort": key_package.extensions().contains(ExtensionType::LastResort),
+        "extensions": any_extensions(key_package.extensions()),
+        "leaf_node": leaf_node(key_package.leaf_node()),
+    });
+    if let LeafNodeSource::KeyPackage(lifetime) = key_package.leaf_node().leaf_node_source() {
+        description["lifetime"] = json!({
+            "not_before": lifetime.not_before(),
+            "not_after": lifetime.not_after(),
+        });
+    }
+    description
+}
+
+pub(crate) fn ratchet_tree(ratchet_tree: &RatchetTreeIn) -> Value {
+    let nodes: Vec<_> = ratchet_tree
+        .nodes_with_blanks()
+        .enumerate()
+        .map(|(index, node)| {
+            let mut description = match node {
+                None => json!({ "type": "blank" }),
+                Some(NodeIn::LeafNode(leaf_node)) => {
+                    json!({ "type": "leaf", "leaf_node": self::leaf_node(leaf_node) })
+                }
+                Some(NodeIn::ParentNode(parent_node)) => {
+                    json!({ "type": "parent", "parent_node": self::parent_node(parent_node) })
+                }
+            };
+            description["index"] = json!(index);
+            description
+        })
+        .collect();
+    json!({ "nodes": nodes })
+}
+
+fn sender(sender: &openmls::framing::Sender) -> Value {
+    serde_json::to_value(sender).unwrap_or_else(|_| json!(format!("{sender:?}")))
+}
+
+fn proposal_or_ref(proposal_or_ref: &ProposalOrRefIn) -> Value {
+    match proposal_or_ref {
+        ProposalOrRefIn::Proposal(proposal) => self::proposal(proposal),
+        ProposalOrRefIn::Reference(reference) => json!({
+            "type": "Reference",
+            "reference": hex::encode(reference.as_slice()),
+        }),
+    }
+}
+
+fn proposal(proposal: &ProposalIn) -> Value {
+    let proposal_type = format!("{:?}", proposal.proposal_type());
+    match proposal {
+        ProposalIn::Add(add) => json!({
+            "type": proposal_type,
+            "key_package": key_package(add.unverified_key_package()),
+        }),
+        ProposalIn::Update(update) => json!({
+            "type": proposal_type,
+            "leaf_node": leaf_node(update.unverified_leaf_node()),
+        }),
+        ProposalIn::Remove(remove) => json!({
+            "type": proposal_type,
+            "removed": remove.removed().u32(),
+        }),
+        ProposalIn::GroupContextExtensions(group_context_extensions) => json!({
+            "type": proposal_type,
+            "extensions": any_extensions(group_context_extensions.unverified_extensions()),
+        }),
+        ProposalIn::SelfRemove => json!({ "type": proposal_type }),
+        ProposalIn::Custom(custom) => json!({
+            "type": proposal_type,
+            "payload": hex::encode(custom.payload()),
+        }),
+        other => json!({
+            "type": proposal_type,
+            "content": format!("{other:?}"),
+        }),
+    }
+}
+
+fn leaf_node(leaf_node: &LeafNodeIn) -> Value {
+    let source = match leaf_node.leaf_node_source() {
+        LeafNodeSource::KeyPackage(lifetime) => json!({
+            "type": "key_package",
+            "not_before": lifetime.not_before(),
+            "not_after": lifetime.not_after(),
+        }),
+        LeafNodeSource::Update => json!({ "type": "update" }),
+        LeafNodeSource::Commit(parent_hash) => json!({
+            "type": "commit",
+            "parent_hash": hex::encode(parent_hash.as_slice()),
+        }),
+    };
+    json!({
+        "encryption_key": hex::encode(leaf_node.encryption_key().as_slice()),
+        "signature_key": hex::encode(leaf_node.signature_key().as_slice()),
+        "credential": credential(leaf_node.credential()),
+        "capabilities": capabilities(leaf_node.capabilities()),
+        "source": source,
+        "extensions": extensions(leaf_node.extensions().iter()),
+    })
+}
+
+fn parent_node(parent_node: &ParentNode) -> Value {
+    let unmerged_leaves: Vec<_> = parent_node
+        .unmerged_leaves()
+        .iter()
+        .map(|leaf| leaf.u32())
+        .collect();
+    json!({
+        "encryption_key": hex::encode(parent_node.encryption_key().as_slice()),
+        "parent_hash": hex::encode(parent_node.parent_hash()),
+        "unmerged_leaves": unmerged_leaves,
+    })
+}
+
+fn credential(credential: &Credential) -> Value {
+    let mut description = json!({
+        "type": format!("{:?}", credential.credential_type()),
+    });
+    match credential.credential_type() {
+        CredentialType::Basic => match BasicCredential::try_from(credential.clone()) {
+            Ok(basic) => {
+                description["identity"] = json!(String::from_utf8_lossy(basic.identity()));
+                description["identity_hex"] = json!(hex::encode(basic.identity()));
+            }
+            Err(_) => {
+                description["content"] = json!(hex::encode(credential.serialized_content()));
+            }
+        },
+        _ => description["content"] = json!(hex::encode(credential.serialized_content())),
+    }
+    description
+}
+
+fn capabilities(capabilities: &Capabilities) -> Value {
+    fn debug_list<T: std::fmt::Debug>(items: &[T]) -> Vec<String> {
+        items.iter().map(|item| format!("{item:?}")).collect()
+    }
+
+    let ciphersuites: Vec<_> = capabilities
+        .ciphersuites()
+        .iter()
+        .map(|ciphersuite| format!("0x{:04x}", ciphersuite.value()))
+        .collect();
+    json!({
+        "versions": debug_list(capabilities.versions()),
+        "ciphersuites": ciphersuites,
+        "extensions": debug_list(capabilities.extensions()),
+        "proposals": debug_list(capabilities.proposals()),
+        "credentials": debug_list(capabilities.credentials()),
+    })
+}
+
+fn any_extensions(extensions: &Extensions<AnyObject>) -> Value {
+    self::extensions(extensions.iter())
+}
+
+fn extensions<'a>(extensions: impl Iterator<Item = &'a Extension>) -> Value {
+    extensions.map(extension).collect()
+}
+
+fn extension(extension: &Extension) -> Value {
+    let extension_type = format!("{:?}", extension.extension_type());
+    match extension {
+        Extension::ApplicationId(application_id) => json!({
+            "type": extension_type,
+            "application_id": hex::encode(application_id.as_slice()),
+        }),
+        Extension::RatchetTree(ratchet_tree) => json!({
+            "type": extension_type,
+            "ratchet_tree": self::ratchet_tree(ratchet_tree.ratchet_tree()),
+        }),
+        Extension::RequiredCapabilities(required_capabilities) => json!({
+            "type": extension_type,
+            "extension_types": format!("{:?}", required_capabilities.extension_types()),
+            "proposal_types": format!("{:?}", required_capabilities.proposal_types()),
+            "credential_types": format!("{:?}", required_capabilities.credential_types()),
+        }),
+        Extension::ExternalPub(external_pub) => json!({
+            "type": extension_type,
+            "external_pub": hex::encode(external_pub.external_pub().as_slice()),
+        }),
+        Extension::ExternalSenders(external_senders) => {
+            let senders: Vec<_> = external_senders
+                .iter()
+                .map(|sender| {
+                    json!({
+                        "credential": credential(sender.credential()),
+                        "signature_key": hex::encode(sender.signature_key().as_slice()),
+                    })
+                })
+                .collect();
+            json!({
+                "type": extension_type,
+                "external_senders": senders,
+            })
+        }
+        Extension::Unknown(_, unknown) => json!({
+            "type": extension_type,
+            "content": hex::encode(&unknown.0),
+        }),
+        other => json!({
+            "type": extension_type,
+            "content": format!("{other:?}"),
+        }),
+    }
+}
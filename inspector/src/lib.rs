@@ -0,0 +1,161 @@
+//! # MLS Inspector
+//!
+//! Decodes MLS messages and structures, renders them as JSON and verifies
+//! their signatures where possible.
+//!
+//! The input is decoded with the TLS codec of OpenMLS, i.e. exactly as
+//! OpenMLS would decode it when receiving it. Decoded structures are not
+//! validated before they are rendered, so that malformed or forged inputs can
+//! be inspected, too. Instead, the output contains a separate `verification`
+//! entry with the result of verifying the input:
+//!
+//! * Key packages are validated, including the signatures of the key package
+//!   and its leaf node.
+//! * Group infos are verified against the ratchet tree, which is taken from
+//!   the ratchet tree extension or the [`VerificationContext`]. This also
+//!   validates the ratchet tree, i.e. all leaf node signatures and parent
+//!   hashes.
+//! * Ratchet trees are validated against the group info in the
+//!   [`VerificationContext`].
+//! * The signatures of public messages are verified with the signature key of
+//!   the sender in the group described by the group info and ratchet tree in
+//!   the [`VerificationContext`]. The membership tag of messages from members
+//!   is verified if the context has the membership key of the group. The
+//!   content of the message isn't validated against the group.
+//!
+//! Private messages and Welcome messages are encrypted and can't be verified
+//! without the keys of a member.
+
+mod describe;
+mod verify;
+
+use openmls::{
+    framing::{MlsMessageBodyIn, MlsMessageIn},
+    key_packages::KeyPackageIn,
+    messages::{group_info::VerifiableGroupInfo, Welcome},
+    treesync::RatchetTreeIn,
+};
+use serde_json::{json, Value};
+use thiserror::Error;
+use tls_codec::Deserialize;
+
+pub use verify::{Verification, VerificationContext};
+
+/// The MLS structure that is expected in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum InputKind {
+    /// An `MLSMessage` with any wire format.
+    Message,
+    /// A `KeyPackage` that is not wrapped in an `MLSMessage`.
+    KeyPackage,
+    /// A `Welcome` that is not wrapped in an `MLSMessage`.
+    Welcome,
+    /// A `GroupInfo` that is not wrapped in an `MLSMessage`.
+    GroupInfo,
+    /// A ratchet tree, as exported by `MlsGroup::export_ratchet_tree()`.
+    RatchetTree,
+}
+
+/// Errors when inspecting an input.
+#[derive(Error, Debug)]
+pub enum InspectError {
+    /// The input can't be decoded as the expected structure.
+    #[error("Error decoding the input: {0}")]
+    Decode(#[from] tls_codec::Error),
+    /// The input is an `MLSMessage`, but doesn't contain a group info.
+    #[error("The MLS message doesn't contain a group info.")]
+    NotAGroupInfo,
+}
+
+/// Decode `input` as `kind`, and return its JSON representation together with
+/// the result of its verification.
+pub fn inspect(
+    kind: InputKind,
+    input: &[u8],
+    context: &VerificationContext,
+) -> Result<Value, InspectError> {
+    let inspection = match kind {
+        InputKind::Message => {
+            let message = MlsMessageIn::tls_deserialize_exact(input)?;
+            let wire_format = format!("{:?}", message.wire_format());
+            let (kind, description, verification) = match message.extract() {
+                MlsMessageBodyIn::PublicMessage(public_message) => (
+                    "public_message",
+                    describe::public_message(&public_message),
+                    context.verify_public_message(public_message),
+                ),
+                MlsMessageBodyIn::PrivateMessage(private_message) => (
+                    "private_message",
+                    describe::private_message(&private_message),
+                    Verification::skipped("private messages are encrypted"),
+                ),
+                MlsMessageBodyIn::Welcome(welcome) => (
+                    "welcome",
+                    describe::welcome(&welcome),
+                    Verification::skipped("welcome messages are encrypted"),
+                ),
+                MlsMessageBodyIn::GroupInfo(group_info) => (
+                    "group_info",
+                    describe::group_info(&group_info),
+                    context.verify_group_info(group_info),
+                ),
+                MlsMessageBodyIn::KeyPackage(key_package) => (
+                    "key_package",
+                    describe::key_package(&key_package),
+                    verify::key_package(key_package),
+                ),
+            };
+            json!({
+                "wire_format": wire_format,
+                kind: description,
+                "verification": verification,
+            })
+        }
+        InputKind::KeyPackage => {
+            let key_package = KeyPackageIn::tls_deserialize_exact(input)?;
+            json!({
+                "key_package": describe::key_package(&key_package),
+                "verification": verify::key_package(key_package),
+            })
+        }
+        InputKind::Welcome => {
+            let welcome = Welcome::tls_deserialize_exact(input)?;
+            json!({
+                "welcome": describe::welcome(&welcome),
+                "verification": Verification::skipped("welcome messages are encrypted"),
+            })
+        }
+        InputKind::GroupInfo => {
+            let group_info = VerifiableGroupInfo::tls_deserialize_exact(input)?;
+            json!({
+                "group_info": describe::group_info(&group_info),
+                "verification": context.verify_group_info(group_info),
+            })
+        }
+        InputKind::RatchetTree => {
+            let ratchet_tree = RatchetTreeIn::tls_deserialize_exact(input)?;
+            json!({
+                "ratchet_tree": describe::ratchet_tree(&ratchet_tree),
+                "verification": context.verify_ratchet_tree(ratchet_tree),
+            })
+        }
+    };
+
+    Ok(inspection)
+}
+
+/// Decode a group info that is either wrapped in an `MLSMessage` or not.
+pub fn decode_group_info(input: &[u8]) -> Result<VerifiableGroupInfo, InspectError> {
+    match MlsMessageIn::tls_deserialize_exact(input) {
+        Ok(message) => match message.extract() {
+            MlsMessageBodyIn::GroupInfo(group_info) => Ok(group_info),
+            _ => Err(InspectError::NotAGroupInfo),
+        },
+        Err(_) => Ok(VerifiableGroupInfo::tls_deserialize_exact(input)?),
+    }
+}
+
+/// Decode a ratchet tree.
+pub fn decode_ratchet_tree(input: &[u8]) -> Result<RatchetTreeIn, InspectError> {
+    Ok(RatchetTreeIn::tls_deserialize_exact(input)?)
+}
@@ -0,0 +1,101 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::Parser;
+use mls_inspector::{
+    decode_group_info, decode_ratchet_tree, inspect, InputKind, VerificationContext,
+};
+
+/// Decode an MLS structure, print it as JSON and verify its signatures.
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+    /// The structure to decode.
+    #[arg(value_enum)]
+    kind: InputKind,
+
+    /// The file to read. Reads from stdin if omitted or `-`.
+    file: Option<PathBuf>,
+
+    /// Read hex encoded inputs instead of raw bytes. Applies to all inputs.
+    #[arg(long)]
+    hex: bool,
+
+    /// The ratchet tree of the group, to verify group infos without a
+    /// ratchet tree extension and public messages.
+    #[arg(long, value_name = "FILE")]
+    ratchet_tree: Option<PathBuf>,
+
+    /// The group info of the group, to verify ratchet trees and public
+    /// messages. May be wrapped in an `MLSMessage`.
+    #[arg(long, value_name = "FILE")]
+    group_info: Option<PathBuf>,
+
+    /// The hex encoded membership key of the group, to verify the membership
+    /// tag of public messages from members.
+    #[arg(long, value_name = "HEX")]
+    membership_key: Option<String>,
+}
+
+fn read_input(path: Option<&Path>, hex: bool) -> Result<Vec<u8>, String> {
+    let bytes = match path {
+        Some(path) if path != Path::new("-") => {
+            fs::read(path).map_err(|e| format!("Error reading {}: {e}", path.display()))?
+        }
+        _ => {
+            let mut bytes = vec![];
+            io::stdin()
+                .read_to_end(&mut bytes)
+                .map_err(|e| format!("Error reading stdin: {e}"))?;
+            bytes
+        }
+    };
+
+    if !hex {
+        return Ok(bytes);
+    }
+    let text = String::from_utf8(bytes).map_err(|_| "The hex input is not UTF-8".to_owned())?;
+    let text: String = text.split_whitespace().collect();
+    hex::decode(text).map_err(|e| format!("Error decoding hex input: {e}"))
+}
+
+fn run(args: Args) -> Result<String, String> {
+    let mut context = VerificationContext::default();
+    if let Some(path) = &args.ratchet_tree {
+        let input = read_input(Some(path), args.hex)?;
+        context.ratchet_tree =
+            Some(decode_ratchet_tree(&input).map_err(|e| format!("Ratchet tree: {e}"))?);
+    }
+    if let Some(path) = &args.group_info {
+        let input = read_input(Some(path), args.hex)?;
+        context.group_info =
+            Some(decode_group_info(&input).map_err(|e| format!("Group info: {e}"))?);
+    }
+    if let Some(membership_key) = &args.membership_key {
+        context.membership_key = Some(
+            hex::decode(membership_key)
+                .map_err(|e| format!("Error decoding membership key: {e}"))?,
+        );
+    }
+
+    let input = read_input(args.file.as_deref(), args.hex)?;
+    let inspection = inspect(args.kind, &input, &context).map_err(|e| e.to_string())?;
+    serde_json::to_string_pretty(&inspection).map_err(|e| e.to_string())
+}
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(output) => {
+            println!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,237 @@
+//! Verification of decoded inputs.
+
+use std::fmt::Display;
+
+use openmls::{
+    framing::{PublicMessageIn, Sender},
+    group::{ProposalStore, PublicGroup},
+    key_packages::KeyPackageIn,
+    messages::{group_info::VerifiableGroupInfo, proposals_in::ProposalIn},
+    prelude::SignaturePublicKey,
+    treesync::RatchetTreeIn,
+    versions::ProtocolVersion,
+};
+use openmls_rust_crypto::OpenMlsRustCrypto;
+use openmls_traits::OpenMlsProvider;
+use serde::Serialize;
+use tls_codec::Serialize as _;
+
+/// The result of verifying an input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Verification {
+    /// All signatures of the input are valid.
+    Valid,
+    /// The input failed verification.
+    Invalid {
+        /// The error returned by OpenMLS.
+        error: String,
+    },
+    /// The input can't be verified.
+    Skipped {
+        /// Why the input can't be verified.
+        reason: &'static str,
+    },
+}
+
+impl Verification {
+    pub(crate) fn skipped(reason: &'static str) -> Self {
+        Self::Skipped { reason }
+    }
+
+    fn from_result<T, E: Display>(result: Result<T, E>) -> Self {
+        match result {
+            Ok(_) => Self::Valid,
+            Err(error) => Self::Invalid {
+                error: error.to_string(),
+            },
+        }
+    }
+
+    /// Returns `true` if the input was verified successfully.
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Self::Valid)
+    }
+}
+
+/// Additional inputs that are required to verify some of the structures.
+#[derive(Debug, Clone, Default)]
+pub struct VerificationContext {
+    /// The ratchet tree of the group.
+    pub ratchet_tree: Option<RatchetTreeIn>,
+    /// The group info of the group, for the epoch of the verified input.
+    pub group_info: Option<VerifiableGroupInfo>,
+    /// The membership key of the group, for the epoch of the verified input.
+    pub membership_key: Option<Vec<u8>>,
+}
+
+impl VerificationContext {
+    /// Verify a group info against the ratchet tree in its ratchet tree
+    /// extension or, if it has none, the ratchet tree of the context.
+    pub(crate) fn verify_group_info(&self, group_info: VerifiableGroupInfo) -> Verification {
+        let ratchet_tree = match group_info.extensions().ratchet_tree() {
+            Some(extension) => extension.ratchet_tree().clone(),
+            None => match &self.ratchet_tree {
+                Some(ratchet_tree) => ratchet_tree.clone(),
+                None => {
+                    return Verification::skipped(
+                        "the group info has no ratchet tree extension and no ratchet tree was \
+                         provided",
+                    )
+                }
+            },
+        };
+        Verification::from_result(public_group(ratchet_tree, group_info))
+    }
+
+    /// Verify a ratchet tree against the group info of the context.
+    pub(crate) fn verify_ratchet_tree(&self, ratchet_tree: RatchetTreeIn) -> Verification {
+        let Some(group_info) = &self.group_info else {
+            return Verification::skipped("no group info was provided");
+        };
+        Verification::from_result(public_group(ratchet_tree, group_info.clone()))
+    }
+
+    /// Verify the signature of a public message with the signature key of its
+    /// sender in the group described by the context and, if the context has a
+    /// membership key, its membership tag. The content of the message isn't
+    /// validated against the group.
+    pub(crate) fn verify_public_message(&self, message: PublicMessageIn) -> Verification {
+        let Some(group_info) = &self.group_info else {
+            return Verification::skipped("no group info was provided");
+        };
+        let ratchet_tree = match (&self.ratchet_tree, group_info.extensions().ratchet_tree()) {
+            (Some(ratchet_tree), _) => ratchet_tree.clone(),
+            (None, Some(extension)) => extension.ratchet_tree().clone(),
+            (None, None) => return Verification::skipped("no ratchet tree was provided"),
+        };
+        let provider = OpenMlsRustCrypto::default();
+        let group = match PublicGroup::from_external(
+            provider.crypto(),
+            provider.storage(),
+            ratchet_tree,
+            group_info.clone(),
+            ProposalStore::new(),
+        ) {
+            Ok((group, _)) => group,
+            Err(error) => {
+                return Verification::Invalid {
+                    error: format!("The group can't be built from the context: {error}"),
+                }
+            }
+        };
+        if message.group_id() != group.group_id()
+            || message.epoch() != group.group_context().epoch()
+        {
+            return Verification::Invalid {
+                error: format!(
+                    "The message is for epoch {} of group {}, but the group info is for epoch {} \
+                     of group {}",
+                    message.epoch().as_u64(),
+                    hex::encode(message.group_id().as_slice()),
+                    group.group_context().epoch().as_u64(),
+                    hex::encode(group.group_id().as_slice()),
+                ),
+            };
+        }
+
+        let signature_key = match sender_signature_key(&group, &message) {
+            Ok(signature_key) => signature_key,
+            Err(error) => return Verification::Invalid { error },
+        };
+        let serialized_context = match group.group_context().tls_serialize_detached() {
+            Ok(serialized_context) => serialized_context,
+            Err(error) => {
+                return Verification::Invalid {
+                    error: error.to_string(),
+                }
+            }
+        };
+        if let Err(error) = message.verify_signature(
+            provider.crypto(),
+            group.ciphersuite(),
+            &signature_key,
+            &serialized_context,
+        ) {
+            return Verification::Invalid {
+                error: error.to_string(),
+            };
+        }
+
+        // Only members have to add a membership tag.
+        if !matches!(message.sender(), Sender::Member(_)) {
+            return Verification::Valid;
+        }
+        match &self.membership_key {
+            Some(membership_key) => Verification::from_result(message.verify_membership_tag(
+                provider.crypto(),
+                group.ciphersuite(),
+                membership_key,
+                &serialized_context,
+            )),
+            None => Verification::skipped(
+                "the signature is valid, but the membership tag can't be verified without the \
+                 membership key",
+            ),
+        }
+    }
+}
+
+/// Look up the signature key of the sender of a public message. Keys of new
+/// members are taken from the message itself.
+fn sender_signature_key(
+    group: &PublicGroup,
+    message: &PublicMessageIn,
+) -> Result<SignaturePublicKey, String> {
+    match message.sender() {
+        Sender::Member(leaf_index) => group
+            .leaf(*leaf_index)
+            .map(|leaf_node| leaf_node.signature_key().clone())
+            .ok_or_else(|| format!("The sender {leaf_index} is not a member of the group")),
+        Sender::External(index) => group
+            .group_context()
+            .extensions()
+            .external_senders()
+            .and_then(|external_senders| external_senders.get(index.index()))
+            .map(|external_sender| external_sender.signature_key().clone())
+            .ok_or_else(|| {
+                format!(
+                    "The external sender {} is not in the external senders of the group",
+                    index.index()
+                )
+            }),
+        Sender::NewMemberProposal => match message.unverified_proposal() {
+            Some(ProposalIn::Add(add)) => Ok(add
+                .unverified_key_package()
+                .leaf_node()
+                .signature_key()
+                .clone()),
+            _ => Err("The new member proposal is not an add proposal".to_owned()),
+        },
+        Sender::NewMemberCommit => message
+            .unverified_path_leaf_node()
+            .map(|leaf_node| leaf_node.signature_key().clone())
+            .ok_or_else(|| "The external commit has no update path".to_owned()),
+    }
+}
+
+/// Validate a key package, including the signature of its leaf node.
+pub(crate) fn key_package(key_package: KeyPackageIn) -> Verification {
+    let provider = OpenMlsRustCrypto::default();
+    Verification::from_result(key_package.validate(provider.crypto(), ProtocolVersion::Mls10))
+}
+
+fn public_group(
+    ratchet_tree: RatchetTreeIn,
+    group_info: VerifiableGroupInfo,
+) -> Result<(), impl Display> {
+    let provider = OpenMlsRustCrypto::default();
+    PublicGroup::from_external(
+        provider.crypto(),
+        provider.storage(),
+        ratchet_tree,
+        group_info,
+        ProposalStore::new(),
+    )
+    .map(|_| ())
+}
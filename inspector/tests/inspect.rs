@@ -0,0 +1,300 @@
+use mls_inspector::{
+    decode_group_info, decode_ratchet_tree, inspect, InputKind, VerificationContext,
+};
+use openmls::prelude::*;
+use openmls_basic_credential::SignatureKeyPair;
+use openmls_rust_crypto::OpenMlsRustCrypto;
+use serde_json::Value;
+use tls_codec::Serialize as _;
+
+const CIPHERSUITE: Ciphersuite = Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519;
+
+struct Member {
+    provider: OpenMlsRustCrypto,
+    signer: SignatureKeyPair,
+    credential_with_key: CredentialWithKey,
+}
+
+impl Member {
+    fn new(identity: &[u8]) -> Self {
+        let provider = OpenMlsRustCrypto::default();
+        let signer = SignatureKeyPair::new(CIPHERSUITE.signature_algorithm()).unwrap();
+        let credential_with_key = CredentialWithKey {
+            credential: BasicCredential::new(identity.to_vec()).into(),
+            signature_key: signer.to_public_vec().into(),
+        };
+        Self {
+            provider,
+            signer,
+            credential_with_key,
+        }
+    }
+
+    fn key_package(&self) -> KeyPackage {
+        KeyPackage::builder()
+            .build(
+                CIPHERSUITE,
+                &self.provider,
+                &self.signer,
+                self.credential_with_key.clone(),
+            )
+            .unwrap()
+            .key_package()
+            .clone()
+    }
+}
+
+/// Alice creates a group with public messages and adds Bob.
+fn alice_and_bob() -> (Member, MlsGroup, KeyPackage) {
+    let alice = Member::new(b"Alice");
+    let bob = Member::new(b"Bob");
+    let config = MlsGroupCreateConfig::builder()
+        .ciphersuite(CIPHERSUITE)
+        .wire_format_policy(PURE_PLAINTEXT_WIRE_FORMAT_POLICY)
+        .build();
+    let mut group = MlsGroup::new(
+        &alice.provider,
+        &alice.signer,
+        &config,
+        alice.credential_with_key.clone(),
+    )
+    .unwrap();
+
+    let bob_key_package = bob.key_package();
+    group
+        .add_members(
+            &alice.provider,
+            &alice.signer,
+            std::slice::from_ref(&bob_key_package),
+        )
+        .unwrap();
+    group.merge_pending_commit(&alice.provider).unwrap();
+
+    (alice, group, bob_key_package)
+}
+
+fn group_info_bytes(alice: &Member, group: &MlsGroup, with_ratchet_tree: bool) -> Vec<u8> {
+    group
+        .export_group_info(alice.provider.crypto(), &alice.signer, with_ratchet_tree)
+        .unwrap()
+        .tls_serialize_detached()
+        .unwrap()
+}
+
+fn status(inspection: &Value) -> &str {
+    inspection["verification"]["status"].as_str().unwrap()
+}
+
+#[test]
+fn key_package() {
+    let bob_key_package = Member::new(b"Bob").key_package();
+    let mut bytes = bob_key_package.tls_serialize_detached().unwrap();
+
+    let inspection = inspect(
+        InputKind::KeyPackage,
+        &bytes,
+        &VerificationContext::default(),
+    )
+    .unwrap();
+    assert_eq!(status(&inspection), "valid");
+    let leaf_node = &inspection["key_package"]["leaf_node"];
+    assert_eq!(leaf_node["credential"]["identity"], "Bob");
+    assert_eq!(leaf_node["source"]["type"], "key_package");
+
+    // Flip a bit in the signature of the key package, which is at the end.
+    *bytes.last_mut().unwrap() ^= 1;
+    let inspection = inspect(
+        InputKind::KeyPackage,
+        &bytes,
+        &VerificationContext::default(),
+    )
+    .unwrap();
+    assert_eq!(status(&inspection), "invalid");
+}
+
+#[test]
+fn key_package_in_message() {
+    let bob_key_package = Member::new(b"Bob").key_package();
+    let bytes = MlsMessageOut::from(bob_key_package)
+        .tls_serialize_detached()
+        .unwrap();
+
+    let inspection = inspect(InputKind::Message, &bytes, &VerificationContext::default()).unwrap();
+    assert_eq!(inspection["wire_format"], "KeyPackage");
+    assert_eq!(status(&inspection), "valid");
+}
+
+#[test]
+fn group_info_and_ratchet_tree() {
+    let (alice, group, _) = alice_and_bob();
+    let group_id = hex::encode(group.group_id().as_slice());
+
+    // The group info carries its ratchet tree.
+    let bytes = group_info_bytes(&alice, &group, true);
+    let inspection = inspect(InputKind::Message, &bytes, &VerificationContext::default()).unwrap();
+    assert_eq!(inspection["group_info"]["group_id"], group_id.as_str());
+    assert_eq!(inspection["group_info"]["epoch"], 1);
+    assert_eq!(status(&inspection), "valid");
+
+    // Without the ratchet tree, the group info can't be verified on its own.
+    let group_info = decode_group_info(&group_info_bytes(&alice, &group, false)).unwrap();
+    let bytes = group_info.tls_serialize_detached().unwrap();
+    let inspection = inspect(
+        InputKind::GroupInfo,
+        &bytes,
+        &VerificationContext::default(),
+    )
+    .unwrap();
+    assert_eq!(status(&inspection), "skipped");
+
+    // The ratchet tree is verified against the group info.
+    let ratchet_tree = group
+        .export_ratchet_tree()
+        .tls_serialize_detached()
+        .unwrap();
+    let context = VerificationContext {
+        ratchet_tree: None,
+        group_info: Some(group_info.clone()),
+        membership_key: None,
+    };
+    let inspection = inspect(InputKind::RatchetTree, &ratchet_tree, &context).unwrap();
+    assert_eq!(status(&inspection), "valid");
+    let nodes = inspection["ratchet_tree"]["nodes"].as_array().unwrap();
+    assert_eq!(nodes.len(), 3);
+    assert_eq!(nodes[0]["leaf_node"]["credential"]["identity"], "Alice");
+    assert_eq!(nodes[1]["type"], "parent");
+    assert_eq!(nodes[2]["leaf_node"]["credential"]["identity"], "Bob");
+
+    // And the group info against the ratchet tree.
+    let context = VerificationContext {
+        ratchet_tree: Some(decode_ratchet_tree(&ratchet_tree).unwrap()),
+        group_info: None,
+        membership_key: None,
+    };
+    let inspection = inspect(InputKind::GroupInfo, &bytes, &context).unwrap();
+    assert_eq!(status(&inspection), "valid");
+}
+
+#[test]
+fn public_commit() {
+    let (alice, mut group, bob_key_package) = alice_and_bob();
+    let mut context = VerificationContext {
+        ratchet_tree: None,
+        group_info: Some(decode_group_info(&group_info_bytes(&alice, &group, true)).unwrap()),
+        membership_key: None,
+    };
+
+    let bob_index = group
+        .members()
+        .find(|member| {
+            member.signature_key == bob_key_package.leaf_node().signature_key().as_slice()
+        })
+        .unwrap()
+        .index;
+    let (commit, _, _) = group
+        .remove_members(&alice.provider, &alice.signer, &[bob_index])
+        .unwrap();
+    let mut bytes = commit.tls_serialize_detached().unwrap();
+
+    let inspection = inspect(InputKind::Message, &bytes, &context).unwrap();
+    assert_eq!(inspection["wire_format"], "PublicMessage");
+    let message = &inspection["public_message"];
+    assert_eq!(message["epoch"], 1);
+    assert_eq!(message["content_type"], "Commit");
+    assert_eq!(message["proposals"][0]["type"], "Remove");
+    assert_eq!(message["proposals"][0]["removed"], 1);
+    // The signature is valid, but the membership tag needs the membership key.
+    assert_eq!(status(&inspection), "skipped");
+
+    // A wrong membership key is detected.
+    context.membership_key = Some(vec![0; CIPHERSUITE.hash_length()]);
+    let inspection = inspect(InputKind::Message, &bytes, &context).unwrap();
+    assert_eq!(status(&inspection), "invalid");
+    context.membership_key = None;
+
+    // A forged signature is detected. The message ends with the signature,
+    // the confirmation tag and the membership tag.
+    let signature_byte = bytes.len() - 2 * CIPHERSUITE.hash_length() - 4;
+    bytes[signature_byte] ^= 0xff;
+    let inspection = inspect(InputKind::Message, &bytes, &context).unwrap();
+    assert_eq!(status(&inspection), "invalid");
+
+    // Without the group, the commit can't be verified.
+    let inspection = inspect(InputKind::Message, &bytes, &VerificationContext::default()).unwrap();
+    assert_eq!(status(&inspection), "skipped");
+}
+
+#[test]
+fn public_commit_by_reference() {
+    let (alice, mut group, bob_key_package) = alice_and_bob();
+    let bob_index = group
+        .members()
+        .find(|member| {
+            member.signature_key == bob_key_package.leaf_node().signature_key().as_slice()
+        })
+        .unwrap()
+        .index;
+    let context = VerificationContext {
+        ratchet_tree: None,
+        group_info: Some(decode_group_info(&group_info_bytes(&alice, &group, true)).unwrap()),
+        membership_key: None,
+    };
+
+    // The proposal isn't known to the inspector, but that doesn't matter for
+    // the signature.
+    group
+        .propose_remove_member(&alice.provider, &alice.signer, bob_index)
+        .unwrap();
+    let (commit, _, _) = group
+        .commit_to_pending_proposals(&alice.provider, &alice.signer)
+        .unwrap();
+    let bytes = commit.tls_serialize_detached().unwrap();
+
+    let inspection = inspect(InputKind::Message, &bytes, &context).unwrap();
+    assert_eq!(
+        inspection["public_message"]["proposals"][0]["type"],
+        "Reference"
+    );
+    assert_eq!(status(&inspection), "skipped");
+    assert!(inspection["verification"]["reason"]
+        .as_str()
+        .unwrap()
+        .contains("membership key"));
+}
+
+#[test]
+fn external_commit() {
+    let (alice, group, _) = alice_and_bob();
+    let group_info = decode_group_info(&group_info_bytes(&alice, &group, true)).unwrap();
+    let context = VerificationContext {
+        ratchet_tree: None,
+        group_info: Some(group_info.clone()),
+        membership_key: None,
+    };
+
+    let charlie = Member::new(b"Charlie");
+    let (_, bundle) = MlsGroup::external_commit_builder()
+        .build_group(
+            &charlie.provider,
+            group_info,
+            charlie.credential_with_key.clone(),
+        )
+        .unwrap()
+        .load_psks(charlie.provider.storage())
+        .unwrap()
+        .build(
+            charlie.provider.rand(),
+            charlie.provider.crypto(),
+            &charlie.signer,
+            |_| true,
+        )
+        .unwrap()
+        .finalize(&charlie.provider)
+        .unwrap();
+    let bytes = bundle.commit().tls_serialize_detached().unwrap();
+
+    // Messages from new members have no membership tag.
+    let inspection = inspect(InputKind::Message, &bytes, &context).unwrap();
+    assert_eq!(inspection["public_message"]["sender"], "NewMemberCommit");
+    assert_eq!(status(&inspection), "valid");
+}
@@ -17,12 +17,15 @@ use mls_client::{
 use mls_interop_proto::mls_client;
 use openmls::{
     credentials::{BasicCredential, Credential, CredentialType, CredentialWithKey},
+    extensions::{Extension as MlsExtension, Extensions},
     framing::{MlsMessageBodyIn, MlsMessageIn, MlsMessageOut, ProcessedMessageContent},
     group::{
-        GroupEpoch, GroupId, MlsGroup, MlsGroupCreateConfig, MlsGroupJoinConfig, StagedWelcome,
-        WireFormatPolicy, PURE_CIPHERTEXT_WIRE_FORMAT_POLICY, PURE_PLAINTEXT_WIRE_FORMAT_POLICY,
+        CommitMessageBundle, GroupEpoch, GroupId, MlsGroup, MlsGroupCreateConfig,
+        MlsGroupJoinConfig, StagedWelcome, WireFormatPolicy, PURE_CIPHERTEXT_WIRE_FORMAT_POLICY,
+        PURE_PLAINTEXT_WIRE_FORMAT_POLICY,
     },
     key_packages::{KeyPackage, KeyPackageBundle},
+    messages::proposals::ReInitProposal,
     prelude::{Capabilities, ExtensionType, SenderRatchetConfiguration},
     schedule::{psk::ResumptionPskUsage, ExternalPsk, PreSharedKeyId, Psk},
     treesync::{LeafNodeParameters, RatchetTreeIn},
@@ -31,7 +34,7 @@ use openmls::{
 use openmls_basic_credential::SignatureKeyPair;
 use openmls_rust_crypto::OpenMlsRustCrypto;
 use openmls_traits::{random::OpenMlsRand, types::Ciphersuite, OpenMlsProvider};
-use tls_codec::{Deserialize, Serialize};
+use tls_codec::{Deserialize, Serialize, VLBytes};
 use tonic::{async_trait, transport::Server, Code, Request, Response, Status};
 use tracing::{debug, error, info, instrument, trace, Span};
 use tracing_subscriber::EnvFilter;
@@ -68,10 +71,15 @@ type PendingState = (
 /// individual MLS clients, as well as pending key packages that it was told to
 /// create. It also contains a transaction id map, that maps the `u32`
 /// transaction ids to key package hashes.
+///
+/// A group that waits to be reinitialized keeps its state id, which is used as
+/// its reinit id. The key packages for the successor groups of such groups are
+/// kept by reinit id.
 pub struct MlsClientImpl {
     groups: Mutex<Vec<InteropGroup>>,
     pending_state: Mutex<HashMap<Vec<u8>, PendingState>>,
     transaction_id_map: Mutex<HashMap<u32, Vec<u8>>>, // Indirection, linking to pending key packages
+    pending_reinit_state: Mutex<HashMap<u32, PendingState>>,
 }
 
 impl MlsClientImpl {
@@ -81,8 +89,43 @@ impl MlsClientImpl {
             groups: Mutex::new(Vec::new()),
             pending_state: Mutex::new(HashMap::new()),
             transaction_id_map: Mutex::new(HashMap::new()),
+            pending_reinit_state: Mutex::new(HashMap::new()),
         }
     }
+
+    /// Creates a key package for the successor group of the group with the
+    /// given state id, which waits to be reinitialized after a ReInit commit
+    /// has been merged. The state id is used as reinit id.
+    fn reinit_pending(
+        &self,
+        state_id: u32,
+        interop_group: &InteropGroup,
+    ) -> Result<HandleReInitCommitResponse, Status> {
+        let group = &interop_group.group;
+        trace!(epoch=?group.epoch(), "New group state.");
+
+        let reinit_proposal = group
+            .reinit_proposal()
+            .ok_or(Status::aborted("commit didn't contain a ReInit proposal"))?;
+        let pending_state = new_pending_state(
+            reinit_proposal.ciphersuite(),
+            group.credential().map_err(into_status)?.clone(),
+        );
+
+        let key_package: MlsMessageOut = pending_state.0.key_package().clone().into();
+        let response = HandleReInitCommitResponse {
+            reinit_id: state_id,
+            key_package: key_package.tls_serialize_detached().map_err(into_status)?,
+            epoch_authenticator: group.epoch_authenticator().as_slice().to_vec(),
+        };
+
+        self.pending_reinit_state
+            .lock()
+            .unwrap()
+            .insert(state_id, pending_state);
+
+        Ok(response)
+    }
 }
 
 fn into_status<E: Display>(e: E) -> Status {
@@ -167,6 +210,213 @@ where
     }
 }
 
+/// Creates a key package with the given `credential` and fresh signature keys
+/// in a new provider.
+fn new_pending_state(ciphersuite: Ciphersuite, credential: Credential) -> PendingState {
+    let crypto_provider = OpenMlsRustCrypto::default();
+    let signature_keys = SignatureKeyPair::new(ciphersuite.signature_algorithm()).unwrap();
+
+    let key_package = KeyPackage::builder()
+        .leaf_node_capabilities(Capabilities::new(
+            Some(&[ProtocolVersion::Mls10, ProtocolVersion::Other(999)]),
+            Some(&[
+                Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519,
+                Ciphersuite::MLS_128_DHKEMP256_AES128GCM_SHA256_P256,
+                Ciphersuite::MLS_128_DHKEMX25519_CHACHA20POLY1305_SHA256_Ed25519,
+            ]),
+            Some(&EXTENSION_TYPES),
+            None,
+            Some(&CREDENTIAL_TYPES),
+        ))
+        .build(
+            ciphersuite,
+            &crypto_provider,
+            &signature_keys,
+            CredentialWithKey {
+                credential: credential.clone(),
+                signature_key: signature_keys.public().into(),
+            },
+        )
+        .unwrap();
+
+    (key_package, credential, signature_keys, crypto_provider)
+}
+
+/// Stores the key package of the `pending_state` in its provider, so that it
+/// can be found when processing a welcome message.
+fn store_key_package(pending_state: &PendingState) -> Result<(), Status> {
+    use openmls_traits::storage::StorageProvider as _;
+
+    let (key_package, _, _, crypto_provider) = pending_state;
+    crypto_provider
+        .storage()
+        .write_key_package(
+            &key_package
+                .key_package()
+                .hash_ref(crypto_provider.crypto())
+                .map_err(into_status)?,
+            key_package,
+        )
+        .map_err(into_status)
+}
+
+/// Converts the extensions of a request into OpenMLS extensions.
+fn to_extensions(extensions: &[Extension]) -> Result<Vec<MlsExtension>, Status> {
+    extensions
+        .iter()
+        .map(|extension| {
+            let mut bytes = (extension.extension_type as u16)
+                .tls_serialize_detached()
+                .map_err(into_status)?;
+            VLBytes::new(extension.extension_data.clone())
+                .tls_serialize(&mut bytes)
+                .map_err(into_status)?;
+            MlsExtension::tls_deserialize_exact(bytes)
+                .map_err(|_| Status::invalid_argument("invalid extension"))
+        })
+        .collect()
+}
+
+/// Processes the given proposals (by reference) in the group and stores them
+/// for the next commit. Own proposals are skipped.
+fn process_proposals(
+    interop_group: &mut InteropGroup,
+    proposals: &[Vec<u8>],
+) -> Result<(), Status> {
+    let group = &mut interop_group.group;
+
+    for proposal in proposals {
+        trace!("Handling proposal by reference.");
+
+        let message = MlsMessageIn::tls_deserialize(&mut proposal.as_slice())
+            .map_err(|_| Status::aborted("failed to deserialize proposal"))?;
+        if interop_group.messages_out.contains(&message) {
+            trace!("Skipping processing of own proposal");
+            continue;
+        }
+        trace!("Processing proposal ...");
+        let processed_message = group
+            .process_message(
+                &interop_group.crypto_provider,
+                message.try_into_protocol_message().unwrap(),
+            )
+            .map_err(into_status)?;
+        trace!("... done");
+
+        match processed_message.into_content() {
+            ProcessedMessageContent::ApplicationMessage(_) => unreachable!(),
+            ProcessedMessageContent::ProposalMessage(proposal) => {
+                group
+                    .store_pending_proposal(interop_group.crypto_provider.storage(), *proposal)
+                    .map_err(|err| {
+                        tonic::Status::internal(format!("error storing proposal: {err}"))
+                    })?;
+            }
+            ProcessedMessageContent::ExternalJoinProposalMessage(_) => unreachable!(),
+            ProcessedMessageContent::StagedCommitMessage(_) => unreachable!(),
+            ProcessedMessageContent::OwnPendingCommit => unreachable!(),
+        }
+    }
+
+    Ok(())
+}
+
+/// Processes the given commit in the group and merges it.
+fn process_commit(interop_group: &mut InteropGroup, commit: &[u8]) -> Result<(), Status> {
+    let group = &mut interop_group.group;
+
+    debug!("Deserializing `MlsMessageIn`.");
+    let message = MlsMessageIn::tls_deserialize_exact(commit).map_err(|_| {
+        error!("Failed to deserialize ciphertext");
+        Status::aborted("failed to deserialize ciphertext")
+    })?;
+    debug!("Deserialized.");
+    trace!(?message);
+
+    debug!("Processing message.");
+    let processed_message = group
+        .process_message(
+            &interop_group.crypto_provider,
+            message.try_into_protocol_message().unwrap(),
+        )
+        .map_err(into_status)?;
+    debug!("Processed.");
+    trace!(?processed_message);
+
+    match processed_message.into_content() {
+        ProcessedMessageContent::ApplicationMessage(_) => unreachable!(),
+        ProcessedMessageContent::ProposalMessage(_) => unreachable!(),
+        ProcessedMessageContent::ExternalJoinProposalMessage(_) => unreachable!(),
+        ProcessedMessageContent::StagedCommitMessage(staged_commit) => {
+            debug!(commit=?staged_commit, "Merging staged commit.");
+            group
+                .merge_staged_commit(&interop_group.crypto_provider, *staged_commit)
+                .map_err(into_status)?;
+        }
+        ProcessedMessageContent::OwnPendingCommit => unreachable!(),
+    }
+
+    Ok(())
+}
+
+/// Adds a subgroup or successor group that was just created to the `groups`
+/// and returns the messages for the other members.
+fn new_subgroup(
+    groups: &mut Vec<InteropGroup>,
+    interop_group: InteropGroup,
+    message_bundle: CommitMessageBundle,
+    external_tree: bool,
+) -> Result<CreateSubgroupResponse, Status> {
+    let (_commit, welcome, _group_info) = message_bundle.into_messages();
+    let welcome = welcome
+        .ok_or(Status::internal("no welcome message was created"))?
+        .tls_serialize_detached()
+        .map_err(|_| Status::aborted("failed to serialize welcome"))?;
+
+    let ratchet_tree = if external_tree {
+        interop_group
+            .group
+            .export_ratchet_tree()
+            .tls_serialize_detached()
+            .map_err(|_| Status::aborted("failed to serialize ratchet tree"))?
+    } else {
+        vec![]
+    };
+    let epoch_authenticator = interop_group
+        .group
+        .epoch_authenticator()
+        .as_slice()
+        .to_vec();
+
+    let state_id = groups.len() as u32;
+    groups.push(interop_group);
+
+    Ok(CreateSubgroupResponse {
+        state_id,
+        welcome,
+        ratchet_tree,
+        epoch_authenticator,
+    })
+}
+
+/// Adds a subgroup or successor group that was just joined to the `groups`.
+fn join_subgroup(groups: &mut Vec<InteropGroup>, interop_group: InteropGroup) -> JoinGroupResponse {
+    trace!("   in epoch {:?}", interop_group.group.epoch());
+    let epoch_authenticator = interop_group
+        .group
+        .epoch_authenticator()
+        .as_slice()
+        .to_vec();
+
+    let state_id = groups.len() as u32;
+    groups.push(interop_group);
+
+    JoinGroupResponse {
+        state_id,
+        epoch_authenticator,
+    }
+}
+
 #[async_trait]
 impl MlsClient for MlsClientImpl {
     #[instrument(skip_all)]
@@ -270,7 +520,6 @@ impl MlsClient for MlsClientImpl {
         let request = request.get_ref();
         info!(?request, "Request");
 
-        let crypto_provider = OpenMlsRustCrypto::default();
         let ciphersuite = *to_ciphersuite(request.cipher_suite)?;
         let identity = request.identity.clone();
 
@@ -280,30 +529,8 @@ impl MlsClient for MlsClientImpl {
         );
 
         let credential = BasicCredential::new(identity);
-        let signature_keys = SignatureKeyPair::new(ciphersuite.signature_algorithm()).unwrap();
-
-        let key_package = KeyPackage::builder()
-            .leaf_node_capabilities(Capabilities::new(
-                Some(&[ProtocolVersion::Mls10, ProtocolVersion::Other(999)]),
-                Some(&[
-                    Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519,
-                    Ciphersuite::MLS_128_DHKEMP256_AES128GCM_SHA256_P256,
-                    Ciphersuite::MLS_128_DHKEMX25519_CHACHA20POLY1305_SHA256_Ed25519,
-                ]),
-                Some(&EXTENSION_TYPES),
-                None,
-                Some(&CREDENTIAL_TYPES),
-            ))
-            .build(
-                ciphersuite,
-                &crypto_provider,
-                &signature_keys,
-                CredentialWithKey {
-                    credential: credential.clone().into(),
-                    signature_key: signature_keys.public().into(),
-                },
-            )
-            .unwrap();
+        let (key_package, credential, signature_keys, crypto_provider) =
+            new_pending_state(ciphersuite, credential.into());
 
         let transaction_id: [u8; 4] = crypto_provider.rand().random_array().unwrap();
         let transaction_id = u32::from_be_bytes(transaction_id);
@@ -331,12 +558,7 @@ impl MlsClient for MlsClientImpl {
             .insert(transaction_id, request.identity.clone());
         self.pending_state.lock().unwrap().insert(
             request.identity.clone(),
-            (
-                key_package,
-                credential.into(),
-                signature_keys,
-                crypto_provider,
-            ),
+            (key_package, credential, signature_keys, crypto_provider),
         );
 
         info!(?response, "Response");
@@ -366,7 +588,7 @@ impl MlsClient for MlsClientImpl {
             .build();
 
         let mut pending_key_packages = self.pending_state.lock().unwrap();
-        let (my_key_package, _my_credential, my_signature_keys, crypto_provider) =
+        let pending_state =
             pending_key_packages
                 .remove(&request.identity)
                 .ok_or(Status::aborted(format!(
@@ -374,20 +596,10 @@ impl MlsClient for MlsClientImpl {
                     request.identity
                 )))?;
 
-        use openmls_traits::storage::StorageProvider as _;
-
         // Store the key package in the key store with the hash reference as id
         // for retrieval when parsing welcome messages.
-        crypto_provider
-            .storage()
-            .write_key_package(
-                &my_key_package
-                    .key_package()
-                    .hash_ref(crypto_provider.crypto())
-                    .map_err(into_status)?,
-                &my_key_package,
-            )
-            .map_err(into_status)?;
+        store_key_package(&pending_state)?;
+        let (_my_key_package, _my_credential, my_signature_keys, crypto_provider) = pending_state;
 
         let welcome = MlsMessageIn::tls_deserialize(&mut request.welcome.as_slice())
             .map_err(|_| Status::aborted("failed to deserialize MlsMessage with a Welcome"))?
@@ -921,10 +1133,39 @@ impl MlsClient for MlsClientImpl {
         let request = request.get_ref();
         info!(?request, "Request");
 
-        let response = Status::unimplemented("Re-init is not implemented");
+        let mut groups = self.groups.lock().unwrap();
+        let interop_group = groups
+            .get_mut(request.state_id as usize)
+            .ok_or_else(|| Status::new(Code::InvalidArgument, "unknown state_id"))?;
+        trace!("   in epoch {:?}", interop_group.group.epoch());
+
+        let extensions =
+            Extensions::from_vec(to_extensions(&request.extensions)?).map_err(into_status)?;
+        let reinit_proposal = ReInitProposal::new(
+            GroupId::from_slice(&request.group_id),
+            ProtocolVersion::Mls10,
+            *to_ciphersuite(request.cipher_suite)?,
+            extensions,
+        );
+
+        let (proposal, _) = interop_group
+            .group
+            .propose_reinit(
+                &interop_group.crypto_provider,
+                &interop_group.signature_keys,
+                reinit_proposal,
+            )
+            .map_err(into_status)?;
+
+        // Store the proposal for potential future use.
+        interop_group.messages_out.push(proposal.clone().into());
+
+        let proposal = proposal.to_bytes().unwrap();
+
+        let response = ProposalResponse { proposal };
 
         info!(?response, "Response");
-        Err(response)
+        Ok(Response::new(response))
     }
 
     #[instrument(skip_all, fields(actor))]
@@ -939,44 +1180,12 @@ impl MlsClient for MlsClientImpl {
         let interop_group = groups
             .get_mut(request.state_id as usize)
             .ok_or_else(|| Status::new(Code::InvalidArgument, "unknown state_id"))?;
-        let group = &mut interop_group.group;
-
-        trace!(epoch=?group.epoch(), "Current group state.");
+        trace!(epoch=?interop_group.group.epoch(), "Current group state.");
 
         // Proposals by reference. These proposals are standalone proposals. They should
         // be appended to the proposal store.
-        for proposal in &request.by_reference {
-            trace!("Handling proposal by reference.");
-
-            let message = MlsMessageIn::tls_deserialize(&mut proposal.as_slice())
-                .map_err(|_| Status::aborted("failed to deserialize proposal"))?;
-            if interop_group.messages_out.contains(&message) {
-                trace!("Skipping processing of own proposal");
-                continue;
-            }
-            trace!("Processing proposal ...");
-            let processed_message = group
-                .process_message(
-                    &interop_group.crypto_provider,
-                    message.try_into_protocol_message().unwrap(),
-                )
-                .map_err(into_status)?;
-            trace!("... done");
-
-            match processed_message.into_content() {
-                ProcessedMessageContent::ApplicationMessage(_) => unreachable!(),
-                ProcessedMessageContent::ProposalMessage(proposal) => {
-                    group
-                        .store_pending_proposal(interop_group.crypto_provider.storage(), *proposal)
-                        .map_err(|err| {
-                            tonic::Status::internal(format!("error storing proposal: {err}"))
-                        })?;
-                }
-                ProcessedMessageContent::ExternalJoinProposalMessage(_) => unreachable!(),
-                ProcessedMessageContent::StagedCommitMessage(_) => unreachable!(),
-                ProcessedMessageContent::OwnPendingCommit => unreachable!(),
-            }
-        }
+        process_proposals(interop_group, &request.by_reference)?;
+        let group = &mut interop_group.group;
 
         // Proposals by value. These proposals are inline proposals. They should be
         // converted into group operations.
@@ -1124,76 +1333,12 @@ impl MlsClient for MlsClientImpl {
         let interop_group = groups
             .get_mut(request.state_id as usize)
             .ok_or_else(|| Status::new(Code::InvalidArgument, "unknown state_id"))?;
-        let group = &mut interop_group.group;
-
-        trace!(epoch=?group.epoch(), "Current group state.");
+        trace!(epoch=?interop_group.group.epoch(), "Current group state.");
 
         // XXX[FK]: This is a horrible API.
-
-        for proposal in &request.proposal {
-            // trace!("   proposals by reference ... we don't care.");
-            let message = MlsMessageIn::tls_deserialize(&mut proposal.as_slice())
-                .map_err(|_| Status::aborted("failed to deserialize proposal"))?;
-            if interop_group.messages_out.contains(&message) {
-                trace!("   skipping processing of own proposal");
-                continue;
-            }
-            trace!("   processing proposal ...");
-            let processed_message = group
-                .process_message(
-                    &interop_group.crypto_provider,
-                    message.try_into_protocol_message().unwrap(),
-                )
-                .map_err(into_status)?;
-            trace!("       done");
-            match processed_message.into_content() {
-                ProcessedMessageContent::ApplicationMessage(_) => unreachable!(),
-                ProcessedMessageContent::ProposalMessage(proposal) => {
-                    group
-                        .store_pending_proposal(interop_group.crypto_provider.storage(), *proposal)
-                        .map_err(|err| {
-                            tonic::Status::internal(format!(
-                                "error storing pending proposal: {err}"
-                            ))
-                        })?;
-                }
-                ProcessedMessageContent::ExternalJoinProposalMessage(_) => unreachable!(),
-                ProcessedMessageContent::StagedCommitMessage(_) => unreachable!(),
-                ProcessedMessageContent::OwnPendingCommit => unreachable!(),
-            }
-        }
-
-        debug!("Deserializing `MlsMessageIn`.");
-        let message =
-            MlsMessageIn::tls_deserialize(&mut request.commit.as_slice()).map_err(|_| {
-                error!("Failed to deserialize ciphertext");
-                Status::aborted("failed to deserialize ciphertext")
-            })?;
-        debug!("Deserialized.");
-        trace!(?message);
-
-        debug!("Processing message.");
-        let processed_message = group
-            .process_message(
-                &interop_group.crypto_provider,
-                message.try_into_protocol_message().unwrap(),
-            )
-            .map_err(into_status)?;
-        debug!("Processed.");
-        trace!(?processed_message);
-
-        match processed_message.into_content() {
-            ProcessedMessageContent::ApplicationMessage(_) => unreachable!(),
-            ProcessedMessageContent::ProposalMessage(_) => unreachable!(),
-            ProcessedMessageContent::ExternalJoinProposalMessage(_) => unreachable!(),
-            ProcessedMessageContent::StagedCommitMessage(staged_commit) => {
-                debug!(commit=?staged_commit, "Merging staged commit.");
-                group
-                    .merge_staged_commit(&interop_group.crypto_provider, *staged_commit)
-                    .map_err(into_status)?;
-            }
-            ProcessedMessageContent::OwnPendingCommit => unreachable!(),
-        }
+        process_proposals(interop_group, &request.proposal)?;
+        process_commit(interop_group, &request.commit)?;
+        let group = &interop_group.group;
 
         trace!(epoch=?group.epoch(), "New group state.");
 
@@ -1381,53 +1526,354 @@ impl MlsClient for MlsClientImpl {
         ))
     }
 
+    #[instrument(skip_all, fields(actor))]
     async fn re_init_commit(
         &self,
-        _: Request<CommitRequest>,
+        request: Request<CommitRequest>,
     ) -> Result<Response<CommitResponse>, Status> {
-        todo!()
+        let request = request.get_ref();
+        info!(?request, "Request");
+
+        let mut groups = self.groups.lock().unwrap();
+        let interop_group = groups
+            .get_mut(request.state_id as usize)
+            .ok_or_else(|| Status::new(Code::InvalidArgument, "unknown state_id"))?;
+        trace!(epoch=?interop_group.group.epoch(), "Current group state.");
+
+        // A commit with a ReInit proposal must not contain any other proposals,
+        // so the ReInit proposal is always sent by reference.
+        if !request.by_value.is_empty() {
+            return Err(Status::invalid_argument(
+                "ReInit commits can't contain proposals by value",
+            ));
+        }
+        process_proposals(interop_group, &request.by_reference)?;
+
+        let (commit, _welcome_option, _group_info) = interop_group
+            .group
+            .commit_to_pending_proposals(
+                &interop_group.crypto_provider,
+                &interop_group.signature_keys,
+            )
+            .map_err(into_status)?;
+        debug!(commit=?interop_group.group.pending_commit(), "Pending commit created. (Note: Not merged yet.)");
+
+        let response = CommitResponse {
+            commit: commit.to_bytes().unwrap(),
+            welcome: vec![],
+            ratchet_tree: vec![],
+        };
+
+        info!(?response, "Response");
+        Ok(Response::new(response))
     }
 
+    #[instrument(skip_all, fields(actor))]
     async fn handle_pending_re_init_commit(
         &self,
-        _: Request<HandlePendingCommitRequest>,
+        request: Request<HandlePendingCommitRequest>,
     ) -> Result<Response<HandleReInitCommitResponse>, Status> {
-        todo!()
+        let request = request.get_ref();
+        info!(?request, "Request");
+
+        let mut groups = self.groups.lock().unwrap();
+        let interop_group = groups
+            .get_mut(request.state_id as usize)
+            .ok_or_else(|| Status::new(Code::InvalidArgument, "unknown state_id"))?;
+        trace!(epoch=?interop_group.group.epoch(), "Current group state.");
+
+        trace!(commit=?interop_group.group.pending_commit(), "Merging pending commit.");
+        interop_group
+            .group
+            .merge_pending_commit(&interop_group.crypto_provider)
+            .map_err(into_status)?;
+
+        let response = self.reinit_pending(request.state_id, interop_group)?;
+
+        info!(?response, "Response");
+        Ok(Response::new(response))
     }
 
+    #[instrument(skip_all, fields(actor))]
     async fn handle_re_init_commit(
         &self,
-        _: Request<HandleCommitRequest>,
+        request: Request<HandleCommitRequest>,
     ) -> Result<Response<HandleReInitCommitResponse>, Status> {
-        todo!()
+        let request = request.get_ref();
+        info!(?request, "Request");
+
+        let mut groups = self.groups.lock().unwrap();
+        let interop_group = groups
+            .get_mut(request.state_id as usize)
+            .ok_or_else(|| Status::new(Code::InvalidArgument, "unknown state_id"))?;
+        trace!(epoch=?interop_group.group.epoch(), "Current group state.");
+
+        process_proposals(interop_group, &request.proposal)?;
+        process_commit(interop_group, &request.commit)?;
+
+        let response = self.reinit_pending(request.state_id, interop_group)?;
+
+        info!(?response, "Response");
+        Ok(Response::new(response))
     }
 
+    #[instrument(skip_all, fields(actor))]
     async fn re_init_welcome(
         &self,
-        _: Request<ReInitWelcomeRequest>,
+        request: Request<ReInitWelcomeRequest>,
     ) -> Result<Response<CreateSubgroupResponse>, Status> {
-        todo!()
+        let request = request.get_ref();
+        info!(?request, "Request");
+
+        let key_packages = request
+            .key_package
+            .iter()
+            .map(|key_package| {
+                MlsMessageIn::tls_deserialize_exact(key_package)
+                    .map_err(|_| Status::invalid_argument("Invalid key package"))?
+                    .into_keypackage()
+                    .ok_or(Status::invalid_argument("Message was not a key package"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut groups = self.groups.lock().unwrap();
+        let old_group = &groups
+            .get(request.reinit_id as usize)
+            .ok_or_else(|| Status::new(Code::InvalidArgument, "unknown reinit_id"))?
+            .group;
+        let reinit_builder = old_group.reinit().map_err(into_status)?;
+        let ciphersuite = reinit_builder.reinit_proposal().ciphersuite();
+
+        let provider = OpenMlsRustCrypto::default();
+        let signature_keys = SignatureKeyPair::new(ciphersuite.signature_algorithm()).unwrap();
+        signature_keys.store(provider.storage()).unwrap();
+        let credential_with_key = CredentialWithKey {
+            credential: old_group.credential().map_err(into_status)?.clone(),
+            signature_key: signature_keys.public().into(),
+        };
+
+        let (mut group, message_bundle) = reinit_builder
+            .finish(
+                key_packages,
+                |builder| builder.force_self_update(request.force_path),
+                &provider,
+                &signature_keys,
+                credential_with_key,
+            )
+            .map_err(into_status)?;
+        group.merge_pending_commit(&provider).map_err(into_status)?;
+
+        let interop_group = InteropGroup {
+            wire_format_policy: group.configuration().wire_format_policy(),
+            group,
+            signature_keys,
+            messages_out: Vec::new(),
+            crypto_provider: provider,
+        };
+        let response = new_subgroup(
+            &mut groups,
+            interop_group,
+            message_bundle,
+            request.external_tree,
+        )?;
+
+        info!(?response, "Response");
+        Ok(Response::new(response))
     }
 
+    #[instrument(skip_all, fields(actor))]
     async fn handle_re_init_welcome(
         &self,
-        _: Request<HandleReInitWelcomeRequest>,
+        request: Request<HandleReInitWelcomeRequest>,
     ) -> Result<Response<JoinGroupResponse>, Status> {
-        todo!()
+        let request = request.get_ref();
+        info!(?request, "Request");
+
+        let pending_state = self
+            .pending_reinit_state
+            .lock()
+            .unwrap()
+            .remove(&request.reinit_id)
+            .ok_or(Status::aborted("failed to find key package for reinit_id"))?;
+        store_key_package(&pending_state)?;
+        let (_key_package, _credential, signature_keys, crypto_provider) = pending_state;
+
+        let welcome = MlsMessageIn::tls_deserialize(&mut request.welcome.as_slice())
+            .map_err(|_| Status::aborted("failed to deserialize MlsMessage with a Welcome"))?
+            .into_welcome()
+            .ok_or(Status::aborted("expected a welcome"))?;
+        let ratchet_tree = ratchet_tree_from_config(request.ratchet_tree.clone());
+
+        let mut groups = self.groups.lock().unwrap();
+        let old_group = &groups
+            .get(request.reinit_id as usize)
+            .ok_or_else(|| Status::new(Code::InvalidArgument, "unknown reinit_id"))?
+            .group;
+        let group = old_group
+            .join_reinit(
+                &crypto_provider,
+                old_group.configuration(),
+                welcome,
+                ratchet_tree,
+            )
+            .map_err(into_status)?
+            .into_group(&crypto_provider)
+            .map_err(into_status)?;
+
+        let interop_group = InteropGroup {
+            wire_format_policy: group.configuration().wire_format_policy(),
+            group,
+            signature_keys,
+            messages_out: Vec::new(),
+            crypto_provider,
+        };
+        let response = join_subgroup(&mut groups, interop_group);
+
+        info!(?response, "Response");
+        Ok(Response::new(response))
     }
 
+    #[instrument(skip_all, fields(actor))]
     async fn create_branch(
         &self,
-        _: Request<CreateBranchRequest>,
+        request: Request<CreateBranchRequest>,
     ) -> Result<Response<CreateSubgroupResponse>, Status> {
-        todo!()
+        let request = request.get_ref();
+        info!(?request, "Request");
+
+        let key_packages = request
+            .key_packages
+            .iter()
+            .map(|key_package| {
+                MlsMessageIn::tls_deserialize_exact(key_package)
+                    .map_err(|_| Status::invalid_argument("Invalid key package"))?
+                    .into_keypackage()
+                    .ok_or(Status::invalid_argument("Message was not a key package"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let extensions =
+            Extensions::from_vec(to_extensions(&request.extensions)?).map_err(into_status)?;
+
+        let mut groups = self.groups.lock().unwrap();
+        let parent_group = &groups
+            .get(request.state_id as usize)
+            .ok_or_else(|| Status::new(Code::InvalidArgument, "unknown state_id"))?
+            .group;
+        trace!(epoch=?parent_group.epoch(), "Current group state.");
+
+        let provider = OpenMlsRustCrypto::default();
+        let signature_keys =
+            SignatureKeyPair::new(parent_group.ciphersuite().signature_algorithm()).unwrap();
+        signature_keys.store(provider.storage()).unwrap();
+        let credential_with_key = CredentialWithKey {
+            credential: parent_group.credential().map_err(into_status)?.clone(),
+            signature_key: signature_keys.public().into(),
+        };
+
+        let (mut group, message_bundle) = parent_group
+            .branch()
+            .refine_group_builder(|builder| {
+                builder
+                    .with_group_id(GroupId::from_slice(&request.group_id))
+                    .with_group_context_extensions(extensions.clone())
+            })
+            .finish(
+                key_packages,
+                |builder| builder.force_self_update(request.force_path),
+                &provider,
+                &signature_keys,
+                credential_with_key,
+            )
+            .map_err(into_status)?;
+        group.merge_pending_commit(&provider).map_err(into_status)?;
+
+        let interop_group = InteropGroup {
+            wire_format_policy: group.configuration().wire_format_policy(),
+            group,
+            signature_keys,
+            messages_out: Vec::new(),
+            crypto_provider: provider,
+        };
+        let response = new_subgroup(
+            &mut groups,
+            interop_group,
+            message_bundle,
+            request.external_tree,
+        )?;
+
+        info!(?response, "Response");
+        Ok(Response::new(response))
     }
 
+    #[instrument(skip_all, fields(actor))]
     async fn handle_branch(
         &self,
-        _: Request<HandleBranchRequest>,
+        request: Request<HandleBranchRequest>,
     ) -> Result<Response<HandleBranchResponse>, Status> {
-        todo!()
+        let request = request.get_ref();
+        info!(?request, "Request");
+
+        // The key package for the subgroup was created with the given
+        // transaction id.
+        let identity = self
+            .transaction_id_map
+            .lock()
+            .unwrap()
+            .get(&request.transaction_id)
+            .cloned()
+            .ok_or(Status::invalid_argument("unknown transaction_id"))?;
+        let pending_state =
+            self.pending_state
+                .lock()
+                .unwrap()
+                .remove(&identity)
+                .ok_or(Status::aborted(format!(
+                    "failed to find key package for identity {identity:x?}"
+                )))?;
+        store_key_package(&pending_state)?;
+        let (_key_package, _credential, signature_keys, crypto_provider) = pending_state;
+
+        let welcome = MlsMessageIn::tls_deserialize(&mut request.welcome.as_slice())
+            .map_err(|_| Status::aborted("failed to deserialize MlsMessage with a Welcome"))?
+            .into_welcome()
+            .ok_or(Status::aborted("expected a welcome"))?;
+        let ratchet_tree = ratchet_tree_from_config(request.ratchet_tree.clone());
+
+        let mut groups = self.groups.lock().unwrap();
+        let parent_group = &groups
+            .get(request.state_id as usize)
+            .ok_or_else(|| Status::new(Code::InvalidArgument, "unknown state_id"))?
+            .group;
+        let group = parent_group
+            .join_branch(
+                &crypto_provider,
+                parent_group.configuration(),
+                welcome,
+                ratchet_tree,
+            )
+            .map_err(into_status)?
+            .into_group(&crypto_provider)
+            .map_err(into_status)?;
+
+        let interop_group = InteropGroup {
+            wire_format_policy: group.configuration().wire_format_policy(),
+            group,
+            signature_keys,
+            messages_out: Vec::new(),
+            crypto_provider,
+        };
+        let JoinGroupResponse {
+            state_id,
+            epoch_authenticator,
+        } = join_subgroup(&mut groups, interop_group);
+
+        let response = HandleBranchResponse {
+            state_id,
+            epoch_authenticator,
+        };
+
+        info!(?response, "Response");
+        Ok(Response::new(response))
     }
 
     async fn new_member_add_proposal(
@@ -13,6 +13,8 @@ use rand_chacha::ChaCha20Core;
 
 use tls_codec::SecretVLBytes;
 
+use crate::ecdsa;
+
 /// The libcrux-backed cryptography provider for OpenMLS
 pub struct CryptoProvider {
     pub(super) rng: Mutex<ReseedingRng<ChaCha20Core, OsRng>>,
@@ -37,7 +39,7 @@ impl OpenMlsCrypto for CryptoProvider {
         }?;
 
         match ciphersuite.signature_algorithm() {
-            SignatureScheme::ED25519 => Ok(()),
+            SignatureScheme::ED25519 | SignatureScheme::ECDSA_SECP256R1_SHA256 => Ok(()),
             _ => Err(CryptoError::UnsupportedCiphersuite),
         }?;
 
@@ -46,7 +48,7 @@ impl OpenMlsCrypto for CryptoProvider {
         }?;
 
         match ciphersuite.hpke_aead_algorithm() {
-            HpkeAeadType::ChaCha20Poly1305 => Ok(()),
+            HpkeAeadType::ChaCha20Poly1305 | HpkeAeadType::AesGcm128 => Ok(()),
             _ => Err(CryptoError::UnsupportedCiphersuite),
         }?;
 
@@ -56,11 +58,10 @@ impl OpenMlsCrypto for CryptoProvider {
     fn supported_ciphersuites(&self) -> Vec<Ciphersuite> {
         vec![
             Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519,
+            Ciphersuite::MLS_128_DHKEMP256_AES128GCM_SHA256_P256,
             Ciphersuite::MLS_128_DHKEMX25519_CHACHA20POLY1305_SHA256_Ed25519,
             #[cfg(feature = "draft-ietf-mls-pq-ciphersuites")]
             Ciphersuite::MLS_256_XWING_CHACHA20POLY1305_SHA256_Ed25519,
-            // TODO: enable
-            //Ciphersuite::MLS_128_DHKEMP256_AES128GCM_SHA256_P256,
         ]
     }
 
@@ -205,24 +206,24 @@ impl OpenMlsCrypto for CryptoProvider {
     }
 
     fn signature_key_gen(&self, alg: SignatureScheme) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
-        if !matches!(alg, SignatureScheme::ED25519) {
-            return Err(CryptoError::UnsupportedSignatureScheme);
-        }
-
         let mut rng = self
             .rng
             .lock()
             .map_err(|_| CryptoError::CryptoLibraryError)
             .map(GuardedRng)?;
 
-        libcrux_ed25519::generate_key_pair(&mut rng)
-            .map_err(|_| CryptoError::SigningError)
-            .map(|(signing_key, verification_key)| {
-                (
-                    signing_key.into_bytes().to_vec(),
-                    verification_key.into_bytes().to_vec(),
-                )
-            })
+        match alg {
+            SignatureScheme::ED25519 => libcrux_ed25519::generate_key_pair(&mut rng)
+                .map_err(|_| CryptoError::SigningError)
+                .map(|(signing_key, verification_key)| {
+                    (
+                        signing_key.into_bytes().to_vec(),
+                        verification_key.into_bytes().to_vec(),
+                    )
+                }),
+            SignatureScheme::ECDSA_SECP256R1_SHA256 => ecdsa::key_gen(&mut rng),
+            _ => Err(CryptoError::UnsupportedSignatureScheme),
+        }
     }
 
     fn verify_signature(
@@ -232,8 +233,12 @@ impl OpenMlsCrypto for CryptoProvider {
         pk: &[u8],
         signature: &[u8],
     ) -> Result<(), CryptoError> {
-        if !matches!(alg, SignatureScheme::ED25519) {
-            return Err(CryptoError::UnsupportedSignatureScheme);
+        match alg {
+            SignatureScheme::ED25519 => (),
+            SignatureScheme::ECDSA_SECP256R1_SHA256 => {
+                return ecdsa::verify(data, pk, signature);
+            }
+            _ => return Err(CryptoError::UnsupportedSignatureScheme),
         }
 
         let pk = <&[u8; 32]>::try_from(pk).map_err(|_| CryptoError::InvalidLength)?;
@@ -246,8 +251,18 @@ impl OpenMlsCrypto for CryptoProvider {
     }
 
     fn sign(&self, alg: SignatureScheme, data: &[u8], key: &[u8]) -> Result<Vec<u8>, CryptoError> {
-        if !matches!(alg, SignatureScheme::ED25519) {
-            return Err(CryptoError::UnsupportedSignatureScheme);
+        match alg {
+            SignatureScheme::ED25519 => (),
+            SignatureScheme::ECDSA_SECP256R1_SHA256 => {
+                let mut rng = self
+                    .rng
+                    .lock()
+                    .map_err(|_| CryptoError::CryptoLibraryError)
+                    .map(GuardedRng)?;
+
+                return ecdsa::sign(data, key, &mut rng);
+            }
+            _ => return Err(CryptoError::UnsupportedSignatureScheme),
         }
 
         let key = <&[u8; 32]>::try_from(key).map_err(|_| CryptoError::InvalidLength)?;
@@ -0,0 +1,184 @@
+//! ECDSA on P-256 with SHA-256 for the `ecdsa_secp256r1_sha256` signature
+//! scheme.
+//!
+//! libcrux produces and consumes raw `r || s` signatures and `x || y` public
+//! keys. MLS uses DER-encoded signatures and uncompressed SEC1 public keys,
+//! like `openmls_rust_crypto`, so this module converts between the two.
+
+use libcrux_ecdsa::{p256, DigestAlgorithm};
+use openmls_traits::types::CryptoError;
+use rand::CryptoRng;
+
+const SCALAR_LEN: usize = 32;
+const POINT_LEN: usize = 64;
+
+/// Tag of an uncompressed SEC1 point.
+const UNCOMPRESSED_POINT: u8 = 0x04;
+
+const DER_SEQUENCE: u8 = 0x30;
+const DER_INTEGER: u8 = 0x02;
+
+/// Generate a key pair, returning the private scalar and the uncompressed
+/// public key.
+pub(crate) fn key_gen(rng: &mut impl CryptoRng) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
+    let sk = p256::PrivateKey::random(rng).map_err(|_| CryptoError::InsufficientRandomness)?;
+    let sk: &[u8] = sk.as_ref();
+    let raw_pk = libcrux_ecdh::secret_to_public(libcrux_ecdh::Algorithm::P256, sk)
+        .map_err(|_| CryptoError::CryptoLibraryError)?;
+
+    let mut pk = Vec::with_capacity(1 + raw_pk.len());
+    pk.push(UNCOMPRESSED_POINT);
+    pk.extend_from_slice(&raw_pk);
+
+    Ok((sk.to_vec(), pk))
+}
+
+/// Sign `data` with the private scalar `key`, returning a DER-encoded
+/// signature.
+pub(crate) fn sign(
+    data: &[u8],
+    key: &[u8],
+    rng: &mut impl CryptoRng,
+) -> Result<Vec<u8>, CryptoError> {
+    if key.len() != SCALAR_LEN {
+        return Err(CryptoError::InvalidLength);
+    }
+    let key = p256::PrivateKey::try_from(key).map_err(|_| CryptoError::CryptoLibraryError)?;
+
+    let signature = p256::rand::sign(DigestAlgorithm::Sha256, data, &key, rng)
+        .map_err(|_| CryptoError::SigningError)?;
+    let (r, s) = signature.as_bytes();
+
+    Ok(der_encode(r, s))
+}
+
+/// Verify the DER-encoded `signature` on `data` with the uncompressed public
+/// key `pk`.
+pub(crate) fn verify(data: &[u8], pk: &[u8], signature: &[u8]) -> Result<(), CryptoError> {
+    // Parse the point explicitly. `PublicKey::try_from` guesses the encoding
+    // and takes some raw `x || y` points for compressed ones.
+    if pk.len() != 1 + POINT_LEN {
+        return Err(CryptoError::CryptoLibraryError);
+    }
+    let pk = p256::uncompressed_to_coordinates(pk).map_err(|_| CryptoError::CryptoLibraryError)?;
+    p256::validate_point(&pk).map_err(|_| CryptoError::CryptoLibraryError)?;
+    let pk = p256::PublicKey(pk);
+
+    let (r, s) = der_decode(signature).ok_or(CryptoError::InvalidSignature)?;
+    let signature = p256::Signature::from_raw(r, s);
+
+    p256::verify(DigestAlgorithm::Sha256, data, &signature, &pk)
+        .map_err(|_| CryptoError::InvalidSignature)
+}
+
+/// Encode `r` and `s` as a DER `SEQUENCE` of two `INTEGER`s.
+///
+/// All lengths are below 128, so the short length form suffices.
+fn der_encode(r: &[u8; SCALAR_LEN], s: &[u8; SCALAR_LEN]) -> Vec<u8> {
+    let r = der_integer(r);
+    let s = der_integer(s);
+
+    let mut out = Vec::with_capacity(2 + r.len() + s.len());
+    out.push(DER_SEQUENCE);
+    out.push((r.len() + s.len()) as u8);
+    out.extend_from_slice(&r);
+    out.extend_from_slice(&s);
+    out
+}
+
+fn der_integer(value: &[u8; SCALAR_LEN]) -> Vec<u8> {
+    // Strip leading zeros, but keep at least one byte.
+    let start = value.iter().position(|b| *b != 0).unwrap_or(SCALAR_LEN - 1);
+    let value = &value[start..];
+    // Prepend a zero byte if the value would otherwise be negative.
+    let pad = value[0] & 0x80 != 0;
+
+    let len = value.len() + usize::from(pad);
+    let mut out = Vec::with_capacity(2 + len);
+    out.push(DER_INTEGER);
+    out.push(len as u8);
+    if pad {
+        out.push(0);
+    }
+    out.extend_from_slice(value);
+    out
+}
+
+/// Decode a DER-encoded signature into `r` and `s`.
+///
+/// Returns `None` unless the input is a strict DER encoding of two
+/// non-negative integers that fit into a scalar.
+fn der_decode(signature: &[u8]) -> Option<([u8; SCALAR_LEN], [u8; SCALAR_LEN])> {
+    let (&tag, rest) = signature.split_first()?;
+    let (&len, rest) = rest.split_first()?;
+    if tag != DER_SEQUENCE || usize::from(len) != rest.len() {
+        return None;
+    }
+
+    let (r, rest) = der_read_integer(rest)?;
+    let (s, rest) = der_read_integer(rest)?;
+    if !rest.is_empty() {
+        return None;
+    }
+
+    Some((r, s))
+}
+
+fn der_read_integer(input: &[u8]) -> Option<([u8; SCALAR_LEN], &[u8])> {
+    let (&tag, rest) = input.split_first()?;
+    let (&len, rest) = rest.split_first()?;
+    if tag != DER_INTEGER || len == 0 || len >= 0x80 || usize::from(len) > rest.len() {
+        return None;
+    }
+    let (mut value, rest) = rest.split_at(usize::from(len));
+
+    // Negative values are invalid.
+    if value[0] & 0x80 != 0 {
+        return None;
+    }
+    // A leading zero is only allowed if the next byte has its high bit set.
+    if value.len() > 1 && value[0] == 0 {
+        if value[1] & 0x80 == 0 {
+            return None;
+        }
+        value = &value[1..];
+    }
+    if value.len() > SCALAR_LEN {
+        return None;
+    }
+
+    let mut out = [0u8; SCALAR_LEN];
+    out[SCALAR_LEN - value.len()..].copy_from_slice(value);
+    Some((out, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn der_roundtrip() {
+        let mut r = [0u8; SCALAR_LEN];
+        r[0] = 0x80;
+        r[31] = 0x01;
+        let mut s = [0u8; SCALAR_LEN];
+        s[2] = 0x7f;
+
+        let encoded = der_encode(&r, &s);
+        // r needs a leading zero, s drops its two leading zero bytes.
+        assert_eq!(encoded.len(), 2 + (2 + 33) + (2 + 30));
+        assert_eq!(der_decode(&encoded), Some((r, s)));
+
+        // Trailing data and non-minimal encodings are rejected.
+        let mut trailing = encoded.clone();
+        trailing.push(0);
+        assert_eq!(der_decode(&trailing), None);
+
+        let mut one = [0u8; SCALAR_LEN];
+        one[31] = 1;
+        let minimal = [DER_SEQUENCE, 6, DER_INTEGER, 1, 1, DER_INTEGER, 1, 1];
+        assert_eq!(der_decode(&minimal), Some((one, one)));
+        let padded = [DER_SEQUENCE, 7, DER_INTEGER, 2, 0, 1, DER_INTEGER, 1, 1];
+        assert_eq!(der_decode(&padded), None);
+    }
+}
@@ -1,6 +1,7 @@
 use openmls_traits::{types::CryptoError, OpenMlsProvider};
 
 mod crypto;
+mod ecdsa;
 #[cfg(feature = "virtual-clients-draft")]
 mod ff1;
 mod rand;
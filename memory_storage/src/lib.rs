@@ -1,6 +1,11 @@
 use openmls_traits::storage::*;
-use serde::Serialize;
-use std::{collections::HashMap, sync::RwLock};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::HashMap,
+    ops::Deref,
+    sync::{RwLock, RwLockWriteGuard},
+    thread::{self, ThreadId},
+};
 
 #[cfg(feature = "test-utils")]
 use std::io::Write as _;
@@ -12,9 +17,30 @@ mod test_store;
 #[cfg(feature = "persistence")]
 pub mod persistence;
 
+/// The stored values, by storage key.
+type Values = HashMap<Vec<u8>, Vec<u8>>;
+
+/// The value each storage key had before a transaction first wrote it, or
+/// `None` if the key didn't exist.
+type UndoLog = HashMap<Vec<u8>, Option<Vec<u8>>>;
+
+/// An in-memory [`StorageProvider`].
+///
+/// Transactions are scoped to the thread that begins them: the writes of a
+/// thread are only recorded in, and rolled back with, its own open
+/// transactions. A rollback restores the previous values of the keys the
+/// transaction wrote, so writes of other threads to other keys are kept.
+/// Transactions are not isolated from each other though, so concurrent
+/// transactions must not write the same keys, e.g. the same group.
 #[derive(Debug, Default)]
 pub struct MemoryStorage {
     pub values: RwLock<HashMap<Vec<u8>, Vec<u8>>>,
+    /// The undo logs of the open transactions of each thread, innermost last.
+    transactions: RwLock<HashMap<ThreadId, Vec<UndoLog>>>,
+    /// The number of writes left before the next one fails, if a failure is
+    /// injected.
+    #[cfg(feature = "test-utils")]
+    failing_write: RwLock<Option<usize>>,
 }
 
 // For testing we want to clone.
@@ -24,6 +50,7 @@ impl Clone for MemoryStorage {
         let values = self.values.read().unwrap();
         Self {
             values: RwLock::new(values.clone()),
+            ..Default::default()
         }
     }
 }
@@ -80,11 +107,85 @@ impl MemoryStorage {
 
         Ok(Self {
             values: RwLock::new(map),
+            ..Default::default()
         })
     }
 }
 
+// For testing we want to fail writes midway through an operation.
+#[cfg(feature = "test-utils")]
 impl MemoryStorage {
+    /// Lets the write after the next `writes` writes fail with
+    /// [`MemoryStorageError::InjectedFailure`]. Deletes count as writes.
+    pub fn fail_after_writes(&self, writes: usize) {
+        *self.failing_write.write().unwrap() = Some(writes);
+    }
+}
+
+/// Write access to the values, which records the previous value of every
+/// written key in the innermost open transaction of the current thread.
+struct ValuesMut<'a> {
+    values: RwLockWriteGuard<'a, Values>,
+    transactions: RwLockWriteGuard<'a, HashMap<ThreadId, Vec<UndoLog>>>,
+}
+
+impl ValuesMut<'_> {
+    fn record(&mut self, storage_key: &[u8]) {
+        let Some(undo_log) = self
+            .transactions
+            .get_mut(&thread::current().id())
+            .and_then(|undo_logs| undo_logs.last_mut())
+        else {
+            return;
+        };
+        if !undo_log.contains_key(storage_key) {
+            undo_log.insert(storage_key.to_vec(), self.values.get(storage_key).cloned());
+        }
+    }
+
+    fn insert(&mut self, storage_key: Vec<u8>, value: Vec<u8>) {
+        self.record(&storage_key);
+        self.values.insert(storage_key, value);
+    }
+
+    fn remove(&mut self, storage_key: &[u8]) {
+        self.record(storage_key);
+        self.values.remove(storage_key);
+    }
+}
+
+impl Deref for ValuesMut<'_> {
+    type Target = Values;
+
+    fn deref(&self) -> &Values {
+        &self.values
+    }
+}
+
+impl MemoryStorage {
+    /// Internal helper to get write access to the values, failing if a
+    /// failure was injected for this write.
+    fn values_mut(&self) -> Result<ValuesMut<'_>, MemoryStorageError> {
+        #[cfg(feature = "test-utils")]
+        {
+            let mut failing_write = self.failing_write.write().unwrap();
+            match *failing_write {
+                Some(0) => {
+                    *failing_write = None;
+                    return Err(MemoryStorageError::InjectedFailure);
+                }
+                Some(writes) => *failing_write = Some(writes - 1),
+                None => (),
+            }
+        }
+        // The values are always locked before the transactions.
+        let values = self.values.write().unwrap();
+        Ok(ValuesMut {
+            values,
+            transactions: self.transactions.write().unwrap(),
+        })
+    }
+
     /// Internal helper to abstract write operations.
     #[inline(always)]
     fn write<const VERSION: u16>(
@@ -93,7 +194,7 @@ impl MemoryStorage {
         key: &[u8],
         value: Vec<u8>,
     ) -> Result<(), <Self as StorageProvider<CURRENT_VERSION>>::Error> {
-        let mut values = self.values.write().unwrap();
+        let mut values = self.values_mut()?;
         let storage_key = build_key_from_vec::<VERSION>(label, key.to_vec());
 
         #[cfg(feature = "test-utils")]
@@ -110,7 +211,7 @@ impl MemoryStorage {
         key: &[u8],
         value: Vec<u8>,
     ) -> Result<(), <Self as StorageProvider<CURRENT_VERSION>>::Error> {
-        let mut values = self.values.write().unwrap();
+        let mut values = self.values_mut()?;
         let storage_key = build_key_from_vec::<VERSION>(label, key.to_vec());
 
         #[cfg(feature = "test-utils")]
@@ -118,15 +219,15 @@ impl MemoryStorage {
         log::trace!("{}", std::backtrace::Backtrace::capture());
 
         // fetch value from db, falling back to an empty list if doens't exist
-        let list_bytes = values.entry(storage_key).or_insert(b"[]".to_vec());
+        let list_bytes = values.get(&storage_key).map_or(&b"[]"[..], Vec::as_slice);
 
         // parse old value and push new data
         let mut list: Vec<Vec<u8>> = serde_json::from_slice(list_bytes)?;
         list.push(value);
 
-        // write back, reusing the old buffer
-        list_bytes.truncate(0);
-        serde_json::to_writer(list_bytes, &list)?;
+        // write back
+        let list_bytes = serde_json::to_vec(&list)?;
+        values.insert(storage_key, list_bytes);
 
         Ok(())
     }
@@ -137,7 +238,7 @@ impl MemoryStorage {
         key: &[u8],
         value: Vec<u8>,
     ) -> Result<(), <Self as StorageProvider<CURRENT_VERSION>>::Error> {
-        let mut values = self.values.write().unwrap();
+        let mut values = self.values_mut()?;
         let storage_key = build_key_from_vec::<VERSION>(label, key.to_vec());
 
         #[cfg(feature = "test-utils")]
@@ -145,7 +246,7 @@ impl MemoryStorage {
         log::trace!("{}", std::backtrace::Backtrace::capture());
 
         // fetch value from db, falling back to an empty list if doens't exist
-        let list_bytes = values.entry(storage_key).or_insert(b"[]".to_vec());
+        let list_bytes = values.get(&storage_key).map_or(&b"[]"[..], Vec::as_slice);
 
         // parse old value, find value to delete and remove it from list
         let mut list: Vec<Vec<u8>> = serde_json::from_slice(list_bytes)?;
@@ -153,9 +254,9 @@ impl MemoryStorage {
             list.remove(pos);
         }
 
-        // write back, reusing the old buffer
-        list_bytes.truncate(0);
-        serde_json::to_writer(list_bytes, &list)?;
+        // write back
+        let list_bytes = serde_json::to_vec(&list)?;
+        values.insert(storage_key, list_bytes);
 
         Ok(())
     }
@@ -221,7 +322,7 @@ impl MemoryStorage {
         label: &[u8],
         key: &[u8],
     ) -> Result<(), <Self as StorageProvider<CURRENT_VERSION>>::Error> {
-        let mut values = self.values.write().unwrap();
+        let mut values = self.values_mut()?;
 
         let mut storage_key = label.to_vec();
         storage_key.extend_from_slice(key);
@@ -237,6 +338,60 @@ impl MemoryStorage {
     }
 }
 
+impl MemoryStorage {
+    /// Internal helper to remove the undo log of the innermost open
+    /// transaction of the current thread.
+    fn pop_transaction(&self) -> Result<UndoLog, MemoryStorageError> {
+        let mut transactions = self.transactions.write().unwrap();
+        let thread_id = thread::current().id();
+        let undo_logs = transactions
+            .get_mut(&thread_id)
+            .ok_or(MemoryStorageError::NoTransaction)?;
+        let undo_log = undo_logs.pop().ok_or(MemoryStorageError::NoTransaction)?;
+        if undo_logs.is_empty() {
+            transactions.remove(&thread_id);
+        }
+        Ok(undo_log)
+    }
+
+    /// Returns the ids of all groups with a stored tree.
+    ///
+    /// This allows applications to visit all groups in the storage, e.g. to
+    /// migrate them to a new storage format.
+    pub fn group_ids<GroupId: traits::GroupId<CURRENT_VERSION> + DeserializeOwned>(
+        &self,
+    ) -> Result<Vec<GroupId>, MemoryStorageError> {
+        self.keys::<CURRENT_VERSION, _>(TREE_LABEL)
+    }
+
+    /// Returns the references of all stored key packages.
+    ///
+    /// This allows applications to visit all key packages in the storage, e.g.
+    /// to migrate them to a new storage format.
+    pub fn key_package_refs<
+        KeyPackageRef: traits::HashReference<CURRENT_VERSION> + DeserializeOwned,
+    >(
+        &self,
+    ) -> Result<Vec<KeyPackageRef>, MemoryStorageError> {
+        self.keys::<CURRENT_VERSION, _>(KEY_PACKAGE_LABEL)
+    }
+
+    /// Internal helper to read the keys of all values with the given label.
+    fn keys<const VERSION: u16, K: DeserializeOwned>(
+        &self,
+        label: &[u8],
+    ) -> Result<Vec<K>, MemoryStorageError> {
+        let values = self.values.read().unwrap();
+        let version = u16::to_be_bytes(VERSION);
+
+        values
+            .keys()
+            .filter_map(|storage_key| storage_key.strip_prefix(label)?.strip_suffix(&version))
+            .map(|key| serde_json::from_slice(key).map_err(MemoryStorageError::from))
+            .collect()
+    }
+}
+
 /// Errors thrown by the key store.
 #[derive(thiserror::Error, Debug, Copy, Clone, PartialEq, Eq)]
 pub enum MemoryStorageError {
@@ -246,6 +401,11 @@ pub enum MemoryStorageError {
     UnsupportedMethod,
     #[error("Error serializing value.")]
     SerializationError,
+    #[error("There is no open transaction.")]
+    NoTransaction,
+    #[cfg(feature = "test-utils")]
+    #[error("Injected storage failure.")]
+    InjectedFailure,
 }
 
 const KEY_PACKAGE_LABEL: &[u8] = b"KeyPackage";
@@ -282,10 +442,55 @@ const OWN_LEAF_NODE_INDEX_LABEL: &[u8] = b"OwnLeafNodeIndex";
 const EPOCH_SECRETS_LABEL: &[u8] = b"EpochSecrets";
 const RESUMPTION_PSK_STORE_LABEL: &[u8] = b"ResumptionPsk";
 const MESSAGE_SECRETS_LABEL: &[u8] = b"MessageSecrets";
+const FUTURE_EPOCH_MESSAGES_LABEL: &[u8] = b"FutureEpochMessages";
+
+// related to migrations of the stored values
+const MIGRATED_GROUP_LABEL: &[u8] = b"MigratedGroup";
+const MIGRATED_KEY_PACKAGE_LABEL: &[u8] = b"MigratedKeyPackage";
 
 impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
     type Error = MemoryStorageError;
 
+    fn begin_transaction(&self) -> Result<(), Self::Error> {
+        self.transactions
+            .write()
+            .unwrap()
+            .entry(thread::current().id())
+            .or_default()
+            .push(UndoLog::new());
+        Ok(())
+    }
+
+    fn commit_transaction(&self) -> Result<(), Self::Error> {
+        let undo_log = self.pop_transaction()?;
+
+        // Keys written for the first time in a nested transaction are rolled
+        // back with the outer one.
+        let mut transactions = self.transactions.write().unwrap();
+        if let Some(outer_undo_log) = transactions
+            .get_mut(&thread::current().id())
+            .and_then(|undo_logs| undo_logs.last_mut())
+        {
+            for (storage_key, value) in undo_log {
+                outer_undo_log.entry(storage_key).or_insert(value);
+            }
+        }
+        Ok(())
+    }
+
+    fn rollback_transaction(&self) -> Result<(), Self::Error> {
+        let undo_log = self.pop_transaction()?;
+
+        let mut values = self.values.write().unwrap();
+        for (storage_key, value) in undo_log {
+            match value {
+                Some(value) => values.insert(storage_key, value),
+                None => values.remove(&storage_key),
+            };
+        }
+        Ok(())
+    }
+
     fn queue_proposal<
         GroupId: traits::GroupId<CURRENT_VERSION>,
         ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
@@ -332,7 +537,7 @@ impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
         group_id: &GroupId,
         interim_transcript_hash: &InterimTranscriptHash,
     ) -> Result<(), Self::Error> {
-        let mut values = self.values.write().unwrap();
+        let mut values = self.values_mut()?;
         let key = build_key::<CURRENT_VERSION, &GroupId>(INTERIM_TRANSCRIPT_HASH_LABEL, group_id);
         let value = serde_json::to_vec(&interim_transcript_hash).unwrap();
 
@@ -348,7 +553,7 @@ impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
         group_id: &GroupId,
         group_context: &GroupContext,
     ) -> Result<(), Self::Error> {
-        let mut values = self.values.write().unwrap();
+        let mut values = self.values_mut()?;
         let key = build_key::<CURRENT_VERSION, &GroupId>(GROUP_CONTEXT_LABEL, group_id);
         let value = serde_json::to_vec(&group_context).unwrap();
 
@@ -364,7 +569,7 @@ impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
         group_id: &GroupId,
         confirmation_tag: &ConfirmationTag,
     ) -> Result<(), Self::Error> {
-        let mut values = self.values.write().unwrap();
+        let mut values = self.values_mut()?;
         let key = build_key::<CURRENT_VERSION, &GroupId>(CONFIRMATION_TAG_LABEL, group_id);
         let value = serde_json::to_vec(&confirmation_tag).unwrap();
 
@@ -380,7 +585,7 @@ impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
         public_key: &SignaturePublicKey,
         signature_key_pair: &SignatureKeyPair,
     ) -> Result<(), Self::Error> {
-        let mut values = self.values.write().unwrap();
+        let mut values = self.values_mut()?;
         let key =
             build_key::<CURRENT_VERSION, &SignaturePublicKey>(SIGNATURE_KEY_PAIR_LABEL, public_key);
         let value = serde_json::to_vec(&signature_key_pair).unwrap();
@@ -793,6 +998,42 @@ impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
         self.delete::<CURRENT_VERSION>(EPOCH_SECRETS_LABEL, &serde_json::to_vec(group_id)?)
     }
 
+    fn future_epoch_messages<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        FutureEpochMessages: traits::FutureEpochMessages<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<FutureEpochMessages>, Self::Error> {
+        self.read(FUTURE_EPOCH_MESSAGES_LABEL, &serde_json::to_vec(group_id)?)
+    }
+
+    fn supports_future_epoch_messages(&self) -> bool {
+        true
+    }
+
+    fn write_future_epoch_messages<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        FutureEpochMessages: traits::FutureEpochMessages<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        future_epoch_messages: &FutureEpochMessages,
+    ) -> Result<(), Self::Error> {
+        self.write::<CURRENT_VERSION>(
+            FUTURE_EPOCH_MESSAGES_LABEL,
+            &serde_json::to_vec(group_id)?,
+            serde_json::to_vec(future_epoch_messages)?,
+        )
+    }
+
+    fn delete_future_epoch_messages<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete::<CURRENT_VERSION>(FUTURE_EPOCH_MESSAGES_LABEL, &serde_json::to_vec(group_id)?)
+    }
+
     fn write_encryption_epoch_key_pairs<
         GroupId: traits::GroupId<CURRENT_VERSION>,
         EpochKey: traits::EpochKey<CURRENT_VERSION>,
@@ -868,7 +1109,7 @@ impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
         // Get all proposal refs for this group.
         let proposal_refs: Vec<ProposalRef> =
             self.read_list(PROPOSAL_QUEUE_REFS_LABEL, &serde_json::to_vec(group_id)?)?;
-        let mut values = self.values.write().unwrap();
+        let mut values = self.values_mut()?;
         for proposal_ref in proposal_refs {
             // Delete all proposals.
             let key = serde_json::to_vec(&(group_id, proposal_ref))?;
@@ -1083,7 +1324,7 @@ impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
         let serialized_epoch_id = serde_json::to_vec(epoch_id)?;
         // Hold the write lock across the liveness check and the deletion so a
         // material stored concurrently cannot be orphaned.
-        let mut values = self.values.write().unwrap();
+        let mut values = self.values_mut()?;
         let referenced = values
             .iter()
             .any(|(key, value)| is_epoch_tag(key) && value == &serialized_epoch_id);
@@ -1192,7 +1433,7 @@ impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
         // Take the write lock once so the advanced tree and all materials are
         // written together. A reader cannot observe an advanced tree without
         // the materials it produced.
-        let mut values = self.values.write().unwrap();
+        let mut values = self.values_mut()?;
         let tree_key = build_key_from_vec::<CURRENT_VERSION>(
             VC_OPERATION_TREE_LABEL,
             serialized_epoch_id.clone(),
@@ -1259,6 +1500,49 @@ impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
     }
 }
 
+impl MigrationLog<CURRENT_VERSION> for MemoryStorage {
+    fn group_migrated<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+        name: &str,
+    ) -> Result<bool, Self::Error> {
+        let key = serde_json::to_vec(&(group_id, name))?;
+        let migrated: Option<bool> = self.read::<CURRENT_VERSION, _>(MIGRATED_GROUP_LABEL, &key)?;
+        Ok(migrated.is_some())
+    }
+
+    fn record_group_migration<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+        name: &str,
+    ) -> Result<(), Self::Error> {
+        let key = serde_json::to_vec(&(group_id, name))?;
+        let value = serde_json::to_vec(&true)?;
+        self.write::<CURRENT_VERSION>(MIGRATED_GROUP_LABEL, &key, value)
+    }
+
+    fn key_package_migrated<KeyPackageRef: traits::HashReference<CURRENT_VERSION>>(
+        &self,
+        hash_ref: &KeyPackageRef,
+        name: &str,
+    ) -> Result<bool, Self::Error> {
+        let key = serde_json::to_vec(&(hash_ref, name))?;
+        let migrated: Option<bool> =
+            self.read::<CURRENT_VERSION, _>(MIGRATED_KEY_PACKAGE_LABEL, &key)?;
+        Ok(migrated.is_some())
+    }
+
+    fn record_key_package_migration<KeyPackageRef: traits::HashReference<CURRENT_VERSION>>(
+        &self,
+        hash_ref: &KeyPackageRef,
+        name: &str,
+    ) -> Result<(), Self::Error> {
+        let key = serde_json::to_vec(&(hash_ref, name))?;
+        let value = serde_json::to_vec(&true)?;
+        self.write::<CURRENT_VERSION>(MIGRATED_KEY_PACKAGE_LABEL, &key, value)
+    }
+}
+
 /// Build a key with version and label.
 fn build_key_from_vec<const V: u16>(label: &[u8], key: Vec<u8>) -> Vec<u8> {
     let mut key_out = label.to_vec();
@@ -0,0 +1,56 @@
+use openmls_memory_storage::MemoryStorage;
+use openmls_traits::storage::{
+    traits::{self},
+    Entity, Key, StorageProvider, CURRENT_VERSION,
+};
+use serde::{Deserialize, Serialize};
+
+// Test types
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug, Clone)]
+struct TestGroupId(Vec<u8>);
+impl traits::GroupId<CURRENT_VERSION> for TestGroupId {}
+impl Key<CURRENT_VERSION> for TestGroupId {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug, Clone)]
+struct TestHashReference(Vec<u8>);
+impl traits::HashReference<CURRENT_VERSION> for TestHashReference {}
+impl Key<CURRENT_VERSION> for TestHashReference {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct TestBlob(Vec<u8>);
+impl traits::TreeSync<CURRENT_VERSION> for TestBlob {}
+impl traits::GroupContext<CURRENT_VERSION> for TestBlob {}
+impl traits::KeyPackage<CURRENT_VERSION> for TestBlob {}
+impl Entity<CURRENT_VERSION> for TestBlob {}
+
+/// The ids of all groups with a tree and the references of all key packages
+/// can be listed.
+#[test]
+fn list_groups_and_key_packages() {
+    let storage = MemoryStorage::default();
+    let group_a = TestGroupId(b"group a".to_vec());
+    let group_b = TestGroupId(b"group b".to_vec());
+    let group_c = TestGroupId(b"group c".to_vec());
+    let key_package_ref = TestHashReference(b"key package".to_vec());
+    let blob = TestBlob(b"blob".to_vec());
+
+    storage.write_tree(&group_a, &blob).unwrap();
+    storage.write_tree(&group_b, &blob).unwrap();
+    // Groups without a tree are not listed.
+    storage.write_context(&group_c, &blob).unwrap();
+    storage.write_key_package(&key_package_ref, &blob).unwrap();
+
+    let mut group_ids: Vec<TestGroupId> = storage.group_ids().unwrap();
+    group_ids.sort();
+    assert_eq!(group_ids, vec![group_a.clone(), group_b]);
+
+    let key_package_refs: Vec<TestHashReference> = storage.key_package_refs().unwrap();
+    assert_eq!(key_package_refs, vec![key_package_ref.clone()]);
+
+    storage.delete_tree(&group_a).unwrap();
+    storage.delete_key_package(&key_package_ref).unwrap();
+    let group_ids: Vec<TestGroupId> = storage.group_ids().unwrap();
+    assert_eq!(group_ids.len(), 1);
+    let key_package_refs: Vec<TestHashReference> = storage.key_package_refs().unwrap();
+    assert!(key_package_refs.is_empty());
+}
@@ -0,0 +1,91 @@
+use openmls_memory_storage::{MemoryStorage, MemoryStorageError};
+use openmls_traits::storage::{
+    traits::{self},
+    Entity, Key, StorageProvider, CURRENT_VERSION,
+};
+use serde::{Deserialize, Serialize};
+
+// Test types
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct TestGroupId(Vec<u8>);
+impl traits::GroupId<CURRENT_VERSION> for TestGroupId {}
+impl Key<CURRENT_VERSION> for TestGroupId {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct TestTree(Vec<u8>);
+impl traits::TreeSync<CURRENT_VERSION> for TestTree {}
+impl Entity<CURRENT_VERSION> for TestTree {}
+
+/// Committed transactions persist their writes, rolled back transactions
+/// discard them, and nested transactions behave like savepoints.
+///
+/// The `MemoryStorage` also implements the test version of the storage, so
+/// the transaction methods have to be called on the current version
+/// explicitly.
+#[test]
+fn commit_and_rollback() {
+    let group_id = TestGroupId(b"TestGroupId".to_vec());
+    let old_tree = TestTree(b"old".to_vec());
+    let new_tree = TestTree(b"new".to_vec());
+    let storage = MemoryStorage::default();
+    storage.write_tree(&group_id, &old_tree).unwrap();
+
+    // Roll back a write and a delete
+    StorageProvider::<CURRENT_VERSION>::begin_transaction(&storage).unwrap();
+    storage.write_tree(&group_id, &new_tree).unwrap();
+    let tree: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(tree, Some(new_tree.clone()));
+    storage.delete_tree(&group_id).unwrap();
+    StorageProvider::<CURRENT_VERSION>::rollback_transaction(&storage).unwrap();
+    let tree: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(tree, Some(old_tree.clone()));
+
+    // Roll back an inner transaction, but commit the outer one
+    StorageProvider::<CURRENT_VERSION>::begin_transaction(&storage).unwrap();
+    storage.write_tree(&group_id, &new_tree).unwrap();
+    StorageProvider::<CURRENT_VERSION>::begin_transaction(&storage).unwrap();
+    storage.delete_tree(&group_id).unwrap();
+    StorageProvider::<CURRENT_VERSION>::rollback_transaction(&storage).unwrap();
+    StorageProvider::<CURRENT_VERSION>::commit_transaction(&storage).unwrap();
+    let tree: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(tree, Some(new_tree));
+
+    // There is no open transaction left
+    assert_eq!(
+        StorageProvider::<CURRENT_VERSION>::commit_transaction(&storage),
+        Err(MemoryStorageError::NoTransaction)
+    );
+    assert_eq!(
+        StorageProvider::<CURRENT_VERSION>::rollback_transaction(&storage),
+        Err(MemoryStorageError::NoTransaction)
+    );
+}
+
+/// Transactions are scoped to the thread that begins them, so rolling back a
+/// transaction keeps the writes of other threads.
+#[test]
+fn transactions_are_scoped_to_threads() {
+    let group_id = TestGroupId(b"TestGroupId".to_vec());
+    let other_group_id = TestGroupId(b"OtherGroupId".to_vec());
+    let tree = TestTree(b"tree".to_vec());
+    let storage = MemoryStorage::default();
+
+    StorageProvider::<CURRENT_VERSION>::begin_transaction(&storage).unwrap();
+    storage.write_tree(&group_id, &tree).unwrap();
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            // The other thread has no open transaction.
+            assert_eq!(
+                StorageProvider::<CURRENT_VERSION>::commit_transaction(&storage),
+                Err(MemoryStorageError::NoTransaction)
+            );
+            storage.write_tree(&other_group_id, &tree).unwrap();
+        });
+    });
+    StorageProvider::<CURRENT_VERSION>::rollback_transaction(&storage).unwrap();
+
+    let rolled_back_tree: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(rolled_back_tree, None);
+    let other_tree: Option<TestTree> = storage.tree(&other_group_id).unwrap();
+    assert_eq!(other_tree, Some(tree));
+}
@@ -0,0 +1,74 @@
+//! Benchmarks for the tree hash computation when adding, removing and updating
+//! members of large groups.
+//!
+//! Every operation is measured twice: once on a tree with populated tree hash
+//! caches, where only the direct path of the changed leaf is hashed, and once
+//! on a tree without caches, where the whole tree has to be hashed.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use openmls::treesync::tests_and_kats::tree_hash_benchmark::TreeHashBenchmark;
+use openmls_rust_crypto::OpenMlsRustCrypto;
+use openmls_traits::{types::Ciphersuite, OpenMlsProvider};
+
+const MEMBER_COUNTS: [u32; 3] = [1_000, 10_000, 50_000];
+
+type Operation = fn(&TreeHashBenchmark, &OpenMlsRustCrypto, u32) -> Vec<u8>;
+
+const OPERATIONS: [(&str, Operation); 3] = [
+    ("add", |benchmark, provider, _| {
+        benchmark.add(provider.crypto())
+    }),
+    ("remove", |benchmark, provider, member_count| {
+        benchmark.remove(provider.crypto(), member_count / 2)
+    }),
+    ("update", |benchmark, provider, member_count| {
+        benchmark.update(provider.crypto(), member_count / 2)
+    }),
+];
+
+fn tree_hash(c: &mut Criterion) {
+    let provider = &OpenMlsRustCrypto::default();
+    let ciphersuite = Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519;
+
+    let benchmarks: Vec<_> = MEMBER_COUNTS
+        .into_iter()
+        .map(|member_count| {
+            (
+                member_count,
+                TreeHashBenchmark::new(provider, ciphersuite, member_count),
+            )
+        })
+        .collect();
+
+    for (name, operation) in OPERATIONS {
+        let mut group = c.benchmark_group(format!("Tree hash: {name}"));
+        group.sample_size(10);
+        for (member_count, benchmark) in &benchmarks {
+            group.bench_with_input(
+                BenchmarkId::new("cached", member_count),
+                member_count,
+                |b, &member_count| b.iter(|| operation(benchmark, provider, member_count)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new("uncached", member_count),
+                member_count,
+                |b, &member_count| {
+                    b.iter_batched(
+                        || benchmark.without_cache(),
+                        |benchmark| {
+                            operation(&benchmark, provider, member_count);
+                            // Return the tree, so that it isn't dropped while
+                            // measuring.
+                            benchmark
+                        },
+                        BatchSize::LargeInput,
+                    )
+                },
+            );
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, tree_hash);
+criterion_main!(benches);
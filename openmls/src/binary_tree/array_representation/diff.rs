@@ -26,8 +26,8 @@ use super::{
     sorted_iter::sorted_iter,
     tree::{ABinaryTree, ABinaryTreeError},
     treemath::{
-        copath, direct_path, left, lowest_common_ancestor, right, root, LeafNodeIndex,
-        ParentNodeIndex, TreeNodeIndex, TreeSize, MAX_TREE_SIZE, MIN_TREE_SIZE,
+        copath, direct_path, left, lowest_common_ancestor, right, root, subtree_leaves,
+        LeafNodeIndex, ParentNodeIndex, TreeNodeIndex, TreeSize, MAX_TREE_SIZE, MIN_TREE_SIZE,
     },
 };
 
@@ -358,6 +358,18 @@ impl<L: Clone + Debug + Default, P: Clone + Debug + Default> AbDiff<'_, L, P> {
         right(node_index)
     }
 
+    /// Returns `true` if neither the referenced node nor any node below it
+    /// differs from the original tree, i.e. if the whole subtree is read from
+    /// the original tree.
+    pub(crate) fn subtree_is_unchanged(&self, parent_index: ParentNodeIndex) -> bool {
+        let leaves = subtree_leaves(parent_index);
+        let parents =
+            ParentNodeIndex::new(leaves.start().u32())..ParentNodeIndex::new(leaves.end().u32());
+        leaves.end().u32() < self.original_tree.leaf_count()
+            && self.leaf_diff.range(leaves).next().is_none()
+            && self.parent_diff.range(parents).next().is_none()
+    }
+
     // Private helper functions below.
     //////////////////////////////////
 
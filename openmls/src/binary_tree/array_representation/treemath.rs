@@ -1,4 +1,4 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, ops::RangeInclusive};
 
 use serde::{Deserialize, Serialize};
 use tls_codec::{TlsDeserialize, TlsDeserializeBytes, TlsSerialize, TlsSize};
@@ -426,6 +426,13 @@ pub(crate) fn common_direct_path(
     common_path
 }
 
+/// The range of leaves in the subtree below the given parent node.
+pub(crate) fn subtree_leaves(index: ParentNodeIndex) -> RangeInclusive<LeafNodeIndex> {
+    let x = index.to_tree_index();
+    let offset = (1 << level(x)) - 1;
+    LeafNodeIndex::from_tree_index(x - offset)..=LeafNodeIndex::from_tree_index(x + offset)
+}
+
 #[cfg(any(feature = "test-utils", test))]
 pub(crate) fn node_width(n: usize) -> usize {
     if n == 0 {
@@ -460,3 +467,21 @@ fn test_node_not_in_tree() {
         ));
     }
 }
+
+#[test]
+fn test_subtree_leaves() {
+    let tests = [
+        (0u32, 0u32, 1u32),
+        (1, 0, 3),
+        (2, 2, 3),
+        (3, 0, 7),
+        (5, 4, 7),
+        (11, 8, 15),
+    ];
+    for (parent, first, last) in tests {
+        assert_eq!(
+            subtree_leaves(ParentNodeIndex::new(parent)),
+            LeafNodeIndex::new(first)..=LeafNodeIndex::new(last)
+        );
+    }
+}
@@ -240,6 +240,43 @@ fn diff_leaf_access() {
     assert_eq!(leaf_outside_of_diff, &0)
 }
 
+#[test]
+fn diff_unchanged_subtrees() {
+    let nodes = (0..7)
+        .map(|i| {
+            if i % 2 == 0 {
+                TreeNode::leaf(i)
+            } else {
+                TreeNode::parent(i)
+            }
+        })
+        .collect();
+    let tree = MlsBinaryTree::new(nodes).expect("error creating tree");
+
+    let mut diff = tree.empty_diff();
+    assert!((0..3).all(|i| diff.subtree_is_unchanged(ParentNodeIndex::new(i))));
+
+    // Changing a leaf affects the subtrees of its direct path.
+    diff.replace_leaf(LeafNodeIndex::new(3), 33);
+    assert!(diff.subtree_is_unchanged(ParentNodeIndex::new(0)));
+    assert!(!diff.subtree_is_unchanged(ParentNodeIndex::new(1)));
+    assert!(!diff.subtree_is_unchanged(ParentNodeIndex::new(2)));
+
+    // Changing a parent affects its own subtree.
+    let mut diff = tree.empty_diff();
+    *diff.parent_mut(ParentNodeIndex::new(0)) = 11;
+    assert!(!diff.subtree_is_unchanged(ParentNodeIndex::new(0)));
+    assert!(!diff.subtree_is_unchanged(ParentNodeIndex::new(1)));
+    assert!(diff.subtree_is_unchanged(ParentNodeIndex::new(2)));
+
+    // Subtrees that are not part of the original tree are never unchanged.
+    let mut diff = tree.empty_diff();
+    diff.grow_tree().unwrap();
+    assert!(diff.subtree_is_unchanged(ParentNodeIndex::new(1)));
+    assert!(!diff.subtree_is_unchanged(ParentNodeIndex::new(3)));
+    assert!(!diff.subtree_is_unchanged(ParentNodeIndex::new(5)));
+}
+
 #[test]
 fn test_from_components() {
     let leaf_nodes = vec![1u32, 2, 3, 4];
@@ -28,7 +28,10 @@
 //! Similarly, only the [`Verifiable`] struct should implement the
 //! [`tls_codec::Deserialize`] trait.
 
-use openmls_traits::{crypto::OpenMlsCrypto, signatures::Signer};
+use openmls_traits::{
+    crypto::OpenMlsCrypto,
+    signatures::{AsyncSigner, Signer},
+};
 use thiserror::Error;
 use tls_codec::Serialize;
 
@@ -72,19 +75,8 @@ pub trait Signable: Sized {
     where
         Self::SignedOutput: SignedStruct<Self>,
     {
-        let payload = self
-            .unsigned_payload()
-            .map_err(|_| SignatureError::SigningError)?;
-        let payload_sign_content =
-            match SignContent::new(self.label(), payload.clone().into()).tls_serialize_detached() {
-                Ok(p) => p,
-                Err(e) => {
-                    log::error!("Serializing SignContent failed, {e:?}");
-                    return Err(SignatureError::SigningError);
-                }
-            };
-        let signature = signer
-            .sign(&payload_sign_content)
+        let (payload, payload_sign_content) = sign_content(&self)?;
+        let signature = Signer::sign(signer, &payload_sign_content)
             .map_err(|_| SignatureError::SigningError)?;
 
         Ok(Self::SignedOutput::from_payload(
@@ -95,6 +87,47 @@ pub trait Signable: Sized {
     }
 }
 
+/// Sign the payload of `signable` with the given [`AsyncSigner`].
+///
+/// This is a free function rather than a method of [`Signable`], so that the
+/// returned future is [`Send`] whenever `signable` and `signer` can be shared
+/// across threads.
+pub(crate) async fn sign_async<T: Signable>(
+    signable: T,
+    signer: &impl AsyncSigner,
+) -> Result<T::SignedOutput, SignatureError>
+where
+    T::SignedOutput: SignedStruct<T>,
+{
+    let (payload, payload_sign_content) = sign_content(&signable)?;
+    let signature = AsyncSigner::sign(signer, &payload_sign_content)
+        .await
+        .map_err(|_| SignatureError::SigningError)?;
+
+    Ok(T::SignedOutput::from_payload(
+        signable,
+        signature.into(),
+        payload,
+    ))
+}
+
+/// Returns the unsigned payload of `signable` together with the serialized
+/// `SignContent` that is passed to the signer.
+fn sign_content(signable: &impl Signable) -> Result<(Vec<u8>, Vec<u8>), SignatureError> {
+    let payload = signable
+        .unsigned_payload()
+        .map_err(|_| SignatureError::SigningError)?;
+    let payload_sign_content =
+        match SignContent::new(signable.label(), payload.clone().into()).tls_serialize_detached() {
+            Ok(p) => p,
+            Err(e) => {
+                log::error!("Serializing SignContent failed, {e:?}");
+                return Err(SignatureError::SigningError);
+            }
+        };
+    Ok((payload, payload_sign_content))
+}
+
 /// This marker trait must be implemented by all structs that contain a verified
 /// self-signature.
 pub trait VerifiedStruct {}
@@ -69,6 +69,7 @@
 //!   * `kem_output_candidate, ciphertext_candidate = EncryptWithLabel(pub, label, context, plaintext)`
 //!   * `DecryptWithLabel(priv, label, context, kem_output_candidate, ciphertext_candidate) == plaintext`
 
+use crate::ciphersuite::{signable::SignatureError, SignContent};
 use crate::prelude_test::{
     signable::{Signable, SignedStruct, VerifiedStruct},
     Signature, Verifiable,
@@ -180,7 +181,7 @@ impl Verifiable for ParsedSignWithLabel {
         self,
         crypto: &impl openmls_traits::crypto::OpenMlsCrypto,
         pk: &crate::ciphersuite::OpenMlsSignaturePublicKey,
-    ) -> Result<Self::VerifiedStruct, crate::ciphersuite::signable::SignatureError> {
+    ) -> Result<Self::VerifiedStruct, SignatureError> {
         self.verify_no_out(crypto, pk)?;
         Ok(())
     }
@@ -312,17 +313,29 @@ pub fn run_test_vector(
         let my_signature = parsed.clone().sign(&parsed.key).unwrap();
 
         // verify signature
-        parsed
-            .clone()
-            .verify(
-                provider.crypto(),
-                &OpenMlsSignaturePublicKey::new(
-                    public.clone().into(),
-                    ciphersuite.signature_algorithm(),
-                )
-                .unwrap(),
-            )
-            .expect("Signature verification failed");
+        match ciphersuite {
+            // The published vectors for the P-384 and P-521 ciphersuites are
+            // signed over the SHA-256 digest of the `SignContent` instead of
+            // the hash of the signature scheme.
+            Ciphersuite::MLS_256_DHKEMP384_AES256GCM_SHA384_P384
+            | Ciphersuite::MLS_256_DHKEMP521_AES256GCM_SHA512_P521 => {
+                verify_sha256_prehash(ciphersuite, &public, &parsed)
+                    .expect("Signature verification failed");
+            }
+            _ => {
+                parsed
+                    .clone()
+                    .verify(
+                        provider.crypto(),
+                        &OpenMlsSignaturePublicKey::new(
+                            public.clone().into(),
+                            ciphersuite.signature_algorithm(),
+                        )
+                        .unwrap(),
+                    )
+                    .expect("Signature verification failed");
+            }
+        }
 
         // verify own signature
         parsed.signature = my_signature.0;
@@ -406,6 +419,50 @@ pub fn run_test_vector(
     Ok(())
 }
 
+/// Verify the published ECDSA signature in `parsed` over the SHA-256 digest
+/// of its `SignContent`.
+fn verify_sha256_prehash(
+    ciphersuite: Ciphersuite,
+    public: &[u8],
+    parsed: &ParsedSignWithLabel,
+) -> Result<(), SignatureError> {
+    use p384::ecdsa::signature::hazmat::PrehashVerifier;
+    use sha2::{Digest, Sha256};
+    use tls_codec::Serialize as _;
+
+    let sign_content = SignContent::new(&parsed.label, parsed.content.clone().into())
+        .tls_serialize_detached()
+        .map_err(|_| SignatureError::VerificationError)?;
+    let digest = Sha256::digest(sign_content);
+    let signature = parsed.signature.as_slice();
+    let verified = match ciphersuite {
+        Ciphersuite::MLS_256_DHKEMP384_AES256GCM_SHA384_P384 => {
+            let key = p384::ecdsa::VerifyingKey::from_sec1_bytes(public)
+                .map_err(|_| SignatureError::VerificationError)?;
+            let signature = p384::ecdsa::Signature::from_der(signature)
+                .map_err(|_| SignatureError::VerificationError)?;
+            key.verify_prehash(&digest, &signature).is_ok()
+        }
+        Ciphersuite::MLS_256_DHKEMP521_AES256GCM_SHA512_P521 => {
+            let key = p521::ecdsa::VerifyingKey::from_sec1_bytes(public)
+                .map_err(|_| SignatureError::VerificationError)?;
+            let signature = p521::ecdsa::Signature::from_der(signature)
+                .map_err(|_| SignatureError::VerificationError)?;
+            // The digest is shorter than half of the P-521 field, which the
+            // prehash verifier rejects. Left-padding with zeros keeps its value.
+            let mut padded = [0u8; 66];
+            padded[66 - digest.len()..].copy_from_slice(&digest);
+            key.verify_prehash(&padded, &signature).is_ok()
+        }
+        _ => false,
+    };
+    if verified {
+        Ok(())
+    } else {
+        Err(SignatureError::VerificationError)
+    }
+}
+
 #[test]
 fn read_test_vectors() {
     let _ = pretty_env_logger::try_init();
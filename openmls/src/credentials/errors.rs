@@ -1,6 +1,7 @@
 //! Credential errors
 //!
-//! This module exposes [`CredentialError`].
+//! This module exposes [`CredentialError`] and [`CredentialValidationError`],
+//! as well as `X509CredentialError` if the `x509` feature is enabled.
 
 use crate::error::LibraryError;
 use thiserror::Error;
@@ -29,3 +30,71 @@ pub enum BasicCredentialError {
     #[error("Wrong credential type.")]
     WrongCredentialType,
 }
+
+/// An error returned by a [`super::CredentialValidator`] if a credential is
+/// not acceptable.
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum CredentialValidationError {
+    /// The type of credential is not supported by the validator.
+    #[error("Unsupported credential type.")]
+    UnsupportedCredentialType,
+    /// The credential was rejected by the validator.
+    #[error("The credential was rejected: {0}")]
+    Rejected(String),
+    /// The group configuration requires a credential validator, which has to
+    /// be set again after the group was loaded from the storage.
+    #[error("The group requires a credential validator, which has to be set again after loading the group.")]
+    ValidatorMissing,
+}
+
+/// An error that occurs when parsing or validating an `X509Credential`.
+#[cfg(feature = "x509")]
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum X509CredentialError {
+    /// TLS codec error
+    #[error(transparent)]
+    TlsCodecError(#[from] tls_codec::Error),
+    /// Wrong credential type
+    #[error("Wrong credential type.")]
+    WrongCredentialType,
+    /// The certificate chain is empty.
+    #[error("The certificate chain is empty.")]
+    EmptyChain,
+    /// A certificate is not a valid DER-encoded X.509 certificate.
+    #[error("A certificate is not a valid DER-encoded X.509 certificate.")]
+    MalformedCertificate,
+    /// The public key algorithm of a certificate is not supported.
+    #[error("The public key algorithm of a certificate is not supported.")]
+    UnsupportedPublicKeyAlgorithm,
+    /// The signature algorithm of a certificate does not match the key of its issuer.
+    #[error("The signature algorithm of a certificate does not match the key of its issuer.")]
+    SignatureAlgorithmMismatch,
+    /// The system time is before the UNIX epoch.
+    #[error("The system time is before the UNIX epoch.")]
+    SystemTimeBeforeUnixEpoch,
+    /// A certificate in the chain has expired.
+    #[error("A certificate in the chain has expired.")]
+    CertificateExpired,
+    /// A certificate in the chain is not valid yet.
+    #[error("A certificate in the chain is not valid yet.")]
+    CertificateNotValidYet,
+    /// The issuer of a certificate does not match the subject of the next
+    /// certificate in the chain.
+    #[error("The issuer of a certificate does not match the subject of the next certificate in the chain.")]
+    IssuerMismatch,
+    /// A certificate that issued another certificate is not a CA certificate.
+    #[error("A certificate that issued another certificate is not a CA certificate.")]
+    NotACertificateAuthority,
+    /// The path length constraint of a CA certificate is exceeded.
+    #[error("The path length constraint of a CA certificate is exceeded.")]
+    PathLengthExceeded,
+    /// The signature of a certificate is invalid.
+    #[error("The signature of a certificate is invalid.")]
+    InvalidSignature,
+    /// The certificate chain is not issued by any of the trust anchors.
+    #[error("The certificate chain is not issued by any of the trust anchors.")]
+    UntrustedChain,
+    /// The public key of the leaf certificate does not match the signature key.
+    #[error("The public key of the leaf certificate does not match the signature key.")]
+    SignatureKeyMismatch,
+}
@@ -20,9 +20,27 @@
 //!
 //! When receiving a credential update from another member, applications must
 //! query the Authentication Service to ensure the new credential is valid.
+//! This can be done by implementing a [`CredentialValidator`] and setting it
+//! in the [`MlsGroupJoinConfig`] with
+//! [`MlsGroupJoinConfigBuilder::credential_validator()`]. Processing messages,
+//! merging commits, joining through a Welcome and external commits then reject
+//! messages that introduce credentials the validator does not accept. A
+//! validator can also be passed to individual operations, e.g., to
+//! [`MlsGroup::process_message_with_credential_validator()`].
 //!
-//! There are multiple [`CredentialType`]s, although OpenMLS currently only
-//! supports the [`BasicCredential`].
+//! The validator itself is not persisted with the group configuration, only
+//! the fact that the group requires one. After loading a group, processing
+//! messages and merging commits fail with
+//! [`CredentialValidationError::ValidatorMissing`] until the validator is set
+//! again with [`MlsGroup::set_credential_validator()`].
+//!
+//! [`MlsGroupJoinConfig`]: crate::group::MlsGroupJoinConfig
+//! [`MlsGroupJoinConfigBuilder::credential_validator()`]: crate::group::MlsGroupJoinConfigBuilder::credential_validator()
+//! [`MlsGroup::set_credential_validator()`]: crate::group::MlsGroup::set_credential_validator()
+//!
+//! There are multiple [`CredentialType`]s. OpenMLS supports the
+//! [`BasicCredential`] and, with the `x509` feature, the `X509Credential`,
+//! which carries an X.509 certificate chain.
 
 use std::io::{Read, Write};
 
@@ -45,6 +63,11 @@ use crate::group::MlsGroup;
 // Public
 pub mod errors;
 
+#[cfg(feature = "x509")]
+mod x509;
+#[cfg(feature = "x509")]
+pub use x509::*;
+
 /// CredentialType.
 ///
 /// This enum contains variants for the different Credential Types.
@@ -89,6 +112,7 @@ pub mod errors;
         openmls_serialization_helpers::Deserialize,
     )
 )]
+#[cfg_attr(not(feature = "0-8-1-storage-format"), legacy_tags)]
 #[repr(u16)]
 pub enum CredentialType {
     #[cfg_attr(not(feature = "0-8-1-storage-format"), storage_tag = 0)]
@@ -177,19 +201,43 @@ impl From<CredentialType> for u16 {
 
 /// X.509 Certificate.
 ///
-/// This struct contains an X.509 certificate chain.  Note that X.509
-/// certificates are not yet supported by OpenMLS.
+/// This struct contains a single DER-encoded X.509 certificate of the
+/// certificate chain in an X.509 [`Credential`]. Parsing and validating the
+/// chain is done by the `X509Credential`, which requires the `x509` feature.
 ///
 /// ```c
 /// struct {
 ///     opaque cert_data<V>;
 /// } Certificate;
 /// ```
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    Serialize,
+    Deserialize,
+    TlsSize,
+    TlsSerialize,
+    TlsDeserialize,
+    TlsDeserializeBytes,
+)]
 pub struct Certificate {
     cert_data: Vec<u8>,
 }
 
+impl Certificate {
+    /// Creates a new [`Certificate`] from the DER-encoded certificate.
+    pub fn new(cert_data: Vec<u8>) -> Self {
+        Self { cert_data }
+    }
+
+    /// Returns the DER-encoded certificate.
+    pub fn cert_data(&self) -> &[u8] {
+        &self.cert_data
+    }
+}
+
 /// Credential.
 ///
 /// OpenMLS does not look into credentials and only passes them along.
@@ -361,6 +409,131 @@ impl From<&Member> for CredentialWithKey {
     }
 }
 
+/// A validator for the credentials of new or changed group members and of
+/// external senders, usually backed by the Authentication Service.
+///
+/// The validator is called for the credentials of added members, of Update
+/// proposals, of the leaf node in the update path of a Commit, of members
+/// joining through an external Commit and of external senders in a Group
+/// Context Extensions proposal. When joining a group, it is called for the
+/// credentials of all existing members and external senders.
+pub trait CredentialValidator {
+    /// Validates the `credential` that is used together with the given
+    /// `signature_key`. Returns an error if the credential is not acceptable.
+    fn validate_credential(
+        &self,
+        credential: &Credential,
+        signature_key: &SignaturePublicKey,
+    ) -> Result<(), CredentialValidationError>;
+}
+
+impl<T: CredentialValidator + ?Sized> CredentialValidator for std::sync::Arc<T> {
+    fn validate_credential(
+        &self,
+        credential: &Credential,
+        signature_key: &SignaturePublicKey,
+    ) -> Result<(), CredentialValidationError> {
+        (**self).validate_credential(credential, signature_key)
+    }
+}
+
+/// The [`CredentialValidator`] set in a group configuration.
+///
+/// Only whether a validator is set is persisted with the configuration. A
+/// configuration that is loaded from the storage holds a
+/// [`ConfiguredCredentialValidator::Missing`] validator instead, which rejects
+/// all credentials until the actual validator is set again.
+#[derive(Clone)]
+pub(crate) enum ConfiguredCredentialValidator {
+    /// The validator that was set in the configuration.
+    Set(std::sync::Arc<dyn CredentialValidator + Send + Sync>),
+    /// A validator was set in the configuration before it was stored.
+    Missing,
+}
+
+impl ConfiguredCredentialValidator {
+    /// Returns an error if the validator has to be set again.
+    pub(crate) fn check_set(&self) -> Result<(), CredentialValidationError> {
+        match self {
+            Self::Set(_) => Ok(()),
+            Self::Missing => Err(CredentialValidationError::ValidatorMissing),
+        }
+    }
+}
+
+impl std::fmt::Debug for ConfiguredCredentialValidator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Set(_) => f
+                .debug_tuple("ConfiguredCredentialValidator::Set")
+                .finish_non_exhaustive(),
+            Self::Missing => f.write_str("ConfiguredCredentialValidator::Missing"),
+        }
+    }
+}
+
+impl PartialEq for ConfiguredCredentialValidator {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Set(validator), Self::Set(other)) => std::sync::Arc::ptr_eq(validator, other),
+            (Self::Missing, Self::Missing) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for ConfiguredCredentialValidator {}
+
+impl CredentialValidator for ConfiguredCredentialValidator {
+    fn validate_credential(
+        &self,
+        credential: &Credential,
+        signature_key: &SignaturePublicKey,
+    ) -> Result<(), CredentialValidationError> {
+        match self {
+            Self::Set(validator) => validator.validate_credential(credential, signature_key),
+            Self::Missing => Err(CredentialValidationError::ValidatorMissing),
+        }
+    }
+}
+
+/// The credential validators that apply to an operation: the one set in the
+/// group configuration and the one passed to the operation. Credentials are
+/// accepted if all of them accept the credential.
+pub(crate) struct CredentialValidators<'a, V: ?Sized> {
+    configured: Option<&'a ConfiguredCredentialValidator>,
+    explicit: Option<&'a V>,
+}
+
+impl<'a, V: CredentialValidator + ?Sized> CredentialValidators<'a, V> {
+    /// Returns the validators, or `None` if there are none.
+    pub(crate) fn new(
+        configured: Option<&'a ConfiguredCredentialValidator>,
+        explicit: Option<&'a V>,
+    ) -> Option<Self> {
+        (configured.is_some() || explicit.is_some()).then_some(Self {
+            configured,
+            explicit,
+        })
+    }
+}
+
+impl<V: CredentialValidator + ?Sized> CredentialValidator for CredentialValidators<'_, V> {
+    fn validate_credential(
+        &self,
+        credential: &Credential,
+        signature_key: &SignaturePublicKey,
+    ) -> Result<(), CredentialValidationError> {
+        if let Some(configured) = self.configured {
+            configured.validate_credential(credential, signature_key)?;
+        }
+        if let Some(explicit) = self.explicit {
+            explicit.validate_credential(credential, signature_key)?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 impl CredentialWithKey {
     pub fn from_parts(credential: Credential, key: &[u8]) -> Self {
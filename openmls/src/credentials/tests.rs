@@ -49,3 +49,19 @@ fn that_unknown_credential_types_are_de_serialized_correctly() {
         assert_eq!(test, got_serialized);
     }
 }
+
+#[test]
+fn certificate_is_encoded_as_variable_length_vector() {
+    let cert_data = vec![0x30u8; 100];
+    let certificate = Certificate::new(cert_data.clone());
+
+    let encoded = certificate.tls_serialize_detached().unwrap();
+    assert_eq!(
+        encoded,
+        VLBytes::from(cert_data).tls_serialize_detached().unwrap()
+    );
+    assert_eq!(
+        Certificate::tls_deserialize_exact(&encoded).unwrap(),
+        certificate
+    );
+}
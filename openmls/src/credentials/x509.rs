@@ -0,0 +1,769 @@
+//! X.509 credentials.
+//!
+//! An [`X509Credential`] carries a chain of DER-encoded X.509 certificates,
+//! starting with the certificate of the client itself (the leaf certificate)
+//! and followed by the certificates of the CAs that issued it. Validating the
+//! credential means checking that
+//!
+//! - all certificates are valid at the validation time,
+//! - each certificate is issued and signed by the next one in the chain,
+//! - the last certificate is one of the [`X509TrustAnchors`] or is signed by
+//!   one of them, and
+//! - the public key of the leaf certificate is the signature key of the leaf
+//!   node that uses the credential.
+//!
+//! The [`X509CredentialValidator`] performs all of these checks and can be
+//! passed wherever a [`CredentialValidator`] is expected.
+//!
+//! Signatures are verified using the [`OpenMlsCrypto`] provider, so only
+//! certificates with keys of the supported [`SignatureScheme`]s can be
+//! validated.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+#[cfg(target_arch = "wasm32")]
+use web_time::{SystemTime, UNIX_EPOCH};
+
+use openmls_traits::{crypto::OpenMlsCrypto, types::SignatureScheme};
+use tls_codec::{DeserializeBytes, Serialize as TlsSerializeTrait};
+use x509_cert::{
+    der::{Decode, Encode},
+    ext::pkix::{BasicConstraints, KeyUsage},
+    spki::{ObjectIdentifier, SubjectPublicKeyInfoOwned},
+    Certificate as X509Certificate,
+};
+
+use super::{
+    errors::{CredentialValidationError, X509CredentialError},
+    Certificate, Credential, CredentialType, CredentialValidator,
+};
+use crate::ciphersuite::SignaturePublicKey;
+
+const ID_EC_PUBLIC_KEY: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+const SECP256R1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.7");
+const SECP384R1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.132.0.34");
+const SECP521R1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.132.0.35");
+const ID_ED25519: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.112");
+const ID_ED448: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.113");
+const ECDSA_WITH_SHA256: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2");
+const ECDSA_WITH_SHA384: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.3");
+const ECDSA_WITH_SHA512: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.4");
+
+/// X.509 Credential.
+///
+/// An `X509Credential` contains a chain of X.509 certificates as defined in
+/// the MLS protocol spec. The first certificate is the leaf certificate, whose
+/// public key must be the signature key of the leaf node that uses the
+/// credential. Each following certificate is the issuer of the previous one.
+///
+/// ```c
+/// struct {
+///     Certificate chain<V>;
+/// } X509Credential;
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct X509Credential {
+    certificates: Vec<Certificate>,
+    parsed_certificates: Vec<X509Certificate>,
+    serialized_chain: Vec<u8>,
+}
+
+impl X509Credential {
+    /// Create a new X.509 credential from a chain of DER-encoded
+    /// certificates, starting with the leaf certificate.
+    ///
+    /// Errors
+    ///
+    /// Returns an [`X509CredentialError`] if the chain is empty or if one of
+    /// the certificates can't be decoded.
+    pub fn new(chain: Vec<Vec<u8>>) -> Result<Self, X509CredentialError> {
+        Self::from_certificates(chain.into_iter().map(Certificate::new).collect())
+    }
+
+    fn from_certificates(certificates: Vec<Certificate>) -> Result<Self, X509CredentialError> {
+        if certificates.is_empty() {
+            return Err(X509CredentialError::EmptyChain);
+        }
+
+        let parsed_certificates = certificates
+            .iter()
+            .map(|certificate| parse_certificate(certificate.cert_data()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut serialized_chain = Vec::new();
+        for certificate in certificates.iter() {
+            certificate.tls_serialize(&mut serialized_chain)?;
+        }
+
+        Ok(Self {
+            certificates,
+            parsed_certificates,
+            serialized_chain,
+        })
+    }
+
+    /// Get the certificates of the chain, starting with the leaf certificate.
+    pub fn certificates(&self) -> &[Certificate] {
+        &self.certificates
+    }
+
+    /// Get the public key of the leaf certificate.
+    pub fn leaf_public_key(&self) -> SignaturePublicKey {
+        self.leaf()
+            .tbs_certificate
+            .subject_public_key_info
+            .subject_public_key
+            .raw_bytes()
+            .to_vec()
+            .into()
+    }
+
+    /// Get the [`SignatureScheme`] of the public key of the leaf certificate.
+    ///
+    /// Errors
+    ///
+    /// Returns [`X509CredentialError::UnsupportedPublicKeyAlgorithm`] if the
+    /// key can't be used with any of the [`SignatureScheme`]s.
+    pub fn leaf_signature_scheme(&self) -> Result<SignatureScheme, X509CredentialError> {
+        signature_scheme(&self.leaf().tbs_certificate.subject_public_key_info)
+    }
+
+    /// Check that the public key of the leaf certificate is the given
+    /// `signature_key`, i.e., that the credential is bound to the leaf node
+    /// using the `signature_key`.
+    pub fn validate_signature_key(
+        &self,
+        signature_key: &SignaturePublicKey,
+    ) -> Result<(), X509CredentialError> {
+        if self.leaf_public_key() != *signature_key {
+            return Err(X509CredentialError::SignatureKeyMismatch);
+        }
+        Ok(())
+    }
+
+    /// Validate the certificate chain against the `trust_anchors` at the
+    /// current time.
+    pub fn validate(
+        &self,
+        crypto: &impl OpenMlsCrypto,
+        trust_anchors: &X509TrustAnchors,
+    ) -> Result<(), X509CredentialError> {
+        self.validate_with_time(crypto, trust_anchors, SystemTime::now())
+    }
+
+    /// Validate the certificate chain against the `trust_anchors` at the
+    /// given time.
+    pub fn validate_with_time(
+        &self,
+        crypto: &impl OpenMlsCrypto,
+        trust_anchors: &X509TrustAnchors,
+        now: SystemTime,
+    ) -> Result<(), X509CredentialError> {
+        let now = now
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| X509CredentialError::SystemTimeBeforeUnixEpoch)?
+            .as_secs();
+
+        for certificate in self.parsed_certificates.iter() {
+            validate_validity(certificate, now)?;
+        }
+
+        // Each certificate must be issued by the next one in the chain. The
+        // number of intermediate CA certificates below an issuer is checked
+        // against its path length constraint.
+        for (intermediates, pair) in self.parsed_certificates.windows(2).enumerate() {
+            validate_issued_by(crypto, &pair[0], &pair[1], intermediates)?;
+        }
+
+        // The last certificate is either a trust anchor itself or is issued
+        // by one.
+        let last = self
+            .parsed_certificates
+            .last()
+            .ok_or(X509CredentialError::EmptyChain)?;
+        if trust_anchors.certificates.contains(last) {
+            return Ok(());
+        }
+        let intermediates = self.parsed_certificates.len() - 1;
+        let mut result = Err(X509CredentialError::UntrustedChain);
+        for trust_anchor in trust_anchors.certificates.iter().filter(|trust_anchor| {
+            trust_anchor.tbs_certificate.subject == last.tbs_certificate.issuer
+        }) {
+            result = validate_issued_by(crypto, last, trust_anchor, intermediates);
+            if result.is_ok() {
+                break;
+            }
+        }
+        result
+    }
+
+    fn leaf(&self) -> &X509Certificate {
+        // The chain is never empty.
+        &self.parsed_certificates[0]
+    }
+}
+
+impl From<X509Credential> for Credential {
+    fn from(credential: X509Credential) -> Self {
+        Credential::new(CredentialType::X509, credential.serialized_chain)
+    }
+}
+
+impl TryFrom<Credential> for X509Credential {
+    type Error = X509CredentialError;
+
+    fn try_from(credential: Credential) -> Result<Self, Self::Error> {
+        if credential.credential_type() != CredentialType::X509 {
+            return Err(X509CredentialError::WrongCredentialType);
+        }
+
+        let mut certificates = Vec::new();
+        let mut bytes = credential.serialized_content();
+        while !bytes.is_empty() {
+            let (certificate, remainder) = Certificate::tls_deserialize_bytes(bytes)?;
+            certificates.push(certificate);
+            bytes = remainder;
+        }
+
+        Self::from_certificates(certificates)
+    }
+}
+
+/// A set of trusted X.509 certificates, usually root CA certificates, that
+/// certificate chains are validated against.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct X509TrustAnchors {
+    certificates: Vec<X509Certificate>,
+}
+
+impl X509TrustAnchors {
+    /// Create a new set of trust anchors from DER-encoded certificates.
+    ///
+    /// Errors
+    ///
+    /// Returns [`X509CredentialError::MalformedCertificate`] if one of the
+    /// certificates can't be decoded.
+    pub fn new(certificates: Vec<Vec<u8>>) -> Result<Self, X509CredentialError> {
+        let certificates = certificates
+            .iter()
+            .map(|certificate| parse_certificate(certificate))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { certificates })
+    }
+
+    /// Returns the number of trust anchors.
+    pub fn len(&self) -> usize {
+        self.certificates.len()
+    }
+
+    /// Returns `true` if there are no trust anchors.
+    pub fn is_empty(&self) -> bool {
+        self.certificates.is_empty()
+    }
+}
+
+/// A [`CredentialValidator`] for X.509 credentials.
+///
+/// The validator rejects credentials of other types, certificate chains that
+/// are not valid at the validation time or don't chain up to one of the
+/// [`X509TrustAnchors`], and leaf certificates with a public key other than
+/// the signature key used with the credential.
+#[derive(Debug, Clone)]
+pub struct X509CredentialValidator<Crypto: OpenMlsCrypto> {
+    crypto: Crypto,
+    trust_anchors: X509TrustAnchors,
+    validation_time: Option<SystemTime>,
+}
+
+impl<Crypto: OpenMlsCrypto> X509CredentialValidator<Crypto> {
+    /// Create a new validator that validates credentials against the given
+    /// `trust_anchors` at the current time.
+    pub fn new(crypto: Crypto, trust_anchors: X509TrustAnchors) -> Self {
+        Self {
+            crypto,
+            trust_anchors,
+            validation_time: None,
+        }
+    }
+
+    /// Validate credentials at the given time instead of the current time.
+    pub fn with_validation_time(mut self, validation_time: SystemTime) -> Self {
+        self.validation_time = Some(validation_time);
+        self
+    }
+
+    /// Validate the given X.509 `credential` and check that it is bound to
+    /// the `signature_key`.
+    pub fn validate(
+        &self,
+        credential: &X509Credential,
+        signature_key: &SignaturePublicKey,
+    ) -> Result<(), X509CredentialError> {
+        credential.validate_with_time(
+            &self.crypto,
+            &self.trust_anchors,
+            self.validation_time.unwrap_or_else(SystemTime::now),
+        )?;
+        credential.validate_signature_key(signature_key)
+    }
+}
+
+impl<Crypto: OpenMlsCrypto> CredentialValidator for X509CredentialValidator<Crypto> {
+    fn validate_credential(
+        &self,
+        credential: &Credential,
+        signature_key: &SignaturePublicKey,
+    ) -> Result<(), CredentialValidationError> {
+        if credential.credential_type() != CredentialType::X509 {
+            return Err(CredentialValidationError::UnsupportedCredentialType);
+        }
+
+        let credential = X509Credential::try_from(credential.clone())
+            .map_err(|e| CredentialValidationError::Rejected(e.to_string()))?;
+        self.validate(&credential, signature_key)
+            .map_err(|e| CredentialValidationError::Rejected(e.to_string()))
+    }
+}
+
+fn parse_certificate(cert_data: &[u8]) -> Result<X509Certificate, X509CredentialError> {
+    X509Certificate::from_der(cert_data).map_err(|_| X509CredentialError::MalformedCertificate)
+}
+
+/// Returns the [`SignatureScheme`] of a public key.
+fn signature_scheme(
+    subject_public_key_info: &SubjectPublicKeyInfoOwned,
+) -> Result<SignatureScheme, X509CredentialError> {
+    let algorithm = &subject_public_key_info.algorithm;
+    match algorithm.oid {
+        ID_EC_PUBLIC_KEY => match algorithm
+            .parameters
+            .as_ref()
+            .and_then(|parameters| parameters.decode_as::<ObjectIdentifier>().ok())
+        {
+            Some(SECP256R1) => Ok(SignatureScheme::ECDSA_SECP256R1_SHA256),
+            Some(SECP384R1) => Ok(SignatureScheme::ECDSA_SECP384R1_SHA384),
+            Some(SECP521R1) => Ok(SignatureScheme::ECDSA_SECP521R1_SHA512),
+            _ => Err(X509CredentialError::UnsupportedPublicKeyAlgorithm),
+        },
+        ID_ED25519 => Ok(SignatureScheme::ED25519),
+        ID_ED448 => Ok(SignatureScheme::ED448),
+        _ => Err(X509CredentialError::UnsupportedPublicKeyAlgorithm),
+    }
+}
+
+/// Returns the OID of the certificate signature algorithm that corresponds
+/// to a [`SignatureScheme`].
+fn signature_algorithm(
+    signature_scheme: SignatureScheme,
+) -> Result<ObjectIdentifier, X509CredentialError> {
+    match signature_scheme {
+        SignatureScheme::ECDSA_SECP256R1_SHA256 => Ok(ECDSA_WITH_SHA256),
+        SignatureScheme::ECDSA_SECP384R1_SHA384 => Ok(ECDSA_WITH_SHA384),
+        SignatureScheme::ECDSA_SECP521R1_SHA512 => Ok(ECDSA_WITH_SHA512),
+        SignatureScheme::ED25519 => Ok(ID_ED25519),
+        SignatureScheme::ED448 => Ok(ID_ED448),
+        #[allow(unreachable_patterns)]
+        _ => Err(X509CredentialError::UnsupportedPublicKeyAlgorithm),
+    }
+}
+
+fn validate_validity(certificate: &X509Certificate, now: u64) -> Result<(), X509CredentialError> {
+    let validity = &certificate.tbs_certificate.validity;
+    if validity.not_before.to_unix_duration().as_secs() > now {
+        return Err(X509CredentialError::CertificateNotValidYet);
+    }
+    if validity.not_after.to_unix_duration().as_secs() < now {
+        return Err(X509CredentialError::CertificateExpired);
+    }
+    Ok(())
+}
+
+/// Validates that `certificate` is issued by `issuer`, which must be a CA
+/// that allows for `intermediates` CA certificates below it.
+fn validate_issued_by(
+    crypto: &impl OpenMlsCrypto,
+    certificate: &X509Certificate,
+    issuer: &X509Certificate,
+    intermediates: usize,
+) -> Result<(), X509CredentialError> {
+    if certificate.tbs_certificate.issuer != issuer.tbs_certificate.subject {
+        return Err(X509CredentialError::IssuerMismatch);
+    }
+
+    let basic_constraints = issuer
+        .tbs_certificate
+        .get::<BasicConstraints>()
+        .map_err(|_| X509CredentialError::MalformedCertificate)?
+        .map(|(_critical, basic_constraints)| basic_constraints);
+    match basic_constraints {
+        Some(basic_constraints) if basic_constraints.ca => {
+            if let Some(path_len_constraint) = basic_constraints.path_len_constraint {
+                if intermediates > path_len_constraint as usize {
+                    return Err(X509CredentialError::PathLengthExceeded);
+                }
+            }
+        }
+        _ => return Err(X509CredentialError::NotACertificateAuthority),
+    }
+
+    let key_usage = issuer
+        .tbs_certificate
+        .get::<KeyUsage>()
+        .map_err(|_| X509CredentialError::MalformedCertificate)?;
+    if let Some((_critical, key_usage)) = key_usage {
+        if !key_usage.key_cert_sign() {
+            return Err(X509CredentialError::NotACertificateAuthority);
+        }
+    }
+
+    let issuer_public_key_info = &issuer.tbs_certificate.subject_public_key_info;
+    let signature_scheme = signature_scheme(issuer_public_key_info)?;
+    if certificate.signature_algorithm.oid != signature_algorithm(signature_scheme)?
+        || certificate.tbs_certificate.signature.oid != certificate.signature_algorithm.oid
+    {
+        return Err(X509CredentialError::SignatureAlgorithmMismatch);
+    }
+
+    let tbs_certificate = certificate
+        .tbs_certificate
+        .to_der()
+        .map_err(|_| X509CredentialError::MalformedCertificate)?;
+    crypto
+        .verify_signature(
+            signature_scheme,
+            &tbs_certificate,
+            issuer_public_key_info.subject_public_key.raw_bytes(),
+            certificate.signature.raw_bytes(),
+        )
+        .map_err(|_| X509CredentialError::InvalidSignature)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, time::Duration};
+
+    use openmls_basic_credential::SignatureKeyPair;
+    use openmls_rust_crypto::{OpenMlsRustCrypto, RustCrypto};
+    use openmls_traits::{types::Ciphersuite, OpenMlsProvider};
+    use p256::ecdsa::DerSignature;
+    use x509_cert::{
+        builder::{Builder, CertificateBuilder, Profile},
+        der::{asn1::BitString, Encode},
+        name::Name,
+        serial_number::SerialNumber,
+        spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned},
+        time::Validity,
+    };
+
+    use super::*;
+    use crate::{
+        credentials::CredentialWithKey,
+        group::{
+            errors::WelcomeError, MlsGroup, MlsGroupCreateConfig, MlsGroupJoinConfig, StagedWelcome,
+        },
+        key_packages::KeyPackage,
+        prelude::MlsMessageIn,
+    };
+
+    // The clients use Ed25519 keys, while the CAs use P-256 keys.
+    const CIPHERSUITE: Ciphersuite = Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519;
+    const CA_SIGNATURE_SCHEME: SignatureScheme = SignatureScheme::ECDSA_SECP256R1_SHA256;
+
+    /// A CA consisting of a certificate and the corresponding signing key.
+    struct TestCa {
+        certificate: X509Certificate,
+        signing_key: p256::ecdsa::SigningKey,
+    }
+
+    fn signing_key(key_pair: &SignatureKeyPair) -> p256::ecdsa::SigningKey {
+        p256::ecdsa::SigningKey::from_slice(key_pair.private()).unwrap()
+    }
+
+    fn public_key_info(key_pair: &SignatureKeyPair) -> SubjectPublicKeyInfoOwned {
+        match key_pair.signature_scheme() {
+            SignatureScheme::ECDSA_SECP256R1_SHA256 => {
+                let verifying_key =
+                    p256::ecdsa::VerifyingKey::from_sec1_bytes(key_pair.public()).unwrap();
+                SubjectPublicKeyInfoOwned::from_key(verifying_key).unwrap()
+            }
+            SignatureScheme::ED25519 => SubjectPublicKeyInfoOwned {
+                algorithm: AlgorithmIdentifierOwned {
+                    oid: ID_ED25519,
+                    parameters: None,
+                },
+                subject_public_key: BitString::from_bytes(key_pair.public()).unwrap(),
+            },
+            scheme => panic!("the test certificates don't support {scheme:?} keys"),
+        }
+    }
+
+    fn validity() -> Validity {
+        Validity::from_now(Duration::from_secs(3600)).unwrap()
+    }
+
+    fn root_ca(name: &str) -> TestCa {
+        let key_pair = SignatureKeyPair::new(CA_SIGNATURE_SCHEME).unwrap();
+        let signing_key = signing_key(&key_pair);
+        let certificate = CertificateBuilder::new(
+            Profile::Root,
+            SerialNumber::from(1u32),
+            validity(),
+            Name::from_str(&format!("CN={name}")).unwrap(),
+            public_key_info(&key_pair),
+            &signing_key,
+        )
+        .unwrap()
+        .build::<DerSignature>()
+        .unwrap();
+
+        TestCa {
+            certificate,
+            signing_key,
+        }
+    }
+
+    fn leaf_certificate(ca: &TestCa, name: &str, key_pair: &SignatureKeyPair) -> Vec<u8> {
+        CertificateBuilder::new(
+            Profile::Leaf {
+                issuer: ca.certificate.tbs_certificate.subject.clone(),
+                enable_key_agreement: false,
+                enable_key_encipherment: false,
+            },
+            SerialNumber::from(2u32),
+            validity(),
+            Name::from_str(&format!("CN={name}")).unwrap(),
+            public_key_info(key_pair),
+            &ca.signing_key,
+        )
+        .unwrap()
+        .build::<DerSignature>()
+        .unwrap()
+        .to_der()
+        .unwrap()
+    }
+
+    fn trust_anchors(ca: &TestCa) -> X509TrustAnchors {
+        X509TrustAnchors::new(vec![ca.certificate.to_der().unwrap()]).unwrap()
+    }
+
+    #[test]
+    fn valid_chain() {
+        let provider = OpenMlsRustCrypto::default();
+        let ca = root_ca("Root CA");
+        let key_pair = SignatureKeyPair::new(CIPHERSUITE.signature_algorithm()).unwrap();
+
+        let credential = X509Credential::new(vec![
+            leaf_certificate(&ca, "Alice", &key_pair),
+            ca.certificate.to_der().unwrap(),
+        ])
+        .unwrap();
+
+        // The credential survives the round trip through a `Credential`.
+        let mls_credential: Credential = credential.clone().into();
+        assert_eq!(mls_credential.credential_type(), CredentialType::X509);
+        assert_eq!(
+            X509Credential::try_from(mls_credential.clone()).unwrap(),
+            credential
+        );
+
+        assert_eq!(
+            credential.leaf_signature_scheme(),
+            Ok(SignatureScheme::ED25519)
+        );
+        assert_eq!(credential.leaf_public_key().as_slice(), key_pair.public());
+        credential
+            .validate(provider.crypto(), &trust_anchors(&ca))
+            .unwrap();
+
+        // The chain does not have to contain the trust anchor.
+        let credential =
+            X509Credential::new(vec![leaf_certificate(&ca, "Alice", &key_pair)]).unwrap();
+        credential
+            .validate(provider.crypto(), &trust_anchors(&ca))
+            .unwrap();
+
+        let validator = X509CredentialValidator::new(RustCrypto::default(), trust_anchors(&ca));
+        validator
+            .validate_credential(&mls_credential, &key_pair.public().to_vec().into())
+            .unwrap();
+    }
+
+    #[test]
+    fn invalid_chain() {
+        let provider = OpenMlsRustCrypto::default();
+        let ca = root_ca("Root CA");
+        let other_ca = root_ca("Other CA");
+        let key_pair = SignatureKeyPair::new(CIPHERSUITE.signature_algorithm()).unwrap();
+        let other_key_pair = SignatureKeyPair::new(CIPHERSUITE.signature_algorithm()).unwrap();
+
+        assert_eq!(
+            X509Credential::new(vec![]),
+            Err(X509CredentialError::EmptyChain)
+        );
+        assert_eq!(
+            X509Credential::new(vec![vec![1, 2, 3]]),
+            Err(X509CredentialError::MalformedCertificate)
+        );
+
+        let credential =
+            X509Credential::new(vec![leaf_certificate(&ca, "Alice", &key_pair)]).unwrap();
+
+        // Untrusted issuer
+        assert_eq!(
+            credential.validate(provider.crypto(), &trust_anchors(&other_ca)),
+            Err(X509CredentialError::UntrustedChain)
+        );
+
+        // Validation time outside the validity period
+        assert_eq!(
+            credential.validate_with_time(
+                provider.crypto(),
+                &trust_anchors(&ca),
+                SystemTime::now() + Duration::from_secs(7200)
+            ),
+            Err(X509CredentialError::CertificateExpired)
+        );
+        assert_eq!(
+            credential.validate_with_time(
+                provider.crypto(),
+                &trust_anchors(&ca),
+                SystemTime::now() - Duration::from_secs(7200)
+            ),
+            Err(X509CredentialError::CertificateNotValidYet)
+        );
+
+        // Forged certificate: the issuer name matches, but the signature
+        // doesn't.
+        let forged_ca = root_ca("Root CA");
+        let credential =
+            X509Credential::new(vec![leaf_certificate(&forged_ca, "Alice", &key_pair)]).unwrap();
+        assert_eq!(
+            credential.validate(provider.crypto(), &trust_anchors(&ca)),
+            Err(X509CredentialError::InvalidSignature)
+        );
+
+        // A leaf certificate can't issue other certificates.
+        let ca_key_pair = SignatureKeyPair::new(CA_SIGNATURE_SCHEME).unwrap();
+        let leaf =
+            X509Certificate::from_der(&leaf_certificate(&ca, "Alice", &ca_key_pair)).unwrap();
+        let leaf_ca = TestCa {
+            certificate: leaf,
+            signing_key: signing_key(&ca_key_pair),
+        };
+        let credential = X509Credential::new(vec![
+            leaf_certificate(&leaf_ca, "Bob", &other_key_pair),
+            leaf_ca.certificate.to_der().unwrap(),
+        ])
+        .unwrap();
+        assert_eq!(
+            credential.validate(provider.crypto(), &trust_anchors(&ca)),
+            Err(X509CredentialError::NotACertificateAuthority)
+        );
+
+        // Subject-key binding
+        let credential =
+            X509Credential::new(vec![leaf_certificate(&ca, "Alice", &key_pair)]).unwrap();
+        assert_eq!(
+            credential.validate_signature_key(&other_key_pair.public().to_vec().into()),
+            Err(X509CredentialError::SignatureKeyMismatch)
+        );
+        let validator = X509CredentialValidator::new(RustCrypto::default(), trust_anchors(&ca));
+        assert!(matches!(
+            validator
+                .validate_credential(&credential.into(), &other_key_pair.public().to_vec().into()),
+            Err(CredentialValidationError::Rejected(_))
+        ));
+        assert_eq!(
+            validator.validate_credential(
+                &crate::credentials::BasicCredential::new(b"Alice".to_vec()).into(),
+                &key_pair.public().to_vec().into()
+            ),
+            Err(CredentialValidationError::UnsupportedCredentialType)
+        );
+    }
+
+    /// Two clients with X.509 credentials issued by the same CA form a group
+    /// and validate each other's credentials.
+    #[test]
+    fn group_with_x509_credentials() {
+        let alice_provider = OpenMlsRustCrypto::default();
+        let bob_provider = OpenMlsRustCrypto::default();
+        let ca = root_ca("Root CA");
+
+        let alice_key_pair = SignatureKeyPair::new(CIPHERSUITE.signature_algorithm()).unwrap();
+        let alice_credential = CredentialWithKey {
+            credential: X509Credential::new(vec![leaf_certificate(&ca, "Alice", &alice_key_pair)])
+                .unwrap()
+                .into(),
+            signature_key: alice_key_pair.public().to_vec().into(),
+        };
+        let bob_key_pair = SignatureKeyPair::new(CIPHERSUITE.signature_algorithm()).unwrap();
+        let bob_credential = CredentialWithKey {
+            credential: X509Credential::new(vec![leaf_certificate(&ca, "Bob", &bob_key_pair)])
+                .unwrap()
+                .into(),
+            signature_key: bob_key_pair.public().to_vec().into(),
+        };
+
+        let capabilities = crate::treesync::node::leaf_node::Capabilities::builder()
+            .credentials(vec![CredentialType::Basic, CredentialType::X509])
+            .build();
+        // The key package is used for both attempts to join the group.
+        let bob_key_package = KeyPackage::builder()
+            .leaf_node_capabilities(capabilities.clone())
+            .mark_as_last_resort()
+            .build(CIPHERSUITE, &bob_provider, &bob_key_pair, bob_credential)
+            .unwrap();
+
+        let mut alice_group = MlsGroup::new(
+            &alice_provider,
+            &alice_key_pair,
+            &MlsGroupCreateConfig::builder()
+                .ciphersuite(CIPHERSUITE)
+                .capabilities(capabilities)
+                .use_ratchet_tree_extension(true)
+                .build(),
+            alice_credential,
+        )
+        .unwrap();
+        let (_commit, welcome, _group_info) = alice_group
+            .add_members(
+                &alice_provider,
+                &alice_key_pair,
+                &[bob_key_package.key_package().clone()],
+            )
+            .unwrap();
+        alice_group.merge_pending_commit(&alice_provider).unwrap();
+
+        let welcome: MlsMessageIn = welcome.into();
+        let welcome = welcome.into_welcome().unwrap();
+
+        // Bob doesn't trust Alice's CA.
+        let other_ca = root_ca("Other CA");
+        let validator =
+            X509CredentialValidator::new(RustCrypto::default(), trust_anchors(&other_ca));
+        let result = StagedWelcome::build_from_welcome(
+            &bob_provider,
+            &MlsGroupJoinConfig::default(),
+            welcome.clone(),
+        )
+        .unwrap()
+        .with_credential_validator(&validator)
+        .build();
+        assert!(matches!(
+            result,
+            Err(WelcomeError::CredentialValidationError(
+                CredentialValidationError::Rejected(_)
+            ))
+        ));
+
+        // Bob trusts Alice's CA.
+        let validator = X509CredentialValidator::new(RustCrypto::default(), trust_anchors(&ca));
+        StagedWelcome::build_from_welcome(&bob_provider, &MlsGroupJoinConfig::default(), welcome)
+            .unwrap()
+            .with_credential_validator(&validator)
+            .build()
+            .unwrap();
+    }
+}
@@ -38,11 +38,13 @@ impl ExternalSender {
         }
     }
 
-    pub(crate) fn credential(&self) -> &Credential {
+    /// Returns the [`Credential`] of the external sender.
+    pub fn credential(&self) -> &Credential {
         &self.credential
     }
 
-    pub(crate) fn signature_key(&self) -> &SignaturePublicKey {
+    /// Returns the signature key of the external sender.
+    pub fn signature_key(&self) -> &SignaturePublicKey {
         &self.signature_key
     }
 }
@@ -77,7 +79,7 @@ impl SenderExtensionIndex {
     }
 
     /// Returns the internal index as usize
-    pub(crate) fn index(&self) -> usize {
+    pub fn index(&self) -> usize {
         self.0 as usize
     }
 }
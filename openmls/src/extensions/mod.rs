@@ -97,6 +97,7 @@ mod tests;
         openmls_serialization_helpers::Deserialize,
     )
 )]
+#[cfg_attr(not(feature = "0-8-1-storage-format"), legacy_tags)]
 pub enum ExtensionType {
     #[cfg_attr(not(feature = "0-8-1-storage-format"), storage_tag = 0)]
     /// The application id extension allows applications to add an explicit,
@@ -322,6 +323,7 @@ impl From<ExtensionType> for u16 {
         openmls_serialization_helpers::Deserialize,
     )
 )]
+#[cfg_attr(not(feature = "0-8-1-storage-format"), legacy_tags)]
 pub enum Extension {
     #[cfg_attr(not(feature = "0-8-1-storage-format"), storage_tag = 0)]
     /// An [`ApplicationIdExtension`]
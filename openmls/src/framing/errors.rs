@@ -52,6 +52,13 @@ pub enum MessageEncryptionError<StorageError> {
     /// Error reading from or writing to storage
     #[error("Error reading from or writing to storage: {0}")]
     StorageError(StorageError),
+    /// The padding strategy is [`PaddingStrategy::Custom`], but the callback
+    /// is not set. Callbacks are not persisted and have to be set again after
+    /// loading a group.
+    ///
+    /// [`PaddingStrategy::Custom`]: crate::group::PaddingStrategy::Custom
+    #[error("The callback of the custom padding strategy is not set.")]
+    MissingPaddingCallback,
     /// Virtual-clients reuse-guard derivation failed.
     #[cfg(feature = "virtual-clients-draft")]
     #[error(transparent)]
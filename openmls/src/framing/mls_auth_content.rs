@@ -5,7 +5,7 @@
 
 use std::io::{Read, Write};
 
-use openmls_traits::signatures::Signer;
+use openmls_traits::signatures::{AsyncSigner, Signer};
 use serde::{Deserialize, Serialize};
 use tls_codec::{
     Deserialize as TlsDeserializeTrait, Serialize as TlsSerializeTrait, Size, TlsSerialize, TlsSize,
@@ -18,7 +18,7 @@ use super::{
 };
 use crate::{
     binary_tree::LeafNodeIndex,
-    ciphersuite::signable::{Signable, SignedStruct},
+    ciphersuite::signable::{sign_async, Signable, SignedStruct},
     error::LibraryError,
     extensions::SenderExtensionIndex,
 };
@@ -96,6 +96,35 @@ impl AuthenticatedContent {
         context: &GroupContext,
         signer: &impl Signer,
     ) -> Result<Self, LibraryError> {
+        Self::new_content_tbs(framing_parameters, sender, body, context)?
+            .sign(signer)
+            .map_err(|_| LibraryError::custom("Signing failed"))
+    }
+
+    /// Like [`Self::new_and_sign`], but signs with an [`AsyncSigner`].
+    async fn new_and_sign_async(
+        framing_parameters: FramingParameters<'_>,
+        sender: Sender,
+        body: FramedContentBody,
+        context: &GroupContext,
+        signer: &impl AsyncSigner,
+    ) -> Result<Self, LibraryError> {
+        sign_async(
+            Self::new_content_tbs(framing_parameters, sender, body, context)?,
+            signer,
+        )
+        .await
+        .map_err(|_| LibraryError::custom("Signing failed"))
+    }
+
+    /// Build the [`FramedContentTbs`] for the given content. The group context
+    /// is included for member and new member commit senders.
+    fn new_content_tbs(
+        framing_parameters: FramingParameters,
+        sender: Sender,
+        body: FramedContentBody,
+        context: &GroupContext,
+    ) -> Result<FramedContentTbs, LibraryError> {
         let mut content_tbs = FramedContentTbs::new(
             framing_parameters.wire_format(),
             context.group_id().clone(),
@@ -112,29 +141,28 @@ impl AuthenticatedContent {
             content_tbs = content_tbs.with_context(serialized_context);
         }
 
-        content_tbs
-            .sign(signer)
-            .map_err(|_| LibraryError::custom("Signing failed"))
+        Ok(content_tbs)
     }
 
     /// This constructor builds an `AuthenticatedContent` containing an application
     /// message. The sender type is always `SenderType::Member`.
-    pub(crate) fn new_application(
+    pub(crate) async fn new_application(
         sender_leaf_index: LeafNodeIndex,
         authenticated_data: &[u8],
         application_message: &[u8],
         context: &GroupContext,
-        signer: &impl Signer,
+        signer: &impl AsyncSigner,
     ) -> Result<Self, LibraryError> {
         let framing_parameters =
             FramingParameters::new(authenticated_data, WireFormat::PrivateMessage);
-        Self::new_and_sign(
+        Self::new_and_sign_async(
             framing_parameters,
             Sender::Member(sender_leaf_index),
             FramedContentBody::application(application_message),
             context,
             signer,
         )
+        .await
     }
 
     /// This constructor builds an `PublicMessage` containing a Proposal.
@@ -207,20 +235,21 @@ impl AuthenticatedContent {
     /// it's an `External` commit, the `SenderType` is `NewMemberCommit`. If it is an
     /// `External` commit, the context is not signed along with the rest of the
     /// commit.
-    pub(crate) fn commit(
-        framing_parameters: FramingParameters,
+    pub(crate) async fn commit(
+        framing_parameters: FramingParameters<'_>,
         sender: Sender,
         commit: Commit,
         context: &GroupContext,
-        signer: &impl Signer,
+        signer: &impl AsyncSigner,
     ) -> Result<Self, LibraryError> {
-        Self::new_and_sign(
+        Self::new_and_sign_async(
             framing_parameters,
             sender,
             FramedContentBody::commit(commit),
             context,
             signer,
         )
+        .await
     }
 
     /// Get the signature.
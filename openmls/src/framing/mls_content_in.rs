@@ -6,13 +6,13 @@ use crate::{
     error::LibraryError,
     framing::SenderContext,
     group::{errors::ValidationError, GroupEpoch, GroupId},
-    messages::{proposals_in::ProposalIn, CommitIn},
+    messages::{
+        proposals_in::{ProposalIn, ProposalOrRefIn},
+        CommitIn,
+    },
     versions::ProtocolVersion,
 };
 
-#[cfg(feature = "extensions-draft")]
-use crate::messages::proposals_in::ProposalOrRefIn;
-
 use std::io::{Read, Write};
 
 use super::{
@@ -78,7 +78,6 @@ impl FramedContentIn {
         })
     }
 
-    #[cfg(feature = "extensions-draft")]
     pub(crate) fn proposals(&self) -> Option<&[ProposalOrRefIn]> {
         match &self.body {
             FramedContentBodyIn::Commit(commit_in) => Some(commit_in.proposals()),
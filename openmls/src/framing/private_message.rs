@@ -5,8 +5,11 @@ use tls_codec::{Serialize, Size, TlsSerialize, TlsSize};
 use super::mls_auth_content::AuthenticatedContent;
 
 use crate::{
-    binary_tree::array_representation::LeafNodeIndex, error::LibraryError,
-    tree::secret_tree::SecretType, tree::sender_ratchet::Generation,
+    binary_tree::array_representation::LeafNodeIndex,
+    error::LibraryError,
+    group::{PaddingPolicy, PaddingStrategy},
+    tree::secret_tree::SecretType,
+    tree::sender_ratchet::Generation,
 };
 
 #[cfg(feature = "virtual-clients-draft")]
@@ -126,7 +129,7 @@ impl PrivateMessage {
         public_message: &AuthenticatedContent,
         ciphersuite: Ciphersuite,
         message_secrets: &mut MessageSecrets,
-        padding_size: usize,
+        padding_policy: PaddingPolicy,
         #[cfg(feature = "virtual-clients-draft")] emulator_ctx: Option<&EmulatorReuseGuardCtx<'_>>,
     ) -> Result<EncryptionOutput, MessageEncryptionError<T>> {
         log::debug!("PrivateMessage::try_from_authenticated_content");
@@ -142,7 +145,7 @@ impl PrivateMessage {
             public_message,
             ciphersuite,
             message_secrets,
-            padding_size,
+            padding_policy.strategy_for(public_message.content().content_type()),
             #[cfg(feature = "virtual-clients-draft")]
             emulator_ctx,
         )
@@ -164,7 +167,7 @@ impl PrivateMessage {
             public_message,
             ciphersuite,
             message_secrets,
-            padding_size,
+            PaddingStrategy::Fixed(padding_size),
             #[cfg(feature = "virtual-clients-draft")]
             None,
         )
@@ -187,7 +190,7 @@ impl PrivateMessage {
             public_message,
             ciphersuite,
             message_secrets,
-            padding_size,
+            PaddingStrategy::Fixed(padding_size),
             #[cfg(feature = "virtual-clients-draft")]
             None,
         )
@@ -203,7 +206,7 @@ impl PrivateMessage {
         public_message: &AuthenticatedContent,
         ciphersuite: Ciphersuite,
         message_secrets: &mut MessageSecrets,
-        padding_size: usize,
+        padding_strategy: PaddingStrategy,
         #[cfg(feature = "virtual-clients-draft")] emulator_ctx: Option<&EmulatorReuseGuardCtx<'_>>,
     ) -> Result<EncryptionOutput, MessageEncryptionError<T>> {
         // https://validation.openmls.tech/#valn1305
@@ -277,10 +280,9 @@ impl PrivateMessage {
                 crypto,
                 &Self::encode_padded_ciphertext_content_detached(
                     public_message,
-                    padding_size,
+                    padding_strategy,
                     ciphersuite.mac_length(),
-                )
-                .map_err(LibraryError::missing_bound_check)?,
+                )?,
                 &private_message_content_aad_bytes,
                 &prepared_nonce,
             )
@@ -352,25 +354,20 @@ impl PrivateMessage {
     }
 
     /// Encodes the `PrivateMessageContent` struct with padding.
-    fn encode_padded_ciphertext_content_detached(
+    fn encode_padded_ciphertext_content_detached<T>(
         authenticated_content: &AuthenticatedContent,
-        padding_size: usize,
+        padding_strategy: PaddingStrategy,
         mac_len: usize,
-    ) -> Result<Vec<u8>, tls_codec::Error> {
+    ) -> Result<Vec<u8>, MessageEncryptionError<T>> {
         let plaintext_length = authenticated_content
             .content()
             .serialized_len_without_type()
             + authenticated_content.auth.tls_serialized_len();
 
-        let padding_length = if padding_size > 0 {
-            // Calculate padding block size.
-            // Only the AEAD tag is added.
-            let padding_offset = plaintext_length + mac_len;
-            // Return padding block size
-            (padding_size - (padding_offset % padding_size)) % padding_size
-        } else {
-            0
-        };
+        // Only the AEAD tag is added.
+        let padding_length = padding_strategy
+            .padding_length(plaintext_length + mac_len)
+            .ok_or(MessageEncryptionError::MissingPaddingCallback)?;
 
         // Persist all initial fields manually (avoids cloning them)
         let buffer = &mut Vec::with_capacity(plaintext_length + padding_length);
@@ -379,13 +376,19 @@ impl PrivateMessage {
         // is not part of the struct as per MLS spec.
         authenticated_content
             .content()
-            .serialize_without_type(buffer)?;
-        authenticated_content.auth.tls_serialize(buffer)?;
+            .serialize_without_type(buffer)
+            .map_err(LibraryError::missing_bound_check)?;
+        authenticated_content
+            .auth
+            .tls_serialize(buffer)
+            .map_err(LibraryError::missing_bound_check)?;
         // Note: The `tls_codec::Serialize` implementation for `&[u8]` prepends the length.
         // We do not want this here and thus use the "raw" `write_all` method.
-        buffer
-            .write_all(&vec![0u8; padding_length])
-            .map_err(|_| Error::EncodingError("Failed to write padding.".into()))?;
+        buffer.write_all(&vec![0u8; padding_length]).map_err(|_| {
+            LibraryError::missing_bound_check(Error::EncodingError(
+                "Failed to write padding.".into(),
+            ))
+        })?;
 
         Ok(buffer.to_vec())
     }
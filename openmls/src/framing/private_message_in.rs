@@ -34,7 +34,16 @@ use super::*;
 /// } PrivateMessage;
 /// ```
 #[derive(
-    Debug, PartialEq, Eq, Clone, TlsSerialize, TlsSize, TlsDeserialize, TlsDeserializeBytes,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    serde::Serialize,
+    serde::Deserialize,
+    TlsSerialize,
+    TlsSize,
+    TlsDeserialize,
+    TlsDeserializeBytes,
 )]
 pub struct PrivateMessageIn {
     group_id: GroupId,
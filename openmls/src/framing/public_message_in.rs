@@ -3,13 +3,20 @@
 //! A PublicMessageIn is a framing structure for MLS messages. It can contain
 //! Proposals, Commits and application messages.
 
-use crate::{error::LibraryError, group::errors::ValidationError, versions::ProtocolVersion};
+use crate::{
+    ciphersuite::signable::Verifiable,
+    error::LibraryError,
+    group::errors::ValidationError,
+    messages::proposals_in::{ProposalIn, ProposalOrRefIn},
+    treesync::LeafNodeIn,
+    versions::ProtocolVersion,
+};
 
 use super::{
     mls_auth_content::FramedContentAuthData,
     mls_auth_content_in::{AuthenticatedContentIn, VerifiableAuthenticatedContentIn},
     mls_content::{framed_content_tbs_serialized_detached, AuthenticatedContentTbm},
-    mls_content_in::FramedContentIn,
+    mls_content_in::{FramedContentBodyIn, FramedContentIn},
     *,
 };
 
@@ -166,6 +173,56 @@ impl PublicMessageIn {
         Ok(())
     }
 
+    /// Verify the membership tag of this message with the given raw
+    /// membership key. The `serialized_context` is the serialized
+    /// [`GroupContext`](crate::group::GroupContext) of the epoch the message
+    /// was sent in.
+    ///
+    /// This only checks the tag. Use [`PublicGroup::process_message()`] or
+    /// [`MlsGroup::process_message()`] to validate the message for a group.
+    ///
+    /// [`PublicGroup::process_message()`]: crate::group::PublicGroup::process_message()
+    /// [`MlsGroup::process_message()`]: crate::group::MlsGroup::process_message()
+    pub fn verify_membership_tag(
+        &self,
+        crypto: &impl openmls_traits::crypto::OpenMlsCrypto,
+        ciphersuite: Ciphersuite,
+        membership_key: &[u8],
+        serialized_context: &[u8],
+    ) -> Result<(), ValidationError> {
+        let membership_key = MembershipKey::from_secret(Secret::from_slice(membership_key));
+        self.verify_membership(crypto, ciphersuite, &membership_key, serialized_context)
+    }
+
+    /// Verify the signature of this message with the signature key of its
+    /// sender. The `serialized_context` is the serialized
+    /// [`GroupContext`](crate::group::GroupContext) of the epoch the message
+    /// was sent in. It is only signed by members and new members joining
+    /// with an external commit.
+    ///
+    /// This only checks the signature. Use [`PublicGroup::process_message()`]
+    /// or [`MlsGroup::process_message()`] to validate the message for a
+    /// group.
+    ///
+    /// [`PublicGroup::process_message()`]: crate::group::PublicGroup::process_message()
+    /// [`MlsGroup::process_message()`]: crate::group::MlsGroup::process_message()
+    pub fn verify_signature(
+        &self,
+        crypto: &impl openmls_traits::crypto::OpenMlsCrypto,
+        ciphersuite: Ciphersuite,
+        signature_key: &SignaturePublicKey,
+        serialized_context: &[u8],
+    ) -> Result<(), ValidationError> {
+        let signature_key = OpenMlsSignaturePublicKey::from_signature_key(
+            signature_key.clone(),
+            ciphersuite.signature_algorithm(),
+        );
+        self.clone()
+            .into_verifiable_content(serialized_context.to_vec())
+            .verify_no_out(crypto, &signature_key)
+            .map_err(|_| ValidationError::InvalidSignature)
+    }
+
     /// Get the group epoch.
     pub fn epoch(&self) -> GroupEpoch {
         self.content.epoch
@@ -198,6 +255,39 @@ impl PublicMessageIn {
     pub fn confirmation_tag(&self) -> Option<&ConfirmationTag> {
         self.auth.confirmation_tag.as_ref()
     }
+
+    /// Get the authenticated data of the message.
+    pub fn authenticated_data(&self) -> &[u8] {
+        self.content.authenticated_data.as_slice()
+    }
+
+    /// Get the (unverified) proposal, if this message is a proposal.
+    ///
+    /// Note: The proposal is only validated when the message is processed.
+    pub fn unverified_proposal(&self) -> Option<&ProposalIn> {
+        match &self.content.body {
+            FramedContentBodyIn::Proposal(proposal) => Some(proposal),
+            _ => None,
+        }
+    }
+
+    /// Get the (unverified) committed proposals, if this message is a commit.
+    ///
+    /// Note: The proposals are only validated when the message is processed.
+    pub fn unverified_committed_proposals(&self) -> Option<&[ProposalOrRefIn]> {
+        self.content.proposals()
+    }
+
+    /// Get the (unverified) leaf node of the update path, if this message is
+    /// a commit with a path.
+    ///
+    /// Note: The leaf node is only validated when the message is processed.
+    pub fn unverified_path_leaf_node(&self) -> Option<&LeafNodeIn> {
+        match &self.content.body {
+            FramedContentBodyIn::Commit(commit) => commit.path().map(|path| path.leaf_node()),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
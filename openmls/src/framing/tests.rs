@@ -11,7 +11,7 @@ use crate::{
     ciphersuite::signable::{Signable, SignatureError},
     extensions::Extensions,
     framing::*,
-    group::errors::*,
+    group::{errors::*, PaddingPolicy},
     key_packages::tests::key_package,
     prelude::LeafNodeParameters,
     schedule::psk::PskSecret,
@@ -287,7 +287,7 @@ fn wire_format_checks() {
             &plaintext,
             ciphersuite,
             &mut message_secrets,
-            0,
+            PaddingPolicy::default(),
             #[cfg(feature = "virtual-clients-draft")]
             None,
         )
@@ -347,15 +347,16 @@ fn membership_tag() {
     let membership_key = MembershipKey::from_secret(
         Secret::random(ciphersuite, provider.rand()).expect("Not enough randomness."),
     );
-    let public_message: PublicMessage = AuthenticatedContent::new_application(
-        LeafNodeIndex::new(987543210),
-        &[1, 2, 3],
-        &[4, 5, 6],
-        &group_context,
-        &signature_keys,
-    )
-    .expect("An unexpected error occurred.")
-    .into();
+    let public_message: PublicMessage =
+        crate::utils::resolve_ready(AuthenticatedContent::new_application(
+            LeafNodeIndex::new(987543210),
+            &[1, 2, 3],
+            &[4, 5, 6],
+            &group_context,
+            &signature_keys,
+        ))
+        .expect("An unexpected error occurred.")
+        .into();
 
     let mut public_message = PublicMessageIn::from(public_message);
 
@@ -383,6 +384,14 @@ fn membership_tag() {
             &serialized_context
         )
         .is_ok());
+    assert!(public_message
+        .verify_membership_tag(
+            provider.crypto(),
+            ciphersuite,
+            membership_key.as_slice(),
+            &serialized_context
+        )
+        .is_ok());
 
     // Change the content of the plaintext message
     public_message.set_content(FramedContentBodyIn::Application(vec![7, 8, 9].into()));
@@ -479,13 +488,13 @@ fn unknown_sender<Provider: OpenMlsProvider>(ciphersuite: Ciphersuite, provider:
 
     // Alice sends a message with a sender that is outside of the group
     // Expected result: SenderError::UnknownSender
-    let bogus_sender_message = AuthenticatedContent::new_application(
+    let bogus_sender_message = crate::utils::resolve_ready(AuthenticatedContent::new_application(
         LeafNodeIndex::new(0),
         &[],
         &[1, 2, 3],
         alice_group.export_group_context(),
         &alice_signature_keys,
-    )
+    ))
     .expect("Could not create new ApplicationMessage.");
 
     let enc_message = PrivateMessage::encrypt_with_different_header::<StorageError>(
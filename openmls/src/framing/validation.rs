@@ -329,10 +329,17 @@ impl UnverifiedMessage {
     pub fn committed_proposals(&self) -> Option<&[ProposalOrRefIn]> {
         self.verifiable_content.committed_proposals()
     }
+
+    /// Get the [`Sender`] of the message.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn sender(&self) -> &Sender {
+        self.verifiable_content.sender()
+    }
 }
 
 /// A message that has passed all syntax and semantics checks.
 #[derive(Debug)]
+#[cfg_attr(any(test, feature = "test-utils"), derive(Clone, PartialEq))]
 pub struct ProcessedMessage {
     group_id: GroupId,
     epoch: GroupEpoch,
@@ -448,6 +455,11 @@ impl ProcessedMessage {
         &self.content
     }
 
+    /// Returns a mutable reference to the content of the message.
+    pub(crate) fn content_mut(&mut self) -> &mut ProcessedMessageContent {
+        &mut self.content
+    }
+
     /// Returns the content of the message and consumes the message.
     pub fn into_content(self) -> ProcessedMessageContent {
         self.content
@@ -482,6 +494,7 @@ impl ProcessedMessage {
 /// See the content variants' documentation for more information.
 /// [`StagedCommit`] and [`QueuedProposal`] can be inspected for authorization purposes.
 #[derive(Debug)]
+#[cfg_attr(any(test, feature = "test-utils"), derive(Clone, PartialEq))]
 pub enum ProcessedMessageContent {
     /// An application message.
     ///
@@ -538,6 +551,7 @@ pub enum ProcessedMessageContent {
 
 /// Application message received through a [ProcessedMessage].
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "test-utils"), derive(Clone))]
 pub struct ApplicationMessage {
     bytes: Vec<u8>,
 }
@@ -11,6 +11,7 @@ pub use super::mls_group::errors::*;
 use super::public_group::errors::CreationFromExternalError;
 use crate::{
     ciphersuite::signable::SignatureError,
+    credentials::errors::CredentialValidationError,
     error::LibraryError,
     extensions::errors::{ExtensionError, InvalidExtensionError},
     framing::errors::MessageDecryptionError,
@@ -101,6 +102,9 @@ pub enum WelcomeError<StorageError> {
     /// A group with this [`GroupId`] already exists.
     #[error("A group with this [`GroupId`] already exists.")]
     GroupAlreadyExists,
+    /// See [`CredentialValidationError`] for more details.
+    #[error(transparent)]
+    CredentialValidationError(#[from] CredentialValidationError),
     /// A virtual-clients error occurred while deriving or validating the
     /// virtual client's join material.
     #[cfg(feature = "virtual-clients-draft")]
@@ -168,13 +172,19 @@ impl<StorageError> From<ExternalCommitBuilderError<StorageError>>
                 ExternalCommitError::StorageError(error)
             }
             // These should not happen since `join_by_external_commit` doesn't
-            // take proposals as input.
+            // take proposals or a credential validator as input.
             ExternalCommitBuilderError::InvalidProposal(e) => {
                 log::error!("Error validating proposal in external commit: {e}");
                 ExternalCommitError::LibraryError(LibraryError::custom(
                     "Error creating external commit",
                 ))
             }
+            ExternalCommitBuilderError::CredentialValidationError(e) => {
+                log::error!("Error validating credential in external commit: {e}");
+                ExternalCommitError::LibraryError(LibraryError::custom(
+                    "Error creating external commit",
+                ))
+            }
         }
     }
 }
@@ -530,6 +540,15 @@ pub enum ProposalValidationError {
     /// Regular Commits may not contain ExternalInit proposals, but one was found
     #[error("Found ExternalInit proposal in regular commit")]
     ExternalInitProposalInRegularCommit,
+    /// A Commit may contain at most one ReInit proposal.
+    #[error("A Commit may contain at most one ReInit proposal.")]
+    MultipleReInitProposals,
+    /// A Commit that contains a ReInit proposal may not contain any other proposals.
+    #[error("A Commit that contains a ReInit proposal may not contain any other proposals.")]
+    ReInitWithOtherProposals,
+    /// The ReInit proposal's protocol version is lower than the group's protocol version.
+    #[error("The ReInit proposal's protocol version is lower than the group's protocol version.")]
+    ReInitVersionDowngrade,
 }
 
 /// External Commit validaton error
@@ -653,6 +672,9 @@ pub enum MergeCommitError<StorageError> {
     /// Error writing updated group to storage.
     #[error("Error writing updated group data to storage.")]
     StorageError(StorageError),
+    /// See [`CredentialValidationError`] for more details.
+    #[error(transparent)]
+    CredentialValidationError(#[from] CredentialValidationError),
 }
 
 #[cfg(feature = "extensions-draft")]
@@ -0,0 +1,706 @@
+//! This module contains helpers for detecting forks. Members exchange [`EpochCheckpoint`]s, which
+//! bind the confirmation tag of an epoch to the sender and are authenticated with the epoch
+//! authenticator and the sender's signature key. Received checkpoints are compared against the
+//! current epoch and a [`CheckpointHistory`] of past epochs, and the resulting [`ForkReport`] can
+//! be used to remove and re-add the members of the diverged partition.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use openmls_traits::{crypto::OpenMlsCrypto, signatures::Signer, types::Ciphersuite};
+use serde::{Deserialize, Serialize};
+use tls_codec::{
+    Serialize as TlsSerializeTrait, TlsDeserialize, TlsDeserializeBytes, TlsSerialize, TlsSize,
+};
+
+use super::readd::{ReAddError, ReAddExpectKeyPackages};
+use crate::{
+    binary_tree::LeafNodeIndex,
+    ciphersuite::{
+        signable::{Signable, SignatureError, SignedStruct, Verifiable, VerifiedStruct},
+        Mac, OpenMlsSignaturePublicKey, Secret, Signature, SignaturePublicKey,
+    },
+    error::LibraryError,
+    group::{commit_builder::CommitBuilder, GroupEpoch, GroupId, MlsGroup, MlsGroupStateError},
+    messages::ConfirmationTag,
+};
+
+const SIGNATURE_EPOCH_CHECKPOINT_LABEL: &str = "EpochCheckpointTBS";
+
+/// The input to the MAC that is computed with the epoch authenticator.
+#[derive(TlsSerialize, TlsSize)]
+struct EpochCheckpointMacInput<'a> {
+    group_id: &'a GroupId,
+    epoch: GroupEpoch,
+    sender: LeafNodeIndex,
+    confirmation_tag: &'a ConfirmationTag,
+}
+
+#[derive(Debug, PartialEq, Clone, TlsDeserialize, TlsDeserializeBytes, TlsSerialize, TlsSize)]
+struct EpochCheckpointTbs {
+    group_id: GroupId,
+    epoch: GroupEpoch,
+    sender: LeafNodeIndex,
+    confirmation_tag: ConfirmationTag,
+    authenticator_mac: Mac,
+}
+
+/// A checkpoint of the epoch a member is in, created with
+/// [`MlsGroup::epoch_checkpoint()`] and sent to the other members.
+///
+/// When receiving a serialized checkpoint, it can only be deserialized into a
+/// [`VerifiableEpochCheckpoint`], which is verified as part of
+/// [`MlsGroup::detect_fork()`].
+#[derive(Debug, PartialEq, Clone, TlsSerialize, TlsSize)]
+pub struct EpochCheckpoint {
+    payload: EpochCheckpointTbs,
+    signature: Signature,
+}
+
+impl EpochCheckpoint {
+    /// Returns the epoch of the checkpoint.
+    pub fn epoch(&self) -> GroupEpoch {
+        self.payload.epoch
+    }
+
+    /// Returns the leaf index of the member that created the checkpoint.
+    pub fn sender(&self) -> LeafNodeIndex {
+        self.payload.sender
+    }
+}
+
+/// A received [`EpochCheckpoint`] of which the signature has not been verified.
+#[derive(Debug, PartialEq, Clone, TlsDeserialize, TlsDeserializeBytes, TlsSerialize, TlsSize)]
+pub struct VerifiableEpochCheckpoint {
+    payload: EpochCheckpointTbs,
+    signature: Signature,
+}
+
+impl VerifiableEpochCheckpoint {
+    /// Returns the (unverified) group ID of the checkpoint.
+    pub fn group_id(&self) -> &GroupId {
+        &self.payload.group_id
+    }
+
+    /// Returns the (unverified) epoch of the checkpoint.
+    pub fn epoch(&self) -> GroupEpoch {
+        self.payload.epoch
+    }
+
+    /// Returns the (unverified) leaf index of the member that created the checkpoint.
+    pub fn sender(&self) -> LeafNodeIndex {
+        self.payload.sender
+    }
+}
+
+impl Signable for EpochCheckpointTbs {
+    type SignedOutput = EpochCheckpoint;
+
+    fn unsigned_payload(&self) -> Result<Vec<u8>, tls_codec::Error> {
+        self.tls_serialize_detached()
+    }
+
+    fn label(&self) -> &str {
+        SIGNATURE_EPOCH_CHECKPOINT_LABEL
+    }
+}
+
+impl SignedStruct<EpochCheckpointTbs> for EpochCheckpoint {
+    fn from_payload(
+        payload: EpochCheckpointTbs,
+        signature: Signature,
+        _serialized_payload: Vec<u8>,
+    ) -> Self {
+        Self { payload, signature }
+    }
+}
+
+impl Verifiable for VerifiableEpochCheckpoint {
+    type VerifiedStruct = EpochCheckpoint;
+
+    fn unsigned_payload(&self) -> Result<Vec<u8>, tls_codec::Error> {
+        self.payload.tls_serialize_detached()
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn label(&self) -> &str {
+        SIGNATURE_EPOCH_CHECKPOINT_LABEL
+    }
+
+    fn verify(
+        self,
+        crypto: &impl OpenMlsCrypto,
+        pk: &OpenMlsSignaturePublicKey,
+    ) -> Result<Self::VerifiedStruct, SignatureError> {
+        self.verify_no_out(crypto, pk)?;
+        Ok(EpochCheckpoint {
+            payload: self.payload,
+            signature: self.signature,
+        })
+    }
+}
+
+impl VerifiedStruct for EpochCheckpoint {}
+
+/// The state of one of our own epochs that received checkpoints are compared against.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EpochRecord {
+    epoch: GroupEpoch,
+    confirmation_tag: ConfirmationTag,
+    epoch_authenticator: Secret,
+    /// The signature keys of the members in this epoch.
+    #[serde(default)]
+    signature_keys: Vec<(LeafNodeIndex, SignaturePublicKey)>,
+}
+
+impl EpochRecord {
+    fn from_group(group: &MlsGroup) -> Self {
+        Self {
+            epoch: group.epoch(),
+            confirmation_tag: group.confirmation_tag().clone(),
+            epoch_authenticator: Secret::from_slice(group.epoch_authenticator().as_slice()),
+            signature_keys: group
+                .members()
+                .map(|member| (member.index, member.signature_key.into()))
+                .collect(),
+        }
+    }
+
+    fn signature_key(&self, leaf_index: LeafNodeIndex) -> Option<&SignaturePublicKey> {
+        self.signature_keys
+            .iter()
+            .find(|(index, _)| *index == leaf_index)
+            .map(|(_, signature_key)| signature_key)
+    }
+
+    fn authenticator_mac(
+        &self,
+        crypto: &impl OpenMlsCrypto,
+        ciphersuite: Ciphersuite,
+        group_id: &GroupId,
+        sender: LeafNodeIndex,
+        confirmation_tag: &ConfirmationTag,
+    ) -> Result<Mac, LibraryError> {
+        let mac_input = EpochCheckpointMacInput {
+            group_id,
+            epoch: self.epoch,
+            sender,
+            confirmation_tag,
+        }
+        .tls_serialize_detached()
+        .map_err(LibraryError::missing_bound_check)?;
+        Mac::new(crypto, ciphersuite, &self.epoch_authenticator, &mac_input)
+            .map_err(LibraryError::unexpected_crypto_error)
+    }
+
+    /// Returns `true` if the checkpoint was created in the same state as this epoch.
+    fn matches(
+        &self,
+        crypto: &impl OpenMlsCrypto,
+        ciphersuite: Ciphersuite,
+        checkpoint: &EpochCheckpointTbs,
+    ) -> Result<bool, LibraryError> {
+        let expected_mac = self.authenticator_mac(
+            crypto,
+            ciphersuite,
+            &checkpoint.group_id,
+            checkpoint.sender,
+            &checkpoint.confirmation_tag,
+        )?;
+        Ok(checkpoint.confirmation_tag == self.confirmation_tag
+            && checkpoint.authenticator_mac == expected_mac)
+    }
+}
+
+/// A record of our own past epochs, so that checkpoints of members that lag behind can be
+/// compared as well. The application calls [`CheckpointHistory::record()`] after every merged
+/// commit and is responsible for persisting the history.
+///
+/// The history holds the epoch authenticators of past epochs and should be treated as secret.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CheckpointHistory {
+    max_epochs: usize,
+    epochs: VecDeque<EpochRecord>,
+}
+
+impl CheckpointHistory {
+    /// Creates a new, empty history that keeps at most `max_epochs` epochs.
+    pub fn new(max_epochs: usize) -> Self {
+        Self {
+            max_epochs,
+            epochs: VecDeque::new(),
+        }
+    }
+
+    /// Records the current epoch of the group. If the history is full, the oldest epoch is
+    /// dropped. Recording the same epoch twice has no effect.
+    pub fn record(&mut self, group: &MlsGroup) {
+        if self.max_epochs == 0 || self.get(group.epoch()).is_some() {
+            return;
+        }
+        while self.epochs.len() >= self.max_epochs {
+            self.epochs.pop_front();
+        }
+        self.epochs.push_back(EpochRecord::from_group(group));
+    }
+
+    /// Returns the number of recorded epochs.
+    pub fn len(&self) -> usize {
+        self.epochs.len()
+    }
+
+    /// Returns `true` if no epoch is recorded.
+    pub fn is_empty(&self) -> bool {
+        self.epochs.is_empty()
+    }
+
+    fn get(&self, epoch: GroupEpoch) -> Option<&EpochRecord> {
+        self.epochs.iter().find(|record| record.epoch == epoch)
+    }
+
+    fn records(&self) -> impl Iterator<Item = &EpochRecord> {
+        self.epochs.iter()
+    }
+}
+
+/// The outcome of comparing a checkpoint against our own state. The order matters: if a member
+/// sent several checkpoints, the highest outcome is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Verdict {
+    Undetermined,
+    Unverified,
+    InSync,
+    Diverged,
+}
+
+/// The result of [`MlsGroup::detect_fork()`]. Every member that sent a checkpoint appears in
+/// exactly one of the lists.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ForkReport {
+    in_sync: Vec<LeafNodeIndex>,
+    diverged: Vec<LeafNodeIndex>,
+    unverified: Vec<LeafNodeIndex>,
+    undetermined: Vec<LeafNodeIndex>,
+}
+
+impl ForkReport {
+    /// Returns the members whose checkpoints match our own state.
+    pub fn in_sync(&self) -> &[LeafNodeIndex] {
+        &self.in_sync
+    }
+
+    /// Returns the members that sent a checkpoint for an epoch we know, but were in a different
+    /// state in that epoch. These members appear to be in a diverged partition.
+    pub fn diverged(&self) -> &[LeafNodeIndex] {
+        &self.diverged
+    }
+
+    /// Returns the members that sent a checkpoint for an epoch we know, but whose signature
+    /// does not verify with any signature key we know for the member. This is expected if the
+    /// member changed its signature key in a diverged partition, but the checkpoint may also be
+    /// forged, so these members are reported separately from [`ForkReport::diverged()`].
+    pub fn unverified(&self) -> &[LeafNodeIndex] {
+        &self.unverified
+    }
+
+    /// Returns the members whose checkpoints could not be compared, because they are for an
+    /// epoch that is neither the current one nor in the [`CheckpointHistory`].
+    pub fn undetermined(&self) -> &[LeafNodeIndex] {
+        &self.undetermined
+    }
+
+    /// Returns `true` if at least one member appears to be in a diverged partition.
+    pub fn is_forked(&self) -> bool {
+        !self.diverged.is_empty()
+    }
+}
+
+/// Indicates an error occurred while creating an [`EpochCheckpoint`].
+#[derive(Debug, thiserror::Error, PartialEq, Clone)]
+pub enum EpochCheckpointError {
+    /// See [`LibraryError`] for more details.
+    #[error(transparent)]
+    LibraryError(#[from] LibraryError),
+    /// See [`MlsGroupStateError`] for more details.
+    #[error(transparent)]
+    GroupStateError(#[from] MlsGroupStateError),
+    /// See [`SignatureError`] for more details.
+    #[error(transparent)]
+    SignatureError(#[from] SignatureError),
+}
+
+impl MlsGroup {
+    /// Creates an [`EpochCheckpoint`] for the current epoch, signed with `signer`. The
+    /// checkpoint should be sent to the other members, e.g. after merging a commit, so that
+    /// they can detect forks using [`MlsGroup::detect_fork()`].
+    pub fn epoch_checkpoint(
+        &self,
+        crypto: &impl OpenMlsCrypto,
+        signer: &impl Signer,
+    ) -> Result<EpochCheckpoint, EpochCheckpointError> {
+        if !self.is_active() {
+            return Err(MlsGroupStateError::UseAfterEviction.into());
+        }
+
+        let record = EpochRecord::from_group(self);
+        let authenticator_mac = record.authenticator_mac(
+            crypto,
+            self.ciphersuite(),
+            self.group_id(),
+            self.own_leaf_index(),
+            &record.confirmation_tag,
+        )?;
+        let payload = EpochCheckpointTbs {
+            group_id: self.group_id().clone(),
+            epoch: record.epoch,
+            sender: self.own_leaf_index(),
+            confirmation_tag: record.confirmation_tag,
+            authenticator_mac,
+        };
+
+        Ok(payload.sign(signer)?)
+    }
+
+    /// Compares the received checkpoints against the current epoch and the given `history` and
+    /// reports which members appear to be in a diverged partition.
+    ///
+    /// A checkpoint is verified with the signature key the sender had in the checkpoint's epoch.
+    /// If that fails, e.g. because the sender changed its key in only one of the partitions, the
+    /// sender's signature keys in the current epoch and in the other recorded epochs are tried.
+    ///
+    /// Checkpoints of other groups, of leaves that are not members, and our own checkpoints are
+    /// ignored.
+    pub fn detect_fork(
+        &self,
+        crypto: &impl OpenMlsCrypto,
+        history: &CheckpointHistory,
+        checkpoints: impl IntoIterator<Item = VerifiableEpochCheckpoint>,
+    ) -> Result<ForkReport, LibraryError> {
+        let current = EpochRecord::from_group(self);
+        let mut verdicts = BTreeMap::new();
+
+        for checkpoint in checkpoints {
+            let sender = checkpoint.sender();
+            if checkpoint.group_id() != self.group_id() || sender == self.own_leaf_index() {
+                continue;
+            }
+            if self.public_group().leaf(sender).is_none() {
+                continue;
+            }
+
+            let record = if checkpoint.epoch() == current.epoch {
+                Some(&current)
+            } else {
+                history.get(checkpoint.epoch())
+            };
+            let verdict = match record {
+                Some(record) => {
+                    match self.verify_checkpoint(crypto, record, &current, history, checkpoint) {
+                        Some(checkpoint) => {
+                            if record.matches(crypto, self.ciphersuite(), &checkpoint.payload)? {
+                                Verdict::InSync
+                            } else {
+                                Verdict::Diverged
+                            }
+                        }
+                        None => Verdict::Unverified,
+                    }
+                }
+                None => Verdict::Undetermined,
+            };
+
+            let entry = verdicts.entry(sender).or_insert(verdict);
+            *entry = (*entry).max(verdict);
+        }
+
+        let mut report = ForkReport::default();
+        for (sender, verdict) in verdicts {
+            match verdict {
+                Verdict::InSync => report.in_sync.push(sender),
+                Verdict::Diverged => report.diverged.push(sender),
+                Verdict::Unverified => report.unverified.push(sender),
+                Verdict::Undetermined => report.undetermined.push(sender),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Verifies the checkpoint with the signature keys we know for its sender, starting with
+    /// the key in the epoch `record` of the checkpoint. Returns `None` if no key verifies it.
+    fn verify_checkpoint(
+        &self,
+        crypto: &impl OpenMlsCrypto,
+        record: &EpochRecord,
+        current: &EpochRecord,
+        history: &CheckpointHistory,
+        checkpoint: VerifiableEpochCheckpoint,
+    ) -> Option<EpochCheckpoint> {
+        let sender = checkpoint.sender();
+        let mut signature_keys: Vec<&SignaturePublicKey> = vec![];
+        for record in [record, current].into_iter().chain(history.records()) {
+            if let Some(signature_key) = record.signature_key(sender) {
+                if !signature_keys.contains(&signature_key) {
+                    signature_keys.push(signature_key);
+                }
+            }
+        }
+
+        signature_keys.into_iter().find_map(|signature_key| {
+            let pk = OpenMlsSignaturePublicKey::from_signature_key(
+                signature_key.clone(),
+                self.ciphersuite().signature_algorithm(),
+            );
+            checkpoint.clone().verify(crypto, &pk).ok()
+        })
+    }
+
+    /// Like [`MlsGroup::recover_fork_by_readding()`], but uses a [`ForkReport`] to determine
+    /// the partitions. All members that are not reported as diverged are considered to be in our
+    /// partition, including the members in [`ForkReport::unverified()`].
+    pub fn recover_fork_from_report(
+        &'_ mut self,
+        report: &ForkReport,
+    ) -> Result<CommitBuilder<'_, ReAddExpectKeyPackages>, ReAddError> {
+        let own_partition: Vec<_> = self
+            .members()
+            .map(|member| member.index)
+            .filter(|index| !report.diverged.contains(index))
+            .collect();
+
+        self.recover_fork_by_readding(&own_partition)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tls_codec::{Deserialize as _, Serialize as _};
+
+    use super::*;
+    use crate::{
+        credentials::{CredentialWithKey, NewSignerBundle},
+        framing::{MlsMessageIn, ProcessedMessageContent},
+        group::mls_group::tests_and_kats::utils::setup_alice_bob_group,
+        treesync::LeafNodeParameters,
+    };
+    use openmls_basic_credential::SignatureKeyPair;
+
+    fn receive(checkpoint: EpochCheckpoint) -> VerifiableEpochCheckpoint {
+        let bytes = checkpoint.tls_serialize_detached().unwrap();
+        VerifiableEpochCheckpoint::tls_deserialize_exact(bytes).unwrap()
+    }
+
+    #[openmls_test::openmls_test]
+    fn detect_fork() {
+        let alice_provider = &Provider::default();
+        let bob_provider = &Provider::default();
+
+        let (mut alice_group, alice_signer, mut bob_group, bob_signer, _alice_cwk, _bob_cwk) =
+            setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+        let bob_index = bob_group.own_leaf_index();
+
+        let mut alice_history = CheckpointHistory::new(2);
+        alice_history.record(&alice_group);
+
+        // Both are in the same epoch
+        let bob_checkpoint = bob_group
+            .epoch_checkpoint(bob_provider.crypto(), &bob_signer)
+            .unwrap();
+        let report = alice_group
+            .detect_fork(
+                alice_provider.crypto(),
+                &alice_history,
+                [receive(bob_checkpoint.clone())],
+            )
+            .unwrap();
+        assert_eq!(report.in_sync(), &[bob_index]);
+        assert!(!report.is_forked());
+
+        // Alice and Bob merge different commits
+        alice_group
+            .self_update(alice_provider, &alice_signer, LeafNodeParameters::default())
+            .unwrap();
+        alice_group.merge_pending_commit(alice_provider).unwrap();
+        alice_history.record(&alice_group);
+        bob_group
+            .self_update(bob_provider, &bob_signer, LeafNodeParameters::default())
+            .unwrap();
+        bob_group.merge_pending_commit(bob_provider).unwrap();
+        assert_eq!(alice_group.epoch(), bob_group.epoch());
+
+        // Bob's checkpoint of the previous epoch is still in sync, but his new one is not. The
+        // diverged checkpoint takes precedence.
+        let bob_new_checkpoint = bob_group
+            .epoch_checkpoint(bob_provider.crypto(), &bob_signer)
+            .unwrap();
+        let report = alice_group
+            .detect_fork(
+                alice_provider.crypto(),
+                &alice_history,
+                [
+                    receive(bob_checkpoint.clone()),
+                    receive(bob_new_checkpoint.clone()),
+                ],
+            )
+            .unwrap();
+        assert_eq!(report.diverged(), &[bob_index]);
+        assert!(report.in_sync().is_empty());
+        assert!(report.is_forked());
+
+        // Without the history, the old checkpoint can't be compared
+        let report = alice_group
+            .detect_fork(
+                alice_provider.crypto(),
+                &CheckpointHistory::new(0),
+                [receive(bob_checkpoint)],
+            )
+            .unwrap();
+        assert_eq!(report.undetermined(), &[bob_index]);
+
+        // A checkpoint that is not signed by Bob is reported as unverified
+        let forged_checkpoint = bob_group
+            .epoch_checkpoint(bob_provider.crypto(), &alice_signer)
+            .unwrap();
+        let report = alice_group
+            .detect_fork(
+                alice_provider.crypto(),
+                &alice_history,
+                [receive(forged_checkpoint)],
+            )
+            .unwrap();
+        assert_eq!(report.unverified(), &[bob_index]);
+        assert!(!report.is_forked());
+
+        // Alice's own checkpoint is ignored
+        let alice_checkpoint = alice_group
+            .epoch_checkpoint(alice_provider.crypto(), &alice_signer)
+            .unwrap();
+        let report = alice_group
+            .detect_fork(
+                alice_provider.crypto(),
+                &alice_history,
+                [receive(alice_checkpoint)],
+            )
+            .unwrap();
+        assert_eq!(report, ForkReport::default());
+
+        // The report determines who is re-added
+        let report = alice_group
+            .detect_fork(
+                alice_provider.crypto(),
+                &alice_history,
+                [receive(bob_new_checkpoint)],
+            )
+            .unwrap();
+        let builder = alice_group.recover_fork_from_report(&report).unwrap();
+        let complement_partition: Vec<_> = builder
+            .complement_partition()
+            .iter()
+            .map(|member| member.index)
+            .collect();
+        assert_eq!(complement_partition, vec![bob_index]);
+    }
+
+    /// Bob rotates his signature key in a commit that Alice merges, but Bob discards it and
+    /// merges another commit instead. His checkpoint is signed with his old key, which Alice
+    /// knows from her history.
+    #[openmls_test::openmls_test]
+    fn detect_fork_with_rotated_signature_key() {
+        let alice_provider = &Provider::default();
+        let bob_provider = &Provider::default();
+
+        let (mut alice_group, _alice_signer, mut bob_group, bob_signer, _alice_cwk, bob_cwk) =
+            setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+        let bob_index = bob_group.own_leaf_index();
+
+        let mut alice_history = CheckpointHistory::new(2);
+        alice_history.record(&alice_group);
+
+        let bob_new_signer = SignatureKeyPair::new(ciphersuite.signature_algorithm()).unwrap();
+        let new_signer = NewSignerBundle {
+            signer: &bob_new_signer,
+            credential_with_key: CredentialWithKey {
+                credential: bob_cwk.credential,
+                signature_key: bob_new_signer.public().into(),
+            },
+        };
+        let rotation = bob_group
+            .self_update_with_new_signer(
+                bob_provider,
+                &bob_signer,
+                new_signer,
+                LeafNodeParameters::default(),
+            )
+            .unwrap();
+        let rotation: MlsMessageIn = rotation.into_commit().into();
+        let processed_message = alice_group
+            .process_message(alice_provider, rotation.into_protocol_message().unwrap())
+            .unwrap();
+        let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+            processed_message.into_content()
+        else {
+            panic!("Expected a staged commit.");
+        };
+        alice_group
+            .merge_staged_commit(alice_provider, *staged_commit)
+            .unwrap();
+        alice_history.record(&alice_group);
+
+        bob_group
+            .clear_pending_commit(bob_provider.storage())
+            .unwrap();
+        bob_group
+            .self_update(bob_provider, &bob_signer, LeafNodeParameters::default())
+            .unwrap();
+        bob_group.merge_pending_commit(bob_provider).unwrap();
+        assert_eq!(alice_group.epoch(), bob_group.epoch());
+
+        let bob_checkpoint = bob_group
+            .epoch_checkpoint(bob_provider.crypto(), &bob_signer)
+            .unwrap();
+        let report = alice_group
+            .detect_fork(
+                alice_provider.crypto(),
+                &alice_history,
+                [receive(bob_checkpoint.clone())],
+            )
+            .unwrap();
+        assert_eq!(report.diverged(), &[bob_index]);
+
+        // Without the history, Alice only knows Bob's new key
+        let report = alice_group
+            .detect_fork(
+                alice_provider.crypto(),
+                &CheckpointHistory::new(0),
+                [receive(bob_checkpoint)],
+            )
+            .unwrap();
+        assert_eq!(report.unverified(), &[bob_index]);
+    }
+
+    #[openmls_test::openmls_test]
+    fn history_is_bounded() {
+        let alice_provider = &Provider::default();
+        let bob_provider = &Provider::default();
+
+        let (mut alice_group, alice_signer, _bob_group, _bob_signer, _alice_cwk, _bob_cwk) =
+            setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+
+        let mut history = CheckpointHistory::new(2);
+        for _ in 0..3 {
+            history.record(&alice_group);
+            history.record(&alice_group);
+            alice_group
+                .self_update(alice_provider, &alice_signer, LeafNodeParameters::default())
+                .unwrap();
+            alice_group.merge_pending_commit(alice_provider).unwrap();
+        }
+
+        assert_eq!(history.len(), 2);
+        let epoch = alice_group.epoch().as_u64();
+        assert!(history.get((epoch - 3).into()).is_none());
+        assert!(history.get((epoch - 2).into()).is_some());
+        assert!(history.get((epoch - 1).into()).is_some());
+    }
+}
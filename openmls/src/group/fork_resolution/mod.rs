@@ -10,6 +10,15 @@
 //! The [`reboot`] module contains helpers to set up a new group and add all members of the current
 //! group. The application needs to determine who should set that new group up and how to migrate
 //! the group context extensions from the old group. This is the more expensive mechanism.
+//!
+//! The [`detection`] module helps with determining the partitions. Members exchange epoch
+//! checkpoints and compare them against their own history. The resulting report lists the members
+//! that appear to be in a diverged partition and can be used to re-add them.
 
+mod detection;
 mod readd;
 mod reboot;
+
+pub use detection::{
+    CheckpointHistory, EpochCheckpoint, EpochCheckpointError, ForkReport, VerifiableEpochCheckpoint,
+};
@@ -24,11 +24,12 @@ impl MlsGroup {
     pub fn reboot(&'_ self, group_id: GroupId) -> RebootBuilder<'_> {
         let group_builder = MlsGroup::builder()
             .with_wire_format_policy(self.configuration().wire_format_policy)
-            .padding_size(self.configuration().padding_size)
+            .padding_policy(self.configuration().padding_policy)
             .set_past_epoch_deletion_policy(self.configuration().past_epoch_deletion_policy.clone())
             .number_of_resumption_psks(self.configuration().number_of_resumption_psks)
             .use_ratchet_tree_extension(self.configuration().use_ratchet_tree_extension)
             .sender_ratchet_configuration(self.configuration().sender_ratchet_configuration)
+            .future_epoch_buffer(self.configuration().future_epoch_buffer)
             .ciphersuite(self.ciphersuite())
             .with_group_id(group_id);
 
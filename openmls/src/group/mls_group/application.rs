@@ -1,6 +1,6 @@
-use openmls_traits::signatures::Signer;
+use openmls_traits::signatures::{AsyncSigner, Signer};
 
-use crate::storage::OpenMlsProvider;
+use crate::storage::{AsyncOpenMlsProvider, OpenMlsProvider};
 
 #[cfg(feature = "virtual-clients-draft")]
 use crate::tree::secret_tree::SecretType;
@@ -35,7 +35,9 @@ impl MlsGroup {
 
     /// Creates an application message. Returns
     /// `CreateMessageError::MlsGroupStateError::UseAfterEviction` if the member
-    /// is no longer part of the group. Returns
+    /// is no longer part of the group and
+    /// `CreateMessageError::MlsGroupStateError::ReInitPending` if the group
+    /// waits to be reinitialized. Returns
     /// `CreateMessageError::MlsGroupStateError::PendingProposal` if pending
     /// proposals exist. In that case `.process_pending_proposals()` must be
     /// called first and incoming messages from the DS must be processed
@@ -47,14 +49,37 @@ impl MlsGroup {
         signer: &impl Signer,
         message: &[u8],
     ) -> Result<MlsMessageOut, CreateMessageError> {
-        let (_, output) =
-            self.create_message_internal::<_, CreateMessageError>(provider, signer, message)?;
+        let (_, output) = crate::utils::resolve_ready(
+            self.create_message_internal::<_, CreateMessageError>(provider, signer, message),
+        )?;
+        Ok(output)
+    }
+
+    /// Creates an application message, signing it with an [`AsyncSigner`] and
+    /// persisting the updated secret tree to an asynchronous storage.
+    ///
+    /// This is the asynchronous variant of [`MlsGroup::create_message`] for
+    /// signature keys that are held by a remote signing service or an HSM and
+    /// for storage backends that are accessed asynchronously.
+    /// It returns the same errors as [`MlsGroup::create_message`].
+    #[cfg(not(feature = "virtual-clients-draft"))]
+    pub async fn create_message_async<Provider: AsyncOpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+        signer: &impl AsyncSigner,
+        message: &[u8],
+    ) -> Result<MlsMessageOut, CreateMessageError> {
+        let (_, output) = self
+            .create_message_internal::<_, CreateMessageError>(provider, signer, message)
+            .await?;
         Ok(output)
     }
 
     /// Creates an application message. Returns
     /// `CreateMessageError::MlsGroupStateError::UseAfterEviction` if the member
-    /// is no longer part of the group. Returns
+    /// is no longer part of the group and
+    /// `CreateMessageError::MlsGroupStateError::ReInitPending` if the group
+    /// waits to be reinitialized. Returns
     /// `CreateMessageError::MlsGroupStateError::PendingProposal` if pending
     /// proposals exist. In that case `.process_pending_proposals()` must be
     /// called first and incoming messages from the DS must be processed
@@ -67,24 +92,27 @@ impl MlsGroup {
         message: &[u8],
     ) -> Result<MlsMessageOut, CreateMessageError<Provider::StorageError>> {
         let (generation, _generation_id, output) =
-            self.create_message_internal(provider, signer, message)?;
+            crate::utils::resolve_ready(self.create_message_internal(provider, signer, message))?;
         self.confirm_message(provider.storage(), generation)?;
         Ok(output)
     }
 
     #[cfg(not(feature = "virtual-clients-draft"))]
-    fn create_message_internal<Provider: OpenMlsProvider, E>(
+    async fn create_message_internal<Provider: AsyncOpenMlsProvider, E>(
         &mut self,
         provider: &Provider,
-        signer: &impl Signer,
+        signer: &impl AsyncSigner,
         message: &[u8],
     ) -> Result<(u32, MlsMessageOut), E>
     where
-        E: From<LibraryError> + From<MlsGroupStateError>,
+        E: From<LibraryError> + From<MlsGroupStateError> + From<CreateMessageError>,
     {
         if !self.is_active() {
             return Err(MlsGroupStateError::UseAfterEviction.into());
         }
+        if self.reinit_proposal().is_some() {
+            return Err(MlsGroupStateError::ReInitPending.into());
+        }
         if !self.proposal_store().is_empty() {
             return Err(MlsGroupStateError::PendingProposal.into());
         }
@@ -96,14 +124,21 @@ impl MlsGroup {
             message,
             self.context(),
             signer,
-        )?;
+        )
+        .await?;
         let EncryptionOutput {
             generation,
             private_message,
         } = self
             .encrypt(authenticated_content, provider)
-            // We know the application message is wellformed and we have the key material of the current epoch
-            .map_err(|_| LibraryError::custom("Malformed plaintext"))?;
+            .await
+            .map_err(|e| match e {
+                MessageEncryptionError::MissingPaddingCallback => {
+                    E::from(CreateMessageError::MissingPaddingCallback)
+                }
+                // We know the application message is wellformed and we have the key material of the current epoch
+                _ => E::from(LibraryError::custom("Malformed plaintext")),
+            })?;
 
         let output = MlsMessageOut::from_private_message(private_message, self.version());
         self.reset_aad();
@@ -111,10 +146,10 @@ impl MlsGroup {
     }
 
     #[cfg(feature = "virtual-clients-draft")]
-    fn create_message_internal<Provider: OpenMlsProvider>(
+    async fn create_message_internal<Provider: AsyncOpenMlsProvider>(
         &mut self,
         provider: &Provider,
-        signer: &impl Signer,
+        signer: &impl AsyncSigner,
         message: &[u8],
     ) -> Result<
         (
@@ -127,6 +162,9 @@ impl MlsGroup {
         if !self.is_active() {
             return Err(MlsGroupStateError::UseAfterEviction.into());
         }
+        if self.reinit_proposal().is_some() {
+            return Err(MlsGroupStateError::ReInitPending.into());
+        }
         if !self.proposal_store().is_empty() {
             return Err(MlsGroupStateError::PendingProposal.into());
         }
@@ -138,12 +176,13 @@ impl MlsGroup {
             message,
             self.context(),
             signer,
-        )?;
+        )
+        .await?;
         let EncryptionOutput {
             generation,
             private_message,
             generation_id,
-        } = self.encrypt(authenticated_content, provider)?;
+        } = self.encrypt(authenticated_content, provider).await?;
 
         let output = MlsMessageOut::from_private_message(private_message, self.version());
         self.reset_aad();
@@ -178,7 +217,7 @@ impl MlsGroup {
         message: &[u8],
     ) -> Result<UnconfirmedMessage, CreateMessageError<Provider::StorageError>> {
         let (generation, generation_id, message) =
-            self.create_message_internal(provider, signer, message)?;
+            crate::utils::resolve_ready(self.create_message_internal(provider, signer, message))?;
         Ok(UnconfirmedMessage {
             message,
             generation,
@@ -0,0 +1,163 @@
+//! This module contains the functionality to branch a subgroup off a group as
+//! described in Section 11.3 of the MLS specification.
+//!
+//! A subgroup is created by a member of the parent group using
+//! [`MlsGroup::branch()`]. The first Commit of the subgroup adds a subset of the
+//! members of the parent group and injects the resumption PSK of the current
+//! epoch of the parent group with usage [`ResumptionPskUsage::Branch`]. The
+//! other members verify that PSK against the resumption PSKs of the parent
+//! group when they join the subgroup through [`MlsGroup::join_branch()`].
+
+use openmls_traits::signatures::Signer;
+
+use super::{
+    builder::MlsGroupBuilder,
+    commit_builder::{CommitBuilder, CommitMessageBundle, Initial},
+    errors::BranchError,
+    Member, MlsGroup, StagedWelcome,
+};
+use crate::{
+    credentials::{Credential, CredentialWithKey},
+    group::{GroupEpoch, MlsGroupJoinConfig},
+    key_packages::KeyPackage,
+    messages::Welcome,
+    schedule::{psk::ResumptionPskUsage, ResumptionPskSecret},
+    storage::OpenMlsProvider,
+    treesync::RatchetTreeIn,
+};
+
+impl MlsGroup {
+    /// The first step towards branching a subgroup off this group. The
+    /// subgroup uses the ciphersuite and the configuration of this group and
+    /// a random group ID, which can be changed using
+    /// [`BranchBuilder::refine_group_builder()`].
+    pub fn branch(&'_ self) -> BranchBuilder<'_> {
+        let group_builder = self.resuming_group_builder(self.ciphersuite());
+
+        BranchBuilder {
+            group: self,
+            group_builder,
+        }
+    }
+
+    /// Joins a subgroup that was branched off this group using a [`Welcome`]
+    /// message.
+    ///
+    /// In addition to the regular checks when processing a [`Welcome`], this
+    /// verifies that the [`Welcome`] injects a resumption PSK of this group
+    /// with usage [`ResumptionPskUsage::Branch`], that the subgroup uses the
+    /// protocol version and ciphersuite of this group and that all members of
+    /// the subgroup are members of this group. If the PSK was exported from a
+    /// past epoch of this group, it is looked up using
+    /// [`MlsGroup::get_past_resumption_psk()`], so the group must be
+    /// configured to keep enough past resumption PSKs.
+    pub fn join_branch<Provider: OpenMlsProvider>(
+        &self,
+        provider: &Provider,
+        mls_group_config: &MlsGroupJoinConfig,
+        welcome: Welcome,
+        ratchet_tree: Option<RatchetTreeIn>,
+    ) -> Result<StagedWelcome, BranchError<Provider::StorageError>> {
+        let staged_welcome = self.join_resuming_group::<_, BranchError<_>>(
+            provider,
+            mls_group_config,
+            welcome,
+            ratchet_tree,
+            ResumptionPskUsage::Branch,
+            |_| true,
+            |group_context| {
+                group_context.group_id() != self.group_id()
+                    && group_context.protocol_version() == self.version()
+                    && group_context.ciphersuite() == self.ciphersuite()
+            },
+        )?;
+
+        if !staged_welcome
+            .members()
+            .all(|member| self.is_member_credential(&member.credential))
+        {
+            return Err(BranchError::NotAParentGroupMember);
+        }
+
+        Ok(staged_welcome)
+    }
+
+    /// Returns the resumption PSK of the given epoch, which is either the
+    /// current epoch or one of the past epochs kept by this group.
+    pub(crate) fn branch_resumption_psk(&self, epoch: GroupEpoch) -> Option<&ResumptionPskSecret> {
+        if epoch == self.epoch() {
+            Some(self.resumption_psk_secret())
+        } else {
+            self.get_past_resumption_psk(epoch)
+        }
+    }
+
+    /// Returns `true` if a member of this group uses the given credential.
+    fn is_member_credential(&self, credential: &Credential) -> bool {
+        self.members()
+            .any(|member| &member.credential == credential)
+    }
+}
+
+/// The builder type for a subgroup that is branched off a group.
+pub struct BranchBuilder<'a> {
+    group: &'a MlsGroup,
+    group_builder: MlsGroupBuilder,
+}
+
+impl<'a> BranchBuilder<'a> {
+    /// The members of the parent group, so key packages for the members of
+    /// the subgroup can be retrieved.
+    pub fn parent_members(&self) -> impl Iterator<Item = Member> + 'a {
+        self.group
+            .members()
+            .filter(|member| member.index != self.group.own_leaf_index())
+    }
+
+    /// Lets the caller make changes to the [`MlsGroupBuilder`] before the group is created.
+    ///
+    /// Note that the ciphersuite must not be changed, since the other members
+    /// would reject the subgroup otherwise.
+    pub fn refine_group_builder(
+        self,
+        mut f: impl FnMut(MlsGroupBuilder) -> MlsGroupBuilder,
+    ) -> Self {
+        Self {
+            group_builder: f(self.group_builder),
+            ..self
+        }
+    }
+
+    /// Creates the subgroup and its first commit, which adds the
+    /// `new_members` and injects the branch resumption PSK of the parent
+    /// group. All `new_members` must be members of the parent group. The
+    /// caller can also make further changes to the [`CommitBuilder`] using
+    /// the `refine_commit_builder` argument. If that is not desired, provide
+    /// the identity function (`|b| b`).
+    pub fn finish<Provider: OpenMlsProvider>(
+        self,
+        new_members: Vec<KeyPackage>,
+        refine_commit_builder: impl FnMut(CommitBuilder<Initial>) -> CommitBuilder<Initial>,
+        provider: &Provider,
+        signer: &impl Signer,
+        credential_with_key: CredentialWithKey,
+    ) -> Result<(MlsGroup, CommitMessageBundle), BranchError<Provider::StorageError>> {
+        if !new_members.iter().all(|key_package| {
+            self.group
+                .is_member_credential(key_package.leaf_node().credential())
+        }) {
+            return Err(BranchError::NotAParentGroupMember);
+        }
+
+        self.group.create_resuming_group(
+            ResumptionPskUsage::Branch,
+            self.group.ciphersuite(),
+            self.group_builder,
+            new_members,
+            refine_commit_builder,
+            provider,
+            signer,
+            credential_with_key,
+        )
+    }
+}
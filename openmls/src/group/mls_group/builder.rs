@@ -9,10 +9,12 @@ use crate::{
     error::LibraryError,
     extensions::Extensions,
     group::{
-        config::PastEpochDeletionPolicy, past_secrets::MessageSecretsStore,
-        public_group::errors::PublicGroupBuildError, GroupContext, GroupId, MlsGroup,
-        MlsGroupCreateConfig, MlsGroupCreateConfigBuilder, MlsGroupState, NewGroupError,
-        PublicGroup, WireFormatPolicy,
+        config::{FutureEpochBufferConfig, PaddingPolicy, PastEpochDeletionPolicy},
+        future_epoch_messages::FutureEpochMessages,
+        past_secrets::MessageSecretsStore,
+        public_group::errors::PublicGroupBuildError,
+        GroupContext, GroupId, MlsGroup, MlsGroupCreateConfig, MlsGroupCreateConfigBuilder,
+        MlsGroupState, NewGroupError, PublicGroup, WireFormatPolicy,
     },
     key_packages::Lifetime,
     schedule::{
@@ -193,14 +195,25 @@ impl MlsGroupBuilder {
             resumption_psk_store,
             #[cfg(feature = "extensions-draft")]
             application_export_tree: Some(application_export_tree),
+            future_epoch_messages: FutureEpochMessages::default(),
         };
 
-        mls_group
-            .store(provider.storage())
-            .map_err(NewGroupError::StorageError)?;
-        mls_group
-            .store_epoch_keypairs(provider.storage(), &[leaf_keypair])
-            .map_err(NewGroupError::StorageError)?;
+        crate::storage::transaction(
+            provider.storage(),
+            || {
+                crate::utils::resolve_ready(async {
+                    mls_group
+                        .store(provider.storage())
+                        .await
+                        .map_err(NewGroupError::StorageError)?;
+                    mls_group
+                        .store_epoch_keypairs(provider.storage(), &[leaf_keypair])
+                        .await
+                        .map_err(NewGroupError::StorageError)
+                })
+            },
+            NewGroupError::StorageError,
+        )?;
 
         Ok(mls_group)
     }
@@ -223,6 +236,14 @@ impl MlsGroupBuilder {
         self
     }
 
+    /// Sets the `padding_policy` property of the MlsGroup.
+    pub fn padding_policy(mut self, padding_policy: PaddingPolicy) -> Self {
+        self.mls_group_create_config_builder = self
+            .mls_group_create_config_builder
+            .padding_policy(padding_policy);
+        self
+    }
+
     /// Sets the `max_past_epochs` property of the MlsGroup.
     /// This allows application messages from previous epochs to be decrypted.
     ///
@@ -295,6 +316,15 @@ impl MlsGroupBuilder {
         self
     }
 
+    /// Sets the `future_epoch_buffer` property of the MlsGroup.
+    /// See [`FutureEpochBufferConfig`] for more information.
+    pub fn future_epoch_buffer(mut self, future_epoch_buffer: FutureEpochBufferConfig) -> Self {
+        self.mls_group_create_config_builder = self
+            .mls_group_create_config_builder
+            .future_epoch_buffer(future_epoch_buffer);
+        self
+    }
+
     /// Sets the `lifetime` of the group creator's leaf.
     pub fn lifetime(mut self, lifetime: Lifetime) -> Self {
         self.mls_group_create_config_builder =
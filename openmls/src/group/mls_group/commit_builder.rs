@@ -4,13 +4,16 @@
 use std::{borrow::BorrowMut, marker::PhantomData};
 
 use openmls_traits::{
-    crypto::OpenMlsCrypto, random::OpenMlsRand, signatures::Signer, storage::StorageProvider as _,
+    crypto::OpenMlsCrypto,
+    random::OpenMlsRand,
+    signatures::{AsyncSigner, Signer},
+    storage::StorageProvider as _,
 };
 use tls_codec::Serialize as _;
 
 use crate::{
     binary_tree::LeafNodeIndex,
-    ciphersuite::{signable::Signable as _, Secret},
+    ciphersuite::{signable::sign_async, Secret},
     extensions::Extensions,
     framing::{FramingParameters, WireFormat},
     group::{
@@ -86,7 +89,7 @@ use super::{
     mls_auth_content::AuthenticatedContent,
     staged_commit::{MemberStagedCommitState, StagedCommitState},
     AddProposal, CreateCommitResult, GroupContextExtensionProposal, MlsGroup, MlsGroupState,
-    MlsMessageOut, PendingCommitState, Proposal, RemoveProposal, Sender,
+    MlsMessageOut, PendingCommitState, Proposal, ReInitProposal, RemoveProposal, Sender,
 };
 
 #[derive(Debug)]
@@ -318,6 +321,16 @@ impl<'a> CommitBuilder<'a, Initial, &mut MlsGroup> {
         Ok(self)
     }
 
+    /// Adds a ReInit proposal to the list of proposals to be committed. A Commit
+    /// that contains a ReInit proposal must not contain any other proposals,
+    /// so this is usually combined with `consume_proposal_store(false)`.
+    pub fn propose_reinit(mut self, reinit_proposal: ReInitProposal) -> Self {
+        self.stage
+            .own_proposals
+            .push(Proposal::re_init(reinit_proposal));
+        self
+    }
+
     /// Adds a proposal to the proposals to be committed. To add multiple
     /// proposals, use [`Self::add_proposals`].
     pub fn add_proposal(mut self, proposal: Proposal) -> Self {
@@ -480,6 +493,16 @@ impl<'a, G: BorrowMut<MlsGroup>> CommitBuilder<'a, Initial, G> {
     pub fn load_psks<Storage: StorageProvider>(
         self,
         storage: &'a Storage,
+    ) -> Result<CommitBuilder<'a, LoadedPsks, G>, CreateCommitError> {
+        self.load_psks_with(storage, None)
+    }
+
+    /// Like [`Self::load_psks`], but takes the secret of `in_memory_psk` from
+    /// memory instead of reading it from the storage.
+    pub(crate) fn load_psks_with<Storage: StorageProvider>(
+        self,
+        storage: &'a Storage,
+        in_memory_psk: Option<(&PreSharedKeyId, &Secret)>,
     ) -> Result<CommitBuilder<'a, LoadedPsks, G>, CreateCommitError> {
         let psk_ids: Vec<_> = self
             .stage
@@ -498,11 +521,25 @@ impl<'a, G: BorrowMut<MlsGroup>> CommitBuilder<'a, Initial, G> {
             })
             .collect();
 
-        // Load the PSKs and make the PskIds owned.
-        let psks = load_psks(storage, &self.group.borrow().resumption_psk_store, &psk_ids)?
-            .into_iter()
-            .map(|(psk_id_ref, key)| (psk_id_ref.clone(), key))
-            .collect();
+        // Load the PSKs and make the PskIds owned. The order of the PSKs
+        // matters for the PSK secret, so the in-memory PSK keeps its position.
+        let mut psks = Vec::with_capacity(psk_ids.len());
+        for psk_id in psk_ids.iter() {
+            match in_memory_psk {
+                Some((in_memory_id, secret)) if in_memory_id == psk_id => {
+                    psks.push((psk_id.clone(), secret.clone()))
+                }
+                _ => psks.extend(
+                    load_psks(
+                        storage,
+                        &self.group.borrow().resumption_psk_store,
+                        std::slice::from_ref(psk_id),
+                    )?
+                    .into_iter()
+                    .map(|(psk_id_ref, key)| (psk_id_ref.clone(), key)),
+                ),
+            }
+        }
 
         // Initialize GroupInfoConfig
         let use_ratchet_tree_extension = self
@@ -590,7 +627,21 @@ impl<'a, G: BorrowMut<MlsGroup>> CommitBuilder<'a, LoadedPsks, G> {
         signer: &S,
         f: impl FnMut(&QueuedProposal) -> bool,
     ) -> Result<CommitBuilder<'a, Complete, G>, CreateCommitError> {
-        self.build_internal(rand, crypto, signer, None::<NewSignerBundle<'_, S>>, f)
+        crate::utils::resolve_ready(self.build_internal(rand, crypto, signer, None::<(&S, _)>, f))
+    }
+
+    /// Just like `build`, this function validates the inputs and builds the
+    /// commit, but signs with an [`AsyncSigner`]. This can be used for
+    /// signature keys that are held by a remote signing service or an HSM.
+    pub async fn build_async<S: AsyncSigner>(
+        self,
+        rand: &impl OpenMlsRand,
+        crypto: &impl OpenMlsCrypto,
+        signer: &S,
+        f: impl FnMut(&QueuedProposal) -> bool,
+    ) -> Result<CommitBuilder<'a, Complete, G>, CreateCommitError> {
+        self.build_internal(rand, crypto, signer, None::<(&S, _)>, f)
+            .await
     }
 
     /// Just like `build`, this function validates the inputs and builds the
@@ -608,15 +659,25 @@ impl<'a, G: BorrowMut<MlsGroup>> CommitBuilder<'a, LoadedPsks, G> {
         new_signer: NewSignerBundle<'_, S>,
         f: impl FnMut(&QueuedProposal) -> bool,
     ) -> Result<CommitBuilder<'a, Complete, G>, CreateCommitError> {
-        self.build_internal(rand, crypto, old_signer, Some(new_signer), f)
+        let NewSignerBundle {
+            signer,
+            credential_with_key,
+        } = new_signer;
+        crate::utils::resolve_ready(self.build_internal(
+            rand,
+            crypto,
+            old_signer,
+            Some((signer, credential_with_key)),
+            f,
+        ))
     }
 
-    fn build_internal<S: Signer>(
+    async fn build_internal<S: AsyncSigner>(
         self,
         rand: &impl OpenMlsRand,
         crypto: &impl OpenMlsCrypto,
-        old_signer: &impl Signer,
-        new_signer: Option<NewSignerBundle<'_, S>>,
+        old_signer: &impl AsyncSigner,
+        new_signer: Option<(&S, CredentialWithKey)>,
         f: impl FnMut(&QueuedProposal) -> bool,
     ) -> Result<CommitBuilder<'a, Complete, G>, CreateCommitError> {
         #[cfg_attr(not(feature = "virtual-clients-draft"), allow(unused_mut))]
@@ -722,6 +783,10 @@ impl<'a, G: BorrowMut<MlsGroup>> CommitBuilder<'a, LoadedPsks, G> {
             group
                 .public_group
                 .validate_external_commit(&proposal_queue)?;
+        } else {
+            group
+                .public_group
+                .validate_reinit_proposals(&proposal_queue)?;
         }
 
         let proposal_reference_list = proposal_queue.commit_list();
@@ -787,16 +852,16 @@ impl<'a, G: BorrowMut<MlsGroup>> CommitBuilder<'a, LoadedPsks, G> {
                 // Process the path. This includes updating the provisional
                 // group context by updating the epoch and computing the new
                 // tree hash.
-                if let Some(new_signer) = new_signer {
+                if let Some((new_signer, new_credential_with_key)) = new_signer {
                     if let Some(credential_with_key) =
                         cur_stage.leaf_node_parameters.credential_with_key()
                     {
-                        if credential_with_key != &new_signer.credential_with_key {
+                        if credential_with_key != &new_credential_with_key {
                             return Err(CreateCommitError::InvalidLeafNodeParameters);
                         }
                     }
                     cur_stage.leaf_node_parameters.set_credential_with_key(
-                        new_signer.credential_with_key,
+                        new_credential_with_key,
                     );
 
                     diff.compute_path(
@@ -806,10 +871,11 @@ impl<'a, G: BorrowMut<MlsGroup>> CommitBuilder<'a, LoadedPsks, G> {
                         apply_proposals_values.exclusion_list(),
                         &commit_type,
                         &cur_stage.leaf_node_parameters,
-                        new_signer.signer,
+                        new_signer,
                         apply_proposals_values.extensions.clone(),
                         own_update_override,
-                    )?
+                    )
+                    .await?
                 } else {
                     diff.compute_path(
                         rand,
@@ -821,7 +887,8 @@ impl<'a, G: BorrowMut<MlsGroup>> CommitBuilder<'a, LoadedPsks, G> {
                         old_signer,
                         apply_proposals_values.extensions.clone(),
                         own_update_override,
-                    )?
+                    )
+                    .await?
                 }
             } else {
                 // If path is not needed, update the group context and return
@@ -905,7 +972,8 @@ impl<'a, G: BorrowMut<MlsGroup>> CommitBuilder<'a, LoadedPsks, G> {
             commit,
             group.public_group.group_context(),
             old_signer,
-        )?;
+        )
+        .await?;
 
         // Update the confirmed transcript hash using the commit we just created.
         diff.update_confirmed_transcript_hash(crypto, &authenticated_content)?;
@@ -987,79 +1055,81 @@ impl<'a, G: BorrowMut<MlsGroup>> CommitBuilder<'a, LoadedPsks, G> {
 
             let mut extensions = Extensions::from_vec(extensions_list)?;
 
-            let welcome_option = needs_welcome
-                .then(|| -> Result<_, CreateCommitError> {
-                    let group_info_tbs = {
-                        GroupInfoTBS::new(
-                            diff.group_context().clone(),
-                            extensions.clone(),
-                            confirmation_tag.clone(),
-                            own_leaf_index,
-                        )?
-                    };
-                    // Sign to-be-signed group info.
-                    let group_info = group_info_tbs.sign(old_signer)?;
-
-                    // Encrypt GroupInfo object
-                    let (welcome_key, welcome_nonce) = welcome_secret
-                        .derive_welcome_key_nonce(crypto, ciphersuite)
-                        .map_err(LibraryError::unexpected_crypto_error)?;
-                    let encrypted_group_info = welcome_key
-                        .aead_seal(
-                            crypto,
-                            group_info
-                                .tls_serialize_detached()
-                                .map_err(LibraryError::missing_bound_check)?
-                                .as_slice(),
-                            &[],
-                            &welcome_nonce,
-                        )
-                        .map_err(LibraryError::unexpected_crypto_error)?;
-
-                    // Create group secrets for later use, so we can afterwards consume the
-                    // `joiner_secret`.
-                    let encrypted_secrets = diff.encrypt_group_secrets(
-                        &joiner_secret,
-                        apply_proposals_values.invitation_list,
-                        path_computation_result.plain_path.as_deref(),
-                        &apply_proposals_values.presharedkeys,
-                        &encrypted_group_info,
-                        crypto,
+            let welcome_option = if needs_welcome {
+                let group_info_tbs = {
+                    GroupInfoTBS::new(
+                        diff.group_context().clone(),
+                        extensions.clone(),
+                        confirmation_tag.clone(),
                         own_leaf_index,
-                    )?;
-
-                    // Create welcome message
-                    let welcome =
-                        Welcome::new(ciphersuite, encrypted_secrets, encrypted_group_info);
-                    Ok(welcome)
-                })
-                .transpose()?;
+                    )?
+                };
+                // Sign to-be-signed group info.
+                let group_info = sign_async(group_info_tbs, old_signer).await?;
+
+                // Encrypt GroupInfo object
+                let (welcome_key, welcome_nonce) = welcome_secret
+                    .derive_welcome_key_nonce(crypto, ciphersuite)
+                    .map_err(LibraryError::unexpected_crypto_error)?;
+                let encrypted_group_info = welcome_key
+                    .aead_seal(
+                        crypto,
+                        group_info
+                            .tls_serialize_detached()
+                            .map_err(LibraryError::missing_bound_check)?
+                            .as_slice(),
+                        &[],
+                        &welcome_nonce,
+                    )
+                    .map_err(LibraryError::unexpected_crypto_error)?;
+
+                // Create group secrets for later use, so we can afterwards consume the
+                // `joiner_secret`.
+                let encrypted_secrets = diff.encrypt_group_secrets(
+                    &joiner_secret,
+                    apply_proposals_values.invitation_list,
+                    path_computation_result.plain_path.as_deref(),
+                    &apply_proposals_values.presharedkeys,
+                    &encrypted_group_info,
+                    crypto,
+                    own_leaf_index,
+                )?;
+
+                // Create welcome message
+                Some(Welcome::new(
+                    ciphersuite,
+                    encrypted_secrets,
+                    encrypted_group_info,
+                ))
+            } else {
+                None
+            };
 
             // Create the GroupInfo for export if needed. In contrast to the Welcome, this
             // group info contains the external public key extension.
-            let exported_group_info = create_group_info
-                .then(|| -> Result<_, CreateCommitError> {
-                    let external_pub = provisional_epoch_secrets
-                        .external_secret()
-                        .derive_external_keypair(crypto, ciphersuite)
-                        .map_err(LibraryError::unexpected_crypto_error)?
-                        .public;
-
-                    let external_pub_extension =
-                        Extension::ExternalPub(ExternalPubExtension::new(external_pub.into()));
-                    extensions.add(external_pub_extension)?;
-                    let group_info_tbs = {
-                        GroupInfoTBS::new(
-                            diff.group_context().clone(),
-                            extensions,
-                            confirmation_tag.clone(),
-                            own_leaf_index,
-                        )?
-                    };
-                    // Sign to-be-signed group info.
-                    Ok(group_info_tbs.sign(old_signer)?)
-                })
-                .transpose()?;
+            let exported_group_info = if create_group_info {
+                let external_pub = provisional_epoch_secrets
+                    .external_secret()
+                    .derive_external_keypair(crypto, ciphersuite)
+                    .map_err(LibraryError::unexpected_crypto_error)?
+                    .public;
+
+                let external_pub_extension =
+                    Extension::ExternalPub(ExternalPubExtension::new(external_pub.into()));
+                extensions.add(external_pub_extension)?;
+                let group_info_tbs = {
+                    GroupInfoTBS::new(
+                        diff.group_context().clone(),
+                        extensions,
+                        confirmation_tag.clone(),
+                        own_leaf_index,
+                    )?
+                };
+                // Sign to-be-signed group info.
+                Some(sign_async(group_info_tbs, old_signer).await?)
+            } else {
+                None
+            };
 
             (welcome_option, exported_group_info)
         };
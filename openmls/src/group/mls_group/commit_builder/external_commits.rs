@@ -6,11 +6,15 @@ use super::CommitMessageBundle;
 
 use crate::{
     binary_tree::LeafNodeIndex,
-    credentials::CredentialWithKey,
+    credentials::{
+        errors::CredentialValidationError, CredentialValidator, CredentialValidators,
+        CredentialWithKey,
+    },
     error::LibraryError,
     framing::{ContentType, DecryptedMessage, PublicMessageIn, Sender},
     group::{
         commit_builder::{CommitBuilder, ExternalCommitInfo, Initial},
+        future_epoch_messages::FutureEpochMessages,
         past_secrets::MessageSecretsStore,
         public_group::errors::CreationFromExternalError,
         ExternalCommitBuilderFinalizeError, LeafNodeLifetimePolicy, MlsGroup, MlsGroupJoinConfig,
@@ -55,6 +59,9 @@ pub enum ExternalCommitBuilderError<StorageError> {
     /// Error validating proposals.
     #[error("Error validating proposals: {0}")]
     InvalidProposal(#[from] ValidationError),
+    /// See [`CredentialValidationError`] for more details.
+    #[error(transparent)]
+    CredentialValidationError(#[from] CredentialValidationError),
 }
 
 /// This is the builder for external commits. It allows you to build an external
@@ -66,22 +73,23 @@ pub enum ExternalCommitBuilderError<StorageError> {
 /// add one or more [`PreSharedKeyProposal`]s to the external commit and specify
 /// [`LeafNodeParameters`].
 #[derive(Default)]
-pub struct ExternalCommitBuilder {
+pub struct ExternalCommitBuilder<'a> {
     proposals: Vec<PublicMessageIn>,
     ratchet_tree: Option<RatchetTreeIn>,
     config: MlsGroupJoinConfig,
     validate_lifetimes: LeafNodeLifetimePolicy,
     aad: Vec<u8>,
+    credential_validator: Option<&'a dyn CredentialValidator>,
 }
 
 impl MlsGroup {
     /// Creates a new [`ExternalCommitBuilder`] to build an external commit.
-    pub fn external_commit_builder() -> ExternalCommitBuilder {
+    pub fn external_commit_builder<'a>() -> ExternalCommitBuilder<'a> {
         ExternalCommitBuilder::new()
     }
 }
 
-impl ExternalCommitBuilder {
+impl<'a> ExternalCommitBuilder<'a> {
     /// Creates a new [`ExternalCommitBuilder`] with default values.
     pub fn new() -> Self {
         Self::default()
@@ -128,11 +136,36 @@ impl ExternalCommitBuilder {
         self
     }
 
+    /// Validate the credentials of all members and external senders of the
+    /// group using the given [`CredentialValidator`] before building the
+    /// external commit, in addition to the one set in the group
+    /// configuration.
+    pub fn with_credential_validator(
+        mut self,
+        credential_validator: &'a impl CredentialValidator,
+    ) -> Self {
+        self.credential_validator = Some(credential_validator);
+        self
+    }
+
     /// Build the [`MlsGroup`] from the provided [`VerifiableGroupInfo`] and
     /// [`CredentialWithKey`].
     ///
     /// Returns a [`CommitBuilder`] that can be used to further configure the
     /// external commit.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "external_commit_build_group",
+            level = "debug",
+            skip_all,
+            fields(
+                group_id = %crate::instrumentation::HexGroupId(verifiable_group_info.group_id()),
+                epoch = verifiable_group_info.epoch().as_u64(),
+            ),
+            err
+        )
+    )]
     pub fn build_group<Provider: OpenMlsProvider>(
         self,
         provider: &Provider,
@@ -142,12 +175,14 @@ impl ExternalCommitBuilder {
         CommitBuilder<'_, Initial, MlsGroup>,
         ExternalCommitBuilderError<Provider::StorageError>,
     > {
+        time_operation!("external_commit_build_group");
         let ExternalCommitBuilder {
             proposals,
             ratchet_tree,
             mut config,
             aad,
             validate_lifetimes,
+            credential_validator,
         } = self;
 
         // Build the ratchet tree
@@ -168,6 +203,13 @@ impl ExternalCommitBuilder {
             ProposalStore::new(),
             validate_lifetimes,
         )?;
+        // The validator in the group configuration applies in addition to the
+        // given one.
+        if let Some(credential_validator) =
+            CredentialValidators::new(config.credential_validator.as_ref(), credential_validator)
+        {
+            public_group.validate_credentials(&credential_validator)?;
+        }
         let group_context = public_group.group_context();
 
         // Obtain external_pub from GroupInfo extensions.
@@ -296,6 +338,7 @@ impl ExternalCommitBuilder {
             // commit is merged.
             #[cfg(feature = "extensions-draft")]
             application_export_tree: None,
+            future_epoch_messages: FutureEpochMessages::default(),
         };
 
         // Add all proposals to the proposal store.
@@ -349,6 +392,19 @@ impl CommitBuilder<'_, super::Complete, MlsGroup> {
     ///
     /// In contrast to the deprecated [`MlsGroup::join_by_external_commit`]
     /// there is no need to merge the pending commit.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "external_commit_finalize",
+            level = "debug",
+            skip_all,
+            fields(
+                group_id = %crate::instrumentation::HexGroupId(self.group.group_id()),
+                epoch = self.group.epoch().as_u64(),
+            ),
+            err
+        )
+    )]
     pub fn finalize<Provider: OpenMlsProvider>(
         self,
         provider: &Provider,
@@ -356,6 +412,7 @@ impl CommitBuilder<'_, super::Complete, MlsGroup> {
         (MlsGroup, super::CommitMessageBundle),
         ExternalCommitBuilderFinalizeError<Provider::StorageError>,
     > {
+        time_operation!("external_commit_finalize");
         let Self {
             mut group,
             stage:
@@ -376,18 +433,29 @@ impl CommitBuilder<'_, super::Complete, MlsGroup> {
             group.mls_group_config.wire_format_policy = wire_format_policy;
         }
 
-        // Store the group in storage.
-        group
-            .store(provider.storage())
-            .map_err(ExternalCommitBuilderFinalizeError::StorageError)?;
-
-        // Set the current group state to [`MlsGroupState::PendingCommit`],
-        // storing the current [`StagedCommit`] from the commit results
-        group.group_state = MlsGroupState::PendingCommit(Box::new(PendingCommitState::Member(
-            create_commit_result.staged_commit,
-        )));
-
-        group.merge_pending_commit(provider)?;
+        // Store the group and merge the commit in one transaction, so that
+        // the group is not persisted without the commit.
+        crate::storage::transaction(
+            provider.storage(),
+            || {
+                crate::utils::resolve_ready(async {
+                    group
+                        .store(provider.storage())
+                        .await
+                        .map_err(ExternalCommitBuilderFinalizeError::StorageError)
+                })?;
+
+                // Set the current group state to [`MlsGroupState::PendingCommit`],
+                // storing the current [`StagedCommit`] from the commit results
+                group.group_state = MlsGroupState::PendingCommit(Box::new(
+                    PendingCommitState::Member(create_commit_result.staged_commit),
+                ));
+
+                group.merge_pending_commit(provider)?;
+                Ok(())
+            },
+            ExternalCommitBuilderFinalizeError::StorageError,
+        )?;
 
         let bundle = super::CommitMessageBundle {
             version: group.version(),
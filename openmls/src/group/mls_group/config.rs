@@ -32,9 +32,13 @@ use std::time::SystemTime;
 #[cfg(target_arch = "wasm32")]
 use web_time::SystemTime;
 
+use std::sync::Arc;
+
 use super::*;
 use crate::{
+    credentials::{ConfiguredCredentialValidator, CredentialValidator},
     extensions::Extensions,
+    framing::ContentType,
     key_packages::Lifetime,
     tree::sender_ratchet::SenderRatchetConfiguration,
     treesync::{errors::LeafNodeValidationError, node::leaf_node::Capabilities},
@@ -100,7 +104,15 @@ impl<'de> Deserialize<'de> for PastEpochDeletionPolicy {
             Tagged(Tagged),
         }
 
-        Ok(match Format::deserialize(deserializer)? {
+        // The untagged format can only be read by self-describing codecs.
+        // Other codecs only ever stored the plain integer.
+        let format = if deserializer.is_human_readable() {
+            Format::deserialize(deserializer)?
+        } else {
+            Format::Int(u64::deserialize(deserializer)?)
+        };
+
+        Ok(match format {
             Format::Int(u64::MAX) => Self::KeepAll,
             Format::Int(n) => {
                 Self::MaxEpochs(usize::try_from(n).map_err(serde::de::Error::custom)?)
@@ -192,21 +204,89 @@ impl PastEpochDeletionPolicy {
     }
 }
 
+/// Configures the buffer for messages from the next epoch.
+///
+/// With an unordered transport, messages sent in the next epoch can arrive
+/// before the commit that moves the group into that epoch. If the buffer is
+/// enabled, [`MlsGroup::process_message()`] stores such messages and returns
+/// [`ProcessMessageError::MessageBuffered`]. After the commit was merged, the
+/// buffered messages are processed by
+/// [`MlsGroup::process_buffered_messages()`], or by
+/// [`MlsGroup::process_buffered_messages_with_credential_validator()`] if the
+/// credentials they introduce need to be validated. The buffer requires a
+/// storage provider that supports it, see
+/// [`StorageProvider::supports_future_epoch_messages()`].
+///
+/// The buffer holds at most `max_messages` messages. The messages can't be
+/// authenticated before the commit into their epoch is merged, so if the
+/// buffer is full, new messages are rejected with
+/// [`ProcessMessageError::FutureEpochBufferFull`] instead of evicting buffered
+/// ones. If a `max_age` is set, messages older than that are dropped. By
+/// default, the buffer is disabled.
+///
+/// [`ProcessMessageError::MessageBuffered`]: crate::group::ProcessMessageError::MessageBuffered
+/// [`ProcessMessageError::FutureEpochBufferFull`]: crate::group::ProcessMessageError::FutureEpochBufferFull
+/// [`StorageProvider::supports_future_epoch_messages()`]: openmls_traits::storage::StorageProvider::supports_future_epoch_messages
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FutureEpochBufferConfig {
+    max_messages: usize,
+    max_age: Option<std::time::Duration>,
+}
+
+impl FutureEpochBufferConfig {
+    /// Creates a new [`FutureEpochBufferConfig`] that buffers at most
+    /// `max_messages` messages. A value of 0 disables the buffer.
+    pub fn new(max_messages: usize) -> Self {
+        Self {
+            max_messages,
+            max_age: None,
+        }
+    }
+
+    /// Sets the maximum age of buffered messages. Older messages are dropped
+    /// instead of being processed.
+    pub fn with_max_age(mut self, max_age: std::time::Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Returns the maximum number of buffered messages.
+    pub fn max_messages(&self) -> usize {
+        self.max_messages
+    }
+
+    /// Returns the maximum age of buffered messages, if set.
+    pub fn max_age(&self) -> Option<std::time::Duration> {
+        self.max_age
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.max_messages > 0
+    }
+
+    /// Returns `true` if a message received at `received_at` is too old to be
+    /// kept in the buffer at time `now`.
+    pub(crate) fn is_expired(&self, received_at: SystemTime, now: SystemTime) -> bool {
+        match (self.max_age, now.duration_since(received_at)) {
+            (Some(max_age), Ok(age)) => age > max_age,
+            _ => false,
+        }
+    }
+}
+
 /// The [`MlsGroupJoinConfig`] contains all configuration parameters that are
 /// relevant to group operation at runtime. It is used to configure the group's
 /// behaviour when joining an existing group. To configure a newly created
 /// group, use [`MlsGroupCreateConfig`].
-#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct MlsGroupJoinConfig {
     /// Defines the wire format policy for outgoing and incoming handshake messages.
     /// Application are always encrypted regardless.
     pub(crate) wire_format_policy: WireFormatPolicy,
-    /// Size of padding in bytes
-    pub(crate) padding_size: usize,
+    /// Padding policy for outgoing handshake and application messages
+    pub(crate) padding_policy: PaddingPolicy,
     /// Maximum number of past epochs for which application messages
     /// can be decrypted. The default is 0.
-    #[serde(alias = "max_past_epochs")]
-    // alias for backwards compatibility after renaming field
     pub(crate) past_epoch_deletion_policy: PastEpochDeletionPolicy,
     /// Number of resumption secrets to keep
     pub(crate) number_of_resumption_psks: usize,
@@ -214,6 +294,11 @@ pub struct MlsGroupJoinConfig {
     pub(crate) use_ratchet_tree_extension: bool,
     /// Sender ratchet configuration
     pub(crate) sender_ratchet_configuration: SenderRatchetConfiguration,
+    /// Buffer configuration for messages from the next epoch
+    pub(crate) future_epoch_buffer: FutureEpochBufferConfig,
+    /// Validator for new and changed credentials. Only whether a validator
+    /// is set is persisted.
+    pub(crate) credential_validator: Option<ConfiguredCredentialValidator>,
 }
 
 impl MlsGroupJoinConfig {
@@ -228,8 +313,17 @@ impl MlsGroupJoinConfig {
     }
 
     /// Returns the padding size set in this  [`MlsGroupJoinConfig`].
+    ///
+    /// This is the block size of the [`PaddingStrategy::Fixed`] strategy for
+    /// application messages, or 0 if a different strategy is used. See
+    /// [`Self::padding_policy()`] for the full policy.
     pub fn padding_size(&self) -> usize {
-        self.padding_size
+        self.padding_policy.fixed_padding_size()
+    }
+
+    /// Returns the [`PaddingPolicy`] set in this  [`MlsGroupJoinConfig`].
+    pub fn padding_policy(&self) -> PaddingPolicy {
+        self.padding_policy
     }
 
     /// Returns the [`SenderRatchetConfiguration`] set in this  [`MlsGroupJoinConfig`].
@@ -245,6 +339,17 @@ impl MlsGroupJoinConfig {
     pub(crate) fn past_epoch_deletion_policy(&self) -> &PastEpochDeletionPolicy {
         &self.past_epoch_deletion_policy
     }
+
+    /// Returns the [`FutureEpochBufferConfig`] set in this [`MlsGroupJoinConfig`].
+    pub fn future_epoch_buffer(&self) -> &FutureEpochBufferConfig {
+        &self.future_epoch_buffer
+    }
+
+    /// Returns `true` if a [`CredentialValidator`] is set in this
+    /// [`MlsGroupJoinConfig`], or was set before it was stored.
+    pub fn requires_credential_validator(&self) -> bool {
+        self.credential_validator.is_some()
+    }
 }
 
 /// Specifies configuration for the creation of an [`MlsGroup`]. Refer to the
@@ -300,8 +405,19 @@ impl MlsGroupJoinConfigBuilder {
     }
 
     /// Sets the `padding_size` property of the [`MlsGroupJoinConfig`].
+    ///
+    /// This is equivalent to setting the padding policy to
+    /// `PaddingPolicy::uniform(PaddingStrategy::Fixed(padding_size))`.
     pub fn padding_size(mut self, padding_size: usize) -> Self {
-        self.join_config.padding_size = padding_size;
+        self.join_config.padding_policy =
+            PaddingPolicy::uniform(PaddingStrategy::Fixed(padding_size));
+        self
+    }
+
+    /// Sets the `padding_policy` property of the [`MlsGroupJoinConfig`].
+    /// See [`PaddingPolicy`] for more information.
+    pub fn padding_policy(mut self, padding_policy: PaddingPolicy) -> Self {
+        self.join_config.padding_policy = padding_policy;
         self
     }
 
@@ -363,6 +479,33 @@ impl MlsGroupJoinConfigBuilder {
         self
     }
 
+    /// Sets the `future_epoch_buffer` property of the [`MlsGroupJoinConfig`].
+    /// See [`FutureEpochBufferConfig`] for more information.
+    pub fn future_epoch_buffer(mut self, future_epoch_buffer: FutureEpochBufferConfig) -> Self {
+        self.join_config.future_epoch_buffer = future_epoch_buffer;
+        self
+    }
+
+    /// Sets the [`CredentialValidator`] that validates all credentials that
+    /// are introduced into the group, when processing messages, merging
+    /// commits, joining through a Welcome and building an external commit.
+    ///
+    /// The validator itself is not persisted, only that the group requires
+    /// one. After loading the group, processing messages and merging commits
+    /// fail with [`CredentialValidationError::ValidatorMissing`] until the
+    /// validator is set again with [`MlsGroup::set_credential_validator()`].
+    ///
+    /// [`CredentialValidationError::ValidatorMissing`]: crate::credentials::errors::CredentialValidationError::ValidatorMissing
+    pub fn credential_validator(
+        mut self,
+        credential_validator: impl CredentialValidator + Send + Sync + 'static,
+    ) -> Self {
+        self.join_config.credential_validator = Some(ConfiguredCredentialValidator::Set(Arc::new(
+            credential_validator,
+        )));
+        self
+    }
+
     /// Finalizes the builder and returns an [`MlsGroupJoinConfig`].
     pub fn build(self) -> MlsGroupJoinConfig {
         self.join_config
@@ -382,7 +525,12 @@ impl MlsGroupCreateConfig {
 
     /// Returns the [`MlsGroupCreateConfig`] padding size.
     pub fn padding_size(&self) -> usize {
-        self.join_config.padding_size
+        self.join_config.padding_size()
+    }
+
+    /// Returns the [`MlsGroupCreateConfig`] padding policy.
+    pub fn padding_policy(&self) -> PaddingPolicy {
+        self.join_config.padding_policy
     }
 
     /// Returns the [`MlsGroupCreateConfig`] max past epochs.
@@ -405,6 +553,11 @@ impl MlsGroupCreateConfig {
         &self.join_config.sender_ratchet_configuration
     }
 
+    /// Returns the [`MlsGroupCreateConfig`] future epoch buffer configuration.
+    pub fn future_epoch_buffer(&self) -> &FutureEpochBufferConfig {
+        &self.join_config.future_epoch_buffer
+    }
+
     /// Returns the [`Extensions`] set as the initial group context.
     /// This does not contain the initial group context extensions
     /// added from builder calls to `external_senders` or `required_capabilities`.
@@ -460,8 +613,19 @@ impl MlsGroupCreateConfigBuilder {
     }
 
     /// Sets the `padding_size` property of the MlsGroupCreateConfig.
+    ///
+    /// This is equivalent to setting the padding policy to
+    /// `PaddingPolicy::uniform(PaddingStrategy::Fixed(padding_size))`.
     pub fn padding_size(mut self, padding_size: usize) -> Self {
-        self.config.join_config.padding_size = padding_size;
+        self.config.join_config.padding_policy =
+            PaddingPolicy::uniform(PaddingStrategy::Fixed(padding_size));
+        self
+    }
+
+    /// Sets the `padding_policy` property of the MlsGroupCreateConfig.
+    /// See [`PaddingPolicy`] for more information.
+    pub fn padding_policy(mut self, padding_policy: PaddingPolicy) -> Self {
+        self.config.join_config.padding_policy = padding_policy;
         self
     }
 
@@ -531,6 +695,26 @@ impl MlsGroupCreateConfigBuilder {
         self
     }
 
+    /// Sets the `future_epoch_buffer` property of the MlsGroupCreateConfig.
+    /// See [`FutureEpochBufferConfig`] for more information.
+    pub fn future_epoch_buffer(mut self, future_epoch_buffer: FutureEpochBufferConfig) -> Self {
+        self.config.join_config.future_epoch_buffer = future_epoch_buffer;
+        self
+    }
+
+    /// Sets the [`CredentialValidator`] of the MlsGroupCreateConfig.
+    /// See [`MlsGroupJoinConfigBuilder::credential_validator()`] for more
+    /// information.
+    pub fn credential_validator(
+        mut self,
+        credential_validator: impl CredentialValidator + Send + Sync + 'static,
+    ) -> Self {
+        self.config.join_config.credential_validator = Some(ConfiguredCredentialValidator::Set(
+            Arc::new(credential_validator),
+        ));
+        self
+    }
+
     /// Sets the `lifetime` property of the MlsGroupCreateConfig.
     pub fn lifetime(mut self, lifetime: Lifetime) -> Self {
         self.config.lifetime = lifetime;
@@ -692,9 +876,382 @@ pub const MIXED_CIPHERTEXT_WIRE_FORMAT_POLICY: WireFormatPolicy = WireFormatPoli
     incoming: IncomingWireFormatPolicy::Mixed,
 };
 
+/// A callback that maps the length of an unpadded [`PrivateMessage`]
+/// ciphertext to the desired padded length. See [`PaddingStrategy::Custom`].
+///
+/// [`PrivateMessage`]: crate::framing::PrivateMessage
+#[derive(Clone, Copy, Debug)]
+pub struct PaddingCallback(Option<fn(usize) -> usize>);
+
+impl PaddingCallback {
+    /// Creates a new [`PaddingCallback`] from a function that maps an unpadded
+    /// length to a padded length.
+    pub fn new(callback: fn(usize) -> usize) -> Self {
+        Self(Some(callback))
+    }
+
+    /// Returns `true` if the callback is set. Callbacks are not persisted, so
+    /// this is `false` for the callback of a group loaded from storage.
+    pub fn is_set(&self) -> bool {
+        self.0.is_some()
+    }
+}
+
+impl PartialEq for PaddingCallback {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.0, other.0) {
+            (Some(callback), Some(other)) => std::ptr::fn_addr_eq(callback, other),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for PaddingCallback {}
+
+/// Defines how the content of a [`PrivateMessage`] is padded before
+/// encryption.
+///
+/// All strategies operate on the length of the ciphertext without padding,
+/// i.e. the serialized content including the AEAD tag. If a strategy returns a
+/// length that is smaller than the unpadded length, no padding is added. The
+/// padded length is capped at the maximum length of a ciphertext, 2^30 - 1
+/// bytes.
+///
+/// [`PrivateMessage`]: crate::framing::PrivateMessage
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaddingStrategy {
+    /// Pad to a multiple of the given block size in bytes. A block size of 0
+    /// disables padding.
+    Fixed(usize),
+    /// Pad using the Padmé scheme, which leaks at most O(log log L) bits of
+    /// information about the length L while adding at most 12% overhead.
+    Padme,
+    /// Pad to the next power of two.
+    PowerOfTwo,
+    /// Pad to the length returned by the given callback.
+    ///
+    /// **NOTE**: The callback can't be persisted. A group loaded from storage
+    /// fails to encrypt messages with
+    /// [`MessageEncryptionError::MissingPaddingCallback`] until the callback
+    /// is set again via [`MlsGroup::set_configuration()`].
+    ///
+    /// [`MessageEncryptionError::MissingPaddingCallback`]: crate::framing::errors::MessageEncryptionError::MissingPaddingCallback
+    Custom(PaddingCallback),
+}
+
+impl Default for PaddingStrategy {
+    fn default() -> Self {
+        Self::Fixed(0)
+    }
+}
+
+/// The maximum length of a padded ciphertext, which is the maximum length of
+/// a variable-length vector in MLS.
+const MAX_PADDED_LENGTH: usize = (1 << 30) - 1;
+
+impl PaddingStrategy {
+    /// Returns the number of padding bytes to add to content of the given
+    /// unpadded length, or `None` if the callback of a custom strategy is not
+    /// set.
+    pub(crate) fn padding_length(&self, unpadded_length: usize) -> Option<usize> {
+        // Lengths that overflow are capped like all other lengths.
+        let padded_length = match self {
+            Self::Fixed(0) => Some(unpadded_length),
+            Self::Fixed(block_size) => unpadded_length.checked_next_multiple_of(*block_size),
+            Self::Padme => padme(unpadded_length),
+            Self::PowerOfTwo => unpadded_length.checked_next_power_of_two(),
+            Self::Custom(PaddingCallback(callback)) => Some(callback.as_ref()?(unpadded_length)),
+        };
+        Some(
+            padded_length
+                .map_or(MAX_PADDED_LENGTH, |length| length.min(MAX_PADDED_LENGTH))
+                .saturating_sub(unpadded_length),
+        )
+    }
+}
+
+/// Computes the Padmé length for `length`, i.e. rounds it up such that only
+/// the top ⌊log2(⌊log2(length)⌋)⌋ + 1 bits may be non-zero.
+fn padme(length: usize) -> Option<usize> {
+    if length < 2 {
+        return Some(length);
+    }
+    let exponent = length.ilog2();
+    let significant_bits = exponent.ilog2() + 1;
+    let mask = (1usize << (exponent - significant_bits)) - 1;
+    Some(length.checked_add(mask)? & !mask)
+}
+
+impl Serialize for PaddingStrategy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Self::Fixed(block_size) => PaddingStrategyFormat::Fixed(*block_size),
+            Self::Padme => PaddingStrategyFormat::Padme,
+            Self::PowerOfTwo => PaddingStrategyFormat::PowerOfTwo,
+            Self::Custom(_) => PaddingStrategyFormat::Custom,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PaddingStrategy {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match PaddingStrategyFormat::deserialize(deserializer)? {
+            PaddingStrategyFormat::Fixed(block_size) => Self::Fixed(block_size),
+            PaddingStrategyFormat::Padme => Self::Padme,
+            PaddingStrategyFormat::PowerOfTwo => Self::PowerOfTwo,
+            // The callback itself can't be restored. Encryption fails until
+            // the application sets it again.
+            PaddingStrategyFormat::Custom => Self::Custom(PaddingCallback(None)),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+enum PaddingStrategyFormat {
+    Fixed(usize),
+    Padme,
+    PowerOfTwo,
+    Custom,
+}
+
+/// Defines the [`PaddingStrategy`] for outgoing handshake and application
+/// messages. Only messages sent as [`PrivateMessage`] are padded.
+///
+/// [`PrivateMessage`]: crate::framing::PrivateMessage
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaddingPolicy {
+    handshake: PaddingStrategy,
+    application: PaddingStrategy,
+}
+
+impl PaddingPolicy {
+    /// Creates a new padding policy from a [`PaddingStrategy`] for handshake
+    /// messages and one for application messages.
+    pub fn new(handshake: PaddingStrategy, application: PaddingStrategy) -> Self {
+        Self {
+            handshake,
+            application,
+        }
+    }
+
+    /// Creates a new padding policy that uses the same [`PaddingStrategy`] for
+    /// handshake and application messages.
+    pub fn uniform(strategy: PaddingStrategy) -> Self {
+        Self::new(strategy, strategy)
+    }
+
+    /// Returns the padding strategy for handshake messages.
+    pub fn handshake(&self) -> PaddingStrategy {
+        self.handshake
+    }
+
+    /// Returns the padding strategy for application messages.
+    pub fn application(&self) -> PaddingStrategy {
+        self.application
+    }
+
+    /// Returns the block size of the fixed padding for application messages,
+    /// or 0 if a different strategy is used.
+    pub(crate) fn fixed_padding_size(&self) -> usize {
+        match self.application {
+            PaddingStrategy::Fixed(padding_size) => padding_size,
+            _ => 0,
+        }
+    }
+
+    /// Returns the padding strategy for messages of the given content type.
+    pub(crate) fn strategy_for(&self, content_type: ContentType) -> PaddingStrategy {
+        if content_type.is_handshake_message() {
+            self.handshake
+        } else {
+            self.application
+        }
+    }
+}
+
+/// The version of the format of a [`MlsGroupJoinConfig`] that is stored by
+/// codecs that are not self-describing. It precedes the
+/// [`JoinConfigFields`].
+///
+/// Configurations that were stored before the padding policy, the buffer
+/// configuration and the credential validator flag were added have no
+/// version. They start with the tag of the [`OutgoingWireFormatPolicy`]
+/// instead, which is 0 or 1.
+const JOIN_CONFIG_FORMAT_VERSION: u32 = 2;
+
+/// The number of values of an unversioned [`MlsGroupJoinConfig`], counting
+/// the two wire format policies separately.
+const UNVERSIONED_JOIN_CONFIG_VALUES: usize = 7;
+
+/// The fields of a [`MlsGroupJoinConfig`] as they are stored. Only whether a
+/// credential validator is set is stored. A required validator is restored as
+/// [`ConfiguredCredentialValidator::Missing`].
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "MlsGroupJoinConfig")]
+struct JoinConfigFields {
+    wire_format_policy: WireFormatPolicy,
+    #[serde(alias = "padding_size", deserialize_with = "padding_policy_or_size")]
+    // alias for backwards compatibility after replacing the fixed padding size
+    padding_policy: PaddingPolicy,
+    #[serde(alias = "max_past_epochs")]
+    // alias for backwards compatibility after renaming field
+    past_epoch_deletion_policy: PastEpochDeletionPolicy,
+    number_of_resumption_psks: usize,
+    use_ratchet_tree_extension: bool,
+    sender_ratchet_configuration: SenderRatchetConfiguration,
+    #[serde(default)]
+    future_epoch_buffer: FutureEpochBufferConfig,
+    #[serde(default)]
+    requires_credential_validator: bool,
+}
+
+impl From<&MlsGroupJoinConfig> for JoinConfigFields {
+    fn from(config: &MlsGroupJoinConfig) -> Self {
+        Self {
+            wire_format_policy: config.wire_format_policy,
+            padding_policy: config.padding_policy,
+            past_epoch_deletion_policy: config.past_epoch_deletion_policy.clone(),
+            number_of_resumption_psks: config.number_of_resumption_psks,
+            use_ratchet_tree_extension: config.use_ratchet_tree_extension,
+            sender_ratchet_configuration: config.sender_ratchet_configuration,
+            future_epoch_buffer: config.future_epoch_buffer,
+            requires_credential_validator: config.credential_validator.is_some(),
+        }
+    }
+}
+
+impl From<JoinConfigFields> for MlsGroupJoinConfig {
+    fn from(fields: JoinConfigFields) -> Self {
+        Self {
+            wire_format_policy: fields.wire_format_policy,
+            padding_policy: fields.padding_policy,
+            past_epoch_deletion_policy: fields.past_epoch_deletion_policy,
+            number_of_resumption_psks: fields.number_of_resumption_psks,
+            use_ratchet_tree_extension: fields.use_ratchet_tree_extension,
+            sender_ratchet_configuration: fields.sender_ratchet_configuration,
+            future_epoch_buffer: fields.future_epoch_buffer,
+            credential_validator: fields
+                .requires_credential_validator
+                .then_some(ConfiguredCredentialValidator::Missing),
+        }
+    }
+}
+
+/// Reads the padding policy of a [`JoinConfigFields`]. Self-describing codecs
+/// also read the previously supported `padding_size` for all messages.
+fn padding_policy_or_size<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<PaddingPolicy, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Format {
+        Legacy(usize),
+        Policy(PaddingPolicy),
+    }
+
+    if !deserializer.is_human_readable() {
+        return PaddingPolicy::deserialize(deserializer);
+    }
+
+    Ok(match Format::deserialize(deserializer)? {
+        Format::Legacy(padding_size) => {
+            PaddingPolicy::uniform(PaddingStrategy::Fixed(padding_size))
+        }
+        Format::Policy(policy) => policy,
+    })
+}
+
+impl Serialize for MlsGroupJoinConfig {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let fields = JoinConfigFields::from(self);
+        if serializer.is_human_readable() {
+            fields.serialize(serializer)
+        } else {
+            (JOIN_CONFIG_FORMAT_VERSION, fields).serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MlsGroupJoinConfig {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Self-describing codecs store the field names and fill in the
+        // defaults of fields that were added later.
+        if deserializer.is_human_readable() {
+            return JoinConfigFields::deserialize(deserializer).map(Self::from);
+        }
+
+        deserializer.deserialize_tuple(UNVERSIONED_JOIN_CONFIG_VALUES, VersionedJoinConfigVisitor)
+    }
+}
+
+/// Reads a [`MlsGroupJoinConfig`] from a codec that is not self-describing,
+/// with or without a [`JOIN_CONFIG_FORMAT_VERSION`].
+struct VersionedJoinConfigVisitor;
+
+impl VersionedJoinConfigVisitor {
+    fn next<'de, T: Deserialize<'de>, A: serde::de::SeqAccess<'de>>(
+        &self,
+        seq: &mut A,
+        index: usize,
+    ) -> Result<T, A::Error> {
+        seq.next_element()?
+            .ok_or_else(|| serde::de::Error::invalid_length(index, self))
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for VersionedJoinConfigVisitor {
+    type Value = MlsGroupJoinConfig;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a versioned MlsGroupJoinConfig")
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let version: u32 = self.next(&mut seq, 0)?;
+        let outgoing = match version {
+            JOIN_CONFIG_FORMAT_VERSION => {
+                let fields: JoinConfigFields = self.next(&mut seq, 1)?;
+                return Ok(fields.into());
+            }
+            0 => OutgoingWireFormatPolicy::AlwaysCiphertext,
+            1 => OutgoingWireFormatPolicy::AlwaysPlaintext,
+            _ => {
+                return Err(serde::de::Error::invalid_value(
+                    serde::de::Unexpected::Unsigned(version.into()),
+                    &self,
+                ))
+            }
+        };
+
+        // An unversioned configuration, which had a fixed padding size for
+        // all messages.
+        let incoming = self.next(&mut seq, 1)?;
+        let padding_size: usize = self.next(&mut seq, 2)?;
+        Ok(MlsGroupJoinConfig {
+            wire_format_policy: WireFormatPolicy { outgoing, incoming },
+            padding_policy: PaddingPolicy::uniform(PaddingStrategy::Fixed(padding_size)),
+            past_epoch_deletion_policy: self.next(&mut seq, 3)?,
+            number_of_resumption_psks: self.next(&mut seq, 4)?,
+            use_ratchet_tree_extension: self.next(&mut seq, 5)?,
+            sender_ratchet_configuration: self.next(&mut seq, 6)?,
+            ..Default::default()
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::PastEpochDeletionPolicy;
+    use super::{
+        FutureEpochBufferConfig, IncomingWireFormatPolicy, MlsGroupJoinConfig,
+        OutgoingWireFormatPolicy, PaddingCallback, PaddingPolicy, PaddingStrategy,
+        PastEpochDeletionPolicy, WireFormatPolicy, MAX_PADDED_LENGTH,
+    };
+    use crate::tree::sender_ratchet::SenderRatchetConfiguration;
 
     #[test]
     fn past_epoch_deletion_policy_roundtrip() {
@@ -734,4 +1291,179 @@ mod tests {
         let deserialized: PastEpochDeletionPolicy = serde_json::from_str(r#""KeepAll""#).unwrap();
         assert_eq!(deserialized, PastEpochDeletionPolicy::KeepAll);
     }
+
+    #[test]
+    fn padding_strategy_lengths() {
+        // (unpadded length, fixed(16), padme, power of two)
+        for (length, fixed, padme, power_of_two) in [
+            (0, 0, 0, 1),
+            (1, 16, 1, 1),
+            (16, 16, 16, 16),
+            (17, 32, 18, 32),
+            (100, 112, 104, 128),
+            (1000, 1008, 1024, 1024),
+            (1025, 1040, 1088, 2048),
+            (70000, 70000, 71680, 131072),
+        ] {
+            assert_eq!(
+                length + PaddingStrategy::Fixed(16).padding_length(length).unwrap(),
+                fixed
+            );
+            assert_eq!(
+                length + PaddingStrategy::Padme.padding_length(length).unwrap(),
+                padme
+            );
+            assert_eq!(
+                length + PaddingStrategy::PowerOfTwo.padding_length(length).unwrap(),
+                power_of_two
+            );
+            assert_eq!(PaddingStrategy::Fixed(0).padding_length(length), Some(0));
+        }
+
+        // Custom callbacks can't shrink the content.
+        let custom = PaddingStrategy::Custom(PaddingCallback::new(|length| length / 2));
+        assert_eq!(custom.padding_length(100), Some(0));
+        let custom = PaddingStrategy::Custom(PaddingCallback::new(|length| length + 7));
+        assert_eq!(custom.padding_length(100), Some(7));
+    }
+
+    #[test]
+    fn padding_strategy_lengths_are_capped() {
+        let custom = PaddingStrategy::Custom(PaddingCallback::new(|_| usize::MAX));
+        assert_eq!(custom.padding_length(100), Some(MAX_PADDED_LENGTH - 100));
+
+        // The next power of two is larger than the maximum length, or doesn't
+        // fit into a `usize` at all.
+        let length = (1 << 29) + 1;
+        assert_eq!(
+            PaddingStrategy::PowerOfTwo.padding_length(length),
+            Some(MAX_PADDED_LENGTH - length)
+        );
+        for strategy in [
+            PaddingStrategy::PowerOfTwo,
+            PaddingStrategy::Padme,
+            PaddingStrategy::Fixed(1000),
+        ] {
+            assert_eq!(strategy.padding_length(usize::MAX - 1), Some(0));
+        }
+    }
+
+    #[test]
+    fn padding_policy_roundtrip() {
+        let policy = PaddingPolicy::new(PaddingStrategy::Fixed(64), PaddingStrategy::Padme);
+        let json = serde_json::to_string(&policy).unwrap();
+        let deserialized: PaddingPolicy = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, policy);
+
+        // Custom callbacks are restored without the callback, which can't be
+        // used for padding.
+        let policy = PaddingPolicy::new(
+            PaddingStrategy::PowerOfTwo,
+            PaddingStrategy::Custom(PaddingCallback::new(|length| length)),
+        );
+        let json = serde_json::to_string(&policy).unwrap();
+        let deserialized: PaddingPolicy = serde_json::from_str(&json).unwrap();
+        let PaddingStrategy::Custom(callback) = deserialized.application() else {
+            panic!("Expected a custom strategy.");
+        };
+        assert!(!callback.is_set());
+        assert_eq!(deserialized.application().padding_length(100), None);
+        assert_eq!(deserialized.handshake(), PaddingStrategy::PowerOfTwo);
+    }
+
+    #[test]
+    fn padding_policy_deserializes_legacy_padding_size() {
+        let mut json = serde_json::to_value(MlsGroupJoinConfig::default()).unwrap();
+        let config = json.as_object_mut().unwrap();
+        config.remove("padding_policy");
+        config.insert("padding_size".into(), 100.into());
+
+        let deserialized: MlsGroupJoinConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            deserialized.padding_policy(),
+            PaddingPolicy::uniform(PaddingStrategy::Fixed(100))
+        );
+        assert_eq!(deserialized.padding_size(), 100);
+    }
+
+    /// Postcard encodings of join configurations without a format version,
+    /// as they were written before the padding policy was added.
+    #[test]
+    fn join_config_reads_unversioned_postcard() {
+        let stored = hex::decode("010280020508010ad00f").unwrap();
+        let deserialized: MlsGroupJoinConfig = postcard::from_bytes(&stored).unwrap();
+        assert_eq!(
+            deserialized,
+            MlsGroupJoinConfig::builder()
+                .wire_format_policy(WireFormatPolicy {
+                    outgoing: OutgoingWireFormatPolicy::AlwaysPlaintext,
+                    incoming: IncomingWireFormatPolicy::Mixed,
+                })
+                .padding_size(256)
+                .max_past_epochs(5)
+                .number_of_resumption_psks(8)
+                .use_ratchet_tree_extension(true)
+                .sender_ratchet_configuration(SenderRatchetConfiguration::new(10, 2000))
+                .build()
+        );
+
+        let stored = hex::decode("000000ffffffffffffffffff01000005e807").unwrap();
+        let deserialized: MlsGroupJoinConfig = postcard::from_bytes(&stored).unwrap();
+        assert_eq!(
+            deserialized.past_epoch_deletion_policy(),
+            &PastEpochDeletionPolicy::KeepAll
+        );
+        assert_eq!(
+            deserialized,
+            MlsGroupJoinConfig {
+                past_epoch_deletion_policy: PastEpochDeletionPolicy::KeepAll,
+                ..Default::default()
+            }
+        );
+
+        // Written again, the configuration has a format version.
+        let written = postcard::to_allocvec(&deserialized).unwrap();
+        assert_eq!(written[0], 2);
+        let reread: MlsGroupJoinConfig = postcard::from_bytes(&written).unwrap();
+        assert_eq!(reread, deserialized);
+    }
+
+    /// The buffer configuration is stored by both kinds of codecs.
+    /// Configurations that were stored without it disable the buffer.
+    #[test]
+    fn join_config_stores_future_epoch_buffer() {
+        let config = MlsGroupJoinConfig::builder()
+            .future_epoch_buffer(
+                FutureEpochBufferConfig::new(8).with_max_age(std::time::Duration::from_secs(60)),
+            )
+            .build();
+
+        let mut json = serde_json::to_value(&config).unwrap();
+        let deserialized: MlsGroupJoinConfig = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(deserialized, config);
+        let stored = postcard::to_allocvec(&config).unwrap();
+        let deserialized: MlsGroupJoinConfig = postcard::from_bytes(&stored).unwrap();
+        assert_eq!(deserialized, config);
+
+        json.as_object_mut().unwrap().remove("future_epoch_buffer");
+        let deserialized: MlsGroupJoinConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            deserialized.future_epoch_buffer(),
+            &FutureEpochBufferConfig::default()
+        );
+        let stored = hex::decode("010280020508010ad00f").unwrap();
+        let deserialized: MlsGroupJoinConfig = postcard::from_bytes(&stored).unwrap();
+        assert_eq!(
+            deserialized.future_epoch_buffer(),
+            &FutureEpochBufferConfig::default()
+        );
+        assert_eq!(deserialized.future_epoch_buffer().max_messages(), 0);
+    }
+
+    #[test]
+    fn join_config_rejects_unknown_format_version() {
+        let mut stored = postcard::to_allocvec(&MlsGroupJoinConfig::default()).unwrap();
+        stored[0] = 3;
+        assert!(postcard::from_bytes::<MlsGroupJoinConfig>(&stored).is_err());
+    }
 }
@@ -3,7 +3,7 @@ use openmls_traits::{crypto::OpenMlsCrypto, storage::StorageProvider as StorageP
 
 use super::{builder::MlsGroupBuilder, *};
 use crate::{
-    credentials::CredentialWithKey,
+    credentials::{CredentialValidator, CredentialValidators, CredentialWithKey},
     extensions::Extensions,
     group::{
         commit_builder::external_commits::ExternalCommitBuilder,
@@ -145,6 +145,33 @@ impl ProcessedWelcome {
         mls_group_config: &MlsGroupJoinConfig,
         welcome: Welcome,
     ) -> Result<Self, WelcomeError<Provider::StorageError>> {
+        Self::new_from_welcome_internal(provider, mls_group_config, welcome, None)
+    }
+
+    /// Creates a new processed [`Welcome`] message. If a `parent_group` is
+    /// given, branch and reinit resumption PSKs of that group are taken from
+    /// the group itself.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "new_from_welcome",
+            level = "debug",
+            skip_all,
+            fields(
+                ciphersuite = ?welcome.ciphersuite(),
+                group_id = tracing::field::Empty,
+                epoch = tracing::field::Empty,
+            ),
+            err
+        )
+    )]
+    pub(crate) fn new_from_welcome_internal<Provider: OpenMlsProvider>(
+        provider: &Provider,
+        mls_group_config: &MlsGroupJoinConfig,
+        welcome: Welcome,
+        parent_group: Option<&MlsGroup>,
+    ) -> Result<Self, WelcomeError<Provider::StorageError>> {
+        time_operation!("new_from_welcome");
         let (resumption_psk_store, key_material) =
             keys_for_welcome(mls_group_config, &welcome, provider)?;
 
@@ -178,11 +205,20 @@ impl ProcessedWelcome {
         PreSharedKeyId::validate_in_welcome(&group_secrets.psks, ciphersuite)?;
 
         let psk_secret = {
-            let psks = load_psks(
-                provider.storage(),
-                &resumption_psk_store,
-                &group_secrets.psks,
-            )?;
+            let psks = match parent_group {
+                Some(parent_group) => load_parent_psks(
+                    provider.storage(),
+                    &resumption_psk_store,
+                    parent_group.group_id(),
+                    |epoch| parent_group.branch_resumption_psk(epoch),
+                    &group_secrets.psks,
+                )?,
+                None => load_psks(
+                    provider.storage(),
+                    &resumption_psk_store,
+                    &group_secrets.psks,
+                )?,
+            };
 
             PskSecret::new(provider.crypto(), ciphersuite, psks)?
         };
@@ -204,6 +240,17 @@ impl ProcessedWelcome {
             &[],
             provider.crypto(),
         )?;
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            span.record(
+                "group_id",
+                tracing::field::display(crate::instrumentation::HexGroupId(
+                    verifiable_group_info.group_id(),
+                )),
+            );
+            span.record("epoch", verifiable_group_info.epoch().as_u64());
+        }
 
         // On the bundle path, check the required capabilities and the
         // ciphersuite against the local KeyPackage. On the virtual-client path
@@ -270,18 +317,54 @@ impl ProcessedWelcome {
             ratchet_tree,
             LeafNodeLifetimePolicy::Verify,
             false,
+            None,
+        )
+    }
+
+    /// Consume the `ProcessedWelcome` and combine it with the ratchet tree into
+    /// a `StagedWelcome`. In addition to the regular checks, the credentials
+    /// of all members and external senders of the group are validated using
+    /// the given [`CredentialValidator`] and the one set in the group
+    /// configuration.
+    pub fn into_staged_welcome_with_credential_validator<Provider: OpenMlsProvider>(
+        self,
+        provider: &Provider,
+        ratchet_tree: Option<RatchetTreeIn>,
+        credential_validator: &impl CredentialValidator,
+    ) -> Result<StagedWelcome, WelcomeError<Provider::StorageError>> {
+        self.into_staged_welcome_inner(
+            provider,
+            ratchet_tree,
+            LeafNodeLifetimePolicy::Verify,
+            false,
+            Some(credential_validator),
         )
     }
 
     /// Consume the `ProcessedWelcome` and combine it with the ratchet tree into
     /// a `StagedWelcome`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "stage_welcome",
+            level = "debug",
+            skip_all,
+            fields(
+                group_id = %crate::instrumentation::HexGroupId(self.verifiable_group_info.group_id()),
+                epoch = self.verifiable_group_info.epoch().as_u64(),
+            ),
+            err
+        )
+    )]
     pub(crate) fn into_staged_welcome_inner<Provider: OpenMlsProvider>(
         mut self,
         provider: &Provider,
         ratchet_tree: Option<RatchetTreeIn>,
         validate_lifetimes: LeafNodeLifetimePolicy,
         replace_old_group: bool,
+        credential_validator: Option<&dyn CredentialValidator>,
     ) -> Result<StagedWelcome, WelcomeError<Provider::StorageError>> {
+        time_operation!("stage_welcome");
         // Check if we need to replace an old group
         if !replace_old_group
             && MlsGroup::load(provider.storage(), self.verifiable_group_info.group_id())
@@ -315,6 +398,15 @@ impl ProcessedWelcome {
             validate_lifetimes,
         )?;
 
+        // The validator in the group configuration applies in addition to the
+        // given one.
+        if let Some(credential_validator) = CredentialValidators::new(
+            self.mls_group_config.credential_validator.as_ref(),
+            credential_validator,
+        ) {
+            public_group.validate_credentials(&credential_validator)?;
+        }
+
         // Find our own leaf in the tree. On the bundle path this is the leaf
         // whose signature key matches the local KeyPackage. On the
         // virtual-client path there is no local signature key, so the leaf is
@@ -554,10 +646,24 @@ impl StagedWelcome {
     }
 
     /// Consumes the [`StagedWelcome`] and returns the respective [`MlsGroup`].
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "join_group_from_welcome",
+            level = "debug",
+            skip_all,
+            fields(
+                group_id = %crate::instrumentation::HexGroupId(self.group_context().group_id()),
+                epoch = self.group_context().epoch().as_u64(),
+            ),
+            err
+        )
+    )]
     pub fn into_group<Provider: OpenMlsProvider>(
         self,
         provider: &Provider,
     ) -> Result<MlsGroup, WelcomeError<Provider::StorageError>> {
+        time_operation!("join_group_from_welcome");
         // If we got a path secret, derive the path (which also checks if the
         // public keys match) and store the derived keys in the key store.
         let group_keypairs = if let Some(path_keypairs) = self.path_keypairs {
@@ -587,17 +693,28 @@ impl StagedWelcome {
             resumption_psk_store: self.resumption_psk_store,
             #[cfg(feature = "extensions-draft")]
             application_export_tree: Some(application_export_tree),
+            future_epoch_messages: FutureEpochMessages::default(),
         };
 
-        mls_group
-            .store_epoch_keypairs(provider.storage(), group_keypairs.as_slice())
-            .map_err(WelcomeError::StorageError)?;
         // resize the store
         mls_group.resize_message_secrets_store(&past_epoch_deletion_policy);
 
-        mls_group
-            .store(provider.storage())
-            .map_err(WelcomeError::StorageError)?;
+        crate::storage::transaction(
+            provider.storage(),
+            || {
+                crate::utils::resolve_ready(async {
+                    mls_group
+                        .store_epoch_keypairs(provider.storage(), group_keypairs.as_slice())
+                        .await
+                        .map_err(WelcomeError::StorageError)?;
+                    mls_group
+                        .store(provider.storage())
+                        .await
+                        .map_err(WelcomeError::StorageError)
+                })
+            },
+            WelcomeError::StorageError,
+        )?;
 
         Ok(mls_group)
     }
@@ -868,6 +985,7 @@ pub struct JoinBuilder<'a, Provider: OpenMlsProvider> {
     ratchet_tree: Option<RatchetTreeIn>,
     validate_lifetimes: LeafNodeLifetimePolicy,
     replace_old_group: bool,
+    credential_validator: Option<&'a dyn CredentialValidator>,
 }
 
 impl<'a, Provider: OpenMlsProvider> JoinBuilder<'a, Provider> {
@@ -879,6 +997,7 @@ impl<'a, Provider: OpenMlsProvider> JoinBuilder<'a, Provider> {
             ratchet_tree: None,
             replace_old_group: false,
             validate_lifetimes: LeafNodeLifetimePolicy::Verify,
+            credential_validator: None,
         }
     }
 
@@ -903,6 +1022,17 @@ impl<'a, Provider: OpenMlsProvider> JoinBuilder<'a, Provider> {
         self
     }
 
+    /// Validate the credentials of all members and external senders of the
+    /// group using the given [`CredentialValidator`], in addition to the one
+    /// set in the group configuration.
+    pub fn with_credential_validator(
+        mut self,
+        credential_validator: &'a impl CredentialValidator,
+    ) -> Self {
+        self.credential_validator = Some(credential_validator);
+        self
+    }
+
     /// Get a reference to the [`ProcessedWelcome`].
     ///
     /// Use this to inspect the [`Welcome`] message before validation.
@@ -917,6 +1047,7 @@ impl<'a, Provider: OpenMlsProvider> JoinBuilder<'a, Provider> {
             self.ratchet_tree,
             self.validate_lifetimes,
             self.replace_old_group,
+            self.credential_validator,
         )
     }
 }
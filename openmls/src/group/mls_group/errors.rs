@@ -8,12 +8,13 @@
 use thiserror::Error;
 
 use crate::{
+    credentials::errors::CredentialValidationError,
     error::LibraryError,
     extensions::errors::InvalidExtensionError,
     group::{
         errors::{
             CreateAddProposalError, CreateCommitError, MergeCommitError, StageCommitError,
-            ValidationError,
+            ValidationError, WelcomeError,
         },
         CommitBuilderStageError, CreateGroupContextExtProposalError,
     },
@@ -104,6 +105,12 @@ pub enum MlsGroupStateError {
     /// Requested pending proposal hasn't been found in local pending proposals
     #[error("Requested pending proposal hasn't been found in local pending proposals.")]
     PendingProposalNotFound,
+    /// Can't execute operation because the group is waiting to be reinitialized.
+    #[error("Can't execute operation because the group is waiting to be reinitialized.")]
+    ReInitPending,
+    /// Can't execute operation because the group is not waiting to be reinitialized.
+    #[error("Can't execute operation because the group is not waiting to be reinitialized.")]
+    NoReInitPending,
 }
 
 /// Error merging pending commit
@@ -117,6 +124,20 @@ pub enum MergePendingCommitError<StorageError> {
     MergeCommitError(#[from] MergeCommitError<StorageError>),
 }
 
+/// Error merging a commit and processing the buffered messages
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum MergeAndProcessBufferedError<StorageError> {
+    /// See [`LibraryError`] for more details.
+    #[error(transparent)]
+    LibraryError(#[from] LibraryError),
+    /// See [`MergeCommitError`] for more details.
+    #[error(transparent)]
+    MergeCommitError(#[from] MergeCommitError<StorageError>),
+    /// See [`ProcessMessageError`] for more details.
+    #[error(transparent)]
+    ProcessMessageError(#[from] ProcessMessageError<StorageError>),
+}
+
 /// Process message error
 #[derive(Error, Debug, PartialEq, Clone)]
 pub enum PublicProcessMessageError {
@@ -179,6 +200,27 @@ pub enum ProcessMessageError<StorageError> {
     /// The proposal is invalid for the Sender of type [External](crate::prelude::Sender::External)
     #[error("The proposal is invalid for the Sender of type External")]
     UnsupportedProposalType,
+    /// See [`CredentialValidationError`] for more details.
+    #[error(transparent)]
+    CredentialValidationError(#[from] CredentialValidationError),
+    /// The message is from the next epoch and was buffered until the commit
+    /// for that epoch is merged. See [`FutureEpochBufferConfig`] for more
+    /// details.
+    ///
+    /// [`FutureEpochBufferConfig`]: crate::group::FutureEpochBufferConfig
+    #[error("The message is from the next epoch and was buffered until the commit for that epoch is merged.")]
+    MessageBuffered,
+    /// The message is from the next epoch, but the buffer for such messages
+    /// is full. The buffered messages are kept.
+    #[error("The message is from the next epoch, but the buffer for such messages is full.")]
+    FutureEpochBufferFull,
+    /// The message is from the next epoch and the buffer for such messages is
+    /// enabled, but the storage provider doesn't support buffering messages.
+    /// See [`StorageProvider::supports_future_epoch_messages()`].
+    ///
+    /// [`StorageProvider::supports_future_epoch_messages()`]: openmls_traits::storage::StorageProvider::supports_future_epoch_messages
+    #[error("The storage provider doesn't support buffering messages from the next epoch.")]
+    FutureEpochBufferUnsupported,
 
     /// Use `_with_app_data_update` functions for handling AppDataUpdate proposals
     #[cfg(feature = "extensions-draft")]
@@ -202,6 +244,13 @@ pub enum CreateMessageError {
     /// See [`MlsGroupStateError`] for more details.
     #[error(transparent)]
     GroupStateError(#[from] MlsGroupStateError),
+    /// The padding strategy is [`PaddingStrategy::Custom`], but the callback
+    /// is not set. Callbacks are not persisted and have to be set again after
+    /// loading a group.
+    ///
+    /// [`PaddingStrategy::Custom`]: crate::group::PaddingStrategy::Custom
+    #[error("The callback of the custom padding strategy is not set.")]
+    MissingPaddingCallback,
 }
 
 /// Add members error
@@ -570,6 +619,70 @@ pub enum RemoveProposalError<StorageError> {
     Storage(StorageError),
 }
 
+/// ReInit error
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum ReInitError<StorageError> {
+    /// See [`LibraryError`] for more details.
+    #[error(transparent)]
+    LibraryError(#[from] LibraryError),
+    /// See [`MlsGroupStateError`] for more details.
+    #[error(transparent)]
+    GroupStateError(#[from] MlsGroupStateError),
+    /// The protocol version of the ReInit proposal is not supported.
+    #[error("The protocol version of the ReInit proposal is not supported.")]
+    UnsupportedVersion,
+    /// See [`NewGroupError`] for more details.
+    #[error(transparent)]
+    NewGroupError(#[from] NewGroupError<StorageError>),
+    /// See [`CreateCommitError`] for more details.
+    #[error(transparent)]
+    CreateCommitError(#[from] CreateCommitError),
+    /// See [`CommitBuilderStageError`] for more details.
+    #[error(transparent)]
+    CommitBuilderStageError(#[from] CommitBuilderStageError<StorageError>),
+    /// See [`WelcomeError`] for more details.
+    #[error(transparent)]
+    WelcomeError(#[from] WelcomeError<StorageError>),
+    /// The Welcome message does not inject the resumption PSK of the reinitialized group.
+    #[error("The Welcome message does not inject the resumption PSK of the reinitialized group.")]
+    MissingReInitPsk,
+    /// The parameters of the new group don't match the ones in the ReInit proposal.
+    #[error("The parameters of the new group don't match the ones in the ReInit proposal.")]
+    ReInitParameterMismatch,
+}
+
+/// Branch error
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum BranchError<StorageError> {
+    /// See [`LibraryError`] for more details.
+    #[error(transparent)]
+    LibraryError(#[from] LibraryError),
+    /// Error accessing the storage.
+    #[error("Error accessing the storage.")]
+    StorageError(StorageError),
+    /// See [`NewGroupError`] for more details.
+    #[error(transparent)]
+    NewGroupError(#[from] NewGroupError<StorageError>),
+    /// See [`CreateCommitError`] for more details.
+    #[error(transparent)]
+    CreateCommitError(#[from] CreateCommitError),
+    /// See [`CommitBuilderStageError`] for more details.
+    #[error(transparent)]
+    CommitBuilderStageError(#[from] CommitBuilderStageError<StorageError>),
+    /// See [`WelcomeError`] for more details.
+    #[error(transparent)]
+    WelcomeError(#[from] WelcomeError<StorageError>),
+    /// A member of the subgroup is not a member of the parent group.
+    #[error("A member of the subgroup is not a member of the parent group.")]
+    NotAParentGroupMember,
+    /// The Welcome message does not inject a branch resumption PSK of the parent group.
+    #[error("The Welcome message does not inject a branch resumption PSK of the parent group.")]
+    MissingBranchPsk,
+    /// The parameters of the subgroup don't match the ones of the parent group.
+    #[error("The parameters of the subgroup don't match the ones of the parent group.")]
+    BranchParameterMismatch,
+}
+
 #[cfg(feature = "virtual-clients-draft")]
 pub use virtual_clients_draft::*;
 
@@ -0,0 +1,269 @@
+//! Buffering of messages from the next epoch.
+//!
+//! See [`FutureEpochBufferConfig`] for how the buffer is configured.
+
+use std::collections::VecDeque;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::SystemTime;
+#[cfg(target_arch = "wasm32")]
+use web_time::SystemTime;
+
+use super::{
+    errors::{MergeAndProcessBufferedError, ProcessMessageError},
+    *,
+};
+use crate::credentials::CredentialValidator;
+
+/// A message from the next epoch, together with the time it was buffered.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct BufferedMessage {
+    received_at: SystemTime,
+    message: ProtocolMessage,
+}
+
+/// Messages from the next epoch that wait for the commit that moves the group
+/// into that epoch. The oldest message is at the front.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct FutureEpochMessages {
+    messages: VecDeque<BufferedMessage>,
+}
+
+impl FutureEpochMessages {
+    pub(crate) fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Adds a message to the buffer after dropping the expired messages.
+    ///
+    /// The messages can't be authenticated before the group is in their
+    /// epoch, so a full buffer rejects the new message instead of evicting
+    /// older ones, which may be legitimate. Returns `false` if the buffer is
+    /// full.
+    fn push(
+        &mut self,
+        config: &FutureEpochBufferConfig,
+        now: SystemTime,
+        message: ProtocolMessage,
+    ) -> bool {
+        self.messages
+            .retain(|buffered| !config.is_expired(buffered.received_at, now));
+        if self.messages.len() >= config.max_messages() {
+            return false;
+        }
+        self.messages.push_back(BufferedMessage {
+            received_at: now,
+            message,
+        });
+        true
+    }
+}
+
+impl MlsGroup {
+    /// Returns the number of messages from the next epoch that are currently
+    /// buffered.
+    pub fn buffered_future_epoch_messages(&self) -> usize {
+        self.future_epoch_messages.len()
+    }
+
+    /// Processes the buffered messages that are no longer from a future
+    /// epoch, i.e. after the commit into their epoch was merged, and returns
+    /// them in the order they were buffered.
+    ///
+    /// Processed messages are removed from the buffer in the storage.
+    /// Buffered messages that fail to process or are expired are dropped.
+    /// Messages from later epochs remain in the buffer. See
+    /// [`FutureEpochBufferConfig`] for more information.
+    ///
+    /// # Errors:
+    /// Returns a [`ProcessMessageError::StorageError`] if reading or writing
+    /// the storage fails. The buffered messages that were not processed yet
+    /// remain in the storage.
+    pub fn process_buffered_messages<Provider: OpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+    ) -> Result<Vec<ProcessedMessage>, ProcessMessageError<Provider::StorageError>> {
+        crate::utils::resolve_ready(self.process_buffered_messages_async(provider))
+    }
+
+    /// Processes the buffered messages like
+    /// [`MlsGroup::process_buffered_messages()`], but reads and persists the
+    /// group state through an asynchronous storage.
+    pub async fn process_buffered_messages_async<Provider: AsyncOpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+    ) -> Result<Vec<ProcessedMessage>, ProcessMessageError<Provider::StorageError>> {
+        self.process_buffered_messages_internal(provider, None::<&(dyn CredentialValidator + Sync)>)
+            .await
+    }
+
+    /// Processes the buffered messages like
+    /// [`MlsGroup::process_buffered_messages()`], but validates the
+    /// credentials that the messages introduce into the group like
+    /// [`MlsGroup::process_message_with_credential_validator()`]. Messages
+    /// with rejected credentials are dropped.
+    pub fn process_buffered_messages_with_credential_validator<Provider: OpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+        credential_validator: &impl CredentialValidator,
+    ) -> Result<Vec<ProcessedMessage>, ProcessMessageError<Provider::StorageError>> {
+        crate::utils::resolve_ready(
+            self.process_buffered_messages_internal(provider, Some(credential_validator)),
+        )
+    }
+
+    /// Merges a [`StagedCommit`] like [`MlsGroup::merge_staged_commit()`] and
+    /// then processes the buffered messages from the new epoch like
+    /// [`MlsGroup::process_buffered_messages()`]. Returns the processed
+    /// messages in the order they were buffered.
+    ///
+    /// # Errors:
+    /// Returns a [`MergeAndProcessBufferedError::MergeCommitError`] if merging
+    /// fails. No buffered message is processed in that case. Returns a
+    /// [`MergeAndProcessBufferedError::ProcessMessageError`] if reading or
+    /// writing the storage fails while processing the buffered messages. The
+    /// commit is merged in that case and the buffered messages that were not
+    /// processed yet remain in the storage.
+    pub fn merge_staged_commit_and_process_buffered_messages<Provider: OpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+        staged_commit: StagedCommit,
+    ) -> Result<Vec<ProcessedMessage>, MergeAndProcessBufferedError<Provider::StorageError>> {
+        crate::utils::resolve_ready(
+            self.merge_staged_commit_and_process_buffered_messages_async(provider, staged_commit),
+        )
+    }
+
+    /// Merges a [`StagedCommit`] and processes the buffered messages like
+    /// [`MlsGroup::merge_staged_commit_and_process_buffered_messages()`], but
+    /// reads and persists the group state through an asynchronous storage.
+    pub async fn merge_staged_commit_and_process_buffered_messages_async<
+        Provider: AsyncOpenMlsProvider,
+    >(
+        &mut self,
+        provider: &Provider,
+        staged_commit: StagedCommit,
+    ) -> Result<Vec<ProcessedMessage>, MergeAndProcessBufferedError<Provider::StorageError>> {
+        self.merge_staged_commit_async(provider, staged_commit)
+            .await?;
+        Ok(self.process_buffered_messages_async(provider).await?)
+    }
+
+    /// Returns `true` if the buffer is enabled and the message belongs to the
+    /// epoch following the current one.
+    pub(super) fn is_next_epoch_message(&self, message: &ProtocolMessage) -> bool {
+        self.configuration().future_epoch_buffer().is_enabled()
+            && message.group_id() == self.group_id()
+            && message.epoch().as_u64() == self.epoch().as_u64().saturating_add(1)
+    }
+
+    /// Adds a message from the next epoch to the buffer and persists it.
+    ///
+    /// Returns [`ProcessMessageError::FutureEpochBufferFull`] if the buffer is
+    /// full and [`ProcessMessageError::FutureEpochBufferUnsupported`] if the
+    /// storage can't persist the buffer.
+    pub(super) async fn buffer_future_epoch_message<Storage: AsyncStorageProvider>(
+        &mut self,
+        storage: &Storage,
+        message: ProtocolMessage,
+    ) -> Result<(), ProcessMessageError<Storage::Error>> {
+        if !storage.supports_future_epoch_messages() {
+            return Err(ProcessMessageError::FutureEpochBufferUnsupported);
+        }
+        let config = *self.configuration().future_epoch_buffer();
+        // The buffer is only updated once it is persisted, so that a failing
+        // write doesn't leave a message in memory that is lost on reload.
+        let mut future_epoch_messages = self.future_epoch_messages.clone();
+        if !future_epoch_messages.push(&config, SystemTime::now(), message) {
+            return Err(ProcessMessageError::FutureEpochBufferFull);
+        }
+        storage
+            .write_future_epoch_messages(self.group_id(), &future_epoch_messages)
+            .await
+            .map_err(ProcessMessageError::StorageError)?;
+        self.future_epoch_messages = future_epoch_messages;
+        Ok(())
+    }
+
+    /// Processes the buffered messages that are no longer from a future epoch
+    /// with the `credential_validator` if there is one.
+    ///
+    /// The buffer is persisted after every processed message, so that a
+    /// message isn't processed twice if a later one fails with a storage
+    /// error. All messages are dropped if the group is no longer active.
+    async fn process_buffered_messages_internal<
+        Provider: AsyncOpenMlsProvider,
+        Validator: CredentialValidator + ?Sized,
+    >(
+        &mut self,
+        provider: &Provider,
+        credential_validator: Option<&Validator>,
+    ) -> Result<Vec<ProcessedMessage>, ProcessMessageError<Provider::StorageError>> {
+        let mut processed_messages = vec![];
+        if self.future_epoch_messages.is_empty() {
+            return Ok(processed_messages);
+        }
+
+        let config = *self.configuration().future_epoch_buffer();
+        let now = SystemTime::now();
+        let mut index = 0;
+        while index < self.future_epoch_messages.len() {
+            let buffered = &self.future_epoch_messages.messages[index];
+            let expired = !self.is_active() || config.is_expired(buffered.received_at, now);
+            if !expired && buffered.message.epoch() > self.epoch() {
+                index += 1;
+                continue;
+            }
+            let Some(BufferedMessage { message, .. }) =
+                self.future_epoch_messages.messages.remove(index)
+            else {
+                break;
+            };
+            if expired {
+                continue;
+            }
+
+            let epoch = message.epoch();
+            match self
+                .process_message_internal(provider, message, credential_validator)
+                .await
+            {
+                Ok(processed_message) => processed_messages.push(processed_message),
+                Err(ProcessMessageError::StorageError(e)) => {
+                    return Err(ProcessMessageError::StorageError(e))
+                }
+                Err(e) => {
+                    log::warn!("Dropping buffered message from epoch {epoch}: {e:?}");
+                }
+            }
+            self.persist_future_epoch_messages(provider.storage())
+                .await
+                .map_err(ProcessMessageError::StorageError)?;
+        }
+
+        // Persist the buffer once more in case only expired messages were
+        // dropped.
+        self.persist_future_epoch_messages(provider.storage())
+            .await
+            .map_err(ProcessMessageError::StorageError)?;
+        Ok(processed_messages)
+    }
+
+    /// Writes the buffer to the storage, or deletes it if it is empty.
+    async fn persist_future_epoch_messages<Storage: AsyncStorageProvider>(
+        &self,
+        storage: &Storage,
+    ) -> Result<(), Storage::Error> {
+        if self.future_epoch_messages.is_empty() {
+            storage.delete_future_epoch_messages(self.group_id()).await
+        } else {
+            storage
+                .write_future_epoch_messages(self.group_id(), &self.future_epoch_messages)
+                .await
+        }
+    }
+}
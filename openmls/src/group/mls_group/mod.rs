@@ -3,6 +3,7 @@
 //! This module contains [`MlsGroup`] and its submodules.
 //!
 
+use future_epoch_messages::FutureEpochMessages;
 use past_secrets::MessageSecretsStore;
 use proposal_store::ProposalQueue;
 use serde::{Deserialize, Serialize};
@@ -15,7 +16,7 @@ use super::proposal_store::{ProposalStore, QueuedProposal};
 use crate::{
     binary_tree::array_representation::LeafNodeIndex,
     ciphersuite::{hash_ref::ProposalRef, signable::Signable},
-    credentials::Credential,
+    credentials::{ConfiguredCredentialValidator, Credential, CredentialValidator},
     error::LibraryError,
     extensions::Extensions,
     framing::{mls_auth_content::AuthenticatedContent, *},
@@ -33,10 +34,10 @@ use crate::{
     },
     schedule::{
         message_secrets::MessageSecrets,
-        psk::{load_psks, store::ResumptionPskStore, PskSecret},
+        psk::{load_parent_psks, load_psks, store::ResumptionPskStore, PskSecret},
         GroupEpochSecrets, JoinerSecret, KeySchedule,
     },
-    storage::{OpenMlsProvider, StorageProvider},
+    storage::{AsyncOpenMlsProvider, AsyncStorageProvider, OpenMlsProvider, StorageProvider},
     treesync::{
         node::{encryption_keys::EncryptionKeyPair, leaf_node::LeafNode},
         RatchetTree, TreeSync,
@@ -61,17 +62,21 @@ pub use application::UnconfirmedMessage;
 use config::*;
 
 // Crate
+pub(crate) mod branch;
 pub(crate) mod builder;
 pub(crate) mod commit_builder;
 pub(crate) mod config;
 pub(crate) mod creation;
 pub(crate) mod errors;
+pub(crate) mod future_epoch_messages;
 pub(crate) mod membership;
 pub(crate) mod past_secrets;
 pub(crate) mod processing;
 pub(crate) mod proposal;
 pub(crate) mod proposal_store;
+pub(crate) mod reinit;
 pub(crate) mod staged_commit;
+mod successor;
 
 #[cfg(feature = "extensions-draft")]
 pub(crate) mod app_ephemeral;
@@ -159,8 +164,9 @@ impl From<PendingCommitState> for StagedCommit {
 ///   [`MlsGroup::external_commit_builder()`], see the functions documentation for
 ///   more information). From this `Operational`, the group state can either
 ///   transition to [`MlsGroupState::Inactive`], when it processes a commit that
-///   removes this client from the group, or to [`MlsGroupState::PendingCommit`],
-///   when this client creates a commit.
+///   removes this client from the group, to [`MlsGroupState::ReInitPending`],
+///   when it merges a commit that contains a ReInit proposal, or to
+///   [`MlsGroupState::PendingCommit`], when this client creates a commit.
 ///
 /// * [`MlsGroupState::Inactive`]: A group can enter this state from any other
 ///   state when it processes a commit that removes this client from the group.
@@ -168,6 +174,13 @@ impl From<PendingCommitState> for StagedCommit {
 ///   wants to re-join the group, it can either be added by a group member or it
 ///   can join via external commit.
 ///
+/// * [`MlsGroupState::ReInitPending`]: A group enters this state when it merges
+///   a commit that contains a ReInit proposal. This is a terminal state as
+///   well: the group can no longer be used to create proposals, commits or
+///   application messages. Instead, its members move to a successor group
+///   that is set up with the parameters of the [`ReInitProposal`], see
+///   [`MlsGroup::reinit()`] and [`MlsGroup::join_reinit()`].
+///
 /// * [`MlsGroupState::PendingCommit`]: This state is split into two possible
 ///   sub-states, one for each Commit type:
 ///   [`PendingCommitState::Member`] and [`PendingCommitState::External`]:
@@ -202,6 +215,9 @@ pub enum MlsGroupState {
     Operational,
     /// The group is inactive because the member has been removed.
     Inactive,
+    /// A commit with a ReInit proposal has been merged and the group waits to
+    /// be replaced by the successor group described by the proposal.
+    ReInitPending(Box<ReInitProposal>),
 }
 
 /// A `MlsGroup` represents an MLS group with a high-level API. The API exposes
@@ -269,6 +285,9 @@ pub struct MlsGroup {
     /// loaded and has not yet merged a commit.
     #[cfg(feature = "extensions-draft")]
     application_export_tree: Option<ApplicationExportTree>,
+    /// Messages from the next epoch that wait for the commit into that epoch.
+    /// See [`FutureEpochBufferConfig`] for more information.
+    future_epoch_messages: FutureEpochMessages,
 }
 
 impl MlsGroup {
@@ -280,6 +299,9 @@ impl MlsGroup {
     }
 
     /// Sets the configuration.
+    ///
+    /// Only whether the configuration has a [`CredentialValidator`] is
+    /// persisted, see [`MlsGroup::set_credential_validator()`].
     pub fn set_configuration<Storage: StorageProvider>(
         &mut self,
         storage: &Storage,
@@ -289,6 +311,25 @@ impl MlsGroup {
         storage.write_mls_join_config(self.group_id(), mls_group_config)
     }
 
+    /// Sets the [`CredentialValidator`] of the configuration.
+    ///
+    /// The validator itself is not persisted with the configuration, only
+    /// that the group requires one. After loading such a group, processing
+    /// messages and merging commits fail with
+    /// [`CredentialValidationError::ValidatorMissing`] until the validator is
+    /// set again using this function. Since the stored configuration doesn't
+    /// change, nothing is written to the storage.
+    ///
+    /// [`CredentialValidationError::ValidatorMissing`]: crate::credentials::errors::CredentialValidationError::ValidatorMissing
+    pub fn set_credential_validator(
+        &mut self,
+        credential_validator: impl CredentialValidator + Send + Sync + 'static,
+    ) {
+        self.mls_group_config.credential_validator = Some(ConfiguredCredentialValidator::Set(
+            std::sync::Arc::new(credential_validator),
+        ));
+    }
+
     /// Sets the additional authenticated data (AAD) for the next outgoing
     /// message. This is ephemeral and will be reset by every API call that
     /// successfully returns an [`MlsMessageOut`].
@@ -394,6 +435,7 @@ impl MlsGroup {
             }
             MlsGroupState::Operational => None,
             MlsGroupState::Inactive => None,
+            MlsGroupState::ReInitPending(_) => None,
         }
     }
 
@@ -412,16 +454,28 @@ impl MlsGroup {
         &mut self,
         storage: &Storage,
     ) -> Result<(), Storage::Error> {
+        if self.discard_pending_member_commit() {
+            storage.write_group_state(self.group_id(), &self.group_state)?;
+        }
+        Ok(())
+    }
+
+    /// Moves the group back to the operational state if it has a pending
+    /// commit of a member. Returns `true` if the group state changed and has
+    /// to be persisted.
+    pub(super) fn discard_pending_member_commit(&mut self) -> bool {
         match self.group_state {
             MlsGroupState::PendingCommit(ref pending_commit_state) => {
                 if let PendingCommitState::Member(_) = **pending_commit_state {
                     self.group_state = MlsGroupState::Operational;
-                    storage.write_group_state(self.group_id(), &self.group_state)
+                    true
                 } else {
-                    Ok(())
+                    false
                 }
             }
-            MlsGroupState::Operational | MlsGroupState::Inactive => Ok(()),
+            MlsGroupState::Operational
+            | MlsGroupState::Inactive
+            | MlsGroupState::ReInitPending(_) => false,
         }
     }
 
@@ -467,6 +521,13 @@ impl MlsGroup {
         storage: &Storage,
         group_id: &GroupId,
     ) -> Result<Option<MlsGroup>, Storage::Error> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            "load_group",
+            group_id = %crate::instrumentation::HexGroupId(group_id)
+        )
+        .entered();
+        time_operation!("load_group");
         let public_group = PublicGroup::load(storage, group_id)?;
         let group_epoch_secrets = storage.group_epoch_secrets(group_id)?;
         let own_leaf_index = storage.own_leaf_index(group_id)?;
@@ -477,6 +538,68 @@ impl MlsGroup {
         let group_state = storage.group_state(group_id)?;
         #[cfg(feature = "extensions-draft")]
         let application_export_tree = storage.application_export_tree(group_id)?;
+        let future_epoch_messages: Option<FutureEpochMessages> =
+            storage.future_epoch_messages(group_id)?;
+
+        let build = || -> Option<Self> {
+            Some(Self {
+                public_group: public_group?,
+                group_epoch_secrets: group_epoch_secrets?,
+                own_leaf_index: own_leaf_index?,
+                message_secrets_store: message_secrets_store?,
+                resumption_psk_store: resumption_psk_store?,
+                mls_group_config: mls_group_config?,
+                own_leaf_nodes,
+                aad: vec![],
+                #[cfg(feature = "extensions-draft")]
+                safe_aad: SafeAad::empty(),
+                group_state: group_state?,
+                #[cfg(feature = "extensions-draft")]
+                application_export_tree,
+                future_epoch_messages: future_epoch_messages.unwrap_or_default(),
+            })
+        };
+
+        Ok(build())
+    }
+
+    /// Loads the state of the group with given id from persisted state in an
+    /// [`AsyncStorageProvider`].
+    ///
+    /// This is the asynchronous variant of [`MlsGroup::load`].
+    pub async fn load_async<Storage: AsyncStorageProvider>(
+        storage: &Storage,
+        group_id: &GroupId,
+    ) -> Result<Option<MlsGroup>, Storage::Error> {
+        let load = Self::load_async_internal(storage, group_id);
+        #[cfg(feature = "tracing")]
+        let load = tracing::Instrument::instrument(
+            load,
+            tracing::debug_span!(
+                "load_group",
+                group_id = %crate::instrumentation::HexGroupId(group_id)
+            ),
+        );
+        load.await
+    }
+
+    async fn load_async_internal<Storage: AsyncStorageProvider>(
+        storage: &Storage,
+        group_id: &GroupId,
+    ) -> Result<Option<MlsGroup>, Storage::Error> {
+        time_operation!("load_group");
+        let public_group = PublicGroup::load_async(storage, group_id).await?;
+        let group_epoch_secrets = storage.group_epoch_secrets(group_id).await?;
+        let own_leaf_index = storage.own_leaf_index(group_id).await?;
+        let message_secrets_store = storage.message_secrets(group_id).await?;
+        let resumption_psk_store = storage.resumption_psk_store(group_id).await?;
+        let mls_group_config = storage.mls_group_join_config(group_id).await?;
+        let own_leaf_nodes = storage.own_leaf_nodes(group_id).await?;
+        let group_state = storage.group_state(group_id).await?;
+        #[cfg(feature = "extensions-draft")]
+        let application_export_tree = storage.application_export_tree(group_id).await?;
+        let future_epoch_messages: Option<FutureEpochMessages> =
+            storage.future_epoch_messages(group_id).await?;
 
         let build = || -> Option<Self> {
             Some(Self {
@@ -493,6 +616,7 @@ impl MlsGroup {
                 group_state: group_state?,
                 #[cfg(feature = "extensions-draft")]
                 application_export_tree,
+                future_epoch_messages: future_epoch_messages.unwrap_or_default(),
             })
         };
 
@@ -502,9 +626,27 @@ impl MlsGroup {
     /// Remove the persisted state of this group from storage. Note that
     /// signature key material is not managed by OpenMLS and has to be removed
     /// from the storage provider separately (if desired).
+    ///
+    /// All deletions happen in one storage transaction, so that the group is
+    /// either removed entirely or left in storage unchanged.
     pub fn delete<Storage: crate::storage::StorageProvider>(
         &mut self,
         storage: &Storage,
+    ) -> Result<(), Storage::Error> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            "delete_group",
+            group_id = %crate::instrumentation::HexGroupId(self.group_id()),
+            epoch = self.epoch().as_u64()
+        )
+        .entered();
+        time_operation!("delete_group");
+        crate::storage::transaction(storage, || self.delete_internal(storage), |e| e)
+    }
+
+    fn delete_internal<Storage: crate::storage::StorageProvider>(
+        &mut self,
+        storage: &Storage,
     ) -> Result<(), Storage::Error> {
         PublicGroup::delete(storage, self.group_id())?;
         storage.delete_own_leaf_index(self.group_id())?;
@@ -515,6 +657,7 @@ impl MlsGroup {
         storage.delete_own_leaf_nodes(self.group_id())?;
         storage.delete_group_state(self.group_id())?;
         storage.clear_proposal_queue::<GroupId, ProposalRef>(self.group_id())?;
+        storage.delete_future_epoch_messages(self.group_id())?;
 
         #[cfg(feature = "extensions-draft")]
         storage.delete_application_export_tree::<_, ApplicationExportTree>(self.group_id())?;
@@ -707,7 +850,7 @@ impl MlsGroup {
     ///
     /// [`EmulationEpochState`]: crate::components::vc_derivation_info::EmulationEpochState
     #[cfg(feature = "virtual-clients-draft")]
-    pub(crate) fn vc_emulation_state_at_epoch<Storage: StorageProvider>(
+    pub(crate) async fn vc_emulation_state_at_epoch<Storage: AsyncStorageProvider>(
         &self,
         storage: &Storage,
         epoch: GroupEpoch,
@@ -717,12 +860,14 @@ impl MlsGroup {
     > {
         let bindings: Option<crate::components::vc_derivation_info::VcEmulationBindings> = storage
             .vc_emulation_bindings(self.group_id())
+            .await
             .map_err(VcEmulationStateError::Storage)?;
         let Some(epoch_id) = bindings.and_then(|bindings| bindings.get(epoch).cloned()) else {
             return Ok(None);
         };
         let state = storage
             .vc_emulation_epoch_state(&epoch_id)
+            .await
             .map_err(VcEmulationStateError::Storage)?
             .ok_or_else(|| {
                 log::error!("vc: group is bound to emulation epoch, but state is missing");
@@ -732,12 +877,12 @@ impl MlsGroup {
     }
 
     // Encrypt an AuthenticatedContent into an PrivateMessage
-    pub(crate) fn encrypt<Provider: OpenMlsProvider>(
+    pub(crate) async fn encrypt<Provider: AsyncOpenMlsProvider>(
         &mut self,
         public_message: AuthenticatedContent,
         provider: &Provider,
     ) -> Result<EncryptionOutput, MessageEncryptionError<Provider::StorageError>> {
-        let padding_size = self.configuration().padding_size();
+        let padding_policy = self.configuration().padding_policy();
 
         // If this group is bound to an emulation epoch at its current epoch,
         // load the state so the framing layer can derive a deterministic
@@ -745,6 +890,7 @@ impl MlsGroup {
         #[cfg(feature = "virtual-clients-draft")]
         let emulation_state = self
             .vc_emulation_state_at_epoch(provider.storage(), self.epoch())
+            .await
             .map_err(|e| match e {
                 VcEmulationStateError::Storage(e) => MessageEncryptionError::StorageError(e),
                 VcEmulationStateError::MissingEmulationEpochState => {
@@ -764,7 +910,7 @@ impl MlsGroup {
             &public_message,
             self.ciphersuite(),
             self.message_secrets_store.message_secrets_mut(),
-            padding_size,
+            padding_policy,
             #[cfg(feature = "virtual-clients-draft")]
             emulator_ctx.as_ref(),
         )?;
@@ -793,9 +939,11 @@ impl MlsGroup {
             msg
         };
 
+        use openmls_traits::async_storage::AsyncStorageProvider as _;
         provider
             .storage()
             .write_message_secrets(self.group_id(), &self.message_secrets_store)
+            .await
             .map_err(MessageEncryptionError::StorageError)?;
 
         Ok(msg)
@@ -895,32 +1043,36 @@ impl MlsGroup {
     /// indexed by this group's [`GroupId`] and [`GroupEpoch`].
     ///
     /// Returns an error if access to the key store fails.
-    pub(super) fn store_epoch_keypairs<Storage: StorageProvider>(
+    pub(super) async fn store_epoch_keypairs<Storage: AsyncStorageProvider>(
         &self,
         store: &Storage,
         keypair_references: &[EncryptionKeyPair],
     ) -> Result<(), Storage::Error> {
-        store.write_encryption_epoch_key_pairs(
-            self.group_id(),
-            &self.context().epoch(),
-            self.own_leaf_index().u32(),
-            keypair_references,
-        )
+        store
+            .write_encryption_epoch_key_pairs(
+                self.group_id(),
+                &self.context().epoch(),
+                self.own_leaf_index().u32(),
+                keypair_references,
+            )
+            .await
     }
 
     /// Read the [`EncryptionKeyPair`]s of this group and its current
     /// [`GroupEpoch`] from the `provider`'s storage.
     ///
     /// Returns an error if the lookup in the [`StorageProvider`] fails.
-    pub(super) fn read_epoch_keypairs<Storage: StorageProvider>(
+    pub(super) async fn read_epoch_keypairs<Storage: AsyncStorageProvider>(
         &self,
         store: &Storage,
     ) -> Result<Vec<EncryptionKeyPair>, Storage::Error> {
-        store.encryption_epoch_key_pairs(
-            self.group_id(),
-            &self.context().epoch(),
-            self.own_leaf_index().u32(),
-        )
+        store
+            .encryption_epoch_key_pairs(
+                self.group_id(),
+                &self.context().epoch(),
+                self.own_leaf_index().u32(),
+            )
+            .await
     }
 
     /// Delete the [`EncryptionKeyPair`]s from the previous [`GroupEpoch`] from
@@ -928,19 +1080,21 @@ impl MlsGroup {
     ///
     /// Returns an error if access to the key store fails.
     #[cfg(not(feature = "virtual-clients-draft"))]
-    pub(super) fn delete_previous_epoch_keypairs<Storage: StorageProvider>(
+    pub(super) async fn delete_previous_epoch_keypairs<Storage: AsyncStorageProvider>(
         &self,
         store: &Storage,
     ) -> Result<(), Storage::Error> {
-        store.delete_encryption_epoch_key_pairs(
-            self.group_id(),
-            &GroupEpoch::from(self.context().epoch().as_u64() - 1),
-            self.own_leaf_index().u32(),
-        )
+        store
+            .delete_encryption_epoch_key_pairs(
+                self.group_id(),
+                &GroupEpoch::from(self.context().epoch().as_u64() - 1),
+                self.own_leaf_index().u32(),
+            )
+            .await
     }
 
     #[cfg(feature = "virtual-clients-draft")]
-    pub(super) fn delete_previous_epoch_keypairs<Storage: StorageProvider>(
+    pub(super) async fn delete_previous_epoch_keypairs<Storage: AsyncStorageProvider>(
         &self,
         store: &Storage,
         previous_own_leaf_index: LeafNodeIndex,
@@ -950,29 +1104,45 @@ impl MlsGroup {
         // keypairs. Previous-epoch keypairs are still stored under the leaf
         // index from that previous epoch, so the caller must pass that index
         // explicitly instead of having this helper read `self.own_leaf_index()`.
-        store.delete_encryption_epoch_key_pairs(
-            self.group_id(),
-            &GroupEpoch::from(self.context().epoch().as_u64() - 1),
-            previous_own_leaf_index.u32(),
-        )
+        store
+            .delete_encryption_epoch_key_pairs(
+                self.group_id(),
+                &GroupEpoch::from(self.context().epoch().as_u64() - 1),
+                previous_own_leaf_index.u32(),
+            )
+            .await
     }
 
     /// Stores the state of this group. Only to be called from constructors to
     /// store the initial state of the group.
-    pub(super) fn store<Storage: crate::storage::StorageProvider>(
+    pub(super) async fn store<Storage: AsyncStorageProvider>(
         &self,
         storage: &Storage,
     ) -> Result<(), Storage::Error> {
-        self.public_group.store(storage)?;
-        storage.write_group_epoch_secrets(self.group_id(), &self.group_epoch_secrets)?;
-        storage.write_own_leaf_index(self.group_id(), &self.own_leaf_index)?;
-        storage.write_message_secrets(self.group_id(), &self.message_secrets_store)?;
-        storage.write_resumption_psk_store(self.group_id(), &self.resumption_psk_store)?;
-        storage.write_mls_join_config(self.group_id(), &self.mls_group_config)?;
-        storage.write_group_state(self.group_id(), &self.group_state)?;
+        self.public_group.store_async(storage).await?;
+        storage
+            .write_group_epoch_secrets(self.group_id(), &self.group_epoch_secrets)
+            .await?;
+        storage
+            .write_own_leaf_index(self.group_id(), &self.own_leaf_index)
+            .await?;
+        storage
+            .write_message_secrets(self.group_id(), &self.message_secrets_store)
+            .await?;
+        storage
+            .write_resumption_psk_store(self.group_id(), &self.resumption_psk_store)
+            .await?;
+        storage
+            .write_mls_join_config(self.group_id(), &self.mls_group_config)
+            .await?;
+        storage
+            .write_group_state(self.group_id(), &self.group_state)
+            .await?;
         #[cfg(feature = "extensions-draft")]
         if let Some(application_export_tree) = &self.application_export_tree {
-            storage.write_application_export_tree(self.group_id(), application_export_tree)?;
+            storage
+                .write_application_export_tree(self.group_id(), application_export_tree)
+                .await?;
         }
 
         Ok(())
@@ -1005,10 +1175,19 @@ impl MlsGroup {
                 // the `virtual-clients` feature, so the generation is unused.
                 let EncryptionOutput {
                     private_message, ..
-                } = self
-                    .encrypt(mls_auth_content, provider)
-                    // We can be sure the encryption will work because the plaintext was created by us
-                    .map_err(|_| LibraryError::custom("Malformed plaintext"))?;
+                } = crate::utils::resolve_ready(async {
+                    self.encrypt(mls_auth_content, provider)
+                        .await
+                        .map_err(|e| match e {
+                            // The application has to set the callback again
+                            // after loading the group.
+                            MessageEncryptionError::MissingPaddingCallback => LibraryError::custom(
+                                "The callback of the custom padding strategy is not set.",
+                            ),
+                            // We can be sure the encryption will work because the plaintext was created by us
+                            _ => LibraryError::custom("Malformed plaintext"),
+                        })
+                })?;
                 MlsMessageOut::from_private_message(private_message, self.version())
             }
         };
@@ -1016,11 +1195,12 @@ impl MlsGroup {
     }
 
     /// Check if the group is operational. Throws an error if the group is
-    /// inactive or if there is a pending commit.
+    /// inactive, waits to be reinitialized, or if there is a pending commit.
     fn is_operational(&self) -> Result<(), MlsGroupStateError> {
         match self.group_state {
             MlsGroupState::PendingCommit(_) => Err(MlsGroupStateError::PendingCommit),
             MlsGroupState::Inactive => Err(MlsGroupStateError::UseAfterEviction),
+            MlsGroupState::ReInitPending(_) => Err(MlsGroupStateError::ReInitPending),
             MlsGroupState::Operational => Ok(()),
         }
     }
@@ -1148,6 +1328,12 @@ impl MlsGroup {
                     self.application_export_tree, other.application_export_tree
                 ));
             }
+            if self.future_epoch_messages != other.future_epoch_messages {
+                diagnostics.push(format!(
+                    "future_epoch_messages:\n  Current: {:?}\n  Loaded:  {:?}",
+                    self.future_epoch_messages, other.future_epoch_messages
+                ));
+            }
 
             log::error!(
                 "Loaded group does not match current group! Differing fields ({}):\n\n{}",
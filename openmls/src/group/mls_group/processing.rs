@@ -3,13 +3,16 @@
 use std::mem;
 
 use errors::{CommitToPendingProposalsError, MergePendingCommitError};
-use openmls_traits::{crypto::OpenMlsCrypto, signatures::Signer, storage::StorageProvider as _};
+use openmls_traits::{
+    async_storage::AsyncStorageProvider as _, crypto::OpenMlsCrypto, signatures::Signer,
+};
 
 use crate::{
+    credentials::{CredentialValidator, CredentialValidators},
     framing::mls_content::FramedContentBody,
     group::{errors::MergeCommitError, StageCommitError, ValidationError},
     messages::group_info::GroupInfo,
-    storage::OpenMlsProvider,
+    storage::{AsyncOpenMlsProvider, OpenMlsProvider},
     tree::sender_ratchet::SenderRatchetConfiguration,
 };
 
@@ -116,16 +119,177 @@ impl MlsGroup {
     /// and semantic validation of the message. It returns a [ProcessedMessage]
     /// enum.
     ///
+    /// If the group's [`FutureEpochBufferConfig`] enables the buffer, messages
+    /// from the next epoch are buffered until the commit into that epoch is
+    /// merged. See [`MlsGroup::process_buffered_messages()`] and
+    /// [`MlsGroup::merge_staged_commit_and_process_buffered_messages()`].
+    ///
+    /// If the group configuration has a [`CredentialValidator`], the
+    /// credentials that the message introduces are validated with it, as
+    /// described in [`MlsGroup::process_message_with_credential_validator()`].
+    ///
     /// # Errors:
     /// Returns an [`ProcessMessageError`] when the validation checks fail
-    /// with the exact reason of the failure.
+    /// with the exact reason of the failure. Returns
+    /// [`ProcessMessageError::MessageBuffered`] if the message was buffered
+    /// and [`ProcessMessageError::FutureEpochBufferFull`] if it couldn't be
+    /// buffered.
     pub fn process_message<Provider: OpenMlsProvider>(
         &mut self,
         provider: &Provider,
         message: impl Into<ProtocolMessage>,
     ) -> Result<ProcessedMessage, ProcessMessageError<Provider::StorageError>> {
-        let unverified_message = self.unprotect_message(provider, message)?;
+        crate::utils::resolve_ready(self.process_message_async(provider, message))
+    }
 
+    /// Parses incoming messages from the DS like [`MlsGroup::process_message()`],
+    /// but reads and persists the group state through an asynchronous storage.
+    ///
+    /// # Errors:
+    /// Returns the same errors as [`MlsGroup::process_message()`].
+    pub async fn process_message_async<Provider: AsyncOpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+        message: impl Into<ProtocolMessage>,
+    ) -> Result<ProcessedMessage, ProcessMessageError<Provider::StorageError>> {
+        self.buffer_or_process_message(
+            provider,
+            message.into(),
+            None::<&(dyn CredentialValidator + Sync)>,
+        )
+        .await
+    }
+
+    /// Buffers the message if it is from the next epoch and the buffer is
+    /// enabled, and processes it with the `credential_validator` if there is
+    /// one otherwise.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "process_message",
+            level = "debug",
+            skip_all,
+            fields(
+                group_id = %crate::instrumentation::HexGroupId(self.group_id()),
+                epoch = self.epoch().as_u64(),
+                message_epoch = tracing::field::Empty,
+                wire_format = tracing::field::Empty,
+                content_type = tracing::field::Empty,
+                sender_leaf_index = tracing::field::Empty,
+                sender_external_index = tracing::field::Empty,
+                sender_type = tracing::field::Empty,
+                proposal_types = tracing::field::Empty,
+            ),
+            err
+        )
+    )]
+    async fn buffer_or_process_message<
+        Provider: AsyncOpenMlsProvider,
+        Validator: CredentialValidator + ?Sized,
+    >(
+        &mut self,
+        provider: &Provider,
+        message: ProtocolMessage,
+        credential_validator: Option<&Validator>,
+    ) -> Result<ProcessedMessage, ProcessMessageError<Provider::StorageError>> {
+        time_operation!("process_message");
+        #[cfg(feature = "tracing")]
+        crate::instrumentation::record_message(&message);
+        if self.is_active() && self.is_next_epoch_message(&message) {
+            self.buffer_future_epoch_message(provider.storage(), message)
+                .await?;
+            return Err(ProcessMessageError::MessageBuffered);
+        }
+
+        self.process_message_internal(provider, message, credential_validator)
+            .await
+    }
+
+    /// Processes a message that isn't buffered, validating its credentials
+    /// with the `credential_validator` if there is one.
+    ///
+    /// With a credential validator, the secret tree of a [`PrivateMessage`]
+    /// is only persisted once the validator accepted the credentials, so that
+    /// a rejected message doesn't consume key material. If the validator
+    /// rejects the message, the secret tree is restored from the storage.
+    pub(super) async fn process_message_internal<
+        Provider: AsyncOpenMlsProvider,
+        Validator: CredentialValidator + ?Sized,
+    >(
+        &mut self,
+        provider: &Provider,
+        message: ProtocolMessage,
+        credential_validator: Option<&Validator>,
+    ) -> Result<ProcessedMessage, ProcessMessageError<Provider::StorageError>> {
+        // The validator in the group configuration applies in addition to the
+        // given one. If the group was loaded from the storage, it has to be
+        // set again first.
+        let configured_validator = self.mls_group_config.credential_validator.clone();
+        if let Some(configured_validator) = &configured_validator {
+            configured_validator.check_set()?;
+        }
+        let credential_validators =
+            CredentialValidators::new(configured_validator.as_ref(), credential_validator);
+        let credential_validator = credential_validators.as_ref();
+
+        let defer_secret_tree_write =
+            credential_validator.is_some() && matches!(message, ProtocolMessage::PrivateMessage(_));
+        let unverified_message = self
+            .unprotect_message_internal(provider, message, !defer_secret_tree_write)
+            .await?;
+        #[cfg(feature = "tracing")]
+        crate::instrumentation::record_sender(unverified_message.sender());
+
+        let result = self
+            .process_unprotected_message(provider, unverified_message, credential_validator)
+            .await;
+        if defer_secret_tree_write {
+            if let Err(ProcessMessageError::CredentialValidationError(_)) = result {
+                if let Some(message_secrets_store) = provider
+                    .storage()
+                    .message_secrets(self.group_id())
+                    .await
+                    .map_err(ProcessMessageError::StorageError)?
+                {
+                    self.message_secrets_store = message_secrets_store;
+                }
+            } else {
+                provider
+                    .storage()
+                    .write_message_secrets(self.group_id(), &self.message_secrets_store)
+                    .await
+                    .map_err(ProcessMessageError::StorageError)?;
+            }
+        }
+        let processed_message = result?;
+
+        #[cfg(feature = "tracing")]
+        match processed_message.content() {
+            ProcessedMessageContent::ProposalMessage(proposal)
+            | ProcessedMessageContent::ExternalJoinProposalMessage(proposal) => {
+                crate::instrumentation::record_proposal_types(std::iter::once(proposal.as_ref()))
+            }
+            ProcessedMessageContent::StagedCommitMessage(staged_commit) => {
+                crate::instrumentation::record_proposal_types(staged_commit.queued_proposals())
+            }
+            ProcessedMessageContent::ApplicationMessage(_)
+            | ProcessedMessageContent::OwnPendingCommit => (),
+        }
+        Ok(processed_message)
+    }
+
+    /// Processes an unprotected message and validates the credentials it
+    /// introduces with the `credential_validator` if there is one. This
+    /// doesn't write to the storage.
+    async fn process_unprotected_message<
+        Provider: AsyncOpenMlsProvider,
+        Validator: CredentialValidator + ?Sized,
+    >(
+        &self,
+        provider: &Provider,
+        unverified_message: UnverifiedMessage,
+        credential_validator: Option<&Validator>,
+    ) -> Result<ProcessedMessage, ProcessMessageError<Provider::StorageError>> {
         // Check if the commit contains AppDataUpdate proposals - if so, the caller
         // must use process_unverified_message_with_app_data_updates instead
         #[cfg(feature = "extensions-draft")]
@@ -138,7 +302,56 @@ impl MlsGroup {
                 }
             }
         }
-        self.process_unverified_message(provider, unverified_message)
+        let mut processed_message = self
+            .process_unverified_message(provider, unverified_message)
+            .await?;
+
+        if let Some(credential_validator) = credential_validator {
+            match processed_message.content_mut() {
+                ProcessedMessageContent::ProposalMessage(queued_proposal)
+                | ProcessedMessageContent::ExternalJoinProposalMessage(queued_proposal) => {
+                    queued_proposal
+                        .proposal()
+                        .validate_credentials(credential_validator)?
+                }
+                ProcessedMessageContent::StagedCommitMessage(staged_commit) => {
+                    staged_commit.validate_credentials(credential_validator)?;
+                    staged_commit.skip_credential_validation = true;
+                }
+                ProcessedMessageContent::ApplicationMessage(_)
+                | ProcessedMessageContent::OwnPendingCommit => (),
+            }
+        }
+
+        Ok(processed_message)
+    }
+
+    /// Like [`MlsGroup::process_message()`], but additionally validates all
+    /// credentials that the message introduces into the group using the given
+    /// [`CredentialValidator`], in addition to the one set in the group
+    /// configuration. These are the credentials of added members, of
+    /// Update proposals, of the leaf node in the update path of a Commit
+    /// (including members joining through an external Commit) and of external
+    /// senders in a Group Context Extensions proposal.
+    ///
+    /// The credentials are validated before the secret tree is persisted, so
+    /// a rejected message doesn't consume key material.
+    ///
+    /// # Errors:
+    /// Returns a [`ProcessMessageError::CredentialValidationError`] if the
+    /// validator rejects one of the credentials, in addition to the errors
+    /// returned by [`MlsGroup::process_message()`].
+    pub fn process_message_with_credential_validator<Provider: OpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+        message: impl Into<ProtocolMessage>,
+        credential_validator: &impl CredentialValidator,
+    ) -> Result<ProcessedMessage, ProcessMessageError<Provider::StorageError>> {
+        crate::utils::resolve_ready(self.buffer_or_process_message(
+            provider,
+            message.into(),
+            Some(credential_validator),
+        ))
     }
 
     #[cfg(feature = "extensions-draft")]
@@ -153,6 +366,15 @@ impl MlsGroup {
         &mut self,
         provider: &Provider,
         message: impl Into<ProtocolMessage>,
+    ) -> Result<UnverifiedMessage, ProcessMessageError<Provider::StorageError>> {
+        crate::utils::resolve_ready(self.unprotect_message_internal(provider, message, true))
+    }
+
+    async fn unprotect_message_internal<Provider: AsyncOpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+        message: impl Into<ProtocolMessage>,
+        persist_secret_tree: bool,
     ) -> Result<UnverifiedMessage, ProcessMessageError<Provider::StorageError>> {
         // Make sure we are still a member of the group
         if !self.is_active() {
@@ -162,6 +384,14 @@ impl MlsGroup {
         }
         let message = message.into();
 
+        // Once a ReInit has been committed, the group can't move on to a new
+        // epoch anymore
+        if message.is_handshake_message() && self.reinit_proposal().is_some() {
+            return Err(ProcessMessageError::GroupStateError(
+                MlsGroupStateError::ReInitPending,
+            ));
+        }
+
         // Check that handshake messages are compatible with the incoming wire format policy
         if !message.is_external()
             && message.is_handshake_message()
@@ -179,7 +409,8 @@ impl MlsGroup {
 
         // Check if this message will modify the secret tree when decrypting a
         // private message
-        let will_modify_secret_tree = matches!(message, ProtocolMessage::PrivateMessage(_));
+        let will_modify_secret_tree =
+            persist_secret_tree && matches!(message, ProtocolMessage::PrivateMessage(_));
 
         // Resolve the emulator reuse-guard context for `PrivateMessage`
         // before calling `decrypt_message` so storage errors surface as
@@ -191,6 +422,7 @@ impl MlsGroup {
         #[cfg(feature = "virtual-clients-draft")]
         let emulation_state = if let ProtocolMessage::PrivateMessage(private_message) = &message {
             self.vc_emulation_state_at_epoch(provider.storage(), private_message.epoch())
+                .await
                 .map_err(|e| match e {
                     super::VcEmulationStateError::Storage(e) => {
                         ProcessMessageError::StorageError(e)
@@ -231,6 +463,7 @@ impl MlsGroup {
             provider
                 .storage()
                 .write_message_secrets(self.group_id(), &self.message_secrets_store)
+                .await
                 .map_err(ProcessMessageError::StorageError)?;
         }
 
@@ -302,18 +535,78 @@ impl MlsGroup {
 
     /// Merge a [StagedCommit] into the group after inspection. As this advances
     /// the epoch of the group, it also clears any pending commits.
+    ///
+    /// If the group configuration has a [`CredentialValidator`] and the
+    /// commit wasn't processed with it, the credentials that the commit
+    /// introduces are validated before merging.
+    ///
+    /// Buffered messages from the new epoch are not processed, see
+    /// [`MlsGroup::merge_staged_commit_and_process_buffered_messages()`].
     pub fn merge_staged_commit<Provider: OpenMlsProvider>(
         &mut self,
         provider: &Provider,
         staged_commit: StagedCommit,
     ) -> Result<(), MergeCommitError<Provider::StorageError>> {
-        // Check if we were removed from the group
+        crate::utils::resolve_ready(self.merge_staged_commit_async(provider, staged_commit))
+    }
+
+    /// Merge a [StagedCommit] into the group like
+    /// [`MlsGroup::merge_staged_commit()`], but persists the new group state
+    /// through an asynchronous storage.
+    ///
+    /// All writes of the merge happen in one storage transaction. If merging
+    /// fails, the transaction is rolled back and the group should be reloaded
+    /// from storage, where it is still in the old epoch.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "merge_staged_commit",
+            level = "debug",
+            skip_all,
+            fields(
+                group_id = %crate::instrumentation::HexGroupId(self.group_id()),
+                epoch = self.epoch().as_u64(),
+                new_epoch = staged_commit.epoch().as_u64(),
+                self_removed = staged_commit.self_removed(),
+            ),
+            err
+        )
+    )]
+    pub async fn merge_staged_commit_async<Provider: AsyncOpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+        staged_commit: StagedCommit,
+    ) -> Result<(), MergeCommitError<Provider::StorageError>> {
+        time_operation!("merge_staged_commit");
+        if !staged_commit.skip_credential_validation {
+            if let Some(credential_validator) = &self.mls_group_config.credential_validator {
+                staged_commit.validate_credentials(credential_validator)?;
+            }
+        }
+        crate::storage::transaction_async(
+            provider.storage(),
+            self.merge_staged_commit_internal(provider, staged_commit),
+            MergeCommitError::StorageError,
+        )
+        .await
+    }
+
+    async fn merge_staged_commit_internal<Provider: AsyncOpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+        staged_commit: StagedCommit,
+    ) -> Result<(), MergeCommitError<Provider::StorageError>> {
+        // Check if we were removed from the group or if the group is being
+        // reinitialized
         if staged_commit.self_removed() {
             self.group_state = MlsGroupState::Inactive;
+        } else if let Some(reinit_proposal) = staged_commit.reinit_proposal().cloned() {
+            self.group_state = MlsGroupState::ReInitPending(Box::new(reinit_proposal));
         }
         provider
             .storage()
             .write_group_state(self.group_id(), &self.group_state)
+            .await
             .map_err(MergeCommitError::StorageError)?;
 
         // Update the per-epoch emulation bindings. Self-removal drops them.
@@ -327,6 +620,7 @@ impl MlsGroup {
             provider
                 .storage()
                 .delete_vc_emulation_bindings(self.group_id())
+                .await
                 .map_err(|e| {
                     log::error!("vc: drop emulation bindings on self-removal failed: {e:?}");
                     MergeCommitError::StorageError(e)
@@ -335,6 +629,7 @@ impl MlsGroup {
             let mut bindings: crate::components::vc_derivation_info::VcEmulationBindings = provider
                 .storage()
                 .vc_emulation_bindings(self.group_id())
+                .await
                 .map_err(MergeCommitError::StorageError)?
                 .unwrap_or_default();
             let epoch_id = staged_commit
@@ -350,6 +645,7 @@ impl MlsGroup {
                 provider
                     .storage()
                     .write_vc_emulation_bindings(self.group_id(), &bindings)
+                    .await
                     .map_err(|e| {
                         log::error!("vc: persist emulation bindings at merge failed: {e:?}");
                         MergeCommitError::StorageError(e)
@@ -358,7 +654,7 @@ impl MlsGroup {
         }
 
         // Merge staged commit
-        self.merge_commit(provider, staged_commit)?;
+        self.merge_commit(provider, staged_commit).await?;
 
         // Extract and store the resumption psk for the current epoch
         let resumption_psk = self.group_epoch_secrets().resumption_psk();
@@ -367,6 +663,7 @@ impl MlsGroup {
         provider
             .storage()
             .write_resumption_psk_store(self.group_id(), &self.resumption_psk_store)
+            .await
             .map_err(MergeCommitError::StorageError)?;
 
         // Delete own KeyPackageBundles
@@ -374,11 +671,17 @@ impl MlsGroup {
         provider
             .storage()
             .delete_own_leaf_nodes(self.group_id())
+            .await
             .map_err(MergeCommitError::StorageError)?;
 
         // Delete a potential pending commit
-        self.clear_pending_commit(provider.storage())
-            .map_err(MergeCommitError::StorageError)?;
+        if self.discard_pending_member_commit() {
+            provider
+                .storage()
+                .write_group_state(self.group_id(), &self.group_state)
+                .await
+                .map_err(MergeCommitError::StorageError)?;
+        }
 
         Ok(())
     }
@@ -393,11 +696,16 @@ impl MlsGroup {
             MlsGroupState::PendingCommit(_) => {
                 let old_state = mem::replace(&mut self.group_state, MlsGroupState::Operational);
                 if let MlsGroupState::PendingCommit(pending_commit_state) = old_state {
-                    self.merge_staged_commit(provider, (*pending_commit_state).into())?;
+                    let mut staged_commit: StagedCommit = (*pending_commit_state).into();
+                    // Own commits only introduce the credentials that were
+                    // chosen when creating the commit.
+                    staged_commit.skip_credential_validation = true;
+                    self.merge_staged_commit(provider, staged_commit)?;
                 }
                 Ok(())
             }
             MlsGroupState::Inactive => Err(MlsGroupStateError::UseAfterEviction)?,
+            MlsGroupState::ReInitPending(_) => Err(MlsGroupStateError::ReInitPending)?,
             MlsGroupState::Operational => Ok(()),
         }
     }
@@ -434,7 +742,7 @@ impl MlsGroup {
     /// `OperationGenerationConsumed`. Operation secrets are consume-once,
     /// matching the semantics of regular PrivateMessage decryption.
     #[cfg(feature = "virtual-clients-draft")]
-    fn load_vc_commit_material<Provider: OpenMlsProvider>(
+    async fn load_vc_commit_material<Provider: AsyncOpenMlsProvider>(
         &self,
         provider: &Provider,
         commit: &Commit,
@@ -475,6 +783,7 @@ impl MlsGroup {
         let storage = provider.storage();
         let state: EmulationEpochState = storage
             .vc_emulation_epoch_state(epoch_id)
+            .await
             .map_err(|e| {
                 log::error!("vc: load emulation epoch state failed: {e:?}");
                 VirtualClientsError::StorageError
@@ -482,6 +791,7 @@ impl MlsGroup {
             .ok_or(VirtualClientsError::MissingEmulationEpochState)?;
         let mut operation_tree: OperationSecretTree = storage
             .vc_operation_tree(epoch_id)
+            .await
             .map_err(|e| {
                 log::error!("vc: load operation tree failed: {e:?}");
                 VirtualClientsError::StorageError
@@ -540,6 +850,7 @@ impl MlsGroup {
         // derived from the secret.
         storage
             .write_vc_operation_tree(epoch_id, &operation_tree)
+            .await
             .map_err(|e| {
                 log::error!("vc: persist advanced operation tree failed: {e:?}");
                 VirtualClientsError::StorageError
@@ -549,27 +860,30 @@ impl MlsGroup {
     }
 
     /// Helper function to read decryption keypairs.
-    pub(super) fn read_decryption_keypairs(
+    pub(super) async fn read_decryption_keypairs(
         &self,
-        provider: &impl OpenMlsProvider,
+        provider: &impl AsyncOpenMlsProvider,
         own_leaf_nodes: &[LeafNode],
     ) -> Result<(Vec<EncryptionKeyPair>, Vec<EncryptionKeyPair>), StageCommitError> {
         // All keys from the previous epoch are potential decryption keypairs.
-        let old_epoch_keypairs = self.read_epoch_keypairs(provider.storage()).map_err(|e| {
-            log::error!("Error reading epoch keypairs: {e:?}");
-            StageCommitError::MissingDecryptionKey
-        })?;
+        let old_epoch_keypairs =
+            self.read_epoch_keypairs(provider.storage())
+                .await
+                .map_err(|e| {
+                    log::error!("Error reading epoch keypairs: {e:?}");
+                    StageCommitError::MissingDecryptionKey
+                })?;
 
         // If we are processing an update proposal that originally came from
         // us, the keypair corresponding to the leaf in the update is also a
         // potential decryption keypair.
-        let leaf_node_keypairs = own_leaf_nodes
-            .iter()
-            .map(|leaf_node| {
-                EncryptionKeyPair::read(provider, leaf_node.encryption_key())
-                    .ok_or(StageCommitError::MissingDecryptionKey)
-            })
-            .collect::<Result<Vec<EncryptionKeyPair>, StageCommitError>>()?;
+        let mut leaf_node_keypairs = Vec::with_capacity(own_leaf_nodes.len());
+        for leaf_node in own_leaf_nodes {
+            let keypair = EncryptionKeyPair::read(provider, leaf_node.encryption_key())
+                .await
+                .ok_or(StageCommitError::MissingDecryptionKey)?;
+            leaf_node_keypairs.push(keypair);
+        }
 
         Ok((old_epoch_keypairs, leaf_node_keypairs))
     }
@@ -593,15 +907,18 @@ impl MlsGroup {
 
         #[cfg_attr(not(feature = "extensions-draft"), allow(unused_mut))]
         let mut processed = match verified.content.sender() {
-            Sender::Member(_) | Sender::NewMemberProposal | Sender::NewMemberCommit => self
-                .process_internal_authenticated_content_with_app_data_updates(
-                    provider,
-                    verified.content,
-                    verified.credential,
-                    app_data_dict_updates,
-                    #[cfg(feature = "virtual-clients-draft")]
-                    verified.emulator_sender_leaf_index,
-                )?,
+            Sender::Member(_) | Sender::NewMemberProposal | Sender::NewMemberCommit => {
+                crate::utils::resolve_ready(
+                    self.process_internal_authenticated_content_with_app_data_updates(
+                        provider,
+                        verified.content,
+                        verified.credential,
+                        app_data_dict_updates,
+                        #[cfg(feature = "virtual-clients-draft")]
+                        verified.emulator_sender_leaf_index,
+                    ),
+                )?
+            }
             Sender::External(_) => self.process_external_authenticated_content(
                 provider,
                 verified.content,
@@ -644,7 +961,7 @@ impl MlsGroup {
     ///  - ValSem204: Public keys from Path must be verified and match the
     ///    private keys from the direct path
     ///  - ValSem205
-    pub(crate) fn process_unverified_message<Provider: OpenMlsProvider>(
+    pub(crate) async fn process_unverified_message<Provider: AsyncOpenMlsProvider>(
         &self,
         provider: &Provider,
         unverified_message: UnverifiedMessage,
@@ -659,14 +976,16 @@ impl MlsGroup {
 
         #[cfg_attr(not(feature = "extensions-draft"), allow(unused_mut))]
         let mut processed = match verified.content.sender() {
-            Sender::Member(_) | Sender::NewMemberProposal | Sender::NewMemberCommit => self
-                .process_internal_authenticated_content(
+            Sender::Member(_) | Sender::NewMemberProposal | Sender::NewMemberCommit => {
+                self.process_internal_authenticated_content(
                     provider,
                     verified.content,
                     verified.credential,
                     #[cfg(feature = "virtual-clients-draft")]
                     verified.emulator_sender_leaf_index,
-                )?,
+                )
+                .await?
+            }
             Sender::External(_) => self.process_external_authenticated_content(
                 provider,
                 verified.content,
@@ -706,7 +1025,9 @@ impl MlsGroup {
     ///    private keys from the direct path
     ///  - ValSem205
     #[cfg(feature = "extensions-draft")]
-    fn process_internal_authenticated_content_with_app_data_updates<Provider: OpenMlsProvider>(
+    async fn process_internal_authenticated_content_with_app_data_updates<
+        Provider: AsyncOpenMlsProvider,
+    >(
         &self,
         provider: &Provider,
         content: AuthenticatedContent,
@@ -760,7 +1081,7 @@ impl MlsGroup {
                 #[cfg(feature = "virtual-clients-draft")]
                 let (vc_material, vc_emulation_epoch_id) =
                     if is_sibling_vc_commit(commit, &sender, self.own_leaf_index()) {
-                        match self.load_vc_commit_material(provider, commit)? {
+                        match self.load_vc_commit_material(provider, commit).await? {
                             Some((epoch_id, op)) => (Some(op), Some(epoch_id)),
                             None => (None, None),
                         }
@@ -790,20 +1111,23 @@ impl MlsGroup {
                 }
 
                 // Since this is a commit, we need to load the private key material we need for decryption.
-                let (old_epoch_keypairs, leaf_node_keypairs) =
-                    self.read_decryption_keypairs(provider, &self.own_leaf_nodes)?;
-
-                let staged_commit = self.stage_commit_with_app_data_updates(
-                    &content,
-                    old_epoch_keypairs,
-                    leaf_node_keypairs,
-                    app_data_dict_updates,
-                    provider,
-                    #[cfg(feature = "virtual-clients-draft")]
-                    vc_material,
-                    #[cfg(feature = "virtual-clients-draft")]
-                    vc_emulation_epoch_id,
-                )?;
+                let (old_epoch_keypairs, leaf_node_keypairs) = self
+                    .read_decryption_keypairs(provider, &self.own_leaf_nodes)
+                    .await?;
+
+                let staged_commit = self
+                    .stage_commit_with_app_data_updates(
+                        &content,
+                        old_epoch_keypairs,
+                        leaf_node_keypairs,
+                        app_data_dict_updates,
+                        provider,
+                        #[cfg(feature = "virtual-clients-draft")]
+                        vc_material,
+                        #[cfg(feature = "virtual-clients-draft")]
+                        vc_emulation_epoch_id,
+                    )
+                    .await?;
 
                 ProcessedMessageContent::StagedCommitMessage(Box::new(staged_commit))
             }
@@ -821,7 +1145,7 @@ impl MlsGroup {
         ))
     }
 
-    fn process_internal_authenticated_content<Provider: OpenMlsProvider>(
+    async fn process_internal_authenticated_content<Provider: AsyncOpenMlsProvider>(
         &self,
         provider: &Provider,
         content: AuthenticatedContent,
@@ -863,7 +1187,7 @@ impl MlsGroup {
                 #[cfg(feature = "virtual-clients-draft")]
                 let (vc_material, vc_emulation_epoch_id) =
                     if is_sibling_vc_commit(commit, &sender, self.own_leaf_index()) {
-                        match self.load_vc_commit_material(provider, commit)? {
+                        match self.load_vc_commit_material(provider, commit).await? {
                             Some((epoch_id, op)) => (Some(op), Some(epoch_id)),
                             None => (None, None),
                         }
@@ -893,19 +1217,22 @@ impl MlsGroup {
                 }
 
                 // Since this is a commit, we need to load the private key material we need for decryption.
-                let (old_epoch_keypairs, leaf_node_keypairs) =
-                    self.read_decryption_keypairs(provider, &self.own_leaf_nodes)?;
-
-                let staged_commit = self.stage_commit(
-                    &content,
-                    old_epoch_keypairs,
-                    leaf_node_keypairs,
-                    provider,
-                    #[cfg(feature = "virtual-clients-draft")]
-                    vc_material,
-                    #[cfg(feature = "virtual-clients-draft")]
-                    vc_emulation_epoch_id,
-                )?;
+                let (old_epoch_keypairs, leaf_node_keypairs) = self
+                    .read_decryption_keypairs(provider, &self.own_leaf_nodes)
+                    .await?;
+
+                let staged_commit = self
+                    .stage_commit(
+                        &content,
+                        old_epoch_keypairs,
+                        leaf_node_keypairs,
+                        provider,
+                        #[cfg(feature = "virtual-clients-draft")]
+                        vc_material,
+                        #[cfg(feature = "virtual-clients-draft")]
+                        vc_emulation_epoch_id,
+                    )
+                    .await?;
 
                 ProcessedMessageContent::StagedCommitMessage(Box::new(staged_commit))
             }
@@ -928,7 +1255,7 @@ impl MlsGroup {
     ///  - ValSem242
     ///  - ValSem244
     ///  - ValSem246 (as part of ValSem010)
-    fn process_external_authenticated_content<Provider: OpenMlsProvider>(
+    fn process_external_authenticated_content<Provider: AsyncOpenMlsProvider>(
         &self,
         provider: &Provider,
         content: AuthenticatedContent,
@@ -3,7 +3,8 @@ use openmls_traits::{signatures::Signer, storage::StorageProvider as _, types::C
 use super::{
     errors::{ProposalError, ProposeAddMemberError, ProposeRemoveMemberError, RemoveProposalError},
     AddProposal, CreateGroupContextExtProposalError, CustomProposal, FramingParameters, MlsGroup,
-    PreSharedKeyProposal, Proposal, QueuedProposal, RemoveProposal, UpdateProposal, WireFormat,
+    PreSharedKeyProposal, Proposal, QueuedProposal, ReInitProposal, RemoveProposal, UpdateProposal,
+    WireFormat,
 };
 use crate::{
     binary_tree::LeafNodeIndex,
@@ -174,6 +175,20 @@ impl MlsGroup {
         self.propose_pre_shared_key_by_value(provider, signer, value)
     }
 
+    impl_propose_fun!(
+        propose_reinit,
+        ReInitProposal,
+        create_reinit_proposal,
+        ProposalOrRefType::Reference
+    );
+
+    impl_propose_fun!(
+        propose_reinit_by_value,
+        ReInitProposal,
+        create_reinit_proposal,
+        ProposalOrRefType::Proposal
+    );
+
     impl_propose_fun!(
         propose_custom_proposal_by_value,
         CustomProposal,
@@ -255,13 +270,22 @@ impl MlsGroup {
                 }
             }
             Propose::ReInit {
-                group_id: _,
-                version: _,
-                ciphersuite: _,
-                extensions: _,
-            } => Err(ProposalError::LibraryError(LibraryError::custom(
-                "Unsupported proposal type ReInit",
-            ))),
+                group_id,
+                version,
+                ciphersuite,
+                extensions,
+            } => {
+                let reinit_proposal =
+                    ReInitProposal::new(group_id, version, ciphersuite, extensions);
+                match ref_or_value {
+                    ProposalOrRefType::Proposal => {
+                        self.propose_reinit_by_value(provider, signer, reinit_proposal)
+                    }
+                    ProposalOrRefType::Reference => {
+                        self.propose_reinit(provider, signer, reinit_proposal)
+                    }
+                }
+            }
             Propose::ExternalInit(_) => Err(ProposalError::LibraryError(LibraryError::custom(
                 "Unsupported proposal type ExternalInit",
             ))),
@@ -675,6 +699,22 @@ impl MlsGroup {
         )
     }
 
+    pub(crate) fn create_reinit_proposal(
+        &self,
+        framing_parameters: FramingParameters,
+        reinit_proposal: ReInitProposal,
+        signer: &impl Signer,
+    ) -> Result<AuthenticatedContent, LibraryError> {
+        let proposal = Proposal::re_init(reinit_proposal);
+        AuthenticatedContent::member_proposal(
+            framing_parameters,
+            self.own_leaf_index(),
+            proposal,
+            self.context(),
+            signer,
+        )
+    }
+
     #[cfg(feature = "extensions-draft")]
     pub(crate) fn create_app_data_update_proposal(
         &self,
@@ -12,7 +12,7 @@ use crate::{
     group::errors::*,
     messages::proposals::{
         AddProposal, PreSharedKeyProposal, Proposal, ProposalOrRef, ProposalOrRefType,
-        ProposalType, RemoveProposal, UpdateProposal,
+        ProposalType, ReInitProposal, RemoveProposal, UpdateProposal,
     },
     schedule::PreSharedKeyId,
     utils::vector_converter,
@@ -461,6 +461,15 @@ impl ProposalQueue {
         })
     }
 
+    /// Returns the ReInit proposal in the queue, if there is one
+    pub(crate) fn reinit_proposal(&self) -> Option<&ReInitProposal> {
+        self.queued_proposals()
+            .find_map(|queued_proposal| match queued_proposal.proposal() {
+                Proposal::ReInit(reinit_proposal) => Some(reinit_proposal.as_ref()),
+                _ => None,
+            })
+    }
+
     #[cfg(feature = "extensions-draft")]
     /// Returns an iterator over all AppEphemeral proposals in the queue
     /// in the order of the Commit message
@@ -549,6 +558,7 @@ impl ProposalQueue {
         let mut proposal_pool: HashMap<ProposalRef, QueuedProposal> = HashMap::new();
         let mut contains_own_updates = false;
         let mut contains_external_init = false;
+        let mut contains_reinit = false;
 
         let mut member_specific_proposals: HashMap<LeafNodeIndex, QueuedProposal> = HashMap::new();
         let mut register_member_specific_proposal =
@@ -606,7 +616,11 @@ impl ProposalQueue {
                     valid_proposals.add(queued_proposal.proposal_reference());
                 }
                 Proposal::ReInit(_) => {
-                    // TODO #751: Only keep one ReInit
+                    // Only use the first ReInit proposal we find.
+                    if !contains_reinit {
+                        valid_proposals.add(queued_proposal.proposal_reference());
+                        contains_reinit = true;
+                    }
                 }
                 Proposal::ExternalInit(_) => {
                     // Only use the first external init proposal we find.
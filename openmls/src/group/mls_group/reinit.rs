@@ -0,0 +1,158 @@
+//! This module contains the functionality to reinitialize a group as described
+//! in Section 11.2 of the MLS specification.
+//!
+//! A group is reinitialized by committing a [`ReInitProposal`]. Merging such a
+//! Commit puts the group into the terminal [`MlsGroupState::ReInitPending`]
+//! state. One of the members then sets up the successor group with the
+//! parameters of the proposal using [`MlsGroup::reinit()`] and invites the
+//! other members. The first Commit of the successor group injects the
+//! resumption PSK of the old group with usage
+//! [`ResumptionPskUsage::Reinit`], which the other members verify when they
+//! join the successor group through [`MlsGroup::join_reinit()`].
+
+use openmls_traits::signatures::Signer;
+
+use super::{
+    builder::MlsGroupBuilder,
+    commit_builder::{CommitBuilder, CommitMessageBundle, Initial},
+    errors::{MlsGroupStateError, ReInitError},
+    Member, MlsGroup, MlsGroupState, ReInitProposal, StagedWelcome,
+};
+use crate::{
+    credentials::CredentialWithKey, group::MlsGroupJoinConfig, key_packages::KeyPackage,
+    messages::Welcome, schedule::psk::ResumptionPskUsage, storage::OpenMlsProvider,
+    treesync::RatchetTreeIn, versions::ProtocolVersion,
+};
+
+impl MlsGroup {
+    /// Returns the [`ReInitProposal`] that was committed in this group, if the
+    /// group waits to be reinitialized.
+    pub fn reinit_proposal(&self) -> Option<&ReInitProposal> {
+        match self.group_state {
+            MlsGroupState::ReInitPending(ref reinit_proposal) => Some(reinit_proposal),
+            _ => None,
+        }
+    }
+
+    /// The first step towards creating the successor group of a group that
+    /// waits to be reinitialized. The successor group uses the group ID,
+    /// ciphersuite and group context extensions of the committed
+    /// [`ReInitProposal`] and the configuration of the current group.
+    ///
+    /// Returns an error if no ReInit proposal has been committed in this group.
+    pub fn reinit(&'_ self) -> Result<ReInitBuilder<'_>, MlsGroupStateError> {
+        let reinit_proposal = self
+            .reinit_proposal()
+            .ok_or(MlsGroupStateError::NoReInitPending)?;
+
+        let group_builder = self
+            .resuming_group_builder(reinit_proposal.ciphersuite())
+            .with_group_id(reinit_proposal.group_id().clone())
+            .with_group_context_extensions(reinit_proposal.extensions().clone());
+
+        Ok(ReInitBuilder {
+            group: self,
+            reinit_proposal,
+            group_builder,
+        })
+    }
+
+    /// Joins the successor group of a group that waits to be reinitialized
+    /// using a [`Welcome`] message.
+    ///
+    /// In addition to the regular checks when processing a [`Welcome`], this
+    /// verifies that the [`Welcome`] injects the resumption PSK of this group
+    /// with usage [`ResumptionPskUsage::Reinit`] and that the successor group
+    /// uses the parameters of the committed [`ReInitProposal`].
+    pub fn join_reinit<Provider: OpenMlsProvider>(
+        &self,
+        provider: &Provider,
+        mls_group_config: &MlsGroupJoinConfig,
+        welcome: Welcome,
+        ratchet_tree: Option<RatchetTreeIn>,
+    ) -> Result<StagedWelcome, ReInitError<Provider::StorageError>> {
+        let reinit_proposal = self
+            .reinit_proposal()
+            .ok_or(MlsGroupStateError::NoReInitPending)?;
+
+        self.join_resuming_group(
+            provider,
+            mls_group_config,
+            welcome,
+            ratchet_tree,
+            ResumptionPskUsage::Reinit,
+            |epoch| epoch == self.epoch(),
+            |group_context| {
+                group_context.group_id() == reinit_proposal.group_id()
+                    && group_context.protocol_version() == reinit_proposal.version()
+                    && group_context.ciphersuite() == reinit_proposal.ciphersuite()
+                    && group_context.extensions() == reinit_proposal.extensions()
+            },
+        )
+    }
+}
+
+/// The builder type for the successor group of a reinitialized group.
+pub struct ReInitBuilder<'a> {
+    group: &'a MlsGroup,
+    reinit_proposal: &'a ReInitProposal,
+    group_builder: MlsGroupBuilder,
+}
+
+impl<'a> ReInitBuilder<'a> {
+    /// Returns the [`ReInitProposal`] that describes the successor group.
+    pub fn reinit_proposal(&self) -> &ReInitProposal {
+        self.reinit_proposal
+    }
+
+    /// The members of the old group, so new key packages for other members can be retrieved.
+    pub fn old_members(&self) -> impl Iterator<Item = Member> + 'a {
+        self.group
+            .members()
+            .filter(|member| member.index != self.group.own_leaf_index())
+    }
+
+    /// Lets the caller make changes to the [`MlsGroupBuilder`] before the group is created.
+    ///
+    /// Note that the group ID, ciphersuite and group context extensions must
+    /// not be changed, since the other members would reject the successor
+    /// group otherwise.
+    pub fn refine_group_builder(
+        self,
+        mut f: impl FnMut(MlsGroupBuilder) -> MlsGroupBuilder,
+    ) -> Self {
+        Self {
+            group_builder: f(self.group_builder),
+            ..self
+        }
+    }
+
+    /// Creates the successor group and its first commit, which adds the
+    /// `new_members` and injects the resumption PSK of the old group. The
+    /// caller can also make further changes to the [`CommitBuilder`] using the
+    /// `refine_commit_builder` argument. If that is not desired, provide the
+    /// identity function (`|b| b`).
+    pub fn finish<Provider: OpenMlsProvider>(
+        self,
+        new_members: Vec<KeyPackage>,
+        refine_commit_builder: impl FnMut(CommitBuilder<Initial>) -> CommitBuilder<Initial>,
+        provider: &Provider,
+        signer: &impl Signer,
+        credential_with_key: CredentialWithKey,
+    ) -> Result<(MlsGroup, CommitMessageBundle), ReInitError<Provider::StorageError>> {
+        if self.reinit_proposal.version() != ProtocolVersion::default() {
+            return Err(ReInitError::UnsupportedVersion);
+        }
+
+        self.group.create_resuming_group(
+            ResumptionPskUsage::Reinit,
+            self.reinit_proposal.ciphersuite(),
+            self.group_builder,
+            new_members,
+            refine_commit_builder,
+            provider,
+            signer,
+            credential_with_key,
+        )
+    }
+}
@@ -1,7 +1,7 @@
 use core::fmt::Debug;
 
+use openmls_traits::async_storage::AsyncStorageProvider as _;
 use openmls_traits::crypto::OpenMlsCrypto;
-use openmls_traits::storage::StorageProvider as _;
 use serde::{Deserialize, Serialize};
 use tls_codec::Serialize as _;
 
@@ -12,10 +12,10 @@ use super::proposal_store::{
 #[cfg(feature = "virtual-clients-draft")]
 use super::Sender;
 use super::{
-    super::errors::*, errors::ProcessMessageError, load_psks, Credential, Extension, GroupContext,
-    GroupEpochSecrets, GroupId, JoinerSecret, KeySchedule, LeafNode, LibraryError, MessageSecrets,
-    MlsGroup, MlsGroupState, OpenMlsProvider, PendingCommitState, Proposal, ProposalQueue,
-    PskSecret, QueuedProposal,
+    super::errors::*, errors::ProcessMessageError, AsyncOpenMlsProvider, Credential, Extension,
+    GroupContext, GroupEpochSecrets, GroupId, JoinerSecret, KeySchedule, LeafNode, LibraryError,
+    MessageSecrets, MlsGroup, MlsGroupState, PendingCommitState, Proposal, ProposalQueue,
+    PskSecret, QueuedProposal, ReInitProposal,
 };
 use crate::group::diff::PublicGroupDiff;
 use crate::group::GroupEpoch;
@@ -27,14 +27,15 @@ use crate::treesync::errors::TreeSyncFromNodesError;
 use crate::treesync::RatchetTree;
 use crate::{
     ciphersuite::{hash_ref::ProposalRef, Secret},
+    credentials::{errors::CredentialValidationError, CredentialValidator},
     framing::mls_auth_content::AuthenticatedContent,
     group::public_group::{
         diff::{apply_proposals::ApplyProposalsValues, StagedPublicGroupDiff},
         staged_commit::PublicStagedCommitState,
     },
     schedule::{
-        CommitSecret, EpochAuthenticator, EpochSecretsResult, InitSecret, PreSharedKeyId,
-        ResumptionPskSecret,
+        psk::load_psks_async, CommitSecret, EpochAuthenticator, EpochSecretsResult, InitSecret,
+        PreSharedKeyId, ResumptionPskSecret,
     },
     treesync::node::encryption_keys::EncryptionKeyPair,
 };
@@ -87,9 +88,9 @@ impl MlsGroup {
         Ok(())
     }
 
-    fn derive_epoch_secrets(
+    async fn derive_epoch_secrets(
         &self,
-        provider: &impl OpenMlsProvider,
+        provider: &impl AsyncOpenMlsProvider,
         apply_proposals_values: ApplyProposalsValues,
         epoch_secrets: &GroupEpochSecrets,
         commit_secret: CommitSecret,
@@ -135,11 +136,12 @@ impl MlsGroup {
         // Prepare the PskSecret
         // Fails if PSKs are missing ([valn1205](https://validation.openmls.tech/#valn1205))
         let psk_secret = {
-            let psks: Vec<(&PreSharedKeyId, Secret)> = load_psks(
+            let psks: Vec<(&PreSharedKeyId, Secret)> = load_psks_async(
                 provider.storage(),
                 &self.resumption_psk_store,
                 &apply_proposals_values.presharedkeys,
-            )?;
+            )
+            .await?;
 
             PskSecret::new(provider.crypto(), self.ciphersuite(), psks)?
         };
@@ -197,12 +199,29 @@ impl MlsGroup {
     ///  - ValSem241
     ///  - ValSem242
     ///  - ValSem244
-    pub(crate) fn stage_commit(
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "stage_commit",
+            level = "debug",
+            skip_all,
+            fields(
+                group_id = %crate::instrumentation::HexGroupId(self.group_id()),
+                epoch = self.epoch().as_u64(),
+                sender_leaf_index = tracing::field::Empty,
+                sender_external_index = tracing::field::Empty,
+                sender_type = tracing::field::Empty,
+                proposal_types = tracing::field::Empty,
+            ),
+            err
+        )
+    )]
+    pub(crate) async fn stage_commit(
         &self,
         mls_content: &AuthenticatedContent,
         old_epoch_keypairs: Vec<EncryptionKeyPair>,
         leaf_node_keypairs: Vec<EncryptionKeyPair>,
-        provider: &impl OpenMlsProvider,
+        provider: &impl AsyncOpenMlsProvider,
         #[cfg(feature = "virtual-clients-draft")] vc_material: Option<
             crate::components::vc_derivation_info::OperationSecret,
         >,
@@ -210,9 +229,15 @@ impl MlsGroup {
             crate::components::vc_derivation_info::EpochId,
         >,
     ) -> Result<StagedCommit, StageCommitError> {
+        time_operation!("stage_commit");
         let (commit, proposal_queue, sender_index) = self
             .public_group
             .validate_commit(mls_content, provider.crypto())?;
+        #[cfg(feature = "tracing")]
+        {
+            crate::instrumentation::record_sender(mls_content.sender());
+            crate::instrumentation::record_proposal_types(proposal_queue.queued_proposals());
+        }
 
         // Create the provisional public group state (including the tree and
         // group context) and apply proposals.
@@ -243,17 +268,35 @@ impl MlsGroup {
             #[cfg(feature = "virtual-clients-draft")]
             vc_emulation_epoch_id,
         )
+        .await
     }
 
     #[cfg(feature = "extensions-draft")]
     #[allow(clippy::too_many_arguments)]
-    pub(crate) fn stage_commit_with_app_data_updates(
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "stage_commit",
+            level = "debug",
+            skip_all,
+            fields(
+                group_id = %crate::instrumentation::HexGroupId(self.group_id()),
+                epoch = self.epoch().as_u64(),
+                sender_leaf_index = tracing::field::Empty,
+                sender_external_index = tracing::field::Empty,
+                sender_type = tracing::field::Empty,
+                proposal_types = tracing::field::Empty,
+            ),
+            err
+        )
+    )]
+    pub(crate) async fn stage_commit_with_app_data_updates(
         &self,
         mls_content: &AuthenticatedContent,
         old_epoch_keypairs: Vec<EncryptionKeyPair>,
         leaf_node_keypairs: Vec<EncryptionKeyPair>,
         app_data_dict_updates: Option<AppDataUpdates>,
-        provider: &impl OpenMlsProvider,
+        provider: &impl AsyncOpenMlsProvider,
         #[cfg(feature = "virtual-clients-draft")] vc_material: Option<
             crate::components::vc_derivation_info::OperationSecret,
         >,
@@ -261,9 +304,15 @@ impl MlsGroup {
             crate::components::vc_derivation_info::EpochId,
         >,
     ) -> Result<StagedCommit, StageCommitError> {
+        time_operation!("stage_commit");
         let (commit, proposal_queue, sender_index) = self
             .public_group
             .validate_commit(mls_content, provider.crypto())?;
+        #[cfg(feature = "tracing")]
+        {
+            crate::instrumentation::record_sender(mls_content.sender());
+            crate::instrumentation::record_proposal_types(proposal_queue.queued_proposals());
+        }
 
         // Create the provisional public group state (including the tree and
         // group context) and apply proposals.
@@ -290,20 +339,21 @@ impl MlsGroup {
             #[cfg(feature = "virtual-clients-draft")]
             vc_emulation_epoch_id,
         )
+        .await
     }
 
     #[allow(clippy::too_many_arguments)]
-    fn stage_applied_proposal_values(
+    async fn stage_applied_proposal_values(
         &self,
         apply_proposals_values: ApplyProposalsValues,
-        mut diff: PublicGroupDiff,
+        mut diff: PublicGroupDiff<'_>,
         commit: &Commit,
         proposal_queue: ProposalQueue,
         sender_index: LeafNodeIndex,
         mls_content: &AuthenticatedContent,
         old_epoch_keypairs: Vec<EncryptionKeyPair>,
         leaf_node_keypairs: Vec<EncryptionKeyPair>,
-        provider: &impl OpenMlsProvider,
+        provider: &impl AsyncOpenMlsProvider,
         #[cfg(feature = "virtual-clients-draft")] vc_material: Option<
             crate::components::vc_derivation_info::OperationSecret,
         >,
@@ -500,13 +550,15 @@ impl MlsGroup {
             epoch_secrets,
             #[cfg(feature = "extensions-draft")]
             application_exporter,
-        } = self.derive_epoch_secrets(
-            provider,
-            apply_proposals_values,
-            self.group_epoch_secrets(),
-            commit_secret,
-            &serialized_provisional_group_context,
-        )?;
+        } = self
+            .derive_epoch_secrets(
+                provider,
+                apply_proposals_values,
+                self.group_epoch_secrets(),
+                commit_secret,
+                &serialized_provisional_group_context,
+            )
+            .await?;
         let (provisional_group_secrets, provisional_message_secrets) = epoch_secrets.split_secrets(
             serialized_provisional_group_context,
             diff.tree_size(),
@@ -630,7 +682,7 @@ impl MlsGroup {
     ///
     /// This function should not fail and only returns a [`Result`], because it
     /// might throw a `LibraryError`.
-    pub(crate) fn merge_commit<Provider: OpenMlsProvider>(
+    pub(crate) async fn merge_commit<Provider: AsyncOpenMlsProvider>(
         &mut self,
         provider: &Provider,
         staged_commit: StagedCommit,
@@ -639,12 +691,14 @@ impl MlsGroup {
         // that are still relevant in the new epoch.
         let old_epoch_keypairs = self
             .read_epoch_keypairs(provider.storage())
+            .await
             .map_err(MergeCommitError::StorageError)?;
         match staged_commit.state {
             StagedCommitState::PublicState(staged_state) => {
                 self.public_group
                     .merge_diff(staged_state.into_staged_diff());
                 self.store(provider.storage())
+                    .await
                     .map_err(MergeCommitError::StorageError)?;
                 Ok(())
             }
@@ -675,14 +729,13 @@ impl MlsGroup {
                     // support the application exporter.
                     if let Some(application_export_tree) = state.application_export_tree {
                         // Overwrite the existing exporter tree in the storage.
-
-                        use openmls_traits::storage::StorageProvider as _;
                         provider
                             .storage()
                             .write_application_export_tree(
                                 self.group_id(),
                                 &application_export_tree,
                             )
+                            .await
                             .map_err(MergeCommitError::StorageError)?;
 
                         self.application_export_tree = Some(application_export_tree);
@@ -735,20 +788,25 @@ impl MlsGroup {
                 let group_id = self.group_id();
 
                 self.public_group
-                    .store(storage)
+                    .store_async(storage)
+                    .await
                     .map_err(MergeCommitError::StorageError)?;
                 storage
                     .write_own_leaf_index(group_id, &self.own_leaf_index)
+                    .await
                     .map_err(MergeCommitError::StorageError)?;
                 storage
                     .write_group_epoch_secrets(group_id, &self.group_epoch_secrets)
+                    .await
                     .map_err(MergeCommitError::StorageError)?;
                 storage
                     .write_message_secrets(group_id, &self.message_secrets_store)
+                    .await
                     .map_err(MergeCommitError::StorageError)?;
 
                 // Store the relevant keys under the new epoch
                 self.store_epoch_keypairs(storage, epoch_keypairs.as_slice())
+                    .await
                     .map_err(MergeCommitError::StorageError)?;
 
                 // Delete the old keys.
@@ -757,16 +815,19 @@ impl MlsGroup {
                     #[cfg(feature = "virtual-clients-draft")]
                     previous_own_leaf_index,
                 )
+                .await
                 .map_err(MergeCommitError::StorageError)?;
                 if let Some(keypair) = state.new_leaf_keypair_option {
                     keypair
                         .delete(storage)
+                        .await
                         .map_err(MergeCommitError::StorageError)?;
                 }
 
                 // Empty the proposal store
                 storage
                     .clear_proposal_queue::<GroupId, ProposalRef>(group_id)
+                    .await
                     .map_err(MergeCommitError::StorageError)?;
                 self.proposal_store_mut().empty();
 
@@ -797,6 +858,11 @@ pub struct StagedCommit {
     #[cfg(feature = "virtual-clients-draft")]
     #[serde(default)]
     pub(super) vc_emulation_epoch_id: Option<crate::components::vc_derivation_info::EpochId>,
+    /// Whether merging this commit skips the validation of its credentials,
+    /// because they were validated when the commit was processed or because
+    /// it is an own commit.
+    #[serde(skip)]
+    pub(super) skip_credential_validation: bool,
 }
 
 impl StagedCommit {
@@ -814,6 +880,7 @@ impl StagedCommit {
             state,
             #[cfg(feature = "virtual-clients-draft")]
             vc_emulation_epoch_id,
+            skip_credential_validation: false,
         }
     }
 
@@ -860,6 +927,11 @@ impl StagedCommit {
         self.staged_proposal_queue.psk_proposals()
     }
 
+    /// Returns the ReInit proposal that is covered by the Commit message, if there is one.
+    pub fn reinit_proposal(&self) -> Option<&ReInitProposal> {
+        self.staged_proposal_queue.reinit_proposal()
+    }
+
     #[cfg(feature = "extensions-draft")]
     /// Returns the AppEphemeral proposals that are covered by the Commit message as an iterator
     /// over [`QueuedAppEphemeralProposal`].
@@ -942,6 +1014,25 @@ impl StagedCommit {
             )
     }
 
+    /// Validates the credentials returned by
+    /// [`StagedCommit::credentials_to_verify()`] using the given
+    /// [`CredentialValidator`].
+    pub(crate) fn validate_credentials(
+        &self,
+        credential_validator: &(impl CredentialValidator + ?Sized),
+    ) -> Result<(), CredentialValidationError> {
+        if let Some(leaf_node) = self.update_path_leaf_node() {
+            credential_validator
+                .validate_credential(leaf_node.credential(), leaf_node.signature_key())?;
+        }
+
+        self.queued_proposals().try_for_each(|queued_proposal| {
+            queued_proposal
+                .proposal()
+                .validate_credentials(credential_validator)
+        })
+    }
+
     /// Returns `true` if the member was removed through a proposal covered by this Commit message
     /// and `false` otherwise.
     //
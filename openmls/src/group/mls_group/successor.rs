@@ -0,0 +1,194 @@
+//! This module contains the functionality that is shared by the groups that
+//! resume another group using a resumption PSK, as described in Section 11 of
+//! the MLS specification: the successor group of a reinitialized group (see
+//! [`reinit`](super::reinit)) and subgroups branched off a group (see
+//! [`branch`](super::branch)).
+//!
+//! The first Commit of such a group adds the new members and injects the
+//! resumption PSK of the current epoch of the resumed group. The members that
+//! join the group through a [`Welcome`] check that PSK and the group context
+//! of the new group.
+
+use openmls_traits::{signatures::Signer, types::Ciphersuite};
+
+use super::{
+    builder::MlsGroupBuilder,
+    commit_builder::{CommitBuilder, CommitMessageBundle, Initial},
+    MlsGroup, PreSharedKeyProposal, ProcessedWelcome, Proposal, StagedWelcome,
+};
+use crate::{
+    ciphersuite::Secret,
+    credentials::CredentialWithKey,
+    error::LibraryError,
+    group::{
+        BranchError, CommitBuilderStageError, CreateCommitError, GroupContext, GroupEpoch,
+        MlsGroupJoinConfig, NewGroupError, ReInitError, WelcomeError,
+    },
+    key_packages::KeyPackage,
+    messages::Welcome,
+    schedule::{
+        psk::{ResumptionPsk, ResumptionPskUsage},
+        PreSharedKeyId, Psk,
+    },
+    storage::OpenMlsProvider,
+    treesync::RatchetTreeIn,
+};
+
+/// The errors that creating or joining a group that resumes another group can
+/// return, in addition to the ones of the underlying operations.
+pub(crate) trait ResumptionError<StorageError>:
+    From<LibraryError>
+    + From<NewGroupError<StorageError>>
+    + From<CreateCommitError>
+    + From<CommitBuilderStageError<StorageError>>
+    + From<WelcomeError<StorageError>>
+{
+    /// The Welcome message does not inject the expected resumption PSK.
+    fn missing_psk() -> Self;
+
+    /// The group context of the new group doesn't match the resumed group.
+    fn parameter_mismatch() -> Self;
+}
+
+impl<StorageError> ResumptionError<StorageError> for ReInitError<StorageError> {
+    fn missing_psk() -> Self {
+        Self::MissingReInitPsk
+    }
+
+    fn parameter_mismatch() -> Self {
+        Self::ReInitParameterMismatch
+    }
+}
+
+impl<StorageError> ResumptionError<StorageError> for BranchError<StorageError> {
+    fn missing_psk() -> Self {
+        Self::MissingBranchPsk
+    }
+
+    fn parameter_mismatch() -> Self {
+        Self::BranchParameterMismatch
+    }
+}
+
+impl MlsGroup {
+    /// Returns an [`MlsGroupBuilder`] for a group that resumes this group. The
+    /// builder uses the configuration of this group and the given
+    /// `ciphersuite`.
+    pub(super) fn resuming_group_builder(&self, ciphersuite: Ciphersuite) -> MlsGroupBuilder {
+        MlsGroup::builder()
+            .with_wire_format_policy(self.configuration().wire_format_policy)
+            .padding_policy(self.configuration().padding_policy)
+            .set_past_epoch_deletion_policy(self.configuration().past_epoch_deletion_policy.clone())
+            .number_of_resumption_psks(self.configuration().number_of_resumption_psks)
+            .use_ratchet_tree_extension(self.configuration().use_ratchet_tree_extension)
+            .sender_ratchet_configuration(self.configuration().sender_ratchet_configuration)
+            .ciphersuite(ciphersuite)
+    }
+
+    /// Returns the resumption [`Psk`] of the current epoch with the given
+    /// `usage`.
+    pub(super) fn resumption_psk(&self, usage: ResumptionPskUsage) -> Psk {
+        Psk::Resumption(ResumptionPsk::new(
+            usage,
+            self.group_id().clone(),
+            self.epoch(),
+        ))
+    }
+
+    /// Creates a group that resumes this group from the `group_builder`, and
+    /// its first commit, which adds the `new_members` and injects the
+    /// resumption PSK of the current epoch of this group with the given
+    /// `usage`. The PSK ID is created for the `ciphersuite` of the new group.
+    /// The caller can make further changes to the [`CommitBuilder`] using
+    /// `refine_commit_builder`.
+    ///
+    /// The resumption PSK is passed in memory, so that it never ends up in the
+    /// storage.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn create_resuming_group<Provider: OpenMlsProvider, E>(
+        &self,
+        usage: ResumptionPskUsage,
+        ciphersuite: Ciphersuite,
+        group_builder: MlsGroupBuilder,
+        new_members: Vec<KeyPackage>,
+        mut refine_commit_builder: impl FnMut(CommitBuilder<Initial>) -> CommitBuilder<Initial>,
+        provider: &Provider,
+        signer: &impl Signer,
+        credential_with_key: CredentialWithKey,
+    ) -> Result<(MlsGroup, CommitMessageBundle), E>
+    where
+        E: ResumptionError<Provider::StorageError>,
+    {
+        let psk_id = PreSharedKeyId::new(ciphersuite, provider.rand(), self.resumption_psk(usage))
+            .map_err(LibraryError::unexpected_crypto_error)?;
+
+        let mut new_group = group_builder.build(provider, signer, credential_with_key)?;
+
+        let commit_builder = new_group
+            .commit_builder()
+            .propose_adds(new_members)
+            .add_proposal(Proposal::psk(PreSharedKeyProposal::new(psk_id.clone())));
+
+        let psk_secret = Secret::from_slice(self.resumption_psk_secret().as_slice());
+        let message_bundle = refine_commit_builder(commit_builder)
+            .load_psks_with(provider.storage(), Some((&psk_id, &psk_secret)))?
+            .build(provider.rand(), provider.crypto(), signer, |_| true)?
+            .stage_commit(provider)?;
+
+        Ok((new_group, message_bundle))
+    }
+
+    /// Joins a group that resumes this group using a [`Welcome`] message.
+    ///
+    /// In addition to the regular checks when processing a [`Welcome`], this
+    /// verifies that the [`Welcome`] injects a resumption PSK of this group
+    /// with the given `usage`, exported from one of the epochs accepted by
+    /// `accept_epoch`, and that `check_group_context` accepts the group
+    /// context of the new group.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn join_resuming_group<Provider: OpenMlsProvider, E>(
+        &self,
+        provider: &Provider,
+        mls_group_config: &MlsGroupJoinConfig,
+        welcome: Welcome,
+        ratchet_tree: Option<RatchetTreeIn>,
+        usage: ResumptionPskUsage,
+        accept_epoch: impl Fn(GroupEpoch) -> bool,
+        check_group_context: impl FnOnce(&GroupContext) -> bool,
+    ) -> Result<StagedWelcome, E>
+    where
+        E: ResumptionError<Provider::StorageError>,
+    {
+        let processed_welcome = ProcessedWelcome::new_from_welcome_internal(
+            provider,
+            mls_group_config,
+            welcome,
+            Some(self),
+        )?;
+
+        // The PSK secret itself has already been used to decrypt the group
+        // info, so it suffices to check that the expected PSK is injected.
+        if !processed_welcome
+            .psks()
+            .iter()
+            .any(|psk_id| match psk_id.psk() {
+                Psk::Resumption(resumption_psk) => {
+                    resumption_psk.usage() == usage
+                        && resumption_psk.psk_group_id() == self.group_id()
+                        && accept_epoch(resumption_psk.psk_epoch())
+                }
+                _ => false,
+            })
+        {
+            return Err(E::missing_psk());
+        }
+
+        let staged_welcome = processed_welcome.into_staged_welcome(provider, ratchet_tree)?;
+
+        if !check_group_context(staged_welcome.group_context()) {
+            return Err(E::parameter_mismatch());
+        }
+
+        Ok(staged_welcome)
+    }
+}
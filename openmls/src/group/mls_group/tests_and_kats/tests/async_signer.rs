@@ -0,0 +1,205 @@
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
+    thread,
+    time::Duration,
+};
+
+use openmls_basic_credential::SignatureKeyPair;
+use openmls_traits::{
+    signatures::{AsyncSigner, Signer, SignerError},
+    types::SignatureScheme,
+};
+
+use crate::{
+    credentials::test_utils::new_credential,
+    framing::{MlsMessageIn, ProcessedMessageContent},
+    group::{
+        mls_group::tests_and_kats::utils::{block_on, setup_alice_bob_group},
+        MlsGroupJoinConfig, StagedWelcome,
+    },
+    key_packages::KeyPackage,
+};
+
+/// A mock remote signer that signs on a separate thread after an artificial
+/// latency, like a remote signing service or an HSM would.
+struct RemoteSigner {
+    keys: SignatureKeyPair,
+    latency: Duration,
+    requests: AtomicUsize,
+}
+
+impl RemoteSigner {
+    fn new(keys: SignatureKeyPair) -> Self {
+        Self {
+            keys,
+            latency: Duration::from_millis(20),
+            requests: AtomicUsize::new(0),
+        }
+    }
+
+    fn requests(&self) -> usize {
+        self.requests.load(Ordering::SeqCst)
+    }
+}
+
+type SignatureResult = Result<Vec<u8>, SignerError>;
+
+/// A pending signature of the [`RemoteSigner`].
+#[derive(Default)]
+struct RemoteSignature {
+    result: Option<SignatureResult>,
+    waker: Option<Waker>,
+}
+
+struct RemoteSignatureFuture(Arc<Mutex<RemoteSignature>>);
+
+impl Future for RemoteSignatureFuture {
+    type Output = SignatureResult;
+
+    fn poll(self: std::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.0.lock().unwrap();
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl AsyncSigner for RemoteSigner {
+    fn sign(&self, payload: &[u8]) -> impl Future<Output = SignatureResult> + Send {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        let state = Arc::new(Mutex::new(RemoteSignature::default()));
+
+        let keys = self.keys.clone();
+        let payload = payload.to_vec();
+        let latency = self.latency;
+        let remote_state = state.clone();
+        thread::spawn(move || {
+            thread::sleep(latency);
+            let result = Signer::sign(&keys, &payload);
+            let mut state = remote_state.lock().unwrap();
+            state.result = Some(result);
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        });
+
+        RemoteSignatureFuture(state)
+    }
+
+    fn signature_scheme(&self) -> SignatureScheme {
+        Signer::signature_scheme(&self.keys)
+    }
+}
+
+/// Build a key package, a commit and an application message with a remote
+/// signer and check that the other members accept them.
+#[openmls_test::openmls_test]
+fn async_signer() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+    let charlie_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, mut bob_group, _bob_signer, _alice_cwk, _bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+    let alice_signer = RemoteSigner::new(alice_signer);
+
+    // Charlie builds a key package with a remote signer
+    let (charlie_cwk, charlie_signer) = new_credential(
+        charlie_provider,
+        b"Charlie",
+        ciphersuite.signature_algorithm(),
+    );
+    let charlie_signer = RemoteSigner::new(charlie_signer);
+    let charlie_key_package_bundle = block_on(KeyPackage::builder().build_async(
+        ciphersuite,
+        charlie_provider,
+        &charlie_signer,
+        charlie_cwk,
+    ))
+    .expect("Could not build key package.");
+    // The leaf node and the key package are signed
+    assert_eq!(charlie_signer.requests(), 2);
+
+    // Alice adds Charlie with a remote signer. The commit is built on another
+    // thread to check that the future is `Send`.
+    let commit_builder = alice_group
+        .commit_builder()
+        .propose_adds(Some(charlie_key_package_bundle.key_package().clone()))
+        .force_self_update(true)
+        .load_psks(alice_provider.storage())
+        .unwrap();
+    let build_commit = commit_builder.build_async(
+        alice_provider.rand(),
+        alice_provider.crypto(),
+        &alice_signer,
+        |_| true,
+    );
+    let commit_message_bundle =
+        thread::scope(|scope| scope.spawn(|| block_on(build_commit)).join().unwrap())
+            .expect("Could not build commit.")
+            .stage_commit(alice_provider)
+            .expect("Could not stage commit.");
+    // The leaf node, the commit and the group info in the Welcome are signed
+    assert_eq!(alice_signer.requests(), 3);
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("Could not merge commit.");
+
+    let commit: MlsMessageIn = commit_message_bundle.commit().clone().into();
+    let processed_message = bob_group
+        .process_message(bob_provider, commit.into_protocol_message().unwrap())
+        .expect("Could not process commit.");
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a staged commit.");
+    };
+    bob_group
+        .merge_staged_commit(bob_provider, *staged_commit)
+        .expect("Could not merge commit.");
+
+    let welcome: MlsMessageIn = commit_message_bundle
+        .to_welcome_msg()
+        .expect("Expected a Welcome message.")
+        .into();
+    let mut charlie_group = StagedWelcome::new_from_welcome(
+        charlie_provider,
+        &MlsGroupJoinConfig::default(),
+        welcome.into_welcome().unwrap(),
+        Some(alice_group.export_ratchet_tree().into()),
+    )
+    .expect("Could not process Welcome.")
+    .into_group(charlie_provider)
+    .expect("Could not join group.");
+
+    // Alice sends a message with a remote signer
+    let message: MlsMessageIn =
+        block_on(alice_group.create_message_async(alice_provider, &alice_signer, b"hello"))
+            .expect("Could not create message.")
+            .into();
+    assert_eq!(alice_signer.requests(), 4);
+
+    for (group, provider) in [
+        (&mut bob_group, bob_provider),
+        (&mut charlie_group, charlie_provider),
+    ] {
+        let processed_message = group
+            .process_message(provider, message.clone().into_protocol_message().unwrap())
+            .expect("Could not process message.");
+        let ProcessedMessageContent::ApplicationMessage(application_message) =
+            processed_message.into_content()
+        else {
+            panic!("Expected an application message.");
+        };
+        assert_eq!(application_message.into_bytes(), b"hello");
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{
+    framing::{MlsMessageIn, ProcessedMessageContent},
+    group::{
+        mls_group::tests_and_kats::utils::{block_on, setup_alice_bob_group},
+        MlsGroup,
+    },
+    treesync::LeafNodeParameters,
+};
+
+/// Load a group, process and merge a commit and send a message through the
+/// asynchronous API, with a synchronous storage behind it, and check that the
+/// state it persists is the same as the one of the synchronous API.
+#[openmls_test::openmls_test]
+fn async_storage() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, bob_group, bob_signer, _alice_cwk, _bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+
+    // Bob loads his group asynchronously
+    let mut bob_loaded_group = block_on(MlsGroup::load_async(
+        bob_provider.storage(),
+        bob_group.group_id(),
+    ))
+    .expect("Could not load group.")
+    .expect("Group not found in storage.");
+    assert_eq!(bob_loaded_group, bob_group);
+
+    // Alice updates her leaf and Bob processes the commit asynchronously
+    let commit_message_bundle = alice_group
+        .self_update(alice_provider, &alice_signer, LeafNodeParameters::default())
+        .expect("Could not create commit.");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("Could not merge commit.");
+
+    let commit: MlsMessageIn = commit_message_bundle.commit().clone().into();
+    let processed_message = block_on(
+        bob_loaded_group
+            .process_message_async(bob_provider, commit.into_protocol_message().unwrap()),
+    )
+    .expect("Could not process commit.");
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a staged commit.");
+    };
+    block_on(bob_loaded_group.merge_staged_commit_async(bob_provider, *staged_commit))
+        .expect("Could not merge commit.");
+    assert_eq!(bob_loaded_group.epoch(), alice_group.epoch());
+    bob_loaded_group
+        .ensure_persistence(bob_provider.storage())
+        .expect("Bob group persistence check failed after merging.");
+
+    // Bob sends a message asynchronously
+    let message: MlsMessageIn =
+        block_on(bob_loaded_group.create_message_async(bob_provider, &bob_signer, b"hello"))
+            .expect("Could not create message.")
+            .into();
+    bob_loaded_group
+        .ensure_persistence(bob_provider.storage())
+        .expect("Bob group persistence check failed after sending.");
+
+    let processed_message = alice_group
+        .process_message(alice_provider, message.into_protocol_message().unwrap())
+        .expect("Could not process message.");
+    let ProcessedMessageContent::ApplicationMessage(application_message) =
+        processed_message.into_content()
+    else {
+        panic!("Expected an application message.");
+    };
+    assert_eq!(application_message.into_bytes(), b"hello");
+}
+
+/// Process and merge a commit and send a message through the asynchronous API
+/// on a task of a multi-threaded runtime.
+///
+/// This requires a provider that can be shared between threads, which the
+/// SQLite test provider can't, so the test uses the memory storage only.
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn async_storage_multi_threaded_runtime() {
+    use openmls_rust_crypto::OpenMlsRustCrypto;
+    use openmls_traits::{types::Ciphersuite, OpenMlsProvider as _};
+
+    let ciphersuite = Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519;
+    let alice_provider = &OpenMlsRustCrypto::default();
+    let bob_provider = OpenMlsRustCrypto::default();
+
+    let (mut alice_group, alice_signer, bob_group, bob_signer, _alice_cwk, _bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, &bob_provider);
+    let group_id = bob_group.group_id().clone();
+
+    let commit_message_bundle = alice_group
+        .self_update(alice_provider, &alice_signer, LeafNodeParameters::default())
+        .expect("Could not create commit.");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("Could not merge commit.");
+    let commit: MlsMessageIn = commit_message_bundle.commit().clone().into();
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .build()
+        .unwrap();
+    let message = runtime
+        .block_on(runtime.spawn(async move {
+            let mut bob_group = MlsGroup::load_async(bob_provider.storage(), &group_id)
+                .await
+                .expect("Could not load group.")
+                .expect("Group not found in storage.");
+            let processed_message = bob_group
+                .process_message_async(&bob_provider, commit.into_protocol_message().unwrap())
+                .await
+                .expect("Could not process commit.");
+            let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+                processed_message.into_content()
+            else {
+                panic!("Expected a staged commit.");
+            };
+            bob_group
+                .merge_staged_commit_async(&bob_provider, *staged_commit)
+                .await
+                .expect("Could not merge commit.");
+            bob_group
+                .create_message_async(&bob_provider, &bob_signer, b"hello")
+                .await
+                .expect("Could not create message.")
+        }))
+        .expect("The task panicked.");
+
+    let message: MlsMessageIn = message.into();
+    let processed_message = alice_group
+        .process_message(alice_provider, message.into_protocol_message().unwrap())
+        .expect("Could not process message.");
+    let ProcessedMessageContent::ApplicationMessage(application_message) =
+        processed_message.into_content()
+    else {
+        panic!("Expected an application message.");
+    };
+    assert_eq!(application_message.into_bytes(), b"hello");
+}
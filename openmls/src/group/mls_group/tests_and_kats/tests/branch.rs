@@ -0,0 +1,109 @@
+use crate::{
+    framing::MlsMessageIn,
+    group::{
+        errors::*,
+        mls_group::tests_and_kats::utils::{setup_alice_bob_group, setup_client},
+        GroupId,
+    },
+    key_packages::KeyPackageBundle,
+    schedule::{
+        psk::{PskBundle, ResumptionPsk, ResumptionPskUsage},
+        Psk,
+    },
+};
+
+/// Test branching a subgroup: Alice branches a subgroup off the parent group
+/// and invites Bob, who verifies the subgroup against the parent group when
+/// joining it.
+#[openmls_test::openmls_test]
+fn branch() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, bob_group, bob_signer, alice_cwk, bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+
+    // Alice branches a subgroup off the parent group and invites Bob
+    let bob_key_package_bundle =
+        KeyPackageBundle::generate(bob_provider, &bob_signer, ciphersuite, bob_cwk);
+
+    let branch_builder = alice_group.branch();
+    assert_eq!(branch_builder.parent_members().count(), 1);
+    let (mut alice_subgroup, message_bundle) = branch_builder
+        .refine_group_builder(|builder| builder.with_group_id(GroupId::from_slice(b"subgroup")))
+        .finish(
+            vec![bob_key_package_bundle.key_package().clone()],
+            |builder| builder,
+            alice_provider,
+            &alice_signer,
+            alice_cwk,
+        )
+        .expect("Could not create subgroup.");
+
+    // The branch PSK is never written to the storage.
+    let branch_psk = Psk::Resumption(ResumptionPsk::new(
+        ResumptionPskUsage::Branch,
+        alice_group.group_id().clone(),
+        alice_group.epoch(),
+    ));
+    let stored_psk: Option<PskBundle> = alice_provider.storage().psk(&branch_psk).unwrap();
+    assert!(stored_psk.is_none());
+
+    alice_subgroup
+        .merge_pending_commit(alice_provider)
+        .expect("Could not merge commit.");
+
+    let (_commit, welcome, _group_info) = message_bundle.into_messages();
+    let welcome: MlsMessageIn = welcome.expect("Expected a Welcome message.").into();
+    let welcome = welcome.into_welcome().unwrap();
+
+    // Bob joins the subgroup after verifying it
+    let bob_subgroup = bob_group
+        .join_branch(
+            bob_provider,
+            bob_group.configuration(),
+            welcome,
+            Some(alice_subgroup.export_ratchet_tree().into()),
+        )
+        .expect("Could not verify subgroup.")
+        .into_group(bob_provider)
+        .expect("Could not join subgroup.");
+
+    assert_eq!(bob_subgroup.group_id(), &GroupId::from_slice(b"subgroup"));
+    assert_eq!(
+        alice_subgroup
+            .export_secret(alice_provider.crypto(), "branch", b"", 32)
+            .unwrap(),
+        bob_subgroup
+            .export_secret(bob_provider.crypto(), "branch", b"", 32)
+            .unwrap()
+    );
+
+    // The parent group can still be used
+    alice_group
+        .create_message(alice_provider, &alice_signer, b"hello")
+        .expect("Could not create message in parent group.");
+}
+
+/// Only members of the parent group can be added to a subgroup.
+#[openmls_test::openmls_test]
+fn branch_with_non_member() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+    let charlie_provider = &Provider::default();
+
+    let (alice_group, alice_signer, _bob_group, _bob_signer, alice_cwk, _bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+    let (_charlie_cwk, charlie_key_package_bundle, _charlie_signer, _charlie_pk) =
+        setup_client("Charlie", ciphersuite, charlie_provider);
+
+    let result = alice_group.branch().finish(
+        vec![charlie_key_package_bundle.key_package().clone()],
+        |builder| builder,
+        alice_provider,
+        &alice_signer,
+        alice_cwk,
+    );
+
+    assert!(matches!(result, Err(BranchError::NotAParentGroupMember)));
+}
@@ -0,0 +1,438 @@
+use crate::{
+    ciphersuite::SignaturePublicKey,
+    credentials::{
+        errors::CredentialValidationError, BasicCredential, Credential, CredentialValidator,
+        CredentialWithKey,
+    },
+    framing::{MlsMessageIn, ProcessedMessageContent},
+    group::{
+        errors::*,
+        mls_group::tests_and_kats::utils::{setup_alice_bob_group, setup_client},
+        ExternalCommitBuilderError, MlsGroup, MlsGroupJoinConfig, StagedWelcome,
+        PURE_CIPHERTEXT_WIRE_FORMAT_POLICY,
+    },
+    treesync::LeafNodeParameters,
+};
+
+/// A validator that rejects basic credentials with the given identity.
+struct RejectIdentity(&'static [u8]);
+
+impl CredentialValidator for RejectIdentity {
+    fn validate_credential(
+        &self,
+        credential: &Credential,
+        _signature_key: &SignaturePublicKey,
+    ) -> Result<(), CredentialValidationError> {
+        let basic_credential = BasicCredential::try_from(credential.clone())
+            .map_err(|_| CredentialValidationError::UnsupportedCredentialType)?;
+        if basic_credential.identity() == self.0 {
+            return Err(CredentialValidationError::Rejected(
+                "identity is not allowed".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A Commit that adds a member with a rejected credential is rejected, while
+/// the same Commit is accepted by a validator that accepts the credential.
+#[openmls_test::openmls_test]
+fn add_with_rejected_credential() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+    let charlie_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, mut bob_group, _bob_signer, _alice_cwk, _bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+    let (_charlie_cwk, charlie_key_package_bundle, _charlie_signer, _charlie_pk) =
+        setup_client("Charlie", ciphersuite, charlie_provider);
+
+    let (commit, _welcome, _group_info) = alice_group
+        .add_members(
+            alice_provider,
+            &alice_signer,
+            &[charlie_key_package_bundle.key_package().clone()],
+        )
+        .expect("Could not add Charlie.");
+    let commit: MlsMessageIn = commit.into();
+
+    let result = bob_group.process_message_with_credential_validator(
+        bob_provider,
+        commit.clone().into_protocol_message().unwrap(),
+        &RejectIdentity(b"Charlie"),
+    );
+    assert!(matches!(
+        result,
+        Err(ProcessMessageError::CredentialValidationError(
+            CredentialValidationError::Rejected(_)
+        ))
+    ));
+
+    let processed_message = bob_group
+        .process_message_with_credential_validator(
+            bob_provider,
+            commit.into_protocol_message().unwrap(),
+            &RejectIdentity(b"Mallory"),
+        )
+        .expect("Could not process commit.");
+    assert!(matches!(
+        processed_message.into_content(),
+        ProcessedMessageContent::StagedCommitMessage(_)
+    ));
+}
+
+/// A credential that is changed in the update path of a Commit is validated.
+#[openmls_test::openmls_test]
+fn update_path_with_rejected_credential() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+
+    let (mut alice_group, _alice_signer, mut bob_group, bob_signer, _alice_cwk, bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+
+    let new_credential_with_key = CredentialWithKey {
+        credential: BasicCredential::new(b"Mallory".to_vec()).into(),
+        signature_key: bob_cwk.signature_key,
+    };
+    let (commit, _welcome, _group_info) = bob_group
+        .self_update(
+            bob_provider,
+            &bob_signer,
+            LeafNodeParameters::builder()
+                .with_credential_with_key(new_credential_with_key)
+                .build(),
+        )
+        .expect("Could not update own leaf.")
+        .into_contents();
+    let commit: MlsMessageIn = commit.into();
+
+    let result = alice_group.process_message_with_credential_validator(
+        alice_provider,
+        commit.into_protocol_message().unwrap(),
+        &RejectIdentity(b"Mallory"),
+    );
+    assert!(matches!(
+        result,
+        Err(ProcessMessageError::CredentialValidationError(
+            CredentialValidationError::Rejected(_)
+        ))
+    ));
+}
+
+/// A rejected PrivateMessage doesn't consume key material, so the same
+/// message can still be processed afterwards.
+#[openmls_test::openmls_test]
+fn rejected_private_message_keeps_secret_tree() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+
+    let (mut alice_group, _alice_signer, mut bob_group, bob_signer, _alice_cwk, bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+    let join_config = MlsGroupJoinConfig::builder()
+        .wire_format_policy(PURE_CIPHERTEXT_WIRE_FORMAT_POLICY)
+        .build();
+    alice_group
+        .set_configuration(alice_provider.storage(), &join_config)
+        .unwrap();
+    bob_group
+        .set_configuration(bob_provider.storage(), &join_config)
+        .unwrap();
+
+    let new_credential_with_key = CredentialWithKey {
+        credential: BasicCredential::new(b"Mallory".to_vec()).into(),
+        signature_key: bob_cwk.signature_key,
+    };
+    let (proposal, _proposal_ref) = bob_group
+        .propose_self_update(
+            bob_provider,
+            &bob_signer,
+            LeafNodeParameters::builder()
+                .with_credential_with_key(new_credential_with_key)
+                .build(),
+        )
+        .expect("Could not propose an update.");
+    let proposal: MlsMessageIn = proposal.into();
+
+    let result = alice_group.process_message_with_credential_validator(
+        alice_provider,
+        proposal.clone().into_protocol_message().unwrap(),
+        &RejectIdentity(b"Mallory"),
+    );
+    assert!(matches!(
+        result,
+        Err(ProcessMessageError::CredentialValidationError(
+            CredentialValidationError::Rejected(_)
+        ))
+    ));
+
+    let processed_message = alice_group
+        .process_message_with_credential_validator(
+            alice_provider,
+            proposal.into_protocol_message().unwrap(),
+            &RejectIdentity(b"Charlie"),
+        )
+        .expect("Could not process the proposal after a rejection.");
+    assert!(matches!(
+        processed_message.into_content(),
+        ProcessedMessageContent::ProposalMessage(_)
+    ));
+}
+
+/// Joining a group through a Welcome or an external Commit fails if the
+/// credential of an existing member is rejected.
+#[openmls_test::openmls_test]
+fn join_with_rejected_credential() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+    let charlie_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, _bob_group, _bob_signer, _alice_cwk, _bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+    let (charlie_cwk, charlie_key_package_bundle, _charlie_signer, _charlie_pk) =
+        setup_client("Charlie", ciphersuite, charlie_provider);
+
+    // Join through a Welcome
+    let (_commit, welcome, _group_info) = alice_group
+        .add_members(
+            alice_provider,
+            &alice_signer,
+            &[charlie_key_package_bundle.key_package().clone()],
+        )
+        .expect("Could not add Charlie.");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("Could not merge commit.");
+    let welcome: MlsMessageIn = welcome.into();
+    let welcome = welcome.into_welcome().unwrap();
+
+    let result = StagedWelcome::build_from_welcome(
+        charlie_provider,
+        &MlsGroupJoinConfig::default(),
+        welcome,
+    )
+    .expect("Could not process Welcome.")
+    .with_ratchet_tree(alice_group.export_ratchet_tree().into())
+    .with_credential_validator(&RejectIdentity(b"Bob"))
+    .build();
+    assert!(matches!(
+        result,
+        Err(WelcomeError::CredentialValidationError(
+            CredentialValidationError::Rejected(_)
+        ))
+    ));
+
+    // Join through an external Commit
+    let verifiable_group_info = alice_group
+        .export_group_info(alice_provider.crypto(), &alice_signer, true)
+        .unwrap()
+        .into_verifiable_group_info()
+        .unwrap();
+
+    let result = MlsGroup::external_commit_builder()
+        .with_credential_validator(&RejectIdentity(b"Bob"))
+        .build_group(charlie_provider, verifiable_group_info, charlie_cwk);
+    assert!(matches!(
+        result,
+        Err(ExternalCommitBuilderError::CredentialValidationError(
+            CredentialValidationError::Rejected(_)
+        ))
+    ));
+}
+
+/// The validator in the group configuration is used when processing messages
+/// and when merging commits that weren't processed with it.
+#[openmls_test::openmls_test]
+fn configured_validator_rejects_credential() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+    let charlie_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, mut bob_group, _bob_signer, _alice_cwk, _bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+    let (_charlie_cwk, charlie_key_package_bundle, _charlie_signer, _charlie_pk) =
+        setup_client("Charlie", ciphersuite, charlie_provider);
+
+    let default_config = MlsGroupJoinConfig::builder()
+        .wire_format_policy(bob_group.configuration().wire_format_policy())
+        .build();
+    let rejecting_config = MlsGroupJoinConfig::builder()
+        .wire_format_policy(bob_group.configuration().wire_format_policy())
+        .credential_validator(RejectIdentity(b"Charlie"))
+        .build();
+    bob_group
+        .set_configuration(bob_provider.storage(), &rejecting_config)
+        .unwrap();
+
+    let (commit, _welcome, _group_info) = alice_group
+        .add_members(
+            alice_provider,
+            &alice_signer,
+            &[charlie_key_package_bundle.key_package().clone()],
+        )
+        .expect("Could not add Charlie.");
+    let commit: MlsMessageIn = commit.into();
+
+    let result = bob_group.process_message(
+        bob_provider,
+        commit.clone().into_protocol_message().unwrap(),
+    );
+    assert!(matches!(
+        result,
+        Err(ProcessMessageError::CredentialValidationError(
+            CredentialValidationError::Rejected(_)
+        ))
+    ));
+
+    // A commit processed without the validator is validated when merging.
+    bob_group
+        .set_configuration(bob_provider.storage(), &default_config)
+        .unwrap();
+    let processed_message = bob_group
+        .process_message(bob_provider, commit.into_protocol_message().unwrap())
+        .expect("Could not process commit.");
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a staged commit.");
+    };
+    bob_group
+        .set_configuration(bob_provider.storage(), &rejecting_config)
+        .unwrap();
+    let result = bob_group.merge_staged_commit(bob_provider, *staged_commit);
+    assert!(matches!(
+        result,
+        Err(MergeCommitError::CredentialValidationError(
+            CredentialValidationError::Rejected(_)
+        ))
+    ));
+
+    // Own commits are not validated.
+    alice_group
+        .set_configuration(alice_provider.storage(), &rejecting_config)
+        .unwrap();
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("Could not merge commit.");
+}
+
+/// Joining a group through a Welcome or an external Commit uses the validator
+/// in the group configuration.
+#[openmls_test::openmls_test]
+fn join_with_configured_validator() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+    let charlie_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, _bob_group, _bob_signer, _alice_cwk, _bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+    let (charlie_cwk, charlie_key_package_bundle, _charlie_signer, _charlie_pk) =
+        setup_client("Charlie", ciphersuite, charlie_provider);
+
+    let rejecting_config = MlsGroupJoinConfig::builder()
+        .credential_validator(RejectIdentity(b"Bob"))
+        .build();
+
+    // Join through a Welcome
+    let (_commit, welcome, _group_info) = alice_group
+        .add_members(
+            alice_provider,
+            &alice_signer,
+            &[charlie_key_package_bundle.key_package().clone()],
+        )
+        .expect("Could not add Charlie.");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("Could not merge commit.");
+    let welcome: MlsMessageIn = welcome.into();
+    let welcome = welcome.into_welcome().unwrap();
+
+    let result = StagedWelcome::new_from_welcome(
+        charlie_provider,
+        &rejecting_config,
+        welcome,
+        Some(alice_group.export_ratchet_tree().into()),
+    );
+    assert!(matches!(
+        result,
+        Err(WelcomeError::CredentialValidationError(
+            CredentialValidationError::Rejected(_)
+        ))
+    ));
+
+    // Join through an external Commit
+    let verifiable_group_info = alice_group
+        .export_group_info(alice_provider.crypto(), &alice_signer, true)
+        .unwrap()
+        .into_verifiable_group_info()
+        .unwrap();
+
+    let result = MlsGroup::external_commit_builder()
+        .with_config(rejecting_config)
+        .build_group(charlie_provider, verifiable_group_info, charlie_cwk);
+    assert!(matches!(
+        result,
+        Err(ExternalCommitBuilderError::CredentialValidationError(
+            CredentialValidationError::Rejected(_)
+        ))
+    ));
+}
+
+/// A group that was stored with a validator in its configuration doesn't
+/// process messages after loading until the validator is set again.
+#[openmls_test::openmls_test]
+fn loaded_group_requires_validator() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+    let charlie_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, mut bob_group, _bob_signer, _alice_cwk, _bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+    let (_charlie_cwk, charlie_key_package_bundle, _charlie_signer, _charlie_pk) =
+        setup_client("Charlie", ciphersuite, charlie_provider);
+
+    let rejecting_config = MlsGroupJoinConfig::builder()
+        .wire_format_policy(bob_group.configuration().wire_format_policy())
+        .credential_validator(RejectIdentity(b"Charlie"))
+        .build();
+    bob_group
+        .set_configuration(bob_provider.storage(), &rejecting_config)
+        .unwrap();
+
+    let (commit, _welcome, _group_info) = alice_group
+        .add_members(
+            alice_provider,
+            &alice_signer,
+            &[charlie_key_package_bundle.key_package().clone()],
+        )
+        .expect("Could not add Charlie.");
+    let commit: MlsMessageIn = commit.into();
+
+    let mut bob_group = MlsGroup::load(bob_provider.storage(), bob_group.group_id())
+        .unwrap()
+        .expect("Could not load Bob's group.");
+    assert!(bob_group.configuration().requires_credential_validator());
+
+    let result = bob_group.process_message(
+        bob_provider,
+        commit.clone().into_protocol_message().unwrap(),
+    );
+    assert!(matches!(
+        result,
+        Err(ProcessMessageError::CredentialValidationError(
+            CredentialValidationError::ValidatorMissing
+        ))
+    ));
+
+    bob_group.set_credential_validator(RejectIdentity(b"Mallory"));
+    let processed_message = bob_group
+        .process_message(bob_provider, commit.into_protocol_message().unwrap())
+        .expect("Could not process commit.");
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a staged commit.");
+    };
+    bob_group
+        .merge_staged_commit(bob_provider, *staged_commit)
+        .expect("Could not merge commit.");
+}
@@ -0,0 +1,168 @@
+use openmls_libcrux_crypto::Provider as OpenMlsLibcrux;
+use openmls_rust_crypto::OpenMlsRustCrypto;
+use openmls_traits::{
+    crypto::OpenMlsCrypto,
+    signatures::{Signer, SignerError},
+    types::{Ciphersuite, SignatureScheme},
+    OpenMlsProvider,
+};
+
+use crate::{
+    credentials::{BasicCredential, CredentialWithKey},
+    framing::{MlsMessageIn, ProcessedMessageContent},
+    group::{MlsGroup, MlsGroupJoinConfig, StagedWelcome},
+    key_packages::KeyPackage,
+    treesync::LeafNodeParameters,
+};
+
+/// A signer backed by the `sign` function of a crypto provider, so that each
+/// member only uses its own provider.
+struct ProviderSigner<'a, Crypto: OpenMlsCrypto> {
+    crypto: &'a Crypto,
+    private: Vec<u8>,
+    signature_scheme: SignatureScheme,
+}
+
+impl<Crypto: OpenMlsCrypto> Signer for ProviderSigner<'_, Crypto> {
+    fn sign(&self, payload: &[u8]) -> Result<Vec<u8>, SignerError> {
+        self.crypto
+            .sign(self.signature_scheme, payload, &self.private)
+            .map_err(SignerError::CryptoError)
+    }
+
+    fn signature_scheme(&self) -> SignatureScheme {
+        self.signature_scheme
+    }
+}
+
+fn new_member<'a, Provider: OpenMlsProvider>(
+    provider: &'a Provider,
+    identity: &[u8],
+    ciphersuite: Ciphersuite,
+) -> (
+    CredentialWithKey,
+    ProviderSigner<'a, Provider::CryptoProvider>,
+) {
+    let signature_scheme = ciphersuite.signature_algorithm();
+    let (private, public) = provider
+        .crypto()
+        .signature_key_gen(signature_scheme)
+        .expect("Could not generate signature keys.");
+    let credential_with_key = CredentialWithKey {
+        credential: BasicCredential::new(identity.to_vec()).into(),
+        signature_key: public.into(),
+    };
+    let signer = ProviderSigner {
+        crypto: provider.crypto(),
+        private,
+        signature_scheme,
+    };
+
+    (credential_with_key, signer)
+}
+
+/// Alice uses the RustCrypto provider and Bob the libcrux provider. Check that
+/// they can form a group, commit, and exchange messages in every ciphersuite
+/// both providers support.
+#[test]
+fn rust_crypto_and_libcrux_in_one_group() {
+    let alice_provider = &OpenMlsRustCrypto::default();
+    let bob_provider = &OpenMlsLibcrux::default();
+
+    let libcrux_ciphersuites = bob_provider.crypto().supported_ciphersuites();
+    let ciphersuites = alice_provider
+        .crypto()
+        .supported_ciphersuites()
+        .into_iter()
+        .filter(|ciphersuite| libcrux_ciphersuites.contains(ciphersuite));
+
+    for ciphersuite in ciphersuites {
+        let (alice_cwk, alice_signer) = new_member(alice_provider, b"Alice", ciphersuite);
+        let (bob_cwk, bob_signer) = new_member(bob_provider, b"Bob", ciphersuite);
+
+        // Bob's key package and HPKE init key are generated with libcrux
+        let bob_key_package = KeyPackage::builder()
+            .build(ciphersuite, bob_provider, &bob_signer, bob_cwk)
+            .expect("Could not build key package.");
+
+        // Alice creates a group and adds Bob
+        let mut alice_group = MlsGroup::builder()
+            .ciphersuite(ciphersuite)
+            .build(alice_provider, &alice_signer, alice_cwk)
+            .expect("Could not create group.");
+        let (_commit, welcome, _group_info) = alice_group
+            .add_members(
+                alice_provider,
+                &alice_signer,
+                core::slice::from_ref(bob_key_package.key_package()),
+            )
+            .expect("Could not add Bob.");
+        alice_group
+            .merge_pending_commit(alice_provider)
+            .expect("Could not merge commit.");
+
+        // Bob joins from the welcome
+        let welcome: MlsMessageIn = welcome.into();
+        let welcome = welcome.into_welcome().expect("Expected a welcome message.");
+        let mut bob_group = StagedWelcome::new_from_welcome(
+            bob_provider,
+            &MlsGroupJoinConfig::default(),
+            welcome,
+            Some(alice_group.export_ratchet_tree().into()),
+        )
+        .expect("Could not stage welcome.")
+        .into_group(bob_provider)
+        .expect("Could not join group.");
+
+        // Bob updates his leaf, encrypting the path to Alice with libcrux
+        let bob_commit = bob_group
+            .self_update(bob_provider, &bob_signer, LeafNodeParameters::default())
+            .expect("Could not create commit.")
+            .into_commit();
+        bob_group
+            .merge_pending_commit(bob_provider)
+            .expect("Could not merge commit.");
+
+        let bob_commit: MlsMessageIn = bob_commit.into();
+        let processed = alice_group
+            .process_message(alice_provider, bob_commit.into_protocol_message().unwrap())
+            .expect("Could not process commit.");
+        let ProcessedMessageContent::StagedCommitMessage(staged_commit) = processed.into_content()
+        else {
+            panic!("Expected a staged commit.");
+        };
+        alice_group
+            .merge_staged_commit(alice_provider, *staged_commit)
+            .expect("Could not merge commit.");
+        assert_eq!(
+            alice_group.epoch_authenticator(),
+            bob_group.epoch_authenticator(),
+            "{ciphersuite:?}"
+        );
+
+        // Both exchange application messages
+        let message = alice_group
+            .create_message(alice_provider, &alice_signer, b"Hello Bob")
+            .expect("Could not create message.");
+        let message: MlsMessageIn = message.into();
+        let processed = bob_group
+            .process_message(bob_provider, message.into_protocol_message().unwrap())
+            .expect("Could not process message.");
+        let ProcessedMessageContent::ApplicationMessage(message) = processed.into_content() else {
+            panic!("Expected an application message.");
+        };
+        assert_eq!(message.into_bytes(), b"Hello Bob");
+
+        let message = bob_group
+            .create_message(bob_provider, &bob_signer, b"Hello Alice")
+            .expect("Could not create message.");
+        let message: MlsMessageIn = message.into();
+        let processed = alice_group
+            .process_message(alice_provider, message.into_protocol_message().unwrap())
+            .expect("Could not process message.");
+        let ProcessedMessageContent::ApplicationMessage(message) = processed.into_content() else {
+            panic!("Expected an application message.");
+        };
+        assert_eq!(message.into_bytes(), b"Hello Alice");
+    }
+}
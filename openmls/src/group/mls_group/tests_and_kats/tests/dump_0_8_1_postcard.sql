@@ -0,0 +1,92 @@
+PRAGMA foreign_keys=OFF;
+BEGIN TRANSACTION;
+CREATE TABLE openmls_sqlite_storage_migrations(
+             version int4 PRIMARY KEY,
+             name VARCHAR(255),
+             applied_on VARCHAR(255),
+             checksum VARCHAR(255));
+INSERT INTO openmls_sqlite_storage_migrations VALUES(1,'initial','2026-10-19T03:04:43.309041297Z','5121145794404019609');
+INSERT INTO openmls_sqlite_storage_migrations VALUES(2,'constraint_addition','2026-10-19T03:04:43.309081492Z','366327680295175235');
+CREATE TABLE openmls_encryption_keys (
+    provider_version INTEGER NOT NULL,
+    public_key BLOB PRIMARY KEY,
+    key_pair BLOB NOT NULL
+);
+INSERT INTO openmls_encryption_keys VALUES(1,X'20ab6e3bf038054c12de184181a0a44ab951c8e42b07159835150808218951840a',X'20ab6e3bf038054c12de184181a0a44ab951c8e42b07159835150808218951840a20841348655d0c13eedea7704ac6d01d7f24b6e7289eb743a77f86a46046dc912c');
+CREATE TABLE openmls_epoch_keys_pairs (
+    provider_version INTEGER NOT NULL,
+    group_id BLOB NOT NULL,
+    epoch_id BLOB NOT NULL,
+    leaf_index INTEGER NOT NULL,
+    key_pairs BLOB NOT NULL,
+    PRIMARY KEY (group_id, epoch_id, leaf_index)
+);
+INSERT INTO openmls_epoch_keys_pairs VALUES(1,X'0d746573745f67726f75705f6964',X'00',0,X'0120b129022eb1c131b309d1bb52cefd5817472a4798f05c864900892f4c668c043a200fc8188cf57cd8f0566c8b0f52c62ccf997053b93b6a56acad7de37f217dedaa');
+CREATE TABLE openmls_key_packages (
+    provider_version INTEGER NOT NULL,
+    key_package_ref BLOB PRIMARY KEY,
+    key_package BLOB NOT NULL
+);
+INSERT INTO openmls_key_packages VALUES(1,X'20bad3226fa04fcb1b13afb666382aca0d03ba470a31e06065f8d5b32646a5100e',X'000020069e7ec438ecac793c5d99c19a3ce1f67dab119635fd66ff044063306852912020d9954752cd7e283c9612e1f11f64987b3db852bae8e05013b29fd72a7b191015205cdf740bee20ff3f11db3fe2a9a56f152629ff8999f36a5df11be9ea41d4d5da0003426f620100040102034d010780fe030002000382fe0300bbf5d5d606cb8d91da060040f2bf20d3629fabcab424f7ed4151f3befe2f6b1f543c16425f3cb77e60dd5bf536eef74c680f342b99cdaa147fd9282894c0ce272c7d1ca6b50ed3faed7aca0f00406d8713a85eaf200d5be13e823aac2e28ff0656679671745775e24fba747075e9235cb1e16b4bdedbfd4d3f65ccb8983546331e34fb01a4e00857876ccedf2b0e20f32f03f2fc2ccf650bae377e6819f3263205235f8c11e40d70aeaf5d358151bc2087dbd4934bc97cf473224a2c9964f0af2259ce1bf9510584a3326db1ab5855f8');
+CREATE TABLE openmls_own_leaf_nodes (
+    provider_version INTEGER NOT NULL,
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    group_id BLOB NOT NULL,
+    leaf_node BLOB NOT NULL
+);
+INSERT INTO openmls_own_leaf_nodes VALUES(1,1,X'0d746573745f67726f75705f6964',X'20ab6e3bf038054c12de184181a0a44ab951c8e42b07159835150808218951840a2032fdf1ec887a327bf9eef5982ed0795e4a91b8e9f2897ca73599021a18157c910005416c6963650100040102034d010780fe030002000382fe0301004069e2e333f744c789f10cfec6f79e0dadb769c55968f2a57515275f04b3320590d7391b739777f661aa353cbe7c558d2c7a2a75953209074c203bbee89afeb909');
+CREATE TABLE openmls_proposals (
+    provider_version INTEGER NOT NULL,
+    group_id BLOB NOT NULL,
+    proposal_ref BLOB NOT NULL,
+    proposal BLOB NOT NULL,
+    PRIMARY KEY (group_id, proposal_ref)
+);
+INSERT INTO openmls_proposals VALUES(1,X'0d746573745f67726f75705f6964',X'2024393e822f1de32b60178ae49d63228b47c45cb37215674274e331b0af1f534f',X'0120ab6e3bf038054c12de184181a0a44ab951c8e42b07159835150808218951840a2032fdf1ec887a327bf9eef5982ed0795e4a91b8e9f2897ca73599021a18157c910005416c6963650100040102034d010780fe030002000382fe0301004069e2e333f744c789f10cfec6f79e0dadb769c55968f2a57515275f04b3320590d7391b739777f661aa353cbe7c558d2c7a2a75953209074c203bbee89afeb9092024393e822f1de32b60178ae49d63228b47c45cb37215674274e331b0af1f534f000001');
+CREATE TABLE openmls_psks (
+    provider_version INTEGER NOT NULL,
+    psk_id BLOB PRIMARY KEY,
+    psk_bundle BLOB NOT NULL
+);
+CREATE TABLE openmls_signature_keys (
+    provider_version INTEGER NOT NULL,
+    public_key BLOB PRIMARY KEY,
+    signature_key BLOB NOT NULL
+);
+INSERT INTO openmls_signature_keys VALUES(1,X'3832fdf1ec887a327bf9eef5982ed0795e4a91b8e9f2897ca73599021a18157c915275737443727970746f5369676e61747572654b65790807',X'20add046ffe34d0739a732be4c09eab04af5bc6fefe06946ed28ace6954b895f152032fdf1ec887a327bf9eef5982ed0795e4a91b8e9f2897ca73599021a18157c9103');
+CREATE TABLE IF NOT EXISTS "openmls_group_data" (
+    provider_version INTEGER NOT NULL,
+    group_id BLOB NOT NULL,
+    data_type TEXT NOT NULL CHECK (data_type IN (
+        'join_group_config', 
+        'tree', 
+        'interim_transcript_hash',
+        'context', 
+        'confirmation_tag', 
+        'group_state', 
+        'message_secrets', 
+        'resumption_psk_store',
+        'own_leaf_index',
+        'use_ratchet_tree_extension',
+        'group_epoch_secrets',
+        'application_export_tree'
+    )),
+    group_data BLOB NOT NULL,
+    PRIMARY KEY (group_id, data_type)
+);
+INSERT INTO openmls_group_data VALUES(1,X'0d746573745f67726f75705f6964','tree',X'010120b129022eb1c131b309d1bb52cefd5817472a4798f05c864900892f4c668c043a2032fdf1ec887a327bf9eef5982ed0795e4a91b8e9f2897ca73599021a18157c910005416c6963650100040102034d010780fe030002000382fe0300bbf5d5d606cb8d91da0600406b28d81a10c532e7355865756ea020142a16e65ffd9b6c765410cf2d404bd94645af854394e460028041fb76ff2bf78ca1ddfb3cc07d646e3e5118a37347d00e000000209bbeae15972edc817bc3ee447ec79bfd4b335b9d89e05a7693aaf2a9e9a0133c');
+INSERT INTO openmls_group_data VALUES(1,X'0d746573745f67726f75705f6964','confirmation_tag',X'204a8d06fd82dc2a1c852ace1cc8edabd6c6291b1482656ff1ca256a9073d9ee45');
+INSERT INTO openmls_group_data VALUES(1,X'0d746573745f67726f75705f6964','context',X'00000d746573745f67726f75705f696400209bbeae15972edc817bc3ee447ec79bfd4b335b9d89e05a7693aaf2a9e9a0133c0000');
+INSERT INTO openmls_group_data VALUES(1,X'0d746573745f67726f75705f6964','interim_transcript_hash',X'200cc36c9b3ce45352cfd8ab4ed8a3bde386f3421e747dd452daab0bc7fd96bdd9');
+INSERT INTO openmls_group_data VALUES(1,X'0d746573745f67726f75705f6964','group_epoch_secrets',X'203387422c0bfe25831c32b77087681360bafb415803bfe9dab8e22940d1da65f820421ab6bbd780e05572e900b43f284ee78933425c079cf656573c938f7a0bb2c820bb6623bb2d164d2051b2bab2c834f4d3bc56f80d051fd24e957a45d82163f3fa200c8298acead1e7510ae88d99dd545661585b8e2e2d81fef028bcda2165cf786520aafd6792f9d42a416d2339f0b6098f21efef9664b6af35215f7e6b5f891ef8e0');
+INSERT INTO openmls_group_data VALUES(1,X'0d746573745f67726f75705f6964','own_leaf_index',X'00');
+INSERT INTO openmls_group_data VALUES(1,X'0d746573745f67726f75705f6964','resumption_psk_store',X'20010020aafd6792f9d42a416d2339f0b6098f21efef9664b6af35215f7e6b5f891ef8e001');
+INSERT INTO openmls_group_data VALUES(1,X'0d746573745f67726f75705f6964','join_group_config',X'00000000000005e807');
+INSERT INTO openmls_group_data VALUES(1,X'0d746573745f67726f75705f6964','group_state',X'01');
+INSERT INTO openmls_group_data VALUES(1,X'0d746573745f67726f75705f6964','message_secrets',X'000020ff103b2441afa9162f7ba6e41a3f013e986ebd5dd3fb83564dbbb06adfd5d82920cc88bf52ee02211e8481083fe715cd25325b3097592c1445681cdca1fc7af93520166574b74f044e7c74627feb2751915e27b39725c98b019ae9f7598c7aa0ffb23d000100010d746573745f67726f75705f69640000000000000000209bbeae15972edc817bc3ee447ec79bfd4b335b9d89e05a7693aaf2a9e9a0133c0000000100010001010020245141c7eeb0ea93bb09ffe9ebe0620adbadc8382e742f3d75cf39906fb7dd7901010100206c97125d3b85efebd8a3ec9e20e3ffbe6a34f15416220f76bdcbbd37b43d26770001');
+PRAGMA writable_schema=ON;
+CREATE TABLE IF NOT EXISTS sqlite_sequence(name,seq);
+DELETE FROM sqlite_sequence;
+INSERT INTO sqlite_sequence VALUES('openmls_own_leaf_nodes',1);
+PRAGMA writable_schema=OFF;
+COMMIT;
@@ -0,0 +1,401 @@
+//! Tests for buffering messages from the next epoch, see
+//! [`FutureEpochBufferConfig`].
+
+use openmls_traits::signatures::Signer;
+
+use super::transactions::InjectWriteFailure;
+use crate::{
+    ciphersuite::SignaturePublicKey,
+    credentials::{errors::CredentialValidationError, Credential, CredentialValidator},
+    framing::{MlsMessageIn, ProcessedMessageContent, ProtocolMessage},
+    group::{
+        future_epoch_messages::FutureEpochMessages,
+        mls_group::tests_and_kats::utils::{setup_alice_bob_group, setup_client},
+        FutureEpochBufferConfig, MlsGroup, MlsGroupJoinConfig, ProcessMessageError,
+        ValidationError, PURE_PLAINTEXT_WIRE_FORMAT_POLICY,
+    },
+    storage::OpenMlsProvider,
+    treesync::LeafNodeParameters,
+};
+
+/// Alice creates a commit and merges it. Then she sends one application
+/// message per payload in the new epoch.
+fn commit_and_send<Provider: OpenMlsProvider>(
+    provider: &Provider,
+    signer: &impl Signer,
+    group: &mut MlsGroup,
+    payloads: &[&[u8]],
+) -> (ProtocolMessage, Vec<ProtocolMessage>) {
+    let commit_message_bundle = group
+        .self_update(provider, signer, LeafNodeParameters::default())
+        .expect("Could not create commit.");
+    group
+        .merge_pending_commit(provider)
+        .expect("Could not merge commit.");
+    let commit: MlsMessageIn = commit_message_bundle.commit().clone().into();
+
+    let messages = payloads
+        .iter()
+        .map(|payload| {
+            let message: MlsMessageIn = group
+                .create_message(provider, signer, payload)
+                .expect("Could not create message.")
+                .into();
+            message.into_protocol_message().unwrap()
+        })
+        .collect();
+
+    (commit.into_protocol_message().unwrap(), messages)
+}
+
+/// Processes the commit and merges it. Returns the payloads of the buffered
+/// application messages that were processed after the merge.
+fn process_commit<Provider: OpenMlsProvider>(
+    provider: &Provider,
+    group: &mut MlsGroup,
+    commit: ProtocolMessage,
+) -> Vec<Vec<u8>> {
+    let processed_message = group
+        .process_message(provider, commit)
+        .expect("Could not process commit.");
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a staged commit.");
+    };
+    group
+        .merge_staged_commit(provider, *staged_commit)
+        .expect("Could not merge commit.");
+
+    group
+        .process_buffered_messages(provider)
+        .expect("Could not process buffered messages.")
+        .into_iter()
+        .map(|processed_message| match processed_message.into_content() {
+            ProcessedMessageContent::ApplicationMessage(application_message) => {
+                application_message.into_bytes()
+            }
+            _ => panic!("Expected an application message."),
+        })
+        .collect()
+}
+
+fn enable_buffer<Provider: OpenMlsProvider>(
+    provider: &Provider,
+    group: &mut MlsGroup,
+    config: FutureEpochBufferConfig,
+) {
+    let join_config = MlsGroupJoinConfig::builder()
+        .wire_format_policy(PURE_PLAINTEXT_WIRE_FORMAT_POLICY)
+        .future_epoch_buffer(config)
+        .build();
+    group
+        .set_configuration(provider.storage(), &join_config)
+        .expect("Could not set configuration.");
+}
+
+/// A message from the next epoch is buffered, survives reloading the group and
+/// is processed when the commit into its epoch is merged.
+#[openmls_test::openmls_test]
+fn buffered_message_is_processed_after_merge() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, mut bob_group, _bob_signer, _alice_cwk, _bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+    enable_buffer(
+        bob_provider,
+        &mut bob_group,
+        FutureEpochBufferConfig::new(8),
+    );
+
+    let (commit, messages) =
+        commit_and_send(alice_provider, &alice_signer, &mut alice_group, &[b"hello"]);
+
+    // The application message arrives before the commit
+    for message in messages {
+        let err = bob_group
+            .process_message(bob_provider, message)
+            .expect_err("Message from the next epoch was processed.");
+        assert!(matches!(err, ProcessMessageError::MessageBuffered));
+    }
+    assert_eq!(bob_group.buffered_future_epoch_messages(), 1);
+    bob_group
+        .ensure_persistence(bob_provider.storage())
+        .expect("Bob group persistence check failed after buffering.");
+
+    let mut bob_group = MlsGroup::load(bob_provider.storage(), bob_group.group_id())
+        .expect("Could not load group.")
+        .expect("Group not found in storage.");
+    assert_eq!(bob_group.buffered_future_epoch_messages(), 1);
+
+    let processed = process_commit(bob_provider, &mut bob_group, commit);
+    assert_eq!(processed, vec![b"hello".to_vec()]);
+    assert_eq!(bob_group.epoch(), alice_group.epoch());
+    assert_eq!(bob_group.buffered_future_epoch_messages(), 0);
+    let stored: Option<FutureEpochMessages> = bob_provider
+        .storage()
+        .future_epoch_messages(bob_group.group_id())
+        .unwrap();
+    assert!(stored.is_none());
+    bob_group
+        .ensure_persistence(bob_provider.storage())
+        .expect("Bob group persistence check failed after merging.");
+}
+
+/// Merging a commit with
+/// [`MlsGroup::merge_staged_commit_and_process_buffered_messages()`] returns the
+/// buffered messages from the new epoch.
+#[openmls_test::openmls_test]
+fn merge_returns_buffered_messages() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, mut bob_group, _bob_signer, _alice_cwk, _bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+    enable_buffer(
+        bob_provider,
+        &mut bob_group,
+        FutureEpochBufferConfig::new(8),
+    );
+
+    let (commit, messages) = commit_and_send(
+        alice_provider,
+        &alice_signer,
+        &mut alice_group,
+        &[b"first", b"second"],
+    );
+    for message in messages {
+        let err = bob_group
+            .process_message(bob_provider, message)
+            .expect_err("Message from the next epoch was processed.");
+        assert!(matches!(err, ProcessMessageError::MessageBuffered));
+    }
+
+    let processed_message = bob_group
+        .process_message(bob_provider, commit)
+        .expect("Could not process commit.");
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a staged commit.");
+    };
+    let processed: Vec<Vec<u8>> = bob_group
+        .merge_staged_commit_and_process_buffered_messages(bob_provider, *staged_commit)
+        .expect("Could not merge commit.")
+        .into_iter()
+        .map(|processed_message| match processed_message.into_content() {
+            ProcessedMessageContent::ApplicationMessage(application_message) => {
+                application_message.into_bytes()
+            }
+            _ => panic!("Expected an application message."),
+        })
+        .collect();
+
+    assert_eq!(processed, vec![b"first".to_vec(), b"second".to_vec()]);
+    assert_eq!(bob_group.epoch(), alice_group.epoch());
+    assert_eq!(bob_group.buffered_future_epoch_messages(), 0);
+    bob_group
+        .ensure_persistence(bob_provider.storage())
+        .expect("Bob group persistence check failed after merging.");
+}
+
+/// Without a buffer, a message from the next epoch is rejected.
+#[openmls_test::openmls_test]
+fn buffer_disabled_by_default() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, mut bob_group, _bob_signer, _alice_cwk, _bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+
+    let (commit, messages) =
+        commit_and_send(alice_provider, &alice_signer, &mut alice_group, &[b"hello"]);
+
+    for message in messages {
+        let err = bob_group
+            .process_message(bob_provider, message)
+            .expect_err("Message from the next epoch was processed.");
+        assert!(matches!(
+            err,
+            ProcessMessageError::ValidationError(ValidationError::WrongEpoch)
+        ));
+    }
+    assert_eq!(bob_group.buffered_future_epoch_messages(), 0);
+
+    let processed = process_commit(bob_provider, &mut bob_group, commit);
+    assert!(processed.is_empty());
+}
+
+/// The buffer rejects new messages when it is full and drops messages that
+/// are older than the maximum age.
+#[openmls_test::openmls_test]
+fn buffer_limits() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, mut bob_group, _bob_signer, _alice_cwk, _bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+
+    // At most two messages are kept
+    enable_buffer(
+        bob_provider,
+        &mut bob_group,
+        FutureEpochBufferConfig::new(2),
+    );
+    let (commit, messages) = commit_and_send(
+        alice_provider,
+        &alice_signer,
+        &mut alice_group,
+        &[b"one", b"two", b"three"],
+    );
+    let errors = messages
+        .into_iter()
+        .map(|message| {
+            bob_group
+                .process_message(bob_provider, message)
+                .unwrap_err()
+        })
+        .collect::<Vec<_>>();
+    assert!(matches!(errors[0], ProcessMessageError::MessageBuffered));
+    assert!(matches!(errors[1], ProcessMessageError::MessageBuffered));
+    assert!(matches!(
+        errors[2],
+        ProcessMessageError::FutureEpochBufferFull
+    ));
+    assert_eq!(bob_group.buffered_future_epoch_messages(), 2);
+
+    let processed = process_commit(bob_provider, &mut bob_group, commit);
+    assert_eq!(processed, vec![b"one".to_vec(), b"two".to_vec()]);
+
+    // Messages expire after the maximum age
+    enable_buffer(
+        bob_provider,
+        &mut bob_group,
+        FutureEpochBufferConfig::new(2).with_max_age(std::time::Duration::from_millis(1)),
+    );
+    let (commit, messages) =
+        commit_and_send(alice_provider, &alice_signer, &mut alice_group, &[b"late"]);
+    for message in messages {
+        let err = bob_group
+            .process_message(bob_provider, message)
+            .unwrap_err();
+        assert!(matches!(err, ProcessMessageError::MessageBuffered));
+    }
+    assert_eq!(bob_group.buffered_future_epoch_messages(), 1);
+    std::thread::sleep(std::time::Duration::from_millis(10));
+
+    let processed = process_commit(bob_provider, &mut bob_group, commit);
+    assert!(processed.is_empty());
+    assert_eq!(bob_group.buffered_future_epoch_messages(), 0);
+}
+
+/// A message that can't be persisted isn't kept in the buffer either.
+#[openmls_test::openmls_test]
+fn failed_buffer_write_drops_message() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, mut bob_group, _bob_signer, _alice_cwk, _bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+    enable_buffer(
+        bob_provider,
+        &mut bob_group,
+        FutureEpochBufferConfig::new(8),
+    );
+
+    let (commit, mut messages) =
+        commit_and_send(alice_provider, &alice_signer, &mut alice_group, &[b"hello"]);
+    let message = messages.remove(0);
+
+    InjectWriteFailure::fail_after_writes(bob_provider.storage(), 0);
+    let err = bob_group
+        .process_message(bob_provider, message.clone())
+        .expect_err("Message was buffered despite the failing write.");
+    assert!(matches!(err, ProcessMessageError::StorageError(_)));
+    assert_eq!(bob_group.buffered_future_epoch_messages(), 0);
+    bob_group
+        .ensure_persistence(bob_provider.storage())
+        .expect("Bob group persistence check failed after the failing write.");
+
+    // The message can be buffered once the storage works again.
+    InjectWriteFailure::allow_writes(bob_provider.storage());
+    let err = bob_group
+        .process_message(bob_provider, message)
+        .expect_err("Message from the next epoch was processed.");
+    assert!(matches!(err, ProcessMessageError::MessageBuffered));
+    let processed = process_commit(bob_provider, &mut bob_group, commit);
+    assert_eq!(processed, vec![b"hello".to_vec()]);
+}
+
+/// A validator that rejects all credentials.
+struct RejectAll;
+
+impl CredentialValidator for RejectAll {
+    fn validate_credential(
+        &self,
+        _credential: &Credential,
+        _signature_key: &SignaturePublicKey,
+    ) -> Result<(), CredentialValidationError> {
+        Err(CredentialValidationError::Rejected(
+            "no credential is allowed".to_string(),
+        ))
+    }
+}
+
+/// Buffered messages are processed with the credential validator, and
+/// messages with rejected credentials are dropped.
+#[openmls_test::openmls_test]
+fn buffered_messages_are_validated() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+    let charlie_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, mut bob_group, _bob_signer, _alice_cwk, _bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+    let (_charlie_cwk, charlie_key_package_bundle, _charlie_signer, _charlie_pk) =
+        setup_client("Charlie", ciphersuite, charlie_provider);
+    enable_buffer(
+        bob_provider,
+        &mut bob_group,
+        FutureEpochBufferConfig::new(8),
+    );
+
+    // Alice proposes to add Charlie in the next epoch
+    let (commit, _) = commit_and_send(alice_provider, &alice_signer, &mut alice_group, &[]);
+    let (proposal, _proposal_ref) = alice_group
+        .propose_add_member(
+            alice_provider,
+            &alice_signer,
+            charlie_key_package_bundle.key_package(),
+        )
+        .expect("Could not propose to add Charlie.");
+    let proposal: MlsMessageIn = proposal.into();
+
+    let err = bob_group
+        .process_message(bob_provider, proposal.into_protocol_message().unwrap())
+        .expect_err("Message from the next epoch was processed.");
+    assert!(matches!(err, ProcessMessageError::MessageBuffered));
+
+    let processed_message = bob_group
+        .process_message(bob_provider, commit)
+        .expect("Could not process commit.");
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a staged commit.");
+    };
+    bob_group
+        .merge_staged_commit(bob_provider, *staged_commit)
+        .expect("Could not merge commit.");
+
+    let processed = bob_group
+        .process_buffered_messages_with_credential_validator(bob_provider, &RejectAll)
+        .expect("Could not process buffered messages.");
+    assert!(processed.is_empty());
+    assert_eq!(bob_group.buffered_future_epoch_messages(), 0);
+    assert!(!bob_group.has_pending_proposals());
+    bob_group
+        .ensure_persistence(bob_provider.storage())
+        .expect("Bob group persistence check failed after processing.");
+}
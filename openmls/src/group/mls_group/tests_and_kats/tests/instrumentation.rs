@@ -0,0 +1,310 @@
+use std::sync::{Arc, Mutex};
+
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Metadata, Subscriber,
+};
+
+use openmls_rust_crypto::{MemoryStorage, RustCrypto};
+use openmls_traits::OpenMlsProvider;
+
+use crate::{
+    framing::{MlsMessageIn, ProcessedMessageContent},
+    group::mls_group::tests_and_kats::utils::{setup_alice_bob_group, setup_alice_group},
+    prelude::Ciphersuite,
+    storage::InstrumentedStorage,
+};
+
+#[derive(Debug)]
+struct RecordedSpan {
+    metadata: &'static Metadata<'static>,
+    fields: Vec<(&'static str, String)>,
+}
+
+impl RecordedSpan {
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Debug, Default)]
+struct Recorded {
+    spans: Vec<RecordedSpan>,
+    events: Vec<Vec<(&'static str, String)>>,
+    stack: Vec<span::Id>,
+}
+
+/// A subscriber that records all spans and events, to check which fields
+/// the instrumentation emits.
+#[derive(Debug, Clone, Default)]
+struct Recorder(Arc<Mutex<Recorded>>);
+
+impl Recorder {
+    fn spans(&self, name: &str) -> Vec<Vec<(&'static str, String)>> {
+        self.0
+            .lock()
+            .unwrap()
+            .spans
+            .iter()
+            .filter(|span| span.metadata.name() == name)
+            .map(|span| span.fields.clone())
+            .collect()
+    }
+
+    /// Returns the value of `field` in the most recent span called `name`.
+    fn span_field(&self, name: &str, field: &str) -> Option<String> {
+        let recorded = self.0.lock().unwrap();
+        let span = recorded
+            .spans
+            .iter()
+            .rfind(|span| span.metadata.name() == name)?;
+        span.field(field).map(str::to_owned)
+    }
+
+    /// Returns the `method` fields of all `storage_call` spans.
+    fn storage_calls(&self) -> Vec<String> {
+        self.spans("storage_call")
+            .into_iter()
+            .filter_map(|fields| {
+                fields
+                    .into_iter()
+                    .find(|(field, _)| *field == "method")
+                    .map(|(_, value)| value)
+            })
+            .collect()
+    }
+
+    fn timed_operations(&self) -> Vec<String> {
+        self.0
+            .lock()
+            .unwrap()
+            .events
+            .iter()
+            .filter(|fields| fields.iter().any(|(field, _)| *field == "duration_us"))
+            .filter_map(|fields| {
+                fields
+                    .iter()
+                    .find(|(field, _)| *field == "operation")
+                    .map(|(_, value)| value.clone())
+            })
+            .collect()
+    }
+}
+
+struct FieldVisitor<'a>(&'a mut Vec<(&'static str, String)>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name(), value.to_owned()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.push((field.name(), format!("{value:?}")));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attributes: &span::Attributes<'_>) -> span::Id {
+        let mut fields = vec![];
+        attributes.record(&mut FieldVisitor(&mut fields));
+        let mut recorded = self.0.lock().unwrap();
+        recorded.spans.push(RecordedSpan {
+            metadata: attributes.metadata(),
+            fields,
+        });
+        span::Id::from_u64(recorded.spans.len() as u64)
+    }
+
+    fn record(&self, span: &span::Id, values: &span::Record<'_>) {
+        let mut recorded = self.0.lock().unwrap();
+        let span = &mut recorded.spans[span.into_u64() as usize - 1];
+        values.record(&mut FieldVisitor(&mut span.fields));
+    }
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = vec![];
+        event.record(&mut FieldVisitor(&mut fields));
+        self.0.lock().unwrap().events.push(fields);
+    }
+
+    fn enter(&self, span: &span::Id) {
+        self.0.lock().unwrap().stack.push(span.clone());
+    }
+
+    fn exit(&self, _span: &span::Id) {
+        self.0.lock().unwrap().stack.pop();
+    }
+
+    fn current_span(&self) -> tracing_core::span::Current {
+        let recorded = self.0.lock().unwrap();
+        match recorded.stack.last() {
+            Some(id) => {
+                let metadata = recorded.spans[id.into_u64() as usize - 1].metadata;
+                tracing_core::span::Current::new(id.clone(), metadata)
+            }
+            None => tracing_core::span::Current::none(),
+        }
+    }
+}
+
+/// Process and merge a commit and check that the spans carry the public
+/// fields of the operations.
+#[openmls_test::openmls_test]
+fn group_operations_are_traced() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+    let recorder = Recorder::default();
+
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let (mut alice_group, alice_signer, mut bob_group, _bob_signer, _alice_cwk, _bob_cwk) =
+            setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+
+        let (commit, _welcome, _group_info) = alice_group
+            .remove_members(alice_provider, &alice_signer, &[bob_group.own_leaf_index()])
+            .expect("Could not create commit.");
+        let commit: MlsMessageIn = commit.into();
+
+        let processed_message = bob_group
+            .process_message(bob_provider, commit.into_protocol_message().unwrap())
+            .expect("Could not process commit.");
+        let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+            processed_message.into_content()
+        else {
+            panic!("Expected a commit.");
+        };
+        bob_group
+            .merge_staged_commit(bob_provider, *staged_commit)
+            .expect("Could not merge commit.");
+    });
+
+    assert_eq!(
+        recorder.span_field("process_message", "sender_leaf_index"),
+        Some("0".to_owned())
+    );
+    assert_eq!(
+        recorder.span_field("process_message", "proposal_types"),
+        Some("[Remove]".to_owned())
+    );
+    assert_eq!(
+        recorder.span_field("process_message", "content_type"),
+        Some("Commit".to_owned())
+    );
+    assert_eq!(
+        recorder.span_field("stage_commit", "proposal_types"),
+        Some("[Remove]".to_owned())
+    );
+    assert_eq!(
+        recorder.span_field("merge_staged_commit", "self_removed"),
+        Some("true".to_owned())
+    );
+    assert_eq!(
+        recorder.span_field("merge_staged_commit", "new_epoch"),
+        Some("2".to_owned())
+    );
+    assert!(recorder
+        .span_field("new_from_welcome", "group_id")
+        .is_some_and(|group_id| !group_id.is_empty()));
+    assert!(!recorder.spans("join_group_from_welcome").is_empty());
+
+    let timed_operations = recorder.timed_operations();
+    for operation in [
+        "process_message",
+        "stage_commit",
+        "merge_staged_commit",
+        "new_from_welcome",
+    ] {
+        assert!(
+            timed_operations.iter().any(|timed| timed == operation),
+            "{operation} was not timed"
+        );
+    }
+}
+
+/// A provider whose storage calls are traced.
+#[derive(Default)]
+struct InstrumentedProvider {
+    crypto: RustCrypto,
+    storage: InstrumentedStorage<MemoryStorage>,
+}
+
+impl OpenMlsProvider for InstrumentedProvider {
+    type CryptoProvider = RustCrypto;
+    type RandProvider = RustCrypto;
+    type StorageProvider = InstrumentedStorage<MemoryStorage>;
+
+    fn crypto(&self) -> &Self::CryptoProvider {
+        &self.crypto
+    }
+    fn rand(&self) -> &Self::RandProvider {
+        &self.crypto
+    }
+    fn storage(&self) -> &Self::StorageProvider {
+        &self.storage
+    }
+}
+
+/// Create a group on an instrumented storage and check that the storage
+/// calls get their own spans.
+#[test]
+fn storage_calls_are_traced() {
+    let provider = InstrumentedProvider::default();
+    let recorder = Recorder::default();
+
+    tracing::subscriber::with_default(recorder.clone(), || {
+        setup_alice_group(
+            Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519,
+            &provider,
+        );
+    });
+
+    let storage_calls = recorder.storage_calls();
+    for method in ["write_mls_join_config", "write_tree", "tree"] {
+        assert!(
+            storage_calls.iter().any(|call| call == method),
+            "{method} was not traced"
+        );
+    }
+}
+
+/// Load a group from an instrumented asynchronous storage and check that
+/// the storage calls get their own spans.
+#[cfg(not(feature = "virtual-clients-draft"))]
+#[test]
+fn async_storage_calls_are_traced() {
+    use crate::{
+        group::{mls_group::tests_and_kats::utils::block_on, MlsGroup},
+        storage::InstrumentedAsyncStorage,
+    };
+
+    let provider = InstrumentedProvider::default();
+    let (group, ..) = setup_alice_group(
+        Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519,
+        &provider,
+    );
+    let storage = InstrumentedAsyncStorage::new(provider.storage.into_inner());
+    let recorder = Recorder::default();
+
+    let loaded_group = tracing::subscriber::with_default(recorder.clone(), || {
+        block_on(MlsGroup::load_async(&storage, group.group_id()))
+    })
+    .expect("Could not load group.")
+    .expect("Group not found.");
+    assert_eq!(loaded_group.epoch(), group.epoch());
+
+    let storage_calls = recorder.storage_calls();
+    for method in ["mls_group_join_config", "tree", "group_context"] {
+        assert!(
+            storage_calls.iter().any(|call| call == method),
+            "{method} was not traced"
+        );
+    }
+}
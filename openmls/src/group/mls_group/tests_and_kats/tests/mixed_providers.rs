@@ -0,0 +1,132 @@
+use rand::{rngs::StdRng, RngExt, SeedableRng};
+
+use crate::{
+    binary_tree::LeafNodeIndex,
+    group::MlsGroupCreateConfig,
+    test_utils::test_framework::{
+        mixed_provider::MixedProvider, noop_authentication_service, ActionType, CodecUse,
+        MlsGroupTestSetup,
+    },
+    treesync::LeafNodeParameters,
+};
+
+const NUMBER_OF_CLIENTS: usize = 12;
+const NUMBER_OF_EPOCHS: usize = 16;
+
+/// The environment variable that holds the hex-encoded seed of a failed run.
+const SEED_VARIABLE: &str = "OPENMLS_TEST_SEED";
+
+/// Read the seed from [`SEED_VARIABLE`] to replay a run, or draw a random one.
+fn test_seed() -> [u8; 32] {
+    match std::env::var(SEED_VARIABLE) {
+        Ok(seed) => hex::decode(seed.trim())
+            .ok()
+            .and_then(|seed| seed.try_into().ok())
+            .unwrap_or_else(|| panic!("{SEED_VARIABLE} must be 32 hex-encoded bytes")),
+        Err(_) => rand::rng().random(),
+    }
+}
+
+/// Run a random script of commits in a group whose members use different
+/// crypto providers and storage backends. After every epoch, all members must
+/// agree on the ratchet tree, the tree hash, the epoch authenticator and the
+/// exporter output.
+///
+/// The seed of the run is logged at the debug level and is part of the
+/// messages of failed commits, so that a failed run can be replayed with
+/// [`SEED_VARIABLE`].
+#[openmls_test::openmls_test]
+fn mixed_providers_agree() {
+    if !MixedProvider::supported_by_all_backends(ciphersuite) {
+        return;
+    }
+
+    let _ = pretty_env_logger::try_init();
+
+    let seed = test_seed();
+    let seed_assignment = format!("{SEED_VARIABLE}={}", hex::encode(seed));
+    log::debug!("{seed_assignment}");
+    let mut rng = StdRng::from_seed(seed);
+
+    let setup = MlsGroupTestSetup::with_providers(
+        MlsGroupCreateConfig::test_default(ciphersuite),
+        (0..NUMBER_OF_CLIENTS).map(MixedProvider::for_client),
+        CodecUse::SerializedMessages,
+    );
+    let group_id = setup
+        .create_random_group(3, ciphersuite, noop_authentication_service)
+        .expect("Error creating group.");
+    let mut groups = setup.groups.write().unwrap();
+    let group = groups.get_mut(&group_id).unwrap();
+    setup.check_group_states(group, noop_authentication_service);
+
+    // Removed clients still have the group in their storage and can't join
+    // again, so only clients that never were members are added.
+    let mut fresh_clients = setup
+        .clients
+        .read()
+        .unwrap()
+        .keys()
+        .filter(|id| group.members.iter().all(|(_, member_id)| member_id != *id))
+        .cloned()
+        .collect::<Vec<_>>();
+    fresh_clients.sort();
+
+    for _ in 0..NUMBER_OF_EPOCHS {
+        let (_, committer_id) = group.members[rng.random_range(0..group.members.len())].clone();
+        match rng.random_range(0..3) {
+            0 => {
+                log::debug!("Self-update by {committer_id:?}");
+                setup
+                    .self_update(
+                        ActionType::Commit,
+                        group,
+                        &committer_id,
+                        LeafNodeParameters::default(),
+                        &noop_authentication_service,
+                    )
+                    .unwrap_or_else(|e| panic!("Error updating with {seed_assignment}: {e:?}"));
+            }
+            1 if !fresh_clients.is_empty() => {
+                let number_of_adds = rng.random_range(1..=fresh_clients.len().min(2));
+                let new_members = fresh_clients.drain(..number_of_adds).collect::<Vec<_>>();
+                log::debug!("Add of {new_members:?} by {committer_id:?}");
+                setup
+                    .add_clients(
+                        ActionType::Commit,
+                        group,
+                        &committer_id,
+                        new_members,
+                        &noop_authentication_service,
+                    )
+                    .unwrap_or_else(|e| {
+                        panic!("Error adding members with {seed_assignment}: {e:?}")
+                    });
+            }
+            _ if group.members.len() > 2 => {
+                let targets = group
+                    .members
+                    .iter()
+                    .filter(|(_, id)| *id != committer_id)
+                    .map(|(index, _)| *index)
+                    .collect::<Vec<_>>();
+                let target = targets[rng.random_range(0..targets.len())];
+                log::debug!("Removal of leaf {target} by {committer_id:?}");
+                setup
+                    .remove_clients(
+                        ActionType::Commit,
+                        group,
+                        &committer_id,
+                        &[LeafNodeIndex::new(target as u32)],
+                        noop_authentication_service,
+                    )
+                    .unwrap_or_else(|e| {
+                        panic!("Error removing member with {seed_assignment}: {e:?}")
+                    });
+            }
+            _ => continue,
+        }
+
+        setup.check_group_states(group, noop_authentication_service);
+    }
+}
@@ -1,7 +1,19 @@
 //! Test and Known Answer Test (KAT) modules for the MLS group.
 
+#[cfg(not(feature = "virtual-clients-draft"))]
+mod async_signer;
+#[cfg(not(feature = "virtual-clients-draft"))]
+mod async_storage;
+mod branch;
 mod commit_builder_leaf_node_validation;
+mod credential_validator;
+#[cfg(feature = "libcrux-provider")]
+mod cross_provider;
 mod external_init;
+mod future_epoch_messages;
+#[cfg(feature = "tracing")]
+mod instrumentation;
+mod mixed_providers;
 mod mls_group;
 mod past_secrets;
 // Pulls in `openmls_sqlite_storage` and `openmls_libcrux_crypto`
@@ -9,4 +21,8 @@ mod past_secrets;
 #[cfg(all(feature = "sqlite-provider", feature = "libcrux-provider"))]
 mod past_secrets_storage_compatibility;
 mod proposals;
+mod reinit;
 mod secret_tree_persistence;
+#[cfg(all(feature = "sqlite-provider", not(feature = "0-8-1-storage-format")))]
+mod storage_migration_0_8_1;
+mod transactions;
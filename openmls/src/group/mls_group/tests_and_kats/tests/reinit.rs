@@ -0,0 +1,193 @@
+use crate::{
+    extensions::Extensions,
+    framing::{MlsMessageIn, ProcessedMessageContent},
+    group::{
+        errors::*,
+        mls_group::tests_and_kats::utils::{setup_alice_bob_group, setup_client},
+        GroupId,
+    },
+    key_packages::KeyPackageBundle,
+    messages::proposals::ReInitProposal,
+    schedule::psk::{PskBundle, ResumptionPskUsage},
+    treesync::LeafNodeParameters,
+    versions::ProtocolVersion,
+};
+
+/// Test the full ReInit flow: Bob proposes to reinitialize the group, Alice
+/// commits the proposal, both groups wait to be reinitialized, and Alice sets
+/// up the successor group, which Bob joins.
+#[openmls_test::openmls_test]
+fn reinit() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, mut bob_group, bob_signer, alice_cwk, bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+
+    let reinit_proposal = ReInitProposal::new(
+        GroupId::from_slice(b"successor group"),
+        ProtocolVersion::Mls10,
+        ciphersuite,
+        Extensions::empty(),
+    );
+
+    // Bob proposes to reinitialize the group
+    let (proposal, _) = bob_group
+        .propose_reinit(bob_provider, &bob_signer, reinit_proposal.clone())
+        .expect("Could not create ReInit proposal.");
+
+    let processed_message = alice_group
+        .process_message(alice_provider, proposal.into_protocol_message().unwrap())
+        .expect("Could not process proposal.");
+    let ProcessedMessageContent::ProposalMessage(queued_proposal) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a proposal.");
+    };
+    alice_group
+        .store_pending_proposal(alice_provider.storage(), *queued_proposal)
+        .unwrap();
+
+    // Alice commits to the ReInit proposal
+    let (commit, welcome, _group_info) = alice_group
+        .commit_to_pending_proposals(alice_provider, &alice_signer)
+        .expect("Could not commit to ReInit proposal.");
+    assert!(welcome.is_none());
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("Could not merge commit.");
+
+    let processed_message = bob_group
+        .process_message(bob_provider, commit.into_protocol_message().unwrap())
+        .expect("Could not process commit.");
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a staged commit.");
+    };
+    assert_eq!(staged_commit.reinit_proposal(), Some(&reinit_proposal));
+    bob_group
+        .merge_staged_commit(bob_provider, *staged_commit)
+        .expect("Could not merge commit.");
+
+    // Both groups now wait to be reinitialized and can't be used anymore
+    assert_eq!(alice_group.reinit_proposal(), Some(&reinit_proposal));
+    assert_eq!(bob_group.reinit_proposal(), Some(&reinit_proposal));
+    assert!(alice_group.is_active());
+    assert!(matches!(
+        alice_group.create_message(alice_provider, &alice_signer, b"hello"),
+        Err(CreateMessageError::GroupStateError(
+            MlsGroupStateError::ReInitPending
+        ))
+    ));
+    assert!(matches!(
+        bob_group.self_update(bob_provider, &bob_signer, LeafNodeParameters::default()),
+        Err(SelfUpdateError::GroupStateError(
+            MlsGroupStateError::ReInitPending
+        ))
+    ));
+
+    // The state survives a reload from storage
+    alice_group
+        .ensure_persistence(alice_provider.storage())
+        .expect("Alice group persistence check failed after merging ReInit commit");
+
+    // Alice sets up the successor group and invites Bob
+    let bob_key_package_bundle =
+        KeyPackageBundle::generate(bob_provider, &bob_signer, ciphersuite, bob_cwk);
+
+    let (mut new_alice_group, message_bundle) = alice_group
+        .reinit()
+        .expect("Group should wait to be reinitialized.")
+        .finish(
+            vec![bob_key_package_bundle.key_package().clone()],
+            |builder| builder,
+            alice_provider,
+            &alice_signer,
+            alice_cwk,
+        )
+        .expect("Could not create successor group.");
+
+    // The reinit PSK is never written to the storage.
+    let stored_psk: Option<PskBundle> = alice_provider
+        .storage()
+        .psk(&alice_group.resumption_psk(ResumptionPskUsage::Reinit))
+        .unwrap();
+    assert!(stored_psk.is_none());
+
+    new_alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("Could not merge commit.");
+
+    let (_commit, welcome, _group_info) = message_bundle.into_messages();
+    let welcome: MlsMessageIn = welcome.expect("Expected a Welcome message.").into();
+    let welcome = welcome.into_welcome().unwrap();
+
+    // Bob joins the successor group after verifying it
+    let new_bob_group = bob_group
+        .join_reinit(
+            bob_provider,
+            bob_group.configuration(),
+            welcome,
+            Some(new_alice_group.export_ratchet_tree().into()),
+        )
+        .expect("Could not verify successor group.")
+        .into_group(bob_provider)
+        .expect("Could not join successor group.");
+
+    assert_eq!(new_bob_group.group_id(), reinit_proposal.group_id());
+    assert_eq!(new_bob_group.ciphersuite(), reinit_proposal.ciphersuite());
+    assert_eq!(
+        new_alice_group
+            .export_secret(alice_provider.crypto(), "reinit", b"", 32)
+            .unwrap(),
+        new_bob_group
+            .export_secret(bob_provider.crypto(), "reinit", b"", 32)
+            .unwrap()
+    );
+
+    // The old groups can be deleted now
+    alice_group.delete(alice_provider.storage()).unwrap();
+    bob_group.delete(bob_provider.storage()).unwrap();
+}
+
+/// A Commit that contains a ReInit proposal must not contain any other
+/// proposals.
+#[openmls_test::openmls_test]
+fn reinit_with_other_proposals() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+    let charlie_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, _bob_group, _bob_signer, _alice_cwk, _bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+    let (_charlie_cwk, charlie_key_package_bundle, _charlie_signer, _charlie_pk) =
+        setup_client("Charlie", ciphersuite, charlie_provider);
+
+    let reinit_proposal = ReInitProposal::new(
+        GroupId::from_slice(b"successor group"),
+        ProtocolVersion::Mls10,
+        ciphersuite,
+        Extensions::empty(),
+    );
+
+    let result = alice_group
+        .commit_builder()
+        .propose_reinit(reinit_proposal)
+        .propose_adds(Some(charlie_key_package_bundle.key_package().clone()))
+        .load_psks(alice_provider.storage())
+        .unwrap()
+        .build(
+            alice_provider.rand(),
+            alice_provider.crypto(),
+            &alice_signer,
+            |_| true,
+        );
+
+    assert!(matches!(
+        result,
+        Err(CreateCommitError::ProposalValidationError(
+            ProposalValidationError::ReInitWithOtherProposals
+        ))
+    ));
+}
@@ -0,0 +1,129 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use crate::prelude::*;
+use crate::storage::migration::{migrate_group_from_0_8_1, migrate_key_package_from_0_8_1};
+
+use openmls_rust_crypto::RustCrypto as CryptoProvider;
+use openmls_sqlite_storage::{Codec, Connection, SqliteStorageProvider};
+use openmls_traits::storage::StorageProvider;
+use serde::Serialize;
+
+const SQL_STATEMENTS_PATH: &str =
+    "src/group/mls_group/tests_and_kats/tests/dump_0_8_1_postcard.sql";
+const TEST_GROUP_ID: &[u8] = b"test_group_id";
+
+/// The extension and credential types in the capabilities of the stored leaf
+/// nodes and key package. Their positions in the enums differ from their
+/// storage tags, so they are only read correctly in the 0.8.1 format.
+const UNKNOWN_EXTENSION_TYPE: ExtensionType = ExtensionType::Unknown(0xff00);
+const OTHER_CREDENTIAL_TYPE: CredentialType = CredentialType::Other(0xff02);
+
+/// Test the migration of a storage written by OpenMLS 0.8.1 with a codec that
+/// is not self-describing.
+/// - Loads a Sqlite dump written by `openmls = 0.8.1` with a `postcard` codec,
+///   containing a group with a queued update proposal and a key package
+/// - Checks that the enums are read from the wrong tags without the migration
+/// - Migrates the group and the key package to the current format
+/// - Checks that the group can be loaded and that the enums in the
+///   capabilities of its leaf nodes were read from their 0.8.1 tags
+/// - Checks that migrating again leaves the values unchanged
+#[test]
+fn migrate_from_0_8_1_postcard() {
+    let storage = load_dump();
+
+    let group_ids: Vec<GroupId> = storage.group_ids().unwrap();
+    assert_eq!(vec![GroupId::from_slice(TEST_GROUP_ID)], group_ids);
+    let key_package_refs: Vec<KeyPackageRef> = storage.key_package_refs().unwrap();
+    assert_eq!(1, key_package_refs.len());
+
+    // Read in the current format, the stored tags belong to other variants.
+    let own_leaf_nodes: Vec<LeafNode> = storage.own_leaf_nodes(&group_ids[0]).unwrap();
+    let capabilities = own_leaf_nodes[0].capabilities();
+    assert!(!capabilities.extensions().contains(&UNKNOWN_EXTENSION_TYPE));
+    assert!(!capabilities.credentials().contains(&OTHER_CREDENTIAL_TYPE));
+
+    for group_id in &group_ids {
+        assert!(migrate_group_from_0_8_1(&storage, group_id).unwrap());
+    }
+    for key_package_ref in &key_package_refs {
+        assert!(migrate_key_package_from_0_8_1(&storage, key_package_ref).unwrap());
+    }
+
+    let check_capabilities = |capabilities: &Capabilities| {
+        assert!(capabilities.extensions().contains(&UNKNOWN_EXTENSION_TYPE));
+        assert!(capabilities.credentials().contains(&OTHER_CREDENTIAL_TYPE));
+    };
+
+    let group = MlsGroup::load(&storage, &group_ids[0])
+        .expect("error loading group from storage")
+        .expect("no group available for this group id");
+    check_capabilities(group.own_leaf_node().unwrap().capabilities());
+    let proposals: Vec<_> = group.pending_proposals().collect();
+    assert_eq!(1, proposals.len());
+    let Proposal::Update(update) = proposals[0].proposal() else {
+        panic!("expected an update proposal");
+    };
+    check_capabilities(update.leaf_node().capabilities());
+
+    let key_package_bundle: KeyPackageBundle = storage
+        .key_package(&key_package_refs[0])
+        .unwrap()
+        .expect("no key package available for this reference");
+    check_capabilities(key_package_bundle.key_package().leaf_node().capabilities());
+
+    // The migration was recorded, so running it again does not read the
+    // migrated values in the 0.8.1 format.
+    assert!(!migrate_group_from_0_8_1(&storage, &group_ids[0]).unwrap());
+    assert!(!migrate_key_package_from_0_8_1(&storage, &key_package_refs[0]).unwrap());
+    let reloaded = MlsGroup::load(&storage, &group_ids[0]).unwrap().unwrap();
+    assert_eq!(group, reloaded);
+
+    // The crypto provider is only needed to check the key package reference.
+    let crypto = CryptoProvider::default();
+    assert_eq!(
+        key_package_refs[0],
+        key_package_bundle.key_package().hash_ref(&crypto).unwrap()
+    );
+}
+
+/// The join configuration has no format version in the 0.8.1 format. It is
+/// read without the migration.
+#[test]
+fn join_config_from_0_8_1_postcard() {
+    let storage = load_dump();
+
+    let join_config: MlsGroupJoinConfig = storage
+        .mls_group_join_config(&GroupId::from_slice(TEST_GROUP_ID))
+        .unwrap()
+        .expect("no join configuration available for this group id");
+    assert_eq!(join_config, MlsGroupJoinConfig::default());
+}
+
+/// Loads the Sqlite dump written by `openmls = 0.8.1`.
+fn load_dump() -> SqliteStorageProvider<PostcardCodec, Connection> {
+    let sql_statements = std::fs::read_to_string(SQL_STATEMENTS_PATH).unwrap();
+    let conn = Connection::open_in_memory().expect("error opening database connection");
+    conn.execute_batch(&sql_statements)
+        .expect("error executing sqlite statements");
+    let mut storage = SqliteStorageProvider::<PostcardCodec, Connection>::new(conn);
+    storage
+        .run_migrations()
+        .expect("error running the schema migrations");
+    storage
+}
+
+/// A codec that is not self-describing.
+#[derive(Default)]
+struct PostcardCodec;
+
+impl Codec for PostcardCodec {
+    type Error = postcard::Error;
+
+    fn to_vec<T: Serialize>(value: &T) -> Result<Vec<u8>, Self::Error> {
+        postcard::to_allocvec(value)
+    }
+
+    fn from_slice<T: serde::de::DeserializeOwned>(slice: &[u8]) -> Result<T, Self::Error> {
+        postcard::from_bytes(slice)
+    }
+}
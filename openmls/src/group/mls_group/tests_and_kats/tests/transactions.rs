@@ -0,0 +1,138 @@
+use openmls_memory_storage::MemoryStorage;
+
+use crate::{
+    framing::{MlsMessageIn, ProcessedMessageContent},
+    group::{mls_group::tests_and_kats::utils::setup_alice_bob_group, MlsGroup},
+    treesync::LeafNodeParameters,
+};
+
+/// A storage that can let writes fail midway through an operation.
+pub(super) trait InjectWriteFailure {
+    /// Lets the write after the next `writes` writes fail.
+    fn fail_after_writes(&self, writes: usize);
+
+    /// Lets all following writes succeed.
+    fn allow_writes(&self);
+}
+
+impl InjectWriteFailure for MemoryStorage {
+    fn fail_after_writes(&self, writes: usize) {
+        MemoryStorage::fail_after_writes(self, writes);
+    }
+
+    fn allow_writes(&self) {
+        MemoryStorage::fail_after_writes(self, usize::MAX);
+    }
+}
+
+/// The SQLite storage counts the writes to its tables with temporary
+/// triggers, which abort the write once the budget of writes is used up.
+#[cfg(all(feature = "sqlite-provider", not(target_arch = "wasm32")))]
+impl<C: openmls_sqlite_storage::Codec> InjectWriteFailure
+    for openmls_sqlite_storage::SqliteStorageProvider<C, openmls_sqlite_storage::Connection>
+{
+    fn fail_after_writes(&self, writes: usize) {
+        let connection = self.connection();
+        connection
+            .execute_batch(
+                "CREATE TEMP TABLE IF NOT EXISTS write_budget (remaining INTEGER NOT NULL);
+                DELETE FROM write_budget;",
+            )
+            .unwrap();
+        connection
+            .execute(
+                "INSERT INTO write_budget (remaining) VALUES (?1)",
+                [writes as i64],
+            )
+            .unwrap();
+
+        let tables = connection
+            .prepare(
+                "SELECT name FROM sqlite_master
+                WHERE type = 'table' AND name LIKE 'openmls_%'
+                AND name != 'openmls_sqlite_storage_migrations'",
+            )
+            .unwrap()
+            .query_map([], |row| row.get::<_, String>(0))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        for table in tables {
+            for operation in ["INSERT", "UPDATE", "DELETE"] {
+                connection
+                    .execute_batch(&format!(
+                        "CREATE TEMP TRIGGER IF NOT EXISTS fail_{operation}_{table}
+                        BEFORE {operation} ON main.{table}
+                        BEGIN
+                            SELECT RAISE(ABORT, 'injected failure')
+                            WHERE (SELECT remaining FROM write_budget) = 0;
+                            UPDATE write_budget SET remaining = remaining - 1;
+                        END;"
+                    ))
+                    .unwrap();
+            }
+        }
+    }
+
+    fn allow_writes(&self) {
+        // Without a budget, the triggers never abort a write.
+        self.connection()
+            .execute_batch("DELETE FROM write_budget;")
+            .unwrap();
+    }
+}
+
+/// Let each write of processing and merging a commit fail in turn, and check
+/// that the group can always be reloaded, either unchanged in the old epoch
+/// or in the new epoch.
+#[openmls_test::openmls_test]
+fn merge_commit_is_atomic() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, bob_group, _bob_signer, _alice_cwk, _bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+
+    let commit_message_bundle = alice_group
+        .self_update(alice_provider, &alice_signer, LeafNodeParameters::default())
+        .expect("Could not create commit.");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("Could not merge commit.");
+    let commit: MlsMessageIn = commit_message_bundle.commit().clone().into();
+    let commit = commit.into_protocol_message().unwrap();
+
+    for failing_write in 0.. {
+        let mut group = MlsGroup::load(bob_provider.storage(), bob_group.group_id())
+            .expect("Could not load group.")
+            .expect("Group not found in storage.");
+        InjectWriteFailure::fail_after_writes(bob_provider.storage(), failing_write);
+
+        let merged = group
+            .process_message(bob_provider, commit.clone())
+            .map_err(|_| ())
+            .and_then(|processed_message| {
+                let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+                    processed_message.into_content()
+                else {
+                    panic!("Expected a staged commit.");
+                };
+                group
+                    .merge_staged_commit(bob_provider, *staged_commit)
+                    .map_err(|_| ())
+            });
+
+        let reloaded_group = MlsGroup::load(bob_provider.storage(), bob_group.group_id())
+            .expect("Could not reload group.")
+            .expect("Group not found in storage.");
+        if merged.is_ok() {
+            // All writes succeeded, so some of the earlier runs must have
+            // failed.
+            assert!(failing_write > 0);
+            assert_eq!(reloaded_group.epoch(), alice_group.epoch());
+            assert_eq!(reloaded_group, group);
+            break;
+        }
+        assert_eq!(reloaded_group, bob_group);
+    }
+}
@@ -202,3 +202,29 @@ pub(crate) fn setup_alice_bob_group<Provider: OpenMlsProvider>(
         bob_credential,
     )
 }
+
+#[cfg(not(feature = "virtual-clients-draft"))]
+struct ThreadWaker(std::thread::Thread);
+
+#[cfg(not(feature = "virtual-clients-draft"))]
+impl std::task::Wake for ThreadWaker {
+    fn wake(self: std::sync::Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Drive `future` to completion on the current thread.
+#[cfg(not(feature = "virtual-clients-draft"))]
+pub(crate) fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    use std::task::{Context, Poll, Waker};
+
+    let mut future = std::pin::pin!(future);
+    let waker = Waker::from(std::sync::Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
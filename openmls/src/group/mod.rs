@@ -23,6 +23,7 @@ pub(crate) mod public_group;
 // Public
 pub use errors::*;
 pub use group_context::GroupContext;
+pub use mls_group::branch::*;
 pub use mls_group::builder::*;
 pub use mls_group::commit_builder::*;
 pub use mls_group::config::*;
@@ -31,10 +32,14 @@ pub use mls_group::membership::*;
 #[cfg(feature = "extensions-draft")]
 pub use mls_group::processing::{AppDataDictionaryUpdater, AppDataUpdates};
 pub use mls_group::proposal_store::*;
+pub use mls_group::reinit::*;
 pub use mls_group::staged_commit::StagedCommit;
 pub use mls_group::{Member, *};
 pub use public_group::*;
 
+#[cfg(feature = "fork-resolution")]
+pub use fork_resolution::*;
+
 // Private
 #[cfg(feature = "fork-resolution")]
 mod fork_resolution;
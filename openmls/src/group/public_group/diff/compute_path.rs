@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use openmls_traits::{crypto::OpenMlsCrypto, random::OpenMlsRand, signatures::Signer};
+use openmls_traits::{crypto::OpenMlsCrypto, random::OpenMlsRand, signatures::AsyncSigner};
 use serde::{Deserialize, Serialize};
 use tls_codec::Serialize as _;
 
@@ -43,7 +43,7 @@ pub(crate) struct PathComputationResult {
 
 impl PublicGroupDiff<'_> {
     #[allow(clippy::too_many_arguments)]
-    pub(crate) fn compute_path(
+    pub(crate) async fn compute_path(
         &mut self,
         rand: &impl OpenMlsRand,
         crypto: &impl OpenMlsCrypto,
@@ -51,7 +51,7 @@ impl PublicGroupDiff<'_> {
         exclusion_list: HashSet<&LeafNodeIndex>,
         commit_type: &CommitType,
         leaf_node_params: &LeafNodeParameters,
-        signer: &impl Signer,
+        signer: &impl AsyncSigner,
         gc_extensions: Option<Extensions<GroupContext>>,
         own_update_override: Option<OwnUpdatePathOverride>,
     ) -> Result<PathComputationResult, CreateCommitError> {
@@ -106,17 +106,20 @@ impl PublicGroupDiff<'_> {
 
         // Derive and apply an update path based on the previously
         // generated new leaf.
-        let (plain_path, new_keypairs, commit_secret) = self.diff.apply_own_update_path(
-            rand,
-            crypto,
-            signer,
-            ciphersuite,
-            commit_type,
-            self.group_context().group_id().clone(),
-            leaf_index,
-            leaf_node_params,
-            own_update_override,
-        )?;
+        let (plain_path, new_keypairs, commit_secret) = self
+            .diff
+            .apply_own_update_path(
+                rand,
+                crypto,
+                signer,
+                ciphersuite,
+                commit_type,
+                self.group_context().group_id().clone(),
+                leaf_index,
+                leaf_node_params,
+                own_update_override,
+            )
+            .await?;
 
         // After we've processed the path, we can update the group context s.t.
         // the updated group context is used for path secret encryption. Note
@@ -32,6 +32,7 @@ use crate::{
         LeafNodeIndex,
     },
     ciphersuite::{hash_ref::ProposalRef, signable::Verifiable},
+    credentials::{errors::CredentialValidationError, CredentialValidator},
     error::LibraryError,
     extensions::RequiredCapabilitiesExtension,
     framing::{InterimTranscriptHashInput, Sender},
@@ -42,7 +43,7 @@ use crate::{
         ConfirmationTag, PathSecret,
     },
     schedule::CommitSecret,
-    storage::PublicStorageProvider,
+    storage::{AsyncStorageProvider, PublicStorageProvider},
     treesync::{
         errors::{DerivePathError, TreeSyncFromNodesError},
         node::{
@@ -383,6 +384,32 @@ impl PublicGroup {
         self.treesync().full_leaf_members()
     }
 
+    /// Validates the credentials of all members and external senders of this
+    /// [`PublicGroup`] using the given [`CredentialValidator`].
+    pub(crate) fn validate_credentials(
+        &self,
+        credential_validator: &(impl CredentialValidator + ?Sized),
+    ) -> Result<(), CredentialValidationError> {
+        self.treesync()
+            .full_leaves()
+            .try_for_each(|(_, leaf_node)| {
+                credential_validator
+                    .validate_credential(leaf_node.credential(), leaf_node.signature_key())
+            })?;
+
+        self.group_context()
+            .extensions()
+            .external_senders()
+            .into_iter()
+            .flatten()
+            .try_for_each(|external_sender| {
+                credential_validator.validate_credential(
+                    external_sender.credential(),
+                    external_sender.signature_key(),
+                )
+            })
+    }
+
     /// Export the nodes of the public tree.
     pub fn export_ratchet_tree(&self) -> RatchetTree {
         self.treesync().export_ratchet_tree()
@@ -496,6 +523,31 @@ impl PublicGroup {
         Ok(())
     }
 
+    /// Stores the [`PublicGroup`] to an [`AsyncStorageProvider`]. Called when
+    /// an [`MlsGroup`] is stored asynchronously.
+    ///
+    /// [`MlsGroup`]: crate::group::MlsGroup
+    pub(crate) async fn store_async<Storage: AsyncStorageProvider>(
+        &self,
+        storage: &Storage,
+    ) -> Result<(), Storage::Error> {
+        let group_id = self.group_context.group_id();
+        storage.write_tree(group_id, self.treesync()).await?;
+        storage
+            .write_confirmation_tag(group_id, self.confirmation_tag())
+            .await?;
+        storage
+            .write_context(group_id, self.group_context())
+            .await?;
+        storage
+            .write_interim_transcript_hash(
+                group_id,
+                &InterimTranscriptHash(self.interim_transcript_hash.clone()),
+            )
+            .await?;
+        Ok(())
+    }
+
     /// Deletes the [`PublicGroup`] from storage.
     pub fn delete<Storage: PublicStorageProvider>(
         storage: &Storage,
@@ -539,6 +591,38 @@ impl PublicGroup {
         Ok(build())
     }
 
+    /// Loads the [`PublicGroup`] corresponding to a [`GroupId`] from an
+    /// [`AsyncStorageProvider`].
+    pub async fn load_async<Storage: AsyncStorageProvider>(
+        storage: &Storage,
+        group_id: &GroupId,
+    ) -> Result<Option<Self>, Storage::Error> {
+        let treesync = storage.tree(group_id).await?;
+        let proposals: Vec<(ProposalRef, QueuedProposal)> =
+            storage.queued_proposals(group_id).await?;
+        let group_context = storage.group_context(group_id).await?;
+        let interim_transcript_hash: Option<InterimTranscriptHash> =
+            storage.interim_transcript_hash(group_id).await?;
+        let confirmation_tag = storage.confirmation_tag(group_id).await?;
+        let mut proposal_store = ProposalStore::new();
+
+        for (_ref, proposal) in proposals {
+            proposal_store.add(proposal);
+        }
+
+        let build = || -> Option<Self> {
+            Some(Self {
+                treesync: treesync?,
+                proposal_store,
+                group_context: group_context?,
+                interim_transcript_hash: interim_transcript_hash?.0,
+                confirmation_tag: confirmation_tag?,
+            })
+        };
+
+        Ok(build())
+    }
+
     /// Returns a reference to the [`ProposalStore`].
     pub(crate) fn proposal_store(&self) -> &ProposalStore {
         &self.proposal_store
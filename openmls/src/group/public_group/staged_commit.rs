@@ -203,6 +203,8 @@ impl PublicGroup {
                 self.validate_update_proposals(&proposal_queue, *committer_leaf_index)?;
 
                 self.validate_no_external_init_proposals(&proposal_queue)?;
+
+                self.validate_reinit_proposals(&proposal_queue)?;
             }
             Sender::External(_) => {
                 // A commit cannot be issued by a pre-configured sender.
@@ -18,7 +18,7 @@ use crate::{
         errors::{ExternalCommitValidationError, ProposalValidationError, ValidationError},
         past_secrets::MessageSecretsStore,
         proposal_store::ProposalQueue,
-        GroupContextExtensionsProposalValidationError, Member,
+        GroupContextExtensionsProposalValidationError, GroupEpoch, Member,
     },
     messages::{
         proposals::{Proposal, ProposalOrRefType, ProposalType},
@@ -511,17 +511,54 @@ impl PublicGroup {
     ///
     /// * ValSem401: The nonce of a PreSharedKeyID must have length KDF.Nh.
     /// * ValSem402: PSK in proposal must be of type Resumption (with usage Application) or External.
+    ///   Resumption PSKs with usage ReInit or Branch are only allowed in the
+    ///   first Commit of a group, which sets up the successor of a group.
     pub(crate) fn validate_pre_shared_key_proposals(
         &self,
         proposal_queue: &ProposalQueue,
     ) -> Result<(), ProposalValidationError> {
+        let is_first_commit = self.group_context().epoch() == GroupEpoch::from(0);
         for proposal in proposal_queue.psk_proposals() {
             let psk_id = proposal.psk_proposal().clone().into_psk_id();
 
             // ValSem401
             // ValSem402
             // https://validation.openmls.tech/#valn0803
-            psk_id.validate_in_proposal(self.ciphersuite())?;
+            psk_id.validate_in_proposal(self.ciphersuite(), is_first_commit)?;
+        }
+
+        Ok(())
+    }
+
+    /// Validate ReInit proposals.
+    ///
+    /// This method implements the following checks:
+    ///
+    /// * A Commit contains at most one ReInit proposal.
+    /// * A Commit that contains a ReInit proposal contains no other proposals.
+    /// * The version of the ReInit proposal is not lower than the version of
+    ///   the group.
+    pub(crate) fn validate_reinit_proposals(
+        &self,
+        proposal_queue: &ProposalQueue,
+    ) -> Result<(), ProposalValidationError> {
+        let mut reinit_proposals = proposal_queue.filtered_by_type(ProposalType::Reinit);
+        let Some(reinit_proposal) = reinit_proposals.next() else {
+            return Ok(());
+        };
+
+        if reinit_proposals.next().is_some() {
+            return Err(ProposalValidationError::MultipleReInitProposals);
+        }
+
+        if proposal_queue.queued_proposals().count() > 1 {
+            return Err(ProposalValidationError::ReInitWithOtherProposals);
+        }
+
+        if let Proposal::ReInit(reinit_proposal) = reinit_proposal.proposal() {
+            if reinit_proposal.version() < self.version() {
+                return Err(ProposalValidationError::ReInitVersionDowngrade);
+            }
         }
 
         Ok(())
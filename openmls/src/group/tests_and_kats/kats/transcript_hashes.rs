@@ -219,8 +219,14 @@ pub fn generate_test_vector(ciphersuite: Ciphersuite) -> TranscriptTestVector {
             credential_with_key_and_signer.signer
         };
 
-        AuthenticatedContent::commit(framing_parameters, sender, commit, &group_context, &signer)
-            .unwrap()
+        crate::utils::resolve_ready(AuthenticatedContent::commit(
+            framing_parameters,
+            sender,
+            commit,
+            &group_context,
+            &signer,
+        ))
+        .unwrap()
     };
 
     // Now, calculate `confirmed_transcript_hash_after` ...
@@ -65,7 +65,8 @@ fn padding() {
                 .expect("An unexpected error occurred.");
             // Set the padding size
             let mut new_config = group_state.configuration().clone();
-            new_config.padding_size = padding_size;
+            new_config.padding_policy =
+                PaddingPolicy::uniform(PaddingStrategy::Fixed(padding_size));
             group_state
                 .set_configuration(provider.storage(), &new_config)
                 .unwrap();
@@ -95,6 +96,86 @@ fn padding() {
             }
         }
     }
+
+    // Application messages use the application strategy of the padding policy.
+    for group_state in alice.group_states.borrow_mut().values_mut() {
+        let credential = alice
+            .credentials
+            .get(&group_state.ciphersuite())
+            .expect("An unexpected error occurred.");
+        let mut new_config = group_state.configuration().clone();
+        new_config.padding_policy =
+            PaddingPolicy::new(PaddingStrategy::Fixed(0), PaddingStrategy::PowerOfTwo);
+        group_state
+            .set_configuration(provider.storage(), &new_config)
+            .unwrap();
+        for _ in 0..10 {
+            let message = randombytes(random_usize() % 1000);
+            let application_message = group_state
+                .create_message(provider, &credential.signer, &message)
+                .unwrap();
+            let private_message = match application_message.body() {
+                MlsMessageBodyOut::PrivateMessage(pm) => pm,
+                _ => panic!("Unexpected match."),
+            };
+            let length = private_message.ciphertext().len();
+            assert!(
+                length.is_power_of_two(),
+                "Error: message length {length} is not a power of two"
+            );
+        }
+    }
+}
+
+/// A custom padding callback isn't persisted. After loading the group, messages
+/// can't be encrypted until the callback is set again.
+#[openmls_test::openmls_test]
+fn custom_padding_after_load() {
+    let provider = &Provider::default();
+    let (mut group, _, signer, _) =
+        crate::group::mls_group::tests_and_kats::utils::setup_alice_group(ciphersuite, provider);
+
+    let mut config = group.configuration().clone();
+    config.padding_policy =
+        PaddingPolicy::uniform(PaddingStrategy::Custom(PaddingCallback::new(|length| {
+            length.next_multiple_of(100)
+        })));
+    group
+        .set_configuration(provider.storage(), &config)
+        .unwrap();
+    let message = group.create_message(provider, &signer, b"hello").unwrap();
+    let MlsMessageBodyOut::PrivateMessage(private_message) = message.body() else {
+        panic!("Unexpected match.");
+    };
+    assert_eq!(private_message.ciphertext().len() % 100, 0);
+
+    let mut group = MlsGroup::load(provider.storage(), group.group_id())
+        .unwrap()
+        .unwrap();
+    let PaddingStrategy::Custom(callback) = group.configuration().padding_policy().application()
+    else {
+        panic!("Expected a custom padding strategy.");
+    };
+    assert!(!callback.is_set());
+    let error = group
+        .create_message(provider, &signer, b"hello")
+        .unwrap_err();
+    #[cfg(not(feature = "virtual-clients-draft"))]
+    assert_eq!(error, CreateMessageError::MissingPaddingCallback);
+    #[cfg(feature = "virtual-clients-draft")]
+    assert_eq!(
+        error,
+        CreateMessageError::MessageEncryptionError(MessageEncryptionError::MissingPaddingCallback)
+    );
+
+    group
+        .set_configuration(provider.storage(), &config)
+        .unwrap();
+    let message = group.create_message(provider, &signer, b"hello").unwrap();
+    let MlsMessageBodyOut::PrivateMessage(private_message) = message.body() else {
+        panic!("Unexpected match.");
+    };
+    assert_eq!(private_message.ciphertext().len() % 100, 0);
 }
 
 /// Check that PrivateMessageContent's padding field is verified to be all-zero.
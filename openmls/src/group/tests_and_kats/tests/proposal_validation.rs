@@ -1944,12 +1944,16 @@ fn test_valsem110() {
 
     // We have to store the keypair with the proper label s.t. Bob can actually
     // process the commit.
-    let leaf_keypair = alice_group
-        .read_epoch_keypairs(alice_provider.storage())
-        .unwrap()
-        .into_iter()
-        .find(|keypair| keypair.public_key() == &alice_encryption_key)
-        .unwrap();
+    let leaf_keypair = crate::utils::resolve_ready(async {
+        alice_group
+            .read_epoch_keypairs(alice_provider.storage())
+            .await
+            .map_err(|_| crate::error::LibraryError::custom("Could not read epoch keypairs"))
+    })
+    .unwrap()
+    .into_iter()
+    .find(|keypair| keypair.public_key() == &alice_encryption_key)
+    .unwrap();
     leaf_keypair.write(alice_provider.storage()).unwrap();
 
     // Have bob process the resulting plaintext
@@ -13,7 +13,7 @@ use tls_codec::Serialize;
 
 use crate::{
     ciphersuite::signable::Signable, credentials::*, framing::*, group::*, key_packages::*,
-    messages::ConfirmationTag, test_utils::*, *,
+    messages::ConfirmationTag, test_utils::*, treesync::node::leaf_node::Capabilities, *,
 };
 
 use self::storage::OpenMlsProvider;
@@ -127,6 +127,13 @@ pub(crate) fn setup(
             .ciphersuite(group_config.ciphersuite)
             .use_ratchet_tree_extension(group_config.use_ratchet_tree_extension)
             .with_wire_format_policy(PURE_PLAINTEXT_WIRE_FORMAT_POLICY)
+            // The setup also uses ciphersuites that aren't advertised by
+            // default.
+            .with_capabilities(
+                Capabilities::builder()
+                    .ciphersuites(vec![group_config.ciphersuite])
+                    .build(),
+            )
             .build(
                 provider,
                 &credential_with_key_and_signer.signer,
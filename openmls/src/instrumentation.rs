@@ -0,0 +1,101 @@
+//! Instrumentation of group operations with [`tracing`].
+//!
+//! With the `tracing` feature enabled, the group operations that process,
+//! stage and merge messages and join groups are wrapped in spans. The spans
+//! and events only carry public information, such as the group id, the epoch,
+//! the sender's leaf index, the proposal types and the duration of the
+//! operation. Key material and message content are never recorded.
+//!
+//! Operations that write to the storage more than once, e.g. merging a
+//! commit, run in a `storage_transaction` span that covers all of their
+//! storage calls. To get a span per storage call, wrap the storage provider in
+//! an [`InstrumentedStorage`] or, for asynchronous storage, an
+//! [`InstrumentedAsyncStorage`]. Their `storage_call` spans record the name of
+//! the called method and the error of a failing call.
+//!
+//! [`InstrumentedStorage`]: crate::storage::InstrumentedStorage
+//! [`InstrumentedAsyncStorage`]: crate::storage::InstrumentedAsyncStorage
+
+use std::fmt;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+use crate::{
+    framing::{ProtocolMessage, Sender},
+    group::{GroupId, QueuedProposal},
+};
+
+/// Formats a [`GroupId`] as lowercase hex for span fields.
+pub(crate) struct HexGroupId<'a>(pub(crate) &'a GroupId);
+
+impl fmt::Display for HexGroupId<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0.as_slice() {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Emits an event with the duration of an operation when it is dropped, i.e.
+/// when the operation returns, no matter if it succeeded.
+pub(crate) struct OperationTimer {
+    operation: &'static str,
+    start: Instant,
+}
+
+impl OperationTimer {
+    pub(crate) fn start(operation: &'static str) -> Self {
+        Self {
+            operation,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for OperationTimer {
+    fn drop(&mut self) {
+        let duration_us = u64::try_from(self.start.elapsed().as_micros()).unwrap_or(u64::MAX);
+        tracing::debug!(
+            operation = self.operation,
+            duration_us,
+            "{} finished",
+            self.operation
+        );
+    }
+}
+
+/// Records the public header fields of an incoming message in the current
+/// span.
+pub(crate) fn record_message(message: &ProtocolMessage) {
+    let span = tracing::Span::current();
+    span.record("message_epoch", message.epoch().as_u64());
+    span.record("wire_format", tracing::field::debug(message.wire_format()));
+    span.record(
+        "content_type",
+        tracing::field::debug(message.content_type()),
+    );
+}
+
+/// Records the sender of a message in the current span. Members are recorded
+/// by their leaf index, all other senders by their type.
+pub(crate) fn record_sender(sender: &Sender) {
+    let span = tracing::Span::current();
+    match sender {
+        Sender::Member(leaf_index) => span.record("sender_leaf_index", leaf_index.u32()),
+        Sender::External(index) => span.record("sender_external_index", index.index() as u64),
+        Sender::NewMemberProposal => span.record("sender_type", "new_member_proposal"),
+        Sender::NewMemberCommit => span.record("sender_type", "new_member_commit"),
+    };
+}
+
+/// Records the types of the given proposals in the current span.
+pub(crate) fn record_proposal_types<'a>(proposals: impl Iterator<Item = &'a QueuedProposal>) {
+    let proposal_types: Vec<_> = proposals
+        .map(|proposal| proposal.proposal().proposal_type())
+        .collect();
+    tracing::Span::current().record("proposal_types", tracing::field::debug(proposal_types));
+}
@@ -123,6 +123,26 @@ impl KeyPackageIn {
         }
     }
 
+    /// Returns the unverified [`Ciphersuite`].
+    pub fn ciphersuite(&self) -> Ciphersuite {
+        self.payload.ciphersuite
+    }
+
+    /// Returns a reference to the unverified [`InitKey`].
+    pub fn hpke_init_key(&self) -> &InitKey {
+        &self.payload.init_key
+    }
+
+    /// Returns a reference to the unverified [`LeafNodeIn`].
+    pub fn leaf_node(&self) -> &LeafNodeIn {
+        &self.payload.leaf_node
+    }
+
+    /// Returns a reference to the unverified [`Extensions`].
+    pub fn extensions(&self) -> &Extensions<AnyObject> {
+        &self.payload.extensions
+    }
+
     /// Verify that this key package is valid:
     /// * verify that the signature on this key package is valid
     /// * verify that the signature on the leaf node is valid
@@ -108,7 +108,10 @@ use crate::{
     versions::ProtocolVersion,
 };
 use openmls_traits::{
-    crypto::OpenMlsCrypto, signatures::Signer, storage::StorageProvider, types::Ciphersuite,
+    crypto::OpenMlsCrypto,
+    signatures::{AsyncSigner, Signer},
+    storage::StorageProvider,
+    types::Ciphersuite,
 };
 use serde::{Deserialize, Serialize};
 use tls_codec::{
@@ -285,10 +288,10 @@ impl KeyPackage {
     }
 
     /// Create a new key package for the given `ciphersuite` and `identity`.
-    pub(crate) fn create(
+    pub(crate) async fn create(
         ciphersuite: Ciphersuite,
         provider: &impl OpenMlsProvider,
-        signer: &impl Signer,
+        signer: &impl AsyncSigner,
         credential_with_key: CredentialWithKey,
         extensions: Extensions<KeyPackage>,
         leaf_node_params: KeyPackageLeafNodeParams,
@@ -314,7 +317,8 @@ impl KeyPackage {
             extensions,
             leaf_node_params,
             init_key.public.into(),
-        )?;
+        )
+        .await?;
 
         Ok(KeyPackageCreationResult {
             key_package,
@@ -332,10 +336,10 @@ impl KeyPackage {
     /// encryption key ([`HpkeKeyPair`]) of the leaf node.
     ///
     /// The caller is responsible for storing the new values.
-    fn new_from_keys(
+    async fn new_from_keys(
         ciphersuite: Ciphersuite,
         provider: &impl OpenMlsProvider,
-        signer: &impl Signer,
+        signer: &impl AsyncSigner,
         credential_with_key: CredentialWithKey,
         extensions: Extensions<KeyPackage>,
         leaf_node_params: KeyPackageLeafNodeParams,
@@ -360,7 +364,7 @@ impl KeyPackage {
         };
 
         let (leaf_node, encryption_key_pair) =
-            LeafNode::new(provider, signer, new_leaf_node_params)?;
+            LeafNode::new(provider, signer, new_leaf_node_params).await?;
 
         let key_package_tbs = KeyPackageTbs {
             protocol_version: ProtocolVersion::default(),
@@ -370,7 +374,7 @@ impl KeyPackage {
             extensions,
         };
 
-        let key_package = key_package_tbs.sign(signer)?;
+        let key_package = sign_async(key_package_tbs, signer).await?;
 
         Ok((key_package, encryption_key_pair))
     }
@@ -582,23 +586,53 @@ impl KeyPackageBuilder {
             capabilities: self.leaf_node_capabilities.unwrap_or_default(),
             extensions: self.leaf_node_extensions.unwrap_or_default(),
         };
-        KeyPackage::create(
+        crate::utils::resolve_ready(KeyPackage::create(
             ciphersuite,
             provider,
             signer,
             credential_with_key,
             self.key_package_extensions.unwrap_or_default(),
             leaf_node_params,
-        )
+        ))
     }
 
     /// Finalize and build the key package.
     pub fn build(
-        mut self,
+        self,
         ciphersuite: Ciphersuite,
         provider: &impl OpenMlsProvider,
         signer: &impl Signer,
         credential_with_key: CredentialWithKey,
+    ) -> Result<KeyPackageBundle, KeyPackageNewError> {
+        crate::utils::resolve_ready(self.build_internal(
+            ciphersuite,
+            provider,
+            signer,
+            credential_with_key,
+        ))
+    }
+
+    /// Finalize and build the key package, signing it with an [`AsyncSigner`].
+    ///
+    /// This is the asynchronous variant of [`KeyPackageBuilder::build`] for
+    /// signature keys that are held by a remote signing service or an HSM.
+    pub async fn build_async(
+        self,
+        ciphersuite: Ciphersuite,
+        provider: &impl OpenMlsProvider,
+        signer: &impl AsyncSigner,
+        credential_with_key: CredentialWithKey,
+    ) -> Result<KeyPackageBundle, KeyPackageNewError> {
+        self.build_internal(ciphersuite, provider, signer, credential_with_key)
+            .await
+    }
+
+    async fn build_internal(
+        mut self,
+        ciphersuite: Ciphersuite,
+        provider: &impl OpenMlsProvider,
+        signer: &impl AsyncSigner,
+        credential_with_key: CredentialWithKey,
     ) -> Result<KeyPackageBundle, KeyPackageNewError> {
         self.ensure_last_resort();
 
@@ -618,7 +652,8 @@ impl KeyPackageBuilder {
             credential_with_key,
             self.key_package_extensions.unwrap_or_default(),
             leaf_node_params,
-        )?;
+        )
+        .await?;
 
         // Store the key package in the key store with the hash reference as id
         // for retrieval when parsing welcome messages.
@@ -984,7 +1019,20 @@ impl KeyPackageBundle {
         ciphersuite: Ciphersuite,
         credential_with_key: CredentialWithKey,
     ) -> Self {
-        KeyPackage::builder()
+        let mut builder = KeyPackage::builder();
+        // Ciphersuites that aren't advertised by default have to be
+        // advertised explicitly.
+        if !Capabilities::default()
+            .ciphersuites()
+            .contains(&ciphersuite.into())
+        {
+            builder = builder.leaf_node_capabilities(
+                Capabilities::builder()
+                    .ciphersuites(vec![ciphersuite])
+                    .build(),
+            );
+        }
+        builder
             .build(ciphersuite, provider, signer, credential_with_key)
             .unwrap()
     }
@@ -185,6 +185,7 @@ pub mod group;
 pub mod key_packages;
 pub mod messages;
 pub mod schedule;
+pub mod sframe;
 pub mod treesync;
 pub mod versions;
 
@@ -194,6 +195,8 @@ pub mod storage;
 
 // Private
 mod binary_tree;
+#[cfg(feature = "tracing")]
+mod instrumentation;
 mod skip_validation;
 mod tree;
 
@@ -97,7 +97,7 @@ impl VerifiableGroupInfo {
     /// Get (unverified) signer of the verifiable group info.
     ///
     /// Note: This method should only be used when necessary to verify the group info signature.
-    pub(crate) fn signer(&self) -> LeafNodeIndex {
+    pub fn signer(&self) -> LeafNodeIndex {
         self.payload.signer
     }
 
@@ -293,10 +293,13 @@ impl CommitIn {
         Ok(Commit { proposals, path })
     }
 
-    #[cfg(feature = "extensions-draft")]
     pub(crate) fn proposals(&self) -> &[ProposalOrRefIn] {
         &self.proposals
     }
+
+    pub(crate) fn path(&self) -> Option<&UpdatePathIn> {
+        self.path.as_ref()
+    }
 }
 
 // The following `From` implementation( breaks abstraction layers and MUST
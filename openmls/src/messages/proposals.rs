@@ -15,6 +15,7 @@ use tls_codec::{
 use crate::{
     binary_tree::array_representation::LeafNodeIndex,
     ciphersuite::hash_ref::{make_proposal_ref, KeyPackageRef, ProposalRef},
+    credentials::{errors::CredentialValidationError, CredentialValidator},
     error::LibraryError,
     extensions::Extensions,
     framing::{
@@ -84,6 +85,7 @@ use crate::component::ComponentId;
         openmls_serialization_helpers::Deserialize,
     )
 )]
+#[cfg_attr(not(feature = "0-8-1-storage-format"), legacy_tags)]
 #[allow(missing_docs)]
 #[repr(u16)]
 pub enum ProposalType {
@@ -268,6 +270,7 @@ impl From<ProposalType> for u16 {
         openmls_serialization_helpers::Deserialize,
     )
 )]
+#[cfg_attr(not(feature = "0-8-1-storage-format"), legacy_tags)]
 #[allow(missing_docs)]
 pub enum Proposal {
     #[cfg_attr(not(feature = "0-8-1-storage-format"), storage_tag = 0)]
@@ -335,7 +338,6 @@ impl Proposal {
         Self::ExternalInit(Box::new(p))
     }
 
-    #[cfg(test)]
     /// Build a ReInit proposal.
     pub(crate) fn re_init(p: ReInitProposal) -> Self {
         Self::ReInit(Box::new(p))
@@ -402,6 +404,39 @@ impl Proposal {
     pub fn is_remove(&self) -> bool {
         matches!(self, Self::Remove(..))
     }
+
+    /// Validates the credentials that this proposal introduces into the group
+    /// using the given [`CredentialValidator`]. These are the credentials of
+    /// added members, of updated leaf nodes and of new external senders.
+    pub(crate) fn validate_credentials(
+        &self,
+        credential_validator: &(impl CredentialValidator + ?Sized),
+    ) -> Result<(), CredentialValidationError> {
+        match self {
+            Proposal::Add(add_proposal) => {
+                let leaf_node = add_proposal.key_package().leaf_node();
+                credential_validator
+                    .validate_credential(leaf_node.credential(), leaf_node.signature_key())
+            }
+            Proposal::Update(update_proposal) => {
+                let leaf_node = update_proposal.leaf_node();
+                credential_validator
+                    .validate_credential(leaf_node.credential(), leaf_node.signature_key())
+            }
+            Proposal::GroupContextExtensions(gce_proposal) => gce_proposal
+                .extensions()
+                .external_senders()
+                .into_iter()
+                .flatten()
+                .try_for_each(|external_sender| {
+                    credential_validator.validate_credential(
+                        external_sender.credential(),
+                        external_sender.signature_key(),
+                    )
+                }),
+            _ => Ok(()),
+        }
+    }
 }
 
 /// Add Proposal.
@@ -567,6 +602,44 @@ pub struct ReInitProposal {
     pub(crate) extensions: Extensions<GroupContext>,
 }
 
+impl ReInitProposal {
+    /// Create a new [`ReInitProposal`] for a successor group with the given
+    /// parameters.
+    pub fn new(
+        group_id: GroupId,
+        version: ProtocolVersion,
+        ciphersuite: Ciphersuite,
+        extensions: Extensions<GroupContext>,
+    ) -> Self {
+        Self {
+            group_id,
+            version,
+            ciphersuite,
+            extensions,
+        }
+    }
+
+    /// Returns the group ID of the successor group.
+    pub fn group_id(&self) -> &GroupId {
+        &self.group_id
+    }
+
+    /// Returns the protocol version of the successor group.
+    pub fn version(&self) -> ProtocolVersion {
+        self.version
+    }
+
+    /// Returns the ciphersuite of the successor group.
+    pub fn ciphersuite(&self) -> Ciphersuite {
+        self.ciphersuite
+    }
+
+    /// Returns the group context extensions of the successor group.
+    pub fn extensions(&self) -> &Extensions<GroupContext> {
+        &self.extensions
+    }
+}
+
 /// ExternalInit Proposal.
 ///
 /// An ExternalInit proposal is used by new members that want to join a group by
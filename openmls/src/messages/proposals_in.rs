@@ -167,6 +167,11 @@ impl AddProposalIn {
         self.key_package.unverified_credential()
     }
 
+    /// Get the (unverified) key package of the member to add.
+    pub fn unverified_key_package(&self) -> &KeyPackageIn {
+        &self.key_package
+    }
+
     /// Returns a [`AddProposal`] after successful validation.
     pub(crate) fn validate(
         self,
@@ -211,6 +216,11 @@ pub struct UpdateProposalIn {
 }
 
 impl UpdateProposalIn {
+    /// Get the (unverified) leaf node of the update.
+    pub fn unverified_leaf_node(&self) -> &LeafNodeIn {
+        &self.leaf_node
+    }
+
     /// Returns a [`UpdateProposal`] after successful validation.
     pub(crate) fn validate(
         self,
@@ -465,6 +475,11 @@ pub struct GroupContextExtensionProposalIn {
 }
 
 impl GroupContextExtensionProposalIn {
+    /// Get the (unverified) extensions of the proposal.
+    pub fn unverified_extensions(&self) -> &Extensions<AnyObject> {
+        &self.extensions_tbv
+    }
+
     pub(crate) fn validate(self) -> Result<GroupContextExtensionProposal, ValidationError> {
         let group_context_extensions = self.extensions_tbv;
         Ok(GroupContextExtensionProposal::new(
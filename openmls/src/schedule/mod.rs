@@ -940,7 +940,6 @@ impl MembershipKey {
         ))
     }
 
-    #[cfg(any(feature = "test-utils", test))]
     pub(crate) fn from_secret(secret: Secret) -> Self {
         Self { secret }
     }
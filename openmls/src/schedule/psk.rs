@@ -12,7 +12,7 @@ use crate::component::ComponentId;
 use crate::{
     group::{GroupEpoch, GroupId},
     schedule::psk::store::ResumptionPskStore,
-    storage::{OpenMlsProvider, StorageProvider},
+    storage::{AsyncStorageProvider, OpenMlsProvider, StorageProvider},
 };
 
 /// Resumption PSK usage.
@@ -368,15 +368,32 @@ impl PreSharedKeyId {
 
     // ----- Validation ----------------------------------------------------------------------------
 
-    pub(crate) fn validate_in_proposal(self, ciphersuite: Ciphersuite) -> Result<(), PskError> {
+    /// Validate this PSK ID as part of a PreSharedKey proposal. Resumption
+    /// PSKs with usage ReInit or Branch are only accepted if
+    /// `is_first_commit` is set, i.e., if the proposal is committed in the
+    /// first Commit of a group that succeeds or branches off another group.
+    pub(crate) fn validate_in_proposal(
+        self,
+        ciphersuite: Ciphersuite,
+        is_first_commit: bool,
+    ) -> Result<(), PskError> {
         // ValSem402
         match self.psk() {
             Psk::Resumption(resumption_psk) => {
                 // https://validation.openmls.tech/#valn0801
                 // https://validation.openmls.tech/#valn0802
-                if resumption_psk.usage != ResumptionPskUsage::Application {
+                let allowed = if is_first_commit {
+                    vec![
+                        ResumptionPskUsage::Application,
+                        ResumptionPskUsage::Reinit,
+                        ResumptionPskUsage::Branch,
+                    ]
+                } else {
+                    vec![ResumptionPskUsage::Application]
+                };
+                if !allowed.contains(&resumption_psk.usage) {
                     return Err(PskError::UsageMismatch {
-                        allowed: vec![ResumptionPskUsage::Application],
+                        allowed,
                         got: resumption_psk.usage,
                     });
                 }
@@ -592,6 +609,15 @@ pub(crate) fn load_psks<'p, Storage: StorageProvider>(
     storage: &Storage,
     resumption_psk_store: &ResumptionPskStore,
     psk_ids: &'p [PreSharedKeyId],
+) -> Result<Vec<(&'p PreSharedKeyId, Secret)>, PskError> {
+    crate::utils::resolve_ready(load_psks_async(storage, resumption_psk_store, psk_ids))
+}
+
+/// Like [`load_psks`], but reads the PSKs from an [`AsyncStorageProvider`].
+pub(crate) async fn load_psks_async<'p, Storage: AsyncStorageProvider>(
+    storage: &Storage,
+    resumption_psk_store: &ResumptionPskStore,
+    psk_ids: &'p [PreSharedKeyId],
 ) -> Result<Vec<(&'p PreSharedKeyId, Secret)>, PskError> {
     let mut psk_bundles = Vec::new();
 
@@ -599,6 +625,22 @@ pub(crate) fn load_psks<'p, Storage: StorageProvider>(
         log_crypto!(trace, "PSK store {:?}", resumption_psk_store);
 
         match &psk_id.psk {
+            // Resumption PSKs with usage ReInit or Branch originate from a
+            // different group than the one the key schedule is run for, so
+            // they are looked up by PSK ID.
+            Psk::Resumption(resumption)
+                if resumption.usage() != ResumptionPskUsage::Application =>
+            {
+                let psk_bundle: Option<PskBundle> = storage
+                    .psk(psk_id.psk())
+                    .await
+                    .map_err(|_| PskError::KeyNotFound)?;
+                if let Some(psk_bundle) = psk_bundle {
+                    psk_bundles.push((psk_id, psk_bundle.secret));
+                } else {
+                    return Err(PskError::KeyNotFound);
+                }
+            }
             Psk::Resumption(resumption) => {
                 if let Some(psk_bundle) = resumption_psk_store.get(resumption.psk_epoch()) {
                     psk_bundles.push((psk_id, psk_bundle.secret.clone()));
@@ -609,6 +651,7 @@ pub(crate) fn load_psks<'p, Storage: StorageProvider>(
             Psk::External(_) => {
                 let psk_bundle: Option<PskBundle> = storage
                     .psk(psk_id.psk())
+                    .await
                     .map_err(|_| PskError::KeyNotFound)?;
                 if let Some(psk_bundle) = psk_bundle {
                     psk_bundles.push((psk_id, psk_bundle.secret));
@@ -620,6 +663,7 @@ pub(crate) fn load_psks<'p, Storage: StorageProvider>(
             Psk::Application(_) => {
                 let psk_bundle: Option<PskBundle> = storage
                     .psk(psk_id.psk())
+                    .await
                     .map_err(|_| PskError::KeyNotFound)?;
                 if let Some(psk_bundle) = psk_bundle {
                     psk_bundles.push((psk_id, psk_bundle.secret));
@@ -633,6 +677,43 @@ pub(crate) fn load_psks<'p, Storage: StorageProvider>(
     Ok(psk_bundles)
 }
 
+/// Loads the PSKs of a Welcome message for a subgroup or the successor group
+/// of the parent group with the given `parent_group_id`. Branch and ReInit
+/// resumption PSKs of the parent group are looked up using
+/// `parent_resumption_psk`, all other PSKs are loaded as in [`load_psks`].
+pub(crate) fn load_parent_psks<'p, 'g, Storage: StorageProvider>(
+    storage: &Storage,
+    resumption_psk_store: &ResumptionPskStore,
+    parent_group_id: &GroupId,
+    parent_resumption_psk: impl Fn(GroupEpoch) -> Option<&'g ResumptionPskSecret>,
+    psk_ids: &'p [PreSharedKeyId],
+) -> Result<Vec<(&'p PreSharedKeyId, Secret)>, PskError> {
+    let mut psk_bundles = Vec::new();
+
+    for psk_id in psk_ids.iter() {
+        match &psk_id.psk {
+            Psk::Resumption(resumption)
+                if matches!(
+                    resumption.usage(),
+                    ResumptionPskUsage::Branch | ResumptionPskUsage::Reinit
+                ) && resumption.psk_group_id() == parent_group_id =>
+            {
+                let Some(resumption_psk) = parent_resumption_psk(resumption.psk_epoch()) else {
+                    return Err(PskError::KeyNotFound);
+                };
+                psk_bundles.push((psk_id, resumption_psk.secret.clone()));
+            }
+            _ => psk_bundles.extend(load_psks(
+                storage,
+                resumption_psk_store,
+                std::slice::from_ref(psk_id),
+            )?),
+        }
+    }
+
+    Ok(psk_bundles)
+}
+
 /// This module contains a store that can hold a rollover list of resumption PSKs.
 pub mod store {
     use serde::{Deserialize, Serialize};
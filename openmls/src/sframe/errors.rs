@@ -0,0 +1,40 @@
+//! SFrame errors.
+
+use thiserror::Error;
+
+use crate::{error::LibraryError, group::ExportSecretError};
+
+/// SFrame error
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum SFrameError {
+    /// See [`LibraryError`] for more details.
+    #[error(transparent)]
+    LibraryError(#[from] LibraryError),
+    /// See [`ExportSecretError`] for more details.
+    #[error(transparent)]
+    ExportSecret(#[from] ExportSecretError),
+    /// The SFrame ciphersuite is not supported.
+    #[error("The SFrame ciphersuite {0:#06x} is not supported.")]
+    UnsupportedCiphersuite(u16),
+    /// The KID layout has more than 64 bits for the epoch and the sender
+    /// index.
+    #[error("The KID layout has more than 64 bits for the epoch and the sender index.")]
+    InvalidKidLayout,
+    /// The sender index or the context doesn't fit into the bits of the KID
+    /// layout.
+    #[error("The sender index or the context doesn't fit into the bits of the KID layout.")]
+    KidOverflow,
+    /// The overlap window is too long to tell the retained epochs apart by the
+    /// epoch bits of a KID.
+    #[error("The overlap window is too long for the epoch bits of the KID layout.")]
+    OverlapWindowTooLong,
+    /// The epoch secret was derived for a different SFrame ciphersuite.
+    #[error("The epoch secret was derived for a different SFrame ciphersuite.")]
+    CiphersuiteMismatch,
+    /// The epoch secret is not newer than the current epoch of the key store.
+    #[error("The epoch secret is not newer than the current epoch of the key store.")]
+    OutdatedEpoch,
+    /// The key store doesn't have a secret for the requested epoch.
+    #[error("The key store doesn't have a secret for the requested epoch.")]
+    UnknownEpoch,
+}
@@ -0,0 +1,493 @@
+//! # SFrame key derivation
+//!
+//! This module derives keys for [SFrame] (RFC 9605), the end-to-end encryption
+//! of real-time media frames, from the epochs of an MLS group as described in
+//! [Section 5.2] of the RFC.
+//!
+//! In every epoch, all members export the same `sframe_epoch_secret` from the
+//! group with the MLS exporter. Every sender has its own `base_key`, which is
+//! expanded from the `sframe_epoch_secret` with the sender's leaf index:
+//!
+//! ```text
+//! sframe_epoch_secret = MLS-Exporter("SFrame 1.0", "", AEAD.Nh)
+//! base_key = HKDF-Expand(sframe_epoch_secret, encode_big_endian(index, 4), AEAD.Nh)
+//! ```
+//!
+//! The key and salt are then derived from the sender's `base_key` and a key
+//! ID (KID) that encodes the sender's leaf index, the low bits of the epoch
+//! and an application defined context. The split of the 64 KID bits is fixed
+//! for a session by a [`KidLayout`]:
+//!
+//! ```text
+//!  64-S-E bits   S bits   E bits
+//! +------------+--------+-------+
+//! |  context   | index  | epoch |
+//! +------------+--------+-------+
+//! ```
+//!
+//! An [`SFrameEpochSecret`] holds the `sframe_epoch_secret` of one epoch. It
+//! can be exported from an [`MlsGroup`] or, to provision the media stack
+//! before the commit is merged, from a [`StagedCommit`].
+//!
+//! Media frames of the previous epoch are still in flight for a short time
+//! after a commit. The [`SFrameKeyStore`] therefore keeps the secrets of a
+//! configurable number of past epochs next to the current one and finds the
+//! epoch of a received frame by the epoch bits of its KID.
+//!
+//! The encryption of frames itself is not part of OpenMLS.
+//!
+//! [SFrame]: https://www.rfc-editor.org/rfc/rfc9605.html
+//! [Section 5.2]: https://www.rfc-editor.org/rfc/rfc9605.html#section-5.2
+
+use std::{collections::VecDeque, fmt};
+
+use openmls_traits::{crypto::OpenMlsCrypto, types::HashType};
+use tls_codec::SecretVLBytes;
+
+use crate::{
+    binary_tree::LeafNodeIndex,
+    error::LibraryError,
+    group::{GroupEpoch, MlsGroup, StagedCommit},
+};
+
+mod errors;
+
+#[cfg(test)]
+mod tests_and_kats;
+
+pub use errors::SFrameError;
+
+/// The label of the MLS exporter for the `sframe_epoch_secret`.
+pub const SFRAME_EXPORTER_LABEL: &str = "SFrame 1.0";
+
+const SFRAME_KEY_LABEL: &[u8] = b"SFrame 1.0 Secret key ";
+const SFRAME_SALT_LABEL: &[u8] = b"SFrame 1.0 Secret salt ";
+
+/// The SFrame ciphersuites of [RFC 9605, Section 4.5].
+///
+/// [RFC 9605, Section 4.5]: https://www.rfc-editor.org/rfc/rfc9605.html#section-4.5
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u16)]
+#[allow(non_camel_case_types)]
+pub enum SFrameCiphersuite {
+    /// AES-128-CTR with HMAC-SHA256, truncated to 80 bits.
+    AES_128_CTR_HMAC_SHA256_80 = 0x0001,
+    /// AES-128-CTR with HMAC-SHA256, truncated to 64 bits.
+    AES_128_CTR_HMAC_SHA256_64 = 0x0002,
+    /// AES-128-CTR with HMAC-SHA256, truncated to 32 bits.
+    AES_128_CTR_HMAC_SHA256_32 = 0x0003,
+    /// AES-128-GCM with SHA256.
+    AES_128_GCM_SHA256_128 = 0x0004,
+    /// AES-256-GCM with SHA512.
+    AES_256_GCM_SHA512_128 = 0x0005,
+}
+
+impl SFrameCiphersuite {
+    /// Returns the code point of the ciphersuite.
+    pub fn value(self) -> u16 {
+        self as u16
+    }
+
+    /// Returns the hash function of the ciphersuite's KDF.
+    pub fn hash_algorithm(self) -> HashType {
+        match self {
+            Self::AES_256_GCM_SHA512_128 => HashType::Sha2_512,
+            _ => HashType::Sha2_256,
+        }
+    }
+
+    /// Returns the output length of the hash function of the ciphersuite's
+    /// KDF (`AEAD.Nh`).
+    pub fn hash_length(self) -> usize {
+        match self.hash_algorithm() {
+            HashType::Sha2_512 => 64,
+            _ => 32,
+        }
+    }
+
+    /// Returns the length of the `sframe_key` (`AEAD.Nk`). For the AES-CTR
+    /// ciphersuites, this includes the key of the HMAC.
+    pub fn key_length(self) -> usize {
+        match self {
+            Self::AES_128_CTR_HMAC_SHA256_80
+            | Self::AES_128_CTR_HMAC_SHA256_64
+            | Self::AES_128_CTR_HMAC_SHA256_32 => 48,
+            Self::AES_128_GCM_SHA256_128 => 16,
+            Self::AES_256_GCM_SHA512_128 => 32,
+        }
+    }
+
+    /// Returns the length of the `sframe_salt` (`AEAD.Nn`).
+    pub fn nonce_length(self) -> usize {
+        12
+    }
+}
+
+impl TryFrom<u16> for SFrameCiphersuite {
+    type Error = SFrameError;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        match value {
+            0x0001 => Ok(Self::AES_128_CTR_HMAC_SHA256_80),
+            0x0002 => Ok(Self::AES_128_CTR_HMAC_SHA256_64),
+            0x0003 => Ok(Self::AES_128_CTR_HMAC_SHA256_32),
+            0x0004 => Ok(Self::AES_128_GCM_SHA256_128),
+            0x0005 => Ok(Self::AES_256_GCM_SHA512_128),
+            _ => Err(SFrameError::UnsupportedCiphersuite(value)),
+        }
+    }
+}
+
+/// The split of a KID into the context, the sender's leaf index and the low
+/// bits of the epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KidLayout {
+    epoch_bits: u32,
+    index_bits: u32,
+}
+
+impl KidLayout {
+    /// Create a layout with `epoch_bits` (`E`) for the epoch and `index_bits`
+    /// (`S`) for the sender's leaf index. The remaining bits are used for the
+    /// context.
+    ///
+    /// Returns [`SFrameError::InvalidKidLayout`] if `E + S` exceeds 64 bits,
+    /// or if `S` exceeds the 32 bits of a leaf index.
+    pub fn new(epoch_bits: u8, index_bits: u8) -> Result<Self, SFrameError> {
+        let (epoch_bits, index_bits) = (u32::from(epoch_bits), u32::from(index_bits));
+        if epoch_bits + index_bits > u64::BITS || index_bits > u32::BITS {
+            return Err(SFrameError::InvalidKidLayout);
+        }
+        Ok(Self {
+            epoch_bits,
+            index_bits,
+        })
+    }
+
+    /// Returns the KID of `sender` in `epoch`, as
+    /// `(context << (S + E)) + (index << E) + (epoch % (1 << E))`.
+    ///
+    /// Returns [`SFrameError::KidOverflow`] if the leaf index or the context
+    /// don't fit into their bits.
+    pub fn kid(
+        &self,
+        context: u64,
+        epoch: GroupEpoch,
+        sender: LeafNodeIndex,
+    ) -> Result<u64, SFrameError> {
+        let index = u64::from(sender.u32());
+        let context_bits = u64::BITS - self.epoch_bits - self.index_bits;
+        if low_bits(index, self.index_bits) != index || low_bits(context, context_bits) != context {
+            return Err(SFrameError::KidOverflow);
+        }
+        Ok(shl(context, self.epoch_bits + self.index_bits)
+            | shl(index, self.epoch_bits)
+            | self.epoch_bits(epoch.as_u64()))
+    }
+
+    /// Returns the leaf index of the sender encoded in `kid`.
+    pub fn sender(&self, kid: u64) -> LeafNodeIndex {
+        // The index has at most 32 bits, see `new`.
+        LeafNodeIndex::new(low_bits(shr(kid, self.epoch_bits), self.index_bits) as u32)
+    }
+
+    /// Returns the context encoded in `kid`.
+    pub fn context(&self, kid: u64) -> u64 {
+        shr(kid, self.epoch_bits + self.index_bits)
+    }
+
+    /// Returns the epoch bits of `value`, which is either a KID or an epoch.
+    fn epoch_bits(&self, value: u64) -> u64 {
+        low_bits(value, self.epoch_bits)
+    }
+}
+
+fn low_bits(value: u64, bits: u32) -> u64 {
+    if bits >= u64::BITS {
+        value
+    } else {
+        value & ((1 << bits) - 1)
+    }
+}
+
+// Shifting a `u64` by 64 bits overflows, but a KID layout can use all 64 bits
+// for the epoch and the index.
+fn shl(value: u64, bits: u32) -> u64 {
+    value.checked_shl(bits).unwrap_or(0)
+}
+
+fn shr(value: u64, bits: u32) -> u64 {
+    value.checked_shr(bits).unwrap_or(0)
+}
+
+/// The `sframe_epoch_secret` of an epoch, exported from the group.
+pub struct SFrameEpochSecret {
+    ciphersuite: SFrameCiphersuite,
+    epoch: GroupEpoch,
+    secret: SecretVLBytes,
+}
+
+impl fmt::Debug for SFrameEpochSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SFrameEpochSecret")
+            .field("ciphersuite", &self.ciphersuite)
+            .field("epoch", &self.epoch)
+            .field("secret", &"***")
+            .finish()
+    }
+}
+
+impl SFrameEpochSecret {
+    /// Export the `sframe_epoch_secret` of the current epoch of `group`.
+    pub fn from_group(
+        crypto: &impl OpenMlsCrypto,
+        group: &MlsGroup,
+        ciphersuite: SFrameCiphersuite,
+    ) -> Result<Self, SFrameError> {
+        let secret = group.export_secret(
+            crypto,
+            SFRAME_EXPORTER_LABEL,
+            &[],
+            ciphersuite.hash_length(),
+        )?;
+        Ok(Self::new(ciphersuite, group.epoch(), secret))
+    }
+
+    /// Export the `sframe_epoch_secret` of the epoch that `staged_commit`
+    /// moves the group to, so that the keys can be provisioned before the
+    /// commit is merged.
+    pub fn from_staged_commit(
+        crypto: &impl OpenMlsCrypto,
+        staged_commit: &StagedCommit,
+        ciphersuite: SFrameCiphersuite,
+    ) -> Result<Self, SFrameError> {
+        let secret = staged_commit.export_secret(
+            crypto,
+            SFRAME_EXPORTER_LABEL,
+            &[],
+            ciphersuite.hash_length(),
+        )?;
+        Ok(Self::new(ciphersuite, staged_commit.epoch(), secret))
+    }
+
+    fn new(ciphersuite: SFrameCiphersuite, epoch: GroupEpoch, secret: Vec<u8>) -> Self {
+        Self {
+            ciphersuite,
+            epoch,
+            secret: secret.into(),
+        }
+    }
+
+    /// Returns the SFrame ciphersuite the secret was exported for.
+    pub fn ciphersuite(&self) -> SFrameCiphersuite {
+        self.ciphersuite
+    }
+
+    /// Returns the epoch of the secret.
+    pub fn epoch(&self) -> GroupEpoch {
+        self.epoch
+    }
+
+    /// Returns the `sframe_epoch_secret`.
+    pub fn secret(&self) -> &[u8] {
+        self.secret.as_slice()
+    }
+
+    /// Derive the `base_key` of `sender` in the epoch.
+    pub fn base_key(
+        &self,
+        crypto: &impl OpenMlsCrypto,
+        sender: LeafNodeIndex,
+    ) -> Result<SecretVLBytes, SFrameError> {
+        crypto
+            .hkdf_expand(
+                self.ciphersuite.hash_algorithm(),
+                self.secret(),
+                &sender.u32().to_be_bytes(),
+                self.ciphersuite.hash_length(),
+            )
+            .map_err(|e| LibraryError::unexpected_crypto_error(e).into())
+    }
+
+    /// Derive the SFrame key for `kid` from the `base_key` of `sender`.
+    pub fn derive_key(
+        &self,
+        crypto: &impl OpenMlsCrypto,
+        sender: LeafNodeIndex,
+        kid: u64,
+    ) -> Result<SFrameKey, SFrameError> {
+        let base_key = self.base_key(crypto, sender)?;
+        derive_key(crypto, self.ciphersuite, base_key.as_slice(), kid)
+    }
+}
+
+/// The key and salt for the frames with one KID, as derived in
+/// [RFC 9605, Section 4.4.2].
+///
+/// [RFC 9605, Section 4.4.2]: https://www.rfc-editor.org/rfc/rfc9605.html#section-4.4.2
+pub struct SFrameKey {
+    kid: u64,
+    key: SecretVLBytes,
+    salt: SecretVLBytes,
+}
+
+impl fmt::Debug for SFrameKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SFrameKey")
+            .field("kid", &self.kid)
+            .field("key", &"***")
+            .field("salt", &"***")
+            .finish()
+    }
+}
+
+impl SFrameKey {
+    /// Returns the KID of the key.
+    pub fn kid(&self) -> u64 {
+        self.kid
+    }
+
+    /// Returns the `sframe_key`.
+    pub fn key(&self) -> &[u8] {
+        self.key.as_slice()
+    }
+
+    /// Returns the `sframe_salt`.
+    pub fn salt(&self) -> &[u8] {
+        self.salt.as_slice()
+    }
+}
+
+fn label(prefix: &[u8], kid: u64, ciphersuite: SFrameCiphersuite) -> Vec<u8> {
+    [
+        prefix,
+        &kid.to_be_bytes(),
+        &ciphersuite.value().to_be_bytes(),
+    ]
+    .concat()
+}
+
+fn derive_key(
+    crypto: &impl OpenMlsCrypto,
+    ciphersuite: SFrameCiphersuite,
+    base_key: &[u8],
+    kid: u64,
+) -> Result<SFrameKey, SFrameError> {
+    let hash = ciphersuite.hash_algorithm();
+    let secret = crypto
+        .hkdf_extract(hash, &[], base_key)
+        .map_err(LibraryError::unexpected_crypto_error)?;
+    let key = crypto
+        .hkdf_expand(
+            hash,
+            secret.as_slice(),
+            &label(SFRAME_KEY_LABEL, kid, ciphersuite),
+            ciphersuite.key_length(),
+        )
+        .map_err(LibraryError::unexpected_crypto_error)?;
+    let salt = crypto
+        .hkdf_expand(
+            hash,
+            secret.as_slice(),
+            &label(SFRAME_SALT_LABEL, kid, ciphersuite),
+            ciphersuite.nonce_length(),
+        )
+        .map_err(LibraryError::unexpected_crypto_error)?;
+    Ok(SFrameKey { kid, key, salt })
+}
+
+/// The SFrame secrets of the current epoch and of the past epochs in the
+/// overlap window.
+#[derive(Debug)]
+pub struct SFrameKeyStore {
+    ciphersuite: SFrameCiphersuite,
+    layout: KidLayout,
+    max_past_epochs: usize,
+    // The secrets of the current and past epochs, ordered from the newest to
+    // the oldest epoch.
+    secrets: VecDeque<SFrameEpochSecret>,
+}
+
+impl SFrameKeyStore {
+    /// Create an empty key store that keeps the secrets of up to
+    /// `max_past_epochs` epochs before the current one.
+    ///
+    /// Returns [`SFrameError::OverlapWindowTooLong`] if the epoch bits of
+    /// `layout` can't tell all retained epochs apart.
+    pub fn new(
+        ciphersuite: SFrameCiphersuite,
+        layout: KidLayout,
+        max_past_epochs: usize,
+    ) -> Result<Self, SFrameError> {
+        let retained_epochs = (max_past_epochs as u64).saturating_add(1);
+        if layout.epoch_bits < u64::BITS && retained_epochs > 1 << layout.epoch_bits {
+            return Err(SFrameError::OverlapWindowTooLong);
+        }
+        Ok(Self {
+            ciphersuite,
+            layout,
+            max_past_epochs,
+            secrets: VecDeque::new(),
+        })
+    }
+
+    /// Returns the KID layout of the store.
+    pub fn layout(&self) -> KidLayout {
+        self.layout
+    }
+
+    /// Returns the current epoch, if the store has a secret.
+    pub fn current_epoch(&self) -> Option<GroupEpoch> {
+        self.secrets.front().map(SFrameEpochSecret::epoch)
+    }
+
+    /// Make `secret` the secret of the current epoch. The secret of the
+    /// previous epoch is kept for decryption, and the oldest secret is dropped
+    /// if the overlap window is full.
+    ///
+    /// Returns [`SFrameError::OutdatedEpoch`] if the epoch of `secret` is not
+    /// newer than the current epoch.
+    pub fn add_epoch(&mut self, secret: SFrameEpochSecret) -> Result<(), SFrameError> {
+        if secret.ciphersuite() != self.ciphersuite {
+            return Err(SFrameError::CiphersuiteMismatch);
+        }
+        if self
+            .current_epoch()
+            .is_some_and(|current_epoch| secret.epoch() <= current_epoch)
+        {
+            return Err(SFrameError::OutdatedEpoch);
+        }
+        self.secrets.push_front(secret);
+        self.secrets
+            .truncate(self.max_past_epochs.saturating_add(1));
+        Ok(())
+    }
+
+    /// Derive the key to encrypt frames from `sender` in the current epoch.
+    pub fn encryption_key(
+        &self,
+        crypto: &impl OpenMlsCrypto,
+        sender: LeafNodeIndex,
+        context: u64,
+    ) -> Result<SFrameKey, SFrameError> {
+        let secret = self.secrets.front().ok_or(SFrameError::UnknownEpoch)?;
+        let kid = self.layout.kid(context, secret.epoch(), sender)?;
+        secret.derive_key(crypto, sender, kid)
+    }
+
+    /// Derive the key to decrypt frames with `kid`, from the current or one of
+    /// the past epochs.
+    pub fn decryption_key(
+        &self,
+        crypto: &impl OpenMlsCrypto,
+        kid: u64,
+    ) -> Result<SFrameKey, SFrameError> {
+        let epoch_bits = self.layout.epoch_bits(kid);
+        let secret = self
+            .secrets
+            .iter()
+            .find(|secret| self.layout.epoch_bits(secret.epoch().as_u64()) == epoch_bits)
+            .ok_or(SFrameError::UnknownEpoch)?;
+        secret.derive_key(crypto, self.layout.sender(kid), kid)
+    }
+}
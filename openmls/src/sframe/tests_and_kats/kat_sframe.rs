@@ -0,0 +1,87 @@
+//! # Known Answer Tests for the SFrame key derivation
+//!
+//! The test vectors are the SFrame encryption test vectors of
+//! [RFC 9605, Appendix C.5], reduced to the key derivation.
+//!
+//! Parameters:
+//! * SFrame ciphersuite
+//!
+//! Format:
+//!
+//! ```text
+//! {
+//!   "cipher_suite": /* uint16 */,
+//!   "kid": /* uint64 */,
+//!   "base_key": /* hex-encoded binary data */,
+//!   "sframe_key_label": /* hex-encoded binary data */,
+//!   "sframe_salt_label": /* hex-encoded binary data */,
+//!   "sframe_secret": /* hex-encoded binary data */,
+//!   "sframe_key": /* hex-encoded binary data */,
+//!   "sframe_salt": /* hex-encoded binary data */,
+//! }
+//! ```
+//!
+//! Verification:
+//!
+//! * `sframe_secret == HKDF-Extract("", base_key)`
+//! * `sframe_key == HKDF-Expand(sframe_secret, sframe_key_label, AEAD.Nk)`
+//! * `sframe_salt == HKDF-Expand(sframe_secret, sframe_salt_label, AEAD.Nn)`
+//!
+//! [RFC 9605, Appendix C.5]: https://www.rfc-editor.org/rfc/rfc9605.html#appendix-C.5
+
+use openmls_rust_crypto::OpenMlsRustCrypto;
+use openmls_traits::{crypto::OpenMlsCrypto, OpenMlsProvider};
+use serde::Deserialize;
+
+use crate::{
+    sframe::{derive_key, label, SFrameCiphersuite, SFRAME_KEY_LABEL, SFRAME_SALT_LABEL},
+    test_utils::*,
+};
+
+#[derive(Deserialize, Debug, Clone)]
+struct SFrameTestVector {
+    cipher_suite: u16,
+    kid: u64,
+    base_key: String,
+    sframe_key_label: String,
+    sframe_salt_label: String,
+    sframe_secret: String,
+    sframe_key: String,
+    sframe_salt: String,
+}
+
+fn run_test_vector(test: SFrameTestVector, provider: &OpenMlsRustCrypto) {
+    let ciphersuite = SFrameCiphersuite::try_from(test.cipher_suite).unwrap();
+    let base_key = hex_to_bytes(&test.base_key);
+
+    assert_eq!(
+        label(SFRAME_KEY_LABEL, test.kid, ciphersuite),
+        hex_to_bytes(&test.sframe_key_label)
+    );
+    assert_eq!(
+        label(SFRAME_SALT_LABEL, test.kid, ciphersuite),
+        hex_to_bytes(&test.sframe_salt_label)
+    );
+
+    let sframe_secret = provider
+        .crypto()
+        .hkdf_extract(ciphersuite.hash_algorithm(), &[], &base_key)
+        .unwrap();
+    assert_eq!(sframe_secret.as_slice(), hex_to_bytes(&test.sframe_secret));
+
+    let key = derive_key(provider.crypto(), ciphersuite, &base_key, test.kid).unwrap();
+    assert_eq!(key.kid(), test.kid);
+    assert_eq!(key.key(), hex_to_bytes(&test.sframe_key));
+    assert_eq!(key.salt(), hex_to_bytes(&test.sframe_salt));
+}
+
+#[test]
+fn read_test_vectors() {
+    let provider = OpenMlsRustCrypto::default();
+
+    let tests: Vec<SFrameTestVector> = read_json!("../../../test_vectors/sframe.json");
+    assert_eq!(tests.len(), 5);
+    for test in tests {
+        run_test_vector(test, &provider);
+    }
+}
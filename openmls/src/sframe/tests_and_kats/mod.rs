@@ -0,0 +1,2 @@
+mod kat_sframe;
+mod tests;
@@ -0,0 +1,277 @@
+use openmls_traits::OpenMlsProvider;
+
+use crate::{
+    binary_tree::LeafNodeIndex,
+    framing::{MlsMessageIn, ProcessedMessageContent},
+    group::{mls_group::tests_and_kats::utils::setup_alice_bob_group, GroupEpoch, MlsGroup},
+    sframe::*,
+    treesync::LeafNodeParameters,
+};
+
+const CIPHERSUITE: SFrameCiphersuite = SFrameCiphersuite::AES_128_GCM_SHA256_128;
+
+#[test]
+fn kid_layout() {
+    let layout = KidLayout::new(4, 8).unwrap();
+    let kid = layout
+        .kid(3, GroupEpoch::from(0x25), LeafNodeIndex::new(0x2a))
+        .unwrap();
+    assert_eq!(kid, (3 << 12) | (0x2a << 4) | 0x5);
+    assert_eq!(layout.sender(kid), LeafNodeIndex::new(0x2a));
+    assert_eq!(layout.context(kid), 3);
+
+    // The leaf index doesn't fit into 8 bits.
+    assert_eq!(
+        layout.kid(0, GroupEpoch::from(0), LeafNodeIndex::new(0x100)),
+        Err(SFrameError::KidOverflow)
+    );
+
+    // All 64 bits are used for the epoch and the index, so the context must
+    // be 0.
+    let layout = KidLayout::new(32, 32).unwrap();
+    let kid = layout
+        .kid(0, GroupEpoch::from(u64::MAX), LeafNodeIndex::new(u32::MAX))
+        .unwrap();
+    assert_eq!(kid, u64::MAX);
+    assert_eq!(layout.sender(kid), LeafNodeIndex::new(u32::MAX));
+    assert_eq!(layout.context(kid), 0);
+    assert_eq!(
+        layout.kid(1, GroupEpoch::from(0), LeafNodeIndex::new(0)),
+        Err(SFrameError::KidOverflow)
+    );
+
+    assert_eq!(KidLayout::new(40, 30), Err(SFrameError::InvalidKidLayout));
+    assert_eq!(KidLayout::new(8, 33), Err(SFrameError::InvalidKidLayout));
+}
+
+#[test]
+fn overlap_window_must_fit_into_epoch_bits() {
+    let layout = KidLayout::new(2, 8).unwrap();
+    assert!(SFrameKeyStore::new(CIPHERSUITE, layout, 3).is_ok());
+    assert_eq!(
+        SFrameKeyStore::new(CIPHERSUITE, layout, 4).unwrap_err(),
+        SFrameError::OverlapWindowTooLong
+    );
+}
+
+/// With 64 epoch bits, the overlap window can be unbounded.
+#[test]
+fn unbounded_overlap_window() {
+    let crypto = openmls_rust_crypto::RustCrypto::default();
+    let layout = KidLayout::new(64, 0).unwrap();
+    let mut keys = SFrameKeyStore::new(CIPHERSUITE, layout, usize::MAX).unwrap();
+    for epoch in 0..3u64 {
+        keys.add_epoch(SFrameEpochSecret::new(
+            CIPHERSUITE,
+            GroupEpoch::from(epoch),
+            vec![epoch as u8; 32],
+        ))
+        .unwrap();
+    }
+
+    let key = keys
+        .encryption_key(&crypto, LeafNodeIndex::new(0), 0)
+        .unwrap();
+    assert_eq!(key.kid(), 2);
+    for kid in 0..3 {
+        assert!(keys.decryption_key(&crypto, kid).is_ok());
+    }
+}
+
+/// All members derive the same keys, and the frames of the previous epoch can
+/// still be decrypted after a commit.
+#[openmls_test::openmls_test]
+fn epoch_rollover() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+    let (mut alice_group, alice_signer, mut bob_group, _bob_signer, _alice_cwk, _bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+
+    let layout = KidLayout::new(2, 8).unwrap();
+    let mut alice_keys = SFrameKeyStore::new(CIPHERSUITE, layout, 1).unwrap();
+    let mut bob_keys = SFrameKeyStore::new(CIPHERSUITE, layout, 1).unwrap();
+    alice_keys
+        .add_epoch(
+            SFrameEpochSecret::from_group(alice_provider.crypto(), &alice_group, CIPHERSUITE)
+                .unwrap(),
+        )
+        .unwrap();
+    bob_keys
+        .add_epoch(
+            SFrameEpochSecret::from_group(bob_provider.crypto(), &bob_group, CIPHERSUITE).unwrap(),
+        )
+        .unwrap();
+
+    let old_key = alice_keys
+        .encryption_key(alice_provider.crypto(), alice_group.own_leaf_index(), 0)
+        .unwrap();
+    assert_eq!(layout.sender(old_key.kid()), alice_group.own_leaf_index());
+    assert_same_key(
+        &old_key,
+        &bob_keys
+            .decryption_key(bob_provider.crypto(), old_key.kid())
+            .unwrap(),
+    );
+
+    // Alice commits, and Bob provisions the keys of the new epoch from the
+    // staged commit.
+    let commit = self_update(&mut alice_group, alice_provider, &alice_signer);
+    alice_keys
+        .add_epoch(
+            SFrameEpochSecret::from_group(alice_provider.crypto(), &alice_group, CIPHERSUITE)
+                .unwrap(),
+        )
+        .unwrap();
+    let staged_commit = stage(&mut bob_group, bob_provider, commit);
+    let secret =
+        SFrameEpochSecret::from_staged_commit(bob_provider.crypto(), &staged_commit, CIPHERSUITE)
+            .unwrap();
+    assert_eq!(secret.epoch(), alice_group.epoch());
+    bob_keys.add_epoch(secret).unwrap();
+    bob_group
+        .merge_staged_commit(bob_provider, staged_commit)
+        .unwrap();
+
+    let new_key = alice_keys
+        .encryption_key(alice_provider.crypto(), alice_group.own_leaf_index(), 0)
+        .unwrap();
+    assert_ne!(new_key.kid(), old_key.kid());
+    assert_ne!(new_key.key(), old_key.key());
+    assert_same_key(
+        &new_key,
+        &bob_keys
+            .decryption_key(bob_provider.crypto(), new_key.kid())
+            .unwrap(),
+    );
+    // The frames of the previous epoch are still in the overlap window.
+    assert_same_key(
+        &old_key,
+        &bob_keys
+            .decryption_key(bob_provider.crypto(), old_key.kid())
+            .unwrap(),
+    );
+
+    // Secrets of past epochs can't become current again.
+    assert_eq!(
+        bob_keys.add_epoch(
+            SFrameEpochSecret::from_group(bob_provider.crypto(), &bob_group, CIPHERSUITE).unwrap()
+        ),
+        Err(SFrameError::OutdatedEpoch)
+    );
+
+    // After another commit, the first epoch is out of the overlap window.
+    let commit = self_update(&mut alice_group, alice_provider, &alice_signer);
+    let staged_commit = stage(&mut bob_group, bob_provider, commit);
+    bob_keys
+        .add_epoch(
+            SFrameEpochSecret::from_staged_commit(
+                bob_provider.crypto(),
+                &staged_commit,
+                CIPHERSUITE,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+    assert_eq!(bob_keys.current_epoch(), Some(GroupEpoch::from(3)));
+    assert_eq!(
+        bob_keys
+            .decryption_key(bob_provider.crypto(), old_key.kid())
+            .unwrap_err(),
+        SFrameError::UnknownEpoch
+    );
+    assert!(bob_keys
+        .decryption_key(bob_provider.crypto(), new_key.kid())
+        .is_ok());
+}
+
+/// The keys are derived from the MLS group as in RFC 9605, Section 5.2, with a
+/// separate `base_key` for every sender.
+#[openmls_test::openmls_test]
+fn mls_derived_keys() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+    let (alice_group, _alice_signer, bob_group, _bob_signer, _alice_cwk, _bob_cwk) =
+        setup_alice_bob_group(ciphersuite, alice_provider, bob_provider);
+    let crypto = alice_provider.crypto();
+
+    for sframe_ciphersuite in [
+        SFrameCiphersuite::AES_128_GCM_SHA256_128,
+        SFrameCiphersuite::AES_256_GCM_SHA512_128,
+    ] {
+        let secret =
+            SFrameEpochSecret::from_group(crypto, &alice_group, sframe_ciphersuite).unwrap();
+        let sframe_epoch_secret = alice_group
+            .export_secret(crypto, "SFrame 1.0", &[], sframe_ciphersuite.hash_length())
+            .unwrap();
+        assert_eq!(secret.secret(), sframe_epoch_secret);
+
+        let layout = KidLayout::new(4, 8).unwrap();
+        let mut keys = SFrameKeyStore::new(sframe_ciphersuite, layout, 0).unwrap();
+        keys.add_epoch(secret).unwrap();
+
+        for sender in [alice_group.own_leaf_index(), bob_group.own_leaf_index()] {
+            let base_key = crypto
+                .hkdf_expand(
+                    sframe_ciphersuite.hash_algorithm(),
+                    &sframe_epoch_secret,
+                    &sender.u32().to_be_bytes(),
+                    sframe_ciphersuite.hash_length(),
+                )
+                .unwrap();
+            let kid = layout.kid(7, alice_group.epoch(), sender).unwrap();
+            let expected =
+                derive_key(crypto, sframe_ciphersuite, base_key.as_slice(), kid).unwrap();
+
+            let key = keys.encryption_key(crypto, sender, 7).unwrap();
+            assert_same_key(&key, &expected);
+            assert_same_key(&keys.decryption_key(crypto, kid).unwrap(), &expected);
+        }
+
+        // The senders have different base keys, so their keys differ even if
+        // the KIDs only differ in the sender index.
+        let alice_key = keys
+            .encryption_key(crypto, alice_group.own_leaf_index(), 7)
+            .unwrap();
+        let bob_key = keys
+            .encryption_key(crypto, bob_group.own_leaf_index(), 7)
+            .unwrap();
+        assert_ne!(alice_key.key(), bob_key.key());
+        assert_ne!(alice_key.salt(), bob_key.salt());
+    }
+}
+
+fn assert_same_key(a: &SFrameKey, b: &SFrameKey) {
+    assert_eq!(a.kid(), b.kid());
+    assert_eq!(a.key(), b.key());
+    assert_eq!(a.salt(), b.salt());
+}
+
+fn self_update(
+    group: &mut MlsGroup,
+    provider: &impl OpenMlsProvider,
+    signer: &impl openmls_traits::signatures::Signer,
+) -> MlsMessageIn {
+    let bundle = group
+        .self_update(provider, signer, LeafNodeParameters::default())
+        .expect("Could not create commit.");
+    group
+        .merge_pending_commit(provider)
+        .expect("Could not merge commit.");
+    bundle.commit().clone().into()
+}
+
+fn stage(
+    group: &mut MlsGroup,
+    provider: &impl OpenMlsProvider,
+    commit: MlsMessageIn,
+) -> crate::group::StagedCommit {
+    let processed_message = group
+        .process_message(provider, commit.into_protocol_message().unwrap())
+        .expect("Could not process commit.");
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a staged commit.");
+    };
+    *staged_commit
+}
@@ -10,6 +10,7 @@
 use openmls_traits::storage::{traits, Entity, Key, CURRENT_VERSION};
 
 use crate::binary_tree::LeafNodeIndex;
+use crate::group::mls_group::future_epoch_messages::FutureEpochMessages;
 use crate::group::proposal_store::QueuedProposal;
 use crate::group::{MlsGroupJoinConfig, MlsGroupState};
 #[cfg(feature = "extensions-draft")]
@@ -30,8 +31,15 @@ use crate::{
     treesync::{node::encryption_keys::EncryptionKeyPair, EncryptionKey},
 };
 
+#[cfg(feature = "tracing")]
+mod instrumented;
 #[cfg(test)]
 pub mod kat_storage_stability;
+#[cfg(not(feature = "0-8-1-storage-format"))]
+pub mod migration;
+
+#[cfg(feature = "tracing")]
+pub use instrumented::{InstrumentedAsyncStorage, InstrumentedStorage};
 
 /// A convenience trait for the current version of the storage.
 /// Throughout the code, this one should be used instead of `openmls_traits::storage::StorageProvider`.
@@ -80,6 +88,109 @@ impl<
     type StorageError = Error;
 }
 
+/// A convenience trait for the current version of the asynchronous storage.
+/// Throughout the code, this one should be used instead of
+/// `openmls_traits::async_storage::AsyncStorageProvider`.
+pub trait AsyncStorageProvider:
+    openmls_traits::async_storage::AsyncStorageProvider<CURRENT_VERSION>
+{
+}
+
+impl<P: openmls_traits::async_storage::AsyncStorageProvider<CURRENT_VERSION>> AsyncStorageProvider
+    for P
+{
+}
+
+/// A convenience trait for the asynchronous OpenMLS provider that defines the
+/// storage provider for the current version of storage.
+/// Throughout the code, this one should be used instead of
+/// `openmls_traits::AsyncOpenMlsProvider`.
+pub trait AsyncOpenMlsProvider:
+    openmls_traits::AsyncOpenMlsProvider<StorageProvider = Self::Storage>
+{
+    /// The storage to use
+    type Storage: AsyncStorageProvider<Error = Self::StorageError>;
+    /// The storage error type
+    type StorageError: std::error::Error;
+}
+
+impl<
+        Error: std::error::Error,
+        SP: AsyncStorageProvider<Error = Error>,
+        OP: openmls_traits::AsyncOpenMlsProvider<StorageProvider = SP>,
+    > AsyncOpenMlsProvider for OP
+{
+    type Storage = SP;
+    type StorageError = Error;
+}
+
+/// Runs `operation` in a storage transaction. The transaction is committed
+/// if the operation succeeds and rolled back if it fails, so that either all
+/// or none of its writes are persisted.
+///
+/// `storage_error` maps errors of the storage itself to the error type of the
+/// operation.
+// The bound uses the `openmls_traits` trait, because the compiler fails to
+// prove the supertrait bound of the convenience trait for `Storage::Error` in
+// the `Fn` bound.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "storage_transaction", level = "debug", skip_all)
+)]
+pub(crate) fn transaction<
+    Storage: openmls_traits::storage::StorageProvider<CURRENT_VERSION>,
+    T,
+    E,
+>(
+    storage: &Storage,
+    operation: impl FnOnce() -> Result<T, E>,
+    storage_error: impl Fn(Storage::Error) -> E,
+) -> Result<T, E> {
+    storage.begin_transaction().map_err(&storage_error)?;
+    match operation() {
+        Ok(value) => {
+            storage.commit_transaction().map_err(storage_error)?;
+            Ok(value)
+        }
+        Err(e) => {
+            if let Err(rollback_error) = storage.rollback_transaction() {
+                log::error!("Rolling back the storage transaction failed: {rollback_error:?}");
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Runs `operation` in a storage transaction like [`transaction`], on an
+/// asynchronous storage.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "storage_transaction", level = "debug", skip_all)
+)]
+pub(crate) async fn transaction_async<
+    Storage: openmls_traits::async_storage::AsyncStorageProvider<CURRENT_VERSION>,
+    T,
+    E,
+>(
+    storage: &Storage,
+    operation: impl std::future::Future<Output = Result<T, E>>,
+    storage_error: impl Fn(Storage::Error) -> E,
+) -> Result<T, E> {
+    storage.begin_transaction().await.map_err(&storage_error)?;
+    match operation.await {
+        Ok(value) => {
+            storage.commit_transaction().await.map_err(storage_error)?;
+            Ok(value)
+        }
+        Err(e) => {
+            if let Err(rollback_error) = storage.rollback_transaction().await {
+                log::error!("Rolling back the storage transaction failed: {rollback_error:?}");
+            }
+            Err(e)
+        }
+    }
+}
+
 // Implementations for the Entity and Key traits
 
 impl Entity<CURRENT_VERSION> for QueuedProposal {}
@@ -135,6 +246,9 @@ impl traits::GroupState<CURRENT_VERSION> for MlsGroupState {}
 impl Entity<CURRENT_VERSION> for LeafNode {}
 impl traits::LeafNode<CURRENT_VERSION> for LeafNode {}
 
+impl Entity<CURRENT_VERSION> for FutureEpochMessages {}
+impl traits::FutureEpochMessages<CURRENT_VERSION> for FutureEpochMessages {}
+
 // Crypto
 
 impl Key<CURRENT_VERSION> for GroupEpoch {}
@@ -0,0 +1,450 @@
+//! Storage providers that emit a [`tracing`] span per storage call.
+//!
+//! [`InstrumentedStorage`] wraps a [`StorageProvider`] and
+//! [`InstrumentedAsyncStorage`] wraps an [`AsyncStorageProvider`]. Every call
+//! that can fail runs in a `storage_call` span whose `method` field is the
+//! name of the called method, and a failing call emits an event with the
+//! error. Keys and values are never recorded.
+//!
+//! There are two wrappers because every [`StorageProvider`] is also an
+//! [`AsyncStorageProvider`], so a single wrapper can't implement both traits.
+
+use std::future::Future;
+
+use openmls_traits::{
+    async_storage::AsyncStorageProvider,
+    storage::{traits, MigrationLog, StorageProvider},
+};
+use tracing::Instrument;
+
+fn call_span(method: &'static str) -> tracing::Span {
+    tracing::debug_span!("storage_call", method)
+}
+
+fn record_error<T, E: std::fmt::Display>(result: Result<T, E>) -> Result<T, E> {
+    if let Err(e) = &result {
+        tracing::debug!(error = %e, "storage call failed");
+    }
+    result
+}
+
+fn call<T, E: std::fmt::Display>(
+    method: &'static str,
+    f: impl FnOnce() -> Result<T, E>,
+) -> Result<T, E> {
+    let _span = call_span(method).entered();
+    record_error(f())
+}
+
+fn call_async<T, E: std::fmt::Display>(
+    method: &'static str,
+    future: impl Future<Output = Result<T, E>> + Send,
+) -> impl Future<Output = Result<T, E>> + Send {
+    async move { record_error(future.await) }.instrument(call_span(method))
+}
+
+/// A [`StorageProvider`] that runs every call to the wrapped provider in a
+/// `storage_call` span.
+#[derive(Debug, Default)]
+pub struct InstrumentedStorage<S> {
+    storage: S,
+}
+
+impl<S> InstrumentedStorage<S> {
+    /// Wraps `storage`.
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// Returns the wrapped storage provider.
+    pub fn inner(&self) -> &S {
+        &self.storage
+    }
+
+    /// Returns the wrapped storage provider, consuming the wrapper.
+    pub fn into_inner(self) -> S {
+        self.storage
+    }
+}
+
+/// An [`AsyncStorageProvider`] that runs every call to the wrapped provider
+/// in a `storage_call` span.
+#[derive(Debug, Default)]
+pub struct InstrumentedAsyncStorage<S> {
+    storage: S,
+}
+
+impl<S> InstrumentedAsyncStorage<S> {
+    /// Wraps `storage`.
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// Returns the wrapped storage provider.
+    pub fn inner(&self) -> &S {
+        &self.storage
+    }
+
+    /// Returns the wrapped storage provider, consuming the wrapper.
+    pub fn into_inner(self) -> S {
+        self.storage
+    }
+}
+
+/// Implements [`StorageProvider`] for [`InstrumentedStorage`] and
+/// [`AsyncStorageProvider`] for [`InstrumentedAsyncStorage`] from a list of
+/// method signatures.
+///
+/// Each entry is written without the receiver and with the `Ok` type as its
+/// return type, and each generic bound names a trait in [`traits`] without
+/// the `VERSION` parameter. Both traits have the same fallible methods, so
+/// one list covers both wrappers.
+macro_rules! instrumented_storage {
+    ($(
+        $(#[$attr:meta])*
+        fn $method:ident $(<$($generic:ident: $bound:ident),* $(,)?>)? (
+            $($arg:ident: $ty:ty),* $(,)?
+        ) -> $ok:ty;
+    )*) => {
+        impl<const VERSION: u16, S: StorageProvider<VERSION>> StorageProvider<VERSION>
+            for InstrumentedStorage<S>
+        {
+            type Error = S::Error;
+
+            fn supports_future_epoch_messages(&self) -> bool {
+                StorageProvider::<VERSION>::supports_future_epoch_messages(&self.storage)
+            }
+
+            $(
+                $(#[$attr])*
+                fn $method<$($($generic: traits::$bound<VERSION>),*)?>(
+                    &self,
+                    $($arg: $ty),*
+                ) -> Result<$ok, Self::Error> {
+                    call(stringify!($method), || {
+                        StorageProvider::<VERSION>::$method::<$($($generic),*)?>(
+                            &self.storage,
+                            $($arg),*
+                        )
+                    })
+                }
+            )*
+        }
+
+        impl<const VERSION: u16, S: AsyncStorageProvider<VERSION>> AsyncStorageProvider<VERSION>
+            for InstrumentedAsyncStorage<S>
+        {
+            type Error = S::Error;
+
+            fn supports_future_epoch_messages(&self) -> bool {
+                AsyncStorageProvider::<VERSION>::supports_future_epoch_messages(&self.storage)
+            }
+
+            $(
+                $(#[$attr])*
+                fn $method<$($($generic: traits::$bound<VERSION> + Send + Sync),*)?>(
+                    &self,
+                    $($arg: $ty),*
+                ) -> impl Future<Output = Result<$ok, Self::Error>> + Send {
+                    call_async(
+                        stringify!($method),
+                        AsyncStorageProvider::<VERSION>::$method::<$($($generic),*)?>(
+                            &self.storage,
+                            $($arg),*
+                        ),
+                    )
+                }
+            )*
+        }
+    };
+}
+
+instrumented_storage! {
+    fn begin_transaction() -> ();
+    fn commit_transaction() -> ();
+    fn rollback_transaction() -> ();
+    fn write_mls_join_config<GroupId: GroupId, MlsGroupJoinConfig: MlsGroupJoinConfig>(
+        group_id: &GroupId,
+        config: &MlsGroupJoinConfig,
+    ) -> ();
+    fn append_own_leaf_node<GroupId: GroupId, LeafNode: LeafNode>(
+        group_id: &GroupId,
+        leaf_node: &LeafNode,
+    ) -> ();
+    fn queue_proposal<GroupId: GroupId, ProposalRef: ProposalRef, QueuedProposal: QueuedProposal>(
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+        proposal: &QueuedProposal,
+    ) -> ();
+    fn write_tree<GroupId: GroupId, TreeSync: TreeSync>(group_id: &GroupId, tree: &TreeSync) -> ();
+    fn write_interim_transcript_hash<
+        GroupId: GroupId,
+        InterimTranscriptHash: InterimTranscriptHash,
+    >(
+        group_id: &GroupId,
+        interim_transcript_hash: &InterimTranscriptHash,
+    ) -> ();
+    fn write_context<GroupId: GroupId, GroupContext: GroupContext>(
+        group_id: &GroupId,
+        group_context: &GroupContext,
+    ) -> ();
+    fn write_confirmation_tag<GroupId: GroupId, ConfirmationTag: ConfirmationTag>(
+        group_id: &GroupId,
+        confirmation_tag: &ConfirmationTag,
+    ) -> ();
+    fn write_group_state<GroupState: GroupState, GroupId: GroupId>(
+        group_id: &GroupId,
+        group_state: &GroupState,
+    ) -> ();
+    fn write_message_secrets<GroupId: GroupId, MessageSecrets: MessageSecrets>(
+        group_id: &GroupId,
+        message_secrets: &MessageSecrets,
+    ) -> ();
+    fn write_resumption_psk_store<GroupId: GroupId, ResumptionPskStore: ResumptionPskStore>(
+        group_id: &GroupId,
+        resumption_psk_store: &ResumptionPskStore,
+    ) -> ();
+    fn write_own_leaf_index<GroupId: GroupId, LeafNodeIndex: LeafNodeIndex>(
+        group_id: &GroupId,
+        own_leaf_index: &LeafNodeIndex,
+    ) -> ();
+    fn write_group_epoch_secrets<GroupId: GroupId, GroupEpochSecrets: GroupEpochSecrets>(
+        group_id: &GroupId,
+        group_epoch_secrets: &GroupEpochSecrets,
+    ) -> ();
+    fn write_future_epoch_messages<GroupId: GroupId, FutureEpochMessages: FutureEpochMessages>(
+        group_id: &GroupId,
+        future_epoch_messages: &FutureEpochMessages,
+    ) -> ();
+    #[cfg(feature = "extensions-draft")]
+    fn write_application_export_tree<
+        GroupId: GroupId,
+        ApplicationExportTree: ApplicationExportTree,
+    >(
+        group_id: &GroupId,
+        application_export_tree: &ApplicationExportTree,
+    ) -> ();
+    #[cfg(feature = "virtual-clients-draft")]
+    fn write_vc_emulation_epoch_state<
+        EpochId: VcEpochId,
+        VcEmulationEpochState: VcEmulationEpochState,
+    >(
+        epoch_id: &EpochId,
+        vc_emulation_epoch_state: &VcEmulationEpochState,
+    ) -> ();
+    #[cfg(feature = "virtual-clients-draft")]
+    fn write_vc_emulation_bindings<GroupId: GroupId, VcEmulationBindings: VcEmulationBindings>(
+        group_id: &GroupId,
+        bindings: &VcEmulationBindings,
+    ) -> ();
+    #[cfg(feature = "virtual-clients-draft")]
+    fn write_vc_operation_tree<EpochId: VcEpochId, VcOperationTree: VcOperationTree>(
+        epoch_id: &EpochId,
+        vc_operation_tree: &VcOperationTree,
+    ) -> ();
+    #[cfg(feature = "virtual-clients-draft")]
+    fn write_retained_key_package_material_batch<
+        EpochId: VcEpochId,
+        VcOperationTree: VcOperationTree,
+        KeyPackageRef: HashReference,
+        RetainedKeyPackageMaterial: RetainedKeyPackageMaterial,
+    >(
+        epoch_id: &EpochId,
+        operation_tree: &VcOperationTree,
+        materials: &[(KeyPackageRef, RetainedKeyPackageMaterial)],
+    ) -> ();
+    fn write_signature_key_pair<
+        SignaturePublicKey: SignaturePublicKey,
+        SignatureKeyPair: SignatureKeyPair,
+    >(
+        public_key: &SignaturePublicKey,
+        signature_key_pair: &SignatureKeyPair,
+    ) -> ();
+    fn write_encryption_key_pair<EncryptionKey: EncryptionKey, HpkeKeyPair: HpkeKeyPair>(
+        public_key: &EncryptionKey,
+        key_pair: &HpkeKeyPair,
+    ) -> ();
+    fn write_encryption_epoch_key_pairs<
+        GroupId: GroupId,
+        EpochKey: EpochKey,
+        HpkeKeyPair: HpkeKeyPair,
+    >(
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+        key_pairs: &[HpkeKeyPair],
+    ) -> ();
+    fn write_key_package<HashReference: HashReference, KeyPackage: KeyPackage>(
+        hash_ref: &HashReference,
+        key_package: &KeyPackage,
+    ) -> ();
+    fn write_psk<PskId: PskId, PskBundle: PskBundle>(psk_id: &PskId, psk: &PskBundle) -> ();
+    fn mls_group_join_config<GroupId: GroupId, MlsGroupJoinConfig: MlsGroupJoinConfig>(
+        group_id: &GroupId,
+    ) -> Option<MlsGroupJoinConfig>;
+    fn own_leaf_nodes<GroupId: GroupId, LeafNode: LeafNode>(group_id: &GroupId) -> Vec<LeafNode>;
+    fn queued_proposal_refs<GroupId: GroupId, ProposalRef: ProposalRef>(
+        group_id: &GroupId,
+    ) -> Vec<ProposalRef>;
+    fn queued_proposals<GroupId: GroupId, ProposalRef: ProposalRef, QueuedProposal: QueuedProposal>(
+        group_id: &GroupId,
+    ) -> Vec<(ProposalRef, QueuedProposal)>;
+    fn tree<GroupId: GroupId, TreeSync: TreeSync>(group_id: &GroupId) -> Option<TreeSync>;
+    fn group_context<GroupId: GroupId, GroupContext: GroupContext>(
+        group_id: &GroupId,
+    ) -> Option<GroupContext>;
+    fn interim_transcript_hash<GroupId: GroupId, InterimTranscriptHash: InterimTranscriptHash>(
+        group_id: &GroupId,
+    ) -> Option<InterimTranscriptHash>;
+    fn confirmation_tag<GroupId: GroupId, ConfirmationTag: ConfirmationTag>(
+        group_id: &GroupId,
+    ) -> Option<ConfirmationTag>;
+    fn group_state<GroupState: GroupState, GroupId: GroupId>(
+        group_id: &GroupId,
+    ) -> Option<GroupState>;
+    fn message_secrets<GroupId: GroupId, MessageSecrets: MessageSecrets>(
+        group_id: &GroupId,
+    ) -> Option<MessageSecrets>;
+    fn resumption_psk_store<GroupId: GroupId, ResumptionPskStore: ResumptionPskStore>(
+        group_id: &GroupId,
+    ) -> Option<ResumptionPskStore>;
+    fn own_leaf_index<GroupId: GroupId, LeafNodeIndex: LeafNodeIndex>(
+        group_id: &GroupId,
+    ) -> Option<LeafNodeIndex>;
+    fn group_epoch_secrets<GroupId: GroupId, GroupEpochSecrets: GroupEpochSecrets>(
+        group_id: &GroupId,
+    ) -> Option<GroupEpochSecrets>;
+    fn signature_key_pair<
+        SignaturePublicKey: SignaturePublicKey,
+        SignatureKeyPair: SignatureKeyPair,
+    >(
+        public_key: &SignaturePublicKey,
+    ) -> Option<SignatureKeyPair>;
+    fn encryption_key_pair<HpkeKeyPair: HpkeKeyPair, EncryptionKey: EncryptionKey>(
+        public_key: &EncryptionKey,
+    ) -> Option<HpkeKeyPair>;
+    fn encryption_epoch_key_pairs<GroupId: GroupId, EpochKey: EpochKey, HpkeKeyPair: HpkeKeyPair>(
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> Vec<HpkeKeyPair>;
+    fn key_package<KeyPackageRef: HashReference, KeyPackage: KeyPackage>(
+        hash_ref: &KeyPackageRef,
+    ) -> Option<KeyPackage>;
+    fn psk<PskBundle: PskBundle, PskId: PskId>(psk_id: &PskId) -> Option<PskBundle>;
+    fn future_epoch_messages<GroupId: GroupId, FutureEpochMessages: FutureEpochMessages>(
+        group_id: &GroupId,
+    ) -> Option<FutureEpochMessages>;
+    #[cfg(feature = "extensions-draft")]
+    fn application_export_tree<GroupId: GroupId, ApplicationExportTree: ApplicationExportTree>(
+        group_id: &GroupId,
+    ) -> Option<ApplicationExportTree>;
+    #[cfg(feature = "virtual-clients-draft")]
+    fn vc_emulation_epoch_state<EpochId: VcEpochId, VcEmulationEpochState: VcEmulationEpochState>(
+        epoch_id: &EpochId,
+    ) -> Option<VcEmulationEpochState>;
+    #[cfg(feature = "virtual-clients-draft")]
+    fn vc_emulation_bindings<GroupId: GroupId, VcEmulationBindings: VcEmulationBindings>(
+        group_id: &GroupId,
+    ) -> Option<VcEmulationBindings>;
+    #[cfg(feature = "virtual-clients-draft")]
+    fn vc_operation_tree<EpochId: VcEpochId, VcOperationTree: VcOperationTree>(
+        epoch_id: &EpochId,
+    ) -> Option<VcOperationTree>;
+    #[cfg(feature = "virtual-clients-draft")]
+    fn retained_key_package_material<
+        KeyPackageRef: HashReference,
+        RetainedKeyPackageMaterial: RetainedKeyPackageMaterial,
+    >(
+        hash_ref: &KeyPackageRef,
+    ) -> Option<RetainedKeyPackageMaterial>;
+    #[cfg(feature = "virtual-clients-draft")]
+    fn has_retained_key_package_material_for_epoch<EpochId: VcEpochId>(epoch_id: &EpochId) -> bool;
+    fn remove_proposal<GroupId: GroupId, ProposalRef: ProposalRef>(
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+    ) -> ();
+    fn delete_own_leaf_nodes<GroupId: GroupId>(group_id: &GroupId) -> ();
+    fn delete_group_config<GroupId: GroupId>(group_id: &GroupId) -> ();
+    fn delete_tree<GroupId: GroupId>(group_id: &GroupId) -> ();
+    fn delete_confirmation_tag<GroupId: GroupId>(group_id: &GroupId) -> ();
+    fn delete_group_state<GroupId: GroupId>(group_id: &GroupId) -> ();
+    fn delete_context<GroupId: GroupId>(group_id: &GroupId) -> ();
+    fn delete_interim_transcript_hash<GroupId: GroupId>(group_id: &GroupId) -> ();
+    fn delete_message_secrets<GroupId: GroupId>(group_id: &GroupId) -> ();
+    fn delete_all_resumption_psk_secrets<GroupId: GroupId>(group_id: &GroupId) -> ();
+    fn delete_own_leaf_index<GroupId: GroupId>(group_id: &GroupId) -> ();
+    fn delete_group_epoch_secrets<GroupId: GroupId>(group_id: &GroupId) -> ();
+    fn clear_proposal_queue<GroupId: GroupId, ProposalRef: ProposalRef>(group_id: &GroupId) -> ();
+    fn delete_signature_key_pair<SignaturePublicKey: SignaturePublicKey>(
+        public_key: &SignaturePublicKey,
+    ) -> ();
+    fn delete_encryption_key_pair<EncryptionKey: EncryptionKey>(public_key: &EncryptionKey) -> ();
+    fn delete_encryption_epoch_key_pairs<GroupId: GroupId, EpochKey: EpochKey>(
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> ();
+    fn delete_key_package<KeyPackageRef: HashReference>(hash_ref: &KeyPackageRef) -> ();
+    fn delete_psk<PskKey: PskId>(psk_id: &PskKey) -> ();
+    fn delete_future_epoch_messages<GroupId: GroupId>(group_id: &GroupId) -> ();
+    #[cfg(feature = "extensions-draft")]
+    fn delete_application_export_tree<
+        GroupId: GroupId,
+        ApplicationExportTree: ApplicationExportTree,
+    >(
+        group_id: &GroupId,
+    ) -> ();
+    #[cfg(feature = "virtual-clients-draft")]
+    fn delete_vc_emulation_state_if_unreferenced<EpochId: VcEpochId>(epoch_id: &EpochId) -> bool;
+    #[cfg(feature = "virtual-clients-draft")]
+    fn delete_vc_emulation_bindings<GroupId: GroupId>(group_id: &GroupId) -> ();
+    #[cfg(feature = "virtual-clients-draft")]
+    fn delete_retained_key_package_material<KeyPackageRef: HashReference>(
+        hash_ref: &KeyPackageRef,
+    ) -> ();
+}
+
+impl<const VERSION: u16, S: MigrationLog<VERSION>> MigrationLog<VERSION>
+    for InstrumentedStorage<S>
+{
+    fn group_migrated<GroupId: traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+        name: &str,
+    ) -> Result<bool, Self::Error> {
+        call("group_migrated", || {
+            self.storage.group_migrated(group_id, name)
+        })
+    }
+
+    fn record_group_migration<GroupId: traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+        name: &str,
+    ) -> Result<(), Self::Error> {
+        call("record_group_migration", || {
+            self.storage.record_group_migration(group_id, name)
+        })
+    }
+
+    fn key_package_migrated<KeyPackageRef: traits::HashReference<VERSION>>(
+        &self,
+        hash_ref: &KeyPackageRef,
+        name: &str,
+    ) -> Result<bool, Self::Error> {
+        call("key_package_migrated", || {
+            self.storage.key_package_migrated(hash_ref, name)
+        })
+    }
+
+    fn record_key_package_migration<KeyPackageRef: traits::HashReference<VERSION>>(
+        &self,
+        hash_ref: &KeyPackageRef,
+        name: &str,
+    ) -> Result<(), Self::Error> {
+        call("record_key_package_migration", || {
+            self.storage.record_key_package_migration(hash_ref, name)
+        })
+    }
+}
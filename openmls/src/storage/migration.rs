@@ -0,0 +1,231 @@
+//! Migration from the OpenMLS 0.8.1 storage format
+//!
+//! OpenMLS 0.8.1 serialized the enums [`ExtensionType`], [`Extension`],
+//! [`CredentialType`], [`ProposalType`] and [`Proposal`] with the position of
+//! each variant as its tag. The current format uses fixed storage tags
+//! instead, so that adding variants does not change the stored values. The
+//! two formats only differ for codecs that are not self-describing, such as
+//! `bincode` or `postcard`. Self-describing codecs, such as the `serde_json`
+//! codec of the `MemoryStorage`, store the variant names in both formats.
+//!
+//! Since 0.8.1, fields were also added to the stored message secrets.
+//! Self-describing codecs fill in their defaults, but other codecs can not
+//! read these values either. The join configuration reads its unversioned
+//! 0.8.1 format directly, and the migration rewrites it with the current
+//! format version.
+//!
+//! The functions in this module read the affected values in the 0.8.1 format
+//! and write them back in the current format. The values are read with
+//! wrapper types that decode the 0.8.1 format explicitly, so reading values
+//! elsewhere is not affected by a running migration. This allows upgrading a
+//! storage written by OpenMLS 0.8.1 in place, without enabling the
+//! `0-8-1-storage-format` feature:
+//!
+//! - [`migrate_group_from_0_8_1`] migrates the state of a group,
+//! - [`migrate_key_package_from_0_8_1`] migrates a key package bundle.
+//!
+//! The migration must run for every group and key package before they are
+//! used with the current format, and the crate must be built with the same
+//! features as the version that wrote the values. Reading a value that is
+//! already stored in the current format in the 0.8.1 format fails or corrupts
+//! it, and the two formats can not be told apart reliably. The migration is
+//! therefore recorded for each group and key package in the [`MigrationLog`]
+//! of the storage, within the same transaction as the rewritten values, and
+//! values that were already migrated are left unchanged. Groups and key
+//! packages that are created after the upgrade are written in the current
+//! format and must not be migrated.
+//!
+//! [`ExtensionType`]: crate::extensions::ExtensionType
+//! [`Extension`]: crate::extensions::Extension
+//! [`CredentialType`]: crate::credentials::CredentialType
+//! [`ProposalType`]: crate::messages::proposals::ProposalType
+//! [`Proposal`]: crate::messages::proposals::Proposal
+
+mod format_0_8_1;
+
+use openmls_traits::storage::{MigrationLog, CURRENT_VERSION};
+
+use self::format_0_8_1::Read0_8_1;
+use crate::{
+    ciphersuite::hash_ref::{KeyPackageRef, ProposalRef},
+    group::{
+        past_secrets::MessageSecretsStore, proposal_store::QueuedProposal, GroupContext, GroupId,
+        MlsGroupJoinConfig, MlsGroupState,
+    },
+    prelude::KeyPackageBundle,
+    treesync::{LeafNode, TreeSync},
+};
+
+/// The name of the migration from the 0.8.1 storage format in the
+/// [`MigrationLog`].
+pub const MIGRATION_FROM_0_8_1: &str = "openmls-0.8.1-storage-format";
+
+/// Rewrites the state of the group with the given `group_id` from the 0.8.1
+/// storage format to the current format.
+///
+/// The tree, group context, message secrets, group state, join configuration,
+/// own leaf nodes and queued proposals of the group are rewritten in one
+/// storage transaction, together with the record of the migration in the
+/// [`MigrationLog`]. The other values of the group are stored in the same
+/// format by both versions and are left unchanged. See the
+/// [module documentation](self) for when to call this function.
+///
+/// Returns `false` without touching the group if it was already migrated.
+pub fn migrate_group_from_0_8_1<Storage: MigrationLog<CURRENT_VERSION>>(
+    storage: &Storage,
+    group_id: &GroupId,
+) -> Result<bool, Storage::Error> {
+    super::transaction(
+        storage,
+        || {
+            if storage.group_migrated(group_id, MIGRATION_FROM_0_8_1)? {
+                return Ok(false);
+            }
+
+            let tree: Option<Read0_8_1<TreeSync>> = storage.tree(group_id)?;
+            let context: Option<Read0_8_1<GroupContext>> = storage.group_context(group_id)?;
+            let message_secrets: Option<Read0_8_1<MessageSecretsStore>> =
+                storage.message_secrets(group_id)?;
+            let group_state: Option<Read0_8_1<MlsGroupState>> = storage.group_state(group_id)?;
+            let join_config: Option<MlsGroupJoinConfig> =
+                storage.mls_group_join_config(group_id)?;
+            let own_leaf_nodes: Vec<Read0_8_1<LeafNode>> = storage.own_leaf_nodes(group_id)?;
+            let proposals: Vec<(ProposalRef, Read0_8_1<QueuedProposal>)> =
+                storage.queued_proposals(group_id)?;
+
+            if let Some(tree) = tree {
+                storage.write_tree(group_id, &tree.0)?;
+            }
+            if let Some(context) = context {
+                storage.write_context(group_id, &context.0)?;
+            }
+            if let Some(message_secrets) = message_secrets {
+                storage.write_message_secrets(group_id, &message_secrets.0)?;
+            }
+            if let Some(group_state) = group_state {
+                storage.write_group_state(group_id, &group_state.0)?;
+            }
+            if let Some(join_config) = join_config {
+                storage.write_mls_join_config(group_id, &join_config)?;
+            }
+
+            // The own leaf nodes and proposals are appended, so they are
+            // removed first and then written in their original order.
+            storage.delete_own_leaf_nodes(group_id)?;
+            for leaf_node in &own_leaf_nodes {
+                storage.append_own_leaf_node(group_id, &leaf_node.0)?;
+            }
+            storage.clear_proposal_queue::<GroupId, ProposalRef>(group_id)?;
+            for (proposal_ref, proposal) in &proposals {
+                storage.queue_proposal(group_id, proposal_ref, &proposal.0)?;
+            }
+
+            storage.record_group_migration(group_id, MIGRATION_FROM_0_8_1)?;
+            Ok(true)
+        },
+        |e| e,
+    )
+}
+
+/// Rewrites the key package bundle with the given `key_package_ref` from the
+/// 0.8.1 storage format to the current format.
+///
+/// The key package bundle is rewritten in one storage transaction, together
+/// with the record of the migration in the [`MigrationLog`]. See the
+/// [module documentation](self) for when to call this function.
+///
+/// Returns `false` without touching the key package bundle if it was already
+/// migrated.
+pub fn migrate_key_package_from_0_8_1<Storage: MigrationLog<CURRENT_VERSION>>(
+    storage: &Storage,
+    key_package_ref: &KeyPackageRef,
+) -> Result<bool, Storage::Error> {
+    super::transaction(
+        storage,
+        || {
+            if storage.key_package_migrated(key_package_ref, MIGRATION_FROM_0_8_1)? {
+                return Ok(false);
+            }
+
+            let key_package_bundle: Option<Read0_8_1<KeyPackageBundle>> =
+                storage.key_package(key_package_ref)?;
+            if let Some(key_package_bundle) = key_package_bundle {
+                storage.write_key_package(key_package_ref, &key_package_bundle.0)?;
+            }
+
+            storage.record_key_package_migration(key_package_ref, MIGRATION_FROM_0_8_1)?;
+            Ok(true)
+        },
+        |e| e,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use openmls_rust_crypto::OpenMlsRustCrypto;
+    use openmls_traits::{storage::StorageProvider as _, types::Ciphersuite, OpenMlsProvider};
+
+    use super::*;
+    use crate::{
+        group::{mls_group::tests_and_kats::utils::setup_client, MlsGroup, MlsGroupCreateConfig},
+        prelude::{KeyPackageBuilder, LeafNodeParameters},
+    };
+
+    const CIPHERSUITE: Ciphersuite =
+        Ciphersuite::MLS_128_DHKEMX25519_CHACHA20POLY1305_SHA256_Ed25519;
+
+    #[test]
+    fn migrate_group() {
+        let provider = OpenMlsRustCrypto::default();
+        let (credential_with_key, _kpb, signer, _pk) =
+            setup_client("Alice", CIPHERSUITE, &provider);
+
+        let mut group = MlsGroup::new(
+            &provider,
+            &signer,
+            &MlsGroupCreateConfig::builder()
+                .ciphersuite(CIPHERSUITE)
+                .build(),
+            credential_with_key,
+        )
+        .unwrap();
+        group
+            .propose_self_update(&provider, &signer, LeafNodeParameters::default())
+            .unwrap();
+
+        // The memory storage uses a self-describing codec, so the group is
+        // rewritten unchanged. The migration is only applied once.
+        assert!(migrate_group_from_0_8_1(provider.storage(), group.group_id()).unwrap());
+        assert!(!migrate_group_from_0_8_1(provider.storage(), group.group_id()).unwrap());
+
+        let loaded = MlsGroup::load(provider.storage(), group.group_id())
+            .unwrap()
+            .unwrap();
+        assert_eq!(group, loaded);
+    }
+
+    #[test]
+    fn migrate_key_package() {
+        let provider = OpenMlsRustCrypto::default();
+        let (credential_with_key, _kpb, signer, _pk) =
+            setup_client("Alice", CIPHERSUITE, &provider);
+
+        let key_package_bundle = KeyPackageBuilder::new()
+            .build(CIPHERSUITE, &provider, &signer, credential_with_key)
+            .unwrap();
+        let key_package_ref = key_package_bundle
+            .key_package()
+            .hash_ref(provider.crypto())
+            .unwrap();
+
+        assert!(migrate_key_package_from_0_8_1(provider.storage(), &key_package_ref).unwrap());
+        assert!(!migrate_key_package_from_0_8_1(provider.storage(), &key_package_ref).unwrap());
+
+        let loaded: KeyPackageBundle = provider
+            .storage()
+            .key_package(&key_package_ref)
+            .unwrap()
+            .unwrap();
+        assert_eq!(key_package_bundle.key_package(), loaded.key_package());
+    }
+}
@@ -0,0 +1,398 @@
+//! Reading values in the 0.8.1 storage format.
+//!
+//! [`Read0_8_1`] wraps the types that the migration reads from the storage.
+//! With codecs that are not self-describing, it reads them through
+//! [`Deserializer0_8_1`], which wraps the deserializer of the codec and
+//! rewrites what changed since 0.8.1 while the value is deserialized:
+//!
+//! - The numeric tags of the enums that got fixed storage tags are mapped from
+//!   the position of the variant to its storage tag.
+//! - Structs that got fields since 0.8.1 are read with their 0.8.1 fields
+//!   only, and the added fields take their defaults.
+//!
+//! Values are read unchanged with self-describing codecs, which store the same
+//! names in both formats.
+
+use std::fmt;
+
+use openmls_traits::storage::{traits, Entity, CURRENT_VERSION};
+use serde::{
+    de::{self, DeserializeSeed, IntoDeserializer},
+    Deserialize, Deserializer, Serialize,
+};
+
+use crate::{
+    credentials::CredentialType,
+    extensions::{Extension, ExtensionType},
+    messages::proposals::{Proposal, ProposalType},
+};
+
+/// Returns the mapping from the numeric tags of the enum with the given name
+/// in the 0.8.1 format to its storage tags, if the enum got storage tags
+/// since.
+fn storage_tags(enum_name: &str) -> Option<fn(u32) -> Option<u32>> {
+    match enum_name {
+        "ExtensionType" => Some(ExtensionType::storage_tag_from_legacy_tag),
+        "Extension" => Some(Extension::storage_tag_from_legacy_tag),
+        "CredentialType" => Some(CredentialType::storage_tag_from_legacy_tag),
+        "ProposalType" => Some(ProposalType::storage_tag_from_legacy_tag),
+        "Proposal" => Some(Proposal::storage_tag_from_legacy_tag),
+        _ => None,
+    }
+}
+
+/// Returns the number of fields that the struct with the given name had in
+/// the 0.8.1 format, if fields were added since.
+fn fields_0_8_1(struct_name: &str) -> Option<usize> {
+    match struct_name {
+        // Without the `added_at` timestamp
+        "MessageSecrets" => Some(5),
+        _ => None,
+    }
+}
+
+/// A value that is read from the storage in the 0.8.1 format and written in
+/// the current format.
+pub(super) struct Read0_8_1<T>(pub(super) T);
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Read0_8_1<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            T::deserialize(deserializer).map(Self)
+        } else {
+            T::deserialize(Deserializer0_8_1(deserializer)).map(Self)
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for Read0_8_1<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<T: Entity<CURRENT_VERSION>> Entity<CURRENT_VERSION> for Read0_8_1<T> {}
+impl<T: traits::TreeSync<CURRENT_VERSION>> traits::TreeSync<CURRENT_VERSION> for Read0_8_1<T> {}
+impl<T: traits::GroupContext<CURRENT_VERSION>> traits::GroupContext<CURRENT_VERSION>
+    for Read0_8_1<T>
+{
+}
+impl<T: traits::MessageSecrets<CURRENT_VERSION>> traits::MessageSecrets<CURRENT_VERSION>
+    for Read0_8_1<T>
+{
+}
+impl<T: traits::GroupState<CURRENT_VERSION>> traits::GroupState<CURRENT_VERSION> for Read0_8_1<T> {}
+impl<T: traits::LeafNode<CURRENT_VERSION>> traits::LeafNode<CURRENT_VERSION> for Read0_8_1<T> {}
+impl<T: traits::QueuedProposal<CURRENT_VERSION>> traits::QueuedProposal<CURRENT_VERSION>
+    for Read0_8_1<T>
+{
+}
+impl<T: traits::KeyPackage<CURRENT_VERSION>> traits::KeyPackage<CURRENT_VERSION> for Read0_8_1<T> {}
+
+/// Wraps the [`Deserializer`] of a codec that is not self-describing, so that
+/// values are read in the 0.8.1 format. All deserializers, visitors and
+/// accessors that it hands out wrap the ones of the codec in turn.
+struct Deserializer0_8_1<D>(D);
+
+/// Forwards the `deserialize_*` methods of [`Deserializer0_8_1`] with a
+/// wrapped visitor.
+macro_rules! forward_deserialize {
+    ($($method:ident($($arg:ident: $ty:ty),*)),* $(,)?) => {
+        $(
+            fn $method<V: de::Visitor<'de>>(
+                self,
+                $($arg: $ty,)*
+                visitor: V,
+            ) -> Result<V::Value, Self::Error> {
+                self.0.$method($($arg,)* Visitor0_8_1::new(visitor))
+            }
+        )*
+    };
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for Deserializer0_8_1<D> {
+    type Error = D::Error;
+
+    forward_deserialize! {
+        deserialize_any(),
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_i16(),
+        deserialize_i32(),
+        deserialize_i64(),
+        deserialize_i128(),
+        deserialize_u8(),
+        deserialize_u16(),
+        deserialize_u32(),
+        deserialize_u64(),
+        deserialize_u128(),
+        deserialize_f32(),
+        deserialize_f64(),
+        deserialize_char(),
+        deserialize_str(),
+        deserialize_string(),
+        deserialize_bytes(),
+        deserialize_byte_buf(),
+        deserialize_option(),
+        deserialize_unit(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_newtype_struct(name: &'static str),
+        deserialize_seq(),
+        deserialize_tuple(len: usize),
+        deserialize_tuple_struct(name: &'static str, len: usize),
+        deserialize_map(),
+        deserialize_identifier(),
+        deserialize_ignored_any(),
+    }
+
+    fn deserialize_struct<V: de::Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        // Codecs that are not self-describing read as many fields as are
+        // requested. The visitor fills in the defaults of the others.
+        let fields = match fields_0_8_1(name) {
+            Some(len) => &fields[..len.min(fields.len())],
+            None => fields,
+        };
+        self.0
+            .deserialize_struct(name, fields, Visitor0_8_1::new(visitor))
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let visitor = Visitor0_8_1 {
+            visitor,
+            storage_tags: storage_tags(name),
+        };
+        self.0.deserialize_enum(name, variants, visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.0.is_human_readable()
+    }
+}
+
+/// Wraps a [`de::Visitor`], see [`Deserializer0_8_1`].
+struct Visitor0_8_1<V> {
+    visitor: V,
+    /// The mapping of the numeric tags if the visitor visits an enum that got
+    /// storage tags since 0.8.1.
+    storage_tags: Option<fn(u32) -> Option<u32>>,
+}
+
+impl<V> Visitor0_8_1<V> {
+    fn new(visitor: V) -> Self {
+        Self {
+            visitor,
+            storage_tags: None,
+        }
+    }
+}
+
+/// Forwards the `visit_*` methods of [`Visitor0_8_1`] for primitive values.
+macro_rules! forward_visit {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method<E: de::Error>(self, v: $ty) -> Result<Self::Value, E> {
+                self.visitor.$method(v)
+            }
+        )*
+    };
+}
+
+impl<'de, V: de::Visitor<'de>> de::Visitor<'de> for Visitor0_8_1<V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.visitor.expecting(formatter)
+    }
+
+    forward_visit! {
+        visit_bool(bool),
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f32(f32),
+        visit_f64(f64),
+        visit_char(char),
+        visit_str(&str),
+        visit_borrowed_str(&'de str),
+        visit_string(String),
+        visit_bytes(&[u8]),
+        visit_borrowed_bytes(&'de [u8]),
+        visit_byte_buf(Vec<u8>),
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.visitor.visit_none()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.visitor.visit_some(Deserializer0_8_1(deserializer))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.visitor.visit_unit()
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        self.visitor
+            .visit_newtype_struct(Deserializer0_8_1(deserializer))
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        self.visitor.visit_seq(Access0_8_1(seq))
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        self.visitor.visit_map(Access0_8_1(map))
+    }
+
+    fn visit_enum<A: de::EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        self.visitor.visit_enum(EnumAccess0_8_1 {
+            access: data,
+            storage_tags: self.storage_tags,
+        })
+    }
+}
+
+/// Wraps a [`DeserializeSeed`], see [`Deserializer0_8_1`].
+struct Seed0_8_1<S>(S);
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for Seed0_8_1<S> {
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.0.deserialize(Deserializer0_8_1(deserializer))
+    }
+}
+
+/// Reads the numeric tag of an enum variant in the 0.8.1 format and passes
+/// its storage tag to the wrapped [`DeserializeSeed`].
+struct TagSeed<S> {
+    seed: S,
+    storage_tags: fn(u32) -> Option<u32>,
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for TagSeed<S> {
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let tag = u32::deserialize(deserializer)?;
+        let storage_tag = (self.storage_tags)(tag).ok_or_else(|| {
+            <D::Error as de::Error>::custom(format!("unexpected tag {tag} in the 0.8.1 format"))
+        })?;
+        self.seed.deserialize(storage_tag.into_deserializer())
+    }
+}
+
+/// Wraps a [`de::SeqAccess`], [`de::MapAccess`] or [`de::VariantAccess`], see
+/// [`Deserializer0_8_1`].
+struct Access0_8_1<A>(A);
+
+impl<'de, A: de::SeqAccess<'de>> de::SeqAccess<'de> for Access0_8_1<A> {
+    type Error = A::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        self.0.next_element_seed(Seed0_8_1(seed))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.0.size_hint()
+    }
+}
+
+impl<'de, A: de::MapAccess<'de>> de::MapAccess<'de> for Access0_8_1<A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        self.0.next_key_seed(Seed0_8_1(seed))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0.next_value_seed(Seed0_8_1(seed))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.0.size_hint()
+    }
+}
+
+impl<'de, A: de::VariantAccess<'de>> de::VariantAccess<'de> for Access0_8_1<A> {
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        self.0.unit_variant()
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        self.0.newtype_variant_seed(Seed0_8_1(seed))
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0.tuple_variant(len, Visitor0_8_1::new(visitor))
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0.struct_variant(fields, Visitor0_8_1::new(visitor))
+    }
+}
+
+/// Wraps a [`de::EnumAccess`], see [`Deserializer0_8_1`].
+struct EnumAccess0_8_1<A> {
+    access: A,
+    storage_tags: Option<fn(u32) -> Option<u32>>,
+}
+
+impl<'de, A: de::EnumAccess<'de>> de::EnumAccess<'de> for EnumAccess0_8_1<A> {
+    type Error = A::Error;
+    type Variant = Access0_8_1<A::Variant>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let (value, variant) = match self.storage_tags {
+            Some(storage_tags) => self.access.variant_seed(TagSeed { seed, storage_tags })?,
+            None => self.access.variant_seed(Seed0_8_1(seed))?,
+        };
+        Ok((value, Access0_8_1(variant)))
+    }
+}
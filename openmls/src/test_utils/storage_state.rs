@@ -1,3 +1,4 @@
+use crate::group::mls_group::future_epoch_messages::FutureEpochMessages;
 use crate::prelude::{hash_ref::ProposalRef, past_secrets::MessageSecretsStore, *};
 
 use crate::schedule::psk::store::ResumptionPskStore;
@@ -20,6 +21,7 @@ pub struct NonProposalGroupStorageState {
     resumption_psk_secrets: Option<ResumptionPskStore>,
     own_leaf_index: Option<LeafNodeIndex>,
     group_epoch_secrets: Option<GroupEpochSecrets>,
+    future_epoch_messages: Option<FutureEpochMessages>,
 }
 
 impl NonProposalGroupStorageState {
@@ -50,6 +52,8 @@ impl NonProposalGroupStorageState {
 
         let group_epoch_secrets = store.group_epoch_secrets(group_id).unwrap();
 
+        let future_epoch_messages = store.future_epoch_messages(group_id).unwrap();
+
         Self {
             own_leaf_nodes,
             group_config,
@@ -62,6 +66,7 @@ impl NonProposalGroupStorageState {
             resumption_psk_secrets,
             own_leaf_index,
             group_epoch_secrets,
+            future_epoch_messages,
         }
     }
 }
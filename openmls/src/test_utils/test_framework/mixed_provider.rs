@@ -0,0 +1,1163 @@
+//! A provider whose crypto and storage backends are chosen at runtime.
+//!
+//! The [`MlsGroupTestSetup`](super::MlsGroupTestSetup) uses a single provider
+//! type for all clients. With the [`MixedProvider`], each client can still use
+//! a different crypto provider and storage backend, so that the framework
+//! can check that clients on different backends agree on the group state.
+//!
+//! The libcrux crypto provider is available with the `libcrux-provider`
+//! feature, and the SQLite storage with the `sqlite-provider` feature.
+
+use openmls_memory_storage::{MemoryStorage, MemoryStorageError};
+use openmls_rust_crypto::RustCrypto;
+#[cfg(all(feature = "sqlite-provider", not(target_arch = "wasm32")))]
+use openmls_sqlite_storage::{Codec, Connection, SqliteStorageProvider};
+use openmls_traits::{
+    crypto::OpenMlsCrypto,
+    random::OpenMlsRand,
+    storage::{traits, StorageProvider, CURRENT_VERSION},
+    types::*,
+    OpenMlsProvider,
+};
+use tls_codec::SecretVLBytes;
+
+/// The crypto provider of a [`MixedProvider`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CryptoBackend {
+    RustCrypto,
+    #[cfg(feature = "libcrux-provider")]
+    Libcrux,
+}
+
+impl CryptoBackend {
+    /// All crypto backends enabled in this build.
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::RustCrypto,
+            #[cfg(feature = "libcrux-provider")]
+            Self::Libcrux,
+        ]
+    }
+}
+
+/// The storage backend of a [`MixedProvider`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageBackend {
+    Memory,
+    #[cfg(all(feature = "sqlite-provider", not(target_arch = "wasm32")))]
+    Sqlite,
+}
+
+impl StorageBackend {
+    /// All storage backends enabled in this build.
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::Memory,
+            #[cfg(all(feature = "sqlite-provider", not(target_arch = "wasm32")))]
+            Self::Sqlite,
+        ]
+    }
+}
+
+/// An [`OpenMlsProvider`] with a [`CryptoBackend`] and a [`StorageBackend`].
+pub struct MixedProvider {
+    crypto: MixedCrypto,
+    storage: MixedStorage,
+}
+
+impl MixedProvider {
+    /// Create a provider with the given backends.
+    pub fn new(crypto: CryptoBackend, storage: StorageBackend) -> Self {
+        let crypto = match crypto {
+            CryptoBackend::RustCrypto => MixedCrypto::RustCrypto(RustCrypto::default()),
+            #[cfg(feature = "libcrux-provider")]
+            CryptoBackend::Libcrux => MixedCrypto::Libcrux(
+                openmls_libcrux_crypto::CryptoProvider::new()
+                    .expect("Error creating the libcrux provider."),
+            ),
+        };
+        let storage = match storage {
+            StorageBackend::Memory => MixedStorage::Memory(MemoryStorage::default()),
+            #[cfg(all(feature = "sqlite-provider", not(target_arch = "wasm32")))]
+            StorageBackend::Sqlite => {
+                let connection =
+                    Connection::open_in_memory().expect("Error opening the SQLite connection.");
+                let mut storage = SqliteStorageProvider::new(connection);
+                storage
+                    .run_migrations()
+                    .expect("Error running the SQLite migrations.");
+                MixedStorage::Sqlite(storage)
+            }
+        };
+
+        Self { crypto, storage }
+    }
+
+    /// The provider of the client with index `index`, cycling through all
+    /// combinations of the enabled backends.
+    pub fn for_client(index: usize) -> Self {
+        let crypto_backends = CryptoBackend::all();
+        let storage_backends = StorageBackend::all();
+        let crypto = crypto_backends[index % crypto_backends.len()];
+        let storage = storage_backends[(index / crypto_backends.len()) % storage_backends.len()];
+
+        Self::new(crypto, storage)
+    }
+
+    /// Returns `true` if all enabled crypto backends support `ciphersuite`.
+    pub fn supported_by_all_backends(ciphersuite: Ciphersuite) -> bool {
+        CryptoBackend::all().into_iter().all(|crypto| {
+            Self::new(crypto, StorageBackend::Memory)
+                .crypto()
+                .supports(ciphersuite)
+                .is_ok()
+        })
+    }
+}
+
+impl OpenMlsProvider for MixedProvider {
+    type CryptoProvider = MixedCrypto;
+    type RandProvider = MixedCrypto;
+    type StorageProvider = MixedStorage;
+
+    fn storage(&self) -> &Self::StorageProvider {
+        &self.storage
+    }
+
+    fn crypto(&self) -> &Self::CryptoProvider {
+        &self.crypto
+    }
+
+    fn rand(&self) -> &Self::RandProvider {
+        &self.crypto
+    }
+}
+
+/// The crypto and randomness provider of a [`MixedProvider`].
+pub enum MixedCrypto {
+    RustCrypto(RustCrypto),
+    #[cfg(feature = "libcrux-provider")]
+    Libcrux(openmls_libcrux_crypto::CryptoProvider),
+}
+
+/// Call `$method` on the crypto provider of the [`MixedCrypto`] `$self`.
+macro_rules! delegate_crypto {
+    ($self:ident, $method:ident($($arg:expr),*)) => {
+        match $self {
+            MixedCrypto::RustCrypto(crypto) => crypto.$method($($arg),*),
+            #[cfg(feature = "libcrux-provider")]
+            MixedCrypto::Libcrux(crypto) => crypto.$method($($arg),*),
+        }
+    };
+}
+
+impl OpenMlsCrypto for MixedCrypto {
+    fn supports(&self, ciphersuite: Ciphersuite) -> Result<(), CryptoError> {
+        delegate_crypto!(self, supports(ciphersuite))
+    }
+
+    fn supported_ciphersuites(&self) -> Vec<Ciphersuite> {
+        delegate_crypto!(self, supported_ciphersuites())
+    }
+
+    fn hkdf_extract(
+        &self,
+        hash_type: HashType,
+        salt: &[u8],
+        ikm: &[u8],
+    ) -> Result<SecretVLBytes, CryptoError> {
+        delegate_crypto!(self, hkdf_extract(hash_type, salt, ikm))
+    }
+
+    fn hmac(
+        &self,
+        hash_type: HashType,
+        key: &[u8],
+        message: &[u8],
+    ) -> Result<SecretVLBytes, CryptoError> {
+        delegate_crypto!(self, hmac(hash_type, key, message))
+    }
+
+    fn hkdf_expand(
+        &self,
+        hash_type: HashType,
+        prk: &[u8],
+        info: &[u8],
+        okm_len: usize,
+    ) -> Result<SecretVLBytes, CryptoError> {
+        delegate_crypto!(self, hkdf_expand(hash_type, prk, info, okm_len))
+    }
+
+    fn hash(&self, hash_type: HashType, data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        delegate_crypto!(self, hash(hash_type, data))
+    }
+
+    fn aead_encrypt(
+        &self,
+        alg: AeadType,
+        key: &[u8],
+        data: &[u8],
+        nonce: &[u8],
+        aad: &[u8],
+    ) -> Result<Vec<u8>, CryptoError> {
+        delegate_crypto!(self, aead_encrypt(alg, key, data, nonce, aad))
+    }
+
+    fn aead_decrypt(
+        &self,
+        alg: AeadType,
+        key: &[u8],
+        ct_tag: &[u8],
+        nonce: &[u8],
+        aad: &[u8],
+    ) -> Result<Vec<u8>, CryptoError> {
+        delegate_crypto!(self, aead_decrypt(alg, key, ct_tag, nonce, aad))
+    }
+
+    fn signature_key_gen(&self, alg: SignatureScheme) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
+        delegate_crypto!(self, signature_key_gen(alg))
+    }
+
+    fn verify_signature(
+        &self,
+        alg: SignatureScheme,
+        data: &[u8],
+        pk: &[u8],
+        signature: &[u8],
+    ) -> Result<(), CryptoError> {
+        delegate_crypto!(self, verify_signature(alg, data, pk, signature))
+    }
+
+    fn sign(&self, alg: SignatureScheme, data: &[u8], key: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        delegate_crypto!(self, sign(alg, data, key))
+    }
+
+    fn hpke_seal(
+        &self,
+        config: HpkeConfig,
+        pk_r: &[u8],
+        info: &[u8],
+        aad: &[u8],
+        ptxt: &[u8],
+    ) -> Result<HpkeCiphertext, CryptoError> {
+        delegate_crypto!(self, hpke_seal(config, pk_r, info, aad, ptxt))
+    }
+
+    fn hpke_open(
+        &self,
+        config: HpkeConfig,
+        input: &HpkeCiphertext,
+        sk_r: &[u8],
+        info: &[u8],
+        aad: &[u8],
+    ) -> Result<Vec<u8>, CryptoError> {
+        delegate_crypto!(self, hpke_open(config, input, sk_r, info, aad))
+    }
+
+    fn hpke_setup_sender_and_export(
+        &self,
+        config: HpkeConfig,
+        pk_r: &[u8],
+        info: &[u8],
+        exporter_context: &[u8],
+        exporter_length: usize,
+    ) -> Result<(KemOutput, ExporterSecret), CryptoError> {
+        delegate_crypto!(
+            self,
+            hpke_setup_sender_and_export(config, pk_r, info, exporter_context, exporter_length)
+        )
+    }
+
+    fn hpke_setup_receiver_and_export(
+        &self,
+        config: HpkeConfig,
+        enc: &[u8],
+        sk_r: &[u8],
+        info: &[u8],
+        exporter_context: &[u8],
+        exporter_length: usize,
+    ) -> Result<ExporterSecret, CryptoError> {
+        delegate_crypto!(
+            self,
+            hpke_setup_receiver_and_export(
+                config,
+                enc,
+                sk_r,
+                info,
+                exporter_context,
+                exporter_length
+            )
+        )
+    }
+
+    fn derive_hpke_keypair(
+        &self,
+        config: HpkeConfig,
+        ikm: &[u8],
+    ) -> Result<HpkeKeyPair, CryptoError> {
+        delegate_crypto!(self, derive_hpke_keypair(config, ikm))
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn ff1_aes128_encrypt(&self, key: &[u8; 16], plaintext: u32) -> Result<u32, CryptoError> {
+        delegate_crypto!(self, ff1_aes128_encrypt(key, plaintext))
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn ff1_aes128_decrypt(&self, key: &[u8; 16], ciphertext: u32) -> Result<u32, CryptoError> {
+        delegate_crypto!(self, ff1_aes128_decrypt(key, ciphertext))
+    }
+}
+
+/// The error of the randomness provider of a [`MixedCrypto`].
+#[derive(Debug, thiserror::Error)]
+pub enum MixedRandError {
+    #[error(transparent)]
+    RustCrypto(#[from] openmls_rust_crypto::RandError),
+    #[cfg(feature = "libcrux-provider")]
+    #[error(transparent)]
+    Libcrux(#[from] openmls_libcrux_crypto::RandError),
+}
+
+impl OpenMlsRand for MixedCrypto {
+    type Error = MixedRandError;
+
+    fn random_array<const N: usize>(&self) -> Result<[u8; N], Self::Error> {
+        match self {
+            MixedCrypto::RustCrypto(crypto) => Ok(crypto.random_array()?),
+            #[cfg(feature = "libcrux-provider")]
+            MixedCrypto::Libcrux(crypto) => Ok(crypto.random_array()?),
+        }
+    }
+
+    fn random_vec(&self, len: usize) -> Result<Vec<u8>, Self::Error> {
+        match self {
+            MixedCrypto::RustCrypto(crypto) => Ok(crypto.random_vec(len)?),
+            #[cfg(feature = "libcrux-provider")]
+            MixedCrypto::Libcrux(crypto) => Ok(crypto.random_vec(len)?),
+        }
+    }
+}
+
+/// Serializes the values in the SQLite storage as JSON.
+#[cfg(all(feature = "sqlite-provider", not(target_arch = "wasm32")))]
+#[derive(Default)]
+pub struct JsonCodec;
+
+#[cfg(all(feature = "sqlite-provider", not(target_arch = "wasm32")))]
+impl Codec for JsonCodec {
+    type Error = serde_json::Error;
+
+    fn to_vec<T: serde::Serialize>(value: &T) -> Result<Vec<u8>, Self::Error> {
+        serde_json::to_vec(value)
+    }
+
+    fn from_slice<T: serde::de::DeserializeOwned>(slice: &[u8]) -> Result<T, Self::Error> {
+        serde_json::from_slice(slice)
+    }
+}
+
+#[cfg(all(feature = "sqlite-provider", not(target_arch = "wasm32")))]
+type SqliteStorage = SqliteStorageProvider<JsonCodec, Connection>;
+
+/// The storage provider of a [`MixedProvider`].
+pub enum MixedStorage {
+    Memory(MemoryStorage),
+    #[cfg(all(feature = "sqlite-provider", not(target_arch = "wasm32")))]
+    Sqlite(SqliteStorage),
+}
+
+/// The error of a [`MixedStorage`].
+#[derive(Debug, thiserror::Error)]
+pub enum MixedStorageError {
+    #[error(transparent)]
+    Memory(#[from] MemoryStorageError),
+    #[cfg(all(feature = "sqlite-provider", not(target_arch = "wasm32")))]
+    #[error(transparent)]
+    Sqlite(<SqliteStorage as StorageProvider<CURRENT_VERSION>>::Error),
+}
+
+/// Call `$method` on the storage backend of the [`MixedStorage`] `$self`.
+macro_rules! delegate {
+    ($self:ident, $method:ident$(::<$($generic:ty),*>)?($($arg:ident),*)) => {
+        match $self {
+            MixedStorage::Memory(storage) => {
+                StorageProvider::<CURRENT_VERSION>::$method$(::<$($generic),*>)?(storage, $($arg),*)
+                    .map_err(MixedStorageError::Memory)
+            }
+            #[cfg(all(feature = "sqlite-provider", not(target_arch = "wasm32")))]
+            MixedStorage::Sqlite(storage) => {
+                StorageProvider::<CURRENT_VERSION>::$method$(::<$($generic),*>)?(storage, $($arg),*)
+                    .map_err(MixedStorageError::Sqlite)
+            }
+        }
+    };
+}
+
+impl StorageProvider<CURRENT_VERSION> for MixedStorage {
+    type Error = MixedStorageError;
+
+    fn begin_transaction(&self) -> Result<(), Self::Error> {
+        delegate!(self, begin_transaction())
+    }
+
+    fn commit_transaction(&self) -> Result<(), Self::Error> {
+        delegate!(self, commit_transaction())
+    }
+
+    fn rollback_transaction(&self) -> Result<(), Self::Error> {
+        delegate!(self, rollback_transaction())
+    }
+
+    fn write_mls_join_config<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        config: &MlsGroupJoinConfig,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, write_mls_join_config(group_id, config))
+    }
+
+    fn append_own_leaf_node<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNode: traits::LeafNode<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        leaf_node: &LeafNode,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, append_own_leaf_node(group_id, leaf_node))
+    }
+
+    fn queue_proposal<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+        QueuedProposal: traits::QueuedProposal<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+        proposal: &QueuedProposal,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, queue_proposal(group_id, proposal_ref, proposal))
+    }
+
+    fn write_tree<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        TreeSync: traits::TreeSync<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        tree: &TreeSync,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, write_tree(group_id, tree))
+    }
+
+    fn write_interim_transcript_hash<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        InterimTranscriptHash: traits::InterimTranscriptHash<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        interim_transcript_hash: &InterimTranscriptHash,
+    ) -> Result<(), Self::Error> {
+        delegate!(
+            self,
+            write_interim_transcript_hash(group_id, interim_transcript_hash)
+        )
+    }
+
+    fn write_context<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupContext: traits::GroupContext<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_context: &GroupContext,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, write_context(group_id, group_context))
+    }
+
+    fn write_confirmation_tag<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ConfirmationTag: traits::ConfirmationTag<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        confirmation_tag: &ConfirmationTag,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, write_confirmation_tag(group_id, confirmation_tag))
+    }
+
+    fn write_group_state<
+        GroupState: traits::GroupState<CURRENT_VERSION>,
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_state: &GroupState,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, write_group_state(group_id, group_state))
+    }
+
+    fn write_message_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MessageSecrets: traits::MessageSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        message_secrets: &MessageSecrets,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, write_message_secrets(group_id, message_secrets))
+    }
+
+    fn write_resumption_psk_store<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ResumptionPskStore: traits::ResumptionPskStore<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        resumption_psk_store: &ResumptionPskStore,
+    ) -> Result<(), Self::Error> {
+        delegate!(
+            self,
+            write_resumption_psk_store(group_id, resumption_psk_store)
+        )
+    }
+
+    fn write_own_leaf_index<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNodeIndex: traits::LeafNodeIndex<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        own_leaf_index: &LeafNodeIndex,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, write_own_leaf_index(group_id, own_leaf_index))
+    }
+
+    fn write_group_epoch_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupEpochSecrets: traits::GroupEpochSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_epoch_secrets: &GroupEpochSecrets,
+    ) -> Result<(), Self::Error> {
+        delegate!(
+            self,
+            write_group_epoch_secrets(group_id, group_epoch_secrets)
+        )
+    }
+
+    fn supports_future_epoch_messages(&self) -> bool {
+        match self {
+            MixedStorage::Memory(storage) => {
+                StorageProvider::<CURRENT_VERSION>::supports_future_epoch_messages(storage)
+            }
+            #[cfg(all(feature = "sqlite-provider", not(target_arch = "wasm32")))]
+            MixedStorage::Sqlite(storage) => {
+                StorageProvider::<CURRENT_VERSION>::supports_future_epoch_messages(storage)
+            }
+        }
+    }
+
+    fn write_future_epoch_messages<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        FutureEpochMessages: traits::FutureEpochMessages<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        future_epoch_messages: &FutureEpochMessages,
+    ) -> Result<(), Self::Error> {
+        delegate!(
+            self,
+            write_future_epoch_messages(group_id, future_epoch_messages)
+        )
+    }
+
+    #[cfg(feature = "extensions-draft")]
+    fn write_application_export_tree<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ApplicationExportTree: traits::ApplicationExportTree<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        application_export_tree: &ApplicationExportTree,
+    ) -> Result<(), Self::Error> {
+        delegate!(
+            self,
+            write_application_export_tree(group_id, application_export_tree)
+        )
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn write_vc_emulation_epoch_state<
+        EpochId: traits::VcEpochId<CURRENT_VERSION>,
+        VcEmulationEpochState: traits::VcEmulationEpochState<CURRENT_VERSION>,
+    >(
+        &self,
+        epoch_id: &EpochId,
+        vc_emulation_epoch_state: &VcEmulationEpochState,
+    ) -> Result<(), Self::Error> {
+        delegate!(
+            self,
+            write_vc_emulation_epoch_state(epoch_id, vc_emulation_epoch_state)
+        )
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn write_vc_emulation_bindings<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        VcEmulationBindings: traits::VcEmulationBindings<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        bindings: &VcEmulationBindings,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, write_vc_emulation_bindings(group_id, bindings))
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn write_vc_operation_tree<
+        EpochId: traits::VcEpochId<CURRENT_VERSION>,
+        VcOperationTree: traits::VcOperationTree<CURRENT_VERSION>,
+    >(
+        &self,
+        epoch_id: &EpochId,
+        vc_operation_tree: &VcOperationTree,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, write_vc_operation_tree(epoch_id, vc_operation_tree))
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn write_retained_key_package_material_batch<
+        EpochId: traits::VcEpochId<CURRENT_VERSION>,
+        VcOperationTree: traits::VcOperationTree<CURRENT_VERSION>,
+        KeyPackageRef: traits::HashReference<CURRENT_VERSION>,
+        RetainedKeyPackageMaterial: traits::RetainedKeyPackageMaterial<CURRENT_VERSION>,
+    >(
+        &self,
+        epoch_id: &EpochId,
+        operation_tree: &VcOperationTree,
+        materials: &[(KeyPackageRef, RetainedKeyPackageMaterial)],
+    ) -> Result<(), Self::Error> {
+        delegate!(
+            self,
+            write_retained_key_package_material_batch(epoch_id, operation_tree, materials)
+        )
+    }
+
+    fn write_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION>,
+        SignatureKeyPair: traits::SignatureKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+        signature_key_pair: &SignatureKeyPair,
+    ) -> Result<(), Self::Error> {
+        delegate!(
+            self,
+            write_signature_key_pair(public_key, signature_key_pair)
+        )
+    }
+
+    fn write_encryption_key_pair<
+        EncryptionKey: traits::EncryptionKey<CURRENT_VERSION>,
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+        key_pair: &HpkeKeyPair,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, write_encryption_key_pair(public_key, key_pair))
+    }
+
+    fn write_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        EpochKey: traits::EpochKey<CURRENT_VERSION>,
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+        key_pairs: &[HpkeKeyPair],
+    ) -> Result<(), Self::Error> {
+        delegate!(
+            self,
+            write_encryption_epoch_key_pairs(group_id, epoch, leaf_index, key_pairs)
+        )
+    }
+
+    fn write_key_package<
+        HashReference: traits::HashReference<CURRENT_VERSION>,
+        KeyPackage: traits::KeyPackage<CURRENT_VERSION>,
+    >(
+        &self,
+        hash_ref: &HashReference,
+        key_package: &KeyPackage,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, write_key_package(hash_ref, key_package))
+    }
+
+    fn write_psk<
+        PskId: traits::PskId<CURRENT_VERSION>,
+        PskBundle: traits::PskBundle<CURRENT_VERSION>,
+    >(
+        &self,
+        psk_id: &PskId,
+        psk: &PskBundle,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, write_psk(psk_id, psk))
+    }
+
+    fn mls_group_join_config<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<MlsGroupJoinConfig>, Self::Error> {
+        delegate!(self, mls_group_join_config(group_id))
+    }
+
+    fn own_leaf_nodes<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNode: traits::LeafNode<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<LeafNode>, Self::Error> {
+        delegate!(self, own_leaf_nodes(group_id))
+    }
+
+    fn queued_proposal_refs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<ProposalRef>, Self::Error> {
+        delegate!(self, queued_proposal_refs(group_id))
+    }
+
+    fn queued_proposals<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+        QueuedProposal: traits::QueuedProposal<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<(ProposalRef, QueuedProposal)>, Self::Error> {
+        delegate!(self, queued_proposals(group_id))
+    }
+
+    fn tree<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        TreeSync: traits::TreeSync<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<TreeSync>, Self::Error> {
+        delegate!(self, tree(group_id))
+    }
+
+    fn group_context<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupContext: traits::GroupContext<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupContext>, Self::Error> {
+        delegate!(self, group_context(group_id))
+    }
+
+    fn interim_transcript_hash<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        InterimTranscriptHash: traits::InterimTranscriptHash<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<InterimTranscriptHash>, Self::Error> {
+        delegate!(self, interim_transcript_hash(group_id))
+    }
+
+    fn confirmation_tag<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ConfirmationTag: traits::ConfirmationTag<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<ConfirmationTag>, Self::Error> {
+        delegate!(self, confirmation_tag(group_id))
+    }
+
+    fn group_state<
+        GroupState: traits::GroupState<CURRENT_VERSION>,
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupState>, Self::Error> {
+        delegate!(self, group_state(group_id))
+    }
+
+    fn message_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MessageSecrets: traits::MessageSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<MessageSecrets>, Self::Error> {
+        delegate!(self, message_secrets(group_id))
+    }
+
+    fn resumption_psk_store<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ResumptionPskStore: traits::ResumptionPskStore<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<ResumptionPskStore>, Self::Error> {
+        delegate!(self, resumption_psk_store(group_id))
+    }
+
+    fn own_leaf_index<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNodeIndex: traits::LeafNodeIndex<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<LeafNodeIndex>, Self::Error> {
+        delegate!(self, own_leaf_index(group_id))
+    }
+
+    fn group_epoch_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupEpochSecrets: traits::GroupEpochSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupEpochSecrets>, Self::Error> {
+        delegate!(self, group_epoch_secrets(group_id))
+    }
+
+    fn future_epoch_messages<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        FutureEpochMessages: traits::FutureEpochMessages<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<FutureEpochMessages>, Self::Error> {
+        delegate!(self, future_epoch_messages(group_id))
+    }
+
+    fn signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION>,
+        SignatureKeyPair: traits::SignatureKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> Result<Option<SignatureKeyPair>, Self::Error> {
+        delegate!(self, signature_key_pair(public_key))
+    }
+
+    fn encryption_key_pair<
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+        EncryptionKey: traits::EncryptionKey<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> Result<Option<HpkeKeyPair>, Self::Error> {
+        delegate!(self, encryption_key_pair(public_key))
+    }
+
+    fn encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        EpochKey: traits::EpochKey<CURRENT_VERSION>,
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> Result<Vec<HpkeKeyPair>, Self::Error> {
+        delegate!(
+            self,
+            encryption_epoch_key_pairs(group_id, epoch, leaf_index)
+        )
+    }
+
+    fn key_package<
+        KeyPackageRef: traits::HashReference<CURRENT_VERSION>,
+        KeyPackage: traits::KeyPackage<CURRENT_VERSION>,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<Option<KeyPackage>, Self::Error> {
+        delegate!(self, key_package(hash_ref))
+    }
+
+    fn psk<PskBundle: traits::PskBundle<CURRENT_VERSION>, PskId: traits::PskId<CURRENT_VERSION>>(
+        &self,
+        psk_id: &PskId,
+    ) -> Result<Option<PskBundle>, Self::Error> {
+        delegate!(self, psk(psk_id))
+    }
+
+    #[cfg(feature = "extensions-draft")]
+    fn application_export_tree<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ApplicationExportTree: traits::ApplicationExportTree<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<ApplicationExportTree>, Self::Error> {
+        delegate!(self, application_export_tree(group_id))
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn vc_emulation_epoch_state<
+        EpochId: traits::VcEpochId<CURRENT_VERSION>,
+        VcEmulationEpochState: traits::VcEmulationEpochState<CURRENT_VERSION>,
+    >(
+        &self,
+        epoch_id: &EpochId,
+    ) -> Result<Option<VcEmulationEpochState>, Self::Error> {
+        delegate!(self, vc_emulation_epoch_state(epoch_id))
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn vc_emulation_bindings<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        VcEmulationBindings: traits::VcEmulationBindings<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<VcEmulationBindings>, Self::Error> {
+        delegate!(self, vc_emulation_bindings(group_id))
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn vc_operation_tree<
+        EpochId: traits::VcEpochId<CURRENT_VERSION>,
+        VcOperationTree: traits::VcOperationTree<CURRENT_VERSION>,
+    >(
+        &self,
+        epoch_id: &EpochId,
+    ) -> Result<Option<VcOperationTree>, Self::Error> {
+        delegate!(self, vc_operation_tree(epoch_id))
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn retained_key_package_material<
+        KeyPackageRef: traits::HashReference<CURRENT_VERSION>,
+        RetainedKeyPackageMaterial: traits::RetainedKeyPackageMaterial<CURRENT_VERSION>,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<Option<RetainedKeyPackageMaterial>, Self::Error> {
+        delegate!(self, retained_key_package_material(hash_ref))
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn has_retained_key_package_material_for_epoch<EpochId: traits::VcEpochId<CURRENT_VERSION>>(
+        &self,
+        epoch_id: &EpochId,
+    ) -> Result<bool, Self::Error> {
+        delegate!(self, has_retained_key_package_material_for_epoch(epoch_id))
+    }
+
+    fn remove_proposal<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, remove_proposal(group_id, proposal_ref))
+    }
+
+    fn delete_own_leaf_nodes<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, delete_own_leaf_nodes(group_id))
+    }
+
+    fn delete_group_config<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, delete_group_config(group_id))
+    }
+
+    fn delete_tree<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, delete_tree(group_id))
+    }
+
+    fn delete_confirmation_tag<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, delete_confirmation_tag(group_id))
+    }
+
+    fn delete_group_state<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, delete_group_state(group_id))
+    }
+
+    fn delete_context<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, delete_context(group_id))
+    }
+
+    fn delete_interim_transcript_hash<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, delete_interim_transcript_hash(group_id))
+    }
+
+    fn delete_message_secrets<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, delete_message_secrets(group_id))
+    }
+
+    fn delete_all_resumption_psk_secrets<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, delete_all_resumption_psk_secrets(group_id))
+    }
+
+    fn delete_own_leaf_index<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, delete_own_leaf_index(group_id))
+    }
+
+    fn delete_group_epoch_secrets<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, delete_group_epoch_secrets(group_id))
+    }
+
+    fn delete_future_epoch_messages<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, delete_future_epoch_messages(group_id))
+    }
+
+    fn clear_proposal_queue<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, clear_proposal_queue::<GroupId, ProposalRef>(group_id))
+    }
+
+    fn delete_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, delete_signature_key_pair(public_key))
+    }
+
+    fn delete_encryption_key_pair<EncryptionKey: traits::EncryptionKey<CURRENT_VERSION>>(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, delete_encryption_key_pair(public_key))
+    }
+
+    fn delete_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        EpochKey: traits::EpochKey<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> Result<(), Self::Error> {
+        delegate!(
+            self,
+            delete_encryption_epoch_key_pairs(group_id, epoch, leaf_index)
+        )
+    }
+
+    fn delete_key_package<KeyPackageRef: traits::HashReference<CURRENT_VERSION>>(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, delete_key_package(hash_ref))
+    }
+
+    fn delete_psk<PskKey: traits::PskId<CURRENT_VERSION>>(
+        &self,
+        psk_id: &PskKey,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, delete_psk(psk_id))
+    }
+
+    #[cfg(feature = "extensions-draft")]
+    fn delete_application_export_tree<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ApplicationExportTree: traits::ApplicationExportTree<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        delegate!(
+            self,
+            delete_application_export_tree::<GroupId, ApplicationExportTree>(group_id)
+        )
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn delete_vc_emulation_state_if_unreferenced<EpochId: traits::VcEpochId<CURRENT_VERSION>>(
+        &self,
+        epoch_id: &EpochId,
+    ) -> Result<bool, Self::Error> {
+        delegate!(self, delete_vc_emulation_state_if_unreferenced(epoch_id))
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn delete_vc_emulation_bindings<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, delete_vc_emulation_bindings(group_id))
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn delete_retained_key_package_material<
+        KeyPackageRef: traits::HashReference<CURRENT_VERSION>,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<(), Self::Error> {
+        delegate!(self, delete_retained_key_package_material(hash_ref))
+    }
+}
@@ -47,6 +47,7 @@ use tls_codec::{Deserialize, Serialize};
 
 pub mod client;
 pub mod errors;
+pub mod mixed_provider;
 
 use self::client::*;
 use self::errors::*;
@@ -56,13 +57,16 @@ use self::errors::*;
 /// that this state is only consistent if operations are conducted as per spec
 /// and messages are distributed correctly to all clients via the
 /// `distribute_to_members` function of `TestSetup`, which also updates the
-/// `public_tree` field.
+/// `public_tree`, `tree_hash`, `epoch_authenticator` and `exporter_secret`
+/// fields.
 pub struct Group {
     pub group_id: GroupId,
     pub members: Vec<(usize, Vec<u8>)>,
     pub ciphersuite: Ciphersuite,
     pub group_config: MlsGroupJoinConfig,
     pub public_tree: RatchetTree,
+    pub tree_hash: Vec<u8>,
+    pub epoch_authenticator: Vec<u8>,
     pub exporter_secret: Vec<u8>,
 }
 
@@ -140,11 +144,28 @@ impl<Provider: OpenMlsProvider + Default> MlsGroupTestSetup<Provider> {
         default_mgp: MlsGroupCreateConfig,
         number_of_clients: usize,
         use_codec: CodecUse,
+    ) -> Self {
+        Self::with_providers(
+            default_mgp,
+            (0..number_of_clients).map(|_| Provider::default()),
+            use_codec,
+        )
+    }
+}
+
+impl<Provider: OpenMlsProvider> MlsGroupTestSetup<Provider> {
+    /// Create a new `MlsGroupTestSetup` with the given default
+    /// `MlsGroupCreateConfig` and one client for each of the given providers.
+    /// Each client only gets credentials for the ciphersuites its provider
+    /// supports.
+    pub fn with_providers(
+        default_mgp: MlsGroupCreateConfig,
+        providers: impl IntoIterator<Item = Provider>,
+        use_codec: CodecUse,
     ) -> Self {
         let mut clients = HashMap::new();
-        for i in 0..number_of_clients {
+        for (i, provider) in providers.into_iter().enumerate() {
             let identity = i.to_be_bytes().to_vec();
-            let provider = Provider::default();
             let mut credentials = HashMap::new();
             for ciphersuite in provider.crypto().supported_ciphersuites().iter() {
                 let credential = BasicCredential::new(identity.clone());
@@ -354,17 +375,19 @@ impl<Provider: OpenMlsProvider + Default> MlsGroupTestSetup<Provider> {
             )
             .collect();
         group.public_tree = sender_group.export_ratchet_tree();
+        group.tree_hash = sender_group.tree_hash().to_vec();
+        group.epoch_authenticator = sender_group.epoch_authenticator().as_slice().to_vec();
         group.exporter_secret = sender_group
             .export_secret(sender.provider.crypto(), "test", &[], 32)
             .map_err(ClientError::ExportSecretError)?;
         Ok(())
     }
 
-    /// Check if the public tree and the exporter secret with label "test" and
-    /// length of the given group is the same for each group member. It also has
-    /// each group member encrypt an application message and delivers all of
-    /// these messages to all other members. This function panics if any of the
-    /// above tests fail.
+    /// Check if the public tree, the tree hash, the epoch authenticator and the
+    /// exporter secret with label "test" and length of the given group is the
+    /// same for each group member. It also has each group member encrypt an
+    /// application message and delivers all of these messages to all other
+    /// members. This function panics if any of the above tests fail.
     pub fn check_group_states<AS: Fn(&Credential) -> bool>(
         &self,
         group: &mut Group,
@@ -385,6 +408,11 @@ impl<Provider: OpenMlsProvider + Default> MlsGroupTestSetup<Provider> {
                 // Some group members may not have received their welcome messages yet.
                 if let Some(group_state) = group_states.get_mut(&group.group_id) {
                     assert_eq!(group_state.export_ratchet_tree(), group.public_tree);
+                    assert_eq!(group_state.tree_hash(), group.tree_hash);
+                    assert_eq!(
+                        group_state.epoch_authenticator().as_slice(),
+                        group.epoch_authenticator
+                    );
                     assert_eq!(
                         group_state
                             .export_secret(m.provider.crypto(), "test", &[], 32)
@@ -483,6 +511,8 @@ impl<Provider: OpenMlsProvider + Default> MlsGroupTestSetup<Provider> {
             .get(&group_id)
             .expect("An unexpected error occurred.");
         let public_tree = group.export_ratchet_tree();
+        let tree_hash = group.tree_hash().to_vec();
+        let epoch_authenticator = group.epoch_authenticator().as_slice().to_vec();
         let exporter_secret =
             group.export_secret(group_creator.provider.crypto(), "test", &[], 32)?;
         let member_ids = vec![(0, group_creator_id)];
@@ -492,6 +522,8 @@ impl<Provider: OpenMlsProvider + Default> MlsGroupTestSetup<Provider> {
             ciphersuite,
             group_config: self.default_mgp.join_config.clone(),
             public_tree,
+            tree_hash,
+            epoch_authenticator,
             exporter_secret,
         };
         groups.insert(group_id.clone(), group);
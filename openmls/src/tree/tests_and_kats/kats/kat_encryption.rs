@@ -281,13 +281,13 @@ fn build_application_messages(
     let membership_key = MembershipKey::from_secret(
         Secret::random(group.ciphersuite(), provider.rand()).expect("Not enough randomness."),
     );
-    let content = AuthenticatedContent::new_application(
+    let content = crate::utils::resolve_ready(AuthenticatedContent::new_application(
         sender_index,
         &[1, 2, 3],
         &[4, 5, 6],
         group.export_group_context(),
         signer,
-    )
+    ))
     .expect("An unexpected error occurred.");
     let mut plaintext: PublicMessage = content.clone().into();
     plaintext
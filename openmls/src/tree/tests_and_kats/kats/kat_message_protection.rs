@@ -292,10 +292,11 @@ pub fn run_test_vector(
             &signer,
         )
         .unwrap();
-        let my_proposal_priv = sender_group
-            .encrypt(proposal_authenticated_content, provider)
-            .unwrap()
-            .private_message;
+        let my_proposal_priv = crate::utils::resolve_ready(
+            sender_group.encrypt(proposal_authenticated_content, provider),
+        )
+        .unwrap()
+        .private_message;
 
         let my_proposal_priv_out = MlsMessageOut::from_private_message(
             my_proposal_priv,
@@ -401,21 +402,23 @@ pub fn run_test_vector(
         // Wrap `commit` into a `PrivateMessage`.
         let group = setup_group(provider, ciphersuite, &test, false);
         let mut sender_group = setup_group(provider, ciphersuite, &test, true);
-        let mut commit_authenticated_content = AuthenticatedContent::commit(
-            FramingParameters::new(&[], WireFormat::PrivateMessage),
-            Sender::Member(sender_index),
-            commit.clone().into(),
-            &group_context,
-            &signer,
-        )
-        .unwrap();
+        let mut commit_authenticated_content =
+            crate::utils::resolve_ready(AuthenticatedContent::commit(
+                FramingParameters::new(&[], WireFormat::PrivateMessage),
+                Sender::Member(sender_index),
+                commit.clone().into(),
+                &group_context,
+                &signer,
+            ))
+            .unwrap();
         commit_authenticated_content.set_confirmation_tag(ConfirmationTag(Mac {
             mac_value: vec![0; 32].into(), // Set a fake mac, we don't check it.
         }));
-        let my_commit_pub = sender_group
-            .encrypt(commit_authenticated_content, provider)
-            .unwrap()
-            .private_message;
+        let my_commit_pub = crate::utils::resolve_ready(
+            sender_group.encrypt(commit_authenticated_content, provider),
+        )
+        .unwrap()
+        .private_message;
 
         let my_commit_priv_out = MlsMessageOut::from_private_message(
             my_commit_pub,
@@ -433,14 +436,15 @@ pub fn run_test_vector(
         // Wrap `commit` into a `PublicMessage`.
         let group = setup_group(provider, ciphersuite, &test, false);
         let mut sender_group = setup_group(provider, ciphersuite, &test, true);
-        let mut commit_authenticated_content = AuthenticatedContent::commit(
-            FramingParameters::new(&[], WireFormat::PublicMessage),
-            Sender::Member(sender_index),
-            commit.clone().into(),
-            &group_context,
-            &signer,
-        )
-        .unwrap();
+        let mut commit_authenticated_content =
+            crate::utils::resolve_ready(AuthenticatedContent::commit(
+                FramingParameters::new(&[], WireFormat::PublicMessage),
+                Sender::Member(sender_index),
+                commit.clone().into(),
+                &group_context,
+                &signer,
+            ))
+            .unwrap();
         commit_authenticated_content.set_confirmation_tag(ConfirmationTag(Mac {
             mac_value: vec![0; 32].into(), // Set a fake mac, we don't check it.
         }));
@@ -22,7 +22,7 @@ use std::collections::HashSet;
 use log::debug;
 use openmls_traits::crypto::OpenMlsCrypto;
 use openmls_traits::random::OpenMlsRand;
-use openmls_traits::{signatures::Signer, types::Ciphersuite};
+use openmls_traits::{signatures::AsyncSigner, types::Ciphersuite};
 use serde::{Deserialize, Serialize};
 
 use super::node::leaf_node::UpdateLeafNodeParams;
@@ -55,7 +55,7 @@ use crate::group::GroupId;
 use crate::{
     binary_tree::{
         array_representation::{
-            LeafNodeIndex, ParentNodeIndex, TreeNodeIndex, TreeSize, MIN_TREE_SIZE,
+            subtree_leaves, LeafNodeIndex, ParentNodeIndex, TreeNodeIndex, TreeSize, MIN_TREE_SIZE,
         },
         MlsBinaryTreeDiff, StagedMlsBinaryTreeDiff,
     },
@@ -326,7 +326,7 @@ impl TreeSyncDiff<'_> {
     }
 
     /// Given a new [`LeafNode`], use it to create a new path starting from
-    /// `leaf_index` and apply it to this diff. The given [`Signer`] reference
+    /// `leaf_index` and apply it to this diff. The given [`AsyncSigner`] reference
     /// is used to sign the target [`LeafNode`] after updating its parent hash.
     ///
     /// If `vc_override` is `Some`, the contained `path_secret` is used to
@@ -341,11 +341,11 @@ impl TreeSyncDiff<'_> {
     ///
     /// Returns an error if the target leaf is not in the tree.
     #[allow(clippy::too_many_arguments)]
-    pub(crate) fn apply_own_update_path(
+    pub(crate) async fn apply_own_update_path(
         &mut self,
         rand: &impl OpenMlsRand,
         crypto: &impl OpenMlsCrypto,
-        signer: &impl Signer,
+        signer: &impl AsyncSigner,
         ciphersuite: Ciphersuite,
         commit_type: &CommitType,
         group_id: GroupId,
@@ -398,7 +398,8 @@ impl TreeSyncDiff<'_> {
             signer,
             #[cfg(feature = "virtual-clients-draft")]
             leaf_keypair_override,
-        )?;
+        )
+        .await?;
 
         // We insert the fresh leaf into the tree.
         self.diff.replace_leaf(leaf_index, leaf_node.into());
@@ -765,11 +766,11 @@ impl TreeSyncDiff<'_> {
     /// This turns the diff into a staged diff. In the process, the diff
     /// computes and sets the new tree hash.
     pub(crate) fn into_staged_diff(
-        self,
+        mut self,
         crypto: &impl OpenMlsCrypto,
         ciphersuite: Ciphersuite,
     ) -> Result<StagedTreeSyncDiff, LibraryError> {
-        let new_tree_hash = self.compute_tree_hashes(crypto, ciphersuite)?;
+        let new_tree_hash = self.update_tree_hash_caches(crypto, ciphersuite, self.diff.root())?;
         debug_assert!(self.verify_parent_hashes(crypto, ciphersuite).is_ok());
         Ok(StagedTreeSyncDiff {
             diff: self.diff.into(),
@@ -777,9 +778,10 @@ impl TreeSyncDiff<'_> {
         })
     }
 
-    /// Helper function to compute and set the tree hash of the given node and
-    /// all nodes below it in the tree. The leaf nodes in `exclusion_list` are
-    /// not included in the tree hash.
+    /// Helper function to compute the tree hash of the given node. The leaf
+    /// nodes in `exclusion_list` are not included in the tree hash. Cached tree
+    /// hashes are used for subtrees that the diff didn't change and populated
+    /// where they are missing.
     pub(crate) fn compute_tree_hash(
         &self,
         crypto: &impl OpenMlsCrypto,
@@ -798,6 +800,21 @@ impl TreeSyncDiff<'_> {
                 }
             }
             TreeNodeIndex::Parent(parent_index) => {
+                let node = self.diff.parent(parent_index);
+
+                // The cached tree hash can only be used if the subtree wasn't
+                // changed by the diff and none of its leaves are excluded.
+                let subtree_leaves = subtree_leaves(parent_index);
+                let cacheable = self.diff.subtree_is_unchanged(parent_index)
+                    && !exclusion_list
+                        .iter()
+                        .any(|leaf_index| subtree_leaves.contains(leaf_index));
+                if cacheable {
+                    if let Some(tree_hash) = node.tree_hash() {
+                        return Ok(tree_hash.to_vec());
+                    }
+                }
+
                 // Compute left hash.
                 let left_child = self.diff.left_child(parent_index);
                 let left_hash =
@@ -807,19 +824,62 @@ impl TreeSyncDiff<'_> {
                 let right_hash =
                     self.compute_tree_hash(crypto, ciphersuite, right_child, exclusion_list)?;
 
-                let node = self.diff.parent(parent_index);
-
-                node.compute_tree_hash(crypto, ciphersuite, left_hash, right_hash, exclusion_list)
+                let tree_hash = node.compute_tree_hash(
+                    crypto,
+                    ciphersuite,
+                    left_hash,
+                    right_hash,
+                    exclusion_list,
+                )?;
+                if cacheable {
+                    node.cache_tree_hash(&tree_hash);
+                }
+                Ok(tree_hash)
             }
         }
     }
 
+    /// Compute the tree hash of the given node and update the tree hash caches
+    /// of all changed parent nodes below it. Parent nodes that weren't changed
+    /// themselves, but that have changed nodes in their subtree, are copied
+    /// into the diff, so that their cache is updated when the diff is merged.
+    ///
+    /// This must only be called once the diff won't be changed anymore.
+    fn update_tree_hash_caches(
+        &mut self,
+        crypto: &impl OpenMlsCrypto,
+        ciphersuite: Ciphersuite,
+        node_index: TreeNodeIndex,
+    ) -> Result<Vec<u8>, LibraryError> {
+        let parent_index = match node_index {
+            TreeNodeIndex::Parent(parent_index)
+                if !self.diff.subtree_is_unchanged(parent_index) =>
+            {
+                parent_index
+            }
+            // Leaves aren't cached and unchanged subtrees are cached in the
+            // original tree.
+            _ => return self.compute_tree_hash(crypto, ciphersuite, node_index, &HashSet::new()),
+        };
+
+        let left_child = self.diff.left_child(parent_index);
+        let left_hash = self.update_tree_hash_caches(crypto, ciphersuite, left_child)?;
+        let right_child = self.diff.right_child(parent_index);
+        let right_hash = self.update_tree_hash_caches(crypto, ciphersuite, right_child)?;
+
+        let node = self.diff.parent_mut(parent_index);
+        let tree_hash =
+            node.compute_tree_hash(crypto, ciphersuite, left_hash, right_hash, &HashSet::new())?;
+        node.set_tree_hash(tree_hash.clone());
+        Ok(tree_hash)
+    }
+
     /// Return a reference to the leaf with the given index.
     pub(crate) fn leaf(&self, index: LeafNodeIndex) -> Option<&LeafNode> {
         self.diff.leaf(index).node().as_ref()
     }
 
-    /// Compute and set the tree hash of all nodes in the tree.
+    /// Compute the tree hash of the root node.
     pub(crate) fn compute_tree_hashes(
         &self,
         crypto: &impl OpenMlsCrypto,
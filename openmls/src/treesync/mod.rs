@@ -32,19 +32,14 @@ use tls_codec::{TlsDeserialize, TlsDeserializeBytes, TlsSerialize, TlsSize};
 
 use self::{
     diff::{StagedTreeSyncDiff, TreeSyncDiff},
-    node::{
-        leaf_node::{
-            Capabilities, NewLeafNodeParams, TreeInfoTbs, TreePosition, VerifiableLeafNode,
-        },
-        NodeIn,
+    node::leaf_node::{
+        Capabilities, NewLeafNodeParams, TreeInfoTbs, TreePosition, VerifiableLeafNode,
     },
     treesync_node::{TreeSyncLeafNode, TreeSyncNode, TreeSyncParentNode},
 };
+use crate::binary_tree::array_representation::ParentNodeIndex;
 #[cfg(any(feature = "test-utils", test))]
 use crate::{binary_tree::array_representation::level, test_utils::bytes_to_hex};
-use crate::{
-    binary_tree::array_representation::ParentNodeIndex, treesync::node::leaf_node::LeafNodeIn,
-};
 use crate::{
     binary_tree::{
         array_representation::{is_node_in_tree, LeafNodeIndex, TreeSize},
@@ -82,11 +77,11 @@ pub use node::encryption_keys::EncryptionKey;
 // Public re-exports
 pub use node::{
     leaf_node::{
-        LeafNode, LeafNodeParameters, LeafNodeParametersBuilder, LeafNodeSource,
+        LeafNode, LeafNodeIn, LeafNodeParameters, LeafNodeParametersBuilder, LeafNodeSource,
         LeafNodeUpdateError,
     },
     parent_node::ParentNode,
-    Node,
+    Node, NodeIn,
 };
 
 // Tests
@@ -266,6 +261,12 @@ impl RatchetTreeIn {
         self.0.iter().flatten()
     }
 
+    /// Returns an iterator over all node positions in the ratchet tree, in
+    /// array representation order. Blank nodes are `None`.
+    pub fn nodes_with_blanks(&self) -> impl Iterator<Item = Option<&NodeIn>> {
+        self.0.iter().map(Option::as_ref)
+    }
+
     /// Returns an iterator over all leaf nodes in the ratchet tree.
     pub fn leaves(&self) -> impl Iterator<Item = &LeafNodeIn> {
         self.nodes().filter_map(|node| match node {
@@ -443,7 +444,8 @@ impl TreeSync {
             extensions,
             tree_info_tbs: TreeInfoTbs::KeyPackage,
         };
-        let (leaf, encryption_key_pair) = LeafNode::new(provider, signer, new_leaf_node_params)?;
+        let (leaf, encryption_key_pair) =
+            crate::utils::resolve_ready(LeafNode::new(provider, signer, new_leaf_node_params))?;
 
         let node = Node::leaf_node(leaf);
         let path_secret: PathSecret = Secret::random(ciphersuite, provider.rand())
@@ -611,6 +613,27 @@ impl TreeSync {
         self.tree.tree_size()
     }
 
+    /// Returns a copy of this tree without any cached tree hashes.
+    #[cfg(any(feature = "test-utils", test))]
+    pub(crate) fn without_tree_hash_caches(&self) -> Self {
+        let leaves = self.tree.leaves().map(|(_, leaf)| leaf.clone()).collect();
+        let parents = self
+            .tree
+            .parents()
+            .map(|(_, parent)| {
+                let mut parent = parent.clone();
+                parent.clear_tree_hash();
+                parent
+            })
+            .collect();
+        let tree = MlsBinaryTree::from_components(leaves, parents)
+            .expect("The components are taken from a valid tree.");
+        Self {
+            tree,
+            tree_hash: self.tree_hash.clone(),
+        }
+    }
+
     /// Returns an iterator over the (non-blank) [`LeafNode`]s in the tree.
     pub fn full_leaves(&self) -> impl Iterator<Item = (LeafNodeIndex, &LeafNode)> {
         self.tree
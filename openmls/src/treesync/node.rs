@@ -45,6 +45,8 @@ impl Node {
     }
 }
 
+/// A node of a [`RatchetTreeIn`](super::RatchetTreeIn) that has not been
+/// verified yet.
 #[derive(
     Debug,
     PartialEq,
@@ -3,7 +3,6 @@ use std::fmt::Debug;
 use openmls_traits::{
     crypto::OpenMlsCrypto,
     random::OpenMlsRand,
-    storage::{StorageProvider as StorageProviderTrait, CURRENT_VERSION},
     types::{Ciphersuite, HpkeCiphertext, HpkeKeyPair},
 };
 use serde::{Deserialize, Serialize};
@@ -12,7 +11,7 @@ use tls_codec::{TlsDeserialize, TlsDeserializeBytes, TlsSerialize, TlsSize, VLBy
 use crate::{
     ciphersuite::{hpke, HpkePrivateKey, HpkePublicKey, Secret},
     error::LibraryError,
-    storage::{OpenMlsProvider, StorageProvider},
+    storage::{AsyncOpenMlsProvider, AsyncStorageProvider, StorageProvider},
 };
 
 /// [`EncryptionKey`] contains an HPKE public key that allows the encryption of
@@ -41,7 +40,7 @@ impl EncryptionKey {
     }
 
     /// Return the internal [`HpkePublicKey`] as slice.
-    pub(crate) fn as_slice(&self) -> &[u8] {
+    pub fn as_slice(&self) -> &[u8] {
         self.key.as_slice()
     }
 
@@ -174,13 +173,15 @@ impl EncryptionKeyPair {
     /// of the key package or the epoch encryption key pairs.
     ///
     /// Returns `None` if the keypair cannot be read from the store.
-    pub(crate) fn read(
-        provider: &impl OpenMlsProvider,
+    pub(crate) async fn read(
+        provider: &impl AsyncOpenMlsProvider,
         encryption_key: &EncryptionKey,
     ) -> Option<EncryptionKeyPair> {
+        use openmls_traits::async_storage::AsyncStorageProvider as _;
         provider
             .storage()
             .encryption_key_pair(encryption_key)
+            .await
             .ok()
             .flatten()
     }
@@ -190,11 +191,11 @@ impl EncryptionKeyPair {
     /// This must only be used for encryption key pairs that are generated for
     /// update leaf nodes. All other encryption key pairs are stored as part
     /// of the key package or the epoch encryption key pairs.
-    pub(crate) fn delete<Storage: StorageProviderTrait<CURRENT_VERSION>>(
+    pub(crate) async fn delete<Storage: AsyncStorageProvider>(
         &self,
         store: &Storage,
     ) -> Result<(), Storage::Error> {
-        store.delete_encryption_key_pair(self.public_key())
+        store.delete_encryption_key_pair(self.public_key()).await
     }
 
     pub(crate) fn public_key(&self) -> &EncryptionKey {
@@ -221,13 +222,20 @@ impl EncryptionKeyPair {
 
 #[cfg(feature = "test-utils")]
 pub mod test_utils {
+    use openmls_traits::storage::StorageProvider as _;
+
     use super::*;
+    use crate::storage::OpenMlsProvider;
 
     pub fn read_keys_from_key_store(
         provider: &impl OpenMlsProvider,
         encryption_key: &EncryptionKey,
     ) -> HpkeKeyPair {
-        let keys = EncryptionKeyPair::read(provider, encryption_key).unwrap();
+        let keys: EncryptionKeyPair = provider
+            .storage()
+            .encryption_key_pair(encryption_key)
+            .unwrap()
+            .unwrap();
 
         HpkeKeyPair {
             private: keys.private_key.key,
@@ -1,6 +1,9 @@
 //! This module contains the [`LeafNode`] struct and its implementation.
 use openmls_traits::{
-    crypto::OpenMlsCrypto, random::OpenMlsRand, signatures::Signer, types::Ciphersuite,
+    crypto::OpenMlsCrypto,
+    random::OpenMlsRand,
+    signatures::{AsyncSigner, Signer},
+    types::Ciphersuite,
 };
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -13,7 +16,7 @@ use super::encryption_keys::{EncryptionKey, EncryptionKeyPair};
 use crate::{
     binary_tree::array_representation::LeafNodeIndex,
     ciphersuite::{
-        signable::{Signable, SignedStruct, Verifiable, VerifiedStruct},
+        signable::{sign_async, Signable, SignedStruct, Verifiable, VerifiedStruct},
         Signature, SignaturePublicKey,
     },
     credentials::{Credential, CredentialType, CredentialWithKey},
@@ -189,9 +192,9 @@ impl LeafNode {
     /// This function generates a fresh HPKE key pair for the leaf node and
     /// returns the HPKE key pair along with the new leaf node.
     /// The caller is responsible for storing the private key.
-    pub(crate) fn new(
+    pub(crate) async fn new(
         provider: &impl OpenMlsProvider,
-        signer: &impl Signer,
+        signer: &impl AsyncSigner,
         new_leaf_node_params: NewLeafNodeParams,
     ) -> Result<(Self, EncryptionKeyPair), LibraryError> {
         let NewLeafNodeParams {
@@ -215,7 +218,8 @@ impl LeafNode {
             extensions,
             tree_info_tbs,
             signer,
-        )?;
+        )
+        .await?;
 
         Ok((leaf_node, encryption_key_pair))
     }
@@ -241,7 +245,7 @@ impl LeafNode {
             tree_info_tbs,
         } = new_leaf_node_params;
 
-        let leaf_node = Self::new_with_key(
+        let leaf_node = crate::utils::resolve_ready(Self::new_with_key(
             encryption_key_pair.public_key().clone(),
             credential_with_key,
             leaf_node_source,
@@ -249,7 +253,7 @@ impl LeafNode {
             extensions,
             tree_info_tbs,
             signer,
-        )?;
+        ))?;
 
         Ok((leaf_node, encryption_key_pair))
     }
@@ -277,14 +281,14 @@ impl LeafNode {
 
     /// Create a new leaf node with a given HPKE encryption key pair.
     /// The key pair must be stored in the key store by the caller.
-    fn new_with_key(
+    async fn new_with_key(
         encryption_key: EncryptionKey,
         credential_with_key: CredentialWithKey,
         leaf_node_source: LeafNodeSource,
         capabilities: Capabilities,
         extensions: Extensions<LeafNode>,
         tree_info_tbs: TreeInfoTbs,
-        signer: &impl Signer,
+        signer: &impl AsyncSigner,
     ) -> Result<Self, LibraryError> {
         let leaf_node_tbs = LeafNodeTbs::new(
             encryption_key,
@@ -295,8 +299,8 @@ impl LeafNode {
             tree_info_tbs,
         );
 
-        leaf_node_tbs
-            .sign(signer)
+        sign_async(leaf_node_tbs, signer)
+            .await
             .map_err(|_| LibraryError::custom("Signing failed"))
     }
 
@@ -307,7 +311,7 @@ impl LeafNode {
     /// as the leaf's encryption keypair instead of generating a fresh one.
     /// This is the hook for the virtual-clients-draft sender.
     #[allow(clippy::too_many_arguments)]
-    pub(in crate::treesync) fn new_with_parent_hash(
+    pub(in crate::treesync) async fn new_with_parent_hash(
         rand: &impl OpenMlsRand,
         crypto: &impl OpenMlsCrypto,
         ciphersuite: Ciphersuite,
@@ -315,7 +319,7 @@ impl LeafNode {
         leaf_node_params: UpdateLeafNodeParams,
         group_id: GroupId,
         leaf_index: LeafNodeIndex,
-        signer: &impl Signer,
+        signer: &impl AsyncSigner,
         #[cfg(feature = "virtual-clients-draft")] encryption_key_pair_override: Option<
             EncryptionKeyPair,
         >,
@@ -341,8 +345,8 @@ impl LeafNode {
         );
 
         // Sign the leaf node
-        let leaf_node = leaf_node_tbs
-            .sign(signer)
+        let leaf_node = sign_async(leaf_node_tbs, signer)
+            .await
             .map_err(|_| LibraryError::custom("Signing failed"))?;
 
         Ok((leaf_node, encryption_key_pair))
@@ -377,7 +381,8 @@ impl LeafNode {
             tree_info_tbs,
         };
 
-        let (leaf_node, encryption_key_pair) = Self::new(provider, signer, new_leaf_node_params)?;
+        let (leaf_node, encryption_key_pair) =
+            crate::utils::resolve_ready(Self::new(provider, signer, new_leaf_node_params))?;
 
         // Store the encryption key pair in the key store.
         encryption_key_pair
@@ -765,6 +770,8 @@ impl TreePosition {
 
 const LEAF_NODE_SIGNATURE_LABEL: &str = "LeafNodeTBS";
 
+/// A leaf node that has not been verified yet, e.g. because it was received
+/// as part of a message.
 #[derive(
     Debug,
     Clone,
@@ -826,6 +833,21 @@ impl LeafNodeIn {
         &self.payload.credential
     }
 
+    /// Returns a reference to the unverified [`Capabilities`].
+    pub fn capabilities(&self) -> &Capabilities {
+        &self.payload.capabilities
+    }
+
+    /// Returns a reference to the unverified [`LeafNodeSource`].
+    pub fn leaf_node_source(&self) -> &LeafNodeSource {
+        &self.payload.leaf_node_source
+    }
+
+    /// Returns a reference to the unverified [`Extensions`].
+    pub fn extensions(&self) -> &Extensions<LeafNode> {
+        &self.payload.extensions
+    }
+
     /// Assume that signature is valid and return the corresponding [`LeafNode`].
     ///
     /// # Safety
@@ -2,3 +2,5 @@
 pub mod kats;
 #[cfg(test)]
 mod tests;
+#[cfg(any(feature = "test-utils", test))]
+pub mod tree_hash_benchmark;
@@ -248,8 +248,8 @@ pub fn run_test_vector(test: TreeKemTest, provider: &impl OpenMlsProvider) {
             let leaf_node_params = UpdateLeafNodeParams::derive(leaf_node);
 
             // TODO(#1279): Update own leaf.
-            let (vec_plain_update_path_nodes, _, commit_secret) = diff_after_kat
-                .apply_own_update_path(
+            let (vec_plain_update_path_nodes, _, commit_secret) =
+                crate::utils::resolve_ready(diff_after_kat.apply_own_update_path(
                     provider.rand(),
                     provider.crypto(),
                     &signer,
@@ -259,7 +259,7 @@ pub fn run_test_vector(test: TreeKemTest, provider: &impl OpenMlsProvider) {
                     LeafNodeIndex::new(path_test.sender),
                     leaf_node_params,
                     None,
-                )
+                ))
                 .unwrap();
 
             // TODO(#1279): Update GroupContext.
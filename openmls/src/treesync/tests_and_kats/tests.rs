@@ -8,6 +8,7 @@ use crate::{
 };
 
 mod test_diff;
+mod test_tree_hash_cache;
 mod test_unmerged_leaves;
 
 /// Pathological example taken from ...
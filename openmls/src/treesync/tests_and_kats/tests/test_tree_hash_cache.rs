@@ -0,0 +1,111 @@
+use openmls_traits::types::Ciphersuite;
+
+use crate::{
+    binary_tree::LeafNodeIndex,
+    credentials::test_utils::new_credential,
+    key_packages::KeyPackageBundle,
+    storage::OpenMlsProvider,
+    treesync::{
+        diff::TreeSyncDiff, node::Node, tests_and_kats::tree_hash_benchmark::TreeHashBenchmark,
+        LeafNode, RatchetTree, TreeSync,
+    },
+};
+
+fn new_leaf_node(
+    provider: &impl OpenMlsProvider,
+    ciphersuite: Ciphersuite,
+    name: &[u8],
+) -> LeafNode {
+    let (credential_with_key, signer) =
+        new_credential(provider, name, ciphersuite.signature_algorithm());
+    KeyPackageBundle::generate(provider, &signer, ciphersuite, credential_with_key)
+        .key_package()
+        .leaf_node()
+        .clone()
+}
+
+// Verifies that the tree hash computed using the cached tree hashes matches
+// the tree hash computed from scratch.
+#[openmls_test::openmls_test]
+fn test_cached_tree_hash() {
+    let provider = &Provider::default();
+    let crypto = provider.crypto();
+    let benchmark = TreeHashBenchmark::new(provider, ciphersuite, 13);
+
+    assert_eq!(benchmark.add(crypto), benchmark.without_cache().add(crypto));
+    for leaf_index in [0, 5, 12] {
+        assert_eq!(
+            benchmark.remove(crypto, leaf_index),
+            benchmark.without_cache().remove(crypto, leaf_index)
+        );
+        assert_eq!(
+            benchmark.update(crypto, leaf_index),
+            benchmark.without_cache().update(crypto, leaf_index)
+        );
+    }
+}
+
+// Verifies that the cached tree hashes remain valid when diffs are merged.
+#[openmls_test::openmls_test]
+fn test_tree_hash_cache_after_merge() {
+    let provider = &Provider::default();
+    let crypto = provider.crypto();
+
+    let ratchet_tree = RatchetTree::trimmed(
+        [b"leaf0", b"leaf1", b"leaf2", b"leaf3", b"leaf4"]
+            .iter()
+            .enumerate()
+            .flat_map(|(index, name)| {
+                let leaf = Some(Node::leaf_node(new_leaf_node(provider, ciphersuite, *name)));
+                if index == 0 {
+                    vec![leaf]
+                } else {
+                    vec![None, leaf]
+                }
+            })
+            .collect(),
+    );
+    let mut tree = TreeSync::from_ratchet_tree(crypto, ciphersuite, ratchet_tree)
+        .expect("error generating tree");
+    let new_leaf = new_leaf_node(provider, ciphersuite, b"new leaf");
+
+    let operations: [&dyn Fn(&mut TreeSyncDiff); 5] = [
+        &|diff| {
+            diff.add_leaf(new_leaf.clone()).expect("error adding leaf");
+        },
+        &|diff| {
+            diff.blank_leaf(LeafNodeIndex::new(2));
+            diff.trim_tree();
+        },
+        &|diff| diff.update_leaf(new_leaf.clone(), LeafNodeIndex::new(0)),
+        &|diff| {
+            diff.add_leaf(new_leaf.clone()).expect("error adding leaf");
+        },
+        &|diff| {
+            diff.blank_leaf(LeafNodeIndex::new(5));
+            diff.blank_leaf(LeafNodeIndex::new(4));
+            diff.trim_tree();
+        },
+    ];
+
+    for operation in operations {
+        let mut diff = tree.empty_diff();
+        operation(&mut diff);
+        let staged_diff = diff
+            .into_staged_diff(crypto, ciphersuite)
+            .expect("error staging diff");
+        tree.merge_diff(staged_diff);
+
+        let cached_tree_hash = tree
+            .empty_diff()
+            .compute_tree_hashes(crypto, ciphersuite)
+            .expect("error computing tree hash");
+        let tree_hash = tree
+            .without_tree_hash_caches()
+            .empty_diff()
+            .compute_tree_hashes(crypto, ciphersuite)
+            .expect("error computing tree hash");
+        assert_eq!(tree.tree_hash(), tree_hash);
+        assert_eq!(cached_tree_hash, tree_hash);
+    }
+}
@@ -0,0 +1,129 @@
+//! Helpers to benchmark the tree hash computation of large trees.
+//!
+//! Building a large group through the public API is slow and the cost of a
+//! commit in such a group is dominated by the path encryption. The
+//! [`TreeHashBenchmark`] instead operates on a [`TreeSync`] instance directly
+//! and only measures the tree operations and the tree hash computation.
+
+use openmls_traits::{crypto::OpenMlsCrypto, types::Ciphersuite};
+
+use crate::{
+    binary_tree::{array_representation::TreeSize, LeafNodeIndex, MlsBinaryTree},
+    credentials::test_utils::new_credential,
+    key_packages::KeyPackage,
+    storage::OpenMlsProvider,
+    treesync::{
+        diff::TreeSyncDiff,
+        treesync_node::{TreeSyncLeafNode, TreeSyncParentNode},
+        LeafNode, TreeSync,
+    },
+};
+
+/// A tree with a given number of members to run tree operations on.
+///
+/// All members share the same leaf node and all parent nodes are blank, i.e.
+/// the tree looks like the tree of a group in which all members were added
+/// without anyone committing afterwards. This doesn't affect the cost of the
+/// tree hash computation, since every node is hashed the same way.
+///
+/// None of the operations change the tree, so that they can be run
+/// repeatedly. They return the tree hash the tree would have after merging
+/// the operation.
+#[derive(Debug, Clone)]
+pub struct TreeHashBenchmark {
+    ciphersuite: Ciphersuite,
+    tree: TreeSync,
+    leaf_node: LeafNode,
+}
+
+impl TreeHashBenchmark {
+    /// Create a tree with `member_count` members and populate its tree hash
+    /// caches.
+    pub fn new(
+        provider: &impl OpenMlsProvider,
+        ciphersuite: Ciphersuite,
+        member_count: u32,
+    ) -> Self {
+        let (credential_with_key, signer) =
+            new_credential(provider, b"member", ciphersuite.signature_algorithm());
+        let key_package_bundle = KeyPackage::builder()
+            .build(ciphersuite, provider, &signer, credential_with_key)
+            .expect("Error creating the key package.");
+        let leaf_node = key_package_bundle.key_package().leaf_node().clone();
+
+        let tree_size = TreeSize::from_leaf_count(member_count.max(1));
+        let leaves = (0..tree_size.leaf_count())
+            .map(|leaf_index| {
+                if leaf_index < member_count {
+                    leaf_node.clone().into()
+                } else {
+                    TreeSyncLeafNode::blank()
+                }
+            })
+            .collect();
+        let parents = (0..tree_size.parent_count())
+            .map(|_| TreeSyncParentNode::blank())
+            .collect();
+        let tree = MlsBinaryTree::from_components(leaves, parents)
+            .expect("Error creating the binary tree.");
+        let mut tree = TreeSync {
+            tree,
+            tree_hash: vec![],
+        };
+        tree.populate_parent_hashes(provider.crypto(), ciphersuite)
+            .expect("Error computing the tree hash.");
+
+        Self {
+            ciphersuite,
+            tree,
+            leaf_node,
+        }
+    }
+
+    /// Return a copy of the benchmark without any cached tree hashes. The
+    /// first operation on the copy has to compute the tree hash from scratch.
+    pub fn without_cache(&self) -> Self {
+        Self {
+            ciphersuite: self.ciphersuite,
+            tree: self.tree.without_tree_hash_caches(),
+            leaf_node: self.leaf_node.clone(),
+        }
+    }
+
+    /// Return the current tree hash.
+    pub fn tree_hash(&self) -> &[u8] {
+        self.tree.tree_hash()
+    }
+
+    /// Add a member in the left-most free leaf.
+    pub fn add(&self, crypto: &impl OpenMlsCrypto) -> Vec<u8> {
+        let mut diff = self.tree.empty_diff();
+        diff.add_leaf(self.leaf_node.clone())
+            .expect("Error adding a leaf.");
+        self.staged_tree_hash(crypto, diff)
+    }
+
+    /// Remove the member at `leaf_index`.
+    pub fn remove(&self, crypto: &impl OpenMlsCrypto, leaf_index: u32) -> Vec<u8> {
+        let mut diff = self.tree.empty_diff();
+        diff.blank_leaf(LeafNodeIndex::new(leaf_index));
+        self.staged_tree_hash(crypto, diff)
+    }
+
+    /// Update the member at `leaf_index`, which replaces its leaf and blanks
+    /// its direct path.
+    pub fn update(&self, crypto: &impl OpenMlsCrypto, leaf_index: u32) -> Vec<u8> {
+        let mut diff = self.tree.empty_diff();
+        diff.update_leaf(self.leaf_node.clone(), LeafNodeIndex::new(leaf_index));
+        self.staged_tree_hash(crypto, diff)
+    }
+
+    /// Stage the diff, which computes the new tree hash.
+    fn staged_tree_hash(&self, crypto: &impl OpenMlsCrypto, diff: TreeSyncDiff) -> Vec<u8> {
+        let (_, tree_hash) = diff
+            .into_staged_diff(crypto, self.ciphersuite)
+            .expect("Error computing the tree hash.")
+            .into_parts();
+        tree_hash
+    }
+}
@@ -1,6 +1,6 @@
 //! This module contains the [`TreeSyncNode`] struct and its implementation.
 
-use std::collections::HashSet;
+use std::{collections::HashSet, sync::OnceLock};
 
 use openmls_traits::crypto::OpenMlsCrypto;
 use openmls_traits::types::Ciphersuite;
@@ -76,8 +76,9 @@ impl TreeSyncLeafNode {
         &self.node
     }
 
-    /// Compute the tree hash for this node, thus populating the `tree_hash`
-    /// field.
+    /// Compute the tree hash for this node. Unlike the tree hashes of parent
+    /// nodes, leaf tree hashes are not cached, since they only require a
+    /// single hash computation.
     pub(in crate::treesync) fn compute_tree_hash(
         &self,
         crypto: &impl OpenMlsCrypto,
@@ -109,6 +110,21 @@ impl From<TreeSyncLeafNode> for Option<Node> {
     }
 }
 
+/// The cached tree hash of a parent node, i.e. the tree hash of the subtree
+/// rooted at the node.
+///
+/// The cache can be populated through a shared reference, so that trees that
+/// are only read by a diff can still be populated. It is neither serialized
+/// nor considered when comparing nodes.
+#[derive(Debug, Clone, Default)]
+struct TreeHashCache(OnceLock<Vec<u8>>);
+
+impl PartialEq for TreeHashCache {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[cfg_attr(any(test, feature = "test-utils"), derive(PartialEq))]
 /// This intermediate struct on top of `Option<Node>` allows us to cache tree
@@ -116,6 +132,8 @@ impl From<TreeSyncLeafNode> for Option<Node> {
 /// `node = None`.
 pub(crate) struct TreeSyncParentNode {
     node: Option<ParentNode>,
+    #[serde(skip)]
+    tree_hash: TreeHashCache,
 }
 
 impl TreeSyncParentNode {
@@ -129,11 +147,37 @@ impl TreeSyncParentNode {
         &self.node
     }
 
-    /// Return a mutable reference to the contained `Option<Node>`.
+    /// Return a mutable reference to the contained `Option<Node>`. This
+    /// clears the cached tree hash.
     pub(in crate::treesync) fn node_mut(&mut self) -> &mut Option<ParentNode> {
+        self.tree_hash = TreeHashCache::default();
         &mut self.node
     }
 
+    /// Return the cached tree hash, if there is one.
+    ///
+    /// The caller has to ensure that the subtree below this node didn't change
+    /// since the hash was cached.
+    pub(in crate::treesync) fn tree_hash(&self) -> Option<&[u8]> {
+        self.tree_hash.0.get().map(Vec::as_slice)
+    }
+
+    /// Cache the given tree hash if no tree hash is cached yet.
+    pub(in crate::treesync) fn cache_tree_hash(&self, tree_hash: &[u8]) {
+        let _ = self.tree_hash.0.set(tree_hash.to_vec());
+    }
+
+    /// Replace the cached tree hash with the given one.
+    pub(in crate::treesync) fn set_tree_hash(&mut self, tree_hash: Vec<u8>) {
+        self.tree_hash = TreeHashCache(OnceLock::from(tree_hash));
+    }
+
+    /// Clear the cached tree hash.
+    #[cfg(any(feature = "test-utils", test))]
+    pub(in crate::treesync) fn clear_tree_hash(&mut self) {
+        self.tree_hash = TreeHashCache::default();
+    }
+
     /// Compute the tree hash for this node. Leaf nodes from the exclusion list
     /// are filtered out.
     pub(in crate::treesync) fn compute_tree_hash(
@@ -181,13 +225,16 @@ impl TreeSyncParentNode {
 
 impl From<ParentNode> for TreeSyncParentNode {
     fn from(node: ParentNode) -> Self {
-        Self { node: Some(node) }
+        Self {
+            node: Some(node),
+            tree_hash: TreeHashCache::default(),
+        }
     }
 }
 
 impl From<ParentNode> for Box<TreeSyncParentNode> {
     fn from(node: ParentNode) -> Self {
-        Box::new(TreeSyncParentNode { node: Some(node) })
+        Box::new(node.into())
     }
 }
 
@@ -54,6 +54,20 @@ macro_rules! log_content {
     (trace, $($arg:tt)*) => {{}};
 }
 
+// With the tracing feature enabled, the duration of an operation is emitted as
+// an event when the enclosing block is left.
+#[cfg(feature = "tracing")]
+macro_rules! time_operation {
+    ($operation:literal) => {
+        let _timer = crate::instrumentation::OperationTimer::start($operation);
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! time_operation {
+    ($operation:literal) => {};
+}
+
 /// Helper mod that converts a objects that implement FromIterator<_,_> (like a
 /// HashMap or a BTreeMap) into a vector of tuples and vice versa.
 pub mod vector_converter {
@@ -81,3 +95,28 @@ pub mod vector_converter {
         Ok(T::from_iter(container))
     }
 }
+
+/// Resolves a future that never suspends, e.g. one that only awaits
+/// [`AsyncSigner`]s that are backed by a synchronous [`Signer`], or
+/// [`AsyncStorageProvider`]s that are backed by a synchronous
+/// [`StorageProvider`].
+///
+/// This is used to drive the asynchronous code paths from the synchronous
+/// API. Returns a [`LibraryError`] if the future is pending.
+///
+/// [`AsyncSigner`]: openmls_traits::signatures::AsyncSigner
+/// [`Signer`]: openmls_traits::signatures::Signer
+/// [`AsyncStorageProvider`]: openmls_traits::async_storage::AsyncStorageProvider
+/// [`StorageProvider`]: openmls_traits::storage::StorageProvider
+pub(crate) fn resolve_ready<T, E: From<crate::error::LibraryError>>(
+    future: impl std::future::Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    let future = std::pin::pin!(future);
+    match future.poll(&mut std::task::Context::from_waker(std::task::Waker::noop())) {
+        std::task::Poll::Ready(output) => output,
+        std::task::Poll::Pending => Err(crate::error::LibraryError::custom(
+            "A synchronous operation did not complete",
+        )
+        .into()),
+    }
+}
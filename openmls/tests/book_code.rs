@@ -295,6 +295,19 @@ fn book_operations() {
         .build();
     // ANCHOR_END: mls_group_config_example
 
+    // ANCHOR: padding_policy_example
+    let padded_join_config = MlsGroupJoinConfig::builder()
+        .padding_policy(PaddingPolicy::new(
+            PaddingStrategy::Fixed(64), // handshake messages
+            PaddingStrategy::Padme,     // application messages
+        ))
+        .build();
+    // ANCHOR_END: padding_policy_example
+    assert_eq!(
+        padded_join_config.padding_policy().application(),
+        PaddingStrategy::Padme
+    );
+
     let welcome: MlsMessageIn = welcome.into();
     let welcome = welcome
         .into_welcome()
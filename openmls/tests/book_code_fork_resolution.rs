@@ -2,7 +2,7 @@
 
 use openmls::{
     group::{JoinBuilder, ProcessedWelcome},
-    prelude::*,
+    prelude::{tls_codec::*, *},
 };
 use openmls_basic_credential::SignatureKeyPair;
 use openmls_test::openmls_test;
@@ -155,6 +155,38 @@ fn book_example_readd() {
 
     // ANCHOR_END: readd_prepare_group
 
+    // ANCHOR: detect_fork
+    // Alice keeps a history of her recent epochs, so that she can also compare checkpoints of
+    // members that lag behind. It is updated after every merged commit.
+    let mut alice_history = CheckpointHistory::new(16);
+    alice_history.record(&alice_group);
+
+    // After merging, Bob and Charlie send a checkpoint of their epoch to the group
+    let bob_checkpoint = bob_group
+        .epoch_checkpoint(bob_provider.crypto(), &bob_signature_keys)
+        .unwrap()
+        .tls_serialize_detached()
+        .unwrap();
+    let charlie_checkpoint = charlie_group
+        .epoch_checkpoint(charlie_provider.crypto(), &charlie_signature_keys)
+        .unwrap()
+        .tls_serialize_detached()
+        .unwrap();
+
+    // Alice compares the received checkpoints against her own state
+    let checkpoints = [bob_checkpoint, charlie_checkpoint]
+        .into_iter()
+        .map(|bytes| VerifiableEpochCheckpoint::tls_deserialize_exact(bytes).unwrap());
+    let report = alice_group
+        .detect_fork(alice_provider.crypto(), &alice_history, checkpoints)
+        .unwrap();
+
+    // Bob is in a different partition than Alice and Charlie
+    assert!(report.is_forked());
+    assert_eq!(report.diverged(), &[bob_group.own_leaf_index()]);
+    assert_eq!(report.in_sync(), &[charlie_group.own_leaf_index()]);
+    // ANCHOR_END: detect_fork
+
     // ANCHOR: readd_do_it
     // Let Alice re-add the members of the other partition (i.e. Bob)
     let bob_new_kpb = generate_key_package(
@@ -0,0 +1,815 @@
+//! HPKE ([RFC 9180]) in base mode for the DH-based KEMs that `hpke-rs` does
+//! not implement: DHKEM(P-384, HKDF-SHA384), DHKEM(P-521, HKDF-SHA512) and
+//! DHKEM(X448, HKDF-SHA512).
+//!
+//! The key derivation and encryption use the HKDF and AEAD functions of the
+//! [`RustCrypto`] provider. Private keys are the serialized scalars and public
+//! keys the uncompressed SEC1 points or the X448 u-coordinates, as defined by
+//! `SerializePrivateKey` and `SerializePublicKey` in RFC 9180.
+//!
+//! [RFC 9180]: https://www.rfc-editor.org/rfc/rfc9180
+
+use ed448_goldilocks_plus::x448;
+use openmls_traits::{
+    crypto::OpenMlsCrypto,
+    random::OpenMlsRand,
+    types::{
+        AeadType, CryptoError, HashType, HpkeAeadType, HpkeConfig, HpkeKdfType, HpkeKemType,
+        HpkeKeyPair,
+    },
+};
+use p384::elliptic_curve::sec1::ToEncodedPoint;
+
+use crate::RustCrypto;
+
+/// Returns whether the KEM is implemented by this module.
+pub(crate) fn supports(kem: HpkeKemType) -> bool {
+    Kem::try_from(kem).is_ok()
+}
+
+/// Single-shot encryption to `pk_r`, returning the encapsulated key and the
+/// ciphertext.
+pub(crate) fn seal(
+    crypto: &RustCrypto,
+    config: &HpkeConfig,
+    pk_r: &[u8],
+    info: &[u8],
+    aad: &[u8],
+    ptxt: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
+    let (enc, context) = setup_sender(crypto, config, pk_r, info)?;
+    let aead = aead_type(config.2)?;
+    let ciphertext = crypto.aead_encrypt(aead, &context.key, ptxt, &context.base_nonce, aad)?;
+    Ok((enc, ciphertext))
+}
+
+/// Single-shot decryption with `sk_r`.
+pub(crate) fn open(
+    crypto: &RustCrypto,
+    config: &HpkeConfig,
+    enc: &[u8],
+    sk_r: &[u8],
+    info: &[u8],
+    aad: &[u8],
+    ctxt: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    let context = setup_receiver(crypto, config, enc, sk_r, info)?;
+    let aead = aead_type(config.2)?;
+    crypto.aead_decrypt(aead, &context.key, ctxt, &context.base_nonce, aad)
+}
+
+/// Sets up a sender context for `pk_r` and exports a secret of
+/// `exporter_length` bytes, returning the encapsulated key and the secret.
+pub(crate) fn setup_sender_and_export(
+    crypto: &RustCrypto,
+    config: &HpkeConfig,
+    pk_r: &[u8],
+    info: &[u8],
+    exporter_context: &[u8],
+    exporter_length: usize,
+) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
+    let (enc, context) = setup_sender(crypto, config, pk_r, info)?;
+    let exported_secret = context.export(crypto, exporter_context, exporter_length)?;
+    Ok((enc, exported_secret))
+}
+
+/// Sets up a receiver context with `sk_r` and exports a secret of
+/// `exporter_length` bytes.
+pub(crate) fn setup_receiver_and_export(
+    crypto: &RustCrypto,
+    config: &HpkeConfig,
+    enc: &[u8],
+    sk_r: &[u8],
+    info: &[u8],
+    exporter_context: &[u8],
+    exporter_length: usize,
+) -> Result<Vec<u8>, CryptoError> {
+    let context = setup_receiver(crypto, config, enc, sk_r, info)?;
+    context.export(crypto, exporter_context, exporter_length)
+}
+
+/// Deterministically derives a key pair from `ikm`.
+pub(crate) fn derive_key_pair(
+    crypto: &RustCrypto,
+    config: &HpkeConfig,
+    ikm: &[u8],
+) -> Result<HpkeKeyPair, CryptoError> {
+    let (private, public) = Kem::try_from(config.0)?.derive_key_pair(crypto, ikm)?;
+    Ok(HpkeKeyPair {
+        private: private.into(),
+        public,
+    })
+}
+
+fn setup_sender(
+    crypto: &RustCrypto,
+    config: &HpkeConfig,
+    pk_r: &[u8],
+    info: &[u8],
+) -> Result<(Vec<u8>, Context), CryptoError> {
+    let kem = Kem::try_from(config.0)?;
+    let (shared_secret, enc) = kem.encap(crypto, pk_r)?;
+    let context = Context::new(crypto, config, &shared_secret, info)?;
+    Ok((enc, context))
+}
+
+fn setup_receiver(
+    crypto: &RustCrypto,
+    config: &HpkeConfig,
+    enc: &[u8],
+    sk_r: &[u8],
+    info: &[u8],
+) -> Result<Context, CryptoError> {
+    let kem = Kem::try_from(config.0)?;
+    let shared_secret = kem.decap(crypto, enc, sk_r)?;
+    Context::new(crypto, config, &shared_secret, info)
+}
+
+/// The DH-based KEMs implemented by this module.
+#[derive(Clone, Copy)]
+enum Kem {
+    P384,
+    P521,
+    X448,
+}
+
+impl TryFrom<HpkeKemType> for Kem {
+    type Error = CryptoError;
+
+    fn try_from(kem: HpkeKemType) -> Result<Self, Self::Error> {
+        match kem {
+            HpkeKemType::DhKemP384 => Ok(Self::P384),
+            HpkeKemType::DhKemP521 => Ok(Self::P521),
+            HpkeKemType::DhKem448 => Ok(Self::X448),
+            _ => Err(CryptoError::UnsupportedCiphersuite),
+        }
+    }
+}
+
+impl Kem {
+    fn id(self) -> HpkeKemType {
+        match self {
+            Self::P384 => HpkeKemType::DhKemP384,
+            Self::P521 => HpkeKemType::DhKemP521,
+            Self::X448 => HpkeKemType::DhKem448,
+        }
+    }
+
+    fn kdf(self) -> HashType {
+        match self {
+            Self::P384 => HashType::Sha2_384,
+            Self::P521 | Self::X448 => HashType::Sha2_512,
+        }
+    }
+
+    /// The length of a private key (`Nsk`).
+    fn private_key_len(self) -> usize {
+        match self {
+            Self::P384 => 48,
+            Self::P521 => 66,
+            Self::X448 => 56,
+        }
+    }
+
+    /// The length of a public key (`Npk`).
+    fn public_key_len(self) -> usize {
+        match self {
+            Self::P384 => 97,
+            Self::P521 => 133,
+            Self::X448 => 56,
+        }
+    }
+
+    /// The length of the shared secret (`Nsecret`).
+    fn shared_secret_len(self) -> usize {
+        match self {
+            Self::P384 => 48,
+            Self::P521 | Self::X448 => 64,
+        }
+    }
+
+    fn suite_id(self) -> Vec<u8> {
+        [b"KEM".as_slice(), &(self.id() as u16).to_be_bytes()].concat()
+    }
+
+    /// `DeriveKeyPair` of RFC 9180, Section 7.1.3, returning the private and
+    /// the public key.
+    fn derive_key_pair(
+        self,
+        crypto: &RustCrypto,
+        ikm: &[u8],
+    ) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
+        let suite_id = self.suite_id();
+        let dkp_prk = labeled_extract(crypto, self.kdf(), &suite_id, &[], "dkp_prk", ikm)?;
+
+        let private_key = match self {
+            Self::P384 | Self::P521 => {
+                let bitmask = match self {
+                    Self::P521 => 0x01,
+                    _ => 0xff,
+                };
+                let mut private_key = None;
+                for counter in 0..=u8::MAX {
+                    let mut candidate = labeled_expand(
+                        crypto,
+                        self.kdf(),
+                        &suite_id,
+                        &dkp_prk,
+                        "candidate",
+                        &[counter],
+                        self.private_key_len(),
+                    )?;
+                    candidate[0] &= bitmask;
+                    if self.public_key(&candidate).is_ok() {
+                        private_key = Some(candidate);
+                        break;
+                    }
+                }
+                private_key.ok_or(CryptoError::CryptoLibraryError)?
+            }
+            Self::X448 => labeled_expand(
+                crypto,
+                self.kdf(),
+                &suite_id,
+                &dkp_prk,
+                "sk",
+                &[],
+                self.private_key_len(),
+            )?,
+        };
+        let public_key = self.public_key(&private_key)?;
+
+        Ok((private_key, public_key))
+    }
+
+    /// Computes the public key for the private key `sk`. This fails if `sk`
+    /// is not a valid private key.
+    ///
+    /// NIST curve private keys shorter than `Nsk` are zero-padded, because
+    /// some implementations strip the leading zero bytes of P-521 keys.
+    fn public_key(self, sk: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        match self {
+            Self::P384 => {
+                let sk =
+                    p384::SecretKey::from_slice(sk).map_err(|_| CryptoError::CryptoLibraryError)?;
+                Ok(sk.public_key().to_encoded_point(false).as_bytes().into())
+            }
+            Self::P521 => {
+                let sk =
+                    p521::SecretKey::from_slice(sk).map_err(|_| CryptoError::CryptoLibraryError)?;
+                Ok(sk.public_key().to_encoded_point(false).as_bytes().into())
+            }
+            Self::X448 => {
+                let sk: [u8; x448::X448_LENGTH] =
+                    sk.try_into().map_err(|_| CryptoError::InvalidLength)?;
+                let pk = x448::PublicKey::from(&x448::StaticSecret::from(sk));
+                Ok(pk.as_bytes().to_vec())
+            }
+        }
+    }
+
+    /// Computes the Diffie-Hellman shared secret of `sk` and `pk`.
+    ///
+    /// Only the encoding of `SerializePublicKey` is accepted for `pk`, i.e.
+    /// no compressed SEC1 points.
+    fn dh(self, sk: &[u8], pk: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        if pk.len() != self.public_key_len() {
+            return Err(CryptoError::InvalidPublicKey);
+        }
+        match self {
+            Self::P384 => {
+                let sk =
+                    p384::SecretKey::from_slice(sk).map_err(|_| CryptoError::CryptoLibraryError)?;
+                let pk = p384::PublicKey::from_sec1_bytes(pk)
+                    .map_err(|_| CryptoError::InvalidPublicKey)?;
+                let shared_secret =
+                    p384::ecdh::diffie_hellman(sk.to_nonzero_scalar(), pk.as_affine());
+                Ok(shared_secret.raw_secret_bytes().to_vec())
+            }
+            Self::P521 => {
+                let sk =
+                    p521::SecretKey::from_slice(sk).map_err(|_| CryptoError::CryptoLibraryError)?;
+                let pk = p521::PublicKey::from_sec1_bytes(pk)
+                    .map_err(|_| CryptoError::InvalidPublicKey)?;
+                let shared_secret =
+                    p521::ecdh::diffie_hellman(sk.to_nonzero_scalar(), pk.as_affine());
+                Ok(shared_secret.raw_secret_bytes().to_vec())
+            }
+            Self::X448 => {
+                let sk: [u8; x448::X448_LENGTH] =
+                    sk.try_into().map_err(|_| CryptoError::InvalidLength)?;
+                let pk: [u8; x448::X448_LENGTH] =
+                    pk.try_into().map_err(|_| CryptoError::InvalidPublicKey)?;
+                let shared_secret =
+                    x448::StaticSecret::from(sk).diffie_hellman(&x448::PublicKey::from(pk));
+                // RFC 9180, Section 7.1.4: reject the all-zero output.
+                if !shared_secret.was_contributory() {
+                    return Err(CryptoError::InvalidPublicKey);
+                }
+                Ok(shared_secret.as_bytes().to_vec())
+            }
+        }
+    }
+
+    /// `Encap` of RFC 9180, Section 4.1, returning the shared secret and the
+    /// encapsulated key.
+    fn encap(self, crypto: &RustCrypto, pk_r: &[u8]) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
+        let ikm = crypto
+            .random_vec(self.private_key_len())
+            .map_err(|_| CryptoError::InsufficientRandomness)?;
+        let (sk_e, enc) = self.derive_key_pair(crypto, &ikm)?;
+        let dh = self.dh(&sk_e, pk_r)?;
+        let kem_context = [enc.as_slice(), pk_r].concat();
+        let shared_secret = self.extract_and_expand(crypto, &dh, &kem_context)?;
+        Ok((shared_secret, enc))
+    }
+
+    /// `Decap` of RFC 9180, Section 4.1.
+    fn decap(self, crypto: &RustCrypto, enc: &[u8], sk_r: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let dh = self.dh(sk_r, enc)?;
+        let pk_r = self.public_key(sk_r)?;
+        let kem_context = [enc, pk_r.as_slice()].concat();
+        self.extract_and_expand(crypto, &dh, &kem_context)
+    }
+
+    fn extract_and_expand(
+        self,
+        crypto: &RustCrypto,
+        dh: &[u8],
+        kem_context: &[u8],
+    ) -> Result<Vec<u8>, CryptoError> {
+        let suite_id = self.suite_id();
+        let eae_prk = labeled_extract(crypto, self.kdf(), &suite_id, &[], "eae_prk", dh)?;
+        labeled_expand(
+            crypto,
+            self.kdf(),
+            &suite_id,
+            &eae_prk,
+            "shared_secret",
+            kem_context,
+            self.shared_secret_len(),
+        )
+    }
+}
+
+/// The encryption context of RFC 9180, Section 5.1, in base mode.
+struct Context {
+    kdf: HashType,
+    suite_id: Vec<u8>,
+    key: Vec<u8>,
+    base_nonce: Vec<u8>,
+    exporter_secret: Vec<u8>,
+}
+
+impl Context {
+    /// `KeySchedule` of RFC 9180, Section 5.1, with the base mode and
+    /// without a PSK.
+    fn new(
+        crypto: &RustCrypto,
+        config: &HpkeConfig,
+        shared_secret: &[u8],
+        info: &[u8],
+    ) -> Result<Self, CryptoError> {
+        const MODE_BASE: u8 = 0x00;
+
+        let kdf = kdf_type(config.1);
+        let suite_id = [
+            b"HPKE".as_slice(),
+            &(config.0 as u16).to_be_bytes(),
+            &(config.1 as u16).to_be_bytes(),
+            &(config.2 as u16).to_be_bytes(),
+        ]
+        .concat();
+
+        let psk_id_hash = labeled_extract(crypto, kdf, &suite_id, &[], "psk_id_hash", &[])?;
+        let info_hash = labeled_extract(crypto, kdf, &suite_id, &[], "info_hash", info)?;
+        let key_schedule_context = [[MODE_BASE].as_slice(), &psk_id_hash, &info_hash].concat();
+        let secret = labeled_extract(crypto, kdf, &suite_id, shared_secret, "secret", &[])?;
+
+        // The key and nonce are empty for the export-only AEAD.
+        let (key_length, nonce_length) = match aead_type(config.2) {
+            Ok(aead) => (aead.key_size(), aead.nonce_size()),
+            Err(_) => (0, 0),
+        };
+        let key = labeled_expand(
+            crypto,
+            kdf,
+            &suite_id,
+            &secret,
+            "key",
+            &key_schedule_context,
+            key_length,
+        )?;
+        let base_nonce = labeled_expand(
+            crypto,
+            kdf,
+            &suite_id,
+            &secret,
+            "base_nonce",
+            &key_schedule_context,
+            nonce_length,
+        )?;
+        let exporter_secret = labeled_expand(
+            crypto,
+            kdf,
+            &suite_id,
+            &secret,
+            "exp",
+            &key_schedule_context,
+            kdf.size(),
+        )?;
+
+        Ok(Self {
+            kdf,
+            suite_id,
+            key,
+            base_nonce,
+            exporter_secret,
+        })
+    }
+
+    /// `Export` of RFC 9180, Section 5.3.
+    fn export(
+        &self,
+        crypto: &RustCrypto,
+        exporter_context: &[u8],
+        length: usize,
+    ) -> Result<Vec<u8>, CryptoError> {
+        labeled_expand(
+            crypto,
+            self.kdf,
+            &self.suite_id,
+            &self.exporter_secret,
+            "sec",
+            exporter_context,
+            length,
+        )
+        .map_err(|_| CryptoError::ExporterError)
+    }
+}
+
+fn kdf_type(kdf: HpkeKdfType) -> HashType {
+    match kdf {
+        HpkeKdfType::HkdfSha256 => HashType::Sha2_256,
+        HpkeKdfType::HkdfSha384 => HashType::Sha2_384,
+        HpkeKdfType::HkdfSha512 => HashType::Sha2_512,
+    }
+}
+
+fn aead_type(aead: HpkeAeadType) -> Result<AeadType, CryptoError> {
+    match aead {
+        HpkeAeadType::AesGcm128 => Ok(AeadType::Aes128Gcm),
+        HpkeAeadType::AesGcm256 => Ok(AeadType::Aes256Gcm),
+        HpkeAeadType::ChaCha20Poly1305 => Ok(AeadType::ChaCha20Poly1305),
+        HpkeAeadType::Export => Err(CryptoError::UnsupportedAeadAlgorithm),
+    }
+}
+
+/// `LabeledExtract` of RFC 9180, Section 4.
+fn labeled_extract(
+    crypto: &RustCrypto,
+    kdf: HashType,
+    suite_id: &[u8],
+    salt: &[u8],
+    label: &str,
+    ikm: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    let labeled_ikm = [b"HPKE-v1".as_slice(), suite_id, label.as_bytes(), ikm].concat();
+    let prk = crypto.hkdf_extract(kdf, salt, &labeled_ikm)?;
+    Ok(prk.as_slice().to_vec())
+}
+
+/// `LabeledExpand` of RFC 9180, Section 4.
+fn labeled_expand(
+    crypto: &RustCrypto,
+    kdf: HashType,
+    suite_id: &[u8],
+    prk: &[u8],
+    label: &str,
+    info: &[u8],
+    length: usize,
+) -> Result<Vec<u8>, CryptoError> {
+    let encoded_length = u16::try_from(length).map_err(|_| CryptoError::InvalidLength)?;
+    let labeled_info = [
+        encoded_length.to_be_bytes().as_slice(),
+        b"HPKE-v1",
+        suite_id,
+        label.as_bytes(),
+        info,
+    ]
+    .concat();
+    let okm = crypto.hkdf_expand(kdf, prk, &labeled_info, length)?;
+    Ok(okm.as_slice().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A base mode test vector in the layout of RFC 9180, Appendix A. The
+    /// sender and receiver key pairs are derived from `ikm_e` and `ikm_r`,
+    /// the two ciphertexts encrypt [`PLAINTEXT`] with the AAD `Count-0` and
+    /// `Count-1`, and the exports are 32 bytes for the exporter contexts
+    /// [`EXPORTER_CONTEXTS`].
+    struct Vector {
+        config: HpkeConfig,
+        ikm_e: &'static str,
+        pk_em: &'static str,
+        sk_em: &'static str,
+        ikm_r: &'static str,
+        pk_rm: &'static str,
+        sk_rm: &'static str,
+        ciphertexts: [&'static str; 2],
+        exports: [&'static str; 3],
+    }
+
+    /// `Ode on a Grecian Urn`
+    const INFO: &str = "4f6465206f6e2061204772656369616e2055726e";
+    /// `Beauty is truth, truth beauty`
+    const PLAINTEXT: &str = "4265617574792069732074727574682c20747275746820626561757479";
+    const EXPORTER_CONTEXTS: [&str; 3] = ["", "00", "54657374436f6e74657874"];
+
+    /// RFC 9180, Appendix A.6.1: DHKEM(P-521, HKDF-SHA512), HKDF-SHA512,
+    /// AES-256-GCM.
+    const P521: Vector = Vector {
+        config: HpkeConfig(HpkeKemType::DhKemP521, HpkeKdfType::HkdfSha512, HpkeAeadType::AesGcm256),
+        ikm_e: "7f06ab8215105fc46aceeb2e3dc5028b44364f960426eb0d8e4026c2f8b5d7e7a986688f1591abf5ab753c357a5d6f0440414b4ed4ede71317772ac98d9239f70904",
+        pk_em: "040138b385ca16bb0d5fa0c0665fbbd7e69e3ee29f63991d3e9b5fa740aab8900aaeed46ed73a49055758425a0ce36507c54b29cc5b85a5cee6bae0cf1c21f2731ece2013dc3fb7c8d21654bb161b463962ca19e8c654ff24c94dd2898de12051f1ed0692237fb02b2f8d1dc1c73e9b366b529eb436e98a996ee522aef863dd5739d2f29b0",
+        sk_em: "014784c692da35df6ecde98ee43ac425dbdd0969c0c72b42f2e708ab9d535415a8569bdacfcc0a114c85b8e3f26acf4d68115f8c91a66178cdbd03b7bcc5291e374b",
+        ikm_r: "2ad954bbe39b7122529f7dde780bff626cd97f850d0784a432784e69d86eccaade43b6c10a8ffdb94bf943c6da479db137914ec835a7e715e36e45e29b587bab3bf1",
+        pk_rm: "0401b45498c1714e2dce167d3caf162e45e0642afc7ed435df7902ccae0e84ba0f7d373f646b7738bbbdca11ed91bdeae3cdcba3301f2457be452f271fa6837580e661012af49583a62e48d44bed350c7118c0d8dc861c238c72a2bda17f64704f464b57338e7f40b60959480c0e58e6559b190d81663ed816e523b6b6a418f66d2451ec64",
+        sk_rm: "01462680369ae375e4b3791070a7458ed527842f6a98a79ff5e0d4cbde83c27196a3916956655523a6a2556a7af62c5cadabe2ef9da3760bb21e005202f7b2462847",
+        ciphertexts: [
+            "170f8beddfe949b75ef9c387e201baf4132fa7374593dfafa90768788b7b2b200aafcc6d80ea4c795a7c5b841a",
+            "d9ee248e220ca24ac00bbbe7e221a832e4f7fa64c4fbab3945b6f3af0c5ecd5e16815b328be4954a05fd352256",
+        ],
+        exports: [
+            "05e2e5bd9f0c30832b80a279ff211cc65eceb0d97001524085d609ead60d0412",
+            "fca69744bb537f5b7a1596dbf34eaa8d84bf2e3ee7f1a155d41bd3624aa92b63",
+            "f389beaac6fcf6c0d9376e20f97e364f0609a88f1bc76d7328e9104df8477013",
+        ],
+    };
+
+    /// DHKEM(P-384, HKDF-SHA384), HKDF-SHA384, AES-256-GCM. RFC 9180 has no
+    /// vector for this KEM, so it was generated with the HPKE implementation
+    /// of OpenSSL 3.5 from the inputs of Appendix A.
+    const P384: Vector = Vector {
+        config: HpkeConfig(HpkeKemType::DhKemP384, HpkeKdfType::HkdfSha384, HpkeAeadType::AesGcm256),
+        ikm_e: "c7bf4ebd9724546ad79dc55ee6765191dd01c095e32475d68c08bad60bf2142d5c0cba20058ba9a43ae525ad2d3a1891",
+        pk_em: "0476c78b8de63920c3151ccf7c778471b220a02ab1d01b438b713d706d891f69e0f3910f25724a4190dbbeb941fe3406797089f6ef6285761c9473c003f72c0a5cb05a0b6e6eb46411c56a5b91d295d1792ced494fb6b5ca4e9adb1d6f7bff1cb0",
+        sk_em: "6480104de80492a98b65f19925c90aee38caacc7354e51812e938c342ba09e7f55180ef10119e51df8e6918397b17d60",
+        ikm_r: "bc00524c3d89b8b18fde771f76fc96eb22cec06a8a144b4ae5c1604f97ab7b8ff18d562976d4abcfc8dcfb43ecde7299",
+        pk_rm: "042a4f83b664ba1f8e7754018b1697f0c3248a1e05b75b389093868a6703f4146ba01c829b45b86280594ebb17bb318e793105752af461708a9eb005f7807847643c363d8114bee77309373a1cb76a46bd26a864974911fcc937fba8d7c6b92d57",
+        sk_rm: "0a07784db10313c396f8ef526b92bffaaa3977f1455344f1614b37a3f6cb5edfac4098b87285a3d40dccb7605b43100a",
+        ciphertexts: [
+            "c1ce2b63f2626cccc05a3f8d9057077f50c14b08019f1c0ee14b4ce1d4e2262accd9e4026f3aea88dd2b7de029",
+            "6b3c9c70434d3cc6653cda7e9107d2552fa6c2e2dd662a3b6d734956e7ad0a6ebf8abfccb56612ea594ed8b1ea",
+        ],
+        exports: [
+            "31c5dfaef4aa61536256c1265d104f504acf2d49c645ce50ceecdde396269c00",
+            "adaa49e4df291766ca87652069b31f883cc75ba2b62cfcbd9acebf4b04b700ad",
+            "a018de5ffd6898ab0d55d753edc6395bb920ad8e6dfa22c90374d6d7ae3eeb92",
+        ],
+    };
+
+    /// DHKEM(X448, HKDF-SHA512), HKDF-SHA512, ChaCha20Poly1305. RFC 9180 has
+    /// no vector for this KEM, so it was generated with the HPKE
+    /// implementation of OpenSSL 3.5 from the inputs of Appendix A. OpenSSL
+    /// reports the private keys clamped. They are listed unclamped, like the
+    /// X25519 private keys in Appendix A.1.
+    const X448: Vector = Vector {
+        config: HpkeConfig(HpkeKemType::DhKem448, HpkeKdfType::HkdfSha512, HpkeAeadType::ChaCha20Poly1305),
+        ikm_e: "87a12060ad55357c48e444a2f1e0e932ba429e3a4f0740fb9fab9a3d8fb50d43459eaf3c39ecaccdf3fe699136f3e8ec83bb9cc024754356",
+        pk_em: "1fc87a0af182099522246c43398fe60cb41e47acf8a7924c7726af3beb39c775a930546f26ebf48e60c435fce9b49329cbb3422740be7529",
+        sk_em: "200a331cb10ca051eb48b3500dd1031030c01eacfa600ca222778d9641ba230e48ce28bc72922eb5ab0f32aee583fba5e67a29a4f9162c15",
+        ikm_r: "b19985230cfeb3a1577486b10c14f6bda58b64c4d5c64f3c14cbd7521d9e74557c508a9beb94b6b2b072a99da55061592afa036ea710ee66",
+        pk_rm: "965bbfcd47dd306b8f03bae8d7f28b8fc7d6daa71b13ab639f53505a5656d7cd22ff32da95d8df91b9fa1a44f67fca259f9452f27aeb82fe",
+        sk_rm: "4c7b9e2c680c48e04d7dc80af1c937042dd0f655bc0c69b2b104cda29c50c2162983cf9632dd35ee984fa80c72bf71cf66c79aa1296377ef",
+        ciphertexts: [
+            "1d51e67c04b45441fdeb57fe9744b06a5309e3f0e8c638b58cdbea5992a238c9721ffa989458a0a99126ae6cdf",
+            "d64b94aa65fe4a04b98a74ea2b3c4f380a72bb4832b5d18926dc9c8c058bfd554db857fcebc0cf81786cc6cfdf",
+        ],
+        exports: [
+            "21d88dbbde23583462f5267f9671611378c58736702649b3c454c466e373fb93",
+            "a9589a69646c755b98078835a82f4d8f5a2e9d50f1058416f5c9c4ed513b8c38",
+            "f9095266f65e721e01299b39c4e0387ccbd396478d858f1945a062d4348961f3",
+        ],
+    };
+
+    fn check(vector: &Vector) {
+        let crypto = RustCrypto::default();
+        let hex = |s: &str| hex::decode(s).unwrap();
+
+        let sender = derive_key_pair(&crypto, &vector.config, &hex(vector.ikm_e)).unwrap();
+        assert_eq!(sender.private.as_ref(), hex(vector.sk_em));
+        assert_eq!(sender.public, hex(vector.pk_em));
+        let receiver = derive_key_pair(&crypto, &vector.config, &hex(vector.ikm_r)).unwrap();
+        assert_eq!(receiver.private.as_ref(), hex(vector.sk_rm));
+        assert_eq!(receiver.public, hex(vector.pk_rm));
+
+        // The encapsulated key is the public key of the sender. The
+        // ciphertexts after the first one use the nonce of their sequence
+        // number, so they are decrypted with the context directly.
+        let enc = hex(vector.pk_em);
+        let context = setup_receiver(
+            &crypto,
+            &vector.config,
+            &enc,
+            &hex(vector.sk_rm),
+            &hex(INFO),
+        )
+        .unwrap();
+        let aead = aead_type(vector.config.2).unwrap();
+        for (sequence_number, ciphertext) in vector.ciphertexts.iter().enumerate() {
+            let mut nonce = context.base_nonce.clone();
+            let nonce_len = nonce.len();
+            for (n, s) in nonce[nonce_len - 8..]
+                .iter_mut()
+                .zip((sequence_number as u64).to_be_bytes())
+            {
+                *n ^= s;
+            }
+            let aad = format!("Count-{sequence_number}");
+            let plaintext = crypto
+                .aead_decrypt(aead, &context.key, &hex(ciphertext), &nonce, aad.as_bytes())
+                .unwrap();
+            assert_eq!(plaintext, hex(PLAINTEXT));
+        }
+        let plaintext = open(
+            &crypto,
+            &vector.config,
+            &enc,
+            &hex(vector.sk_rm),
+            &hex(INFO),
+            b"Count-0",
+            &hex(vector.ciphertexts[0]),
+        )
+        .unwrap();
+        assert_eq!(plaintext, hex(PLAINTEXT));
+
+        for (exporter_context, exported) in EXPORTER_CONTEXTS.iter().zip(vector.exports) {
+            let secret = setup_receiver_and_export(
+                &crypto,
+                &vector.config,
+                &enc,
+                &hex(vector.sk_rm),
+                &hex(INFO),
+                &hex(exporter_context),
+                32,
+            )
+            .unwrap();
+            assert_eq!(secret, hex(exported));
+        }
+
+        // Encryption is randomized, so it is checked by decrypting.
+        let (enc, ciphertext) = seal(
+            &crypto,
+            &vector.config,
+            &hex(vector.pk_rm),
+            &hex(INFO),
+            b"Count-0",
+            &hex(PLAINTEXT),
+        )
+        .unwrap();
+        let plaintext = open(
+            &crypto,
+            &vector.config,
+            &enc,
+            &hex(vector.sk_rm),
+            &hex(INFO),
+            b"Count-0",
+            &ciphertext,
+        )
+        .unwrap();
+        assert_eq!(plaintext, hex(PLAINTEXT));
+    }
+
+    #[test]
+    fn p521_vector() {
+        check(&P521);
+    }
+
+    #[test]
+    fn p384_vector() {
+        check(&P384);
+    }
+
+    #[test]
+    fn x448_vector() {
+        check(&X448);
+    }
+
+    /// Public keys that aren't valid `SerializePublicKey` outputs for the KEM
+    /// of `vector`.
+    fn invalid_public_keys(vector: &Vector) -> Vec<Vec<u8>> {
+        let pk = hex::decode(vector.pk_rm).unwrap();
+        let mut invalid = vec![
+            vec![],
+            pk[..pk.len() - 1].to_vec(),
+            [pk.as_slice(), &[0]].concat(),
+        ];
+        match Kem::try_from(vector.config.0).unwrap() {
+            Kem::P384 | Kem::P521 => {
+                // The identity, a compressed point, and a point that isn't
+                // on the curve.
+                invalid.push(vec![0x00]);
+                let mut compressed = pk[..(pk.len() + 1) / 2].to_vec();
+                compressed[0] = 0x02 | (pk[pk.len() - 1] & 1);
+                invalid.push(compressed);
+                let mut off_curve = pk.clone();
+                off_curve[pk.len() - 1] ^= 1;
+                invalid.push(off_curve);
+            }
+            Kem::X448 => {
+                // The points of small order 0 and 1, and p - 1, which result
+                // in the all-zero shared secret.
+                invalid.push(vec![0; 56]);
+                let mut one = vec![0; 56];
+                one[0] = 1;
+                invalid.push(one);
+                let mut p_minus_one = vec![0xff; 56];
+                p_minus_one[0] = 0xfe;
+                p_minus_one[28] = 0xfe;
+                invalid.push(p_minus_one);
+            }
+        }
+        invalid
+    }
+
+    fn check_invalid_inputs(vector: &Vector) {
+        let crypto = RustCrypto::default();
+        let hex = |s: &str| hex::decode(s).unwrap();
+        let info = hex(INFO);
+        let sk_r = hex(vector.sk_rm);
+
+        for pk in invalid_public_keys(vector) {
+            assert!(
+                seal(&crypto, &vector.config, &pk, &info, &[], &hex(PLAINTEXT)).is_err(),
+                "sealed to the invalid public key {}",
+                hex::encode(&pk)
+            );
+            assert!(
+                setup_sender_and_export(&crypto, &vector.config, &pk, &info, &[], 32).is_err(),
+                "exported for the invalid public key {}",
+                hex::encode(&pk)
+            );
+            assert!(
+                setup_receiver_and_export(&crypto, &vector.config, &pk, &sk_r, &info, &[], 32)
+                    .is_err(),
+                "accepted the invalid encapsulated key {}",
+                hex::encode(&pk)
+            );
+        }
+
+        // Private keys must be in the range of the scalars.
+        let kem = Kem::try_from(vector.config.0).unwrap();
+        let enc = hex(vector.pk_em);
+        let mut invalid_private_keys = vec![vec![]];
+        match kem {
+            // Shorter NIST curve private keys are zero-padded.
+            Kem::P384 | Kem::P521 => {
+                invalid_private_keys.push(vec![0; kem.private_key_len()]);
+                invalid_private_keys.push(vec![0xff; kem.private_key_len()]);
+            }
+            Kem::X448 => invalid_private_keys.push(sk_r[1..].to_vec()),
+        }
+        for sk in invalid_private_keys {
+            assert!(
+                setup_receiver_and_export(&crypto, &vector.config, &enc, &sk, &info, &[], 32)
+                    .is_err(),
+                "accepted the invalid private key {}",
+                hex::encode(&sk)
+            );
+        }
+
+        // Modified ciphertexts, additional data, info and encapsulated keys
+        // don't decrypt.
+        let ciphertext = hex(vector.ciphertexts[0]);
+        let decrypt = |enc: &[u8], info: &[u8], aad: &[u8], ciphertext: &[u8]| {
+            open(&crypto, &vector.config, enc, &sk_r, info, aad, ciphertext)
+        };
+        assert!(decrypt(&enc, &info, b"Count-0", &ciphertext).is_ok());
+        let mut modified_ciphertext = ciphertext.clone();
+        modified_ciphertext[0] ^= 1;
+        assert!(decrypt(&enc, &info, b"Count-0", &modified_ciphertext).is_err());
+        assert!(decrypt(&enc, &info, b"Count-0", &ciphertext[..ciphertext.len() - 1]).is_err());
+        assert!(decrypt(&enc, &info, b"Count-1", &ciphertext).is_err());
+        assert!(decrypt(&enc, b"", b"Count-0", &ciphertext).is_err());
+        assert!(decrypt(&hex(vector.pk_rm), &info, b"Count-0", &ciphertext).is_err());
+    }
+
+    #[test]
+    fn p521_invalid_inputs() {
+        check_invalid_inputs(&P521);
+    }
+
+    #[test]
+    fn p384_invalid_inputs() {
+        check_invalid_inputs(&P384);
+    }
+
+    #[test]
+    fn x448_invalid_inputs() {
+        check_invalid_inputs(&X448);
+    }
+}
@@ -26,7 +26,9 @@ use rand_core::{RngCore as _, SeedableRng as _};
 use sha2::{Digest, Sha256, Sha384, Sha512};
 use tls_codec::SecretVLBytes;
 
-use crate::hmac;
+#[cfg(feature = "experimental-dhkem-ciphersuites")]
+use crate::dhkem;
+use crate::{hmac, rand_shim::RandCore0_10};
 
 #[derive(Debug)]
 pub struct RustCrypto {
@@ -92,6 +94,11 @@ impl OpenMlsCrypto for RustCrypto {
             Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519
             | Ciphersuite::MLS_128_DHKEMX25519_CHACHA20POLY1305_SHA256_Ed25519
             | Ciphersuite::MLS_128_DHKEMP256_AES128GCM_SHA256_P256 => Ok(()),
+            #[cfg(feature = "experimental-dhkem-ciphersuites")]
+            Ciphersuite::MLS_256_DHKEMX448_AES256GCM_SHA512_Ed448
+            | Ciphersuite::MLS_256_DHKEMP521_AES256GCM_SHA512_P521
+            | Ciphersuite::MLS_256_DHKEMX448_CHACHA20POLY1305_SHA512_Ed448
+            | Ciphersuite::MLS_256_DHKEMP384_AES256GCM_SHA384_P384 => Ok(()),
             #[cfg(feature = "draft-ietf-mls-pq-ciphersuites")]
             Ciphersuite::MLS_192_MLKEM1024_AES256GCM_SHA384_P384
             | Ciphersuite::MLS_128_MLKEM768X25519_AES256GCM_SHA384_Ed25519
@@ -99,6 +106,10 @@ impl OpenMlsCrypto for RustCrypto {
             | Ciphersuite::MLS_128_MLKEM768_AES256GCM_SHA384_P256
             | Ciphersuite::MLS_192_MLKEM768_AES256GCM_SHA384_MLDSA65
             | Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA384_MLDSA87 => Ok(()),
+            #[cfg(any(
+                not(feature = "experimental-dhkem-ciphersuites"),
+                feature = "draft-ietf-mls-pq-ciphersuites"
+            ))]
             _ => Err(CryptoError::UnsupportedCiphersuite),
         }
     }
@@ -108,6 +119,14 @@ impl OpenMlsCrypto for RustCrypto {
             Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519,
             Ciphersuite::MLS_128_DHKEMX25519_CHACHA20POLY1305_SHA256_Ed25519,
             Ciphersuite::MLS_128_DHKEMP256_AES128GCM_SHA256_P256,
+            #[cfg(feature = "experimental-dhkem-ciphersuites")]
+            Ciphersuite::MLS_256_DHKEMX448_AES256GCM_SHA512_Ed448,
+            #[cfg(feature = "experimental-dhkem-ciphersuites")]
+            Ciphersuite::MLS_256_DHKEMP521_AES256GCM_SHA512_P521,
+            #[cfg(feature = "experimental-dhkem-ciphersuites")]
+            Ciphersuite::MLS_256_DHKEMX448_CHACHA20POLY1305_SHA512_Ed448,
+            #[cfg(feature = "experimental-dhkem-ciphersuites")]
+            Ciphersuite::MLS_256_DHKEMP384_AES256GCM_SHA384_P384,
             #[cfg(feature = "draft-ietf-mls-pq-ciphersuites")]
             Ciphersuite::MLS_192_MLKEM1024_AES256GCM_SHA384_P384,
             #[cfg(feature = "draft-ietf-mls-pq-ciphersuites")]
@@ -297,9 +316,29 @@ impl OpenMlsCrypto for RustCrypto {
                 let pk = k.verifying_key().to_encoded_point(false).as_bytes().into();
                 Ok((k.to_bytes().as_slice().into(), pk))
             }
+            SignatureScheme::ECDSA_SECP521R1_SHA512 => {
+                let mut rng = self
+                    .rng
+                    .write()
+                    .map_err(|_| CryptoError::InsufficientRandomness)?;
+                let k = p521::ecdsa::SigningKey::random(&mut *rng);
+                let pk = p521::ecdsa::VerifyingKey::from(&k)
+                    .to_encoded_point(false)
+                    .as_bytes()
+                    .into();
+                Ok((k.to_bytes().as_slice().into(), pk))
+            }
+            SignatureScheme::ED448 => {
+                let mut rng = self
+                    .rng
+                    .write()
+                    .map_err(|_| CryptoError::InsufficientRandomness)?;
+                let sk = ed448_goldilocks_plus::SigningKey::generate(RandCore0_10(&mut *rng));
+                let pk = sk.verifying_key().to_bytes().to_vec();
+                Ok((sk.to_bytes().to_vec(), pk))
+            }
             #[cfg(feature = "draft-ietf-mls-pq-ciphersuites")]
             SignatureScheme::MLDSA65 => {
-                use crate::rand_shim::RandCore0_10;
                 use ml_dsa::{Generate, Keypair};
                 let sk = {
                     let mut rng = self
@@ -316,7 +355,6 @@ impl OpenMlsCrypto for RustCrypto {
             }
             #[cfg(feature = "draft-ietf-mls-pq-ciphersuites")]
             SignatureScheme::MLDSA87 => {
-                use crate::rand_shim::RandCore0_10;
                 use ml_dsa::{Generate, Keypair};
                 let sk = {
                     let mut rng = self
@@ -331,7 +369,6 @@ impl OpenMlsCrypto for RustCrypto {
                 let sk = sk.to_seed().to_vec();
                 Ok((sk, pk))
             }
-            _ => Err(CryptoError::UnsupportedSignatureScheme),
         }
     }
 
@@ -378,6 +415,27 @@ impl OpenMlsCrypto for RustCrypto {
                 )
                 .map_err(|_| CryptoError::InvalidSignature)
             }
+            SignatureScheme::ECDSA_SECP521R1_SHA512 => {
+                let k = p521::ecdsa::VerifyingKey::from_sec1_bytes(pk)
+                    .map_err(|_| CryptoError::CryptoLibraryError)?;
+                k.verify(
+                    data,
+                    &p521::ecdsa::Signature::from_der(signature)
+                        .map_err(|_| CryptoError::InvalidSignature)?,
+                )
+                .map_err(|_| CryptoError::InvalidSignature)
+            }
+            SignatureScheme::ED448 => {
+                use ed448_goldilocks_plus::crypto_signature::Verifier;
+                let k = ed448_goldilocks_plus::VerifyingKey::from_bytes(
+                    pk.try_into().map_err(|_| CryptoError::CryptoLibraryError)?,
+                )
+                .map_err(|_| CryptoError::CryptoLibraryError)?;
+                let signature = ed448_goldilocks_plus::Signature::from_slice(signature)
+                    .map_err(|_| CryptoError::InvalidSignature)?;
+                k.verify(data, &signature)
+                    .map_err(|_| CryptoError::InvalidSignature)
+            }
             #[cfg(feature = "draft-ietf-mls-pq-ciphersuites")]
             SignatureScheme::MLDSA65 => {
                 use ml_dsa::Verifier;
@@ -406,7 +464,6 @@ impl OpenMlsCrypto for RustCrypto {
                 key.verify(data, &signature)
                     .map_err(|_| CryptoError::InvalidSignature)
             }
-            _ => Err(CryptoError::UnsupportedSignatureScheme),
         }
     }
 
@@ -429,6 +486,18 @@ impl OpenMlsCrypto for RustCrypto {
                 let signature: p384::ecdsa::Signature = k.sign(data);
                 Ok(signature.to_der().to_bytes().into())
             }
+            SignatureScheme::ECDSA_SECP521R1_SHA512 => {
+                let k = p521::ecdsa::SigningKey::from_slice(key)
+                    .map_err(|_| CryptoError::CryptoLibraryError)?;
+                let signature: p521::ecdsa::Signature = k.sign(data);
+                Ok(signature.to_der().to_bytes().into())
+            }
+            SignatureScheme::ED448 => {
+                let k = ed448_goldilocks_plus::SigningKey::try_from(key)
+                    .map_err(|_| CryptoError::CryptoLibraryError)?;
+                let signature = k.sign_raw(data);
+                Ok(signature.to_bytes().into())
+            }
             SignatureScheme::ED25519 => {
                 let k = ed25519_dalek::SigningKey::try_from(key)
                     .map_err(|_| CryptoError::CryptoLibraryError)?;
@@ -451,7 +520,6 @@ impl OpenMlsCrypto for RustCrypto {
                 let signature = k.sign(data);
                 Ok(signature.encode().to_vec())
             }
-            _ => Err(CryptoError::UnsupportedSignatureScheme),
         }
     }
 
@@ -463,6 +531,14 @@ impl OpenMlsCrypto for RustCrypto {
         aad: &[u8],
         ptxt: &[u8],
     ) -> Result<types::HpkeCiphertext, CryptoError> {
+        #[cfg(feature = "experimental-dhkem-ciphersuites")]
+        if dhkem::supports(config.0) {
+            let (kem_output, ciphertext) = dhkem::seal(self, &config, pk_r, info, aad, ptxt)?;
+            return Ok(HpkeCiphertext {
+                kem_output: kem_output.into(),
+                ciphertext: ciphertext.into(),
+            });
+        }
         let (kem_output, ciphertext) = hpke_from_config(config)
             .seal(&pk_r.into(), info, aad, ptxt, None, None, None)
             .map_err(|e| match e {
@@ -483,6 +559,19 @@ impl OpenMlsCrypto for RustCrypto {
         info: &[u8],
         aad: &[u8],
     ) -> Result<Vec<u8>, CryptoError> {
+        #[cfg(feature = "experimental-dhkem-ciphersuites")]
+        if dhkem::supports(config.0) {
+            return dhkem::open(
+                self,
+                &config,
+                input.kem_output.as_slice(),
+                sk_r,
+                info,
+                aad,
+                input.ciphertext.as_slice(),
+            )
+            .map_err(|_| CryptoError::HpkeDecryptionError);
+        }
         hpke_from_config(config)
             .open(
                 input.kem_output.as_slice(),
@@ -505,6 +594,19 @@ impl OpenMlsCrypto for RustCrypto {
         exporter_context: &[u8],
         exporter_length: usize,
     ) -> Result<(Vec<u8>, ExporterSecret), CryptoError> {
+        #[cfg(feature = "experimental-dhkem-ciphersuites")]
+        if dhkem::supports(config.0) {
+            let (kem_output, exported_secret) = dhkem::setup_sender_and_export(
+                self,
+                &config,
+                pk_r,
+                info,
+                exporter_context,
+                exporter_length,
+            )
+            .map_err(|_| CryptoError::SenderSetupError)?;
+            return Ok((kem_output, exported_secret.into()));
+        }
         let (kem_output, context) = hpke_from_config(config)
             .setup_sender(&pk_r.into(), info, None, None, None)
             .map_err(|_| CryptoError::SenderSetupError)?;
@@ -523,6 +625,20 @@ impl OpenMlsCrypto for RustCrypto {
         exporter_context: &[u8],
         exporter_length: usize,
     ) -> Result<ExporterSecret, CryptoError> {
+        #[cfg(feature = "experimental-dhkem-ciphersuites")]
+        if dhkem::supports(config.0) {
+            let exported_secret = dhkem::setup_receiver_and_export(
+                self,
+                &config,
+                enc,
+                sk_r,
+                info,
+                exporter_context,
+                exporter_length,
+            )
+            .map_err(|_| CryptoError::ReceiverSetupError)?;
+            return Ok(exported_secret.into());
+        }
         let context = hpke_from_config(config)
             .setup_receiver(enc, &sk_r.into(), info, None, None, None)
             .map_err(|_| CryptoError::ReceiverSetupError)?;
@@ -537,6 +653,10 @@ impl OpenMlsCrypto for RustCrypto {
         config: HpkeConfig,
         ikm: &[u8],
     ) -> Result<types::HpkeKeyPair, CryptoError> {
+        #[cfg(feature = "experimental-dhkem-ciphersuites")]
+        if dhkem::supports(config.0) {
+            return dhkem::derive_key_pair(self, &config, ikm);
+        }
         let kp = hpke_from_config(config)
             .derive_key_pair(ikm)
             .map_err(|e| match e {
@@ -0,0 +1,812 @@
+use openmls_traits::{
+    async_storage::AsyncStorageProvider,
+    storage::{traits, CURRENT_VERSION},
+};
+
+use super::{
+    codec::Codec,
+    connections::Connections,
+    group_data::{GroupDataType, StorableGroupData, StorableGroupDataRef},
+    storage_provider::{load_epoch_key_pairs, load_psk_bundle},
+    wrappers::{
+        StorableEncryptionKeyPair, StorableEncryptionKeyPairRef, StorableEncryptionPublicKeyRef,
+        StorableEpochKeyPairsRef, StorableHashRef, StorableKeyPackage, StorableKeyPackageRef,
+        StorableLeafNode, StorableLeafNodeRef, StorableProposal, StorableProposalRef,
+        StorablePskBundleRef, StorablePskIdRef, StorableSignatureKeyPairs,
+        StorableSignatureKeyPairsRef, StorableSignaturePublicKeyRef,
+    },
+    AsyncPostgresStorageProvider,
+};
+
+impl<C: Codec, Conn: Connections> AsyncStorageProvider<CURRENT_VERSION>
+    for AsyncPostgresStorageProvider<C, Conn>
+{
+    type Error = sqlx::Error;
+
+    // Transactions nest into the transaction of an `AsyncPostgresTransaction`.
+    // On the pool, they are no-ops.
+
+    async fn begin_transaction(&self) -> Result<(), Self::Error> {
+        self.connections.begin_nested().await
+    }
+
+    async fn commit_transaction(&self) -> Result<(), Self::Error> {
+        self.connections.end_nested(true).await
+    }
+
+    async fn rollback_transaction(&self) -> Result<(), Self::Error> {
+        self.connections.end_nested(false).await
+    }
+
+    async fn write_mls_join_config<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        config: &MlsGroupJoinConfig,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupDataRef(config)
+            .store::<_, C>(&mut *connection, group_id, GroupDataType::JoinGroupConfig)
+            .await
+    }
+
+    async fn append_own_leaf_node<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        LeafNode: traits::LeafNode<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        leaf_node: &LeafNode,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableLeafNodeRef(leaf_node)
+            .store::<_, C>(&mut *connection, group_id)
+            .await
+    }
+
+    async fn queue_proposal<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION> + Send + Sync,
+        QueuedProposal: traits::QueuedProposal<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+        proposal: &QueuedProposal,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableProposalRef(proposal_ref, proposal)
+            .store::<_, C>(&mut *connection, group_id)
+            .await
+    }
+
+    async fn write_tree<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        TreeSync: traits::TreeSync<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        tree: &TreeSync,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupDataRef(tree)
+            .store::<_, C>(&mut *connection, group_id, GroupDataType::Tree)
+            .await
+    }
+
+    async fn write_interim_transcript_hash<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        InterimTranscriptHash: traits::InterimTranscriptHash<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        interim_transcript_hash: &InterimTranscriptHash,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupDataRef(interim_transcript_hash)
+            .store::<_, C>(
+                &mut *connection,
+                group_id,
+                GroupDataType::InterimTranscriptHash,
+            )
+            .await
+    }
+
+    async fn write_context<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        GroupContext: traits::GroupContext<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_context: &GroupContext,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupDataRef(group_context)
+            .store::<_, C>(&mut *connection, group_id, GroupDataType::Context)
+            .await
+    }
+
+    async fn write_confirmation_tag<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        ConfirmationTag: traits::ConfirmationTag<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        confirmation_tag: &ConfirmationTag,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupDataRef(confirmation_tag)
+            .store::<_, C>(&mut *connection, group_id, GroupDataType::ConfirmationTag)
+            .await
+    }
+
+    async fn write_group_state<
+        GroupState: traits::GroupState<CURRENT_VERSION> + Send + Sync,
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_state: &GroupState,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupDataRef(group_state)
+            .store::<_, C>(&mut *connection, group_id, GroupDataType::GroupState)
+            .await
+    }
+
+    async fn write_message_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        MessageSecrets: traits::MessageSecrets<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        message_secrets: &MessageSecrets,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupDataRef(message_secrets)
+            .store::<_, C>(&mut *connection, group_id, GroupDataType::MessageSecrets)
+            .await
+    }
+
+    async fn write_resumption_psk_store<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        ResumptionPskStore: traits::ResumptionPskStore<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        resumption_psk_store: &ResumptionPskStore,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupDataRef(resumption_psk_store)
+            .store::<_, C>(
+                &mut *connection,
+                group_id,
+                GroupDataType::ResumptionPskStore,
+            )
+            .await
+    }
+
+    async fn write_own_leaf_index<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        LeafNodeIndex: traits::LeafNodeIndex<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        own_leaf_index: &LeafNodeIndex,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupDataRef(own_leaf_index)
+            .store::<_, C>(&mut *connection, group_id, GroupDataType::OwnLeafIndex)
+            .await
+    }
+
+    async fn write_group_epoch_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        GroupEpochSecrets: traits::GroupEpochSecrets<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_epoch_secrets: &GroupEpochSecrets,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupDataRef(group_epoch_secrets)
+            .store::<_, C>(&mut *connection, group_id, GroupDataType::GroupEpochSecrets)
+            .await
+    }
+
+    fn supports_future_epoch_messages(&self) -> bool {
+        true
+    }
+
+    async fn write_future_epoch_messages<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        FutureEpochMessages: traits::FutureEpochMessages<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        future_epoch_messages: &FutureEpochMessages,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupDataRef(future_epoch_messages)
+            .store::<_, C>(
+                &mut *connection,
+                group_id,
+                GroupDataType::FutureEpochMessages,
+            )
+            .await
+    }
+
+    async fn write_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION> + Send + Sync,
+        SignatureKeyPair: traits::SignatureKeyPair<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+        signature_key_pair: &SignatureKeyPair,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableSignatureKeyPairsRef(signature_key_pair)
+            .store::<_, C>(&mut *connection, public_key)
+            .await
+    }
+
+    async fn write_encryption_key_pair<
+        EncryptionKey: traits::EncryptionKey<CURRENT_VERSION> + Send + Sync,
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+        key_pair: &HpkeKeyPair,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableEncryptionKeyPairRef(key_pair)
+            .store::<_, C>(&mut *connection, public_key)
+            .await
+    }
+
+    async fn write_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        EpochKey: traits::EpochKey<CURRENT_VERSION> + Send + Sync,
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+        key_pairs: &[HpkeKeyPair],
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableEpochKeyPairsRef(key_pairs)
+            .store::<_, _, C>(&mut *connection, group_id, epoch, leaf_index)
+            .await
+    }
+
+    async fn write_key_package<
+        HashReference: traits::HashReference<CURRENT_VERSION> + Send + Sync,
+        KeyPackage: traits::KeyPackage<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        hash_ref: &HashReference,
+        key_package: &KeyPackage,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableKeyPackageRef(key_package)
+            .store::<_, C>(&mut *connection, hash_ref)
+            .await
+    }
+
+    async fn write_psk<
+        PskId: traits::PskId<CURRENT_VERSION> + Send + Sync,
+        PskBundle: traits::PskBundle<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        psk_id: &PskId,
+        psk: &PskBundle,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorablePskBundleRef(psk)
+            .store::<_, C>(&mut *connection, psk_id)
+            .await
+    }
+
+    async fn mls_group_join_config<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<MlsGroupJoinConfig>, Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupData::load::<_, C>(&mut *connection, group_id, GroupDataType::JoinGroupConfig)
+            .await
+    }
+
+    async fn own_leaf_nodes<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        LeafNode: traits::LeafNode<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<LeafNode>, Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableLeafNode::load::<_, C>(&mut *connection, group_id).await
+    }
+
+    async fn queued_proposal_refs<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<ProposalRef>, Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableProposal::<u8, ProposalRef>::load_refs::<_, C>(&mut *connection, group_id).await
+    }
+
+    async fn queued_proposals<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION> + Send + Sync,
+        QueuedProposal: traits::QueuedProposal<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<(ProposalRef, QueuedProposal)>, Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableProposal::load::<_, C>(&mut *connection, group_id).await
+    }
+
+    async fn tree<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        TreeSync: traits::TreeSync<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<TreeSync>, Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupData::load::<_, C>(&mut *connection, group_id, GroupDataType::Tree).await
+    }
+
+    async fn group_context<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        GroupContext: traits::GroupContext<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupContext>, Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupData::load::<_, C>(&mut *connection, group_id, GroupDataType::Context).await
+    }
+
+    async fn interim_transcript_hash<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        InterimTranscriptHash: traits::InterimTranscriptHash<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<InterimTranscriptHash>, Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupData::load::<_, C>(
+            &mut *connection,
+            group_id,
+            GroupDataType::InterimTranscriptHash,
+        )
+        .await
+    }
+
+    async fn confirmation_tag<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        ConfirmationTag: traits::ConfirmationTag<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<ConfirmationTag>, Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupData::load::<_, C>(&mut *connection, group_id, GroupDataType::ConfirmationTag)
+            .await
+    }
+
+    async fn group_state<
+        GroupState: traits::GroupState<CURRENT_VERSION> + Send + Sync,
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupState>, Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupData::load::<_, C>(&mut *connection, group_id, GroupDataType::GroupState).await
+    }
+
+    async fn message_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        MessageSecrets: traits::MessageSecrets<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<MessageSecrets>, Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupData::load::<_, C>(&mut *connection, group_id, GroupDataType::MessageSecrets)
+            .await
+    }
+
+    async fn resumption_psk_store<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        ResumptionPskStore: traits::ResumptionPskStore<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<ResumptionPskStore>, Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupData::load::<_, C>(
+            &mut *connection,
+            group_id,
+            GroupDataType::ResumptionPskStore,
+        )
+        .await
+    }
+
+    async fn own_leaf_index<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        LeafNodeIndex: traits::LeafNodeIndex<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<LeafNodeIndex>, Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupData::load::<_, C>(&mut *connection, group_id, GroupDataType::OwnLeafIndex)
+            .await
+    }
+
+    async fn group_epoch_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        GroupEpochSecrets: traits::GroupEpochSecrets<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupEpochSecrets>, Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupData::load::<_, C>(
+            &mut *connection,
+            group_id,
+            GroupDataType::GroupEpochSecrets,
+        )
+        .await
+    }
+
+    async fn future_epoch_messages<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        FutureEpochMessages: traits::FutureEpochMessages<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<FutureEpochMessages>, Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupData::load::<_, C>(
+            &mut *connection,
+            group_id,
+            GroupDataType::FutureEpochMessages,
+        )
+        .await
+    }
+
+    async fn signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION> + Send + Sync,
+        SignatureKeyPair: traits::SignatureKeyPair<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> Result<Option<SignatureKeyPair>, Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableSignatureKeyPairs::load::<_, C>(&mut *connection, public_key).await
+    }
+
+    async fn encryption_key_pair<
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION> + Send + Sync,
+        EncryptionKey: traits::EncryptionKey<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> Result<Option<HpkeKeyPair>, Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableEncryptionKeyPair::load::<_, C>(&mut *connection, public_key).await
+    }
+
+    async fn encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        EpochKey: traits::EpochKey<CURRENT_VERSION> + Send + Sync,
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> Result<Vec<HpkeKeyPair>, Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        load_epoch_key_pairs::<_, _, _, C>(&mut *connection, group_id, epoch, leaf_index).await
+    }
+
+    async fn key_package<
+        KeyPackageRef: traits::HashReference<CURRENT_VERSION> + Send + Sync,
+        KeyPackage: traits::KeyPackage<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<Option<KeyPackage>, Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableKeyPackage::load::<_, C>(&mut *connection, hash_ref).await
+    }
+
+    async fn psk<
+        PskBundle: traits::PskBundle<CURRENT_VERSION> + Send + Sync,
+        PskId: traits::PskId<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        psk_id: &PskId,
+    ) -> Result<Option<PskBundle>, Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        load_psk_bundle::<_, _, C>(&mut *connection, psk_id).await
+    }
+
+    async fn remove_proposal<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        self.wrap_storable_group_id_ref(group_id)
+            .delete_proposal(&mut *connection, proposal_ref)
+            .await
+    }
+
+    async fn delete_own_leaf_nodes<GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        self.wrap_storable_group_id_ref(group_id)
+            .delete_leaf_nodes(&mut *connection)
+            .await
+    }
+
+    async fn delete_group_config<GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        self.wrap_storable_group_id_ref(group_id)
+            .delete_group_data(&mut *connection, GroupDataType::JoinGroupConfig)
+            .await
+    }
+
+    async fn delete_tree<GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        self.wrap_storable_group_id_ref(group_id)
+            .delete_group_data(&mut *connection, GroupDataType::Tree)
+            .await
+    }
+
+    async fn delete_confirmation_tag<GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        self.wrap_storable_group_id_ref(group_id)
+            .delete_group_data(&mut *connection, GroupDataType::ConfirmationTag)
+            .await
+    }
+
+    async fn delete_group_state<GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        self.wrap_storable_group_id_ref(group_id)
+            .delete_group_data(&mut *connection, GroupDataType::GroupState)
+            .await
+    }
+
+    async fn delete_context<GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        self.wrap_storable_group_id_ref(group_id)
+            .delete_group_data(&mut *connection, GroupDataType::Context)
+            .await
+    }
+
+    async fn delete_interim_transcript_hash<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        self.wrap_storable_group_id_ref(group_id)
+            .delete_group_data(&mut *connection, GroupDataType::InterimTranscriptHash)
+            .await
+    }
+
+    async fn delete_message_secrets<GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        self.wrap_storable_group_id_ref(group_id)
+            .delete_group_data(&mut *connection, GroupDataType::MessageSecrets)
+            .await
+    }
+
+    async fn delete_all_resumption_psk_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        self.wrap_storable_group_id_ref(group_id)
+            .delete_group_data(&mut *connection, GroupDataType::ResumptionPskStore)
+            .await
+    }
+
+    async fn delete_own_leaf_index<GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        self.wrap_storable_group_id_ref(group_id)
+            .delete_group_data(&mut *connection, GroupDataType::OwnLeafIndex)
+            .await
+    }
+
+    async fn delete_group_epoch_secrets<GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        self.wrap_storable_group_id_ref(group_id)
+            .delete_group_data(&mut *connection, GroupDataType::GroupEpochSecrets)
+            .await
+    }
+
+    async fn delete_future_epoch_messages<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        self.wrap_storable_group_id_ref(group_id)
+            .delete_group_data(&mut *connection, GroupDataType::FutureEpochMessages)
+            .await
+    }
+
+    async fn clear_proposal_queue<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        self.wrap_storable_group_id_ref(group_id)
+            .delete_all_proposals(&mut *connection)
+            .await
+    }
+
+    async fn delete_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableSignaturePublicKeyRef(public_key)
+            .delete::<C>(&mut *connection)
+            .await
+    }
+
+    async fn delete_encryption_key_pair<
+        EncryptionKey: traits::EncryptionKey<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableEncryptionPublicKeyRef(public_key)
+            .delete::<C>(&mut *connection)
+            .await
+    }
+
+    async fn delete_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        EpochKey: traits::EpochKey<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        self.wrap_storable_group_id_ref(group_id)
+            .delete_epoch_key_pair(&mut *connection, epoch, leaf_index)
+            .await
+    }
+
+    async fn delete_key_package<
+        KeyPackageRef: traits::HashReference<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableHashRef(hash_ref)
+            .delete_key_package::<C>(&mut *connection)
+            .await
+    }
+
+    async fn delete_psk<PskKey: traits::PskId<CURRENT_VERSION> + Send + Sync>(
+        &self,
+        psk_id: &PskKey,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorablePskIdRef(psk_id).delete::<C>(&mut *connection).await
+    }
+
+    #[cfg(feature = "extensions-draft")]
+    async fn write_application_export_tree<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        ApplicationExportTree: traits::ApplicationExportTree<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        application_export_tree: &ApplicationExportTree,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupDataRef(application_export_tree)
+            .store::<_, C>(
+                &mut *connection,
+                group_id,
+                GroupDataType::ApplicationExportTree,
+            )
+            .await
+    }
+
+    #[cfg(feature = "extensions-draft")]
+    async fn application_export_tree<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        ApplicationExportTree: traits::ApplicationExportTree<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<ApplicationExportTree>, Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        StorableGroupData::load::<_, C>(
+            &mut *connection,
+            group_id,
+            GroupDataType::ApplicationExportTree,
+        )
+        .await
+    }
+
+    #[cfg(feature = "extensions-draft")]
+    async fn delete_application_export_tree<
+        GroupId: traits::GroupId<CURRENT_VERSION> + Send + Sync,
+        ApplicationExportTree: traits::ApplicationExportTree<CURRENT_VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let mut connection = self.connections.connection().await?;
+        self.wrap_storable_group_id_ref(group_id)
+            .delete_group_data(&mut *connection, GroupDataType::ApplicationExportTree)
+            .await
+    }
+}
@@ -0,0 +1,100 @@
+use std::{
+    future::Future,
+    ops::{Deref, DerefMut},
+};
+
+use sqlx::{
+    PgConnection, PgPool, Postgres, TransactionManager, pool::PoolConnection,
+    postgres::PgTransactionManager,
+};
+use tokio::sync::{MappedMutexGuard, MutexGuard};
+
+use super::TransactionConnection;
+
+/// The connections that the operations of an
+/// [`AsyncPostgresStorageProvider`](super::AsyncPostgresStorageProvider) run
+/// on.
+pub trait Connections: Send + Sync {
+    /// Returns a connection to run an operation on.
+    fn connection(&self) -> impl Future<Output = Result<ConnectionGuard<'_>, sqlx::Error>> + Send;
+
+    /// Begins a nested transaction.
+    fn begin_nested(&self) -> impl Future<Output = Result<(), sqlx::Error>> + Send;
+
+    /// Commits or rolls back the innermost nested transaction. Without a
+    /// nested transaction, this does nothing.
+    fn end_nested(&self, commit: bool) -> impl Future<Output = Result<(), sqlx::Error>> + Send;
+}
+
+// Each operation on the pool runs on its own connection, so there is no
+// connection to run nested transactions on.
+impl Connections for PgPool {
+    async fn connection(&self) -> Result<ConnectionGuard<'_>, sqlx::Error> {
+        Ok(ConnectionGuard::Pool(self.acquire().await?))
+    }
+
+    async fn begin_nested(&self) -> Result<(), sqlx::Error> {
+        Ok(())
+    }
+
+    async fn end_nested(&self, _commit: bool) -> Result<(), sqlx::Error> {
+        Ok(())
+    }
+}
+
+impl Connections for TransactionConnection {
+    async fn connection(&self) -> Result<ConnectionGuard<'_>, sqlx::Error> {
+        let transaction = self.0.lock().await;
+        Ok(ConnectionGuard::Transaction(MutexGuard::map(
+            transaction,
+            |transaction| &mut **transaction,
+        )))
+    }
+
+    async fn begin_nested(&self) -> Result<(), sqlx::Error> {
+        let mut transaction = self.0.lock().await;
+        PgTransactionManager::begin(&mut **transaction, None).await
+    }
+
+    async fn end_nested(&self, commit: bool) -> Result<(), sqlx::Error> {
+        let mut transaction = self.0.lock().await;
+        let connection = &mut **transaction;
+        // The outermost transaction belongs to the `AsyncPostgresTransaction`.
+        if PgTransactionManager::get_transaction_depth(connection) <= 1 {
+            return Ok(());
+        }
+        if commit {
+            PgTransactionManager::commit(connection).await
+        } else {
+            PgTransactionManager::rollback(connection).await
+        }
+    }
+}
+
+/// A connection used by the
+/// [`AsyncPostgresStorageProvider`](super::AsyncPostgresStorageProvider),
+/// either the one of a transaction or one acquired from the pool.
+pub enum ConnectionGuard<'a> {
+    Transaction(MappedMutexGuard<'a, PgConnection>),
+    Pool(PoolConnection<Postgres>),
+}
+
+impl Deref for ConnectionGuard<'_> {
+    type Target = PgConnection;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            ConnectionGuard::Transaction(connection) => connection,
+            ConnectionGuard::Pool(connection) => connection,
+        }
+    }
+}
+
+impl DerefMut for ConnectionGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            ConnectionGuard::Transaction(connection) => connection,
+            ConnectionGuard::Pool(connection) => connection,
+        }
+    }
+}
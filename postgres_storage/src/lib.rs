@@ -0,0 +1,193 @@
+#![forbid(unsafe_code)]
+#![deny(missing_docs)]
+#![deny(rustdoc::broken_intra_doc_links)]
+#![deny(rustdoc::private_intra_doc_links)]
+//! # PostgreSQL Storage Provider
+//!
+//! This crate implements a storage provider for OpenMLS using SQLx with a
+//! PostgreSQL database.
+//!
+//! The main struct is [`PostgresStorageProvider`], which implements the
+//! [`StorageProvider`](openmls_traits::storage::StorageProvider) trait from the
+//! `openmls_traits` crate.
+//!
+//! For asynchronous applications, the [`AsyncPostgresStorageProvider`] implements
+//! the [`AsyncStorageProvider`](openmls_traits::async_storage::AsyncStorageProvider)
+//! trait on top of a [`PgPool`]. Unlike the [`PostgresStorageProvider`], it
+//! does not block the executor on database access and can be shared between
+//! tasks. Operations that have to be atomic, such as merging a commit, run on
+//! an [`AsyncPostgresTransaction`], which owns a connection of the pool.
+//!
+//! The crate manages its own database migrations in its own migrations table
+//! with the name `_openmls_sqlx_migrations`. All tables created by this crate
+//! are prefixed with `openmls_` to avoid name clashes.
+
+use std::{cell::RefCell, marker::PhantomData};
+
+use openmls_traits::storage::{CURRENT_VERSION, Entity, Key};
+use serde::Serialize;
+use sqlx::{PgConnection, PgPool, Postgres, Transaction};
+use tokio::sync::Mutex;
+
+pub use crate::codec::Codec;
+use crate::{
+    connections::Connections, migrator::MigratorWrapper, storage_provider::block_async_in_place,
+};
+
+mod async_storage_provider;
+mod codec;
+mod connections;
+mod group_data;
+mod migrator;
+mod storage_provider;
+mod wrappers;
+
+/// [`PostgresStorageProvider`] implements the
+/// [`StorageProvider`](openmls_traits::storage::StorageProvider) trait and can
+/// thus be used as a storage provider for OpenMLS.
+///
+/// It is generic over any codec `C` that implements the [`Codec`] trait.
+/// The codec is used to serialize and deserialize the data stored in the
+/// underlying database.
+pub struct PostgresStorageProvider<'a, C> {
+    connection: RefCell<&'a mut PgConnection>,
+    codec: PhantomData<C>,
+}
+
+impl<'a, C: Codec> PostgresStorageProvider<'a, C> {
+    /// Create a new [`PostgresStorageProvider`] based on the given
+    /// [`PgConnection`].
+    pub fn new(connection: &'a mut PgConnection) -> Self {
+        Self {
+            connection: RefCell::new(connection),
+            codec: PhantomData,
+        }
+    }
+
+    /// Run the migrations for the storage provider. Uses sqlx's built-in
+    /// migration support.
+    pub fn run_migrations(&mut self) -> Result<(), sqlx::migrate::MigrateError> {
+        let mut conn = self.connection.borrow_mut();
+        block_async_in_place(
+            sqlx::migrate!("./migrations").run_direct(&mut MigratorWrapper(*conn)),
+        )?;
+        Ok(())
+    }
+
+    fn wrap_storable_group_id_ref<'b, GroupId: Key<CURRENT_VERSION>>(
+        &self,
+        group_id: &'b GroupId,
+    ) -> StorableGroupIdRef<'b, GroupId, C> {
+        StorableGroupIdRef(group_id, PhantomData)
+    }
+}
+
+/// [`AsyncPostgresStorageProvider`] implements the
+/// [`AsyncStorageProvider`](openmls_traits::async_storage::AsyncStorageProvider)
+/// trait and can thus be used as a storage provider for the asynchronous API
+/// of OpenMLS.
+///
+/// It is generic over any codec `C` that implements the [`Codec`] trait.
+/// The codec is used to serialize and deserialize the data stored in the
+/// underlying database.
+///
+/// Operations on the provider run on connections from the pool and are not
+/// grouped into transactions, so a failure while merging a commit can leave
+/// the group partially updated. To run operations atomically, begin an
+/// [`AsyncPostgresTransaction`] with
+/// [`transaction()`](AsyncPostgresStorageProvider::transaction) and use it as
+/// the storage provider for them.
+pub struct AsyncPostgresStorageProvider<C, Conn = PgPool> {
+    connections: Conn,
+    codec: PhantomData<C>,
+}
+
+/// A transaction of the [`AsyncPostgresStorageProvider`].
+///
+/// The transaction owns a dedicated connection of the pool, and all
+/// operations on it run on that connection. Operations on the
+/// [`AsyncPostgresStorageProvider`] and on other transactions are not
+/// affected. Transactions begun through the
+/// [`AsyncStorageProvider`](openmls_traits::async_storage::AsyncStorageProvider)
+/// trait, e.g. by OpenMLS, are nested into it.
+///
+/// The transaction is applied with
+/// [`commit()`](AsyncPostgresStorageProvider::commit) and discarded with
+/// [`rollback()`](AsyncPostgresStorageProvider::rollback) or when it is
+/// dropped.
+pub type AsyncPostgresTransaction<C> = AsyncPostgresStorageProvider<C, TransactionConnection>;
+
+/// The connection of an [`AsyncPostgresTransaction`].
+pub struct TransactionConnection(Mutex<Transaction<'static, Postgres>>);
+
+impl<C: Codec> AsyncPostgresStorageProvider<C> {
+    /// Create a new [`AsyncPostgresStorageProvider`] based on the given
+    /// [`PgPool`].
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            connections: pool,
+            codec: PhantomData,
+        }
+    }
+
+    /// Run the migrations for the storage provider. Uses sqlx's built-in
+    /// migration support.
+    pub async fn run_migrations(&self) -> Result<(), sqlx::migrate::MigrateError> {
+        let mut connection = self.connections.acquire().await?;
+        sqlx::migrate!("./migrations")
+            .run_direct(&mut MigratorWrapper(&mut connection))
+            .await?;
+        Ok(())
+    }
+
+    /// Begins an [`AsyncPostgresTransaction`] on a dedicated connection of the
+    /// pool.
+    pub async fn transaction(&self) -> Result<AsyncPostgresTransaction<C>, sqlx::Error> {
+        Ok(AsyncPostgresStorageProvider {
+            connections: TransactionConnection(Mutex::new(self.connections.begin().await?)),
+            codec: PhantomData,
+        })
+    }
+}
+
+impl<C: Codec> AsyncPostgresTransaction<C> {
+    /// Commits the transaction.
+    pub async fn commit(self) -> Result<(), sqlx::Error> {
+        self.connections.0.into_inner().commit().await
+    }
+
+    /// Rolls back the transaction.
+    pub async fn rollback(self) -> Result<(), sqlx::Error> {
+        self.connections.0.into_inner().rollback().await
+    }
+}
+
+impl<C: Codec, Conn: Connections> AsyncPostgresStorageProvider<C, Conn> {
+    fn wrap_storable_group_id_ref<'b, GroupId: Key<CURRENT_VERSION>>(
+        &self,
+        group_id: &'b GroupId,
+    ) -> StorableGroupIdRef<'b, GroupId, C> {
+        StorableGroupIdRef(group_id, PhantomData)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct KeyRefWrapper<'a, T: Key<CURRENT_VERSION>, C: Codec>(&'a T, PhantomData<C>);
+
+impl<'a, T: Key<CURRENT_VERSION>, C: Codec> KeyRefWrapper<'a, T, C> {
+    fn new(value: &'a T) -> Self {
+        Self(value, PhantomData)
+    }
+}
+
+struct EntityRefWrapper<'a, T: Entity<CURRENT_VERSION>, C: Codec>(&'a T, PhantomData<C>);
+
+impl<'a, T: Entity<CURRENT_VERSION>, C: Codec> EntityRefWrapper<'a, T, C> {
+    fn new(value: &'a T) -> Self {
+        Self(value, PhantomData)
+    }
+}
+
+struct EntitySliceWrapper<'a, T: Entity<CURRENT_VERSION>, C: Codec>(&'a [T], PhantomData<C>);
+
+struct StorableGroupIdRef<'a, GroupId: Key<CURRENT_VERSION>, C: Codec>(&'a GroupId, PhantomData<C>);
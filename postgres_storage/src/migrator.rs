@@ -0,0 +1,185 @@
+use std::time::{Duration, Instant};
+
+use futures_core::future::BoxFuture;
+use sqlx::{
+    Connection, Executor, PgConnection,
+    migrate::{AppliedMigration, Migrate, MigrateError, Migration},
+    query, query_as,
+};
+
+pub(super) struct MigratorWrapper<'a>(pub(super) &'a mut PgConnection);
+
+// The following migration is copied from the `sqlx::migrate::Migrate`
+// implementation for `PgConnection` in sqlx 8.6. The adaptations are the name
+// of the migration table, which is `_openmls_sqlx_migrations` instead of
+// `_sqlx_migrations`, and the advisory lock, which is derived from that name
+// so that it does not collide with the application's own migrations.
+impl<'a> Migrate for MigratorWrapper<'a> {
+    fn ensure_migrations_table(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=PostgreSQL
+            self.0
+                .execute(
+                    r#"
+CREATE TABLE IF NOT EXISTS _openmls_sqlx_migrations (
+    version BIGINT PRIMARY KEY,
+    description TEXT NOT NULL,
+    installed_on TIMESTAMPTZ NOT NULL DEFAULT now(),
+    success BOOLEAN NOT NULL,
+    checksum BYTEA NOT NULL,
+    execution_time BIGINT NOT NULL
+);
+                "#,
+                )
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn dirty_version(&mut self) -> BoxFuture<'_, Result<Option<i64>, MigrateError>> {
+        Box::pin(async move {
+            // language=PostgreSQL
+            let row: Option<(i64,)> = query_as(
+                "SELECT version FROM _openmls_sqlx_migrations WHERE success = false ORDER BY version LIMIT 1",
+            )
+            .fetch_optional(&mut *self.0)
+            .await?;
+
+            Ok(row.map(|r| r.0))
+        })
+    }
+
+    fn list_applied_migrations(
+        &mut self,
+    ) -> BoxFuture<'_, Result<Vec<AppliedMigration>, MigrateError>> {
+        Box::pin(async move {
+            // language=PostgreSQL
+            let rows: Vec<(i64, Vec<u8>)> =
+                query_as("SELECT version, checksum FROM _openmls_sqlx_migrations ORDER BY version")
+                    .fetch_all(&mut *self.0)
+                    .await?;
+
+            let migrations = rows
+                .into_iter()
+                .map(|(version, checksum)| AppliedMigration {
+                    version,
+                    checksum: checksum.into(),
+                })
+                .collect();
+
+            Ok(migrations)
+        })
+    }
+
+    fn lock(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // Create an application lock over the database. This function will
+            // not return until the lock is acquired.
+            // language=PostgreSQL
+            let _ = query(
+                "SELECT pg_advisory_lock(hashtext(current_database() || '_openmls_sqlx_migrations'))",
+            )
+            .execute(&mut *self.0)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn unlock(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=PostgreSQL
+            let _ = query(
+                "SELECT pg_advisory_unlock(hashtext(current_database() || '_openmls_sqlx_migrations'))",
+            )
+            .execute(&mut *self.0)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn apply<'e: 'm, 'm>(
+        &'e mut self,
+        migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        Box::pin(async move {
+            let mut tx = self.0.begin().await?;
+            let start = Instant::now();
+
+            // Use a single transaction for the actual migration script and the essential bookeeping so we never
+            // execute migrations twice. See https://github.com/launchbadge/sqlx/issues/1966.
+            // The `execution_time` however can only be measured for the whole transaction. This value _only_ exists for
+            // data lineage and debugging reasons, so it is not super important if it is lost. So we initialize it to -1
+            // and update it once the actual transaction completed.
+            let _ = tx
+                .execute(&*migration.sql)
+                .await
+                .map_err(|e| MigrateError::ExecuteMigration(e, migration.version))?;
+
+            // language=PostgreSQL
+            let _ = query(
+                r#"
+    INSERT INTO _openmls_sqlx_migrations ( version, description, success, checksum, execution_time )
+    VALUES ( $1, $2, TRUE, $3, -1 )
+                "#,
+            )
+            .bind(migration.version)
+            .bind(&*migration.description)
+            .bind(&*migration.checksum)
+            .execute(&mut *tx)
+            .await?;
+
+            tx.commit().await?;
+
+            // Update `elapsed_time`.
+            // NOTE: The process may disconnect/die at this point, so the elapsed time value might be lost. We accept
+            //       this small risk since this value is not super important.
+
+            let elapsed = start.elapsed();
+
+            // language=PostgreSQL
+            #[allow(clippy::cast_possible_truncation)]
+            let _ = query(
+                r#"
+    UPDATE _openmls_sqlx_migrations
+    SET execution_time = $1
+    WHERE version = $2
+                "#,
+            )
+            .bind(elapsed.as_nanos() as i64)
+            .bind(migration.version)
+            .execute(&mut *self.0)
+            .await?;
+
+            Ok(elapsed)
+        })
+    }
+
+    fn revert<'e: 'm, 'm>(
+        &'e mut self,
+        migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        Box::pin(async move {
+            // Use a single transaction for the actual migration script and the essential bookkeeping so we never
+            // execute migrations twice. See https://github.com/launchbadge/sqlx/issues/1966.
+            let mut tx = self.0.begin().await?;
+            let start = Instant::now();
+
+            let _ = tx.execute(&*migration.sql).await?;
+
+            // language=PostgreSQL
+            let _ = query(r#"DELETE FROM _openmls_sqlx_migrations WHERE version = $1"#)
+                .bind(migration.version)
+                .execute(&mut *tx)
+                .await?;
+
+            tx.commit().await?;
+
+            let elapsed = start.elapsed();
+
+            Ok(elapsed)
+        })
+    }
+}
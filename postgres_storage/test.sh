@@ -0,0 +1,16 @@
+#!/usr/bin/env bash
+# Runs the tests against a temporary local PostgreSQL instance.
+# Requires `initdb` and `pg_ctl` to be on the `PATH`.
+
+set -euo pipefail
+
+PGDATA="$(mktemp -d)"
+PGPORT="${PGPORT:-54329}"
+trap 'pg_ctl -D "$PGDATA" -m immediate stop > /dev/null; rm -rf "$PGDATA"' EXIT
+
+initdb -D "$PGDATA" -U postgres --auth=trust > /dev/null
+pg_ctl -D "$PGDATA" -o "-p $PGPORT -k $PGDATA -c listen_addresses=localhost" -l "$PGDATA/log" -w start > /dev/null
+
+export DATABASE_URL="postgres://postgres@localhost:$PGPORT/postgres"
+cargo test "$@"
+cargo test -F extensions-draft "$@"
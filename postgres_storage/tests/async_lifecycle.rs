@@ -0,0 +1,260 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::*;
+use openmls_postgres_storage::AsyncPostgresStorageProvider;
+use openmls_traits::async_storage::AsyncStorageProvider;
+use sqlx::postgres::PgPoolOptions;
+use tokio::sync::Barrier;
+
+async fn storage(name: &str) -> AsyncPostgresStorageProvider<JsonCodec> {
+    let pool = PgPoolOptions::new()
+        .connect_with(test_database(name).await)
+        .await
+        .unwrap();
+    let storage = AsyncPostgresStorageProvider::<JsonCodec>::new(pool);
+    storage.run_migrations().await.unwrap();
+    storage
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn proposals() {
+    let group_id = TestGroupId(b"TestGroupId".to_vec());
+    let proposals = (0..10)
+        .map(|i| TestProposal(format!("TestProposal{i}").as_bytes().to_vec()))
+        .collect::<Vec<_>>();
+    let storage = storage("async_lifecycle_proposals").await;
+
+    for (i, proposal) in proposals.iter().enumerate() {
+        storage
+            .queue_proposal(&group_id, &TestProposalRef(i), proposal)
+            .await
+            .unwrap();
+    }
+
+    let proposals_read: Vec<(TestProposalRef, TestProposal)> =
+        storage.queued_proposals(&group_id).await.unwrap();
+    let proposals_expected: Vec<(TestProposalRef, TestProposal)> = (0..10)
+        .map(TestProposalRef)
+        .zip(proposals.clone())
+        .collect();
+    assert_eq!(proposals_expected, proposals_read);
+
+    storage
+        .remove_proposal(&group_id, &TestProposalRef(5))
+        .await
+        .unwrap();
+
+    let proposal_refs_read: Vec<TestProposalRef> =
+        storage.queued_proposal_refs(&group_id).await.unwrap();
+    let mut expected = (0..10).map(TestProposalRef).collect::<Vec<_>>();
+    expected.remove(5);
+    assert_eq!(expected, proposal_refs_read);
+
+    storage
+        .clear_proposal_queue::<TestGroupId, TestProposalRef>(&group_id)
+        .await
+        .unwrap();
+    let proposals_read: Vec<(TestProposalRef, TestProposal)> =
+        storage.queued_proposals(&group_id).await.unwrap();
+    assert!(proposals_read.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn group_data_roundtrip() {
+    let group_id = TestGroupId(b"group-data".to_vec());
+    let tree = TestBlob(b"tree".to_vec());
+    let group_state = TestBlob(b"group-state".to_vec());
+    let own_leaf_index = TestLeafIndex(42);
+    let leaf = TestBlob(b"leaf".to_vec());
+    let storage = storage("async_lifecycle_group_data_roundtrip").await;
+
+    storage.write_tree(&group_id, &tree).await.unwrap();
+    let tree_read: Option<TestBlob> = storage.tree(&group_id).await.unwrap();
+    assert_eq!(Some(tree.clone()), tree_read);
+    storage.delete_tree(&group_id).await.unwrap();
+    let tree_after_delete: Option<TestBlob> = storage.tree(&group_id).await.unwrap();
+    assert!(tree_after_delete.is_none());
+
+    storage
+        .write_group_state(&group_id, &group_state)
+        .await
+        .unwrap();
+    let group_state_read: Option<TestBlob> = storage.group_state(&group_id).await.unwrap();
+    assert_eq!(Some(group_state.clone()), group_state_read);
+
+    storage
+        .write_own_leaf_index(&group_id, &own_leaf_index)
+        .await
+        .unwrap();
+    let own_index_read: Option<TestLeafIndex> = storage.own_leaf_index(&group_id).await.unwrap();
+    assert_eq!(Some(own_leaf_index.clone()), own_index_read);
+
+    storage
+        .append_own_leaf_node(&group_id, &leaf)
+        .await
+        .unwrap();
+    let leaf_nodes_read: Vec<TestBlob> = storage.own_leaf_nodes(&group_id).await.unwrap();
+    assert_eq!(vec![leaf.clone()], leaf_nodes_read);
+    storage.delete_own_leaf_nodes(&group_id).await.unwrap();
+    let leaf_nodes_after_delete: Vec<TestBlob> = storage.own_leaf_nodes(&group_id).await.unwrap();
+    assert!(leaf_nodes_after_delete.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn key_material_roundtrip() {
+    let group_id = TestGroupId(b"key-material-group".to_vec());
+    let signature_public_key = TestSignaturePublicKey(b"signature-public".to_vec());
+    let signature_key_pair = TestSignatureKeyPair(b"signature-key-pair".to_vec());
+    let encryption_key = TestEncryptionKey(b"encryption-public".to_vec());
+    let encryption_pair = TestHpkeKeyPair(b"encryption-pair".to_vec());
+    let epoch = TestEpochKey(b"epoch".to_vec());
+    let epoch_pairs = vec![
+        TestHpkeKeyPair(b"epoch-pair-a".to_vec()),
+        TestHpkeKeyPair(b"epoch-pair-b".to_vec()),
+    ];
+    let hash_ref = TestHashRef(b"hash-ref".to_vec());
+    let key_package = TestKeyPackage(b"key-package".to_vec());
+    let psk_id = TestPskId(b"psk-id".to_vec());
+    let psk_bundle = TestPskBundle(b"psk-bundle".to_vec());
+    let leaf_index: u32 = 7;
+    let storage = storage("async_lifecycle_key_material_roundtrip").await;
+
+    storage
+        .write_signature_key_pair(&signature_public_key, &signature_key_pair)
+        .await
+        .unwrap();
+    let signature_pair_read: Option<TestSignatureKeyPair> = storage
+        .signature_key_pair(&signature_public_key)
+        .await
+        .unwrap();
+    assert_eq!(Some(signature_key_pair.clone()), signature_pair_read);
+
+    storage
+        .write_encryption_key_pair(&encryption_key, &encryption_pair)
+        .await
+        .unwrap();
+    let encryption_pair_read: Option<TestHpkeKeyPair> =
+        storage.encryption_key_pair(&encryption_key).await.unwrap();
+    assert_eq!(Some(encryption_pair.clone()), encryption_pair_read);
+
+    storage
+        .write_encryption_epoch_key_pairs(&group_id, &epoch, leaf_index, &epoch_pairs)
+        .await
+        .unwrap();
+    let epoch_pairs_read: Vec<TestHpkeKeyPair> = storage
+        .encryption_epoch_key_pairs(&group_id, &epoch, leaf_index)
+        .await
+        .unwrap();
+    assert_eq!(epoch_pairs.clone(), epoch_pairs_read);
+    storage
+        .delete_encryption_epoch_key_pairs(&group_id, &epoch, leaf_index)
+        .await
+        .unwrap();
+    let epoch_pairs_after_delete: Vec<TestHpkeKeyPair> = storage
+        .encryption_epoch_key_pairs(&group_id, &epoch, leaf_index)
+        .await
+        .unwrap();
+    assert!(epoch_pairs_after_delete.is_empty());
+
+    storage
+        .write_key_package(&hash_ref, &key_package)
+        .await
+        .unwrap();
+    let key_package_read: Option<TestKeyPackage> = storage.key_package(&hash_ref).await.unwrap();
+    assert_eq!(Some(key_package.clone()), key_package_read);
+    storage.delete_key_package(&hash_ref).await.unwrap();
+    let key_package_after_delete: Option<TestKeyPackage> =
+        storage.key_package(&hash_ref).await.unwrap();
+    assert!(key_package_after_delete.is_none());
+
+    storage.write_psk(&psk_id, &psk_bundle).await.unwrap();
+    let psk_bundle_read: Option<TestPskBundle> = storage.psk(&psk_id).await.unwrap();
+    assert_eq!(Some(psk_bundle.clone()), psk_bundle_read);
+    storage.delete_psk(&psk_id).await.unwrap();
+    let psk_bundle_after_delete: Option<TestPskBundle> = storage.psk(&psk_id).await.unwrap();
+    assert!(psk_bundle_after_delete.is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn transactions() {
+    let group_id = TestGroupId(b"transactions".to_vec());
+    let old_tree = TestBlob(b"old".to_vec());
+    let new_tree = TestBlob(b"new".to_vec());
+    let storage = storage("async_lifecycle_transactions").await;
+    storage.write_tree(&group_id, &old_tree).await.unwrap();
+
+    // Roll back a write and a delete
+    let transaction = storage.transaction().await.unwrap();
+    transaction.write_tree(&group_id, &new_tree).await.unwrap();
+    let tree_read: Option<TestBlob> = transaction.tree(&group_id).await.unwrap();
+    assert_eq!(Some(new_tree.clone()), tree_read);
+    transaction.delete_tree(&group_id).await.unwrap();
+    transaction.rollback().await.unwrap();
+    let tree_read: Option<TestBlob> = storage.tree(&group_id).await.unwrap();
+    assert_eq!(Some(old_tree.clone()), tree_read);
+
+    // Roll back a nested transaction, but commit the transaction
+    let transaction = storage.transaction().await.unwrap();
+    transaction.write_tree(&group_id, &new_tree).await.unwrap();
+    transaction.begin_transaction().await.unwrap();
+    transaction.delete_tree(&group_id).await.unwrap();
+    transaction.rollback_transaction().await.unwrap();
+    transaction.commit().await.unwrap();
+    let tree_read: Option<TestBlob> = storage.tree(&group_id).await.unwrap();
+    assert_eq!(Some(new_tree.clone()), tree_read);
+
+    // A dropped transaction is rolled back
+    let transaction = storage.transaction().await.unwrap();
+    transaction.delete_tree(&group_id).await.unwrap();
+    drop(transaction);
+    let tree_read: Option<TestBlob> = storage.tree(&group_id).await.unwrap();
+    assert_eq!(Some(new_tree), tree_read);
+}
+
+/// Transactions of concurrent tasks run on their own connections, so rolling
+/// back one of them doesn't affect the other.
+#[tokio::test(flavor = "multi_thread")]
+async fn concurrent_transactions() {
+    let storage = Arc::new(storage("async_lifecycle_concurrent_transactions").await);
+
+    let barrier = Arc::new(Barrier::new(2));
+    let tasks = [
+        (b"rolled back".as_slice(), false),
+        (b"committed".as_slice(), true),
+    ]
+    .map(|(name, commit)| {
+        let storage = storage.clone();
+        let barrier = barrier.clone();
+        tokio::spawn(async move {
+            let group_id = TestGroupId(name.to_vec());
+            let transaction = storage.transaction().await.unwrap();
+            // Both transactions are open before either writes.
+            barrier.wait().await;
+            transaction
+                .write_tree(&group_id, &TestBlob(name.to_vec()))
+                .await
+                .unwrap();
+            if commit {
+                transaction.commit().await.unwrap();
+            } else {
+                transaction.rollback().await.unwrap();
+            }
+        })
+    });
+    for task in tasks {
+        task.await.unwrap();
+    }
+
+    let tree_read: Option<TestBlob> = storage
+        .tree(&TestGroupId(b"rolled back".to_vec()))
+        .await
+        .unwrap();
+    assert!(tree_read.is_none());
+    let tree_read: Option<TestBlob> = storage
+        .tree(&TestGroupId(b"committed".to_vec()))
+        .await
+        .unwrap();
+    assert_eq!(Some(TestBlob(b"committed".to_vec())), tree_read);
+}
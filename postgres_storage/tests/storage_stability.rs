@@ -0,0 +1,372 @@
+//! These tests check that the rows written by the storage provider do not
+//! change, so that databases written by earlier versions remain readable.
+//!
+//! The expected rows are in `data/storage_stability.json`. Binary columns are
+//! stored as UTF-8 text, which works because [`JsonCodec`] produces JSON.
+mod common;
+
+use std::collections::BTreeMap;
+
+use common::*;
+use openmls_postgres_storage::AsyncPostgresStorageProvider;
+use openmls_traits::async_storage::AsyncStorageProvider;
+use sqlx::{PgPool, Row as _, postgres::PgPoolOptions};
+
+type Rows = BTreeMap<String, Vec<Vec<String>>>;
+
+/// The tables and their columns, in the order they are listed in the test data.
+/// Columns marked `true` are `BYTEA`, the others are converted to and from text.
+const TABLES: &[(&str, &[(&str, bool)])] = &[
+    (
+        "openmls_group_data",
+        &[
+            ("group_id", true),
+            ("data_type", false),
+            ("group_data", true),
+        ],
+    ),
+    (
+        "openmls_proposal",
+        &[
+            ("group_id", true),
+            ("proposal_ref", true),
+            ("proposal", true),
+        ],
+    ),
+    (
+        "openmls_own_leaf_node",
+        &[("group_id", true), ("leaf_node", true)],
+    ),
+    (
+        "openmls_signature_key",
+        &[("public_key", true), ("signature_key", true)],
+    ),
+    (
+        "openmls_encryption_key",
+        &[("public_key", true), ("key_pair", true)],
+    ),
+    (
+        "openmls_epoch_key_pairs",
+        &[
+            ("group_id", true),
+            ("epoch_id", true),
+            ("leaf_index", false),
+            ("key_pairs", true),
+        ],
+    ),
+    (
+        "openmls_key_package",
+        &[("key_package_ref", true), ("key_package", true)],
+    ),
+    ("openmls_psk", &[("psk_id", true), ("psk_bundle", true)]),
+];
+
+fn expected_rows() -> Rows {
+    serde_json::from_str(include_str!("data/storage_stability.json")).expect("invalid test data")
+}
+
+async fn storage(name: &str) -> (PgPool, AsyncPostgresStorageProvider<JsonCodec>) {
+    let pool = PgPoolOptions::new()
+        .connect_with(test_database(name).await)
+        .await
+        .unwrap();
+    let storage = AsyncPostgresStorageProvider::<JsonCodec>::new(pool.clone());
+    storage.run_migrations().await.unwrap();
+    (pool, storage)
+}
+
+/// Reads all rows of all tables, in insertion order for the ordered tables
+/// and sorted by their columns otherwise.
+async fn dump(pool: &PgPool) -> Rows {
+    let mut rows = Rows::new();
+    for (table, columns) in TABLES {
+        let selected = columns
+            .iter()
+            .map(|(column, binary)| {
+                if *binary {
+                    format!("convert_from({column}, 'UTF8')")
+                } else {
+                    format!("{column}::TEXT")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let order = match *table {
+            "openmls_proposal" | "openmls_own_leaf_node" => "position".to_owned(),
+            _ => (1..=columns.len())
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        let table_rows = sqlx::query(&format!("SELECT {selected} FROM {table} ORDER BY {order}"))
+            .fetch_all(pool)
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| {
+                (0..columns.len())
+                    .map(|i| row.try_get::<String, _>(i).unwrap())
+                    .collect()
+            })
+            .collect();
+        rows.insert(table.to_string(), table_rows);
+    }
+    rows
+}
+
+/// Inserts the given rows directly, bypassing the storage provider.
+async fn restore(pool: &PgPool, rows: &Rows) {
+    for (table, columns) in TABLES {
+        let names = columns
+            .iter()
+            .map(|(column, _)| *column)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let values = columns
+            .iter()
+            .enumerate()
+            .map(|(i, (column, binary))| {
+                let i = i + 1;
+                match (*binary, *column) {
+                    (true, _) => format!("convert_to(${i}, 'UTF8')"),
+                    (false, "leaf_index") => format!("${i}::BIGINT"),
+                    (false, _) => format!("${i}"),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let statement = format!("INSERT INTO {table} ({names}) VALUES ({values})");
+        for row in &rows[*table] {
+            let mut query = sqlx::query(&statement);
+            for value in row {
+                query = query.bind(value);
+            }
+            query.execute(pool).await.unwrap();
+        }
+    }
+}
+
+struct Values {
+    group_id: TestGroupId,
+    join_config: TestBlob,
+    tree: TestBlob,
+    interim_transcript_hash: TestBlob,
+    context: TestBlob,
+    confirmation_tag: TestBlob,
+    group_state: TestBlob,
+    message_secrets: TestBlob,
+    resumption_psk_store: TestBlob,
+    own_leaf_index: TestLeafIndex,
+    group_epoch_secrets: TestBlob,
+    proposals: Vec<(TestProposalRef, TestProposal)>,
+    own_leaf_nodes: Vec<TestBlob>,
+    signature_public_key: TestSignaturePublicKey,
+    signature_key_pair: TestSignatureKeyPair,
+    encryption_key: TestEncryptionKey,
+    encryption_key_pair: TestHpkeKeyPair,
+    epoch: TestEpochKey,
+    leaf_index: u32,
+    epoch_key_pairs: Vec<TestHpkeKeyPair>,
+    hash_ref: TestHashRef,
+    key_package: TestKeyPackage,
+    psk_id: TestPskId,
+    psk_bundle: TestPskBundle,
+}
+
+impl Values {
+    fn new() -> Self {
+        Self {
+            group_id: TestGroupId(b"group".to_vec()),
+            join_config: TestBlob(b"join-config".to_vec()),
+            tree: TestBlob(b"tree".to_vec()),
+            interim_transcript_hash: TestBlob(b"interim".to_vec()),
+            context: TestBlob(b"context".to_vec()),
+            confirmation_tag: TestBlob(b"tag".to_vec()),
+            group_state: TestBlob(b"state".to_vec()),
+            message_secrets: TestBlob(b"secrets".to_vec()),
+            resumption_psk_store: TestBlob(b"psk-store".to_vec()),
+            own_leaf_index: TestLeafIndex(3),
+            group_epoch_secrets: TestBlob(b"epoch-secrets".to_vec()),
+            // Stored out of order to check that insertion order is kept.
+            proposals: vec![
+                (TestProposalRef(2), TestProposal(b"proposal-2".to_vec())),
+                (TestProposalRef(1), TestProposal(b"proposal-1".to_vec())),
+            ],
+            own_leaf_nodes: vec![TestBlob(b"leaf-b".to_vec()), TestBlob(b"leaf-a".to_vec())],
+            signature_public_key: TestSignaturePublicKey(b"signature-public".to_vec()),
+            signature_key_pair: TestSignatureKeyPair(b"signature-key-pair".to_vec()),
+            encryption_key: TestEncryptionKey(b"encryption-public".to_vec()),
+            encryption_key_pair: TestHpkeKeyPair(b"encryption-pair".to_vec()),
+            epoch: TestEpochKey(b"epoch".to_vec()),
+            leaf_index: 7,
+            epoch_key_pairs: vec![
+                TestHpkeKeyPair(b"epoch-pair-a".to_vec()),
+                TestHpkeKeyPair(b"epoch-pair-b".to_vec()),
+            ],
+            hash_ref: TestHashRef(b"hash-ref".to_vec()),
+            key_package: TestKeyPackage(b"key-package".to_vec()),
+            psk_id: TestPskId(b"psk-id".to_vec()),
+            psk_bundle: TestPskBundle(b"psk-bundle".to_vec()),
+        }
+    }
+
+    async fn write(&self, storage: &AsyncPostgresStorageProvider<JsonCodec>) {
+        let group_id = &self.group_id;
+        storage
+            .write_mls_join_config(group_id, &self.join_config)
+            .await
+            .unwrap();
+        storage.write_tree(group_id, &self.tree).await.unwrap();
+        storage
+            .write_interim_transcript_hash(group_id, &self.interim_transcript_hash)
+            .await
+            .unwrap();
+        storage
+            .write_context(group_id, &self.context)
+            .await
+            .unwrap();
+        storage
+            .write_confirmation_tag(group_id, &self.confirmation_tag)
+            .await
+            .unwrap();
+        storage
+            .write_group_state(group_id, &self.group_state)
+            .await
+            .unwrap();
+        storage
+            .write_message_secrets(group_id, &self.message_secrets)
+            .await
+            .unwrap();
+        storage
+            .write_resumption_psk_store(group_id, &self.resumption_psk_store)
+            .await
+            .unwrap();
+        storage
+            .write_own_leaf_index(group_id, &self.own_leaf_index)
+            .await
+            .unwrap();
+        storage
+            .write_group_epoch_secrets(group_id, &self.group_epoch_secrets)
+            .await
+            .unwrap();
+        for (proposal_ref, proposal) in &self.proposals {
+            storage
+                .queue_proposal(group_id, proposal_ref, proposal)
+                .await
+                .unwrap();
+        }
+        for leaf_node in &self.own_leaf_nodes {
+            storage
+                .append_own_leaf_node(group_id, leaf_node)
+                .await
+                .unwrap();
+        }
+        storage
+            .write_signature_key_pair(&self.signature_public_key, &self.signature_key_pair)
+            .await
+            .unwrap();
+        storage
+            .write_encryption_key_pair(&self.encryption_key, &self.encryption_key_pair)
+            .await
+            .unwrap();
+        storage
+            .write_encryption_epoch_key_pairs(
+                group_id,
+                &self.epoch,
+                self.leaf_index,
+                &self.epoch_key_pairs,
+            )
+            .await
+            .unwrap();
+        storage
+            .write_key_package(&self.hash_ref, &self.key_package)
+            .await
+            .unwrap();
+        storage
+            .write_psk(&self.psk_id, &self.psk_bundle)
+            .await
+            .unwrap();
+    }
+
+    async fn check(&self, storage: &AsyncPostgresStorageProvider<JsonCodec>) {
+        let group_id = &self.group_id;
+        let join_config: Option<TestBlob> = storage.mls_group_join_config(group_id).await.unwrap();
+        assert_eq!(Some(&self.join_config), join_config.as_ref());
+        let tree: Option<TestBlob> = storage.tree(group_id).await.unwrap();
+        assert_eq!(Some(&self.tree), tree.as_ref());
+        let interim_transcript_hash: Option<TestBlob> =
+            storage.interim_transcript_hash(group_id).await.unwrap();
+        assert_eq!(
+            Some(&self.interim_transcript_hash),
+            interim_transcript_hash.as_ref()
+        );
+        let context: Option<TestBlob> = storage.group_context(group_id).await.unwrap();
+        assert_eq!(Some(&self.context), context.as_ref());
+        let confirmation_tag: Option<TestBlob> = storage.confirmation_tag(group_id).await.unwrap();
+        assert_eq!(Some(&self.confirmation_tag), confirmation_tag.as_ref());
+        let group_state: Option<TestBlob> = storage.group_state(group_id).await.unwrap();
+        assert_eq!(Some(&self.group_state), group_state.as_ref());
+        let message_secrets: Option<TestBlob> = storage.message_secrets(group_id).await.unwrap();
+        assert_eq!(Some(&self.message_secrets), message_secrets.as_ref());
+        let resumption_psk_store: Option<TestBlob> =
+            storage.resumption_psk_store(group_id).await.unwrap();
+        assert_eq!(
+            Some(&self.resumption_psk_store),
+            resumption_psk_store.as_ref()
+        );
+        let own_leaf_index: Option<TestLeafIndex> = storage.own_leaf_index(group_id).await.unwrap();
+        assert_eq!(Some(&self.own_leaf_index), own_leaf_index.as_ref());
+        let group_epoch_secrets: Option<TestBlob> =
+            storage.group_epoch_secrets(group_id).await.unwrap();
+        assert_eq!(
+            Some(&self.group_epoch_secrets),
+            group_epoch_secrets.as_ref()
+        );
+        let proposals: Vec<(TestProposalRef, TestProposal)> =
+            storage.queued_proposals(group_id).await.unwrap();
+        assert_eq!(self.proposals, proposals);
+        let own_leaf_nodes: Vec<TestBlob> = storage.own_leaf_nodes(group_id).await.unwrap();
+        assert_eq!(self.own_leaf_nodes, own_leaf_nodes);
+        let signature_key_pair: Option<TestSignatureKeyPair> = storage
+            .signature_key_pair(&self.signature_public_key)
+            .await
+            .unwrap();
+        assert_eq!(Some(&self.signature_key_pair), signature_key_pair.as_ref());
+        let encryption_key_pair: Option<TestHpkeKeyPair> = storage
+            .encryption_key_pair(&self.encryption_key)
+            .await
+            .unwrap();
+        assert_eq!(
+            Some(&self.encryption_key_pair),
+            encryption_key_pair.as_ref()
+        );
+        let epoch_key_pairs: Vec<TestHpkeKeyPair> = storage
+            .encryption_epoch_key_pairs(group_id, &self.epoch, self.leaf_index)
+            .await
+            .unwrap();
+        assert_eq!(self.epoch_key_pairs, epoch_key_pairs);
+        let key_package: Option<TestKeyPackage> =
+            storage.key_package(&self.hash_ref).await.unwrap();
+        assert_eq!(Some(&self.key_package), key_package.as_ref());
+        let psk_bundle: Option<TestPskBundle> = storage.psk(&self.psk_id).await.unwrap();
+        assert_eq!(Some(&self.psk_bundle), psk_bundle.as_ref());
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn written_rows_are_stable() {
+    let (pool, storage) = storage("storage_stability_written_rows").await;
+
+    Values::new().write(&storage).await;
+
+    assert_eq!(expected_rows(), dump(&pool).await);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn stored_rows_are_readable() {
+    let (pool, storage) = storage("storage_stability_stored_rows").await;
+
+    restore(&pool, &expected_rows()).await;
+
+    Values::new().check(&storage).await;
+}
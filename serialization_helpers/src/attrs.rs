@@ -37,3 +37,9 @@ pub(crate) fn extract_storage_tag<'a>(
     int.base10_parse()
         .map_err(|_| ExtractStorageTagError::InvalidTag)
 }
+
+/// A helper function to check for a `legacy_tags` attribute in a list of
+/// attributes
+pub(crate) fn has_legacy_tags<'a>(mut attrs: impl Iterator<Item = &'a Attribute>) -> bool {
+    attrs.any(|attr| matches!(&attr.meta, Meta::Path(path) if path.is_ident("legacy_tags")))
+}
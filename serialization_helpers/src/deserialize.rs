@@ -2,7 +2,7 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{parse2, Data, DeriveInput, Fields, FieldsUnnamed, Ident};
 
-use crate::attrs::extract_storage_tag;
+use crate::attrs::{extract_storage_tag, has_legacy_tags};
 
 fn build_name_ident(name: &Ident, suffix: &str) -> Ident {
     Ident::new(&format!("{name}{suffix}"), name.span())
@@ -30,9 +30,10 @@ pub(crate) fn deserialize(input: TokenStream) -> TokenStream {
     let tuple_visitor = build_name_ident(name, "TupleVisitor");
 
     let mut variant_names = vec![];
+    let mut legacy_tag_mapping = vec![];
     let mut match_arms_non_self_describing = vec![];
     let mut match_arms_self_describing = vec![];
-    for variant in data_enum.variants.iter() {
+    for (position, variant) in data_enum.variants.iter().enumerate() {
         // retrieve the variant name
         let variant_name = &variant.ident;
         let variant_name_str = variant_name.to_string();
@@ -69,10 +70,33 @@ pub(crate) fn deserialize(input: TokenStream) -> TokenStream {
             }
             _ => unimplemented!("fields not supported"),
         };
+        let position = position as u32;
+        legacy_tag_mapping.push(quote! { #position => Some(#storage_tag), });
         match_arms_non_self_describing.push(quote! { #storage_tag => { #handling }, });
         match_arms_self_describing.push(quote! { #variant_name_str => { #handling }, });
     }
 
+    // if requested, provide the mapping from the positional tags of the legacy
+    // format to the storage tags
+    let legacy_tags = if has_legacy_tags(input.attrs.iter()) {
+        quote! {
+            impl #name {
+                /// Returns the storage tag of the variant with the given
+                /// numeric tag in the legacy format, which is the position
+                /// of the variant.
+                #[allow(dead_code)]
+                pub(crate) fn storage_tag_from_legacy_tag(legacy_tag: u32) -> Option<u32> {
+                    match legacy_tag {
+                        #(#legacy_tag_mapping)*
+                        _ => None,
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
     let tuple_visitor_impl = if needs_tuple_visitor {
         quote! {
         /// A visitor for deserializing the tuple (A,B) contents of an enum variant
@@ -174,5 +198,7 @@ pub(crate) fn deserialize(input: TokenStream) -> TokenStream {
 
         #tuple_visitor_impl
 
+        #legacy_tags
+
     }
 }
@@ -34,6 +34,30 @@
 //!
 //! These helper macros can also be used with enums that include feature-flagged
 //! variants.
+//!
+//! ### Legacy tags
+//! Values written by a plain `serde` derive use the position of each variant
+//! as its numeric tag. Annotating the enum with `#[legacy_tags]` adds a
+//! `pub(crate)` function `storage_tag_from_legacy_tag` to the enum, which
+//! maps such a position to the storage tag of the variant. A deserializer
+//! that reads values in the legacy format can use it to rewrite the numeric
+//! tags before they are deserialized.
+//! ```rust
+//! #[derive(
+//!     openmls_serialization_helpers::Serialize,
+//!     openmls_serialization_helpers::Deserialize,
+//! )]
+//! #[legacy_tags]
+//! pub enum TestEnum {
+//!   #[storage_tag = 1]
+//!   Unit,
+//!   #[storage_tag = 0]
+//!   Data(Vec<u8>),
+//! }
+//!
+//! assert_eq!(TestEnum::storage_tag_from_legacy_tag(0), Some(1));
+//! assert_eq!(TestEnum::storage_tag_from_legacy_tag(2), None);
+//! ```
 
 mod attrs;
 mod deserialize;
@@ -41,12 +65,12 @@ mod serialize;
 
 use proc_macro::TokenStream;
 
-#[proc_macro_derive(Deserialize, attributes(storage_tag))]
+#[proc_macro_derive(Deserialize, attributes(storage_tag, legacy_tags))]
 pub fn deserialize(input: TokenStream) -> TokenStream {
     deserialize::deserialize(input.into()).into()
 }
 
-#[proc_macro_derive(Serialize, attributes(storage_tag))]
+#[proc_macro_derive(Serialize, attributes(storage_tag, legacy_tags))]
 pub fn serialize(input: TokenStream) -> TokenStream {
     serialize::serialize(input.into()).into()
 }
@@ -241,3 +241,51 @@ fn test_with_feature_flag() {
 
     assert_eq!(data, deserialized);
 }
+
+#[test]
+/// Test that the positional tags written by a plain `serde` derive are mapped
+/// to the storage tags of the `legacy_tags` attribute.
+fn legacy_tags() {
+    #[derive(Debug, serde::Serialize)]
+    enum Legacy {
+        Unit,
+        Value(u64),
+        Data2(u64, [u8; 3]),
+    }
+    #[derive(
+        PartialEq,
+        Eq,
+        Debug,
+        openmls_serialization_helpers::Serialize,
+        openmls_serialization_helpers::Deserialize,
+    )]
+    #[legacy_tags]
+    enum Current {
+        #[storage_tag = 2]
+        Unit,
+        #[storage_tag = 0]
+        Value(u64),
+        #[storage_tag = 1]
+        Data2(u64, [u8; 3]),
+    }
+
+    let cases = [
+        (Legacy::Unit, Current::Unit),
+        (Legacy::Value(60), Current::Value(60)),
+        (Legacy::Data2(1, [1, 2, 3]), Current::Data2(1, [1, 2, 3])),
+    ];
+    for (legacy, current) in cases {
+        // postcard writes the tag as the first byte for small tags
+        let mut serialized = postcard::to_allocvec(&legacy).expect("serialization failed");
+        let storage_tag =
+            Current::storage_tag_from_legacy_tag(serialized[0] as u32).expect("unknown legacy tag");
+        serialized[0] = storage_tag as u8;
+
+        let deserialized: Current =
+            postcard::from_bytes(&serialized).expect("deserialization failed");
+        assert_eq!(current, deserialized);
+    }
+
+    // a tag beyond the legacy positions is unknown
+    assert_eq!(Current::storage_tag_from_legacy_tag(3), None);
+}
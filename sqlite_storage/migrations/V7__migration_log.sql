@@ -0,0 +1,9 @@
+-- Record which groups and key packages a migration of the stored values was
+-- applied to
+CREATE TABLE openmls_migration_log (
+    provider_version INTEGER NOT NULL,
+    entity_type TEXT NOT NULL CHECK (entity_type IN ('group', 'key_package')),
+    entity_key BLOB NOT NULL,
+    migration TEXT NOT NULL,
+    PRIMARY KEY (entity_type, entity_key, migration)
+);
@@ -2,11 +2,12 @@ use std::marker::PhantomData;
 
 use openmls_traits::storage::{Entity, Key};
 use rusqlite::{params, types::FromSql, Connection, OptionalExtension, ToSql};
+use serde::de::DeserializeOwned;
 
 use crate::{
     codec::Codec,
     storage_provider::StorableKeyRef,
-    wrappers::{EntityRefWrapper, EntityWrapper, KeyRefWrapper},
+    wrappers::{EntityRefWrapper, EntityWrapper, KeyRefWrapper, KeyWrapper},
     STORAGE_PROVIDER_VERSION,
 };
 
@@ -23,6 +24,7 @@ pub(super) enum GroupDataType {
     OwnLeafIndex,
     UseRatchetTreeExtension,
     GroupEpochSecrets,
+    FutureEpochMessages,
     #[cfg(feature = "extensions-draft")]
     ApplicationExportTree,
 }
@@ -41,6 +43,7 @@ impl ToSql for GroupDataType {
             GroupDataType::OwnLeafIndex => "own_leaf_index".to_sql(),
             GroupDataType::UseRatchetTreeExtension => "use_ratchet_tree_extension".to_sql(),
             GroupDataType::GroupEpochSecrets => "group_epoch_secrets".to_sql(),
+            GroupDataType::FutureEpochMessages => "future_epoch_messages".to_sql(),
             #[cfg(feature = "extensions-draft")]
             GroupDataType::ApplicationExportTree => "application_export_tree".to_sql(),
         }
@@ -62,6 +65,7 @@ impl FromSql for GroupDataType {
             "own_leaf_index" => Ok(GroupDataType::OwnLeafIndex),
             "use_ratchet_tree_extension" => Ok(GroupDataType::UseRatchetTreeExtension),
             "group_epoch_secrets" => Ok(GroupDataType::GroupEpochSecrets),
+            "future_epoch_messages" => Ok(GroupDataType::FutureEpochMessages),
             #[cfg(feature = "extensions-draft")]
             "application_export_tree" => Ok(GroupDataType::ApplicationExportTree),
             _ => Err(rusqlite::types::FromSqlError::InvalidType),
@@ -102,6 +106,31 @@ impl<GroupData: Entity<STORAGE_PROVIDER_VERSION>> StorableGroupData<GroupData> {
     }
 }
 
+pub(crate) struct StorableGroupId<GroupId>(PhantomData<GroupId>);
+
+impl<GroupId: Key<STORAGE_PROVIDER_VERSION> + DeserializeOwned> StorableGroupId<GroupId> {
+    pub(super) fn load_all<C: Codec>(
+        connection: &Connection,
+    ) -> Result<Vec<GroupId>, rusqlite::Error> {
+        let mut stmt = connection.prepare(
+            "SELECT group_id
+            FROM openmls_group_data
+            WHERE data_type = ?
+                AND provider_version = ?",
+        )?;
+        let group_ids = stmt
+            .query_map(
+                params![GroupDataType::Tree, STORAGE_PROVIDER_VERSION],
+                |row| {
+                    let KeyWrapper::<C, _>(group_id, ..) = row.get(0)?;
+                    Ok(group_id)
+                },
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(group_ids)
+    }
+}
+
 pub(super) struct StorableGroupDataRef<'a, GroupData: Entity<STORAGE_PROVIDER_VERSION>>(
     pub &'a GroupData,
 );
@@ -2,10 +2,11 @@ use std::marker::PhantomData;
 
 use openmls_traits::storage::{Entity, Key};
 use rusqlite::{params, OptionalExtension};
+use serde::de::DeserializeOwned;
 
 use crate::{
     codec::Codec,
-    wrappers::{EntityRefWrapper, EntityWrapper, KeyRefWrapper},
+    wrappers::{EntityRefWrapper, EntityWrapper, KeyRefWrapper, KeyWrapper},
     STORAGE_PROVIDER_VERSION,
 };
 
@@ -60,6 +61,27 @@ impl<KeyPackage: Entity<STORAGE_PROVIDER_VERSION>> StorableKeyPackageRef<'_, Key
     }
 }
 
+pub(crate) struct StorableHash<KeyPackageRef>(PhantomData<KeyPackageRef>);
+
+impl<KeyPackageRef: Key<STORAGE_PROVIDER_VERSION> + DeserializeOwned> StorableHash<KeyPackageRef> {
+    pub(super) fn load_all<C: Codec>(
+        connection: &rusqlite::Connection,
+    ) -> Result<Vec<KeyPackageRef>, rusqlite::Error> {
+        let mut stmt = connection.prepare(
+            "SELECT key_package_ref
+            FROM openmls_key_packages
+            WHERE provider_version = ?1",
+        )?;
+        let key_package_refs = stmt
+            .query_map(params![STORAGE_PROVIDER_VERSION], |row| {
+                let KeyWrapper::<C, _>(key_package_ref, ..) = row.get(0)?;
+                Ok(key_package_ref)
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(key_package_refs)
+    }
+}
+
 pub(super) struct StorableHashRef<'a, KeyPackageRef: Key<STORAGE_PROVIDER_VERSION>>(
     pub &'a KeyPackageRef,
 );
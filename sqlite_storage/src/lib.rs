@@ -32,6 +32,7 @@ mod encryption_key_pairs;
 mod epoch_key_pairs;
 mod group_data;
 mod key_packages;
+mod migration_log;
 mod own_leaf_nodes;
 mod proposals;
 mod psks;
@@ -0,0 +1,73 @@
+use std::marker::PhantomData;
+
+use openmls_traits::storage::Key;
+use rusqlite::{params, OptionalExtension, ToSql};
+
+use crate::{codec::Codec, wrappers::KeyRefWrapper, STORAGE_PROVIDER_VERSION};
+
+/// The type of the stored entity a migration was applied to.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum MigratedEntityType {
+    Group,
+    KeyPackage,
+}
+
+impl ToSql for MigratedEntityType {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        match self {
+            MigratedEntityType::Group => "group".to_sql(),
+            MigratedEntityType::KeyPackage => "key_package".to_sql(),
+        }
+    }
+}
+
+pub(super) struct StorableMigrationRef<'a, EntityKey: Key<STORAGE_PROVIDER_VERSION>>(
+    pub &'a EntityKey,
+);
+
+impl<EntityKey: Key<STORAGE_PROVIDER_VERSION>> StorableMigrationRef<'_, EntityKey> {
+    pub(super) fn load<C: Codec>(
+        &self,
+        connection: &rusqlite::Connection,
+        entity_type: MigratedEntityType,
+        migration: &str,
+    ) -> Result<bool, rusqlite::Error> {
+        connection
+            .query_row(
+                "SELECT 1
+                FROM openmls_migration_log
+                WHERE entity_type = ?1
+                    AND entity_key = ?2
+                    AND migration = ?3
+                    AND provider_version = ?4",
+                params![
+                    entity_type,
+                    KeyRefWrapper::<C, _>(self.0, PhantomData),
+                    migration,
+                    STORAGE_PROVIDER_VERSION
+                ],
+                |_| Ok(()),
+            )
+            .optional()
+            .map(|row| row.is_some())
+    }
+
+    pub(super) fn store<C: Codec>(
+        &self,
+        connection: &rusqlite::Connection,
+        entity_type: MigratedEntityType,
+        migration: &str,
+    ) -> Result<(), rusqlite::Error> {
+        connection.execute(
+            "INSERT OR REPLACE INTO openmls_migration_log (entity_type, entity_key, migration, provider_version)
+            VALUES (?1, ?2, ?3, ?4)",
+            params![
+                entity_type,
+                KeyRefWrapper::<C, _>(self.0, PhantomData),
+                migration,
+                STORAGE_PROVIDER_VERSION
+            ],
+        )?;
+        Ok(())
+    }
+}
@@ -3,8 +3,9 @@ use std::{
     marker::PhantomData,
 };
 
-use openmls_traits::storage::{traits, Key, StorageProvider};
+use openmls_traits::storage::{traits, Key, MigrationLog, StorageProvider};
 use rusqlite::Connection;
+use serde::de::DeserializeOwned;
 
 use crate::{
     codec::Codec,
@@ -12,8 +13,9 @@ use crate::{
         StorableEncryptionKeyPair, StorableEncryptionKeyPairRef, StorableEncryptionPublicKeyRef,
     },
     epoch_key_pairs::{StorableEpochKeyPairs, StorableEpochKeyPairsRef},
-    group_data::{GroupDataType, StorableGroupData, StorableGroupDataRef},
-    key_packages::{StorableHashRef, StorableKeyPackage, StorableKeyPackageRef},
+    group_data::{GroupDataType, StorableGroupData, StorableGroupDataRef, StorableGroupId},
+    key_packages::{StorableHash, StorableHashRef, StorableKeyPackage, StorableKeyPackageRef},
+    migration_log::{MigratedEntityType, StorableMigrationRef},
     own_leaf_nodes::{StorableLeafNode, StorableLeafNodeRef},
     proposals::{StorableProposal, StorableProposalRef},
     psks::{StorablePskBundle, StorablePskBundleRef, StorablePskIdRef},
@@ -44,6 +46,36 @@ impl<C: Codec, ConnectionRef: Borrow<Connection>> SqliteStorageProvider<C, Conne
             _codec: PhantomData,
         }
     }
+
+    /// Returns the connection of the storage provider.
+    ///
+    /// This allows applications to keep their own tables in the same database
+    /// and to wrap their writes and those of OpenMLS in one transaction.
+    pub fn connection(&self) -> &Connection {
+        self.connection.borrow()
+    }
+
+    /// Returns the ids of all groups with a stored tree.
+    ///
+    /// This allows applications to visit all groups in the database, e.g. to
+    /// migrate them to a new storage format.
+    pub fn group_ids<GroupId: traits::GroupId<STORAGE_PROVIDER_VERSION> + DeserializeOwned>(
+        &self,
+    ) -> Result<Vec<GroupId>, rusqlite::Error> {
+        StorableGroupId::load_all::<C>(self.connection.borrow())
+    }
+
+    /// Returns the references of all stored key packages.
+    ///
+    /// This allows applications to visit all key packages in the database,
+    /// e.g. to migrate them to a new storage format.
+    pub fn key_package_refs<
+        KeyPackageRef: traits::HashReference<STORAGE_PROVIDER_VERSION> + DeserializeOwned,
+    >(
+        &self,
+    ) -> Result<Vec<KeyPackageRef>, rusqlite::Error> {
+        StorableHash::load_all::<C>(self.connection.borrow())
+    }
 }
 
 impl<C: Codec, ConnectionRef: BorrowMut<Connection>> SqliteStorageProvider<C, ConnectionRef> {
@@ -74,6 +106,27 @@ impl<C: Codec, ConnectionRef: Borrow<Connection>> StorageProvider<STORAGE_PROVID
 {
     type Error = rusqlite::Error;
 
+    // Transactions are mapped to savepoints, which start a transaction if
+    // there is none and nest into the application's transaction otherwise.
+
+    fn begin_transaction(&self) -> Result<(), Self::Error> {
+        self.connection
+            .borrow()
+            .execute_batch("SAVEPOINT openmls_transaction")
+    }
+
+    fn commit_transaction(&self) -> Result<(), Self::Error> {
+        self.connection
+            .borrow()
+            .execute_batch("RELEASE openmls_transaction")
+    }
+
+    fn rollback_transaction(&self) -> Result<(), Self::Error> {
+        self.connection
+            .borrow()
+            .execute_batch("ROLLBACK TO openmls_transaction; RELEASE openmls_transaction")
+    }
+
     fn write_mls_join_config<
         GroupId: traits::GroupId<STORAGE_PROVIDER_VERSION>,
         MlsGroupJoinConfig: traits::MlsGroupJoinConfig<STORAGE_PROVIDER_VERSION>,
@@ -252,6 +305,25 @@ impl<C: Codec, ConnectionRef: Borrow<Connection>> StorageProvider<STORAGE_PROVID
         Ok(())
     }
 
+    fn supports_future_epoch_messages(&self) -> bool {
+        true
+    }
+
+    fn write_future_epoch_messages<
+        GroupId: traits::GroupId<STORAGE_PROVIDER_VERSION>,
+        FutureEpochMessages: traits::FutureEpochMessages<STORAGE_PROVIDER_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        future_epoch_messages: &FutureEpochMessages,
+    ) -> Result<(), Self::Error> {
+        StorableGroupDataRef(future_epoch_messages).store::<C, _>(
+            self.connection.borrow(),
+            group_id,
+            GroupDataType::FutureEpochMessages,
+        )
+    }
+
     fn write_signature_key_pair<
         SignaturePublicKey: traits::SignaturePublicKey<STORAGE_PROVIDER_VERSION>,
         SignatureKeyPair: traits::SignatureKeyPair<STORAGE_PROVIDER_VERSION>,
@@ -479,6 +551,20 @@ impl<C: Codec, ConnectionRef: Borrow<Connection>> StorageProvider<STORAGE_PROVID
         )
     }
 
+    fn future_epoch_messages<
+        GroupId: traits::GroupId<STORAGE_PROVIDER_VERSION>,
+        FutureEpochMessages: traits::FutureEpochMessages<STORAGE_PROVIDER_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<FutureEpochMessages>, Self::Error> {
+        StorableGroupData::load::<C, _>(
+            self.connection.borrow(),
+            group_id,
+            GroupDataType::FutureEpochMessages,
+        )
+    }
+
     fn signature_key_pair<
         SignaturePublicKey: traits::SignaturePublicKey<STORAGE_PROVIDER_VERSION>,
         SignatureKeyPair: traits::SignatureKeyPair<STORAGE_PROVIDER_VERSION>,
@@ -637,6 +723,14 @@ impl<C: Codec, ConnectionRef: Borrow<Connection>> StorageProvider<STORAGE_PROVID
             .delete_group_data::<C>(self.connection.borrow(), GroupDataType::GroupEpochSecrets)
     }
 
+    fn delete_future_epoch_messages<GroupId: traits::GroupId<STORAGE_PROVIDER_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        StorableKeyRef(group_id)
+            .delete_group_data::<C>(self.connection.borrow(), GroupDataType::FutureEpochMessages)
+    }
+
     fn clear_proposal_queue<
         GroupId: traits::GroupId<STORAGE_PROVIDER_VERSION>,
         ProposalRef: traits::ProposalRef<STORAGE_PROVIDER_VERSION>,
@@ -910,3 +1004,57 @@ impl<C: Codec, ConnectionRef: Borrow<Connection>> StorageProvider<STORAGE_PROVID
         StorableKeyRef(hash_ref).delete_retained_key_package_material::<C>(self.connection.borrow())
     }
 }
+
+impl<C: Codec, ConnectionRef: Borrow<Connection>> MigrationLog<STORAGE_PROVIDER_VERSION>
+    for SqliteStorageProvider<C, ConnectionRef>
+{
+    fn group_migrated<GroupId: traits::GroupId<STORAGE_PROVIDER_VERSION>>(
+        &self,
+        group_id: &GroupId,
+        name: &str,
+    ) -> Result<bool, Self::Error> {
+        StorableMigrationRef(group_id).load::<C>(
+            self.connection.borrow(),
+            MigratedEntityType::Group,
+            name,
+        )
+    }
+
+    fn record_group_migration<GroupId: traits::GroupId<STORAGE_PROVIDER_VERSION>>(
+        &self,
+        group_id: &GroupId,
+        name: &str,
+    ) -> Result<(), Self::Error> {
+        StorableMigrationRef(group_id).store::<C>(
+            self.connection.borrow(),
+            MigratedEntityType::Group,
+            name,
+        )
+    }
+
+    fn key_package_migrated<KeyPackageRef: traits::HashReference<STORAGE_PROVIDER_VERSION>>(
+        &self,
+        hash_ref: &KeyPackageRef,
+        name: &str,
+    ) -> Result<bool, Self::Error> {
+        StorableMigrationRef(hash_ref).load::<C>(
+            self.connection.borrow(),
+            MigratedEntityType::KeyPackage,
+            name,
+        )
+    }
+
+    fn record_key_package_migration<
+        KeyPackageRef: traits::HashReference<STORAGE_PROVIDER_VERSION>,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+        name: &str,
+    ) -> Result<(), Self::Error> {
+        StorableMigrationRef(hash_ref).store::<C>(
+            self.connection.borrow(),
+            MigratedEntityType::KeyPackage,
+            name,
+        )
+    }
+}
@@ -5,7 +5,7 @@ use rusqlite::{
     types::{FromSql, ToSqlOutput},
     ToSql,
 };
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{codec::Codec, STORAGE_PROVIDER_VERSION};
 
@@ -23,6 +23,19 @@ impl<C: Codec, T: Key<STORAGE_PROVIDER_VERSION>> ToSql for KeyRefWrapper<'_, C,
     }
 }
 
+pub(super) struct KeyWrapper<C: Codec, T: Key<STORAGE_PROVIDER_VERSION> + DeserializeOwned>(
+    pub T,
+    pub PhantomData<C>,
+);
+
+impl<C: Codec, T: Key<STORAGE_PROVIDER_VERSION> + DeserializeOwned> FromSql for KeyWrapper<C, T> {
+    fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        let key = C::from_slice(value.as_blob()?)
+            .map_err(|e| rusqlite::types::FromSqlError::Other(Box::new(e)))?;
+        Ok(Self(key, PhantomData))
+    }
+}
+
 pub(super) struct EntityWrapper<C: Codec, T: Entity<STORAGE_PROVIDER_VERSION>>(
     pub T,
     pub PhantomData<C>,
@@ -0,0 +1,71 @@
+use openmls_sqlite_storage::Codec;
+use openmls_traits::storage::{
+    traits::{self},
+    Entity, Key, StorageProvider,
+};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+#[derive(Default)]
+pub struct JsonCodec;
+
+impl Codec for JsonCodec {
+    type Error = serde_json::Error;
+
+    fn to_vec<T: Serialize>(value: &T) -> Result<Vec<u8>, Self::Error> {
+        serde_json::to_vec(value)
+    }
+
+    fn from_slice<T: serde::de::DeserializeOwned>(slice: &[u8]) -> Result<T, Self::Error> {
+        serde_json::from_slice(slice)
+    }
+}
+
+// Test types
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct TestGroupId(Vec<u8>);
+impl traits::GroupId<1> for TestGroupId {}
+impl Key<1> for TestGroupId {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct TestTree(Vec<u8>);
+impl traits::TreeSync<1> for TestTree {}
+impl Entity<1> for TestTree {}
+
+/// Committed transactions persist their writes, rolled back transactions
+/// discard them, and nested transactions behave like savepoints.
+#[test]
+fn commit_and_rollback() {
+    let group_id = TestGroupId(b"TestGroupId".to_vec());
+    let old_tree = TestTree(b"old".to_vec());
+    let new_tree = TestTree(b"new".to_vec());
+    let connection = rusqlite::Connection::open_in_memory().unwrap();
+    let mut storage =
+        openmls_sqlite_storage::SqliteStorageProvider::<JsonCodec, Connection>::new(connection);
+
+    storage.run_migrations().unwrap();
+    storage.write_tree(&group_id, &old_tree).unwrap();
+
+    // Roll back a write and a delete
+    storage.begin_transaction().unwrap();
+    storage.write_tree(&group_id, &new_tree).unwrap();
+    let tree: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(tree, Some(new_tree.clone()));
+    storage.delete_tree(&group_id).unwrap();
+    storage.rollback_transaction().unwrap();
+    let tree: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(tree, Some(old_tree));
+
+    // Roll back an inner transaction, but commit the outer one
+    storage.begin_transaction().unwrap();
+    storage.write_tree(&group_id, &new_tree).unwrap();
+    storage.begin_transaction().unwrap();
+    storage.delete_tree(&group_id).unwrap();
+    storage.rollback_transaction().unwrap();
+    storage.commit_transaction().unwrap();
+    let tree: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(tree, Some(new_tree));
+
+    // There is no open transaction left
+    assert!(storage.commit_transaction().is_err());
+}
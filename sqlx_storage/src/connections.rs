@@ -0,0 +1,79 @@
+use std::{
+    future::Future,
+    ops::{Deref, DerefMut},
+};
+
+use sqlx::{Sqlite, SqliteConnection, SqlitePool, pool::PoolConnection, raw_sql};
+use tokio::sync::{MappedMutexGuard, MutexGuard};
+
+use super::TransactionConnection;
+
+/// The connections that the operations of an
+/// [`AsyncSqliteStorageProvider`](super::AsyncSqliteStorageProvider) run on.
+pub trait Connections: Send + Sync {
+    /// Returns a connection to run an operation on.
+    fn connection(&self) -> impl Future<Output = Result<ConnectionGuard<'_>, sqlx::Error>> + Send;
+
+    /// Runs a statement that begins, releases or rolls back a nested
+    /// transaction.
+    fn nested_transaction(
+        &self,
+        statement: &'static str,
+    ) -> impl Future<Output = Result<(), sqlx::Error>> + Send;
+}
+
+impl Connections for SqlitePool {
+    async fn connection(&self) -> Result<ConnectionGuard<'_>, sqlx::Error> {
+        Ok(ConnectionGuard::Pool(self.acquire().await?))
+    }
+
+    // Each operation runs on its own connection from the pool, so there is no
+    // connection to run the transaction on.
+    async fn nested_transaction(&self, _statement: &'static str) -> Result<(), sqlx::Error> {
+        Ok(())
+    }
+}
+
+impl Connections for TransactionConnection {
+    async fn connection(&self) -> Result<ConnectionGuard<'_>, sqlx::Error> {
+        let transaction = self.0.lock().await;
+        Ok(ConnectionGuard::Transaction(MutexGuard::map(
+            transaction,
+            |transaction| &mut **transaction,
+        )))
+    }
+
+    async fn nested_transaction(&self, statement: &'static str) -> Result<(), sqlx::Error> {
+        let mut transaction = self.0.lock().await;
+        raw_sql(statement).execute(&mut **transaction).await?;
+        Ok(())
+    }
+}
+
+/// A connection used by the
+/// [`AsyncSqliteStorageProvider`](super::AsyncSqliteStorageProvider), either
+/// the one of a transaction or one acquired from the pool.
+pub enum ConnectionGuard<'a> {
+    Transaction(MappedMutexGuard<'a, SqliteConnection>),
+    Pool(PoolConnection<Sqlite>),
+}
+
+impl Deref for ConnectionGuard<'_> {
+    type Target = SqliteConnection;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            ConnectionGuard::Transaction(connection) => connection,
+            ConnectionGuard::Pool(connection) => connection,
+        }
+    }
+}
+
+impl DerefMut for ConnectionGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            ConnectionGuard::Transaction(connection) => connection,
+            ConnectionGuard::Pool(connection) => connection,
+        }
+    }
+}
@@ -16,6 +16,7 @@ pub(super) enum GroupDataType {
     OwnLeafIndex,
     UseRatchetTreeExtension,
     GroupEpochSecrets,
+    FutureEpochMessages,
     #[cfg(feature = "extensions-draft")]
     ApplicationExportTree,
 }
@@ -34,6 +35,7 @@ impl GroupDataType {
             GroupDataType::OwnLeafIndex => "own_leaf_index",
             GroupDataType::UseRatchetTreeExtension => "use_ratchet_tree_extension",
             GroupDataType::GroupEpochSecrets => "group_epoch_secrets",
+            GroupDataType::FutureEpochMessages => "future_epoch_messages",
             #[cfg(feature = "extensions-draft")]
             GroupDataType::ApplicationExportTree => "application_export_tree",
         }
@@ -52,6 +54,7 @@ impl GroupDataType {
             "own_leaf_index" => Some(GroupDataType::OwnLeafIndex),
             "use_ratchet_tree_extension" => Some(GroupDataType::UseRatchetTreeExtension),
             "group_epoch_secrets" => Some(GroupDataType::GroupEpochSecrets),
+            "future_epoch_messages" => Some(GroupDataType::FutureEpochMessages),
             #[cfg(feature = "extensions-draft")]
             "application_export_tree" => Some(GroupDataType::ApplicationExportTree),
             _ => None,
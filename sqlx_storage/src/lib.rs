@@ -11,6 +11,13 @@
 //! [`StorageProvider`](openmls_traits::storage::StorageProvider) trait from the
 //! `openmls_traits` crate.
 //!
+//! For asynchronous applications, the [`AsyncSqliteStorageProvider`] implements
+//! the [`AsyncStorageProvider`](openmls_traits::async_storage::AsyncStorageProvider)
+//! trait on top of a [`SqlitePool`]. Unlike the [`SqliteStorageProvider`], it
+//! does not block the executor on database access and can be shared between
+//! tasks. Operations that have to be atomic, such as merging a commit, run on
+//! an [`AsyncSqliteTransaction`], which owns a connection of the pool.
+//!
 //! The crate manages its own database migrations in its own migrations table
 //! with the name `_openmls_sqlx_migrations`. All tables created by this crate
 //! are prefixed with `openmls_` to avoid name clashes.
@@ -19,12 +26,17 @@ use std::{cell::RefCell, marker::PhantomData};
 
 use openmls_traits::storage::{CURRENT_VERSION, Entity, Key};
 use serde::Serialize;
-use sqlx::SqliteConnection;
+use sqlx::{Sqlite, SqliteConnection, SqlitePool, Transaction};
+use tokio::sync::Mutex;
 
 pub use crate::codec::Codec;
-use crate::{migrator::MigratorWrapper, storage_provider::block_async_in_place};
+use crate::{
+    connections::Connections, migrator::MigratorWrapper, storage_provider::block_async_in_place,
+};
 
+mod async_storage_provider;
 mod codec;
+mod connections;
 mod group_data;
 mod migrator;
 mod storage_provider;
@@ -70,6 +82,94 @@ impl<'a, C: Codec> SqliteStorageProvider<'a, C> {
     }
 }
 
+/// [`AsyncSqliteStorageProvider`] implements the
+/// [`AsyncStorageProvider`](openmls_traits::async_storage::AsyncStorageProvider)
+/// trait and can thus be used as a storage provider for the asynchronous API
+/// of OpenMLS.
+///
+/// It is generic over any codec `C` that implements the [`Codec`] trait.
+/// The codec is used to serialize and deserialize the data stored in the
+/// underlying database.
+///
+/// Operations on the provider run on connections from the pool and are not
+/// grouped into transactions, so a failure while merging a commit can leave
+/// the group partially updated. To run operations atomically, begin an
+/// [`AsyncSqliteTransaction`] with
+/// [`transaction()`](AsyncSqliteStorageProvider::transaction) and use it as
+/// the storage provider for them.
+pub struct AsyncSqliteStorageProvider<C, Conn = SqlitePool> {
+    connections: Conn,
+    codec: PhantomData<C>,
+}
+
+/// A transaction of the [`AsyncSqliteStorageProvider`].
+///
+/// The transaction owns a dedicated connection of the pool, and all
+/// operations on it run on that connection. Operations on the
+/// [`AsyncSqliteStorageProvider`] and on other transactions are not affected.
+/// Transactions begun through the
+/// [`AsyncStorageProvider`](openmls_traits::async_storage::AsyncStorageProvider)
+/// trait, e.g. by OpenMLS, are nested into it.
+///
+/// The transaction is applied with
+/// [`commit()`](AsyncSqliteStorageProvider::commit) and discarded with
+/// [`rollback()`](AsyncSqliteStorageProvider::rollback) or when it is dropped.
+pub type AsyncSqliteTransaction<C> = AsyncSqliteStorageProvider<C, TransactionConnection>;
+
+/// The connection of an [`AsyncSqliteTransaction`].
+pub struct TransactionConnection(Mutex<Transaction<'static, Sqlite>>);
+
+impl<C: Codec> AsyncSqliteStorageProvider<C> {
+    /// Create a new [`AsyncSqliteStorageProvider`] based on the given
+    /// [`SqlitePool`].
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            connections: pool,
+            codec: PhantomData,
+        }
+    }
+
+    /// Run the migrations for the storage provider. Uses sqlx's built-in
+    /// migration support.
+    pub async fn run_migrations(&self) -> Result<(), sqlx::migrate::MigrateError> {
+        let mut connection = self.connections.acquire().await?;
+        sqlx::migrate!("./migrations")
+            .run_direct(&mut MigratorWrapper(&mut connection))
+            .await?;
+        Ok(())
+    }
+
+    /// Begins an [`AsyncSqliteTransaction`] on a dedicated connection of the
+    /// pool.
+    pub async fn transaction(&self) -> Result<AsyncSqliteTransaction<C>, sqlx::Error> {
+        Ok(AsyncSqliteStorageProvider {
+            connections: TransactionConnection(Mutex::new(self.connections.begin().await?)),
+            codec: PhantomData,
+        })
+    }
+}
+
+impl<C: Codec> AsyncSqliteTransaction<C> {
+    /// Commits the transaction.
+    pub async fn commit(self) -> Result<(), sqlx::Error> {
+        self.connections.0.into_inner().commit().await
+    }
+
+    /// Rolls back the transaction.
+    pub async fn rollback(self) -> Result<(), sqlx::Error> {
+        self.connections.0.into_inner().rollback().await
+    }
+}
+
+impl<C: Codec, Conn: Connections> AsyncSqliteStorageProvider<C, Conn> {
+    fn wrap_storable_group_id_ref<'b, GroupId: Key<CURRENT_VERSION>>(
+        &self,
+        group_id: &'b GroupId,
+    ) -> StorableGroupIdRef<'b, GroupId, C> {
+        StorableGroupIdRef(group_id, PhantomData)
+    }
+}
+
 #[derive(Debug, Serialize)]
 struct KeyRefWrapper<'a, T: Key<CURRENT_VERSION>, C: Codec>(&'a T, PhantomData<C>);
 
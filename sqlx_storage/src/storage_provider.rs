@@ -8,7 +8,7 @@ use openmls_traits::storage::{
 };
 use sqlx::{
     Database, Encode, Sqlite, SqliteExecutor, Type, encode::IsNull, error::BoxDynError, query,
-    query_scalar, sqlite::SqliteTypeInfo,
+    query_scalar, raw_sql, sqlite::SqliteTypeInfo,
 };
 
 use super::{
@@ -27,6 +27,28 @@ use super::{
 impl<C: Codec> StorageProvider<CURRENT_VERSION> for SqliteStorageProvider<'_, C> {
     type Error = sqlx::Error;
 
+    // Transactions are mapped to savepoints, which start a transaction if
+    // there is none and nest into the application's transaction otherwise.
+
+    fn begin_transaction(&self) -> Result<(), Self::Error> {
+        let mut connection = self.connection.borrow_mut();
+        let task = raw_sql("SAVEPOINT openmls_transaction").execute(&mut **connection);
+        block_async_in_place(task).map(|_| ())
+    }
+
+    fn commit_transaction(&self) -> Result<(), Self::Error> {
+        let mut connection = self.connection.borrow_mut();
+        let task = raw_sql("RELEASE openmls_transaction").execute(&mut **connection);
+        block_async_in_place(task).map(|_| ())
+    }
+
+    fn rollback_transaction(&self) -> Result<(), Self::Error> {
+        let mut connection = self.connection.borrow_mut();
+        let task = raw_sql("ROLLBACK TO openmls_transaction; RELEASE openmls_transaction")
+            .execute(&mut **connection);
+        block_async_in_place(task).map(|_| ())
+    }
+
     fn write_mls_join_config<
         GroupId: traits::GroupId<CURRENT_VERSION>,
         MlsGroupJoinConfig: traits::MlsGroupJoinConfig<CURRENT_VERSION>,
@@ -212,6 +234,28 @@ impl<C: Codec> StorageProvider<CURRENT_VERSION> for SqliteStorageProvider<'_, C>
         block_async_in_place(task)
     }
 
+    fn supports_future_epoch_messages(&self) -> bool {
+        true
+    }
+
+    fn write_future_epoch_messages<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        FutureEpochMessages: traits::FutureEpochMessages<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        future_epoch_messages: &FutureEpochMessages,
+    ) -> Result<(), Self::Error> {
+        let storable = StorableGroupDataRef(future_epoch_messages);
+        let mut connection = self.connection.borrow_mut();
+        let task = storable.store::<_, C>(
+            &mut **connection,
+            group_id,
+            GroupDataType::FutureEpochMessages,
+        );
+        block_async_in_place(task)
+    }
+
     fn write_signature_key_pair<
         SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION>,
         SignatureKeyPair: traits::SignatureKeyPair<CURRENT_VERSION>,
@@ -474,6 +518,22 @@ impl<C: Codec> StorageProvider<CURRENT_VERSION> for SqliteStorageProvider<'_, C>
         block_async_in_place(task)
     }
 
+    fn future_epoch_messages<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        FutureEpochMessages: traits::FutureEpochMessages<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<FutureEpochMessages>, Self::Error> {
+        let mut connection = self.connection.borrow_mut();
+        let task = StorableGroupData::load::<_, C>(
+            &mut **connection,
+            group_id,
+            GroupDataType::FutureEpochMessages,
+        );
+        block_async_in_place(task)
+    }
+
     fn signature_key_pair<
         SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION>,
         SignatureKeyPair: traits::SignatureKeyPair<CURRENT_VERSION>,
@@ -660,6 +720,17 @@ impl<C: Codec> StorageProvider<CURRENT_VERSION> for SqliteStorageProvider<'_, C>
         block_async_in_place(task)
     }
 
+    fn delete_future_epoch_messages<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let storable = self.wrap_storable_group_id_ref(group_id);
+        let mut connection = self.connection.borrow_mut();
+        let task =
+            storable.delete_group_data(&mut **connection, GroupDataType::FutureEpochMessages);
+        block_async_in_place(task)
+    }
+
     fn clear_proposal_queue<
         GroupId: traits::GroupId<CURRENT_VERSION>,
         ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
@@ -838,7 +909,7 @@ impl<GroupData: Entity<CURRENT_VERSION>> StorableGroupDataRef<'_, GroupData> {
 impl<SignatureKeyPairs: Entity<CURRENT_VERSION>>
     StorableSignatureKeyPairsRef<'_, SignatureKeyPairs>
 {
-    async fn store<SignaturePublicKey: Key<CURRENT_VERSION>, C: Codec>(
+    pub(super) async fn store<SignaturePublicKey: Key<CURRENT_VERSION>, C: Codec>(
         &self,
         executor: impl SqliteExecutor<'_>,
         public_key: &SignaturePublicKey,
@@ -857,7 +928,7 @@ impl<SignatureKeyPairs: Entity<CURRENT_VERSION>>
 }
 
 impl<LeafNode: Entity<CURRENT_VERSION>> StorableLeafNodeRef<'_, LeafNode> {
-    async fn store<GroupId: Key<CURRENT_VERSION>, C: Codec>(
+    pub(super) async fn store<GroupId: Key<CURRENT_VERSION>, C: Codec>(
         &self,
         executor: impl SqliteExecutor<'_>,
         group_id: &GroupId,
@@ -892,7 +963,7 @@ impl<T: Entity<CURRENT_VERSION>, C: Codec> Encode<'_, Sqlite> for EntitySliceWra
 }
 
 impl<KeyPackage: Entity<CURRENT_VERSION>> StorableKeyPackageRef<'_, KeyPackage> {
-    async fn store<KeyPackageRef: Key<CURRENT_VERSION>, C: Codec>(
+    pub(super) async fn store<KeyPackageRef: Key<CURRENT_VERSION>, C: Codec>(
         &self,
         executor: impl SqliteExecutor<'_>,
         key_package_ref: &KeyPackageRef,
@@ -911,7 +982,11 @@ impl<KeyPackage: Entity<CURRENT_VERSION>> StorableKeyPackageRef<'_, KeyPackage>
 }
 
 impl<EpochKeyPairs: Entity<CURRENT_VERSION>> StorableEpochKeyPairsRef<'_, EpochKeyPairs> {
-    async fn store<GroupId: Key<CURRENT_VERSION>, EpochKey: Key<CURRENT_VERSION>, C: Codec>(
+    pub(super) async fn store<
+        GroupId: Key<CURRENT_VERSION>,
+        EpochKey: Key<CURRENT_VERSION>,
+        C: Codec,
+    >(
         &self,
         executor: impl SqliteExecutor<'_>,
         group_id: &GroupId,
@@ -936,7 +1011,7 @@ impl<EpochKeyPairs: Entity<CURRENT_VERSION>> StorableEpochKeyPairsRef<'_, EpochK
 }
 
 impl<PskBundle: Entity<CURRENT_VERSION>> StorablePskBundleRef<'_, PskBundle> {
-    async fn store<PskId: Key<CURRENT_VERSION>, C: Codec>(
+    pub(super) async fn store<PskId: Key<CURRENT_VERSION>, C: Codec>(
         &self,
         executor: impl SqliteExecutor<'_>,
         psk_id: &PskId,
@@ -955,7 +1030,7 @@ impl<PskBundle: Entity<CURRENT_VERSION>> StorablePskBundleRef<'_, PskBundle> {
 }
 
 impl<GroupData: Entity<CURRENT_VERSION>> StorableGroupData<GroupData> {
-    async fn load<GroupId: Key<CURRENT_VERSION>, C: Codec>(
+    pub(super) async fn load<GroupId: Key<CURRENT_VERSION>, C: Codec>(
         executor: impl SqliteExecutor<'_>,
         group_id: &GroupId,
         data_type: GroupDataType,
@@ -977,7 +1052,7 @@ impl<GroupData: Entity<CURRENT_VERSION>> StorableGroupData<GroupData> {
 impl<Proposal: Entity<CURRENT_VERSION>, ProposalRef: Entity<CURRENT_VERSION>>
     StorableProposalRef<'_, Proposal, ProposalRef>
 {
-    async fn store<GroupId: Key<CURRENT_VERSION>, C: Codec>(
+    pub(super) async fn store<GroupId: Key<CURRENT_VERSION>, C: Codec>(
         &self,
         executor: impl SqliteExecutor<'_>,
         group_id: &GroupId,
@@ -998,7 +1073,7 @@ impl<Proposal: Entity<CURRENT_VERSION>, ProposalRef: Entity<CURRENT_VERSION>>
 }
 
 impl<LeafNode: Entity<CURRENT_VERSION>> StorableLeafNode<LeafNode> {
-    async fn load<GroupId: Key<CURRENT_VERSION>, C: Codec>(
+    pub(super) async fn load<GroupId: Key<CURRENT_VERSION>, C: Codec>(
         executor: impl SqliteExecutor<'_>,
         group_id: &GroupId,
     ) -> sqlx::Result<Vec<LeafNode>> {
@@ -1020,7 +1095,7 @@ impl<LeafNode: Entity<CURRENT_VERSION>> StorableLeafNode<LeafNode> {
 impl<Proposal: Entity<CURRENT_VERSION>, ProposalRef: Entity<CURRENT_VERSION>>
     StorableProposal<Proposal, ProposalRef>
 {
-    async fn load<GroupId: Key<CURRENT_VERSION>, C: Codec>(
+    pub(super) async fn load<GroupId: Key<CURRENT_VERSION>, C: Codec>(
         executor: impl SqliteExecutor<'_>,
         group_id: &GroupId,
     ) -> sqlx::Result<Vec<(ProposalRef, Proposal)>> {
@@ -1040,7 +1115,7 @@ impl<Proposal: Entity<CURRENT_VERSION>, ProposalRef: Entity<CURRENT_VERSION>>
         .collect()
     }
 
-    async fn load_refs<GroupId: Key<CURRENT_VERSION>, C: Codec>(
+    pub(super) async fn load_refs<GroupId: Key<CURRENT_VERSION>, C: Codec>(
         executor: impl SqliteExecutor<'_>,
         group_id: &GroupId,
     ) -> sqlx::Result<Vec<ProposalRef>> {
@@ -1058,7 +1133,10 @@ impl<Proposal: Entity<CURRENT_VERSION>, ProposalRef: Entity<CURRENT_VERSION>>
 }
 
 impl<SignatureKeyPairs: Entity<CURRENT_VERSION>> StorableSignatureKeyPairs<SignatureKeyPairs> {
-    async fn load<SignaturePublicKey: SignaturePublicKeyTrait<CURRENT_VERSION>, C: Codec>(
+    pub(super) async fn load<
+        SignaturePublicKey: SignaturePublicKeyTrait<CURRENT_VERSION>,
+        C: Codec,
+    >(
         executor: impl SqliteExecutor<'_>,
         public_key: &SignaturePublicKey,
     ) -> sqlx::Result<Option<SignatureKeyPairs>> {
@@ -1075,7 +1153,7 @@ impl<SignatureKeyPairs: Entity<CURRENT_VERSION>> StorableSignatureKeyPairs<Signa
 }
 
 impl<EncryptionKeyPair: Entity<CURRENT_VERSION>> StorableEncryptionKeyPair<EncryptionKeyPair> {
-    async fn load<EncryptionKey: Key<CURRENT_VERSION>, C: Codec>(
+    pub(super) async fn load<EncryptionKey: Key<CURRENT_VERSION>, C: Codec>(
         executor: impl SqliteExecutor<'_>,
         public_key: &EncryptionKey,
     ) -> sqlx::Result<Option<EncryptionKeyPair>> {
@@ -1094,7 +1172,7 @@ impl<EncryptionKeyPair: Entity<CURRENT_VERSION>> StorableEncryptionKeyPair<Encry
 impl<EncryptionKeyPair: Entity<CURRENT_VERSION>>
     StorableEncryptionKeyPairRef<'_, EncryptionKeyPair>
 {
-    async fn store<EncryptionKey: Key<CURRENT_VERSION>, C: Codec>(
+    pub(super) async fn store<EncryptionKey: Key<CURRENT_VERSION>, C: Codec>(
         &self,
         executor: impl SqliteExecutor<'_>,
         public_key: &EncryptionKey,
@@ -1112,7 +1190,7 @@ impl<EncryptionKeyPair: Entity<CURRENT_VERSION>>
     }
 }
 
-async fn load_epoch_key_pairs<
+pub(super) async fn load_epoch_key_pairs<
     EpochKeyPairs: Entity<CURRENT_VERSION>,
     GroupId: Key<CURRENT_VERSION>,
     EpochKey: Key<CURRENT_VERSION>,
@@ -1140,7 +1218,7 @@ async fn load_epoch_key_pairs<
 }
 
 impl<KeyPackage: Entity<CURRENT_VERSION>> StorableKeyPackage<KeyPackage> {
-    async fn load<KeyPackageRef: Key<CURRENT_VERSION>, C: Codec>(
+    pub(super) async fn load<KeyPackageRef: Key<CURRENT_VERSION>, C: Codec>(
         executor: impl SqliteExecutor<'_>,
         key_package_ref: &KeyPackageRef,
     ) -> sqlx::Result<Option<KeyPackage>> {
@@ -1156,7 +1234,7 @@ impl<KeyPackage: Entity<CURRENT_VERSION>> StorableKeyPackage<KeyPackage> {
     }
 }
 
-async fn load_psk_bundle<
+pub(super) async fn load_psk_bundle<
     PskBundle: Entity<CURRENT_VERSION>,
     PskId: Key<CURRENT_VERSION>,
     C: Codec,
@@ -1176,7 +1254,10 @@ async fn load_psk_bundle<
 }
 
 impl<GroupId: Key<CURRENT_VERSION>, C: Codec> StorableGroupIdRef<'_, GroupId, C> {
-    async fn delete_all_proposals(&self, executor: impl SqliteExecutor<'_>) -> sqlx::Result<()> {
+    pub(super) async fn delete_all_proposals(
+        &self,
+        executor: impl SqliteExecutor<'_>,
+    ) -> sqlx::Result<()> {
         let group_id = KeyRefWrapper::<_, C>(self.0, PhantomData);
         query!("DELETE FROM openmls_proposal WHERE group_id = ?1", group_id)
             .execute(executor)
@@ -1184,7 +1265,7 @@ impl<GroupId: Key<CURRENT_VERSION>, C: Codec> StorableGroupIdRef<'_, GroupId, C>
         Ok(())
     }
 
-    async fn delete_proposal<ProposalRef: ProposalRefTrait<CURRENT_VERSION>>(
+    pub(super) async fn delete_proposal<ProposalRef: ProposalRefTrait<CURRENT_VERSION>>(
         &self,
         executor: impl SqliteExecutor<'_>,
         proposal_ref: &ProposalRef,
@@ -1201,7 +1282,10 @@ impl<GroupId: Key<CURRENT_VERSION>, C: Codec> StorableGroupIdRef<'_, GroupId, C>
         Ok(())
     }
 
-    async fn delete_leaf_nodes(&self, executor: impl SqliteExecutor<'_>) -> sqlx::Result<()> {
+    pub(super) async fn delete_leaf_nodes(
+        &self,
+        executor: impl SqliteExecutor<'_>,
+    ) -> sqlx::Result<()> {
         let group_id = KeyRefWrapper::<_, C>(self.0, PhantomData);
         query!(
             "DELETE FROM openmls_own_leaf_node WHERE group_id = ?1",
@@ -1212,7 +1296,7 @@ impl<GroupId: Key<CURRENT_VERSION>, C: Codec> StorableGroupIdRef<'_, GroupId, C>
         Ok(())
     }
 
-    async fn delete_group_data(
+    pub(super) async fn delete_group_data(
         &self,
         executor: impl SqliteExecutor<'_>,
         data_type: GroupDataType,
@@ -1228,7 +1312,7 @@ impl<GroupId: Key<CURRENT_VERSION>, C: Codec> StorableGroupIdRef<'_, GroupId, C>
         Ok(())
     }
 
-    async fn delete_epoch_key_pair<EpochKey: Key<CURRENT_VERSION>>(
+    pub(super) async fn delete_epoch_key_pair<EpochKey: Key<CURRENT_VERSION>>(
         &self,
         executor: impl SqliteExecutor<'_>,
         epoch_key: &EpochKey,
@@ -1251,7 +1335,10 @@ impl<GroupId: Key<CURRENT_VERSION>, C: Codec> StorableGroupIdRef<'_, GroupId, C>
 impl<SignaturePublicKey: Key<CURRENT_VERSION>>
     StorableSignaturePublicKeyRef<'_, SignaturePublicKey>
 {
-    async fn delete<C: Codec>(&self, executor: impl SqliteExecutor<'_>) -> sqlx::Result<()> {
+    pub(super) async fn delete<C: Codec>(
+        &self,
+        executor: impl SqliteExecutor<'_>,
+    ) -> sqlx::Result<()> {
         let public_key = KeyRefWrapper::<_, C>(self.0, PhantomData);
         query!(
             "DELETE FROM openmls_signature_key WHERE public_key = ?1",
@@ -1266,7 +1353,10 @@ impl<SignaturePublicKey: Key<CURRENT_VERSION>>
 impl<EncryptionPublicKey: Key<CURRENT_VERSION>>
     StorableEncryptionPublicKeyRef<'_, EncryptionPublicKey>
 {
-    async fn delete<C: Codec>(&self, executor: impl SqliteExecutor<'_>) -> sqlx::Result<()> {
+    pub(super) async fn delete<C: Codec>(
+        &self,
+        executor: impl SqliteExecutor<'_>,
+    ) -> sqlx::Result<()> {
         let public_key = KeyRefWrapper::<_, C>(self.0, PhantomData);
         query!(
             "DELETE FROM openmls_encryption_key WHERE public_key = ?1",
@@ -1279,7 +1369,7 @@ impl<EncryptionPublicKey: Key<CURRENT_VERSION>>
 }
 
 impl<KeyPackageRef: Key<CURRENT_VERSION>> StorableHashRef<'_, KeyPackageRef> {
-    async fn delete_key_package<C: Codec>(
+    pub(super) async fn delete_key_package<C: Codec>(
         &self,
         executor: impl SqliteExecutor<'_>,
     ) -> sqlx::Result<()> {
@@ -1295,7 +1385,10 @@ impl<KeyPackageRef: Key<CURRENT_VERSION>> StorableHashRef<'_, KeyPackageRef> {
 }
 
 impl<PskId: Key<CURRENT_VERSION>> StorablePskIdRef<'_, PskId> {
-    async fn delete<C: Codec>(&self, executor: impl SqliteExecutor<'_>) -> sqlx::Result<()> {
+    pub(super) async fn delete<C: Codec>(
+        &self,
+        executor: impl SqliteExecutor<'_>,
+    ) -> sqlx::Result<()> {
         let psks_id = KeyRefWrapper::<_, C>(self.0, PhantomData);
         query!("DELETE FROM openmls_psk WHERE psk_id = ?1", psks_id)
             .execute(executor)
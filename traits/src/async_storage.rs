@@ -0,0 +1,1586 @@
+//! This module describes the asynchronous counterpart of the
+//! [`StorageProvider`] trait.
+//!
+//! The [`AsyncStorageProvider`] is meant for storage backends that are
+//! accessed through an asynchronous driver, e.g. a database connection pool,
+//! where blocking on every storage access is not acceptable. Every
+//! [`StorageProvider`] is also an [`AsyncStorageProvider`] whose futures are
+//! ready immediately, so existing synchronous backends can be used wherever an
+//! [`AsyncStorageProvider`] is expected.
+
+use std::future::Future;
+
+use crate::storage::{traits, StorageProvider};
+
+/// AsyncStorageProvider describes an asynchronous storage backing OpenMLS and
+/// persists the state of OpenMLS groups.
+///
+/// All methods behave like the methods of the same name on
+/// [`StorageProvider`], but return a future that resolves to the result. The
+/// futures are [`Send`], so that OpenMLS operations can be spawned on a
+/// multi-threaded runtime. For this, the keys and values passed to the methods
+/// must be [`Send`] and [`Sync`].
+pub trait AsyncStorageProvider<const VERSION: u16> {
+    /// An opaque error returned by all methods on this trait.
+    type Error: core::fmt::Debug + std::error::Error + Send;
+
+    /// Get the version of this provider.
+    fn version() -> u16 {
+        VERSION
+    }
+
+    /// See [`StorageProvider::begin_transaction`].
+    fn begin_transaction(&self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(Ok(()))
+    }
+
+    /// See [`StorageProvider::commit_transaction`].
+    fn commit_transaction(&self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(Ok(()))
+    }
+
+    /// See [`StorageProvider::rollback_transaction`].
+    fn rollback_transaction(&self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(Ok(()))
+    }
+
+    /// See [`StorageProvider::supports_future_epoch_messages`].
+    fn supports_future_epoch_messages(&self) -> bool {
+        false
+    }
+
+    /// See [`StorageProvider::write_mls_join_config`].
+    fn write_mls_join_config<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        config: &MlsGroupJoinConfig,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::append_own_leaf_node`].
+    fn append_own_leaf_node<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        LeafNode: traits::LeafNode<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        leaf_node: &LeafNode,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::queue_proposal`].
+    fn queue_proposal<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ProposalRef: traits::ProposalRef<VERSION> + Send + Sync,
+        QueuedProposal: traits::QueuedProposal<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+        proposal: &QueuedProposal,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_tree`].
+    fn write_tree<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        TreeSync: traits::TreeSync<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        tree: &TreeSync,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_interim_transcript_hash`].
+    fn write_interim_transcript_hash<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        InterimTranscriptHash: traits::InterimTranscriptHash<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        interim_transcript_hash: &InterimTranscriptHash,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_context`].
+    fn write_context<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        GroupContext: traits::GroupContext<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_context: &GroupContext,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_confirmation_tag`].
+    fn write_confirmation_tag<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ConfirmationTag: traits::ConfirmationTag<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        confirmation_tag: &ConfirmationTag,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_group_state`].
+    fn write_group_state<
+        GroupState: traits::GroupState<VERSION> + Send + Sync,
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_state: &GroupState,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_message_secrets`].
+    fn write_message_secrets<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        MessageSecrets: traits::MessageSecrets<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        message_secrets: &MessageSecrets,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_resumption_psk_store`].
+    fn write_resumption_psk_store<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ResumptionPskStore: traits::ResumptionPskStore<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        resumption_psk_store: &ResumptionPskStore,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_own_leaf_index`].
+    fn write_own_leaf_index<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        LeafNodeIndex: traits::LeafNodeIndex<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        own_leaf_index: &LeafNodeIndex,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_group_epoch_secrets`].
+    fn write_group_epoch_secrets<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        GroupEpochSecrets: traits::GroupEpochSecrets<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_epoch_secrets: &GroupEpochSecrets,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_future_epoch_messages`].
+    fn write_future_epoch_messages<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        FutureEpochMessages: traits::FutureEpochMessages<VERSION> + Send + Sync,
+    >(
+        &self,
+        _group_id: &GroupId,
+        _future_epoch_messages: &FutureEpochMessages,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(Ok(()))
+    }
+
+    /// See [`StorageProvider::write_application_export_tree`].
+    #[cfg(feature = "extensions-draft")]
+    fn write_application_export_tree<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ApplicationExportTree: traits::ApplicationExportTree<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        application_export_tree: &ApplicationExportTree,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_vc_emulation_epoch_state`].
+    #[cfg(feature = "virtual-clients-draft")]
+    fn write_vc_emulation_epoch_state<
+        EpochId: traits::VcEpochId<VERSION> + Send + Sync,
+        VcEmulationEpochState: traits::VcEmulationEpochState<VERSION> + Send + Sync,
+    >(
+        &self,
+        epoch_id: &EpochId,
+        vc_emulation_epoch_state: &VcEmulationEpochState,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_vc_emulation_bindings`].
+    #[cfg(feature = "virtual-clients-draft")]
+    fn write_vc_emulation_bindings<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        VcEmulationBindings: traits::VcEmulationBindings<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        bindings: &VcEmulationBindings,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_vc_operation_tree`].
+    #[cfg(feature = "virtual-clients-draft")]
+    fn write_vc_operation_tree<
+        EpochId: traits::VcEpochId<VERSION> + Send + Sync,
+        VcOperationTree: traits::VcOperationTree<VERSION> + Send + Sync,
+    >(
+        &self,
+        epoch_id: &EpochId,
+        vc_operation_tree: &VcOperationTree,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_retained_key_package_material_batch`].
+    #[cfg(feature = "virtual-clients-draft")]
+    fn write_retained_key_package_material_batch<
+        EpochId: traits::VcEpochId<VERSION> + Send + Sync,
+        VcOperationTree: traits::VcOperationTree<VERSION> + Send + Sync,
+        KeyPackageRef: traits::HashReference<VERSION> + Send + Sync,
+        RetainedKeyPackageMaterial: traits::RetainedKeyPackageMaterial<VERSION> + Send + Sync,
+    >(
+        &self,
+        epoch_id: &EpochId,
+        operation_tree: &VcOperationTree,
+        materials: &[(KeyPackageRef, RetainedKeyPackageMaterial)],
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_signature_key_pair`].
+    fn write_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<VERSION> + Send + Sync,
+        SignatureKeyPair: traits::SignatureKeyPair<VERSION> + Send + Sync,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+        signature_key_pair: &SignatureKeyPair,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_encryption_key_pair`].
+    fn write_encryption_key_pair<
+        EncryptionKey: traits::EncryptionKey<VERSION> + Send + Sync,
+        HpkeKeyPair: traits::HpkeKeyPair<VERSION> + Send + Sync,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+        key_pair: &HpkeKeyPair,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_encryption_epoch_key_pairs`].
+    fn write_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        EpochKey: traits::EpochKey<VERSION> + Send + Sync,
+        HpkeKeyPair: traits::HpkeKeyPair<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+        key_pairs: &[HpkeKeyPair],
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_key_package`].
+    fn write_key_package<
+        HashReference: traits::HashReference<VERSION> + Send + Sync,
+        KeyPackage: traits::KeyPackage<VERSION> + Send + Sync,
+    >(
+        &self,
+        hash_ref: &HashReference,
+        key_package: &KeyPackage,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_psk`].
+    fn write_psk<
+        PskId: traits::PskId<VERSION> + Send + Sync,
+        PskBundle: traits::PskBundle<VERSION> + Send + Sync,
+    >(
+        &self,
+        psk_id: &PskId,
+        psk: &PskBundle,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::mls_group_join_config`].
+    fn mls_group_join_config<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<MlsGroupJoinConfig>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::own_leaf_nodes`].
+    fn own_leaf_nodes<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        LeafNode: traits::LeafNode<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Vec<LeafNode>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::queued_proposal_refs`].
+    fn queued_proposal_refs<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ProposalRef: traits::ProposalRef<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Vec<ProposalRef>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::queued_proposals`].
+    fn queued_proposals<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ProposalRef: traits::ProposalRef<VERSION> + Send + Sync,
+        QueuedProposal: traits::QueuedProposal<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Vec<(ProposalRef, QueuedProposal)>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::tree`].
+    fn tree<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        TreeSync: traits::TreeSync<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<TreeSync>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::group_context`].
+    fn group_context<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        GroupContext: traits::GroupContext<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<GroupContext>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::interim_transcript_hash`].
+    fn interim_transcript_hash<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        InterimTranscriptHash: traits::InterimTranscriptHash<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<InterimTranscriptHash>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::confirmation_tag`].
+    fn confirmation_tag<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ConfirmationTag: traits::ConfirmationTag<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<ConfirmationTag>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::group_state`].
+    fn group_state<
+        GroupState: traits::GroupState<VERSION> + Send + Sync,
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<GroupState>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::message_secrets`].
+    fn message_secrets<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        MessageSecrets: traits::MessageSecrets<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<MessageSecrets>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::resumption_psk_store`].
+    fn resumption_psk_store<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ResumptionPskStore: traits::ResumptionPskStore<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<ResumptionPskStore>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::own_leaf_index`].
+    fn own_leaf_index<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        LeafNodeIndex: traits::LeafNodeIndex<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<LeafNodeIndex>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::group_epoch_secrets`].
+    fn group_epoch_secrets<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        GroupEpochSecrets: traits::GroupEpochSecrets<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<GroupEpochSecrets>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::signature_key_pair`].
+    fn signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<VERSION> + Send + Sync,
+        SignatureKeyPair: traits::SignatureKeyPair<VERSION> + Send + Sync,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> impl Future<Output = Result<Option<SignatureKeyPair>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::encryption_key_pair`].
+    fn encryption_key_pair<
+        HpkeKeyPair: traits::HpkeKeyPair<VERSION> + Send + Sync,
+        EncryptionKey: traits::EncryptionKey<VERSION> + Send + Sync,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> impl Future<Output = Result<Option<HpkeKeyPair>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::encryption_epoch_key_pairs`].
+    fn encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        EpochKey: traits::EpochKey<VERSION> + Send + Sync,
+        HpkeKeyPair: traits::HpkeKeyPair<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> impl Future<Output = Result<Vec<HpkeKeyPair>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::key_package`].
+    fn key_package<
+        KeyPackageRef: traits::HashReference<VERSION> + Send + Sync,
+        KeyPackage: traits::KeyPackage<VERSION> + Send + Sync,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> impl Future<Output = Result<Option<KeyPackage>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::psk`].
+    fn psk<
+        PskBundle: traits::PskBundle<VERSION> + Send + Sync,
+        PskId: traits::PskId<VERSION> + Send + Sync,
+    >(
+        &self,
+        psk_id: &PskId,
+    ) -> impl Future<Output = Result<Option<PskBundle>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::future_epoch_messages`].
+    fn future_epoch_messages<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        FutureEpochMessages: traits::FutureEpochMessages<VERSION> + Send + Sync,
+    >(
+        &self,
+        _group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<FutureEpochMessages>, Self::Error>> + Send {
+        std::future::ready(Ok(None))
+    }
+
+    /// See [`StorageProvider::application_export_tree`].
+    #[cfg(feature = "extensions-draft")]
+    fn application_export_tree<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ApplicationExportTree: traits::ApplicationExportTree<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<ApplicationExportTree>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::vc_emulation_epoch_state`].
+    #[cfg(feature = "virtual-clients-draft")]
+    fn vc_emulation_epoch_state<
+        EpochId: traits::VcEpochId<VERSION> + Send + Sync,
+        VcEmulationEpochState: traits::VcEmulationEpochState<VERSION> + Send + Sync,
+    >(
+        &self,
+        epoch_id: &EpochId,
+    ) -> impl Future<Output = Result<Option<VcEmulationEpochState>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::vc_emulation_bindings`].
+    #[cfg(feature = "virtual-clients-draft")]
+    fn vc_emulation_bindings<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        VcEmulationBindings: traits::VcEmulationBindings<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<VcEmulationBindings>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::vc_operation_tree`].
+    #[cfg(feature = "virtual-clients-draft")]
+    fn vc_operation_tree<
+        EpochId: traits::VcEpochId<VERSION> + Send + Sync,
+        VcOperationTree: traits::VcOperationTree<VERSION> + Send + Sync,
+    >(
+        &self,
+        epoch_id: &EpochId,
+    ) -> impl Future<Output = Result<Option<VcOperationTree>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::retained_key_package_material`].
+    #[cfg(feature = "virtual-clients-draft")]
+    fn retained_key_package_material<
+        KeyPackageRef: traits::HashReference<VERSION> + Send + Sync,
+        RetainedKeyPackageMaterial: traits::RetainedKeyPackageMaterial<VERSION> + Send + Sync,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> impl Future<Output = Result<Option<RetainedKeyPackageMaterial>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::has_retained_key_package_material_for_epoch`].
+    #[cfg(feature = "virtual-clients-draft")]
+    fn has_retained_key_package_material_for_epoch<
+        EpochId: traits::VcEpochId<VERSION> + Send + Sync,
+    >(
+        &self,
+        epoch_id: &EpochId,
+    ) -> impl Future<Output = Result<bool, Self::Error>> + Send;
+
+    /// See [`StorageProvider::remove_proposal`].
+    fn remove_proposal<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ProposalRef: traits::ProposalRef<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_own_leaf_nodes`].
+    fn delete_own_leaf_nodes<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_group_config`].
+    fn delete_group_config<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_tree`].
+    fn delete_tree<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_confirmation_tag`].
+    fn delete_confirmation_tag<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_group_state`].
+    fn delete_group_state<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_context`].
+    fn delete_context<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_interim_transcript_hash`].
+    fn delete_interim_transcript_hash<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_message_secrets`].
+    fn delete_message_secrets<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_all_resumption_psk_secrets`].
+    fn delete_all_resumption_psk_secrets<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_own_leaf_index`].
+    fn delete_own_leaf_index<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_group_epoch_secrets`].
+    fn delete_group_epoch_secrets<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::clear_proposal_queue`].
+    fn clear_proposal_queue<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ProposalRef: traits::ProposalRef<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_signature_key_pair`].
+    fn delete_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<VERSION> + Send + Sync,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_encryption_key_pair`].
+    fn delete_encryption_key_pair<EncryptionKey: traits::EncryptionKey<VERSION> + Send + Sync>(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_encryption_epoch_key_pairs`].
+    fn delete_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        EpochKey: traits::EpochKey<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_key_package`].
+    fn delete_key_package<KeyPackageRef: traits::HashReference<VERSION> + Send + Sync>(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_psk`].
+    fn delete_psk<PskKey: traits::PskId<VERSION> + Send + Sync>(
+        &self,
+        psk_id: &PskKey,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_future_epoch_messages`].
+    fn delete_future_epoch_messages<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        _group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(Ok(()))
+    }
+
+    /// See [`StorageProvider::delete_application_export_tree`].
+    #[cfg(feature = "extensions-draft")]
+    fn delete_application_export_tree<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ApplicationExportTree: traits::ApplicationExportTree<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_vc_emulation_state_if_unreferenced`].
+    #[cfg(feature = "virtual-clients-draft")]
+    fn delete_vc_emulation_state_if_unreferenced<
+        EpochId: traits::VcEpochId<VERSION> + Send + Sync,
+    >(
+        &self,
+        epoch_id: &EpochId,
+    ) -> impl Future<Output = Result<bool, Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_vc_emulation_bindings`].
+    #[cfg(feature = "virtual-clients-draft")]
+    fn delete_vc_emulation_bindings<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_retained_key_package_material`].
+    #[cfg(feature = "virtual-clients-draft")]
+    fn delete_retained_key_package_material<
+        KeyPackageRef: traits::HashReference<VERSION> + Send + Sync,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+impl<const VERSION: u16, S: StorageProvider<VERSION>> AsyncStorageProvider<VERSION> for S {
+    type Error = S::Error;
+
+    fn begin_transaction(&self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::begin_transaction(self))
+    }
+
+    fn commit_transaction(&self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::commit_transaction(self))
+    }
+
+    fn rollback_transaction(&self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::rollback_transaction(self))
+    }
+
+    fn supports_future_epoch_messages(&self) -> bool {
+        StorageProvider::<VERSION>::supports_future_epoch_messages(self)
+    }
+
+    fn write_mls_join_config<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        config: &MlsGroupJoinConfig,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::write_mls_join_config(
+            self, group_id, config,
+        ))
+    }
+
+    fn append_own_leaf_node<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        LeafNode: traits::LeafNode<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        leaf_node: &LeafNode,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::append_own_leaf_node(
+            self, group_id, leaf_node,
+        ))
+    }
+
+    fn queue_proposal<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ProposalRef: traits::ProposalRef<VERSION> + Send + Sync,
+        QueuedProposal: traits::QueuedProposal<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+        proposal: &QueuedProposal,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::queue_proposal(
+            self,
+            group_id,
+            proposal_ref,
+            proposal,
+        ))
+    }
+
+    fn write_tree<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        TreeSync: traits::TreeSync<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        tree: &TreeSync,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::write_tree(self, group_id, tree))
+    }
+
+    fn write_interim_transcript_hash<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        InterimTranscriptHash: traits::InterimTranscriptHash<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        interim_transcript_hash: &InterimTranscriptHash,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::write_interim_transcript_hash(
+            self,
+            group_id,
+            interim_transcript_hash,
+        ))
+    }
+
+    fn write_context<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        GroupContext: traits::GroupContext<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_context: &GroupContext,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::write_context(
+            self,
+            group_id,
+            group_context,
+        ))
+    }
+
+    fn write_confirmation_tag<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ConfirmationTag: traits::ConfirmationTag<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        confirmation_tag: &ConfirmationTag,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::write_confirmation_tag(
+            self,
+            group_id,
+            confirmation_tag,
+        ))
+    }
+
+    fn write_group_state<
+        GroupState: traits::GroupState<VERSION> + Send + Sync,
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_state: &GroupState,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::write_group_state(
+            self,
+            group_id,
+            group_state,
+        ))
+    }
+
+    fn write_message_secrets<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        MessageSecrets: traits::MessageSecrets<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        message_secrets: &MessageSecrets,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::write_message_secrets(
+            self,
+            group_id,
+            message_secrets,
+        ))
+    }
+
+    fn write_resumption_psk_store<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ResumptionPskStore: traits::ResumptionPskStore<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        resumption_psk_store: &ResumptionPskStore,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::write_resumption_psk_store(
+            self,
+            group_id,
+            resumption_psk_store,
+        ))
+    }
+
+    fn write_own_leaf_index<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        LeafNodeIndex: traits::LeafNodeIndex<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        own_leaf_index: &LeafNodeIndex,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::write_own_leaf_index(
+            self,
+            group_id,
+            own_leaf_index,
+        ))
+    }
+
+    fn write_group_epoch_secrets<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        GroupEpochSecrets: traits::GroupEpochSecrets<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_epoch_secrets: &GroupEpochSecrets,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::write_group_epoch_secrets(
+            self,
+            group_id,
+            group_epoch_secrets,
+        ))
+    }
+
+    fn write_future_epoch_messages<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        FutureEpochMessages: traits::FutureEpochMessages<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        future_epoch_messages: &FutureEpochMessages,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::write_future_epoch_messages(
+            self,
+            group_id,
+            future_epoch_messages,
+        ))
+    }
+
+    #[cfg(feature = "extensions-draft")]
+    fn write_application_export_tree<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ApplicationExportTree: traits::ApplicationExportTree<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        application_export_tree: &ApplicationExportTree,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::write_application_export_tree(
+            self,
+            group_id,
+            application_export_tree,
+        ))
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn write_vc_emulation_epoch_state<
+        EpochId: traits::VcEpochId<VERSION> + Send + Sync,
+        VcEmulationEpochState: traits::VcEmulationEpochState<VERSION> + Send + Sync,
+    >(
+        &self,
+        epoch_id: &EpochId,
+        vc_emulation_epoch_state: &VcEmulationEpochState,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::write_vc_emulation_epoch_state(
+            self,
+            epoch_id,
+            vc_emulation_epoch_state,
+        ))
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn write_vc_emulation_bindings<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        VcEmulationBindings: traits::VcEmulationBindings<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        bindings: &VcEmulationBindings,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::write_vc_emulation_bindings(
+            self, group_id, bindings,
+        ))
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn write_vc_operation_tree<
+        EpochId: traits::VcEpochId<VERSION> + Send + Sync,
+        VcOperationTree: traits::VcOperationTree<VERSION> + Send + Sync,
+    >(
+        &self,
+        epoch_id: &EpochId,
+        vc_operation_tree: &VcOperationTree,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::write_vc_operation_tree(
+            self,
+            epoch_id,
+            vc_operation_tree,
+        ))
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn write_retained_key_package_material_batch<
+        EpochId: traits::VcEpochId<VERSION> + Send + Sync,
+        VcOperationTree: traits::VcOperationTree<VERSION> + Send + Sync,
+        KeyPackageRef: traits::HashReference<VERSION> + Send + Sync,
+        RetainedKeyPackageMaterial: traits::RetainedKeyPackageMaterial<VERSION> + Send + Sync,
+    >(
+        &self,
+        epoch_id: &EpochId,
+        operation_tree: &VcOperationTree,
+        materials: &[(KeyPackageRef, RetainedKeyPackageMaterial)],
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(
+            StorageProvider::<VERSION>::write_retained_key_package_material_batch(
+                self,
+                epoch_id,
+                operation_tree,
+                materials,
+            ),
+        )
+    }
+
+    fn write_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<VERSION> + Send + Sync,
+        SignatureKeyPair: traits::SignatureKeyPair<VERSION> + Send + Sync,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+        signature_key_pair: &SignatureKeyPair,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::write_signature_key_pair(
+            self,
+            public_key,
+            signature_key_pair,
+        ))
+    }
+
+    fn write_encryption_key_pair<
+        EncryptionKey: traits::EncryptionKey<VERSION> + Send + Sync,
+        HpkeKeyPair: traits::HpkeKeyPair<VERSION> + Send + Sync,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+        key_pair: &HpkeKeyPair,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::write_encryption_key_pair(
+            self, public_key, key_pair,
+        ))
+    }
+
+    fn write_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        EpochKey: traits::EpochKey<VERSION> + Send + Sync,
+        HpkeKeyPair: traits::HpkeKeyPair<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+        key_pairs: &[HpkeKeyPair],
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(
+            StorageProvider::<VERSION>::write_encryption_epoch_key_pairs(
+                self, group_id, epoch, leaf_index, key_pairs,
+            ),
+        )
+    }
+
+    fn write_key_package<
+        HashReference: traits::HashReference<VERSION> + Send + Sync,
+        KeyPackage: traits::KeyPackage<VERSION> + Send + Sync,
+    >(
+        &self,
+        hash_ref: &HashReference,
+        key_package: &KeyPackage,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::write_key_package(
+            self,
+            hash_ref,
+            key_package,
+        ))
+    }
+
+    fn write_psk<
+        PskId: traits::PskId<VERSION> + Send + Sync,
+        PskBundle: traits::PskBundle<VERSION> + Send + Sync,
+    >(
+        &self,
+        psk_id: &PskId,
+        psk: &PskBundle,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::write_psk(self, psk_id, psk))
+    }
+
+    fn mls_group_join_config<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<MlsGroupJoinConfig>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::mls_group_join_config(
+            self, group_id,
+        ))
+    }
+
+    fn own_leaf_nodes<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        LeafNode: traits::LeafNode<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Vec<LeafNode>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::own_leaf_nodes(self, group_id))
+    }
+
+    fn queued_proposal_refs<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ProposalRef: traits::ProposalRef<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Vec<ProposalRef>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::queued_proposal_refs(
+            self, group_id,
+        ))
+    }
+
+    fn queued_proposals<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ProposalRef: traits::ProposalRef<VERSION> + Send + Sync,
+        QueuedProposal: traits::QueuedProposal<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Vec<(ProposalRef, QueuedProposal)>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::queued_proposals(self, group_id))
+    }
+
+    fn tree<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        TreeSync: traits::TreeSync<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<TreeSync>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::tree(self, group_id))
+    }
+
+    fn group_context<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        GroupContext: traits::GroupContext<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<GroupContext>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::group_context(self, group_id))
+    }
+
+    fn interim_transcript_hash<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        InterimTranscriptHash: traits::InterimTranscriptHash<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<InterimTranscriptHash>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::interim_transcript_hash(
+            self, group_id,
+        ))
+    }
+
+    fn confirmation_tag<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ConfirmationTag: traits::ConfirmationTag<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<ConfirmationTag>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::confirmation_tag(self, group_id))
+    }
+
+    fn group_state<
+        GroupState: traits::GroupState<VERSION> + Send + Sync,
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<GroupState>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::group_state(self, group_id))
+    }
+
+    fn message_secrets<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        MessageSecrets: traits::MessageSecrets<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<MessageSecrets>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::message_secrets(self, group_id))
+    }
+
+    fn resumption_psk_store<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ResumptionPskStore: traits::ResumptionPskStore<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<ResumptionPskStore>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::resumption_psk_store(
+            self, group_id,
+        ))
+    }
+
+    fn own_leaf_index<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        LeafNodeIndex: traits::LeafNodeIndex<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<LeafNodeIndex>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::own_leaf_index(self, group_id))
+    }
+
+    fn group_epoch_secrets<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        GroupEpochSecrets: traits::GroupEpochSecrets<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<GroupEpochSecrets>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::group_epoch_secrets(
+            self, group_id,
+        ))
+    }
+
+    fn signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<VERSION> + Send + Sync,
+        SignatureKeyPair: traits::SignatureKeyPair<VERSION> + Send + Sync,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> impl Future<Output = Result<Option<SignatureKeyPair>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::signature_key_pair(
+            self, public_key,
+        ))
+    }
+
+    fn encryption_key_pair<
+        HpkeKeyPair: traits::HpkeKeyPair<VERSION> + Send + Sync,
+        EncryptionKey: traits::EncryptionKey<VERSION> + Send + Sync,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> impl Future<Output = Result<Option<HpkeKeyPair>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::encryption_key_pair(
+            self, public_key,
+        ))
+    }
+
+    fn encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        EpochKey: traits::EpochKey<VERSION> + Send + Sync,
+        HpkeKeyPair: traits::HpkeKeyPair<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> impl Future<Output = Result<Vec<HpkeKeyPair>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::encryption_epoch_key_pairs(
+            self, group_id, epoch, leaf_index,
+        ))
+    }
+
+    fn key_package<
+        KeyPackageRef: traits::HashReference<VERSION> + Send + Sync,
+        KeyPackage: traits::KeyPackage<VERSION> + Send + Sync,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> impl Future<Output = Result<Option<KeyPackage>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::key_package(self, hash_ref))
+    }
+
+    fn psk<
+        PskBundle: traits::PskBundle<VERSION> + Send + Sync,
+        PskId: traits::PskId<VERSION> + Send + Sync,
+    >(
+        &self,
+        psk_id: &PskId,
+    ) -> impl Future<Output = Result<Option<PskBundle>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::psk(self, psk_id))
+    }
+
+    fn future_epoch_messages<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        FutureEpochMessages: traits::FutureEpochMessages<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<FutureEpochMessages>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::future_epoch_messages(
+            self, group_id,
+        ))
+    }
+
+    #[cfg(feature = "extensions-draft")]
+    fn application_export_tree<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ApplicationExportTree: traits::ApplicationExportTree<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<ApplicationExportTree>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::application_export_tree(
+            self, group_id,
+        ))
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn vc_emulation_epoch_state<
+        EpochId: traits::VcEpochId<VERSION> + Send + Sync,
+        VcEmulationEpochState: traits::VcEmulationEpochState<VERSION> + Send + Sync,
+    >(
+        &self,
+        epoch_id: &EpochId,
+    ) -> impl Future<Output = Result<Option<VcEmulationEpochState>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::vc_emulation_epoch_state(
+            self, epoch_id,
+        ))
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn vc_emulation_bindings<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        VcEmulationBindings: traits::VcEmulationBindings<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<VcEmulationBindings>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::vc_emulation_bindings(
+            self, group_id,
+        ))
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn vc_operation_tree<
+        EpochId: traits::VcEpochId<VERSION> + Send + Sync,
+        VcOperationTree: traits::VcOperationTree<VERSION> + Send + Sync,
+    >(
+        &self,
+        epoch_id: &EpochId,
+    ) -> impl Future<Output = Result<Option<VcOperationTree>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::vc_operation_tree(
+            self, epoch_id,
+        ))
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn retained_key_package_material<
+        KeyPackageRef: traits::HashReference<VERSION> + Send + Sync,
+        RetainedKeyPackageMaterial: traits::RetainedKeyPackageMaterial<VERSION> + Send + Sync,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> impl Future<Output = Result<Option<RetainedKeyPackageMaterial>, Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::retained_key_package_material(
+            self, hash_ref,
+        ))
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn has_retained_key_package_material_for_epoch<
+        EpochId: traits::VcEpochId<VERSION> + Send + Sync,
+    >(
+        &self,
+        epoch_id: &EpochId,
+    ) -> impl Future<Output = Result<bool, Self::Error>> + Send {
+        std::future::ready(
+            StorageProvider::<VERSION>::has_retained_key_package_material_for_epoch(self, epoch_id),
+        )
+    }
+
+    fn remove_proposal<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ProposalRef: traits::ProposalRef<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::remove_proposal(
+            self,
+            group_id,
+            proposal_ref,
+        ))
+    }
+
+    fn delete_own_leaf_nodes<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::delete_own_leaf_nodes(
+            self, group_id,
+        ))
+    }
+
+    fn delete_group_config<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::delete_group_config(
+            self, group_id,
+        ))
+    }
+
+    fn delete_tree<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::delete_tree(self, group_id))
+    }
+
+    fn delete_confirmation_tag<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::delete_confirmation_tag(
+            self, group_id,
+        ))
+    }
+
+    fn delete_group_state<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::delete_group_state(
+            self, group_id,
+        ))
+    }
+
+    fn delete_context<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::delete_context(self, group_id))
+    }
+
+    fn delete_interim_transcript_hash<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::delete_interim_transcript_hash(
+            self, group_id,
+        ))
+    }
+
+    fn delete_message_secrets<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::delete_message_secrets(
+            self, group_id,
+        ))
+    }
+
+    fn delete_all_resumption_psk_secrets<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(
+            StorageProvider::<VERSION>::delete_all_resumption_psk_secrets(self, group_id),
+        )
+    }
+
+    fn delete_own_leaf_index<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::delete_own_leaf_index(
+            self, group_id,
+        ))
+    }
+
+    fn delete_group_epoch_secrets<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::delete_group_epoch_secrets(
+            self, group_id,
+        ))
+    }
+
+    fn clear_proposal_queue<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ProposalRef: traits::ProposalRef<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::clear_proposal_queue::<
+            GroupId,
+            ProposalRef,
+        >(self, group_id))
+    }
+
+    fn delete_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<VERSION> + Send + Sync,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::delete_signature_key_pair(
+            self, public_key,
+        ))
+    }
+
+    fn delete_encryption_key_pair<EncryptionKey: traits::EncryptionKey<VERSION> + Send + Sync>(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::delete_encryption_key_pair(
+            self, public_key,
+        ))
+    }
+
+    fn delete_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        EpochKey: traits::EpochKey<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(
+            StorageProvider::<VERSION>::delete_encryption_epoch_key_pairs(
+                self, group_id, epoch, leaf_index,
+            ),
+        )
+    }
+
+    fn delete_key_package<KeyPackageRef: traits::HashReference<VERSION> + Send + Sync>(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::delete_key_package(
+            self, hash_ref,
+        ))
+    }
+
+    fn delete_psk<PskKey: traits::PskId<VERSION> + Send + Sync>(
+        &self,
+        psk_id: &PskKey,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::delete_psk(self, psk_id))
+    }
+
+    fn delete_future_epoch_messages<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::delete_future_epoch_messages(
+            self, group_id,
+        ))
+    }
+
+    #[cfg(feature = "extensions-draft")]
+    fn delete_application_export_tree<
+        GroupId: traits::GroupId<VERSION> + Send + Sync,
+        ApplicationExportTree: traits::ApplicationExportTree<VERSION> + Send + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(
+            StorageProvider::<VERSION>::delete_application_export_tree::<
+                GroupId,
+                ApplicationExportTree,
+            >(self, group_id),
+        )
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn delete_vc_emulation_state_if_unreferenced<
+        EpochId: traits::VcEpochId<VERSION> + Send + Sync,
+    >(
+        &self,
+        epoch_id: &EpochId,
+    ) -> impl Future<Output = Result<bool, Self::Error>> + Send {
+        std::future::ready(
+            StorageProvider::<VERSION>::delete_vc_emulation_state_if_unreferenced(self, epoch_id),
+        )
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn delete_vc_emulation_bindings<GroupId: traits::GroupId<VERSION> + Send + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(StorageProvider::<VERSION>::delete_vc_emulation_bindings(
+            self, group_id,
+        ))
+    }
+
+    #[cfg(feature = "virtual-clients-draft")]
+    fn delete_retained_key_package_material<
+        KeyPackageRef: traits::HashReference<VERSION> + Send + Sync,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        std::future::ready(
+            StorageProvider::<VERSION>::delete_retained_key_package_material(self, hash_ref),
+        )
+    }
+}
@@ -1,4 +1,7 @@
+use std::future::Future;
+
 use crate::types::{CryptoError, SignatureScheme};
+
 /// Trait errors.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum SignerError {
@@ -17,3 +20,33 @@ pub trait Signer {
     /// The [`SignatureScheme`] of this signer.
     fn signature_scheme(&self) -> SignatureScheme;
 }
+
+/// Sign the provided payload asynchronously and return a signature.
+///
+/// This is the asynchronous counterpart of [`Signer`] for signature keys that
+/// are not available in-process, e.g. keys held by a remote signing service
+/// or an HSM, where a signature has to be awaited instead of blocking the
+/// caller.
+///
+/// Every [`Signer`] is an [`AsyncSigner`] that returns a ready future.
+pub trait AsyncSigner {
+    /// Sign the provided payload.
+    ///
+    /// Returns a future that resolves to a signature on success or an Error.
+    /// The future is [`Send`], so that it can be awaited on a multi-threaded
+    /// runtime.
+    fn sign(&self, payload: &[u8]) -> impl Future<Output = Result<Vec<u8>, SignerError>> + Send;
+
+    /// The [`SignatureScheme`] of this signer.
+    fn signature_scheme(&self) -> SignatureScheme;
+}
+
+impl<S: Signer + ?Sized> AsyncSigner for S {
+    fn sign(&self, payload: &[u8]) -> impl Future<Output = Result<Vec<u8>, SignerError>> + Send {
+        std::future::ready(Signer::sign(self, payload))
+    }
+
+    fn signature_scheme(&self) -> SignatureScheme {
+        Signer::signature_scheme(self)
+    }
+}
@@ -28,13 +28,64 @@ pub const V_TEST: u16 = u16::MAX;
 /// More details can be taken from the comments on the respective method.
 pub trait StorageProvider<const VERSION: u16> {
     /// An opaque error returned by all methods on this trait.
-    type Error: core::fmt::Debug + std::error::Error;
+    type Error: core::fmt::Debug + std::error::Error + Send;
 
     /// Get the version of this provider.
     fn version() -> u16 {
         VERSION
     }
 
+    //
+    //    ---   transactions  ---
+    //
+
+    /// Begins a transaction.
+    ///
+    /// All writes and deletes up to the matching
+    /// [`commit_transaction`](Self::commit_transaction) must be persisted
+    /// together, and all of them must be discarded on
+    /// [`rollback_transaction`](Self::rollback_transaction). Reads within the
+    /// transaction must see its writes.
+    ///
+    /// OpenMLS wraps every operation that performs more than one write, e.g.
+    /// merging a commit, into a transaction, so that a failure midway leaves
+    /// the group in either the old or the new state. A transaction may be
+    /// begun while another one is open, e.g. by the application around
+    /// OpenMLS operations. In that case it must behave like a savepoint of
+    /// the outer transaction.
+    ///
+    /// The default implementation does nothing, i.e. every write is persisted
+    /// on its own.
+    fn begin_transaction(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Commits the innermost open transaction.
+    ///
+    /// See [`begin_transaction`](Self::begin_transaction).
+    fn commit_transaction(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Discards all writes and deletes of the innermost open transaction.
+    ///
+    /// See [`begin_transaction`](Self::begin_transaction).
+    fn rollback_transaction(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Returns `true` if this storage persists the messages from future
+    /// epochs that OpenMLS buffers, see
+    /// [`write_future_epoch_messages`](Self::write_future_epoch_messages).
+    ///
+    /// The default implementation returns `false`. OpenMLS then rejects
+    /// messages it would buffer as unsupported instead of calling the
+    /// methods for future epoch messages, whose default implementations
+    /// don't store anything.
+    fn supports_future_epoch_messages(&self) -> bool {
+        false
+    }
+
     //
     //    ---   setters/writers/enqueuers for group state  ---
     //
@@ -161,6 +212,23 @@ pub trait StorageProvider<const VERSION: u16> {
         group_epoch_secrets: &GroupEpochSecrets,
     ) -> Result<(), Self::Error>;
 
+    /// Writes the messages from future epochs that are buffered for the group
+    /// with the given id. A subsequent write replaces the stored messages.
+    ///
+    /// Storages that implement this must also implement
+    /// [`supports_future_epoch_messages`](Self::supports_future_epoch_messages).
+    /// The default implementation doesn't store anything.
+    fn write_future_epoch_messages<
+        GroupId: traits::GroupId<VERSION>,
+        FutureEpochMessages: traits::FutureEpochMessages<VERSION>,
+    >(
+        &self,
+        _group_id: &GroupId,
+        _future_epoch_messages: &FutureEpochMessages,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// Write the ApplicationExportTree for the group with the given id.
     #[cfg(feature = "extensions-draft")]
     fn write_application_export_tree<
@@ -502,6 +570,20 @@ pub trait StorageProvider<const VERSION: u16> {
         psk_id: &PskId,
     ) -> Result<Option<PskBundle>, Self::Error>;
 
+    /// Returns the messages from future epochs that are buffered for the group
+    /// with the given id.
+    ///
+    /// The default implementation returns `None`.
+    fn future_epoch_messages<
+        GroupId: traits::GroupId<VERSION>,
+        FutureEpochMessages: traits::FutureEpochMessages<VERSION>,
+    >(
+        &self,
+        _group_id: &GroupId,
+    ) -> Result<Option<FutureEpochMessages>, Self::Error> {
+        Ok(None)
+    }
+
     #[cfg(feature = "extensions-draft")]
     /// Get the application export tree for the group with the given id.
     fn application_export_tree<
@@ -712,6 +794,17 @@ pub trait StorageProvider<const VERSION: u16> {
         psk_id: &PskKey,
     ) -> Result<(), Self::Error>;
 
+    /// Deletes the messages from future epochs that are buffered for the group
+    /// with the given id.
+    ///
+    /// The default implementation does nothing.
+    fn delete_future_epoch_messages<GroupId: traits::GroupId<VERSION>>(
+        &self,
+        _group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// Delete the application export tree for the group with the given id.
     #[cfg(feature = "extensions-draft")]
     fn delete_application_export_tree<
@@ -759,6 +852,50 @@ pub trait StorageProvider<const VERSION: u16> {
     ) -> Result<(), Self::Error>;
 }
 
+/// MigrationLog records which groups and key packages a migration of the
+/// stored values was applied to.
+///
+/// Migrations that rewrite stored values in place, such as the migration from
+/// the OpenMLS 0.8.1 storage format, must run exactly once per group or key
+/// package, because the rewritten values can not be told apart from values
+/// that still need to be migrated. A migration is identified by its `name`.
+///
+/// Records should be written within the same transaction as the migrated
+/// values, and are not removed when the group or key package is deleted.
+pub trait MigrationLog<const VERSION: u16>: StorageProvider<VERSION> {
+    /// Returns whether the migration with the given `name` was recorded for
+    /// the group with the given id.
+    fn group_migrated<GroupId: traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+        name: &str,
+    ) -> Result<bool, Self::Error>;
+
+    /// Records that the migration with the given `name` was applied to the
+    /// group with the given id.
+    fn record_group_migration<GroupId: traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+        name: &str,
+    ) -> Result<(), Self::Error>;
+
+    /// Returns whether the migration with the given `name` was recorded for
+    /// the key package with the given hash reference.
+    fn key_package_migrated<KeyPackageRef: traits::HashReference<VERSION>>(
+        &self,
+        hash_ref: &KeyPackageRef,
+        name: &str,
+    ) -> Result<bool, Self::Error>;
+
+    /// Records that the migration with the given `name` was applied to the
+    /// key package with the given hash reference.
+    fn record_key_package_migration<KeyPackageRef: traits::HashReference<VERSION>>(
+        &self,
+        hash_ref: &KeyPackageRef,
+        name: &str,
+    ) -> Result<(), Self::Error>;
+}
+
 // base traits for keys and values
 
 // ANCHOR: key_trait
@@ -812,6 +949,7 @@ pub mod traits {
     pub trait MlsGroupJoinConfig<const VERSION: u16>: Entity<VERSION> {}
     pub trait LeafNode<const VERSION: u16>: Entity<VERSION> {}
     pub trait ApplicationExportTree<const VERSION: u16>: Entity<VERSION> {}
+    pub trait FutureEpochMessages<const VERSION: u16>: Entity<VERSION> {}
     #[cfg(feature = "virtual-clients-draft")]
     pub trait VcEpochId<const VERSION: u16>: Key<VERSION> {}
     #[cfg(feature = "virtual-clients-draft")]
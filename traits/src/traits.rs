@@ -3,6 +3,7 @@
 //! This module defines a number of traits that are used by the public
 //! API of OpenMLS.
 
+pub mod async_storage;
 pub mod crypto;
 pub mod grease;
 pub mod public_storage;
@@ -41,3 +42,41 @@ pub trait OpenMlsProvider {
     fn rand(&self) -> &Self::RandProvider;
 }
 // ANCHOR_END: openmls_provider
+
+/// The asynchronous counterpart of the [`OpenMlsProvider`].
+///
+/// It provides the same crypto and randomness providers, but an
+/// [`AsyncStorageProvider`](async_storage::AsyncStorageProvider). Every
+/// [`OpenMlsProvider`] is also an [`AsyncOpenMlsProvider`].
+pub trait AsyncOpenMlsProvider {
+    type CryptoProvider: crypto::OpenMlsCrypto;
+    type RandProvider: random::OpenMlsRand;
+    type StorageProvider: async_storage::AsyncStorageProvider<{ storage::CURRENT_VERSION }>;
+
+    /// Get the asynchronous storage provider.
+    fn storage(&self) -> &Self::StorageProvider;
+
+    /// Get the crypto provider.
+    fn crypto(&self) -> &Self::CryptoProvider;
+
+    /// Get the randomness provider.
+    fn rand(&self) -> &Self::RandProvider;
+}
+
+impl<P: OpenMlsProvider> AsyncOpenMlsProvider for P {
+    type CryptoProvider = P::CryptoProvider;
+    type RandProvider = P::RandProvider;
+    type StorageProvider = P::StorageProvider;
+
+    fn storage(&self) -> &Self::StorageProvider {
+        OpenMlsProvider::storage(self)
+    }
+
+    fn crypto(&self) -> &Self::CryptoProvider {
+        OpenMlsProvider::crypto(self)
+    }
+
+    fn rand(&self) -> &Self::RandProvider {
+        OpenMlsProvider::rand(self)
+    }
+}